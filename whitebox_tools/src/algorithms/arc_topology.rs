@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use structures::Point2D;
+
+/// The arc-node topology of a set of lines and polygon rings. Every line is
+/// split into arcs at its nodes, i.e. its end points and the vertices at which
+/// it meets, leaves or crosses another line, so that boundaries shared by
+/// neighbouring lines or polygons (e.g. the common edge of two adjacent
+/// polygons) are represented by a single arc. Vertices are matched by exact
+/// coordinates, as they are in polygons derived from a common source, such as
+/// a raster or a planar overlay.
+///
+/// Generalization operations applied to the arcs with their end nodes held
+/// fixed, rather than to each line independently, keep shared boundaries
+/// coincident and leave the lines connected at their nodes.
+pub struct ArcTopology {
+    /// The vertices of each arc. Closed arcs repeat their first point at the end.
+    pub arcs: Vec<Vec<Point2D>>,
+    // the arcs of each line and whether each is traversed in its forward direction
+    lines: Vec<Vec<(usize, bool)>>,
+    is_ring: Vec<bool>,
+}
+
+impl ArcTopology {
+    /// Builds the topology of a set of lines. Lines that repeat their first
+    /// point at the end are treated as rings.
+    pub fn new(lines: &[Vec<Point2D>]) -> ArcTopology {
+        // identify the distinct vertices
+        let mut vertices: Vec<Point2D> = vec![];
+        let mut vertex_map: HashMap<(u64, u64), usize> = HashMap::new();
+        let mut sequences: Vec<Vec<usize>> = Vec::with_capacity(lines.len());
+        let mut is_ring = Vec::with_capacity(lines.len());
+        for line in lines {
+            let mut sequence: Vec<usize> = Vec::with_capacity(line.len());
+            for p in line {
+                let key = (p.x.to_bits(), p.y.to_bits());
+                let id = *vertex_map.entry(key).or_insert_with(|| {
+                    vertices.push(*p);
+                    vertices.len() - 1
+                });
+                if sequence.last() != Some(&id) {
+                    sequence.push(id);
+                }
+            }
+            let ring = line.len() > 3 && sequence.len() > 3 && sequence[0] == sequence[sequence.len() - 1];
+            if ring {
+                sequence.pop(); // rings are stored cyclically
+            }
+            is_ring.push(ring);
+            sequences.push(sequence);
+        }
+
+        // nodes are line end points and vertices with other than two distinct neighbours
+        let mut neighbours: Vec<Vec<usize>> = vec![vec![]; vertices.len()];
+        let mut is_node = vec![false; vertices.len()];
+        for (sequence, &ring) in sequences.iter().zip(is_ring.iter()) {
+            let n = sequence.len();
+            if n == 0 {
+                continue;
+            }
+            if !ring {
+                is_node[sequence[0]] = true;
+                is_node[sequence[n - 1]] = true;
+            }
+            let num_edges = if ring { n } else { n - 1 };
+            for i in 0..num_edges {
+                let (a, b) = (sequence[i], sequence[(i + 1) % n]);
+                if !neighbours[a].contains(&b) {
+                    neighbours[a].push(b);
+                }
+                if !neighbours[b].contains(&a) {
+                    neighbours[b].push(a);
+                }
+            }
+        }
+        for v in 0..vertices.len() {
+            if neighbours[v].len() != 2 {
+                is_node[v] = true;
+            }
+        }
+        // A ring without nodes (e.g. an isolated polygon, or a hole filled by
+        // an island polygon) is given one at its lowest vertex, so that any
+        // other ring following the same path is split at the same place.
+        for (sequence, &ring) in sequences.iter().zip(is_ring.iter()) {
+            if ring && !sequence.iter().any(|&v| is_node[v]) {
+                let lowest = *sequence.iter().min_by(|&&a, &&b| {
+                    let (p, q) = (vertices[a], vertices[b]);
+                    (p.x, p.y).partial_cmp(&(q.x, q.y)).unwrap()
+                }).unwrap();
+                is_node[lowest] = true;
+            }
+        }
+
+        // split the lines into arcs at their nodes; an arc is identified by
+        // its first directed edge, which no other arc can share
+        let mut arcs: Vec<Vec<Point2D>> = vec![];
+        let mut arc_map: HashMap<(usize, usize), (usize, bool)> = HashMap::new();
+        let mut line_arcs: Vec<Vec<(usize, bool)>> = Vec::with_capacity(lines.len());
+        for (sequence, &ring) in sequences.iter().zip(is_ring.iter()) {
+            let mut pieces: Vec<Vec<usize>> = vec![];
+            if ring {
+                let n = sequence.len();
+                let first = (0..n).find(|&i| is_node[sequence[i]]).unwrap();
+                let mut piece = vec![sequence[first]];
+                for j in 1..n + 1 {
+                    let v = sequence[(first + j) % n];
+                    piece.push(v);
+                    if is_node[v] {
+                        pieces.push(piece);
+                        piece = vec![v];
+                    }
+                }
+            } else if sequence.len() > 1 {
+                let mut piece = vec![sequence[0]];
+                for &v in &sequence[1..] {
+                    piece.push(v);
+                    if is_node[v] {
+                        pieces.push(piece);
+                        piece = vec![v];
+                    }
+                }
+            }
+
+            // A closed piece passing through a true node is split in two, so that
+            // generalization cannot move the node as it might on a closed ring.
+            let mut split_pieces = Vec::with_capacity(pieces.len());
+            for piece in pieces {
+                let m = piece.len();
+                if m > 3 && piece[0] == piece[m - 1] && neighbours[piece[0]].len() != 2 {
+                    // split at the lowest vertex, so that the split is independent of direction
+                    let mid = (1..m - 1).min_by(|&a, &b| {
+                        let (p, q) = (vertices[piece[a]], vertices[piece[b]]);
+                        (p.x, p.y).partial_cmp(&(q.x, q.y)).unwrap()
+                    }).unwrap();
+                    split_pieces.push(piece[..mid + 1].to_vec());
+                    split_pieces.push(piece[mid..].to_vec());
+                } else {
+                    split_pieces.push(piece);
+                }
+            }
+
+            let mut arcs_of_line = Vec::with_capacity(split_pieces.len());
+            for piece in split_pieces {
+                let key = (piece[0], piece[1]);
+                let arc = match arc_map.get(&key) {
+                    Some(&a) => a,
+                    None => {
+                        let id = arcs.len();
+                        let m = piece.len();
+                        arc_map.insert((piece[m - 1], piece[m - 2]), (id, false));
+                        arc_map.insert(key, (id, true));
+                        arcs.push(piece.iter().map(|&v| vertices[v]).collect());
+                        (id, true)
+                    }
+                };
+                arcs_of_line.push(arc);
+            }
+            line_arcs.push(arcs_of_line);
+        }
+
+        ArcTopology {
+            arcs: arcs,
+            lines: line_arcs,
+            is_ring: is_ring,
+        }
+    }
+
+    /// The number of lines from which the topology was built.
+    pub fn num_lines(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Reassembles a line from a set of (possibly modified) arcs, which must
+    /// retain the end points of the original arcs.
+    pub fn assemble_line(&self, line: usize, arcs: &[Vec<Point2D>]) -> Vec<Point2D> {
+        let mut points: Vec<Point2D> = vec![];
+        for &(arc, forward) in &self.lines[line] {
+            let start = if points.is_empty() { 0 } else { 1 };
+            if forward {
+                points.extend(arcs[arc][start..].iter().cloned());
+            } else {
+                points.extend(arcs[arc].iter().rev().skip(start).cloned());
+            }
+        }
+        points
+    }
+
+    /// Applies a generalization function, e.g. a simplification or smoothing
+    /// algorithm, to each of the arcs and returns the resulting lines. The
+    /// function must preserve the end points of open arcs and keep closed arcs
+    /// closed. Any ring that would collapse to fewer than
+    /// three distinct vertices is kept intact, as are the neighbouring rings
+    /// with which it shares arcs.
+    pub fn generalize<F>(&self, f: F) -> Vec<Vec<Point2D>>
+        where F: Fn(&[Point2D]) -> Vec<Point2D>
+    {
+        let mut arcs: Vec<Vec<Point2D>> = self.arcs.iter().map(|arc| f(arc)).collect();
+        loop {
+            let mut changed = false;
+            for line in 0..self.lines.len() {
+                if self.is_ring[line] && self.assemble_line(line, &arcs).len() < 4 {
+                    for &(arc, _) in &self.lines[line] {
+                        if arcs[arc] != self.arcs[arc] {
+                            arcs[arc] = self.arcs[arc].clone();
+                            changed = true;
+                        }
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        (0..self.lines.len()).map(|line| self.assemble_line(line, &arcs)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArcTopology;
+    use algorithms::{douglas_peucker, visvalingam_whyatt};
+    use std::f64;
+    use structures::Point2D;
+
+    // two polygons sharing a jagged, curved edge from (10, 0) to (10, 10)
+    fn adjacent_polygons() -> Vec<Vec<Point2D>> {
+        let mut shared: Vec<(f64, f64)> = (0..21).map(|i| {
+            let y = i as f64 * 0.5;
+            let noise = if i % 2 == 0 { 0.15 } else { -0.15 };
+            (10.0 + 1.5 * (y * f64::consts::PI / 10.0).sin() + noise, y)
+        }).collect();
+        shared[0] = (10.0, 0.0);
+        shared[20] = (10.0, 10.0);
+        let mut left = vec![Point2D::new(0.0, 0.0), Point2D::new(0.0, 10.0), Point2D::new(5.0, 10.3)];
+        left.extend(shared.iter().rev().map(|&(x, y)| Point2D::new(x, y)));
+        left.push(Point2D::new(5.0, -0.2));
+        left.push(Point2D::new(0.0, 0.0));
+        // the right ring starts part way along the shared edge
+        let mut right: Vec<Point2D> = shared[7..].iter().map(|&(x, y)| Point2D::new(x, y)).collect();
+        right.extend(vec![Point2D::new(15.0, 9.8), Point2D::new(20.0, 10.0), Point2D::new(20.0, 0.0), Point2D::new(15.0, 0.1)]);
+        right.extend(shared[..8].iter().map(|&(x, y)| Point2D::new(x, y)));
+        vec![left, right]
+    }
+
+    // the vertices of a closed ring from one vertex to another, in ring order
+    fn path_between(ring: &[Point2D], from: Point2D, to: Point2D) -> Vec<Point2D> {
+        let n = ring.len() - 1;
+        let start = (0..n).find(|&i| ring[i] == from).unwrap();
+        let mut path = vec![from];
+        let mut i = start;
+        while ring[i] != to {
+            i = (i + 1) % n;
+            path.push(ring[i]);
+        }
+        path
+    }
+
+    fn assert_shared_edge_coincident(lines: &[Vec<Point2D>]) {
+        let (bottom, top) = (Point2D::new(10.0, 0.0), Point2D::new(10.0, 10.0));
+        for line in lines {
+            assert_eq!(line[0], line[line.len() - 1]);
+        }
+        let left = path_between(&lines[0], top, bottom);
+        let mut right = path_between(&lines[1], bottom, top);
+        right.reverse();
+        assert_eq!(left, right);
+        // the edge is simplified but not collapsed to a straight line
+        assert!(left.len() < 21);
+        assert!(left.len() > 2);
+    }
+
+    #[test]
+    fn test_arcs() {
+        let topology = ArcTopology::new(&adjacent_polygons());
+        assert_eq!(topology.num_lines(), 2);
+        // the shared edge and the remainder of each ring
+        assert_eq!(topology.arcs.len(), 3);
+        assert!(topology.arcs.iter().any(|arc| arc.len() == 21));
+    }
+
+    #[test]
+    fn test_douglas_peucker_keeps_shared_edges_coincident() {
+        let topology = ArcTopology::new(&adjacent_polygons());
+        let lines = topology.generalize(|arc| douglas_peucker(arc, 0.5));
+        assert_shared_edge_coincident(&lines);
+    }
+
+    #[test]
+    fn test_visvalingam_whyatt_keeps_shared_edges_coincident() {
+        let topology = ArcTopology::new(&adjacent_polygons());
+        let lines = topology.generalize(|arc| visvalingam_whyatt(arc, 1.0));
+        assert_shared_edge_coincident(&lines);
+    }
+}
@@ -0,0 +1,137 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::f64;
+use std::usize;
+use structures::{Point2D, Triangulation};
+
+/// Calculates the convex hull of a set of points using Andrew's monotone chain
+/// algorithm. The hull is returned as a closed ring (the first point is
+/// repeated at the end) in clockwise order, i.e. as a shapefile polygon hull,
+/// without collinear vertices. If the points are all collinear, or there are
+/// fewer than three distinct points, the returned ring has fewer than four
+/// points.
+pub fn convex_hull(points: &[Point2D]) -> Vec<Point2D> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+    sorted.dedup();
+    let n = sorted.len();
+    if n < 3 {
+        return sorted;
+    }
+    let cross = |o: &Point2D, a: &Point2D, b: &Point2D| -> f64 {
+        (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+    };
+    // the upper chain, from left to right, and then the lower chain, from right
+    // to left, keeping only clockwise turns
+    let mut hull: Vec<Point2D> = Vec::with_capacity(2 * n);
+    for i in 0..n {
+        while hull.len() >= 2 && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], &sorted[i]) >= 0f64 {
+            hull.pop();
+        }
+        hull.push(sorted[i]);
+    }
+    let lower_len = hull.len() + 1;
+    for i in (0..n - 1).rev() {
+        while hull.len() >= lower_len && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], &sorted[i]) >= 0f64 {
+            hull.pop();
+        }
+        hull.push(sorted[i]);
+    }
+    hull
+}
+
+/// Calculates a concave footprint of a set of points, i.e. a simple polygon
+/// that contains all of the points and fits them more closely than their
+/// convex hull. The Delaunay triangulation of the points is eroded from the
+/// outside inwards, removing boundary triangles with an outer edge longer
+/// than `max_edge_length`, provided that doing so leaves a single polygon
+/// (the chi-shape algorithm of Duckham et al., 2008). The result is returned
+/// as a closed ring in clockwise order. If the points cannot be triangulated,
+/// their convex hull is returned.
+pub fn concave_hull(points: &[Point2D], max_edge_length: f64) -> Vec<Point2D> {
+    let triangulation = match Triangulation::new(points) {
+        Some(t) => t,
+        None => return convex_hull(points),
+    };
+    let num_edges = triangulation.triangles.len();
+    let edge_length = |e: usize| -> f64 {
+        let p = points[triangulation.triangles[e]];
+        p.distance(&points[triangulation.triangles[Triangulation::next_halfedge(e)]])
+    };
+
+    // the boundary half-edges, longest first, and the points on the boundary
+    let mut removed = vec![false; num_edges / 3];
+    let mut is_boundary = vec![false; num_edges];
+    let mut on_boundary = vec![false; points.len()];
+    let mut boundary = BinaryHeap::new();
+    for e in 0..num_edges {
+        if triangulation.halfedges[e] == usize::MAX {
+            is_boundary[e] = true;
+            on_boundary[triangulation.triangles[e]] = true;
+            boundary.push(BoundaryEdge { edge: e, length: edge_length(e) });
+        }
+    }
+
+    while let Some(BoundaryEdge { edge: e, length }) = boundary.pop() {
+        if length <= max_edge_length {
+            break;
+        }
+        let e1 = Triangulation::next_halfedge(e);
+        let e2 = Triangulation::prev_halfedge(e);
+        // the triangle's third point must not already be on the boundary, and its
+        // other two edges must be interior, or the polygon would split in two
+        let apex = triangulation.triangles[e2];
+        if on_boundary[apex] || is_boundary[e1] || is_boundary[e2] {
+            continue;
+        }
+        is_boundary[e] = false;
+        removed[e / 3] = true;
+        on_boundary[apex] = true;
+        for &edge in &[e1, e2] {
+            let opposite = triangulation.halfedges[edge];
+            is_boundary[opposite] = true;
+            boundary.push(BoundaryEdge { edge: opposite, length: edge_length(opposite) });
+        }
+    }
+
+    // trace the boundary of the remaining triangles
+    let mut next_point = vec![usize::MAX; points.len()];
+    let mut start = usize::MAX;
+    for e in 0..num_edges {
+        let opposite = triangulation.halfedges[e];
+        if !removed[e / 3] && (opposite == usize::MAX || removed[opposite / 3]) {
+            start = triangulation.triangles[e];
+            next_point[start] = triangulation.triangles[Triangulation::next_halfedge(e)];
+        }
+    }
+    // the triangles are counter-clockwise, so the boundary is reversed for a clockwise ring
+    let mut ring = vec![points[start]];
+    let mut p = next_point[start];
+    while p != start && p != usize::MAX {
+        ring.push(points[p]);
+        p = next_point[p];
+    }
+    ring.push(points[start]);
+    ring.reverse();
+    ring
+}
+
+#[derive(PartialEq, Debug)]
+struct BoundaryEdge {
+    edge: usize,
+    length: f64,
+}
+
+impl Eq for BoundaryEdge {}
+
+impl PartialOrd for BoundaryEdge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.length.partial_cmp(&other.length)
+    }
+}
+
+impl Ord for BoundaryEdge {
+    fn cmp(&self, other: &BoundaryEdge) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+}
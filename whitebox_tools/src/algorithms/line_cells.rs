@@ -0,0 +1,46 @@
+use std::f64;
+
+/// Returns the grid cells, as (row, column) pairs, that are crossed by the
+/// line segment from (column1, row1) to (column2, row2). Coordinates are given
+/// in fractional grid units, such that cell (r, c) spans the columns c to c + 1
+/// and the rows r to r + 1. Cells are returned in order from the start of the
+/// segment to its end, using the grid traversal method of Amanatides and Woo
+/// (1987).
+pub fn find_line_cells(column1: f64, row1: f64, column2: f64, row2: f64) -> Vec<(isize, isize)> {
+    let mut c = column1.floor() as isize;
+    let mut r = row1.floor() as isize;
+    let end_c = column2.floor() as isize;
+    let end_r = row2.floor() as isize;
+    let d_col = column2 - column1;
+    let d_row = row2 - row1;
+
+    let (step_c, mut t_max_c, t_delta_c) = if d_col > 0f64 {
+        (1isize, (c as f64 + 1f64 - column1) / d_col, 1f64 / d_col)
+    } else if d_col < 0f64 {
+        (-1isize, (column1 - c as f64) / -d_col, 1f64 / -d_col)
+    } else {
+        (0isize, f64::INFINITY, f64::INFINITY)
+    };
+    let (step_r, mut t_max_r, t_delta_r) = if d_row > 0f64 {
+        (1isize, (r as f64 + 1f64 - row1) / d_row, 1f64 / d_row)
+    } else if d_row < 0f64 {
+        (-1isize, (row1 - r as f64) / -d_row, 1f64 / -d_row)
+    } else {
+        (0isize, f64::INFINITY, f64::INFINITY)
+    };
+
+    let num_steps = (end_c - c).abs() + (end_r - r).abs();
+    let mut cells = Vec::with_capacity(num_steps as usize + 1);
+    cells.push((r, c));
+    for _ in 0..num_steps {
+        if t_max_c < t_max_r {
+            c += step_c;
+            t_max_c += t_delta_c;
+        } else {
+            r += step_r;
+            t_max_r += t_delta_r;
+        }
+        cells.push((r, c));
+    }
+    cells
+}
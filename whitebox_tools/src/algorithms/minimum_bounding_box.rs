@@ -0,0 +1,66 @@
+use std::f64;
+use algorithms::convex_hull;
+use structures::Point2D;
+
+/// The property of a bounding box that is minimized by `minimum_bounding_box`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MinimizationCriterion {
+    Area,
+    Perimeter,
+    Length,
+    Width,
+}
+
+/// Calculates the minimum bounding box of a set of points, i.e. the smallest
+/// rectangle, of any orientation, that contains them, according to the
+/// criterion: the box of least area, perimeter, length (long side), or width
+/// (short side). One side of the optimal box is always collinear with an edge
+/// of the points' convex hull, so each hull edge is tried in turn (the
+/// rotating calipers method). The box is returned as a closed ring of five
+/// points in clockwise order, or None if the points are all collinear.
+pub fn minimum_bounding_box(points: &[Point2D], criterion: MinimizationCriterion) -> Option<Vec<Point2D>> {
+    let hull = convex_hull(points);
+    if hull.len() < 4 {
+        return None;
+    }
+    let mut best_value = f64::INFINITY;
+    let mut best_box = vec![];
+    for i in 0..hull.len() - 1 {
+        let (p, q) = (hull[i], hull[i + 1]);
+        let length = p.distance(&q);
+        if length == 0f64 {
+            continue;
+        }
+        // unit vectors along and perpendicular to the edge
+        let (ux, uy) = ((q.x - p.x) / length, (q.y - p.y) / length);
+        let (vx, vy) = (-uy, ux);
+        let (mut min_u, mut max_u) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut min_v, mut max_v) = (f64::INFINITY, f64::NEG_INFINITY);
+        for h in &hull {
+            let u = (h.x - p.x) * ux + (h.y - p.y) * uy;
+            let v = (h.x - p.x) * vx + (h.y - p.y) * vy;
+            min_u = min_u.min(u);
+            max_u = max_u.max(u);
+            min_v = min_v.min(v);
+            max_v = max_v.max(v);
+        }
+        let (a, b) = (max_u - min_u, max_v - min_v);
+        let value = match criterion {
+            MinimizationCriterion::Area => a * b,
+            MinimizationCriterion::Perimeter => 2f64 * (a + b),
+            MinimizationCriterion::Length => a.max(b),
+            MinimizationCriterion::Width => a.min(b),
+        };
+        if value < best_value {
+            best_value = value;
+            let corner = |u: f64, v: f64| Point2D::new(p.x + u * ux + v * vx, p.y + u * uy + v * vy);
+            // (u, v) is a right-handed system, so this order is clockwise
+            best_box = vec![corner(min_u, min_v),
+                            corner(min_u, max_v),
+                            corner(max_u, max_v),
+                            corner(max_u, min_v),
+                            corner(min_u, min_v)];
+        }
+    }
+    Some(best_box)
+}
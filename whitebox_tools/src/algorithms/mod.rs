@@ -0,0 +1,38 @@
+// private sub-module defined in other files
+mod arc_topology;
+mod convex_hull;
+mod line_cells;
+mod minimum_bounding_box;
+mod point_in_poly;
+mod polygon_area;
+mod polygon_cells;
+mod polygon_coverage;
+mod polygon_overlay;
+mod region_boundaries;
+mod simplify;
+mod smooth;
+
+// exports identifiers from private sub-modules in the current module namespace
+pub use self::arc_topology::ArcTopology;
+pub use self::convex_hull::concave_hull;
+pub use self::convex_hull::convex_hull;
+pub use self::line_cells::find_line_cells;
+pub use self::minimum_bounding_box::MinimizationCriterion;
+pub use self::minimum_bounding_box::minimum_bounding_box;
+pub use self::point_in_poly::point_in_poly;
+pub use self::polygon_area::is_clockwise_order;
+pub use self::polygon_area::polygon_area;
+pub use self::polygon_area::polyline_length;
+pub use self::polygon_area::signed_polygon_area;
+pub use self::polygon_cells::find_polygon_cells;
+pub use self::polygon_coverage::clip_ring_to_box;
+pub use self::polygon_coverage::polygon_cell_coverage;
+pub use self::polygon_overlay::LineOverlayPiece;
+pub use self::polygon_overlay::PolygonOverlay;
+pub use self::polygon_overlay::overlay_lines_on_polygons;
+pub use self::region_boundaries::trace_region_boundaries;
+pub use self::simplify::douglas_peucker;
+pub use self::simplify::perpendicular_distance;
+pub use self::simplify::visvalingam_whyatt;
+pub use self::smooth::chaikin_smoothing;
+pub use self::smooth::moving_average_smoothing;
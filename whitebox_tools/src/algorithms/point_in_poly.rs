@@ -0,0 +1,22 @@
+use structures::Point2D;
+
+/// Tests whether a point lies within a polygon ring, using the crossing-number
+/// (even-odd) rule. The ring may or may not repeat its first point at the end.
+/// Points lying exactly on the boundary may be reported as inside or outside.
+pub fn point_in_poly(p: &Point2D, ring: &[Point2D]) -> bool {
+    let n = ring.len();
+    if n < 3 {
+        return false;
+    }
+    let mut inside = false;
+    let mut j = n - 1;
+    for i in 0..n {
+        let (pi, pj) = (ring[i], ring[j]);
+        if (pi.y > p.y) != (pj.y > p.y) &&
+           p.x < (pj.x - pi.x) * (p.y - pi.y) / (pj.y - pi.y) + pi.x {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
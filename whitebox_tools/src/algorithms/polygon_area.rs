@@ -0,0 +1,37 @@
+use structures::Point2D;
+
+/// Calculates the signed area of a polygon ring using the shoelace formula.
+/// The value is positive for counter-clockwise rings and negative for clockwise
+/// rings. The ring may or may not repeat its first point at the end.
+pub fn signed_polygon_area(points: &[Point2D]) -> f64 {
+    let n = points.len();
+    if n < 3 {
+        return 0f64;
+    }
+    let mut area = 0f64;
+    for i in 0..n {
+        let j = (i + 1) % n;
+        area += points[i].x * points[j].y - points[j].x * points[i].y;
+    }
+    area / 2f64
+}
+
+/// Calculates the (unsigned) area of a polygon ring.
+pub fn polygon_area(points: &[Point2D]) -> f64 {
+    signed_polygon_area(points).abs()
+}
+
+/// Returns true if the vertices of a ring are ordered clockwise, which is the
+/// shapefile convention for polygon hulls.
+pub fn is_clockwise_order(points: &[Point2D]) -> bool {
+    signed_polygon_area(points) < 0f64
+}
+
+/// Calculates the length of a sequence of points; for a closed ring this is the perimeter.
+pub fn polyline_length(points: &[Point2D]) -> f64 {
+    let mut length = 0f64;
+    for i in 1..points.len() {
+        length += points[i].distance(&points[i - 1]);
+    }
+    length
+}
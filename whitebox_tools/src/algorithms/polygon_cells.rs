@@ -0,0 +1,63 @@
+use structures::{BoundingBox, Point2D, RTree};
+
+/// Returns the grid cells, as (row, column) pairs, whose centres fall within a
+/// polygon, given as a set of closed rings (hulls and holes). The grid is
+/// described by its north-west corner, cell sizes, and dimensions, and only
+/// cells within the grid are returned. Rows are scanned using the even-odd
+/// rule, so cells within holes are excluded.
+pub fn find_polygon_cells(rings: &[&[Point2D]],
+                          west: f64,
+                          north: f64,
+                          resolution_x: f64,
+                          resolution_y: f64,
+                          rows: isize,
+                          columns: isize)
+                          -> Vec<(isize, isize)> {
+    let mut ret = vec![];
+    let mut extent = BoundingBox::default();
+    for ring in rings {
+        extent.expand_to(&BoundingBox::from_points(ring));
+    }
+    if extent.is_empty() {
+        return ret;
+    }
+
+    // scan each row whose centre lies within the polygon's extent
+    let mut start_row = ((north - extent.max_y) / resolution_y - 0.5).ceil() as isize;
+    let mut end_row = ((north - extent.min_y) / resolution_y - 0.5).floor() as isize;
+    if start_row < 0 { start_row = 0; }
+    if end_row > rows - 1 { end_row = rows - 1; }
+    // the edges crossing each row are found using an R-tree of the edges' envelopes
+    let mut edges = vec![];
+    for points in rings {
+        for i in 1..points.len() {
+            let (p1, p2) = (points[i - 1], points[i]);
+            edges.push((BoundingBox::from_points(&[p1, p2]), (p1, p2)));
+        }
+    }
+    let tree = RTree::bulk_load(edges);
+    let mut intersections: Vec<f64> = vec![];
+    for row in start_row..end_row + 1 {
+        let y = north - (row as f64 + 0.5) * resolution_y;
+        intersections.clear();
+        for &&(p1, p2) in &tree.search(&BoundingBox::new(extent.min_x, y, extent.max_x, y)) {
+            if (p1.y <= y && y < p2.y) || (p2.y <= y && y < p1.y) {
+                intersections.push(p1.x + (y - p1.y) / (p2.y - p1.y) * (p2.x - p1.x));
+            }
+        }
+        intersections.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        // take the cells with centres between each pair of crossings
+        let mut i = 0;
+        while i + 1 < intersections.len() {
+            let mut start_col = ((intersections[i] - west) / resolution_x - 0.5).ceil() as isize;
+            let mut end_col = ((intersections[i + 1] - west) / resolution_x - 0.5).ceil() as isize - 1;
+            if start_col < 0 { start_col = 0; }
+            if end_col > columns - 1 { end_col = columns - 1; }
+            for col in start_col..end_col + 1 {
+                ret.push((row, col));
+            }
+            i += 2;
+        }
+    }
+    ret
+}
@@ -0,0 +1,153 @@
+use algorithms::signed_polygon_area;
+use structures::{BoundingBox, Point2D, RTree};
+
+/// Clips a polygon ring to an axis-aligned box using the Sutherland-Hodgman
+/// algorithm. The ring need not be convex and may or may not repeat its first
+/// point; the signed area of the returned ring equals the signed area of the
+/// part of the input ring that falls within the box, although the result may
+/// contain degenerate (zero-width) edges along the box boundary.
+pub fn clip_ring_to_box(ring: &[Point2D], bb: &BoundingBox) -> Vec<Point2D> {
+    // each edge of the box is a half-plane: (axis, limit, keep values greater than limit)
+    let planes = [(0usize, bb.min_x, true),
+                  (0usize, bb.max_x, false),
+                  (1usize, bb.min_y, true),
+                  (1usize, bb.max_y, false)];
+    let coord = |p: &Point2D, axis: usize| -> f64 { if axis == 0 { p.x } else { p.y } };
+    let mut output: Vec<Point2D> = ring.to_vec();
+    for &(axis, limit, keep_greater) in &planes {
+        if output.is_empty() {
+            break;
+        }
+        let input = output;
+        output = Vec::with_capacity(input.len() + 4);
+        let inside = |p: &Point2D| -> bool {
+            if keep_greater { coord(p, axis) >= limit } else { coord(p, axis) <= limit }
+        };
+        let n = input.len();
+        for i in 0..n {
+            let current = input[i];
+            let previous = input[(i + n - 1) % n];
+            let (cur_in, prev_in) = (inside(&current), inside(&previous));
+            if cur_in != prev_in {
+                let t = (limit - coord(&previous, axis)) / (coord(&current, axis) - coord(&previous, axis));
+                output.push(Point2D::new(previous.x + t * (current.x - previous.x),
+                                         previous.y + t * (current.y - previous.y)));
+            }
+            if cur_in {
+                output.push(current);
+            }
+        }
+    }
+    output
+}
+
+/// Calculates the fraction of each raster grid cell that is covered by a
+/// polygon, given as a set of rings (hulls and holes, in shapefile order). The
+/// grid is described by its north-west corner, cell sizes, and dimensions.
+/// Returns (row, column, fraction) for each cell with a non-zero coverage.
+pub fn polygon_cell_coverage(rings: &[&[Point2D]],
+                             west: f64,
+                             north: f64,
+                             resolution_x: f64,
+                             resolution_y: f64,
+                             rows: isize,
+                             columns: isize)
+                             -> Vec<(isize, isize, f64)> {
+    let mut ret = vec![];
+    let mut extent = BoundingBox::default();
+    let mut ring_boxes = Vec::with_capacity(rings.len());
+    for (i, ring) in rings.iter().enumerate() {
+        let bb = BoundingBox::from_points(ring);
+        extent.expand_to(&bb);
+        ring_boxes.push((bb, i));
+    }
+    if extent.is_empty() {
+        return ret;
+    }
+    // only the rings overlapping a row are clipped to it, which matters for multipart polygons
+    let tree = RTree::bulk_load(ring_boxes);
+    let top_row = (((north - extent.max_y) / resolution_y).floor() as isize).max(0);
+    let bottom_row = (((north - extent.min_y) / resolution_y).floor() as isize).min(rows - 1);
+    let cell_area = resolution_x * resolution_y;
+    for row in top_row..bottom_row + 1 {
+        // clip the rings to the row first, so that each cell only needs to consider the nearby vertices
+        let y_max = north - row as f64 * resolution_y;
+        let row_box = BoundingBox::new(extent.min_x, y_max - resolution_y, extent.max_x, y_max);
+        let mut row_rings = vec![];
+        let mut row_extent = BoundingBox::default();
+        let mut row_ring_indices = tree.search(&row_box);
+        row_ring_indices.sort();
+        for &&i in &row_ring_indices {
+            let clipped = clip_ring_to_box(rings[i], &row_box);
+            if clipped.len() > 2 {
+                row_extent.expand_to(&BoundingBox::from_points(&clipped));
+                row_rings.push(clipped);
+            }
+        }
+        if row_rings.is_empty() {
+            continue;
+        }
+        let left_col = (((row_extent.min_x - west) / resolution_x).floor() as isize).max(0);
+        let right_col = (((row_extent.max_x - west) / resolution_x).floor() as isize).min(columns - 1);
+        for col in left_col..right_col + 1 {
+            let x_min = west + col as f64 * resolution_x;
+            let cell_box = BoundingBox::new(x_min, y_max - resolution_y, x_min + resolution_x, y_max);
+            let mut area = 0f64;
+            for ring in &row_rings {
+                area += signed_polygon_area(&clip_ring_to_box(ring, &cell_box));
+            }
+            // hulls are clockwise (negative area) and holes counter-clockwise
+            let fraction = (area.abs() / cell_area).min(1f64);
+            if fraction > 1e-9 {
+                ret.push((row, col, fraction));
+            }
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::polygon_cell_coverage;
+    use structures::Point2D;
+
+    fn points(coords: &[(f64, f64)]) -> Vec<Point2D> {
+        coords.iter().map(|&(x, y)| Point2D::new(x, y)).collect()
+    }
+
+    #[test]
+    fn test_coverage_of_a_triangle() {
+        // the triangle above the line y = x, over a 4 x 4 grid of unit cells
+        // with its north-west corner at (0, 4)
+        let triangle = points(&[(0.0, 0.0), (0.0, 4.0), (4.0, 4.0), (0.0, 0.0)]);
+        let coverage = polygon_cell_coverage(&[&triangle[..]], 0.0, 4.0, 1.0, 1.0, 4, 4);
+        // cells crossed by the diagonal are half covered and those above it fully
+        assert_eq!(coverage.len(), 10);
+        for &(row, col, fraction) in &coverage {
+            let expected = if col == 3 - row { 0.5 } else if col < 3 - row { 1.0 } else { 0.0 };
+            assert!((fraction - expected).abs() < 1e-12, "cell ({}, {}): {}", row, col, fraction);
+        }
+    }
+
+    #[test]
+    fn test_coverage_of_a_polygon_with_a_hole() {
+        // a clockwise hull covering the grid, with a counter-clockwise hole
+        // from (0.5, 0.5) to (2.5, 2.5)
+        let hull = points(&[(0.0, 0.0), (0.0, 4.0), (4.0, 4.0), (4.0, 0.0), (0.0, 0.0)]);
+        let hole = points(&[(0.5, 0.5), (2.5, 0.5), (2.5, 2.5), (0.5, 2.5), (0.5, 0.5)]);
+        let coverage = polygon_cell_coverage(&[&hull[..], &hole[..]], 0.0, 4.0, 1.0, 1.0, 4, 4);
+
+        // the overlap of the hole with the cell from v to v + 1 along either axis
+        let overlap = |v: isize| -> f64 { [0.5, 1.0, 0.5, 0.0][v as usize] };
+        let mut total = 0f64;
+        for &(row, col, fraction) in &coverage {
+            let expected = 1.0 - overlap(col) * overlap(3 - row);
+            assert!((fraction - expected).abs() < 1e-12, "cell ({}, {}): {}", row, col, fraction);
+            total += fraction;
+        }
+        // the cell from (1, 1) to (2, 2) lies within the hole and is excluded
+        assert_eq!(coverage.len(), 15);
+        assert!(!coverage.iter().any(|&(row, col, _)| row == 2 && col == 1));
+        assert!((total - 12.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,774 @@
+use std::f64;
+use std::collections::HashMap;
+use algorithms::{point_in_poly, signed_polygon_area};
+use structures::{BoundingBox, Point2D, RTree};
+
+/// A polygon overlay of one or more layers of polygon features. Each feature
+/// is given as a set of closed rings (hulls and holes, as in a shapefile
+/// record) and features within a layer may overlap one another.
+///
+/// The boundaries of all of the features are noded against one another, i.e.
+/// split wherever they cross or touch, with points closer together than the
+/// snap tolerance merged into a single node and vertices that lie within the
+/// tolerance of another boundary snapped onto it. The resulting planar graph
+/// divides the plane into faces, each of which lies entirely within or
+/// entirely outside of every input feature. The set-theoretic operations
+/// (intersection, union, difference, etc.) are then a matter of selecting
+/// faces according to the features that contain them and dissolving the
+/// selected faces back into polygons.
+pub struct PolygonOverlay {
+    nodes: Vec<Point2D>,
+    // half-edges 2e and 2e + 1 are the two directions of edge e
+    origin: Vec<usize>,
+    // the outgoing half-edges of each node, in counter-clockwise order
+    outgoing: Vec<Vec<usize>>,
+    // the position of each half-edge in its origin's outgoing list
+    position: Vec<usize>,
+    // the half-edge cycles of each face; the outer boundary is first, followed by any holes
+    faces: Vec<Vec<Vec<usize>>>,
+    // the features of each layer that contain each face
+    face_features: Vec<Vec<Vec<usize>>>,
+}
+
+impl PolygonOverlay {
+    /// Overlays the polygon features of each layer. A snap tolerance of zero
+    /// merges only points that are equal to within numerical precision.
+    pub fn new(layers: &[&[Vec<Vec<Point2D>>]], snap_tolerance: f64) -> PolygonOverlay {
+        let mut segments = vec![];
+        let mut extent = BoundingBox::default();
+        for layer in layers {
+            for feature in layer.iter() {
+                for ring in feature {
+                    extent.expand_to(&BoundingBox::from_points(ring));
+                    add_ring_segments(ring, &mut segments);
+                }
+            }
+        }
+        let tolerance = effective_tolerance(&extent, snap_tolerance);
+        let splits = split_segments(&segments, &vec![0; segments.len()], false, tolerance);
+
+        // build the planar graph
+        let mut snapper = NodeSnapper::new(tolerance);
+        let mut edges: Vec<(usize, usize)> = vec![];
+        let mut edge_map: HashMap<(usize, usize), usize> = HashMap::new();
+        for (i, s) in segments.iter().enumerate() {
+            let mut prev = snapper.get_node(s.0);
+            for &(_, p) in &splits[i] {
+                let node = snapper.get_node(p);
+                add_edge(prev, node, &mut edges, &mut edge_map);
+                prev = node;
+            }
+            let node = snapper.get_node(s.1);
+            add_edge(prev, node, &mut edges, &mut edge_map);
+        }
+        let nodes = snapper.nodes;
+        let mut origin = Vec::with_capacity(edges.len() * 2);
+        let mut outgoing: Vec<Vec<usize>> = vec![vec![]; nodes.len()];
+        for (e, &(u, v)) in edges.iter().enumerate() {
+            origin.push(u);
+            origin.push(v);
+            outgoing[u].push(2 * e);
+            outgoing[v].push(2 * e + 1);
+        }
+        let mut position = vec![0usize; origin.len()];
+        for n in 0..nodes.len() {
+            let p = nodes[n];
+            let mut list = outgoing[n].clone();
+            list.sort_by(|a, b| {
+                let pa = nodes[origin[a ^ 1]];
+                let pb = nodes[origin[b ^ 1]];
+                let angle_a = (pa.y - p.y).atan2(pa.x - p.x);
+                let angle_b = (pb.y - p.y).atan2(pb.x - p.x);
+                angle_a.partial_cmp(&angle_b).unwrap()
+            });
+            for (i, &h) in list.iter().enumerate() {
+                position[h] = i;
+            }
+            outgoing[n] = list;
+        }
+
+        let mut overlay = PolygonOverlay {
+            nodes: nodes,
+            origin: origin,
+            outgoing: outgoing,
+            position: position,
+            faces: vec![],
+            face_features: vec![],
+        };
+
+        // trace the cycles of the graph; with the face on the left of each
+        // half-edge, bounded faces are counter-clockwise and the outer
+        // boundaries of connected components are clockwise
+        let num_half_edges = overlay.origin.len();
+        let mut visited = vec![false; num_half_edges];
+        let mut cycles: Vec<Vec<usize>> = vec![];
+        for h in 0..num_half_edges {
+            if visited[h] {
+                continue;
+            }
+            let mut cycle = vec![];
+            let mut e = h;
+            while !visited[e] {
+                visited[e] = true;
+                cycle.push(e);
+                e = overlay.next_half_edge(e, |_| true);
+            }
+            cycles.push(cycle);
+        }
+
+        // label the connected components, so that the outer boundary of one
+        // component is never placed in a face of the same component
+        let mut component: Vec<usize> = (0..overlay.nodes.len()).collect();
+        fn find(component: &mut Vec<usize>, n: usize) -> usize {
+            let mut root = n;
+            while component[root] != root {
+                root = component[root];
+            }
+            let mut m = n;
+            while component[m] != root {
+                let next = component[m];
+                component[m] = root;
+                m = next;
+            }
+            root
+        }
+        for h in (0..num_half_edges).filter(|h| h % 2 == 0) {
+            let a = find(&mut component, overlay.origin[h]);
+            let b = find(&mut component, overlay.origin[h + 1]);
+            if a != b {
+                component[a] = b;
+            }
+        }
+
+        let mut face_rings = vec![];
+        let mut face_areas = vec![];
+        let mut face_boxes = vec![];
+        let mut outer_boundaries = vec![];
+        for cycle in cycles {
+            let ring = overlay.cycle_points(&cycle);
+            let area = signed_polygon_area(&ring);
+            if area > 0f64 {
+                face_boxes.push(BoundingBox::from_points(&ring));
+                face_rings.push(ring);
+                face_areas.push(area);
+                overlay.faces.push(vec![cycle]);
+            } else {
+                outer_boundaries.push(cycle);
+            }
+        }
+        let face_tree = RTree::bulk_load(face_boxes.into_iter().zip(0..face_rings.len()).collect());
+        for cycle in outer_boundaries {
+            let node = overlay.origin[cycle[0]];
+            let p = overlay.nodes[node];
+            let comp = find(&mut component, node);
+            let mut smallest = None;
+            let mut min_area = f64::INFINITY;
+            for &f in face_tree.search_point(p.x, p.y) {
+                if face_areas[f] < min_area {
+                    let face_node = overlay.origin[overlay.faces[f][0][0]];
+                    if find(&mut component, face_node) != comp && point_in_poly(&p, &face_rings[f]) {
+                        min_area = face_areas[f];
+                        smallest = Some(f);
+                    }
+                }
+            }
+            if let Some(f) = smallest {
+                overlay.faces[f].push(cycle);
+            }
+        }
+
+        // find the features that contain each face
+        let mut feature_trees: Vec<RTree<usize>> = vec![];
+        for layer in layers {
+            feature_trees.push(RTree::bulk_load(layer.iter().enumerate().map(|(i, feature)| {
+                let mut bb = BoundingBox::default();
+                for ring in feature {
+                    bb.expand_to(&BoundingBox::from_points(ring));
+                }
+                (bb, i)
+            }).collect()));
+        }
+        for f in 0..overlay.faces.len() {
+            let rings: Vec<Vec<Point2D>> = overlay.faces[f].iter().map(|c| overlay.cycle_points(c)).collect();
+            let p = interior_point(&rings);
+            let mut contained_by = vec![];
+            for (l, layer) in layers.iter().enumerate() {
+                let mut features: Vec<usize> = feature_trees[l].search_point(p.x, p.y)
+                    .into_iter()
+                    .cloned()
+                    .filter(|&i| feature_contains(&layer[i], &p))
+                    .collect();
+                features.sort();
+                contained_by.push(features);
+            }
+            overlay.face_features.push(contained_by);
+        }
+
+        overlay
+    }
+
+    pub fn num_faces(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Returns the features of a layer that contain a face.
+    pub fn get_face_features(&self, face: usize, layer: usize) -> &[usize] {
+        &self.face_features[face][layer]
+    }
+
+    /// Dissolves a set of faces into polygon rings, returned in shapefile
+    /// order, i.e. each clockwise hull followed by the counter-clockwise holes
+    /// that it contains. Rings are closed and collinear vertices are removed.
+    pub fn dissolve(&self, faces: &[usize]) -> Vec<Vec<Point2D>> {
+        // a half-edge is on the boundary of the dissolved region if its twin is not in the region
+        let mut in_region = vec![false; self.origin.len()];
+        for &f in faces {
+            for cycle in &self.faces[f] {
+                for &h in cycle {
+                    in_region[h] = true;
+                }
+            }
+        }
+        let is_boundary: Vec<bool> = (0..self.origin.len()).map(|h| in_region[h] && !in_region[h ^ 1]).collect();
+        let mut visited = vec![false; self.origin.len()];
+        let mut hulls: Vec<(Vec<Point2D>, f64)> = vec![];
+        let mut holes: Vec<Vec<Point2D>> = vec![];
+        for h in 0..self.origin.len() {
+            if !is_boundary[h] || visited[h] {
+                continue;
+            }
+            let mut cycle = vec![];
+            let mut e = h;
+            while !visited[e] {
+                visited[e] = true;
+                cycle.push(e);
+                e = self.next_half_edge(e, |h| is_boundary[h]);
+            }
+            let mut ring = remove_collinear_points(&self.cycle_points(&cycle));
+            if ring.len() < 3 {
+                continue;
+            }
+            // the region is on the left, so hulls are counter-clockwise; shapefiles want the reverse
+            let area = signed_polygon_area(&ring);
+            ring.reverse();
+            let first = ring[0];
+            ring.push(first);
+            if area > 0f64 {
+                hulls.push((ring, area));
+            } else if area < 0f64 {
+                holes.push(ring);
+            }
+        }
+        assemble_rings(hulls, holes)
+    }
+
+    // the next half-edge around the face on the left of h, considering only
+    // the half-edges accepted by the filter
+    fn next_half_edge<F: Fn(usize) -> bool>(&self, h: usize, filter: F) -> usize {
+        let twin = h ^ 1;
+        let list = &self.outgoing[self.origin[twin]];
+        let n = list.len();
+        let mut pos = self.position[twin];
+        for _ in 0..n {
+            pos = (pos + n - 1) % n;
+            if filter(list[pos]) {
+                return list[pos];
+            }
+        }
+        twin
+    }
+
+    fn cycle_points(&self, cycle: &[usize]) -> Vec<Point2D> {
+        cycle.iter().map(|&h| self.nodes[self.origin[h]]).collect()
+    }
+}
+
+/// A piece of a polyline resulting from an overlay with polygons.
+pub struct LineOverlayPiece {
+    /// The index of the line feature that the piece is part of.
+    pub feature: usize,
+    /// The part of the line feature that the piece is part of.
+    pub part: usize,
+    pub points: Vec<Point2D>,
+    /// The polygon features that contain the piece.
+    pub polygons: Vec<usize>,
+}
+
+/// Splits polyline features wherever they cross the boundaries of polygon
+/// features, returning the maximal pieces of each line part that are contained
+/// by the same set of polygons, in order along the line. Line features are
+/// given as sets of parts and polygons as sets of closed rings.
+pub fn overlay_lines_on_polygons(lines: &[Vec<Vec<Point2D>>],
+                                 polygons: &[Vec<Vec<Point2D>>],
+                                 snap_tolerance: f64)
+                                 -> Vec<LineOverlayPiece> {
+    let mut segments = vec![];
+    let mut groups = vec![];
+    let mut extent = BoundingBox::default();
+    // (feature, part) of the line segments, which come first
+    let mut segment_parts = vec![];
+    for (i, feature) in lines.iter().enumerate() {
+        for (j, part) in feature.iter().enumerate() {
+            extent.expand_to(&BoundingBox::from_points(part));
+            for k in 1..part.len() {
+                if part[k] != part[k - 1] {
+                    segments.push((part[k - 1], part[k]));
+                    groups.push(0);
+                    segment_parts.push((i, j));
+                }
+            }
+        }
+    }
+    let num_line_segments = segments.len();
+    let polygon_tree = RTree::bulk_load(polygons.iter().enumerate().map(|(i, feature)| {
+        let mut bb = BoundingBox::default();
+        for ring in feature {
+            bb.expand_to(&BoundingBox::from_points(ring));
+            add_ring_segments(ring, &mut segments);
+        }
+        extent.expand_to(&bb);
+        (bb, i)
+    }).collect());
+    groups.resize(segments.len(), 1);
+    let tolerance = effective_tolerance(&extent, snap_tolerance);
+    let splits = split_segments(&segments, &groups, true, tolerance);
+
+    let mut ret: Vec<LineOverlayPiece> = vec![];
+    let mut snapper = NodeSnapper::new(tolerance);
+    for s in 0..num_line_segments {
+        let (feature, part) = segment_parts[s];
+        let mut points = vec![snapper.get_node(segments[s].0)];
+        for &(_, p) in &splits[s] {
+            points.push(snapper.get_node(p));
+        }
+        points.push(snapper.get_node(segments[s].1));
+        for k in 1..points.len() {
+            if points[k] == points[k - 1] {
+                continue;
+            }
+            let (p1, p2) = (snapper.nodes[points[k - 1]], snapper.nodes[points[k]]);
+            let mid = p1.midpoint(&p2);
+            let mut contained_by: Vec<usize> = polygon_tree.search_point(mid.x, mid.y)
+                .into_iter()
+                .cloned()
+                .filter(|&i| feature_contains(&polygons[i], &mid))
+                .collect();
+            contained_by.sort();
+            // extend the current piece if it continues with the same containment
+            let mut extended = false;
+            if let Some(last) = ret.last_mut() {
+                if last.feature == feature && last.part == part && last.polygons == contained_by &&
+                   *last.points.last().unwrap() == p1 {
+                    last.points.push(p2);
+                    extended = true;
+                }
+            }
+            if !extended {
+                ret.push(LineOverlayPiece {
+                    feature: feature,
+                    part: part,
+                    points: vec![p1, p2],
+                    polygons: contained_by,
+                });
+            }
+        }
+    }
+    ret
+}
+
+// Merges points that are within the snap tolerance of an existing node.
+struct NodeSnapper {
+    tolerance: f64,
+    nodes: Vec<Point2D>,
+    grid: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl NodeSnapper {
+    fn new(tolerance: f64) -> NodeSnapper {
+        NodeSnapper {
+            tolerance: tolerance,
+            nodes: vec![],
+            grid: HashMap::new(),
+        }
+    }
+
+    fn get_node(&mut self, p: Point2D) -> usize {
+        let gx = (p.x / self.tolerance).floor() as i64;
+        let gy = (p.y / self.tolerance).floor() as i64;
+        let mut nearest = None;
+        let mut min_dist = self.tolerance * self.tolerance;
+        for dx in -1..2 {
+            for dy in -1..2 {
+                if let Some(ids) = self.grid.get(&(gx + dx, gy + dy)) {
+                    for &id in ids {
+                        let dist = self.nodes[id].distance_squared(&p);
+                        if dist <= min_dist {
+                            min_dist = dist;
+                            nearest = Some(id);
+                        }
+                    }
+                }
+            }
+        }
+        match nearest {
+            Some(id) => id,
+            None => {
+                let id = self.nodes.len();
+                self.nodes.push(p);
+                self.grid.entry((gx, gy)).or_insert(vec![]).push(id);
+                id
+            }
+        }
+    }
+}
+
+// The snap tolerance is never allowed to fall below the numerical precision of the coordinates.
+fn effective_tolerance(extent: &BoundingBox, snap_tolerance: f64) -> f64 {
+    let mut precision = 1e-9 * extent.width().max(extent.height());
+    if !(precision > 0f64) {
+        precision = 1e-9;
+    }
+    snap_tolerance.max(precision)
+}
+
+fn add_ring_segments(ring: &[Point2D], segments: &mut Vec<(Point2D, Point2D)>) {
+    let n = ring.len();
+    for k in 0..n {
+        // rings may or may not repeat their first point
+        let (p1, p2) = (ring[k], ring[(k + 1) % n]);
+        if p1 != p2 {
+            segments.push((p1, p2));
+        }
+    }
+}
+
+fn add_edge(u: usize, v: usize, edges: &mut Vec<(usize, usize)>, edge_map: &mut HashMap<(usize, usize), usize>) {
+    if u == v {
+        return;
+    }
+    let key = if u < v { (u, v) } else { (v, u) };
+    if !edge_map.contains_key(&key) {
+        edge_map.insert(key, edges.len());
+        edges.push((u, v));
+    }
+}
+
+// Finds the points at which each segment must be split so that segments only
+// meet at their end points, sorted along each segment. Segment end points that
+// lie within the tolerance of another segment split that segment. If
+// `cross_groups_only` is true, only segments of different groups are compared.
+fn split_segments(segments: &[(Point2D, Point2D)],
+                  groups: &[usize],
+                  cross_groups_only: bool,
+                  tolerance: f64)
+                  -> Vec<Vec<(f64, Point2D)>> {
+    let n = segments.len();
+    let mut splits: Vec<Vec<(f64, Point2D)>> = vec![vec![]; n];
+    let min_x = |i: usize| segments[i].0.x.min(segments[i].1.x);
+    let max_x = |i: usize| segments[i].0.x.max(segments[i].1.x);
+    let min_y = |i: usize| segments[i].0.y.min(segments[i].1.y);
+    let max_y = |i: usize| segments[i].0.y.max(segments[i].1.y);
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|a, b| min_x(*a).partial_cmp(&min_x(*b)).unwrap());
+    for a in 0..n {
+        let i = order[a];
+        for b in a + 1..n {
+            let j = order[b];
+            if min_x(j) > max_x(i) + tolerance {
+                break;
+            }
+            if min_y(j) > max_y(i) + tolerance || min_y(i) > max_y(j) + tolerance {
+                continue;
+            }
+            if cross_groups_only && groups[i] == groups[j] {
+                continue;
+            }
+            let (p1, p2) = segments[i];
+            let (p3, p4) = segments[j];
+            for q in &[p3, p4] {
+                if let Some(t) = project_onto_segment(q, &p1, &p2, tolerance) {
+                    splits[i].push((t, *q));
+                }
+            }
+            for q in &[p1, p2] {
+                if let Some(t) = project_onto_segment(q, &p3, &p4, tolerance) {
+                    splits[j].push((t, *q));
+                }
+            }
+            let (d1, d2) = (p2 - p1, p4 - p3);
+            let denom = d1.x * d2.y - d1.y * d2.x;
+            if denom != 0f64 {
+                let d3 = p3 - p1;
+                let t = (d3.x * d2.y - d3.y * d2.x) / denom;
+                let u = (d3.x * d1.y - d3.y * d1.x) / denom;
+                if t > 0f64 && t < 1f64 && u > 0f64 && u < 1f64 {
+                    let p = Point2D::new(p1.x + t * d1.x, p1.y + t * d1.y);
+                    splits[i].push((t, p));
+                    splits[j].push((u, p));
+                }
+            }
+        }
+    }
+    for s in &mut splits {
+        s.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    }
+    splits
+}
+
+// Returns the position along the segment a-b of the point on the segment
+// nearest to q, if q is within the tolerance of the segment's interior.
+fn project_onto_segment(q: &Point2D, a: &Point2D, b: &Point2D, tolerance: f64) -> Option<f64> {
+    let d = *b - *a;
+    let length_sqr = d.x * d.x + d.y * d.y;
+    if length_sqr == 0f64 {
+        return None;
+    }
+    let t = ((q.x - a.x) * d.x + (q.y - a.y) * d.y) / length_sqr;
+    if t <= 0f64 || t >= 1f64 {
+        return None;
+    }
+    let p = Point2D::new(a.x + t * d.x, a.y + t * d.y);
+    if p.distance(q) <= tolerance {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+// Tests whether a point lies within a polygon feature, using the even-odd rule over all of its rings.
+fn feature_contains(feature: &[Vec<Point2D>], p: &Point2D) -> bool {
+    let mut inside = false;
+    for ring in feature {
+        if point_in_poly(p, ring) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+// Finds a point that is strictly within a face, given its outer ring and
+// holes. The point lies on a horizontal line that passes through no vertices,
+// at the middle of the widest interval of that line within the face.
+fn interior_point(rings: &[Vec<Point2D>]) -> Point2D {
+    let mut ys: Vec<f64> = rings.iter().flat_map(|r| r.iter().map(|p| p.y)).collect();
+    ys.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    ys.dedup();
+    let mut y = ys[0];
+    let mut max_gap = -1f64;
+    for i in 1..ys.len() {
+        if ys[i] - ys[i - 1] > max_gap {
+            max_gap = ys[i] - ys[i - 1];
+            y = (ys[i] + ys[i - 1]) / 2f64;
+        }
+    }
+    let mut crossings = vec![];
+    for ring in rings {
+        let n = ring.len();
+        for k in 0..n {
+            let (p1, p2) = (ring[k], ring[(k + 1) % n]);
+            if (p1.y < y) != (p2.y < y) {
+                crossings.push(p1.x + (y - p1.y) / (p2.y - p1.y) * (p2.x - p1.x));
+            }
+        }
+    }
+    crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut x = rings[0][0].x;
+    let mut max_width = -1f64;
+    let mut i = 0;
+    while i + 1 < crossings.len() {
+        if crossings[i + 1] - crossings[i] > max_width {
+            max_width = crossings[i + 1] - crossings[i];
+            x = (crossings[i] + crossings[i + 1]) / 2f64;
+        }
+        i += 2;
+    }
+    Point2D::new(x, y)
+}
+
+// Removes vertices that lie on the straight line between their neighbours; the ring is not closed.
+fn remove_collinear_points(ring: &[Point2D]) -> Vec<Point2D> {
+    let mut points = ring.to_vec();
+    loop {
+        let n = points.len();
+        if n < 3 {
+            return points;
+        }
+        let mut keep = vec![true; n];
+        let mut removed = false;
+        for i in 0..n {
+            let (prev, p, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+            if !keep[(i + n - 1) % n] {
+                continue; // removing neighbouring vertices in one pass can change the shape
+            }
+            let (d1, d2) = (p - prev, next - p);
+            let cross = d1.x * d2.y - d1.y * d2.x;
+            let dot = d1.x * d2.x + d1.y * d2.y;
+            if dot > 0f64 && cross.abs() <= 1e-12 * (d1.x.abs() + d1.y.abs()) * (d2.x.abs() + d2.y.abs()) {
+                keep[i] = false;
+                removed = true;
+            }
+        }
+        if !removed {
+            return points;
+        }
+        points = points.iter().enumerate().filter(|&(i, _)| keep[i]).map(|(_, p)| *p).collect();
+    }
+}
+
+// Places each hole after the smallest hull that contains it.
+fn assemble_rings(hulls: Vec<(Vec<Point2D>, f64)>, holes: Vec<Vec<Point2D>>) -> Vec<Vec<Point2D>> {
+    let mut hull_holes: Vec<Vec<Vec<Point2D>>> = vec![vec![]; hulls.len()];
+    for hole in holes {
+        // the middle of an edge of the hole cannot lie on another ring
+        let probe = hole[0].midpoint(&hole[1]);
+        let mut which = None;
+        let mut min_area = f64::INFINITY;
+        for h in 0..hulls.len() {
+            if hulls[h].1 < min_area && point_in_poly(&probe, &hulls[h].0) {
+                min_area = hulls[h].1;
+                which = Some(h);
+            }
+        }
+        if let Some(h) = which {
+            hull_holes[h].push(hole);
+        }
+    }
+    let mut ret = vec![];
+    for (h, (hull, _)) in hulls.into_iter().enumerate() {
+        ret.push(hull);
+        for hole in hull_holes[h].drain(..) {
+            ret.push(hole);
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PolygonOverlay;
+    use algorithms::{is_clockwise_order, signed_polygon_area};
+    use structures::Point2D;
+
+    // a closed, clockwise (hull) or counter-clockwise (hole) rectangle
+    fn rectangle(x0: f64, y0: f64, x1: f64, y1: f64, hole: bool) -> Vec<Point2D> {
+        let mut ring = vec![Point2D::new(x0, y0), Point2D::new(x0, y1), Point2D::new(x1, y1), Point2D::new(x1, y0), Point2D::new(x0, y0)];
+        if hole {
+            ring.reverse();
+        }
+        ring
+    }
+
+    // the net area of a set of shapefile-ordered rings
+    fn area(rings: &[Vec<Point2D>]) -> f64 {
+        -rings.iter().map(|r| signed_polygon_area(r)).sum::<f64>()
+    }
+
+    fn num_hulls(rings: &[Vec<Point2D>]) -> usize {
+        rings.iter().filter(|r| is_clockwise_order(r)).count()
+    }
+
+    // the faces contained by features of every layer, or of any layer
+    fn select_faces(overlay: &PolygonOverlay, num_layers: usize, all: bool) -> Vec<usize> {
+        (0..overlay.num_faces()).filter(|&f| {
+            let mut contained = (0..num_layers).map(|l| !overlay.get_face_features(f, l).is_empty());
+            if all { contained.all(|c| c) } else { contained.any(|c| c) }
+        }).collect()
+    }
+
+    #[test]
+    fn test_overlay_with_holes() {
+        let a = vec![vec![rectangle(0.0, 0.0, 10.0, 10.0, false), rectangle(4.0, 4.0, 6.0, 6.0, true)]];
+        let b = vec![vec![rectangle(5.0, 5.0, 15.0, 15.0, false)]];
+        let overlay = PolygonOverlay::new(&[&a, &b], 0.0);
+
+        let intersection = overlay.dissolve(&select_faces(&overlay, 2, true));
+        assert_eq!(num_hulls(&intersection), 1);
+        assert!((area(&intersection) - 24.0).abs() < 1e-9);
+
+        let union = overlay.dissolve(&select_faces(&overlay, 2, false));
+        assert_eq!(num_hulls(&union), 1);
+        assert_eq!(union.len(), 2); // the hole is only partly filled by the second polygon
+        assert!((area(&union) - 172.0).abs() < 1e-9);
+
+        // the hole is not covered by the first layer
+        let hole_only: Vec<usize> = (0..overlay.num_faces()).filter(|&f| {
+            overlay.get_face_features(f, 0).is_empty() && !overlay.get_face_features(f, 1).is_empty()
+        }).collect();
+        let difference = overlay.dissolve(&hole_only);
+        assert!((area(&difference) - (100.0 - 25.0 + 1.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hole_is_retained() {
+        let a = vec![vec![rectangle(0.0, 0.0, 10.0, 10.0, false), rectangle(2.0, 2.0, 4.0, 4.0, true)]];
+        let b = vec![vec![rectangle(6.0, 6.0, 12.0, 12.0, false)]];
+        let overlay = PolygonOverlay::new(&[&a, &b], 0.0);
+        let union = overlay.dissolve(&select_faces(&overlay, 2, false));
+        assert_eq!(union.len(), 2);
+        assert!(is_clockwise_order(&union[0]));
+        assert!(!is_clockwise_order(&union[1]));
+        assert!((area(&union) - (100.0 - 4.0 + 36.0 - 16.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_overlay_multipart_features() {
+        // one feature made of two disjoint parts, crossed by a single overlay feature
+        let a = vec![vec![rectangle(0.0, 0.0, 4.0, 4.0, false), rectangle(6.0, 0.0, 10.0, 4.0, false)]];
+        let b = vec![vec![rectangle(2.0, 1.0, 8.0, 3.0, false)]];
+        let overlay = PolygonOverlay::new(&[&a, &b], 0.0);
+        for f in 0..overlay.num_faces() {
+            assert!(overlay.get_face_features(f, 0).len() <= 1);
+        }
+        let intersection = overlay.dissolve(&select_faces(&overlay, 2, true));
+        assert_eq!(num_hulls(&intersection), 2);
+        assert!((area(&intersection) - 8.0).abs() < 1e-9);
+        let union = overlay.dissolve(&select_faces(&overlay, 2, false));
+        assert_eq!(num_hulls(&union), 1);
+        assert!((area(&union) - (32.0 + 4.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_overlapping_features_within_a_layer() {
+        let a = vec![vec![rectangle(0.0, 0.0, 2.0, 2.0, false)], vec![rectangle(1.0, 1.0, 3.0, 3.0, false)]];
+        let overlay = PolygonOverlay::new(&[&a], 0.0);
+        let shared: Vec<usize> = (0..overlay.num_faces()).filter(|&f| overlay.get_face_features(f, 0).len() == 2).collect();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(overlay.get_face_features(shared[0], 0), &[0, 1]);
+        assert!((area(&overlay.dissolve(&shared)) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_snap_tolerance() {
+        // two adjacent polygons separated by a sliver gap of 0.001
+        let a = vec![vec![rectangle(0.0, 0.0, 5.0, 5.0, false)]];
+        let b = vec![vec![rectangle(5.001, 0.0, 10.0, 5.0, false)]];
+
+        let overlay = PolygonOverlay::new(&[&a, &b], 0.0);
+        let union = overlay.dissolve(&select_faces(&overlay, 2, false));
+        assert_eq!(num_hulls(&union), 2);
+
+        let overlay = PolygonOverlay::new(&[&a, &b], 0.01);
+        let union = overlay.dissolve(&select_faces(&overlay, 2, false));
+        assert_eq!(num_hulls(&union), 1);
+        assert_eq!(union[0].len(), 5); // collinear vertices along the shared edge are removed
+        assert!((area(&union) - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_vertex_snapped_onto_edge() {
+        // the lower vertices of the overlay lie just above the middle of the input's upper edge
+        let a = vec![vec![rectangle(0.0, 0.0, 10.0, 10.0, false)]];
+        let b = vec![vec![rectangle(2.0, 10.0005, 8.0, 15.0, false)]];
+
+        let overlay = PolygonOverlay::new(&[&a, &b], 0.0);
+        assert_eq!(num_hulls(&overlay.dissolve(&select_faces(&overlay, 2, false))), 2);
+
+        let overlay = PolygonOverlay::new(&[&a, &b], 0.001);
+        let union = overlay.dissolve(&select_faces(&overlay, 2, false));
+        assert_eq!(num_hulls(&union), 1);
+        assert_eq!(union.len(), 1);
+        assert!((area(&union) - 130.0).abs() < 0.01);
+        let intersection = overlay.dissolve(&select_faces(&overlay, 2, true));
+        assert!(intersection.is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+use algorithms::{point_in_poly, polygon_area, signed_polygon_area};
+use structures::{Array2D, Point2D};
+
+/// Traces the cell-edge boundaries of labelled raster regions, returning the
+/// polygon rings of each region. `labels` contains region identifiers in the
+/// range 0..num_regions; negative values are treated as background. The rings
+/// of each region are returned in shapefile order, i.e. each clockwise hull is
+/// followed by the counter-clockwise holes that it contains. Rings are closed
+/// (the first point is repeated at the end) and redundant collinear vertices
+/// are removed.
+///
+/// Cells of a region that touch only at a corner are traced as separate rings,
+/// so an 8-connected region is represented by a multi-part polygon.
+pub fn trace_region_boundaries(labels: &Array2D<i32>,
+                               num_regions: usize,
+                               west: f64,
+                               north: f64,
+                               resolution_x: f64,
+                               resolution_y: f64)
+                               -> Vec<Vec<Vec<Point2D>>> {
+    let rows = labels.rows;
+    let columns = labels.columns;
+    // direction of travel along each side of a cell, with the cell on the right:
+    // 0 = top (east), 1 = right (south), 2 = bottom (west), 3 = left (north)
+    let dr = [0isize, 1, 0, -1];
+    let dc = [1isize, 0, -1, 0];
+    // the vertex at which travel along each side begins, relative to the cell's top-left corner
+    let start_r = [0isize, 0, 1, 1];
+    let start_c = [0isize, 1, 1, 0];
+
+    let get_label = |row: isize, col: isize| -> i32 {
+        if row < 0 || col < 0 || row >= rows || col >= columns {
+            return -1;
+        }
+        labels.get_value(row, col)
+    };
+    // side s of a cell is a boundary if the cell across it (in direction (s + 3) % 4) differs
+    let is_boundary = |row: isize, col: isize, s: usize| -> bool {
+        let n = (s + 3) % 4;
+        get_label(row + dr[n], col + dc[n]) != get_label(row, col)
+    };
+
+    let mut visited = vec![0u8; (rows * columns) as usize];
+    let mut rings: Vec<Vec<(Vec<Point2D>, Point2D)>> = vec![vec![]; num_regions];
+    for row in 0..rows {
+        for col in 0..columns {
+            let label = get_label(row, col);
+            if label < 0 {
+                continue;
+            }
+            for side in 0..4usize {
+                let idx = (row * columns + col) as usize;
+                if visited[idx] & (1u8 << side) != 0 || !is_boundary(row, col, side) {
+                    continue;
+                }
+                // trace the ring starting with this edge
+                let mut vertices: Vec<(isize, isize)> = vec![];
+                let (mut r, mut c, mut s) = (row, col, side);
+                loop {
+                    visited[(r * columns + c) as usize] |= 1u8 << s;
+                    vertices.push((r + start_r[s], c + start_c[s]));
+                    let right = (s + 1) % 4;
+                    if is_boundary(r, c, right) {
+                        // turn right, hugging the current cell
+                        s = right;
+                    } else {
+                        let (r2, c2) = (r + dr[s], c + dc[s]);
+                        if is_boundary(r2, c2, s) {
+                            // carry straight on
+                            r = r2;
+                            c = c2;
+                        } else {
+                            // turn left onto the diagonal cell
+                            let left = (s + 3) % 4;
+                            r = r2 + dr[left];
+                            c = c2 + dc[left];
+                            s = left;
+                        }
+                    }
+                    if r == row && c == col && s == side {
+                        break;
+                    }
+                }
+
+                // a point on the ring's first edge that lies on no other ring
+                let probe = Point2D::new(west + (vertices[0].1 + vertices[1].1) as f64 / 2f64 * resolution_x,
+                                         north - (vertices[0].0 + vertices[1].0) as f64 / 2f64 * resolution_y);
+
+                // remove collinear vertices; all edges are either horizontal or vertical
+                let n = vertices.len();
+                let mut points = Vec::with_capacity(n + 1);
+                for i in 0..n {
+                    let prev = vertices[(i + n - 1) % n];
+                    let next = vertices[(i + 1) % n];
+                    let v = vertices[i];
+                    if (prev.0 == v.0 && v.0 == next.0) || (prev.1 == v.1 && v.1 == next.1) {
+                        continue;
+                    }
+                    points.push(Point2D::new(west + v.1 as f64 * resolution_x,
+                                             north - v.0 as f64 * resolution_y));
+                }
+                let first = points[0];
+                points.push(first);
+                rings[label as usize].push((points, probe));
+            }
+        }
+    }
+
+    // assign each hole to the smallest hull of the same region that contains it
+    let mut ret = Vec::with_capacity(num_regions);
+    for region_rings in rings {
+        let mut hulls: Vec<(Vec<Point2D>, f64)> = vec![];
+        let mut holes: Vec<(Vec<Point2D>, Point2D)> = vec![];
+        for (points, probe) in region_rings {
+            if signed_polygon_area(&points) < 0f64 {
+                let area = polygon_area(&points);
+                hulls.push((points, area));
+            } else {
+                holes.push((points, probe));
+            }
+        }
+        let mut hull_holes: Vec<Vec<Vec<Point2D>>> = vec![vec![]; hulls.len()];
+        for (points, probe) in holes {
+            let mut which = 0;
+            let mut min_area = f64::INFINITY;
+            for h in 0..hulls.len() {
+                if hulls[h].1 < min_area && point_in_poly(&probe, &hulls[h].0) {
+                    min_area = hulls[h].1;
+                    which = h;
+                }
+            }
+            if !hulls.is_empty() {
+                hull_holes[which].push(points);
+            }
+        }
+        let mut region = vec![];
+        for (h, (points, _)) in hulls.into_iter().enumerate() {
+            region.push(points);
+            for hole in hull_holes[h].drain(..) {
+                region.push(hole);
+            }
+        }
+        ret.push(region);
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::trace_region_boundaries;
+    use algorithms::{is_clockwise_order, polygon_area};
+    use structures::{Array2D, Point2D};
+
+    // a grid of labels with the given cells set, and the remainder background
+    fn labels(rows: isize, columns: isize, cells: &[(isize, isize, i32)]) -> Array2D<i32> {
+        let mut labels: Array2D<i32> = Array2D::new(rows, columns, -1, -1).unwrap();
+        for &(row, col, label) in cells {
+            labels.set_value(row, col, label);
+        }
+        labels
+    }
+
+    #[test]
+    fn test_region_with_a_hole() {
+        // a 3 x 3 block of region 0 with its centre cell an island of region 1
+        let mut cells = vec![];
+        for row in 1..4 {
+            for col in 1..4 {
+                cells.push((row, col, if row == 2 && col == 2 { 1 } else { 0 }));
+            }
+        }
+        let regions = trace_region_boundaries(&labels(5, 5, &cells), 2, 0.0, 5.0, 1.0, 1.0);
+        assert_eq!(regions.len(), 2);
+
+        // the hull is clockwise and the hole counter-clockwise, with the collinear vertices removed
+        let (hull, hole) = (&regions[0][0], &regions[0][1]);
+        assert_eq!(regions[0].len(), 2);
+        assert_eq!(hull.len(), 5);
+        assert_eq!(hull[0], hull[4]);
+        assert!(is_clockwise_order(hull));
+        assert_eq!(polygon_area(hull), 9.0);
+        for p in hull {
+            assert!((p.x == 1.0 || p.x == 4.0) && (p.y == 1.0 || p.y == 4.0));
+        }
+        assert_eq!(hole.len(), 5);
+        assert!(!is_clockwise_order(hole));
+        assert_eq!(polygon_area(hole), 1.0);
+
+        // the island is a clockwise hull with the same vertices as the hole
+        assert_eq!(regions[1].len(), 1);
+        let island = &regions[1][0];
+        assert!(is_clockwise_order(island));
+        let mut a: Vec<(f64, f64)> = hole[..4].iter().map(|p| (p.x, p.y)).collect();
+        let mut b: Vec<(f64, f64)> = island[..4].iter().map(|p| (p.x, p.y)).collect();
+        a.sort_by(|p, q| p.partial_cmp(q).unwrap());
+        b.sort_by(|p, q| p.partial_cmp(q).unwrap());
+        assert_eq!(a, b);
+        assert!(a.contains(&(2.0, 2.0)) && a.contains(&(3.0, 3.0)));
+    }
+
+    #[test]
+    fn test_diagonal_and_orthogonal_connectivity() {
+        // four cells surrounding the centre of a 3 x 3 grid, touching only at their corners
+        let diamond = [(0, 1), (1, 0), (1, 2), (2, 1)];
+
+        // with orthogonal connectivity each cell is a region of its own
+        let cells: Vec<(isize, isize, i32)> = diamond.iter().enumerate().map(|(i, &(r, c))| (r, c, i as i32)).collect();
+        let regions = trace_region_boundaries(&labels(3, 3, &cells), 4, 0.0, 3.0, 1.0, 1.0);
+        for region in &regions {
+            assert_eq!(region.len(), 1);
+            assert!(is_clockwise_order(&region[0]));
+            assert_eq!(polygon_area(&region[0]), 1.0);
+        }
+
+        // with diagonal connectivity they form one region, which is traced as a
+        // multi-part polygon of four hulls, and the centre cell is not a hole
+        let cells: Vec<(isize, isize, i32)> = diamond.iter().map(|&(r, c)| (r, c, 0)).collect();
+        let regions = trace_region_boundaries(&labels(3, 3, &cells), 1, 0.0, 3.0, 1.0, 1.0);
+        assert_eq!(regions[0].len(), 4);
+        for ring in &regions[0] {
+            assert_eq!(ring.len(), 5);
+            assert!(is_clockwise_order(ring));
+            assert_eq!(polygon_area(ring), 1.0);
+        }
+        let centre = Point2D::new(1.5, 1.5);
+        assert!(regions[0].iter().all(|ring| ring.iter().all(|p| p.distance(&centre) > 0.5)));
+    }
+}
@@ -0,0 +1,163 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use structures::Point2D;
+
+/// Simplifies a line using the Douglas-Peucker algorithm, removing vertices
+/// that lie within `tolerance` of the simplified line. The end points are
+/// always retained, so closed rings (first point equal to last) stay closed.
+pub fn douglas_peucker(points: &[Point2D], tolerance: f64) -> Vec<Point2D> {
+    let n = points.len();
+    if n < 3 || tolerance <= 0f64 {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    keep[n - 1] = true;
+
+    let is_closed = points[0] == points[n - 1];
+    let mut stack = vec![];
+    if is_closed {
+        // A closed ring has no baseline, so split it at its farthest vertex.
+        let mut max_dist = -1f64;
+        let mut split = n / 2;
+        for i in 1..n - 1 {
+            let d = points[i].distance_squared(&points[0]);
+            if d > max_dist {
+                max_dist = d;
+                split = i;
+            }
+        }
+        keep[split] = true;
+        stack.push((0, split));
+        stack.push((split, n - 1));
+    } else {
+        stack.push((0, n - 1));
+    }
+
+    while let Some((start, end)) = stack.pop() {
+        if end <= start + 1 {
+            continue;
+        }
+        let mut max_dist = 0f64;
+        let mut index = start;
+        for i in start + 1..end {
+            let d = perpendicular_distance(&points[i], &points[start], &points[end]);
+            if d > max_dist {
+                max_dist = d;
+                index = i;
+            }
+        }
+        if max_dist > tolerance {
+            keep[index] = true;
+            stack.push((start, index));
+            stack.push((index, end));
+        }
+    }
+
+    let mut ret: Vec<Point2D> = Vec::with_capacity(n);
+    for i in 0..n {
+        if keep[i] {
+            ret.push(points[i]);
+        }
+    }
+    if is_closed && ret.len() < 4 {
+        // the ring would collapse
+        return points.to_vec();
+    }
+    ret
+}
+
+/// Simplifies a line using the Visvalingam-Whyatt algorithm, repeatedly
+/// removing the vertex that forms the smallest triangle with its neighbours
+/// (its effective area) until every remaining vertex has an effective area of
+/// at least `min_area`. As with `douglas_peucker`, the end points are always
+/// retained and closed rings keep at least three distinct vertices.
+pub fn visvalingam_whyatt(points: &[Point2D], min_area: f64) -> Vec<Point2D> {
+    let n = points.len();
+    if n < 3 || min_area <= 0f64 {
+        return points.to_vec();
+    }
+    let is_closed = points[0] == points[n - 1];
+    let min_points = if is_closed { 4 } else { 2 };
+
+    let area = |a: usize, b: usize, c: usize| -> f64 {
+        let (p, q, r) = (points[a], points[b], points[c]);
+        ((q.x - p.x) * (r.y - p.y) - (r.x - p.x) * (q.y - p.y)).abs() / 2f64
+    };
+
+    // a doubly-linked list of the remaining vertices
+    let mut prev: Vec<usize> = (0..n).map(|i| if i > 0 { i - 1 } else { 0 }).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| if i < n - 1 { i + 1 } else { n - 1 }).collect();
+    let mut removed = vec![false; n];
+    let mut effective_area = vec![0f64; n];
+    let mut heap = BinaryHeap::with_capacity(n);
+    for i in 1..n - 1 {
+        effective_area[i] = area(i - 1, i, i + 1);
+        heap.push(Vertex { index: i, area: effective_area[i] });
+    }
+
+    let mut num_points = n;
+    let mut max_removed_area = 0f64;
+    while let Some(v) = heap.pop() {
+        if removed[v.index] || v.area != effective_area[v.index] {
+            continue; // a stale entry
+        }
+        if v.area >= min_area || num_points <= min_points {
+            break;
+        }
+        // a vertex's area is never less than that of a vertex removed before it,
+        // so that the removal order is consistent with the areas
+        max_removed_area = max_removed_area.max(v.area);
+        removed[v.index] = true;
+        num_points -= 1;
+        let (p, q) = (prev[v.index], next[v.index]);
+        next[p] = q;
+        prev[q] = p;
+        for &i in &[p, q] {
+            if i > 0 && i < n - 1 {
+                effective_area[i] = area(prev[i], i, next[i]).max(max_removed_area);
+                heap.push(Vertex { index: i, area: effective_area[i] });
+            }
+        }
+    }
+
+    (0..n).filter(|&i| !removed[i]).map(|i| points[i]).collect()
+}
+
+#[derive(PartialEq, Debug)]
+struct Vertex {
+    index: usize,
+    area: f64,
+}
+
+impl Eq for Vertex {}
+
+impl PartialOrd for Vertex {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        // reversed, so that the heap yields the smallest area first
+        other.area.partial_cmp(&self.area)
+    }
+}
+
+impl Ord for Vertex {
+    fn cmp(&self, other: &Vertex) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+}
+
+/// The distance from point p to the line segment (a, b).
+pub fn perpendicular_distance(p: &Point2D, a: &Point2D, b: &Point2D) -> f64 {
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+    let len_sqr = dx * dx + dy * dy;
+    if len_sqr == 0f64 {
+        return p.distance(a);
+    }
+    let mut t = ((p.x - a.x) * dx + (p.y - a.y) * dy) / len_sqr;
+    if t < 0f64 {
+        t = 0f64;
+    } else if t > 1f64 {
+        t = 1f64;
+    }
+    p.distance(&Point2D::new(a.x + t * dx, a.y + t * dy))
+}
@@ -0,0 +1,78 @@
+use structures::Point2D;
+
+/// Smooths a line using Chaikin's corner-cutting algorithm. Each iteration
+/// replaces every vertex with two new vertices placed at 1/4 and 3/4 along the
+/// adjoining segments. The end points of open lines are retained and closed
+/// rings (first point equal to last) remain closed.
+pub fn chaikin_smoothing(points: &[Point2D], iterations: usize) -> Vec<Point2D> {
+    let mut ret = points.to_vec();
+    if points.len() < 3 {
+        return ret;
+    }
+    let is_closed = points[0] == points[points.len() - 1];
+    for _ in 0..iterations {
+        let n = ret.len();
+        let mut smoothed = Vec::with_capacity(2 * n);
+        if !is_closed {
+            smoothed.push(ret[0]);
+        }
+        for i in 0..n - 1 {
+            let (p, q) = (ret[i], ret[i + 1]);
+            smoothed.push(Point2D::new(0.75 * p.x + 0.25 * q.x, 0.75 * p.y + 0.25 * q.y));
+            smoothed.push(Point2D::new(0.25 * p.x + 0.75 * q.x, 0.25 * p.y + 0.75 * q.y));
+        }
+        if is_closed {
+            let first = smoothed[0];
+            smoothed.push(first);
+        } else {
+            smoothed.push(ret[n - 1]);
+        }
+        ret = smoothed;
+    }
+    ret
+}
+
+/// Smooths a line by replacing each vertex with the average position of the
+/// vertices within a window of `filter_size` vertices centred on it. Near the
+/// ends of open lines the window is narrowed so that it remains centred, and
+/// the end points themselves are retained; closed rings (first point equal to
+/// last) are smoothed cyclically and remain closed.
+pub fn moving_average_smoothing(points: &[Point2D], filter_size: usize, iterations: usize) -> Vec<Point2D> {
+    let mut ret = points.to_vec();
+    let half_window = filter_size / 2;
+    if points.len() < 3 || half_window == 0 {
+        return ret;
+    }
+    let is_closed = points[0] == points[points.len() - 1];
+    for _ in 0..iterations {
+        let n = ret.len();
+        let mut smoothed = ret.clone();
+        if is_closed {
+            // the repeated end point is excluded from the cycle
+            let m = n - 1;
+            let k = half_window.min((m - 1) / 2);
+            for i in 0..m {
+                let (mut x, mut y) = (0f64, 0f64);
+                for j in 0..2 * k + 1 {
+                    let p = ret[(i + m + j - k) % m];
+                    x += p.x;
+                    y += p.y;
+                }
+                smoothed[i] = Point2D::new(x / (2 * k + 1) as f64, y / (2 * k + 1) as f64);
+            }
+            smoothed[m] = smoothed[0];
+        } else {
+            for i in 1..n - 1 {
+                let k = half_window.min(i).min(n - 1 - i);
+                let (mut x, mut y) = (0f64, 0f64);
+                for j in i - k..i + k + 1 {
+                    x += ret[j].x;
+                    y += ret[j].y;
+                }
+                smoothed[i] = Point2D::new(x / (2 * k + 1) as f64, y / (2 * k + 1) as f64);
+            }
+        }
+        ret = smoothed;
+    }
+    ret
+}
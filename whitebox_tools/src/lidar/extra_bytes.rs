@@ -0,0 +1,523 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+
+// Extra bytes are additional per-point attributes, e.g. echo width, deviation or
+// height above ground, that follow the standard fields of a point record. They
+// are described by the descriptors held in the Extra Bytes VLR (record ID 4 of
+// the LASF_Spec user ID) of LAS 1.4, in the order in which they are stored.
+
+use std::f64;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use byteorder::{ByteOrder, LittleEndian};
+use lidar::vlr::Vlr;
+
+/// The record ID of the Extra Bytes VLR, which belongs to the LASF_Spec user ID.
+pub const EXTRA_BYTES_RECORD_ID: u16 = 4;
+
+/// The data type of an extra bytes attribute.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExtraByteType {
+    /// Bytes of unknown meaning; their number is held in the options field.
+    Undocumented,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    F32,
+    F64,
+}
+
+impl ExtraByteType {
+    /// The size, in bytes, of one value of the type.
+    pub fn size(&self) -> usize {
+        match *self {
+            ExtraByteType::Undocumented => 0,
+            ExtraByteType::U8 | ExtraByteType::I8 => 1,
+            ExtraByteType::U16 | ExtraByteType::I16 => 2,
+            ExtraByteType::U32 | ExtraByteType::I32 | ExtraByteType::F32 => 4,
+            ExtraByteType::U64 | ExtraByteType::I64 | ExtraByteType::F64 => 8,
+        }
+    }
+
+    fn code(&self) -> u8 {
+        match *self {
+            ExtraByteType::Undocumented => 0,
+            ExtraByteType::U8 => 1,
+            ExtraByteType::I8 => 2,
+            ExtraByteType::U16 => 3,
+            ExtraByteType::I16 => 4,
+            ExtraByteType::U32 => 5,
+            ExtraByteType::I32 => 6,
+            ExtraByteType::U64 => 7,
+            ExtraByteType::I64 => 8,
+            ExtraByteType::F32 => 9,
+            ExtraByteType::F64 => 10,
+        }
+    }
+
+    fn from_code(code: u8) -> ExtraByteType {
+        match code {
+            1 => ExtraByteType::U8,
+            2 => ExtraByteType::I8,
+            3 => ExtraByteType::U16,
+            4 => ExtraByteType::I16,
+            5 => ExtraByteType::U32,
+            6 => ExtraByteType::I32,
+            7 => ExtraByteType::U64,
+            8 => ExtraByteType::I64,
+            9 => ExtraByteType::F32,
+            10 => ExtraByteType::F64,
+            _ => ExtraByteType::Undocumented,
+        }
+    }
+
+    fn is_signed(&self) -> bool {
+        match *self {
+            ExtraByteType::I8 | ExtraByteType::I16 | ExtraByteType::I32 | ExtraByteType::I64 => true,
+            _ => false,
+        }
+    }
+
+    fn is_float(&self) -> bool {
+        *self == ExtraByteType::F32 || *self == ExtraByteType::F64
+    }
+
+    // The range of the values of an integer type.
+    fn range(&self) -> (f64, f64) {
+        match *self {
+            ExtraByteType::U8 => (0f64, u8::max_value() as f64),
+            ExtraByteType::I8 => (i8::min_value() as f64, i8::max_value() as f64),
+            ExtraByteType::U16 => (0f64, u16::max_value() as f64),
+            ExtraByteType::I16 => (i16::min_value() as f64, i16::max_value() as f64),
+            ExtraByteType::U32 => (0f64, u32::max_value() as f64),
+            ExtraByteType::I32 => (i32::min_value() as f64, i32::max_value() as f64),
+            ExtraByteType::U64 => (0f64, u64::max_value() as f64),
+            ExtraByteType::I64 => (i64::min_value() as f64, i64::max_value() as f64),
+            _ => (f64::NEG_INFINITY, f64::INFINITY),
+        }
+    }
+}
+
+/// Describes an extra bytes attribute. The no-data, minimum and maximum values
+/// are stored unscaled, i.e. as they are found in the point records, and each
+/// is only meaningful if the corresponding bit of the options field is set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtraBytesDescriptor {
+    pub data_type: ExtraByteType,
+    /// The number of values of the attribute, which is greater than one only for
+    /// the two- and three-value array types deprecated in LAS 1.4 R14.
+    pub num_values: usize,
+    /// Bit 0: no-data is set; bit 1: min is set; bit 2: max is set; bit 3: scale
+    /// is set; bit 4: offset is set. For undocumented bytes, it holds their number.
+    pub options: u8,
+    pub name: String,
+    pub no_data: [f64; 3],
+    pub min: [f64; 3],
+    pub max: [f64; 3],
+    pub scale: [f64; 3],
+    pub offset: [f64; 3],
+    pub description: String,
+}
+
+impl ExtraBytesDescriptor {
+    /// Creates the descriptor of a single-valued attribute.
+    pub fn new(name: &str, data_type: ExtraByteType, description: &str) -> ExtraBytesDescriptor {
+        ExtraBytesDescriptor {
+            data_type: data_type,
+            num_values: 1,
+            options: 0,
+            name: name.to_string(),
+            no_data: [0f64; 3],
+            min: [0f64; 3],
+            max: [0f64; 3],
+            scale: [1f64; 3],
+            offset: [0f64; 3],
+            description: description.to_string(),
+        }
+    }
+
+    /// Creates the descriptor of a run of bytes of unknown meaning.
+    pub fn undocumented(num_bytes: u8) -> ExtraBytesDescriptor {
+        let mut descriptor = ExtraBytesDescriptor::new("", ExtraByteType::Undocumented, "");
+        descriptor.options = num_bytes;
+        descriptor
+    }
+
+    /// Reads a descriptor from the 192 bytes that it occupies in the VLR.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ExtraBytesDescriptor, Error> {
+        if bytes.len() < 192 {
+            return Err(Error::new(ErrorKind::InvalidData, "The Extra Bytes VLR is incomplete."));
+        }
+        let code = bytes[2];
+        let mut descriptor = ExtraBytesDescriptor::new(&fixed_length_str(&bytes[4..36]),
+            ExtraByteType::from_code(((code as i32 - 1) % 10 + 1) as u8), &fixed_length_str(&bytes[160..192]));
+        if code > 30 {
+            descriptor.data_type = ExtraByteType::Undocumented;
+        }
+        descriptor.num_values = if code > 20 { 3 } else if code > 10 { 2 } else { 1 };
+        descriptor.options = bytes[3];
+        for i in 0..3 {
+            descriptor.no_data[i] = descriptor.read_any(&bytes[40 + i * 8..48 + i * 8]);
+            descriptor.min[i] = descriptor.read_any(&bytes[64 + i * 8..72 + i * 8]);
+            descriptor.max[i] = descriptor.read_any(&bytes[88 + i * 8..96 + i * 8]);
+            descriptor.scale[i] = LittleEndian::read_f64(&bytes[112 + i * 8..120 + i * 8]);
+            descriptor.offset[i] = LittleEndian::read_f64(&bytes[136 + i * 8..144 + i * 8]);
+        }
+        if descriptor.data_type == ExtraByteType::Undocumented && descriptor.options == 0 {
+            return Err(Error::new(ErrorKind::InvalidData, "An extra bytes descriptor has an unsupported data type."));
+        }
+        Ok(descriptor)
+    }
+
+    /// Writes the descriptor in its 192-byte form.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; 192];
+        bytes[2] = match self.data_type {
+            ExtraByteType::Undocumented => 0,
+            _ => self.data_type.code() + 10 * (self.num_values.max(1).min(3) as u8 - 1),
+        };
+        bytes[3] = self.options;
+        copy_str(&self.name, &mut bytes[4..36]);
+        for i in 0..3 {
+            self.write_any(self.no_data[i], &mut bytes[40 + i * 8..48 + i * 8]);
+            self.write_any(self.min[i], &mut bytes[64 + i * 8..72 + i * 8]);
+            self.write_any(self.max[i], &mut bytes[88 + i * 8..96 + i * 8]);
+            LittleEndian::write_f64(&mut bytes[112 + i * 8..120 + i * 8], self.scale[i]);
+            LittleEndian::write_f64(&mut bytes[136 + i * 8..144 + i * 8], self.offset[i]);
+        }
+        copy_str(&self.description, &mut bytes[160..192]);
+        bytes
+    }
+
+    /// The number of bytes that the attribute occupies in a point record.
+    pub fn size(&self) -> usize {
+        match self.data_type {
+            ExtraByteType::Undocumented => self.options as usize,
+            _ => self.data_type.size() * self.num_values,
+        }
+    }
+
+    pub fn has_no_data(&self) -> bool {
+        self.options & 0b0000_0001u8 != 0
+    }
+
+    pub fn has_scale(&self) -> bool {
+        self.options & 0b0000_1000u8 != 0
+    }
+
+    pub fn has_offset(&self) -> bool {
+        self.options & 0b0001_0000u8 != 0
+    }
+
+    /// Sets the value, in the units of the stored (unscaled) values, that marks
+    /// a point for which the attribute is undefined.
+    pub fn set_no_data(&mut self, value: f64) {
+        self.no_data = [value; 3];
+        self.options |= 0b0000_0001u8;
+    }
+
+    /// Sets the scale factor and offset with which the stored values are converted
+    /// to the values of the attribute.
+    pub fn set_scale_and_offset(&mut self, scale: f64, offset: f64) {
+        self.scale = [scale; 3];
+        self.offset = [offset; 3];
+        self.options |= 0b0001_1000u8;
+    }
+
+    /// Returns the i'th value of the attribute from its bytes within a point
+    /// record, applying the scale factor and offset.
+    pub fn get_value(&self, bytes: &[u8], i: usize) -> f64 {
+        let size = self.data_type.size();
+        if size == 0 || bytes.len() < size * (i + 1) {
+            return f64::NAN;
+        }
+        let b = &bytes[i * size..(i + 1) * size];
+        let raw = match self.data_type {
+            ExtraByteType::U8 => b[0] as f64,
+            ExtraByteType::I8 => b[0] as i8 as f64,
+            ExtraByteType::U16 => LittleEndian::read_u16(b) as f64,
+            ExtraByteType::I16 => LittleEndian::read_i16(b) as f64,
+            ExtraByteType::U32 => LittleEndian::read_u32(b) as f64,
+            ExtraByteType::I32 => LittleEndian::read_i32(b) as f64,
+            ExtraByteType::U64 => LittleEndian::read_u64(b) as f64,
+            ExtraByteType::I64 => LittleEndian::read_i64(b) as f64,
+            ExtraByteType::F32 => LittleEndian::read_f32(b) as f64,
+            _ => LittleEndian::read_f64(b),
+        };
+        let scale = if self.has_scale() { self.scale[i] } else { 1f64 };
+        let offset = if self.has_offset() { self.offset[i] } else { 0f64 };
+        raw * scale + offset
+    }
+
+    /// Stores the i'th value of the attribute in its bytes within a point record,
+    /// removing the scale factor and offset. Integer values are rounded and
+    /// clamped to the range of the data type.
+    pub fn set_value(&self, bytes: &mut [u8], i: usize, value: f64) {
+        let scale = if self.has_scale() && self.scale[i] != 0f64 { self.scale[i] } else { 1f64 };
+        let offset = if self.has_offset() { self.offset[i] } else { 0f64 };
+        self.write_raw((value - offset) / scale, bytes, i);
+    }
+
+    /// Fills the bytes of the attribute within a point record with its no-data
+    /// value, or zeros if it has none.
+    pub fn set_no_data_value(&self, bytes: &mut [u8]) {
+        for i in 0..self.num_values {
+            let value = if self.has_no_data() { self.no_data[i] } else { 0f64 };
+            self.write_raw(value, bytes, i);
+        }
+    }
+
+    fn write_raw(&self, raw: f64, bytes: &mut [u8], i: usize) {
+        let size = self.data_type.size();
+        if size == 0 || bytes.len() < size * (i + 1) {
+            return;
+        }
+        let b = &mut bytes[i * size..(i + 1) * size];
+        let (min, max) = self.data_type.range();
+        let v = if self.data_type.is_float() { raw } else { raw.round().max(min).min(max) };
+        match self.data_type {
+            ExtraByteType::U8 => b[0] = v as u8,
+            ExtraByteType::I8 => b[0] = v as i8 as u8,
+            ExtraByteType::U16 => LittleEndian::write_u16(b, v as u16),
+            ExtraByteType::I16 => LittleEndian::write_i16(b, v as i16),
+            ExtraByteType::U32 => LittleEndian::write_u32(b, v as u32),
+            ExtraByteType::I32 => LittleEndian::write_i32(b, v as i32),
+            ExtraByteType::U64 => LittleEndian::write_u64(b, v as u64),
+            ExtraByteType::I64 => LittleEndian::write_i64(b, v as i64),
+            ExtraByteType::F32 => LittleEndian::write_f32(b, v as f32),
+            _ => LittleEndian::write_f64(b, v),
+        }
+    }
+
+    // The no-data, min and max fields are 8-byte values of the widest type of
+    // the same kind as the data type.
+    fn read_any(&self, b: &[u8]) -> f64 {
+        if self.data_type.is_float() {
+            LittleEndian::read_f64(b)
+        } else if self.data_type.is_signed() {
+            LittleEndian::read_i64(b) as f64
+        } else {
+            LittleEndian::read_u64(b) as f64
+        }
+    }
+
+    fn write_any(&self, value: f64, b: &mut [u8]) {
+        if self.data_type.is_float() {
+            LittleEndian::write_f64(b, value);
+        } else if self.data_type.is_signed() {
+            LittleEndian::write_i64(b, value.round() as i64);
+        } else {
+            LittleEndian::write_u64(b, value.round().max(0f64) as u64);
+        }
+    }
+}
+
+impl fmt::Display for ExtraBytesDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.data_type {
+            ExtraByteType::Undocumented => write!(f, "{} undocumented bytes", self.options),
+            _ => write!(f, "{} ({:?}{}): {}", self.name, self.data_type,
+                if self.num_values > 1 { format!("[{}]", self.num_values) } else { "".to_string() },
+                self.description),
+        }
+    }
+}
+
+/// Returns true if the VLR is the Extra Bytes VLR.
+pub fn is_extra_bytes_vlr(vlr: &Vlr) -> bool {
+    vlr.user_id.trim_matches('\0') == "LASF_Spec" && vlr.record_id == EXTRA_BYTES_RECORD_ID
+}
+
+/// Reads the extra bytes descriptors held in the Extra Bytes VLR.
+pub fn read_extra_bytes_descriptors(vlr: &Vlr) -> Result<Vec<ExtraBytesDescriptor>, Error> {
+    let mut descriptors = vec![];
+    for bytes in vlr.binary_data.chunks(192) {
+        descriptors.push(ExtraBytesDescriptor::from_bytes(bytes)?);
+    }
+    Ok(descriptors)
+}
+
+/// Creates the Extra Bytes VLR holding a set of descriptors.
+pub fn create_extra_bytes_vlr(descriptors: &[ExtraBytesDescriptor]) -> Vlr {
+    let mut vlr: Vlr = Default::default();
+    vlr.user_id = "LASF_Spec".to_string();
+    vlr.record_id = EXTRA_BYTES_RECORD_ID;
+    vlr.description = "Extra Bytes Record".to_string();
+    for descriptor in descriptors {
+        vlr.binary_data.extend_from_slice(&descriptor.to_bytes());
+    }
+    vlr.record_length_after_header = vlr.binary_data.len() as u64;
+    vlr
+}
+
+/// Returns the extra bytes of a point whose attributes all have their no-data
+/// values, or zero for those without one.
+pub fn no_data_extra_bytes(descriptors: &[ExtraBytesDescriptor], num_extra_bytes: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; num_extra_bytes];
+    let mut offset = 0usize;
+    for descriptor in descriptors {
+        if offset + descriptor.size() > num_extra_bytes {
+            break;
+        }
+        descriptor.set_no_data_value(&mut bytes[offset..offset + descriptor.size()]);
+        offset += descriptor.size();
+    }
+    bytes
+}
+
+fn fixed_length_str(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).trim_matches('\0').trim().to_string()
+}
+
+fn copy_str(s: &str, bytes: &mut [u8]) {
+    for (b, c) in bytes.iter_mut().zip(s.bytes()) {
+        *b = c;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lidar::header::LasHeader;
+    use lidar::las::{LasFile, LidarPointRecord};
+    use lidar::point_data::PointData;
+    use std::env;
+    use std::fs;
+    use std::io::ErrorKind;
+
+    const DATA_TYPES: [ExtraByteType; 10] = [ExtraByteType::U8, ExtraByteType::I8, ExtraByteType::U16,
+        ExtraByteType::I16, ExtraByteType::U32, ExtraByteType::I32, ExtraByteType::U64, ExtraByteType::I64,
+        ExtraByteType::F32, ExtraByteType::F64];
+
+    // a descriptor with a scale factor, offset and no-data value suited to its type
+    fn descriptor(data_type: ExtraByteType) -> ExtraBytesDescriptor {
+        let mut descriptor = ExtraBytesDescriptor::new(&format!("{:?}Value", data_type), data_type, &format!("A {:?} attribute", data_type));
+        let no_data = match data_type {
+            ExtraByteType::U8 => 255f64,
+            ExtraByteType::U16 => 65_535f64,
+            ExtraByteType::U32 | ExtraByteType::U64 => 4_294_967_295f64,
+            ExtraByteType::F32 | ExtraByteType::F64 => -9_999.5f64,
+            _ => -100f64,
+        };
+        descriptor.set_no_data(no_data);
+        descriptor.set_scale_and_offset(0.25, -10.0);
+        descriptor
+    }
+
+    #[test]
+    fn test_descriptor_round_trip() {
+        for data_type in DATA_TYPES.iter() {
+            let descriptor = descriptor(*data_type);
+            let bytes = descriptor.to_bytes();
+            assert_eq!(bytes.len(), 192);
+            let input = ExtraBytesDescriptor::from_bytes(&bytes).unwrap();
+            assert_eq!(input, descriptor);
+            assert!(input.has_no_data() && input.has_scale() && input.has_offset());
+            assert_eq!(input.size(), data_type.size());
+        }
+    }
+
+    #[test]
+    fn test_vlr_round_trip() {
+        let mut descriptors: Vec<ExtraBytesDescriptor> = DATA_TYPES.iter().map(|data_type| descriptor(*data_type)).collect();
+        descriptors.push(ExtraBytesDescriptor::undocumented(3));
+        let mut array = ExtraBytesDescriptor::new("Array", ExtraByteType::I16, "");
+        array.num_values = 3;
+        descriptors.push(array);
+
+        let vlr = create_extra_bytes_vlr(&descriptors);
+        assert!(is_extra_bytes_vlr(&vlr));
+        assert_eq!(vlr.record_length_after_header, 192 * descriptors.len() as u64);
+        let input = read_extra_bytes_descriptors(&vlr).unwrap();
+        assert_eq!(input, descriptors);
+        assert_eq!(input[10].size(), 3);
+        assert_eq!(input[11].size(), 6);
+    }
+
+    #[test]
+    fn test_unsupported_data_type() {
+        let mut bytes = ExtraBytesDescriptor::new("Bad", ExtraByteType::U8, "").to_bytes();
+        bytes[2] = 31;
+        assert!(ExtraBytesDescriptor::from_bytes(&bytes).is_err());
+        assert!(ExtraBytesDescriptor::from_bytes(&bytes[0..100]).is_err());
+    }
+
+    #[test]
+    fn test_values() {
+        for data_type in DATA_TYPES.iter() {
+            let descriptor = descriptor(*data_type);
+            let mut bytes = vec![0u8; descriptor.size()];
+            // a stored value of 20 is 20 * 0.25 - 10
+            descriptor.set_value(&mut bytes, 0, -5.0);
+            assert_eq!(descriptor.get_value(&bytes, 0), -5.0);
+            descriptor.set_no_data_value(&mut bytes);
+            assert_eq!(descriptor.get_value(&bytes, 0), descriptor.no_data[0] * 0.25 - 10.0);
+        }
+        // integer values are rounded and clamped to the range of the type
+        let descriptor = ExtraBytesDescriptor::new("Byte", ExtraByteType::U8, "");
+        let mut bytes = vec![0u8];
+        descriptor.set_value(&mut bytes, 0, 7.6);
+        assert_eq!(bytes[0], 8);
+        descriptor.set_value(&mut bytes, 0, 300.0);
+        assert_eq!(bytes[0], 255);
+        descriptor.set_value(&mut bytes, 0, -3.0);
+        assert_eq!(bytes[0], 0);
+    }
+
+    #[test]
+    fn test_no_data_extra_bytes() {
+        let descriptors = vec![descriptor(ExtraByteType::I16), ExtraBytesDescriptor::new("Zero", ExtraByteType::U8, "")];
+        // the undescribed byte that follows the attributes is zero
+        assert_eq!(no_data_extra_bytes(&descriptors, 4), vec![0x9c, 0xff, 0, 0]);
+    }
+
+    #[test]
+    fn test_las_file_round_trip() {
+        let file_name = env::temp_dir().join("wbt_extra_bytes_test.las").to_str().unwrap().to_string();
+        let mut output = LasFile::new(&file_name, "w").unwrap();
+        output.add_header(LasHeader { project_id_used: true, point_format: 1, ..Default::default() });
+        for data_type in DATA_TYPES.iter() {
+            output.add_extra_byte_attribute(descriptor(*data_type)).unwrap();
+        }
+        let e = output.add_extra_byte_attribute(descriptor(ExtraByteType::U8)).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::AlreadyExists);
+        // an existing attribute is kept, whatever its type
+        output.ensure_extra_byte_attribute(ExtraBytesDescriptor::new("U8Value", ExtraByteType::F64, "")).unwrap();
+        output.ensure_extra_byte_attribute(ExtraBytesDescriptor::new("Extra", ExtraByteType::F64, "")).unwrap();
+        assert_eq!(output.get_extra_byte_attributes().len(), 11);
+
+        for i in 0..3 {
+            let point_data = PointData { x: i as f64, y: 2.0, z: 3.0, ..Default::default() };
+            output.add_point_record(LidarPointRecord::PointRecord1 { point_data: point_data, gps_data: 0f64 });
+            if i > 0 {
+                for data_type in DATA_TYPES.iter() {
+                    output.set_extra_byte_value(i, &format!("{:?}Value", data_type), 10.0 * i as f64).unwrap();
+                }
+            }
+        }
+        output.write().unwrap();
+
+        let input = LasFile::new(&file_name, "r").unwrap();
+        fs::remove_file(&file_name).unwrap();
+        assert_eq!(input.get_extra_byte_attributes()[0..10], output.get_extra_byte_attributes()[0..10]);
+        for data_type in DATA_TYPES.iter() {
+            let name = format!("{:?}Value", data_type);
+            // the first point has the no-data value of each attribute
+            let no_data = descriptor(*data_type).no_data[0] * 0.25 - 10.0;
+            assert_eq!(input.get_extra_byte_value(0, &name).unwrap(), no_data);
+            assert_eq!(input.get_extra_byte_value(1, &name).unwrap(), 10.0);
+            assert_eq!(input.get_extra_byte_value(2, &name).unwrap(), 20.0);
+        }
+        assert_eq!(input.get_extra_byte_value(2, "Extra").unwrap(), 0.0);
+    }
+}
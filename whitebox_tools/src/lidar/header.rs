@@ -1,5 +1,6 @@
 use std::fmt;
 use lidar::las::GlobalEncodingField;
+use lidar::point_data::PointData;
 
 #[derive(Default, Clone, Debug)]
 pub struct LasHeader {
@@ -22,8 +23,11 @@ pub struct LasHeader {
     pub number_of_vlrs: u32,
     pub point_format: u8,
     pub point_record_length: u16,
-    pub number_of_points: u32,
-    pub number_of_points_by_return: [u32; 5],
+    /// The number of point records. The LAS 1.4 64-bit count is used for all
+    /// versions, and the legacy 32-bit fields are derived from it on writing.
+    pub number_of_points: u64,
+    /// The number of points by return, for up to the 15 returns of LAS 1.4.
+    pub number_of_points_by_return: [u64; 15],
     pub x_scale_factor: f64,
     pub y_scale_factor: f64,
     pub z_scale_factor: f64,
@@ -37,6 +41,30 @@ pub struct LasHeader {
     pub max_z: f64,
     pub min_z: f64,
     pub waveform_data_start: u64,
+    pub start_of_first_evlr: u64,
+    pub number_of_evlrs: u32,
+}
+
+impl LasHeader {
+    /// Updates the point count, the count by return and the bounds of the
+    /// header to include a point. Points with a return number of zero, which
+    /// are common in practice, are counted as first returns.
+    pub fn add_point(&mut self, point_data: &PointData) {
+        let (x, y, z) = (point_data.x, point_data.y, point_data.z);
+        if x < self.min_x { self.min_x = x; }
+        if x > self.max_x { self.max_x = x; }
+        if y < self.min_y { self.min_y = y; }
+        if y > self.max_y { self.max_y = y; }
+        if z < self.min_z { self.min_z = z; }
+        if z > self.max_z { self.max_z = z; }
+
+        self.number_of_points += 1;
+        let mut which_return = (point_data.bit_field.value & 0b0000_1111u16) as usize;
+        if which_return == 0 { which_return = 1; }
+        if which_return <= self.number_of_points_by_return.len() {
+            self.number_of_points_by_return[which_return - 1] += 1;
+        }
+    }
 }
 
 impl fmt::Display for LasHeader {
@@ -61,8 +89,10 @@ impl fmt::Display for LasHeader {
         s = s + &format!("\nPoint Record Length: {}", self.point_record_length);
         s = s + &format!("\nNum. of Points: {}", self.number_of_points);
         s = s + &"\nNumber of Points by Return: [";
-        for i in 0..self.number_of_points_by_return.len() {
-            if i < self.number_of_points_by_return.len()-1 {
+        // the legacy versions only record the first five returns
+        let num_returns = if self.version_major == 1 && self.version_minor < 4 { 5 } else { 15 };
+        for i in 0..num_returns {
+            if i < num_returns - 1 {
                 s = s + &format!("{}, ", self.number_of_points_by_return[i]);
             } else {
                 s = s + &format!("{}]", self.number_of_points_by_return[i]);
@@ -83,6 +113,10 @@ impl fmt::Display for LasHeader {
         s = s + &format!("\nMin Z: {}", self.min_z);
 
         s = s + &format!("\nWaveform Data Start: {}", self.waveform_data_start);
+        if self.version_major > 1 || self.version_minor >= 4 {
+            s = s + &format!("\nStart of First EVLR: {}", self.start_of_first_evlr);
+            s = s + &format!("\nNumber of EVLRs: {}", self.number_of_evlrs);
+        }
 
         write!(f, "{}", s)
     }
@@ -3,7 +3,7 @@ extern crate time;
 extern crate zip;
 
 use std::io::prelude::*;
-use std::io::{Error, ErrorKind};
+use std::io::{Cursor, Error, ErrorKind};
 use std::fmt;
 use std::f64;
 use std::io::BufWriter;
@@ -12,12 +12,20 @@ use std::fs;
 use std::mem;
 use std::path::Path;
 use std::str;
+use lidar::extra_bytes::{ExtraBytesDescriptor, ExtraByteType, create_extra_bytes_vlr, is_extra_bytes_vlr, no_data_extra_bytes, read_extra_bytes_descriptors};
 use lidar::header::LasHeader;
+use lidar::las_index::LasIndex;
+use lidar::las_stream::LasReader;
+use lidar::laszip::{LasZipCompressor, LasZipDecompressor, is_laszip_vlr};
 use lidar::point_data::{ ClassificationBitField, PointBitField, PointData, RgbData, WaveformPacket };
 use lidar::vlr::Vlr;
 use raster::geotiff::geokeys::GeoKeys;
+use structures::{BoundingBox, Point2D};
+use algorithms::point_in_poly;
 use std::ops::Index;
 use std::io::Seek;
+use io_utils::{ByteOrderReader, Endianness};
+use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
 use self::zip::result::ZipResult;
 use self::zip::CompressionMethod;
 use self::zip::read::{ ZipArchive, ZipFile };
@@ -30,11 +38,17 @@ pub struct LasFile {
     file_mode: String,
     pub header: LasHeader,
     pub vlr_data: Vec<Vlr>,
+    pub evlr_data: Vec<Vlr>,
     point_data: Vec<PointData>,
     // point_buffer_size: usize,
     gps_data: Vec<f64>,
     rgb_data: Vec<RgbData>,
     waveform_data: Vec<WaveformPacket>,
+    // the extra bytes of the points, num_extra_bytes for each, and the attributes
+    // that they contain
+    extra_byte_data: Vec<u8>,
+    num_extra_bytes: usize,
+    extra_bytes: Vec<ExtraBytesDescriptor>,
     pub geokeys: GeoKeys,
     // starting_point: usize,
     header_is_set: bool,
@@ -73,7 +87,7 @@ impl LasFile {
     /// * input: An existing LAS file.
     ///
     /// Output:
-    /// * A LasFile struct, initialized with the header and VLRs of the input file.
+    /// * A LasFile struct, initialized with the header, VLRs, EVLRs and extra bytes attributes of the input file.
     pub fn initialize_using_file<'a>(file_name: &'a str, input: &'a LasFile) -> LasFile {
         let mut output = LasFile { file_name: file_name.to_string(), ..Default::default() };
         output.file_mode = "w".to_string();
@@ -86,6 +100,11 @@ impl LasFile {
         for i in 0..(input.header.number_of_vlrs as usize) {
             output.add_vlr(input.vlr_data[i].clone());
         }
+        for evlr in &input.evlr_data {
+            output.add_evlr(evlr.clone());
+        }
+        // including any extra bytes that the Extra Bytes VLR does not describe
+        output.set_num_extra_bytes(input.num_extra_bytes);
 
         output
     }
@@ -95,6 +114,7 @@ impl LasFile {
         self.header = header;
 
         self.header.number_of_vlrs = 0;
+        self.header.number_of_evlrs = 0;
         self.header.number_of_points = 0;
 
         self.header.version_major = 1;
@@ -109,7 +129,7 @@ impl LasFile {
 
 		self.header.system_id = "WhiteboxTools by John Lindsay   ".to_string();
 		self.header.generating_software = "WhiteboxTools                   ".to_string();
-		self.header.number_of_points_by_return = [0, 0, 0, 0, 0];
+		self.header.number_of_points_by_return = [0; 15];
 
 		self.header.x_scale_factor = 0.0001;
 		self.header.y_scale_factor = 0.0001;
@@ -124,91 +144,164 @@ impl LasFile {
         if !self.header_is_set {
             panic!("The header of a LAS file must be added before any VLRs. Please see add_header().");
         }
+        if is_extra_bytes_vlr(&vlr) {
+            let descriptors = read_extra_bytes_descriptors(&vlr).unwrap_or(vec![]);
+            let num_extra_bytes = descriptors.iter().map(|eb| eb.size()).sum();
+            self.extra_bytes = descriptors;
+            self.set_num_extra_bytes(num_extra_bytes);
+        }
         self.vlr_data.push(vlr);
         self.header.number_of_vlrs += 1;
     }
 
+    /// Adds an extended VLR, which is written after the point records. Files
+    /// containing EVLRs are written as LAS 1.4.
+    pub fn add_evlr(&mut self, evlr: Vlr) {
+        if self.file_mode == "r" { return; }
+        if !self.header_is_set {
+            panic!("The header of a LAS file must be added before any EVLRs. Please see add_header().");
+        }
+        self.evlr_data.push(evlr);
+        self.header.number_of_evlrs += 1;
+    }
+
+    /// Adds a point record. Its extra bytes attributes, if the file has any, are
+    /// set to their no-data values, or zero for those without one.
     pub fn add_point_record(&mut self, point: LidarPointRecord) {
+        let extra_bytes = no_data_extra_bytes(&self.extra_bytes, self.num_extra_bytes);
+        self.add_point_record_with_extra_bytes(point, &extra_bytes);
+    }
+
+    /// Adds a point record along with its extra bytes, e.g. those of a point read
+    /// from a file with the same extra bytes attributes.
+    pub fn add_point_record_with_extra_bytes(&mut self, point: LidarPointRecord, extra_bytes: &[u8]) {
         if self.file_mode == "r" { return; }
         if !self.header_is_set {
             panic!("The header of a LAS file must be added before any point records. Please see add_header().");
         }
-        let mut which_return = 0_usize;
-        let x: f64;
-        let y: f64;
-        let z: f64;
-        match point {
-            LidarPointRecord::PointRecord0 { point_data }  => {
-                self.point_data.push(point_data);
-                x = point_data.x;
-                y = point_data.y;
-                z = point_data.z;
-                which_return = point_data.bit_field.return_number() as usize;
-            },
-            LidarPointRecord::PointRecord1 { point_data, gps_data } => {
-                self.point_data.push(point_data);
-                x = point_data.x;
-                y = point_data.y;
-                z = point_data.z;
-                which_return = point_data.bit_field.return_number() as usize;
-                self.gps_data.push(gps_data);
-            },
-            LidarPointRecord::PointRecord2 { point_data, rgb_data } => {
-                self.point_data.push(point_data);
-                x = point_data.x;
-                y = point_data.y;
-                z = point_data.z;
-                which_return = point_data.bit_field.return_number() as usize;
-                self.rgb_data.push(rgb_data);
-            },
-            LidarPointRecord::PointRecord3 { point_data, gps_data, rgb_data } => {
-                self.point_data.push(point_data);
-                x = point_data.x;
-                y = point_data.y;
-                z = point_data.z;
-                which_return = point_data.bit_field.return_number() as usize;
-                self.gps_data.push(gps_data);
-                self.rgb_data.push(rgb_data);
-            },
+        self.push_point_record(point, extra_bytes);
+    }
+
+    // Stores a point record and updates the point counts and bounds of the header.
+    fn push_point_record(&mut self, point: LidarPointRecord, extra_bytes: &[u8]) {
+        if extra_bytes.len() >= self.num_extra_bytes {
+            self.extra_byte_data.extend_from_slice(&extra_bytes[0..self.num_extra_bytes]);
+        } else {
+            let no_data = no_data_extra_bytes(&self.extra_bytes, self.num_extra_bytes);
+            self.extra_byte_data.extend_from_slice(extra_bytes);
+            self.extra_byte_data.extend_from_slice(&no_data[extra_bytes.len()..]);
         }
+        let point_data = point.get_point_data();
+        self.point_data.push(point_data);
+        if let Some(gps_data) = point.get_gps_time() {
+            self.gps_data.push(gps_data);
+        }
+        if let Some(rgb_data) = point.get_rgb() {
+            self.rgb_data.push(rgb_data);
+        }
+        if let Some(wave_packet) = point.get_waveform_packet() {
+            self.waveform_data.push(wave_packet);
+        }
+        self.header.add_point(&point_data);
+    }
 
-        if x < self.header.min_x { self.header.min_x = x; }
-        if x > self.header.max_x { self.header.max_x = x; }
-        if y < self.header.min_y { self.header.min_y = y; }
-        if y > self.header.max_y { self.header.max_y = y; }
-        if z < self.header.min_z { self.header.min_z = z; }
-        if z > self.header.max_z { self.header.max_z = z; }
+    /// Reads the points of a LAS file that lie within a bounding box. If the file
+    /// has an up-to-date spatial index (see LasIndex), only the points of the
+    /// index cells overlapping the box are read; otherwise all of the points are
+    /// read, as a stream, and filtered. The header of the returned read-only file
+    /// describes the points within the box.
+    pub fn read_within_box(file_name: &str, bb: &BoundingBox) -> Result<LasFile, Error> {
+        LasFile::read_within(file_name, bb, |_, _| true)
+    }
 
-        self.header.number_of_points += 1;
-        if which_return == 0 { which_return = 1; }
-        if which_return <= 5 {
-            self.header.number_of_points_by_return[which_return-1] += 1;
+    /// Reads the points of a LAS file that lie within a polygon, in the same way as
+    /// read_within_box. The polygon may have several rings, e.g. holes; a point is
+    /// within it if it lies within an odd number of the rings.
+    pub fn read_within_polygon(file_name: &str, rings: &[Vec<Point2D>]) -> Result<LasFile, Error> {
+        let mut points = vec![];
+        for ring in rings {
+            points.extend_from_slice(ring);
         }
+        let bb = BoundingBox::from_points(&points);
+        LasFile::read_within(file_name, &bb, |x, y| {
+            let p = Point2D::new(x, y);
+            rings.iter().filter(|ring| point_in_poly(&p, ring)).count() % 2 == 1
+        })
     }
 
-    pub fn get_record(&self, index: usize) -> LidarPointRecord {
-        let lpr: LidarPointRecord;
-        match self.header.point_format {
-            0 => {
-                lpr = LidarPointRecord::PointRecord0 { point_data: self.point_data[index] };
-            },
-            1 => {
-                lpr = LidarPointRecord::PointRecord1 { point_data: self.point_data[index],
-                    gps_data: self.gps_data[index] };
-            },
-            2 => {
-                lpr = LidarPointRecord::PointRecord2 { point_data: self.point_data[index],
-                    rgb_data: self.rgb_data[index] };
-            },
-            3 => {
-                lpr = LidarPointRecord::PointRecord3 { point_data: self.point_data[index],
-                    gps_data: self.gps_data[index], rgb_data: self.rgb_data[index] };
-            },
-            _ => {
-                panic!("Unsupported point format");
-            },
+    /// Reads the points of a LAS file that lie within a bounding box and for whose
+    /// coordinates the is_within function returns true, in the same way as
+    /// read_within_box.
+    pub fn read_within<F: Fn(f64, f64) -> bool>(file_name: &str, bb: &BoundingBox, is_within: F) -> Result<LasFile, Error> {
+        let mut reader = LasReader::new(file_name)?;
+        let intervals = match LasIndex::read(file_name, &reader.header) {
+            Ok(index) => index.query(bb),
+            Err(_) => vec![(0, reader.header.number_of_points)],
+        };
+
+        let mut lf = LasFile { file_name: file_name.to_string(), file_mode: "r".to_string(), ..Default::default() };
+        lf.header = reader.header.clone();
+        lf.vlr_data = reader.vlr_data.clone();
+        lf.evlr_data = reader.evlr_data.clone();
+        lf.geokeys = reader.geokeys.clone();
+        lf.extra_bytes = reader.get_extra_byte_attributes().to_vec();
+        let (use_point_intensity, use_point_userdata) = optional_point_fields(&lf.header, &lf.extra_bytes);
+        lf.num_extra_bytes = num_extra_bytes(&lf.header, use_point_intensity, use_point_userdata);
+        lf.use_point_intensity = true;
+        lf.use_point_userdata = true;
+
+        lf.header.number_of_points = 0;
+        lf.header.number_of_points_by_return = [0; 15];
+        lf.header.min_x = f64::INFINITY;
+        lf.header.max_x = f64::NEG_INFINITY;
+        lf.header.min_y = f64::INFINITY;
+        lf.header.max_y = f64::NEG_INFINITY;
+        lf.header.min_z = f64::INFINITY;
+        lf.header.max_z = f64::NEG_INFINITY;
+        for (start, end) in intervals {
+            reader.seek_range(start, end)?;
+            while let Some(record) = reader.next() {
+                let record = record?;
+                let p = record.get_point_data();
+                if bb.contains_point(p.x, p.y) && is_within(p.x, p.y) {
+                    lf.push_point_record(record, reader.get_extra_bytes());
+                }
+            }
+        }
+        Ok(lf)
+    }
+
+    /// Appends the points of another LAS file, e.g. the buffer points read from a
+    /// neighbouring tile, converting them to the point format of this file. Their
+    /// extra bytes are kept if the two files share the same extra bytes layout and
+    /// are otherwise set to their no-data values. Unlike add_point_record, this
+    /// may be used with files opened for reading, whose points are held in memory.
+    pub fn append_points(&mut self, other: &LasFile) {
+        let format = self.header.point_format;
+        let same_extra_bytes = self.num_extra_bytes == other.num_extra_bytes && self.extra_bytes == other.extra_bytes;
+        let no_data = no_data_extra_bytes(&self.extra_bytes, self.num_extra_bytes);
+        for i in 0..other.point_data.len() {
+            let record = other.get_record(i);
+            let record = if record.get_format() == format {
+                record
+            } else {
+                create_point_record(format, record.get_point_data(), record.get_gps_time().unwrap_or(0f64),
+                    record.get_rgb().unwrap_or_default(), record.get_waveform_packet().unwrap_or_default())
+            };
+            if same_extra_bytes {
+                self.push_point_record(record, other.get_extra_bytes(i));
+            } else {
+                self.push_point_record(record, &no_data);
+            }
         }
-        lpr
+    }
+
+    pub fn get_record(&self, index: usize) -> LidarPointRecord {
+        let format = self.header.point_format;
+        let gps_data = if has_gps_time(format) { self.gps_data[index] } else { 0f64 };
+        let rgb_data = if has_rgb(format) { self.rgb_data[index] } else { Default::default() };
+        let wave_packet = if has_waveform(format) { self.waveform_data[index] } else { Default::default() };
+        create_point_record(format, self.point_data[index], gps_data, rgb_data, wave_packet)
     }
 
     pub fn get_point_info(&self, index: usize) -> PointData {
@@ -216,7 +309,7 @@ impl LasFile {
     }
 
     pub fn get_rgb(&self, index: usize) -> Result<RgbData, Error> {
-        if self.rgb_data.len() >= index {
+        if index < self.rgb_data.len() {
             return Ok(self.rgb_data[index]);
         } else {
             return Err(Error::new(ErrorKind::NotFound, "RGB value not found, possibly because the file point format does not include colour data."));
@@ -224,13 +317,132 @@ impl LasFile {
     }
 
     pub fn get_gps_time(&self, index: usize) -> Result<f64, Error> {
-        if self.gps_data.len() >= index {
+        if index < self.gps_data.len() {
             return Ok(self.gps_data[index]);
         } else {
             return Err(Error::new(ErrorKind::NotFound, "GPS time value not found, possibly because the file point format does not include GPS data."));
         }
     }
 
+    pub fn get_waveform_packet(&self, index: usize) -> Result<WaveformPacket, Error> {
+        if index < self.waveform_data.len() {
+            return Ok(self.waveform_data[index]);
+        } else {
+            return Err(Error::new(ErrorKind::NotFound, "Waveform packet not found, possibly because the file point format does not include waveform data."));
+        }
+    }
+
+    /// Returns the descriptors of the extra bytes attributes of the point records,
+    /// in the order in which they are stored.
+    pub fn get_extra_byte_attributes(&self) -> &[ExtraBytesDescriptor] {
+        &self.extra_bytes
+    }
+
+    /// Returns the extra bytes of a point record.
+    pub fn get_extra_bytes(&self, index: usize) -> &[u8] {
+        &self.extra_byte_data[index * self.num_extra_bytes..(index + 1) * self.num_extra_bytes]
+    }
+
+    /// Returns the value of a point's extra bytes attribute, with its scale factor
+    /// and offset applied. For the deprecated array types, it is the first value.
+    pub fn get_extra_byte_value(&self, index: usize, name: &str) -> Result<f64, Error> {
+        let (i, offset) = self.find_extra_byte_attribute(name)?;
+        let bytes = self.get_extra_bytes(index);
+        Ok(self.extra_bytes[i].get_value(&bytes[offset..], 0))
+    }
+
+    /// Sets the value of a point's extra bytes attribute.
+    pub fn set_extra_byte_value(&mut self, index: usize, name: &str, value: f64) -> Result<(), Error> {
+        let (i, offset) = self.find_extra_byte_attribute(name)?;
+        let start = index * self.num_extra_bytes + offset;
+        let end = start + self.extra_bytes[i].size();
+        self.extra_bytes[i].set_value(&mut self.extra_byte_data[start..end], 0, value);
+        Ok(())
+    }
+
+    /// Adds an extra bytes attribute unless the points already have an attribute of
+    /// the same name, e.g. because the input of a tool is one of its own outputs.
+    pub fn ensure_extra_byte_attribute(&mut self, descriptor: ExtraBytesDescriptor) -> Result<(), Error> {
+        match self.add_extra_byte_attribute(descriptor) {
+            Err(ref e) if e.kind() == ErrorKind::AlreadyExists => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Adds an extra bytes attribute, which follows any existing extra bytes of the
+    /// point records. Points that have already been added receive its no-data value.
+    pub fn add_extra_byte_attribute(&mut self, descriptor: ExtraBytesDescriptor) -> Result<(), Error> {
+        if self.file_mode == "r" {
+            return Err(Error::new(ErrorKind::Other, "The file was opened in read-only mode"));
+        }
+        if !self.header_is_set {
+            panic!("The header of a LAS file must be added before any extra bytes attributes. Please see add_header().");
+        }
+        if descriptor.data_type != ExtraByteType::Undocumented && self.find_extra_byte_attribute(&descriptor.name).is_ok() {
+            return Err(Error::new(ErrorKind::AlreadyExists, format!("The extra bytes attribute {} already exists.", descriptor.name)));
+        }
+        if self.num_extra_bytes + descriptor.size() + point_record_length(self.header.point_format) as usize > u16::max_value() as usize {
+            return Err(Error::new(ErrorKind::Other, "The point records are too long to add the extra bytes attribute."));
+        }
+        // Any extra bytes beyond those described must be described before the new
+        // attribute can follow them.
+        let mut described: usize = self.extra_bytes.iter().map(|eb| eb.size()).sum();
+        while described < self.num_extra_bytes {
+            let num_bytes = (self.num_extra_bytes - described).min(u8::max_value() as usize);
+            self.extra_bytes.push(ExtraBytesDescriptor::undocumented(num_bytes as u8));
+            described += num_bytes;
+        }
+        let num_extra_bytes = self.num_extra_bytes + descriptor.size();
+        self.extra_bytes.push(descriptor);
+        self.set_num_extra_bytes(num_extra_bytes);
+
+        // update the Extra Bytes VLR
+        let vlr = create_extra_bytes_vlr(&self.extra_bytes);
+        match self.vlr_data.iter().position(|vlr| is_extra_bytes_vlr(vlr)) {
+            Some(i) => self.vlr_data[i] = vlr,
+            None => {
+                self.vlr_data.push(vlr);
+                self.header.number_of_vlrs += 1;
+            },
+        }
+        Ok(())
+    }
+
+    // Returns the index of an extra bytes attribute and its position within the
+    // extra bytes of a point.
+    fn find_extra_byte_attribute(&self, name: &str) -> Result<(usize, usize), Error> {
+        let mut offset = 0usize;
+        for (i, eb) in self.extra_bytes.iter().enumerate() {
+            if eb.data_type != ExtraByteType::Undocumented && eb.name == name {
+                return Ok((i, offset));
+            }
+            offset += eb.size();
+        }
+        Err(Error::new(ErrorKind::NotFound, format!("The extra bytes attribute {} was not found.", name)))
+    }
+
+    // Changes the number of extra bytes of each point, padding those of existing
+    // points with no-data values.
+    fn set_num_extra_bytes(&mut self, num_extra_bytes: usize) {
+        let old = self.num_extra_bytes;
+        self.num_extra_bytes = num_extra_bytes;
+        if old == num_extra_bytes || self.point_data.is_empty() {
+            return;
+        }
+        let no_data = no_data_extra_bytes(&self.extra_bytes, self.num_extra_bytes);
+        let mut data = Vec::with_capacity(self.point_data.len() * num_extra_bytes);
+        for i in 0..self.point_data.len() {
+            let bytes = &self.extra_byte_data[i * old..(i + 1) * old];
+            if num_extra_bytes <= old {
+                data.extend_from_slice(&bytes[0..num_extra_bytes]);
+            } else {
+                data.extend_from_slice(bytes);
+                data.extend_from_slice(&no_data[old..]);
+            }
+        }
+        self.extra_byte_data = data;
+    }
+
     pub fn read(&mut self) -> Result<(), Error> {
 
         let buffer = match self.file_name.to_lowercase().ends_with(".zip") {
@@ -269,248 +481,86 @@ impl LasFile {
             },
         };
 
-        self.header.project_id_used = true;
-        self.header.version_major = buffer[24];
-        self.header.version_minor = buffer[25];
-        if self.header.version_major < 1 || self.header.version_major > 2 || self.header.version_minor > 5 {
-            // There's something wrong. It could be that the project ID values are not included in the header.
-            self.header.version_major = buffer[8];
-            self.header.version_minor = buffer[9];
-            if self.header.version_major < 1 || self.header.version_major > 2 || self.header.version_minor > 5 {
-                // There's something very wrong. Throw an error.
-                return Err(Error::new(ErrorKind::Other, "Either the file is formatted incorrectly or it is an unsupported LAS version."));
-            }
-            self.header.project_id_used = false;
-        }
-        unsafe {
-
-            //////////////////////////
-            // Read the File Header //
-            //////////////////////////
-            let mut offset: usize = 0;
-            self.header.file_signature = String::from_utf8_lossy(&buffer[offset..offset+4]).to_string();
-            if self.header.file_signature != "LASF" {
-                return Err(Error::new(ErrorKind::Other, "Either the file is formatted incorrectly or it is an unsupported LAS version."));
-            }
-            offset += 4;
-            self.header.file_source_id = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-            offset += 2;
-            let ge_val = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-            self.header.global_encoding = GlobalEncodingField { value: ge_val};
-            offset += 2;
-            if self.header.project_id_used {
-                self.header.project_id1 = mem::transmute::<[u8; 4], u32>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3]]);
-                offset += 4;
-                self.header.project_id2 = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                offset += 2;
-                self.header.project_id3 = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                offset += 2;
-                for i in 0..8 {
-                    self.header.project_id4[i] = buffer[offset + i];
-                }
-                offset += 8;
-            }
-            // The version major and minor are read earlier.
-            // Two bytes that must be added to the offset here.
-            offset += 2;
-            //self.header.project_id4 = String::from_utf8_lossy(&buffer[16..24]).trim().to_string();
-            self.header.system_id = String::from_utf8_lossy(&buffer[offset..offset+32]).trim().to_string();
-            offset += 32;
-            self.header.generating_software = String::from_utf8_lossy(&buffer[offset..offset+32]).trim().to_string();
-            offset += 32;
-            // self.header.system_id = String::from_utf8_lossy(&buffer[26..58]).trim().to_string();
-            // self.header.generating_software = String::from_utf8_lossy(&buffer[58..90]).trim().to_string();
-            self.header.file_creation_day = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-            offset += 2;
-            self.header.file_creation_year = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-            offset += 2;
-            self.header.header_size = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-            offset += 2;
-            self.header.offset_to_points = mem::transmute::<[u8; 4], u32>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3]]);
-            offset += 4;
-            self.header.number_of_vlrs = mem::transmute::<[u8; 4], u32>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3]]);
-            offset += 4;
-            self.header.point_format = buffer[offset];
-            offset += 1;
-            self.header.point_record_length = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-            offset += 2;
-            self.header.number_of_points = mem::transmute::<[u8; 4], u32>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3]]);
-            offset += 4;
-
-            // let mut num_returns = 5;
-            // if self.header.version_major == 1_u8 && self.header.version_minor > 3_u8 {
-            //     num_returns = 7;
-            // }
-            // offset = 111;
-            for i in 0..5 {
-                self.header.number_of_points_by_return[i] = mem::transmute::<[u8; 4], u32>([buffer[offset + i * 4], buffer[offset + i * 4 + 1], buffer[offset + i * 4 + 2], buffer[offset + i * 4 + 3]]);
-                // self.header.number_of_points_by_return.push(mem::transmute::<[u8; 4], u32>([buffer[offset + i * 4], buffer[offset + i * 4 + 1], buffer[offset + i * 4 + 2], buffer[offset + i * 4 + 3]]));
-            }
-            offset += 5 * 4;
-            self.header.x_scale_factor = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            offset += 8;
-            self.header.y_scale_factor = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            offset += 8;
-            self.header.z_scale_factor = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            offset += 8;
-            self.header.x_offset = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            offset += 8;
-            self.header.y_offset = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            offset += 8;
-            self.header.z_offset = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            offset += 8;
-            self.header.max_x = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            offset += 8;
-            self.header.min_x = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            offset += 8;
-            self.header.max_y = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            offset += 8;
-            self.header.min_y = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            offset += 8;
-            self.header.max_z = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            offset += 8;
-            self.header.min_z = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-
-            if self.header.version_major == 1 && self.header.version_minor == 3 {
-                offset += 8;
-                self.header.waveform_data_start = mem::transmute::<[u8; 8], u64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
-            }
-
-            ///////////////////////
-            // Read the VLR data //
-            ///////////////////////
-            offset = self.header.header_size as usize;
-            //self.vlr_data = vec![Vlr{0'u16, "".to_string(), 0'u16, 0'u16, "".to_string()}; self.header.number_of_vlrs as usize];
-            for _ in 0..self.header.number_of_vlrs {
-                let mut vlr: Vlr = Default::default();
-                vlr.reserved = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                offset += 2;
-                vlr.user_id = String::from_utf8_lossy(&buffer[offset..offset+16]).trim().to_string();
-                offset += 16;
-                vlr.record_id = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                offset += 2;
-                vlr.record_length_after_header = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                offset += 2;
-                vlr.description = String::from_utf8_lossy(&buffer[offset..offset+32]).trim().to_string();
-                offset += 32;
-                // get the byte data
-                for i in 0..vlr.record_length_after_header {
-                    vlr.binary_data.push(buffer[offset + i as usize]);
-                }
-                offset += vlr.record_length_after_header as usize;
-                
-                if vlr.record_id == 34_735 {
-                    self.geokeys.add_key_directory(&vlr.binary_data);
-                } else if vlr.record_id == 34_736 {
-                    self.geokeys.add_double_params(&vlr.binary_data);
-                } else if vlr.record_id == 34_737 {
-                    self.geokeys.add_ascii_params(&vlr.binary_data);
-                }
-                self.vlr_data.push(vlr);
-            }
-
-            if self.file_mode != "rh" { // file_mode = "rh" does not read points, only the header.
-                /////////////////////////
-                // Read the point data //
-                /////////////////////////
-                // self.starting_point = 0;
-                // let _ = self.read_points().unwrap();
-
-                // Intensity and userdata are both optional. Figure out if they need to be read.
-                // The only way to do this is to compare the point record length by point format
-                let rec_lengths = [ [20_u16, 18_u16, 19_u16, 17_u16],
-                                    [28_u16, 26_u16, 27_u16, 25_u16],
-                                    [26_u16, 24_u16, 25_u16, 23_u16],
-                                    [34_u16, 32_u16, 33_u16, 31_u16] ];
-
-                if self.header.point_record_length == rec_lengths[self.header.point_format as usize][0] {
-                    self.use_point_intensity = true;
-                    self.use_point_userdata = true;
-                } else if self.header.point_record_length == rec_lengths[self.header.point_format as usize][1] {
-                    self.use_point_intensity = false;
-                    self.use_point_userdata = true;
-                } else if self.header.point_record_length == rec_lengths[self.header.point_format as usize][2] {
-                    self.use_point_intensity = true;
-                    self.use_point_userdata = false;
-                } else if self.header.point_record_length == rec_lengths[self.header.point_format as usize][3] {
-                    self.use_point_intensity = false;
-                    self.use_point_userdata = false;
-                }
-
-                for i in 0..self.header.number_of_points {
-                    offset = (self.header.offset_to_points + (i as u32) * (self.header.point_record_length as u32)) as usize;
-                    let mut p: PointData = Default::default();
-                    p.x = ((mem::transmute::<[u8; 4], i32>([buffer[offset], buffer[offset+1], buffer[offset+2],
-                        buffer[offset+3]])) as f64) * self.header.x_scale_factor + self.header.x_offset;
-                    offset += 4;
-                    p.y = ((mem::transmute::<[u8; 4], i32>([buffer[offset], buffer[offset+1], buffer[offset+2],
-                        buffer[offset+3]])) as f64) * self.header.y_scale_factor + self.header.y_offset;
-                    offset += 4;
-                    p.z = ((mem::transmute::<[u8; 4], i32>([buffer[offset], buffer[offset+1], buffer[offset+2],
-                        buffer[offset+3]])) as f64) * self.header.z_scale_factor + self.header.z_offset;
-                    offset += 4;
-                    if self.use_point_intensity {
-                        p.intensity = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                        offset += 2;
-                    }
-                    p.bit_field =  PointBitField { value: buffer[offset] };
-                    offset += 1;
-                    p.class_bit_field = ClassificationBitField { value: buffer[offset] };
-                    offset += 1;
-                    p.scan_angle = mem::transmute::<[u8; 1], i8>([buffer[offset]]);
-                    offset += 1;
-                    if self.use_point_userdata {
-                        p.user_data = buffer[offset];
-                        offset += 1;
-                    }
-                    p.point_source_id = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                    self.point_data.push(p);
-                }
-
-                if self.header.point_format == 1 {
-                    // read the GPS data
-                    for i in 0..self.header.number_of_points {
-                        offset = (self.header.offset_to_points + 20u32 + (i as u32) * (self.header.point_record_length as u32)) as usize;
-                        self.gps_data.push(mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]));
+        read_header(&mut self.header, &buffer)?;
+        let laszip_vlr = read_vlrs(&mut self.header, &buffer, &mut self.vlr_data, &mut self.geokeys)?;
+        self.extra_bytes = find_extra_bytes_descriptors(&self.vlr_data);
+        let mut bor = ByteOrderReader::new(buffer, Endianness::LittleEndian);
+
+        if self.file_mode != "rh" { // file_mode = "rh" does not read points, only the header.
+            /////////////////////////
+            // Read the point data //
+            /////////////////////////
+            let (use_point_intensity, use_point_userdata) = optional_point_fields(&self.header, &self.extra_bytes);
+            self.use_point_intensity = use_point_intensity;
+            self.use_point_userdata = use_point_userdata;
+            self.num_extra_bytes = num_extra_bytes(&self.header, use_point_intensity, use_point_userdata);
+            let mut described = 0usize;
+            let num_extra_bytes = self.num_extra_bytes;
+            self.extra_bytes.retain(|eb| { described += eb.size(); described <= num_extra_bytes });
+
+            let num_points = self.header.number_of_points as usize;
+            let record_length = self.header.point_record_length as usize;
+
+            // LAZ points are decompressed into a buffer of point records
+            let mut decompressed;
+            let (pr, offset_to_points) = match laszip_vlr {
+                Some(ref vlr) => {
+                    let decompressor = LasZipDecompressor::new(vlr)?;
+                    if decompressor.record_length() != record_length {
+                        return Err(Error::new(ErrorKind::InvalidData, "The point record length of the LAZ file does not match its LASzip VLR."));
                     }
-                } else if self.header.point_format == 2 {
-                    // read the RGB data
-                    for i in 0..self.header.number_of_points {
-                        offset = (self.header.offset_to_points + 20u32 + (i as u32) * (self.header.point_record_length as u32)) as usize;
-                        let mut rgb: RgbData = Default::default();
-                        rgb.red = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                        offset += 2;
-                        rgb.green = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                        offset += 2;
-                        rgb.blue = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                        self.rgb_data.push(rgb);
-                        // let r: u16 = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                        // offset += 2;
-                        // let g: u16 = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                        // offset += 2;
-                        // let b: u16 = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                        // self.rgb_data.push(RgbData { red: r, green: g, blue: b });
+                    let mut end_of_points = bor.len() as u64;
+                    if self.header.number_of_evlrs > 0 && self.header.start_of_first_evlr > self.header.offset_to_points as u64 {
+                        end_of_points = end_of_points.min(self.header.start_of_first_evlr);
                     }
-                } else if self.header.point_format == 3 {
-                    for i in 0..self.header.number_of_points {
-                        // read the GPS data
-                        offset = (self.header.offset_to_points + 20u32 + (i as u32) * (self.header.point_record_length as u32)) as usize;
-                        self.gps_data.push(mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]));
-                        offset += 8;
-                        // read the RGB data
-                        let mut rgb: RgbData = Default::default();
-                        rgb.red = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                        offset += 2;
-                        rgb.green = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                        offset += 2;
-                        rgb.blue = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
-                        self.rgb_data.push(rgb);
+                    let chunks = decompressor.read_chunk_table(&mut Cursor::new(&bor.buffer[..]),
+                                                               self.header.offset_to_points as u64,
+                                                               end_of_points,
+                                                               num_points as u64)?;
+                    let mut points = Vec::with_capacity(num_points * record_length);
+                    for chunk in &chunks {
+                        let (start, end) = (chunk.offset as usize, (chunk.offset + chunk.byte_count) as usize);
+                        if end > bor.len() {
+                            return Err(Error::new(ErrorKind::InvalidData, "The file is shorter than its compressed points; it may be truncated."));
+                        }
+                        decompressor.decompress_chunk(&bor.buffer[start..end], chunk.num_points as usize, &mut points)?;
                     }
-                } else if self.header.point_format == 4 {
+                    decompressed = ByteOrderReader::new(points, Endianness::LittleEndian);
+                    (&mut decompressed, 0usize)
+                },
+                None => (&mut bor, self.header.offset_to_points as usize),
+            };
 
+            let end_of_points = offset_to_points + num_points * record_length;
+            if end_of_points > pr.len() {
+                return Err(Error::new(ErrorKind::InvalidData, "The file is shorter than its point records; it may be truncated."));
+            }
+            self.point_data.reserve(num_points);
+            self.extra_byte_data.reserve(num_points * self.num_extra_bytes);
+            for i in 0..num_points {
+                pr.seek(offset_to_points + i * record_length);
+                let record = read_point_record(pr, &self.header, self.use_point_intensity, self.use_point_userdata);
+                let end_of_record = offset_to_points + (i + 1) * record_length;
+                self.extra_byte_data.extend_from_slice(&pr.buffer[end_of_record - self.num_extra_bytes..end_of_record]);
+                self.point_data.push(record.get_point_data());
+                if let Some(gps_data) = record.get_gps_time() {
+                    self.gps_data.push(gps_data);
+                }
+                if let Some(rgb_data) = record.get_rgb() {
+                    self.rgb_data.push(rgb_data);
+                }
+                if let Some(wave_packet) = record.get_waveform_packet() {
+                    self.waveform_data.push(wave_packet);
                 }
             }
+        }
 
+        ////////////////////////
+        // Read the EVLR data //
+        ////////////////////////
+        if self.header.number_of_evlrs > 0 {
+            let start = (self.header.start_of_first_evlr as usize).min(bor.len());
+            read_evlrs(&self.header, &bor.buffer[start..], &mut self.evlr_data, &mut self.geokeys)?;
         }
 
         Ok(())
@@ -523,6 +573,7 @@ impl LasFile {
         if !self.header_is_set {
             return Err(Error::new(ErrorKind::Other, "The header of a LAS file must be added before any point records. Please see add_header()."));
         }
+        LasIndex::remove(&self.file_name);
 
         self.header.x_offset = self.header.min_x;
         self.header.y_offset = self.header.min_y;
@@ -568,360 +619,59 @@ impl LasFile {
     }
 
     fn write_data<W: Write>(&mut self, writer: &mut W) -> Result<(), Error> {
-        /////////////////////////////////
-        // Write the header to the file /
-        /////////////////////////////////
-        let mut u16_bytes: [u8; 2];
-        let mut u32_bytes: [u8; 4];
-        let mut u64_bytes: [u8; 8];
-
-        self.header.file_signature = "LASF".to_string();
-        writer.write_all(self.header.file_signature.as_bytes())?;
-        
-        u16_bytes = unsafe {mem::transmute(self.header.file_source_id)};
-        writer.write_all(&u16_bytes)?;
-        
-        u16_bytes = unsafe { mem::transmute(self.header.global_encoding) };
-        writer.write_all(&u16_bytes)?;
-        
-        if self.header.project_id_used {
-            u32_bytes = unsafe { mem::transmute(self.header.project_id1) };
-            writer.write_all(&u32_bytes)?;
-            
-            u16_bytes = unsafe { mem::transmute(self.header.project_id2) };
-            writer.write_all(&u16_bytes)?;
-            
-            u16_bytes = unsafe { mem::transmute(self.header.project_id3) };
-            writer.write_all(&u16_bytes)?;
-            
-            u64_bytes = unsafe { mem::transmute(self.header.project_id4) };
-            writer.write_all(&u64_bytes)?;
+        let format = self.header.point_format;
+        if format > 10 {
+            return Err(Error::new(ErrorKind::Other, "Unsupported point format"));
         }
-
-        self.header.version_major = 1u8;
-        let mut u8_bytes: [u8; 1] = unsafe {mem::transmute(self.header.version_major)};
-        writer.write_all(&u8_bytes)?;
-        
-        self.header.version_minor = 3u8;
-        u8_bytes = unsafe {mem::transmute(self.header.version_minor)};
-        writer.write_all(&u8_bytes)?;
-        
-        if self.header.system_id.len() == 0 {
-            self.header.system_id = fixed_length_string("OTHER", 32);
-        } else if !self.header.system_id.len() != 32 {
-            self.header.system_id = fixed_length_string(&(self.header.system_id), 32);
-        }
-        writer.write_all(self.header.system_id.as_bytes())?; //string_bytes));
-        
-        self.header.generating_software = fixed_length_string("WhiteboxTools                   ", 32);
-        writer.write_all(self.header.generating_software.as_bytes())?;
-        
-        let now = time::now();
-        self.header.file_creation_day = now.tm_yday as u16;
-        u16_bytes = unsafe { mem::transmute(self.header.file_creation_day) };
-        writer.write_all(&u16_bytes)?;
-        
-        self.header.file_creation_year = (now.tm_year + 1900) as u16;
-        u16_bytes = unsafe { mem::transmute(self.header.file_creation_year) };
-        writer.write_all(&u16_bytes)?;
-        
-        self.header.header_size = 235;
-        u16_bytes = unsafe { mem::transmute(self.header.header_size) };
-        writer.write_all(&u16_bytes)?;
-    
-        // figure out the offset to points
-        let mut total_vlr_size = 54 * self.header.number_of_vlrs;
-        for i in 0..(self.header.number_of_vlrs as usize) {
-            total_vlr_size += self.vlr_data[i].record_length_after_header as u32;
-        }
-        self.header.offset_to_points = 235 + total_vlr_size;
-        u32_bytes = unsafe { mem::transmute(self.header.offset_to_points) };
-        writer.write_all(&u32_bytes)?;
-        
-        u32_bytes = unsafe { mem::transmute(self.header.number_of_vlrs) };
-        writer.write_all(&u32_bytes)?;
-        
-        u8_bytes = unsafe {mem::transmute(self.header.point_format)};
-        writer.write_all(&u8_bytes)?;
-
-        // Intensity and userdata are both optional. Figure out if they need to be read.
-        // The only way to do this is to compare the point record length by point format
-        let rec_lengths = [ [20_u16, 18_u16, 19_u16, 17_u16],
-                            [28_u16, 26_u16, 27_u16, 25_u16],
-                            [26_u16, 24_u16, 25_u16, 23_u16],
-                            [34_u16, 32_u16, 33_u16, 31_u16] ];
-
-        if self.use_point_intensity && self.use_point_userdata {
-            self.header.point_record_length = rec_lengths[self.header.point_format as usize][0];
-        } else if !self.use_point_intensity && self.use_point_userdata {
-            self.header.point_record_length = rec_lengths[self.header.point_format as usize][1];
-        } else if self.use_point_intensity && !self.use_point_userdata {
-            self.header.point_record_length = rec_lengths[self.header.point_format as usize][2];
-        } else { //if !self.use_point_intensity && !self.use_point_userdata {
-            self.header.point_record_length = rec_lengths[self.header.point_format as usize][3];
-        }
-
-        u16_bytes = unsafe { mem::transmute(self.header.point_record_length) };
-        writer.write_all(&u16_bytes)?;
-        
-        u32_bytes = unsafe { mem::transmute(self.header.number_of_points) };
-        writer.write_all(&u32_bytes)?;
-        
-        for i in 0..5 {
-            u32_bytes = unsafe { mem::transmute(self.header.number_of_points_by_return[i]) };
-            writer.write_all(&u32_bytes)?;
+        let is_laz = self.file_name.to_lowercase().ends_with(".laz");
+        set_point_record_length(&mut self.header, &mut self.use_point_intensity, &mut self.use_point_userdata, is_laz, self.num_extra_bytes);
+
+        // LAZ files describe their compression in an additional VLR. Compressed points
+        // are written in chunks of varying size, so they are compressed before the
+        // position of the EVLRs, which follow them, is known.
+        let mut laszip_vlr = None;
+        let mut compressed_points = None;
+        if is_laz {
+            let mut compressor = LasZipCompressor::new(format, self.header.point_record_length)?;
+            laszip_vlr = Some(compressor.vlr());
+            let mut record = Vec::with_capacity(self.header.point_record_length as usize);
+            for i in 0..self.header.number_of_points as usize {
+                record.clear();
+                write_point_record(&mut record, &self.header, self.use_point_intensity, self.use_point_userdata, &self.get_record(i), self.get_extra_bytes(i))?;
+                compressor.compress(&record);
+            }
+            compressed_points = Some(compressor.finish());
         }
+        let point_data_size = match compressed_points {
+            // the compressed points are preceded by the position of their chunk table
+            Some((ref chunks, ref chunk_table)) => 8 + (chunks.len() + chunk_table.len()) as u64,
+            None => self.header.number_of_points * self.header.point_record_length as u64,
+        };
 
-        u64_bytes = unsafe { mem::transmute(self.header.x_scale_factor) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.y_scale_factor) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.z_scale_factor) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.x_offset) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.y_offset) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.z_offset) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.max_x) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.min_x) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.max_y) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.min_y) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.max_z) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.min_z) };
-        writer.write_all(&u64_bytes)?;
-        
-        u64_bytes = unsafe { mem::transmute(self.header.waveform_data_start) };
-        writer.write_all(&u64_bytes)?;
-
-        ///////////////////////////////
-        // Write the VLRs to the file /
-        ///////////////////////////////
-        for i in 0..(self.header.number_of_vlrs as usize) {
-            let vlr = self.vlr_data[i].clone();
-            u16_bytes = unsafe { mem::transmute(vlr.reserved) };
-            writer.write_all(&u16_bytes)?;
-
-            let user_id: &str = &vlr.user_id;
-            //string_bytes = unsafe { mem::transmute(user_id) };
-            writer.write_all(fixed_length_string(user_id, 16).as_bytes())?; //string_bytes));
-            
-            u16_bytes = unsafe { mem::transmute(vlr.record_id) };
-            writer.write_all(&u16_bytes)?;
-            
-            u16_bytes = unsafe { mem::transmute(vlr.record_length_after_header) };
-            writer.write_all(&u16_bytes)?;
-            
-            let description: &str = &vlr.description;
-            //string_bytes = unsafe { mem::transmute(description) };
-            writer.write_all(fixed_length_string(description, 32).as_bytes())?;
-            
-            writer.write_all(&vlr.binary_data)?;
-        }
+        write_header(writer, &mut self.header, &mut self.vlr_data, &mut self.evlr_data, laszip_vlr.as_ref(), point_data_size)?;
 
         ////////////////////////////////
         // Write the point to the file /
         ////////////////////////////////
-        let mut val: i32;
-        match self.header.point_format {
-            0 => {
-                for i in 0..self.header.number_of_points as usize {
-                    val = ((self.point_data[i].x - self.header.x_offset) / self.header.x_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    val = ((self.point_data[i].y - self.header.y_offset) / self.header.y_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    val = ((self.point_data[i].z - self.header.z_offset) / self.header.z_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    if self.use_point_intensity {
-                        u16_bytes = unsafe { mem::transmute(self.point_data[i].intensity) };
-                        writer.write_all(&u16_bytes)?;
-                    }
-
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].bit_field.value)};
-                    writer.write_all(&u8_bytes)?;
-                    
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].class_bit_field.value)};
-                    writer.write_all(&u8_bytes)?;
-                    
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].scan_angle)};
-                    writer.write_all(&u8_bytes)?;
-                    
-                    if self.use_point_userdata {
-                        u8_bytes = unsafe {mem::transmute(self.point_data[i].user_data)};
-                        writer.write_all(&u8_bytes)?;
-                    }
-
-                    u16_bytes = unsafe { mem::transmute(self.point_data[i].point_source_id) };
-                    writer.write_all(&u16_bytes)?;
-                }
+        match compressed_points {
+            Some((chunks, chunk_table)) => {
+                let chunk_table_start = self.header.offset_to_points as u64 + 8 + chunks.len() as u64;
+                writer.write_i64::<LittleEndian>(chunk_table_start as i64)?;
+                writer.write_all(&chunks)?;
+                writer.write_all(&chunk_table)?;
             },
-            1 => {
+            None => {
                 for i in 0..self.header.number_of_points as usize {
-                    val = ((self.point_data[i].x - self.header.x_offset) / self.header.x_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    val = ((self.point_data[i].y - self.header.y_offset) / self.header.y_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    val = ((self.point_data[i].z - self.header.z_offset) / self.header.z_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    if self.use_point_intensity {
-                        u16_bytes = unsafe { mem::transmute(self.point_data[i].intensity) };
-                        writer.write_all(&u16_bytes)?;
-                    }
-
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].bit_field.value)};
-                    writer.write_all(&u8_bytes)?;
-                    
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].class_bit_field.value)};
-                    writer.write_all(&u8_bytes)?;
-                    
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].scan_angle)};
-                    writer.write_all(&u8_bytes)?;
-                    
-                    if self.use_point_userdata {
-                        u8_bytes = unsafe {mem::transmute(self.point_data[i].user_data)};
-                        writer.write_all(&u8_bytes)?;
-                    }
-
-                    u16_bytes = unsafe { mem::transmute(self.point_data[i].point_source_id) };
-                    writer.write_all(&u16_bytes)?;
-                    
-                    u64_bytes = unsafe { mem::transmute(self.gps_data[i]) };
-                    writer.write_all(&u64_bytes)?;
+                    write_point_record(writer, &self.header, self.use_point_intensity, self.use_point_userdata, &self.get_record(i), self.get_extra_bytes(i))?;
                 }
             },
-            2 => {
-                for i in 0..self.header.number_of_points as usize {
-                    val = ((self.point_data[i].x - self.header.x_offset) / self.header.x_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    val = ((self.point_data[i].y - self.header.y_offset) / self.header.y_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    val = ((self.point_data[i].z - self.header.z_offset) / self.header.z_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    if self.use_point_intensity {
-                        u16_bytes = unsafe { mem::transmute(self.point_data[i].intensity) };
-                        writer.write_all(&u16_bytes)?;
-                    }
-
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].bit_field.value)};
-                    writer.write_all(&u8_bytes)?;
-
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].class_bit_field.value)};
-                    writer.write_all(&u8_bytes)?;
-                    
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].scan_angle)};
-                    writer.write_all(&u8_bytes)?;
-                    
-                    if self.use_point_userdata {
-                        u8_bytes = unsafe {mem::transmute(self.point_data[i].user_data)};
-                        writer.write_all(&u8_bytes)?;
-                    }
-
-                    u16_bytes = unsafe { mem::transmute(self.point_data[i].point_source_id) };
-                    writer.write_all(&u16_bytes)?;
-                    
-                    u16_bytes = unsafe { mem::transmute(self.rgb_data[i].red) };
-                    writer.write_all(&u16_bytes)?;
-                    
-                    u16_bytes = unsafe { mem::transmute(self.rgb_data[i].green) };
-                    writer.write_all(&u16_bytes)?;
-                    
-                    u16_bytes = unsafe { mem::transmute(self.rgb_data[i].blue) };
-                    writer.write_all(&u16_bytes)?;
-                }
-            },
-            3 => {
-                for i in 0..self.header.number_of_points as usize {
-                    val = ((self.point_data[i].x - self.header.x_offset) / self.header.x_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    val = ((self.point_data[i].y - self.header.y_offset) / self.header.y_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    val = ((self.point_data[i].z - self.header.z_offset) / self.header.z_scale_factor) as i32;
-                    u32_bytes = unsafe { mem::transmute(val) };
-                    writer.write_all(&u32_bytes)?;
-                    
-                    if self.use_point_intensity {
-                        u16_bytes = unsafe { mem::transmute(self.point_data[i].intensity) };
-                        writer.write_all(&u16_bytes)?;
-                    }
-
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].bit_field.value)};
-                    writer.write_all(&u8_bytes)?;
-                    
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].class_bit_field.value)};
-                    writer.write_all(&u8_bytes)?;
-                    
-                    u8_bytes = unsafe {mem::transmute(self.point_data[i].scan_angle)};
-                    writer.write_all(&u8_bytes)?;
-                    
-                    if self.use_point_userdata {
-                        u8_bytes = unsafe {mem::transmute(self.point_data[i].user_data)};
-                        writer.write_all(&u8_bytes)?;
-                    }
-
-                    u16_bytes = unsafe { mem::transmute(self.point_data[i].point_source_id) };
-                    writer.write_all(&u16_bytes)?;
-                    
-                    u64_bytes = unsafe { mem::transmute(self.gps_data[i]) };
-                    writer.write_all(&u64_bytes)?;
-                    
-                    u16_bytes = unsafe { mem::transmute(self.rgb_data[i].red) };
-                    writer.write_all(&u16_bytes)?;
-                    
-                    u16_bytes = unsafe { mem::transmute(self.rgb_data[i].green) };
-                    writer.write_all(&u16_bytes)?;
-                    
-                    u16_bytes = unsafe { mem::transmute(self.rgb_data[i].blue) };
-                    writer.write_all(&u16_bytes)?;
-                }
-            },
-            _ => {
-                return Err(Error::new(ErrorKind::Other, "Unsupported point format"));
-            },
         }
 
+        write_evlrs(writer, &self.evlr_data)?;
+
         Ok(())
     }
 
-
     pub fn get_vlr_data_as_string(&self) -> String {
         let mut s = "".to_string();
         let mut i : usize = 1;
@@ -1018,7 +768,109 @@ pub enum LidarPointRecord {
     PointRecord0 { point_data: PointData },
     PointRecord1 { point_data: PointData, gps_data: f64 },
     PointRecord2 { point_data: PointData, rgb_data: RgbData },
-    PointRecord3 { point_data: PointData, gps_data: f64, rgb_data: RgbData }
+    PointRecord3 { point_data: PointData, gps_data: f64, rgb_data: RgbData },
+    PointRecord4 { point_data: PointData, gps_data: f64, wave_packet: WaveformPacket },
+    PointRecord5 { point_data: PointData, gps_data: f64, rgb_data: RgbData, wave_packet: WaveformPacket },
+    PointRecord6 { point_data: PointData, gps_data: f64 },
+    PointRecord7 { point_data: PointData, gps_data: f64, rgb_data: RgbData },
+    PointRecord8 { point_data: PointData, gps_data: f64, rgb_data: RgbData },
+    PointRecord9 { point_data: PointData, gps_data: f64, wave_packet: WaveformPacket },
+    PointRecord10 { point_data: PointData, gps_data: f64, rgb_data: RgbData, wave_packet: WaveformPacket },
+}
+
+impl LidarPointRecord {
+    /// Returns the point format of the record.
+    pub fn get_format(&self) -> u8 {
+        match *self {
+            LidarPointRecord::PointRecord0 { .. } => 0u8,
+            LidarPointRecord::PointRecord1 { .. } => 1u8,
+            LidarPointRecord::PointRecord2 { .. } => 2u8,
+            LidarPointRecord::PointRecord3 { .. } => 3u8,
+            LidarPointRecord::PointRecord4 { .. } => 4u8,
+            LidarPointRecord::PointRecord5 { .. } => 5u8,
+            LidarPointRecord::PointRecord6 { .. } => 6u8,
+            LidarPointRecord::PointRecord7 { .. } => 7u8,
+            LidarPointRecord::PointRecord8 { .. } => 8u8,
+            LidarPointRecord::PointRecord9 { .. } => 9u8,
+            LidarPointRecord::PointRecord10 { .. } => 10u8,
+        }
+    }
+
+    /// Returns the point data of the record, which is common to all point formats.
+    pub fn get_point_data(&self) -> PointData {
+        match *self {
+            LidarPointRecord::PointRecord0 { point_data } |
+            LidarPointRecord::PointRecord1 { point_data, .. } |
+            LidarPointRecord::PointRecord2 { point_data, .. } |
+            LidarPointRecord::PointRecord3 { point_data, .. } |
+            LidarPointRecord::PointRecord4 { point_data, .. } |
+            LidarPointRecord::PointRecord5 { point_data, .. } |
+            LidarPointRecord::PointRecord6 { point_data, .. } |
+            LidarPointRecord::PointRecord7 { point_data, .. } |
+            LidarPointRecord::PointRecord8 { point_data, .. } |
+            LidarPointRecord::PointRecord9 { point_data, .. } |
+            LidarPointRecord::PointRecord10 { point_data, .. } => point_data,
+        }
+    }
+
+    /// Replaces the point data of the record, keeping its GPS time, colour and
+    /// waveform data, e.g. to reclassify a point.
+    pub fn set_point_data(&mut self, value: PointData) {
+        match *self {
+            LidarPointRecord::PointRecord0 { ref mut point_data } |
+            LidarPointRecord::PointRecord1 { ref mut point_data, .. } |
+            LidarPointRecord::PointRecord2 { ref mut point_data, .. } |
+            LidarPointRecord::PointRecord3 { ref mut point_data, .. } |
+            LidarPointRecord::PointRecord4 { ref mut point_data, .. } |
+            LidarPointRecord::PointRecord5 { ref mut point_data, .. } |
+            LidarPointRecord::PointRecord6 { ref mut point_data, .. } |
+            LidarPointRecord::PointRecord7 { ref mut point_data, .. } |
+            LidarPointRecord::PointRecord8 { ref mut point_data, .. } |
+            LidarPointRecord::PointRecord9 { ref mut point_data, .. } |
+            LidarPointRecord::PointRecord10 { ref mut point_data, .. } => *point_data = value,
+        }
+    }
+
+    /// Returns the GPS time of the record, if its point format includes one.
+    pub fn get_gps_time(&self) -> Option<f64> {
+        match *self {
+            LidarPointRecord::PointRecord0 { .. } |
+            LidarPointRecord::PointRecord2 { .. } => None,
+            LidarPointRecord::PointRecord1 { gps_data, .. } |
+            LidarPointRecord::PointRecord3 { gps_data, .. } |
+            LidarPointRecord::PointRecord4 { gps_data, .. } |
+            LidarPointRecord::PointRecord5 { gps_data, .. } |
+            LidarPointRecord::PointRecord6 { gps_data, .. } |
+            LidarPointRecord::PointRecord7 { gps_data, .. } |
+            LidarPointRecord::PointRecord8 { gps_data, .. } |
+            LidarPointRecord::PointRecord9 { gps_data, .. } |
+            LidarPointRecord::PointRecord10 { gps_data, .. } => Some(gps_data),
+        }
+    }
+
+    /// Returns the colour data of the record, if its point format includes it.
+    pub fn get_rgb(&self) -> Option<RgbData> {
+        match *self {
+            LidarPointRecord::PointRecord2 { rgb_data, .. } |
+            LidarPointRecord::PointRecord3 { rgb_data, .. } |
+            LidarPointRecord::PointRecord5 { rgb_data, .. } |
+            LidarPointRecord::PointRecord7 { rgb_data, .. } |
+            LidarPointRecord::PointRecord8 { rgb_data, .. } |
+            LidarPointRecord::PointRecord10 { rgb_data, .. } => Some(rgb_data),
+            _ => None,
+        }
+    }
+
+    /// Returns the waveform packet of the record, if its point format includes one.
+    pub fn get_waveform_packet(&self) -> Option<WaveformPacket> {
+        match *self {
+            LidarPointRecord::PointRecord4 { wave_packet, .. } |
+            LidarPointRecord::PointRecord5 { wave_packet, .. } |
+            LidarPointRecord::PointRecord9 { wave_packet, .. } |
+            LidarPointRecord::PointRecord10 { wave_packet, .. } => Some(wave_packet),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Default, Copy, Clone, Debug)]
@@ -1096,6 +948,127 @@ impl PointRecord5 {
     }
 }
 
+#[derive(Default, Copy, Clone, Debug)]
+pub struct  PointRecord6 {
+    pub point_data: PointData,
+    pub gps_data: f64,
+}
+
+impl PointRecord6 {
+    pub fn get_format(&self) -> u8 {
+        6u8
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug)]
+pub struct  PointRecord7 {
+    pub point_data: PointData,
+    pub gps_data: f64,
+    pub rgb_data: RgbData,
+}
+
+impl PointRecord7 {
+    pub fn get_format(&self) -> u8 {
+        7u8
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug)]
+pub struct  PointRecord8 {
+    pub point_data: PointData,
+    pub gps_data: f64,
+    pub rgb_data: RgbData,
+}
+
+impl PointRecord8 {
+    pub fn get_format(&self) -> u8 {
+        8u8
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug)]
+pub struct  PointRecord9 {
+    pub point_data: PointData,
+    pub gps_data: f64,
+    pub wave_packet: WaveformPacket,
+}
+
+impl PointRecord9 {
+    pub fn get_format(&self) -> u8 {
+        9u8
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug)]
+pub struct  PointRecord10 {
+    pub point_data: PointData,
+    pub gps_data: f64,
+    pub rgb_data: RgbData,
+    pub wave_packet: WaveformPacket,
+}
+
+impl PointRecord10 {
+    pub fn get_format(&self) -> u8 {
+        10u8
+    }
+}
+
+/// Creates a record of the specified point format. The GPS time, colour and
+/// waveform data are ignored by formats that do not include them.
+fn create_point_record(point_format: u8, point_data: PointData, gps_data: f64, rgb_data: RgbData, wave_packet: WaveformPacket) -> LidarPointRecord {
+    match point_format {
+        0 => LidarPointRecord::PointRecord0 { point_data: point_data },
+        1 => LidarPointRecord::PointRecord1 { point_data: point_data, gps_data: gps_data },
+        2 => LidarPointRecord::PointRecord2 { point_data: point_data, rgb_data: rgb_data },
+        3 => LidarPointRecord::PointRecord3 { point_data: point_data, gps_data: gps_data, rgb_data: rgb_data },
+        4 => LidarPointRecord::PointRecord4 { point_data: point_data, gps_data: gps_data, wave_packet: wave_packet },
+        5 => LidarPointRecord::PointRecord5 { point_data: point_data, gps_data: gps_data, rgb_data: rgb_data,
+                wave_packet: wave_packet },
+        6 => LidarPointRecord::PointRecord6 { point_data: point_data, gps_data: gps_data },
+        7 => LidarPointRecord::PointRecord7 { point_data: point_data, gps_data: gps_data, rgb_data: rgb_data },
+        8 => LidarPointRecord::PointRecord8 { point_data: point_data, gps_data: gps_data, rgb_data: rgb_data },
+        9 => LidarPointRecord::PointRecord9 { point_data: point_data, gps_data: gps_data, wave_packet: wave_packet },
+        10 => LidarPointRecord::PointRecord10 { point_data: point_data, gps_data: gps_data, rgb_data: rgb_data,
+                wave_packet: wave_packet },
+        _ => panic!("Unsupported point format"),
+    }
+}
+
+/// The standard length, in bytes, of a record of each point format.
+fn point_record_length(point_format: u8) -> u16 {
+    match point_format {
+        0 => 20,
+        1 => 28,
+        2 => 26,
+        3 => 34,
+        4 => 57,
+        5 => 63,
+        6 => 30,
+        7 => 36,
+        8 => 38,
+        9 => 59,
+        _ => 67,
+    }
+}
+
+fn has_gps_time(point_format: u8) -> bool {
+    point_format != 0 && point_format != 2
+}
+
+fn has_rgb(point_format: u8) -> bool {
+    match point_format {
+        2 | 3 | 5 | 7 | 8 | 10 => true,
+        _ => false,
+    }
+}
+
+fn has_waveform(point_format: u8) -> bool {
+    match point_format {
+        4 | 5 | 9 | 10 => true,
+        _ => false,
+    }
+}
+
 fn fixed_length_string(s: &str, len: usize) -> String {
     //let array: &[u8: 32];
     let l = s.len();
@@ -1113,9 +1086,824 @@ fn fixed_length_string(s: &str, len: usize) -> String {
     ret
 }
 
+/// Determines the point record length of a file about to be written. Intensity
+/// and user data are both optional in the legacy formats, but not in LAZ files.
+/// Any extra bytes follow the standard fields.
+pub fn set_point_record_length(header: &mut LasHeader, use_point_intensity: &mut bool, use_point_userdata: &mut bool, is_laz: bool, num_extra_bytes: usize) {
+    let format = header.point_format;
+    header.point_record_length = point_record_length(format) + num_extra_bytes as u16;
+    if format < 6 && !is_laz {
+        if !*use_point_intensity {
+            header.point_record_length -= 2;
+        }
+        if !*use_point_userdata {
+            header.point_record_length -= 1;
+        }
+    } else {
+        *use_point_intensity = true;
+        *use_point_userdata = true;
+    }
+}
+
+/// Writes the header and VLRs, updating the header's offsets and, for EVLRs, the
+/// positions that follow from the size of the point data. The LASzip VLR of a
+/// LAZ file is written after the other VLRs.
+pub fn write_header<W: Write>(writer: &mut W, header: &mut LasHeader, vlr_data: &mut [Vlr], evlr_data: &mut [Vlr], laszip_vlr: Option<&Vlr>, point_data_size: u64) -> Result<(), Error> {
+    /////////////////////////////////
+    // Write the header to the file /
+    /////////////////////////////////
+    let mut u16_bytes: [u8; 2];
+    let mut u32_bytes: [u8; 4];
+    let mut u64_bytes: [u8; 8];
+
+    let format = header.point_format;
+    // LAS 1.4 is needed for the extended point formats, EVLRs (other than
+    // waveform data) and more than 2^32 points; other files are written as 1.3.
+    let is_las14 = format >= 6 || evlr_data.iter().any(|evlr| evlr.record_id != 65_535) ||
+        header.number_of_points > u32::max_value() as u64;
+    let header_size = if is_las14 { 375u16 } else { 235u16 };
+    let is_laz = laszip_vlr.is_some();
+
+    // The extended point formats require the co-ordinate reference system to be
+    // stored as OGC WKT, which is flagged by bit 4 of the global encoding. GeoKeys
+    // cannot be converted to WKT here, so a file without a WKT record is rejected.
+    if format >= 6 {
+        let vlrs = &vlr_data[..header.number_of_vlrs as usize];
+        let has_geokeys = vlrs.iter().chain(evlr_data.iter()).any(|vlr| is_geokeys_vlr(vlr));
+        let has_wkt = vlrs.iter().chain(evlr_data.iter()).any(|vlr| is_wkt_vlr(vlr));
+        if has_geokeys && !has_wkt {
+            return Err(Error::new(ErrorKind::InvalidInput, "Point formats 6 to 10 require the co-ordinate reference system to be stored as OGC WKT, but only GeoKeys were found."));
+        }
+        header.global_encoding.value |= 0b0001_0000u16;
+    }
+
+    header.file_signature = "LASF".to_string();
+    writer.write_all(header.file_signature.as_bytes())?;
+    
+    u16_bytes = unsafe {mem::transmute(header.file_source_id)};
+    writer.write_all(&u16_bytes)?;
+    
+    u16_bytes = unsafe { mem::transmute(header.global_encoding) };
+    writer.write_all(&u16_bytes)?;
+    
+    if header.project_id_used {
+        u32_bytes = unsafe { mem::transmute(header.project_id1) };
+        writer.write_all(&u32_bytes)?;
+        
+        u16_bytes = unsafe { mem::transmute(header.project_id2) };
+        writer.write_all(&u16_bytes)?;
+        
+        u16_bytes = unsafe { mem::transmute(header.project_id3) };
+        writer.write_all(&u16_bytes)?;
+        
+        u64_bytes = unsafe { mem::transmute(header.project_id4) };
+        writer.write_all(&u64_bytes)?;
+    }
+
+    header.version_major = 1u8;
+    let mut u8_bytes: [u8; 1] = unsafe {mem::transmute(header.version_major)};
+    writer.write_all(&u8_bytes)?;
+    
+    header.version_minor = if is_las14 { 4u8 } else { 3u8 };
+    u8_bytes = unsafe {mem::transmute(header.version_minor)};
+    writer.write_all(&u8_bytes)?;
+    
+    if header.system_id.len() == 0 {
+        header.system_id = fixed_length_string("OTHER", 32);
+    } else if !header.system_id.len() != 32 {
+        header.system_id = fixed_length_string(&(header.system_id), 32);
+    }
+    writer.write_all(header.system_id.as_bytes())?; //string_bytes));
+    
+    header.generating_software = fixed_length_string("WhiteboxTools                   ", 32);
+    writer.write_all(header.generating_software.as_bytes())?;
+    
+    let now = time::now();
+    header.file_creation_day = now.tm_yday as u16;
+    u16_bytes = unsafe { mem::transmute(header.file_creation_day) };
+    writer.write_all(&u16_bytes)?;
+    
+    header.file_creation_year = (now.tm_year + 1900) as u16;
+    u16_bytes = unsafe { mem::transmute(header.file_creation_year) };
+    writer.write_all(&u16_bytes)?;
+    
+    header.header_size = header_size;
+    u16_bytes = unsafe { mem::transmute(header.header_size) };
+    writer.write_all(&u16_bytes)?;
+
+    // figure out the offset to points
+    let mut total_vlr_size = 54 * header.number_of_vlrs;
+    for i in 0..(header.number_of_vlrs as usize) {
+        vlr_data[i].record_length_after_header = vlr_data[i].binary_data.len() as u64;
+        total_vlr_size += vlr_data[i].record_length_after_header as u32;
+    }
+    if let Some(ref vlr) = laszip_vlr {
+        total_vlr_size += 54 + vlr.binary_data.len() as u32;
+    }
+    header.offset_to_points = header_size as u32 + total_vlr_size;
+    u32_bytes = unsafe { mem::transmute(header.offset_to_points) };
+    writer.write_all(&u32_bytes)?;
+    
+    let number_of_vlrs = header.number_of_vlrs + if is_laz { 1 } else { 0 };
+    u32_bytes = unsafe { mem::transmute(number_of_vlrs) };
+    writer.write_all(&u32_bytes)?;
+    
+    // the two high bits of the point format flag compressed points
+    u8_bytes = unsafe {mem::transmute(if is_laz { format | 0b1000_0000u8 } else { format })};
+    writer.write_all(&u8_bytes)?;
+
+    u16_bytes = unsafe { mem::transmute(header.point_record_length) };
+    writer.write_all(&u16_bytes)?;
+
+    // the legacy point counts, which are zero if they cannot hold the count or
+    // the point format is an extended one
+    let use_legacy_counts = format < 6 && header.number_of_points <= u32::max_value() as u64;
+    let legacy_count = if use_legacy_counts { header.number_of_points as u32 } else { 0u32 };
+    u32_bytes = unsafe { mem::transmute(legacy_count) };
+    writer.write_all(&u32_bytes)?;
+
+    for i in 0..5 {
+        let legacy_count = if use_legacy_counts { header.number_of_points_by_return[i] as u32 } else { 0u32 };
+        u32_bytes = unsafe { mem::transmute(legacy_count) };
+        writer.write_all(&u32_bytes)?;
+    }
+
+    u64_bytes = unsafe { mem::transmute(header.x_scale_factor) };
+    writer.write_all(&u64_bytes)?;
+    
+    u64_bytes = unsafe { mem::transmute(header.y_scale_factor) };
+    writer.write_all(&u64_bytes)?;
+    
+    u64_bytes = unsafe { mem::transmute(header.z_scale_factor) };
+    writer.write_all(&u64_bytes)?;
+    
+    u64_bytes = unsafe { mem::transmute(header.x_offset) };
+    writer.write_all(&u64_bytes)?;
+    
+    u64_bytes = unsafe { mem::transmute(header.y_offset) };
+    writer.write_all(&u64_bytes)?;
+    
+    u64_bytes = unsafe { mem::transmute(header.z_offset) };
+    writer.write_all(&u64_bytes)?;
+    
+    u64_bytes = unsafe { mem::transmute(header.max_x) };
+    writer.write_all(&u64_bytes)?;
+    
+    u64_bytes = unsafe { mem::transmute(header.min_x) };
+    writer.write_all(&u64_bytes)?;
+    
+    u64_bytes = unsafe { mem::transmute(header.max_y) };
+    writer.write_all(&u64_bytes)?;
+    
+    u64_bytes = unsafe { mem::transmute(header.min_y) };
+    writer.write_all(&u64_bytes)?;
+    
+    u64_bytes = unsafe { mem::transmute(header.max_z) };
+    writer.write_all(&u64_bytes)?;
+    
+    u64_bytes = unsafe { mem::transmute(header.min_z) };
+    writer.write_all(&u64_bytes)?;
+    
+    // EVLRs follow the point records
+    let mut evlr_position = header.offset_to_points as u64 + point_data_size;
+    header.start_of_first_evlr = if evlr_data.len() > 0 { evlr_position } else { 0u64 };
+    for evlr in evlr_data.iter_mut() {
+        evlr.record_length_after_header = evlr.binary_data.len() as u64;
+        if evlr.record_id == 65_535 {
+            // waveform data packets, which are located by the header
+            header.waveform_data_start = evlr_position;
+        }
+        evlr_position += 60 + evlr.record_length_after_header;
+    }
+    header.number_of_evlrs = evlr_data.len() as u32;
+
+    u64_bytes = unsafe { mem::transmute(header.waveform_data_start) };
+    writer.write_all(&u64_bytes)?;
+
+    if is_las14 {
+        writer.write_u64::<LittleEndian>(header.start_of_first_evlr)?;
+        writer.write_u32::<LittleEndian>(header.number_of_evlrs)?;
+        writer.write_u64::<LittleEndian>(header.number_of_points)?;
+        for i in 0..15 {
+            writer.write_u64::<LittleEndian>(header.number_of_points_by_return[i])?;
+        }
+    }
+
+    ///////////////////////////////
+    // Write the VLRs to the file /
+    ///////////////////////////////
+    for i in 0..(header.number_of_vlrs as usize) {
+        let vlr = vlr_data[i].clone();
+        u16_bytes = unsafe { mem::transmute(vlr.reserved) };
+        writer.write_all(&u16_bytes)?;
+
+        let user_id: &str = &vlr.user_id;
+        //string_bytes = unsafe { mem::transmute(user_id) };
+        writer.write_all(fixed_length_string(user_id, 16).as_bytes())?; //string_bytes));
+        
+        u16_bytes = unsafe { mem::transmute(vlr.record_id) };
+        writer.write_all(&u16_bytes)?;
+        
+        u16_bytes = unsafe { mem::transmute(vlr.record_length_after_header as u16) };
+        writer.write_all(&u16_bytes)?;
+        
+        let description: &str = &vlr.description;
+        //string_bytes = unsafe { mem::transmute(description) };
+        writer.write_all(fixed_length_string(description, 32).as_bytes())?;
+        
+        writer.write_all(&vlr.binary_data)?;
+    }
+    if let Some(vlr) = laszip_vlr {
+        writer.write_u16::<LittleEndian>(vlr.reserved)?;
+        writer.write_all(fixed_length_string(&vlr.user_id, 16).as_bytes())?;
+        writer.write_u16::<LittleEndian>(vlr.record_id)?;
+        writer.write_u16::<LittleEndian>(vlr.binary_data.len() as u16)?;
+        writer.write_all(fixed_length_string(&vlr.description, 32).as_bytes())?;
+        writer.write_all(&vlr.binary_data)?;
+    }
+
+    Ok(())
+}
+
+/// Returns a boolean indicating whether a VLR holds GeoTIFF keys.
+fn is_geokeys_vlr(vlr: &Vlr) -> bool {
+    vlr.user_id.trim_matches('\0') == "LASF_Projection" &&
+        (vlr.record_id == 34_735 || vlr.record_id == 34_736 || vlr.record_id == 34_737)
+}
+
+/// Returns a boolean indicating whether a VLR holds the OGC WKT co-ordinate
+/// reference system of a file.
+fn is_wkt_vlr(vlr: &Vlr) -> bool {
+    vlr.user_id.trim_matches('\0') == "LASF_Projection" && vlr.record_id == 2_112
+}
+
+/// Writes a point record in the point format of the header, followed by its extra
+/// bytes. The GPS time, colour and waveform data of formats that include them but
+/// the record lacks are zero.
+pub fn write_point_record<W: Write>(writer: &mut W, header: &LasHeader, use_point_intensity: bool, use_point_userdata: bool, record: &LidarPointRecord, extra_bytes: &[u8]) -> Result<(), Error> {
+    let format = header.point_format;
+    let p = &record.get_point_data();
+    writer.write_i32::<LittleEndian>(((p.x - header.x_offset) / header.x_scale_factor).round() as i32)?;
+    writer.write_i32::<LittleEndian>(((p.y - header.y_offset) / header.y_scale_factor).round() as i32)?;
+    writer.write_i32::<LittleEndian>(((p.z - header.z_offset) / header.z_scale_factor).round() as i32)?;
+    if use_point_intensity {
+        writer.write_u16::<LittleEndian>(p.intensity)?;
+    }
+    let mut flags = ((p.bit_field.value >> 10u16) & 0b11u16) as u8; // scan direction and edge of flight line
+    if format < 6 {
+        // The legacy formats have up to seven returns and 32 classes. Classes
+        // that cannot be represented are written as unclassified (1), and the
+        // overlap flag is represented by class 12.
+        flags = flags << 6u8 | p.return_number().min(7) | p.number_of_returns().min(7) << 3u8;
+        writer.write_u8(flags)?;
+        let mut class = p.classification();
+        if p.overlap() {
+            class = 12u8;
+        } else if class > 31u8 {
+            class = 1u8;
+        }
+        writer.write_u8(class | ((p.class_bit_field.value >> 8u16) as u8 & 0b0000_0111u8) << 5u8)?;
+        writer.write_i8(p.scan_angle_degrees().round().max(-128f64).min(127f64) as i8)?;
+        if use_point_userdata {
+            writer.write_u8(p.user_data)?;
+        }
+    } else {
+        writer.write_u8((p.bit_field.value & 0b1111_1111u16) as u8)?;
+        flags = flags << 6u8 | (p.scanner_channel() << 4u8) | ((p.class_bit_field.value >> 8u16) as u8 & 0b0000_1111u8);
+        writer.write_u8(flags)?;
+        writer.write_u8(p.classification())?;
+        writer.write_u8(p.user_data)?;
+        writer.write_i16::<LittleEndian>(p.scan_angle)?;
+    }
+    writer.write_u16::<LittleEndian>(p.point_source_id)?;
+
+    if has_gps_time(format) {
+        writer.write_f64::<LittleEndian>(record.get_gps_time().unwrap_or(0f64))?;
+    }
+    if has_rgb(format) {
+        let rgb = record.get_rgb().unwrap_or_default();
+        writer.write_u16::<LittleEndian>(rgb.red)?;
+        writer.write_u16::<LittleEndian>(rgb.green)?;
+        writer.write_u16::<LittleEndian>(rgb.blue)?;
+        if format == 8 || format == 10 {
+            writer.write_u16::<LittleEndian>(rgb.nir)?;
+        }
+    }
+    if has_waveform(format) {
+        let wp = record.get_waveform_packet().unwrap_or_default();
+        writer.write_u8(wp.packet_descriptor_index)?;
+        writer.write_u64::<LittleEndian>(wp.offset_to_waveform_data)?;
+        writer.write_u32::<LittleEndian>(wp.waveform_packet_size)?;
+        writer.write_f32::<LittleEndian>(wp.ret_point_waveform_loc)?;
+        writer.write_f32::<LittleEndian>(wp.xt)?;
+        writer.write_f32::<LittleEndian>(wp.yt)?;
+        writer.write_f32::<LittleEndian>(wp.zt)?;
+    }
+    writer.write_all(extra_bytes)?;
+    Ok(())
+}
+
+/// Writes the EVLRs, which follow the point records.
+pub fn write_evlrs<W: Write>(writer: &mut W, evlr_data: &[Vlr]) -> Result<(), Error> {
+    ////////////////////////////////
+    // Write the EVLRs to the file /
+    ////////////////////////////////
+    for evlr in evlr_data {
+        writer.write_u16::<LittleEndian>(evlr.reserved)?;
+        writer.write_all(fixed_length_string(&evlr.user_id, 16).as_bytes())?;
+        writer.write_u16::<LittleEndian>(evlr.record_id)?;
+        writer.write_u64::<LittleEndian>(evlr.record_length_after_header)?;
+        writer.write_all(fixed_length_string(&evlr.description, 32).as_bytes())?;
+        writer.write_all(&evlr.binary_data)?;
+    }
+    Ok(())
+}
+
+/// Reads the public header block at the start of a LAS file.
+pub fn read_header(header: &mut LasHeader, buffer: &[u8]) -> Result<(), Error> {
+    if buffer.len() < 227 {
+        return Err(Error::new(ErrorKind::InvalidData, "The file is too short to contain a LAS header."));
+    }
+
+    header.project_id_used = true;
+    header.version_major = buffer[24];
+    header.version_minor = buffer[25];
+    if header.version_major < 1 || header.version_major > 2 || header.version_minor > 5 {
+        // There's something wrong. It could be that the project ID values are not included in the header.
+        header.version_major = buffer[8];
+        header.version_minor = buffer[9];
+        if header.version_major < 1 || header.version_major > 2 || header.version_minor > 5 {
+            // There's something very wrong. Throw an error.
+            return Err(Error::new(ErrorKind::Other, "Either the file is formatted incorrectly or it is an unsupported LAS version."));
+        }
+        header.project_id_used = false;
+    }
+    let header_length = if header.version_minor >= 4 { 375 } else if header.version_minor == 3 { 235 } else { 227 };
+    if buffer.len() < header_length {
+        return Err(Error::new(ErrorKind::InvalidData, "The file is too short to contain a LAS header."));
+    }
+    unsafe {
+
+        //////////////////////////
+        // Read the File Header //
+        //////////////////////////
+        let mut offset: usize = 0;
+        header.file_signature = String::from_utf8_lossy(&buffer[offset..offset+4]).to_string();
+        if header.file_signature != "LASF" {
+            return Err(Error::new(ErrorKind::Other, "Either the file is formatted incorrectly or it is an unsupported LAS version."));
+        }
+        offset += 4;
+        header.file_source_id = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
+        offset += 2;
+        let ge_val = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
+        header.global_encoding = GlobalEncodingField { value: ge_val};
+        offset += 2;
+        if header.project_id_used {
+            header.project_id1 = mem::transmute::<[u8; 4], u32>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3]]);
+            offset += 4;
+            header.project_id2 = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
+            offset += 2;
+            header.project_id3 = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
+            offset += 2;
+            for i in 0..8 {
+                header.project_id4[i] = buffer[offset + i];
+            }
+            offset += 8;
+        }
+        // The version major and minor are read earlier.
+        // Two bytes that must be added to the offset here.
+        offset += 2;
+        //header.project_id4 = String::from_utf8_lossy(&buffer[16..24]).trim().to_string();
+        header.system_id = String::from_utf8_lossy(&buffer[offset..offset+32]).trim().to_string();
+        offset += 32;
+        header.generating_software = String::from_utf8_lossy(&buffer[offset..offset+32]).trim().to_string();
+        offset += 32;
+        // header.system_id = String::from_utf8_lossy(&buffer[26..58]).trim().to_string();
+        // header.generating_software = String::from_utf8_lossy(&buffer[58..90]).trim().to_string();
+        header.file_creation_day = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
+        offset += 2;
+        header.file_creation_year = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
+        offset += 2;
+        header.header_size = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
+        offset += 2;
+        header.offset_to_points = mem::transmute::<[u8; 4], u32>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3]]);
+        offset += 4;
+        header.number_of_vlrs = mem::transmute::<[u8; 4], u32>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3]]);
+        offset += 4;
+        header.point_format = buffer[offset];
+        offset += 1;
+        header.point_record_length = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
+        offset += 2;
+        header.number_of_points = mem::transmute::<[u8; 4], u32>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3]]) as u64;
+        offset += 4;
+
+        // let mut num_returns = 5;
+        // if header.version_major == 1_u8 && header.version_minor > 3_u8 {
+        //     num_returns = 7;
+        // }
+        // offset = 111;
+        for i in 0..5 {
+            header.number_of_points_by_return[i] = mem::transmute::<[u8; 4], u32>([buffer[offset + i * 4], buffer[offset + i * 4 + 1], buffer[offset + i * 4 + 2], buffer[offset + i * 4 + 3]]) as u64;
+            // header.number_of_points_by_return.push(mem::transmute::<[u8; 4], u32>([buffer[offset + i * 4], buffer[offset + i * 4 + 1], buffer[offset + i * 4 + 2], buffer[offset + i * 4 + 3]]));
+        }
+        offset += 5 * 4;
+        header.x_scale_factor = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        offset += 8;
+        header.y_scale_factor = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        offset += 8;
+        header.z_scale_factor = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        offset += 8;
+        header.x_offset = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        offset += 8;
+        header.y_offset = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        offset += 8;
+        header.z_offset = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        offset += 8;
+        header.max_x = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        offset += 8;
+        header.min_x = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        offset += 8;
+        header.max_y = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        offset += 8;
+        header.min_y = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        offset += 8;
+        header.max_z = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        offset += 8;
+        header.min_z = mem::transmute::<[u8; 8], f64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+
+        if header.version_major == 1 && header.version_minor >= 3 {
+            offset += 8;
+            header.waveform_data_start = mem::transmute::<[u8; 8], u64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+        }
+
+        if header.version_major == 1 && header.version_minor >= 4 {
+            offset += 8;
+            header.start_of_first_evlr = mem::transmute::<[u8; 8], u64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+            offset += 8;
+            header.number_of_evlrs = mem::transmute::<[u8; 4], u32>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3]]);
+            offset += 4;
+            // The 64-bit counts supersede the legacy 32-bit counts, which are zero
+            // for the extended point formats or files of more than 2^32 points.
+            header.number_of_points = mem::transmute::<[u8; 8], u64>([buffer[offset], buffer[offset+1], buffer[offset+2], buffer[offset+3], buffer[offset+4], buffer[offset+5], buffer[offset+6], buffer[offset+7]]);
+            offset += 8;
+            for i in 0..15 {
+                header.number_of_points_by_return[i] = mem::transmute::<[u8; 8], u64>([buffer[offset + i * 8], buffer[offset + i * 8 + 1], buffer[offset + i * 8 + 2], buffer[offset + i * 8 + 3], buffer[offset + i * 8 + 4], buffer[offset + i * 8 + 5], buffer[offset + i * 8 + 6], buffer[offset + i * 8 + 7]]);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the VLRs, which follow the header. The LASzip VLR of a compressed file
+/// is returned rather than added to the VLRs.
+pub fn read_vlrs(header: &mut LasHeader, buffer: &[u8], vlr_data: &mut Vec<Vlr>, geokeys: &mut GeoKeys) -> Result<Option<Vlr>, Error> {
+    // the VLR describing the compression of a LAZ file
+    let mut laszip_vlr: Option<Vlr> = None;
+
+    unsafe {
+        ///////////////////////
+        // Read the VLR data //
+        ///////////////////////
+        let mut offset = header.header_size as usize;
+        //vlr_data = vec![Vlr{0'u16, "".to_string(), 0'u16, 0'u16, "".to_string()}; header.number_of_vlrs as usize];
+        for _ in 0..header.number_of_vlrs {
+            if offset + 54 > buffer.len() {
+                return Err(Error::new(ErrorKind::InvalidData, "The file is shorter than its VLRs; it may be truncated."));
+            }
+            let mut vlr: Vlr = Default::default();
+            vlr.reserved = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
+            offset += 2;
+            vlr.user_id = String::from_utf8_lossy(&buffer[offset..offset+16]).trim().to_string();
+            offset += 16;
+            vlr.record_id = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]);
+            offset += 2;
+            vlr.record_length_after_header = mem::transmute::<[u8; 2], u16>([buffer[offset], buffer[offset+1]]) as u64;
+            offset += 2;
+            vlr.description = String::from_utf8_lossy(&buffer[offset..offset+32]).trim().to_string();
+            offset += 32;
+            if offset + vlr.record_length_after_header as usize > buffer.len() {
+                return Err(Error::new(ErrorKind::InvalidData, "The file is shorter than its VLRs; it may be truncated."));
+            }
+            // get the byte data
+            for i in 0..vlr.record_length_after_header {
+                vlr.binary_data.push(buffer[offset + i as usize]);
+            }
+            offset += vlr.record_length_after_header as usize;
+            
+            if vlr.record_id == 34_735 {
+                geokeys.add_key_directory(&vlr.binary_data);
+            } else if vlr.record_id == 34_736 {
+                geokeys.add_double_params(&vlr.binary_data);
+            } else if vlr.record_id == 34_737 {
+                geokeys.add_ascii_params(&vlr.binary_data);
+            }
+            if is_laszip_vlr(&vlr) {
+                laszip_vlr = Some(vlr);
+            } else {
+                vlr_data.push(vlr);
+            }
+        }
+    }
+
+    // The LASzip VLR is only meaningful to the compressed file, and the two high
+    // bits of the point format flag compressed points.
+    if laszip_vlr.is_some() {
+        header.number_of_vlrs -= 1;
+    }
+    header.point_format &= 0b0011_1111u8;
+    let format = header.point_format;
+    if format > 10 {
+        return Err(Error::new(ErrorKind::Other, format!("Unsupported point format {}.", format)));
+    }
+
+    Ok(laszip_vlr)
+}
+
+/// Reads the EVLRs from a buffer beginning at the first of them.
+pub fn read_evlrs(header: &LasHeader, buffer: &[u8], evlr_data: &mut Vec<Vlr>, geokeys: &mut GeoKeys) -> Result<(), Error> {
+    let mut pos = 0usize;
+    for _ in 0..header.number_of_evlrs {
+        if pos + 60 > buffer.len() {
+            return Err(Error::new(ErrorKind::InvalidData, "The file is shorter than its extended VLRs; it may be truncated."));
+        }
+        let mut vlr: Vlr = Default::default();
+        vlr.reserved = LittleEndian::read_u16(&buffer[pos..pos+2]);
+        vlr.user_id = String::from_utf8_lossy(&buffer[pos+2..pos+18]).trim().to_string();
+        vlr.record_id = LittleEndian::read_u16(&buffer[pos+18..pos+20]);
+        vlr.record_length_after_header = LittleEndian::read_u64(&buffer[pos+20..pos+28]);
+        vlr.description = String::from_utf8_lossy(&buffer[pos+28..pos+60]).trim().to_string();
+        pos += 60;
+        let end = pos + vlr.record_length_after_header as usize;
+        if end > buffer.len() {
+            return Err(Error::new(ErrorKind::InvalidData, "The file is shorter than its extended VLRs; it may be truncated."));
+        }
+        vlr.binary_data = buffer[pos..end].to_vec();
+        pos = end;
+
+        if vlr.record_id == 34_735 {
+            geokeys.add_key_directory(&vlr.binary_data);
+        } else if vlr.record_id == 34_736 {
+            geokeys.add_double_params(&vlr.binary_data);
+        } else if vlr.record_id == 34_737 {
+            geokeys.add_ascii_params(&vlr.binary_data);
+        }
+        evlr_data.push(vlr);
+    }
+    Ok(())
+}
+
+/// Determines whether the optional intensity and user data fields of the legacy
+/// point formats are present. The only way to do this is to compare the point
+/// record length, less the extra bytes described by the Extra Bytes VLR, with
+/// the standard length of the point format.
+pub fn optional_point_fields(header: &LasHeader, extra_bytes: &[ExtraBytesDescriptor]) -> (bool, bool) {
+    let rec_length = point_record_length(header.point_format);
+    let described: usize = extra_bytes.iter().map(|eb| eb.size()).sum();
+    if header.point_format < 6 && header.point_record_length as usize >= described {
+        let length = header.point_record_length - described as u16;
+        if length == rec_length - 2 {
+            return (false, true);
+        } else if length == rec_length - 1 {
+            return (true, false);
+        } else if length == rec_length - 3 {
+            return (false, false);
+        }
+    }
+    (true, true)
+}
+
+/// The number of extra bytes that follow the standard fields of each point record.
+pub fn num_extra_bytes(header: &LasHeader, use_point_intensity: bool, use_point_userdata: bool) -> usize {
+    let mut length = point_record_length(header.point_format) as usize;
+    if !use_point_intensity {
+        length -= 2;
+    }
+    if !use_point_userdata {
+        length -= 1;
+    }
+    (header.point_record_length as usize).saturating_sub(length)
+}
+
+/// Reads the extra bytes descriptors of the Extra Bytes VLR, if there is one.
+/// A descriptor that cannot be interpreted leaves the extra bytes undocumented.
+pub fn find_extra_bytes_descriptors(vlr_data: &[Vlr]) -> Vec<ExtraBytesDescriptor> {
+    match vlr_data.iter().find(|vlr| is_extra_bytes_vlr(vlr)) {
+        Some(vlr) => read_extra_bytes_descriptors(vlr).unwrap_or(vec![]),
+        None => vec![],
+    }
+}
+
+/// Reads a point record, in the point format of the header, from the current
+/// position of the reader.
+pub fn read_point_record(pr: &mut ByteOrderReader, header: &LasHeader, use_point_intensity: bool, use_point_userdata: bool) -> LidarPointRecord {
+    let format = header.point_format;
+    let mut p: PointData = Default::default();
+    p.x = pr.read_i32() as f64 * header.x_scale_factor + header.x_offset;
+    p.y = pr.read_i32() as f64 * header.y_scale_factor + header.y_offset;
+    p.z = pr.read_i32() as f64 * header.z_scale_factor + header.z_offset;
+    if use_point_intensity {
+        p.intensity = pr.read_u16();
+    }
+    if format < 6 {
+        // return number (3 bits), number of returns (3), scan direction, edge of flight line
+        let returns = pr.read_u8();
+        p.bit_field = PointBitField { value: (returns & 0b0000_0111u8) as u16 |
+            (((returns >> 3u8) & 0b0000_0111u8) as u16) << 4u16 | ((returns >> 6u8) as u16) << 10u16 };
+        // classification (5 bits), synthetic, keypoint, withheld
+        let class = pr.read_u8();
+        p.class_bit_field = ClassificationBitField { value: (class & 0b0001_1111u8) as u16 |
+            ((class >> 5u8) as u16) << 8u16 };
+        p.set_scan_angle_degrees(pr.read_i8() as f64);
+        if use_point_userdata {
+            p.user_data = pr.read_u8();
+        }
+    } else {
+        // return number (4 bits), number of returns (4); classification flags (4),
+        // scanner channel (2), scan direction, edge of flight line; classification
+        let returns = pr.read_u8();
+        let flags = pr.read_u8();
+        p.bit_field = PointBitField { value: returns as u16 | ((flags >> 4u8) as u16) << 8u16 };
+        p.class_bit_field = ClassificationBitField { value: pr.read_u8() as u16 |
+            ((flags & 0b0000_1111u8) as u16) << 8u16 };
+        p.user_data = pr.read_u8();
+        p.scan_angle = pr.read_i16();
+    }
+    p.point_source_id = pr.read_u16();
+
+    let mut gps_data = 0f64;
+    if has_gps_time(format) {
+        gps_data = pr.read_f64();
+    }
+    let mut rgb_data: RgbData = Default::default();
+    if has_rgb(format) {
+        rgb_data.red = pr.read_u16();
+        rgb_data.green = pr.read_u16();
+        rgb_data.blue = pr.read_u16();
+        if format == 8 || format == 10 {
+            rgb_data.nir = pr.read_u16();
+        }
+    }
+    let mut wave_packet: WaveformPacket = Default::default();
+    if has_waveform(format) {
+        wave_packet.packet_descriptor_index = pr.read_u8();
+        wave_packet.offset_to_waveform_data = pr.read_u64();
+        wave_packet.waveform_packet_size = pr.read_u32();
+        wave_packet.ret_point_waveform_loc = pr.read_f32();
+        wave_packet.xt = pr.read_f32();
+        wave_packet.yt = pr.read_f32();
+        wave_packet.zt = pr.read_f32();
+    }
+    create_point_record(format, p, gps_data, rgb_data, wave_packet)
+}
+
 fn browse_zip_archive<T, F, U>(buf: &mut T, browse_func: F) -> ZipResult<Vec<U>> where T: Read + Seek, F: Fn(&ZipFile) -> ZipResult<U> {
     let mut archive = ZipArchive::new(buf)?;
     (0..archive.len())
         .map(|i| archive.by_index(i).and_then(|file| browse_func(&file)))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{LasFile, LidarPointRecord, read_evlrs, read_header, read_vlrs, write_evlrs, write_header};
+    use lidar::header::LasHeader;
+    use lidar::point_data::{PointBitField, PointData};
+    use lidar::vlr::Vlr;
+    use raster::geotiff::geokeys::GeoKeys;
+    use std::env;
+    use std::fs;
+
+    fn vlr(user_id: &str, record_id: u16, binary_data: Vec<u8>) -> Vlr {
+        Vlr {
+            user_id: user_id.to_string(),
+            record_id: record_id,
+            record_length_after_header: binary_data.len() as u64,
+            description: "test".to_string(),
+            binary_data: binary_data,
+            ..Default::default()
+        }
+    }
+
+    fn las14_header(point_format: u8) -> LasHeader {
+        let mut header = LasHeader::default();
+        header.project_id_used = true;
+        header.point_format = point_format;
+        header.point_record_length = 30;
+        header.x_scale_factor = 0.01;
+        header.y_scale_factor = 0.01;
+        header.z_scale_factor = 0.01;
+        header.min_x = 1.0;
+        header.max_x = 2.0;
+        header.min_y = 3.0;
+        header.max_y = 4.0;
+        header.min_z = 5.0;
+        header.max_z = 6.0;
+        header
+    }
+
+    // writes a header, its VLRs and EVLRs, without point records, and reads them back
+    fn round_trip(header: &mut LasHeader, vlrs: &mut Vec<Vlr>, evlrs: &mut Vec<Vlr>) -> (LasHeader, Vec<Vlr>, Vec<Vlr>) {
+        header.number_of_vlrs = vlrs.len() as u32;
+        let mut buffer = vec![];
+        write_header(&mut buffer, header, vlrs, evlrs, None, 0).unwrap();
+        assert_eq!(buffer.len(), header.offset_to_points as usize);
+        write_evlrs(&mut buffer, evlrs).unwrap();
+
+        let mut input_header = LasHeader::default();
+        read_header(&mut input_header, &buffer).unwrap();
+        let mut geokeys = GeoKeys::default();
+        let mut input_vlrs = vec![];
+        read_vlrs(&mut input_header, &buffer, &mut input_vlrs, &mut geokeys).unwrap();
+        let mut input_evlrs = vec![];
+        read_evlrs(&input_header, &buffer[input_header.start_of_first_evlr as usize..], &mut input_evlrs, &mut geokeys).unwrap();
+        (input_header, input_vlrs, input_evlrs)
+    }
+
+    #[test]
+    fn test_return_number_zero_is_counted_as_first_return() {
+        let file_name = env::temp_dir().join("wbt_las_test_return_zero.las").to_str().unwrap().to_string();
+        let mut output = LasFile::new(&file_name, "w").unwrap();
+        output.add_header(LasHeader { project_id_used: true, ..Default::default() });
+        for (i, ret) in [0u16, 1, 2, 0].iter().enumerate() {
+            let point_data = PointData { x: i as f64, y: 1.0, z: 2.0, bit_field: PointBitField { value: ret | 2 << 4 }, ..Default::default() };
+            output.add_point_record(LidarPointRecord::PointRecord0 { point_data: point_data });
+        }
+        assert_eq!(output.header.number_of_points, 4);
+        assert_eq!(output.header.number_of_points_by_return[0], 3);
+        assert_eq!(output.header.number_of_points_by_return[1], 1);
+        output.write().unwrap();
+
+        let input = LasFile::new(&file_name, "r").unwrap();
+        fs::remove_file(&file_name).unwrap();
+        assert_eq!(input.header.number_of_points, 4);
+        assert_eq!(input.header.number_of_points_by_return[0], 3);
+        assert_eq!(input.header.number_of_points_by_return[1], 1);
+        assert_eq!(input[0].return_number(), 1);
+    }
+
+    #[test]
+    fn test_las14_header_round_trip() {
+        let mut header = las14_header(6);
+        header.number_of_points = 5_000_000_000u64;
+        for i in 0..15 {
+            header.number_of_points_by_return[i] = (i as u64 + 1) * 300_000_000u64;
+        }
+        let wkt = b"GEOGCS[\"WGS 84\",DATUM[\"WGS_1984\",SPHEROID[\"WGS 84\",6378137,298.257223563]],PRIMEM[\"Greenwich\",0],UNIT[\"degree\",0.0174532925199433]]\0".to_vec();
+        let mut vlrs = vec![vlr("LASF_Projection", 2112, wkt.clone()), vlr("test", 1, vec![1, 2, 3])];
+        let mut evlrs = vec![vlr("test", 2, vec![4u8; 70_000]), vlr("test", 3, vec![5, 6])];
+        let (input, input_vlrs, input_evlrs) = round_trip(&mut header, &mut vlrs, &mut evlrs);
+
+        assert_eq!((input.version_major, input.version_minor), (1, 4));
+        assert_eq!(input.header_size, 375);
+        assert_eq!(input.point_format, 6);
+        // the extended formats flag their WKT co-ordinate reference system
+        assert_eq!(input.global_encoding.value & 0b0001_0000u16, 0b0001_0000u16);
+        assert_eq!(input.number_of_points, 5_000_000_000u64);
+        for i in 0..15 {
+            assert_eq!(input.number_of_points_by_return[i], (i as u64 + 1) * 300_000_000u64);
+        }
+        assert_eq!((input.min_x, input.max_x, input.min_y, input.max_y, input.min_z, input.max_z), (1.0, 2.0, 3.0, 4.0, 5.0, 6.0));
+        assert_eq!(input.offset_to_points, header.offset_to_points);
+
+        assert_eq!(input_vlrs.len(), 2);
+        assert_eq!(input_vlrs[0].record_id, 2112);
+        assert_eq!(input_vlrs[0].binary_data, wkt);
+        assert_eq!(input_vlrs[1].binary_data, vec![1, 2, 3]);
+
+        assert_eq!(input.number_of_evlrs, 2);
+        assert_eq!(input.start_of_first_evlr, input.offset_to_points as u64);
+        assert_eq!(input_evlrs.len(), 2);
+        assert_eq!(input_evlrs[0].record_id, 2);
+        assert_eq!(input_evlrs[0].record_length_after_header, 70_000);
+        assert_eq!(input_evlrs[0].binary_data, vec![4u8; 70_000]);
+        assert_eq!(input_evlrs[1].binary_data, vec![5, 6]);
+    }
+
+    #[test]
+    fn test_legacy_format_with_many_points_is_written_as_las14() {
+        let mut header = las14_header(1);
+        header.number_of_points = u32::max_value() as u64 + 10;
+        header.number_of_points_by_return[0] = u32::max_value() as u64 + 10;
+        let (input, _, _) = round_trip(&mut header, &mut vec![], &mut vec![]);
+        assert_eq!((input.version_major, input.version_minor), (1, 4));
+        assert_eq!(input.global_encoding.value & 0b0001_0000u16, 0);
+        assert_eq!(input.number_of_points, u32::max_value() as u64 + 10);
+        assert_eq!(input.number_of_points_by_return[0], u32::max_value() as u64 + 10);
+    }
+
+    #[test]
+    fn test_extended_format_with_only_geokeys_is_rejected() {
+        let mut header = las14_header(7);
+        let mut vlrs = vec![vlr("LASF_Projection", 34_735, vec![1, 0, 1, 0, 0, 0, 0, 0])];
+        header.number_of_vlrs = 1;
+        let mut buffer = vec![];
+        assert!(write_header(&mut buffer, &mut header, &mut vlrs, &mut vec![], None, 0).is_err());
+
+        // GeoKeys accompanying a WKT record are kept
+        vlrs.push(vlr("LASF_Projection", 2112, b"LOCAL_CS[\"test\"]\0".to_vec()));
+        let (input, input_vlrs, _) = round_trip(&mut header, &mut vlrs, &mut vec![]);
+        assert_eq!(input.global_encoding.value & 0b0001_0000u16, 0b0001_0000u16);
+        assert_eq!(input_vlrs.len(), 2);
+    }
+}
@@ -0,0 +1,340 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+
+// A spatial index of the points of a LAS or LAZ file, stored in a sidecar file
+// next to it, in the spirit of the .lax files of LASindex. The index divides the
+// extent of the file into a grid of square cells and lists, for each cell, the
+// intervals of point indices containing its points. Reading the points within an
+// area then requires only the records of these intervals, which, for files whose
+// points are stored in acquisition or spatially sorted order, are a small part
+// of the file.
+
+use std::cmp;
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufReader, BufWriter, Error, ErrorKind};
+use std::path::Path;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use lidar::header::LasHeader;
+use lidar::las_stream::LasReader;
+use structures::BoundingBox;
+
+/// The number of points that the cells of the index hold on average.
+const POINTS_PER_CELL: f64 = 5_000f64;
+
+/// Intervals of a cell separated by fewer points than this are merged, keeping
+/// the index small at the cost of reading a few points outside of the cell.
+const INTERVAL_MERGE_GAP: u64 = 64;
+
+const MAGIC: &[u8; 4] = b"WBLI";
+const VERSION: u16 = 1;
+
+/// A grid index of the points of a LAS file. Points beyond the bounds given by
+/// the header of the file are assigned to the nearest edge cells.
+#[derive(Default, Clone, Debug)]
+pub struct LasIndex {
+    pub min_x: f64,
+    pub max_x: f64,
+    pub min_y: f64,
+    pub max_y: f64,
+    pub cell_size: f64,
+    pub columns: usize,
+    pub rows: usize,
+    // the size and number of points of the indexed file, used to detect an
+    // index that no longer describes its file
+    file_size: u64,
+    number_of_points: u64,
+    // the intervals [start, end) of the point indices of each cell, in row-major order
+    cells: Vec<Vec<(u64, u64)>>,
+}
+
+impl LasIndex {
+    /// Creates the index of a LAS file, reading its points as a stream.
+    pub fn create(las_file_name: &str) -> Result<LasIndex, Error> {
+        let mut reader = LasReader::new(las_file_name)?;
+        let mut index = LasIndex::new(&reader.header, fs::metadata(las_file_name)?.len());
+        let mut i = 0u64;
+        while let Some(point) = reader.next() {
+            let p = point?.get_point_data();
+            let cell = index.get_cell(p.x, p.y);
+            let intervals = &mut index.cells[cell];
+            let extends_last = match intervals.last() {
+                Some(&(_, end)) => end + INTERVAL_MERGE_GAP >= i,
+                None => false,
+            };
+            if extends_last {
+                intervals.last_mut().unwrap().1 = i + 1;
+            } else {
+                intervals.push((i, i + 1));
+            }
+            i += 1;
+        }
+        Ok(index)
+    }
+
+    // Creates an empty index for a file, with a grid sized to hold about
+    // POINTS_PER_CELL points per cell.
+    fn new(header: &LasHeader, file_size: u64) -> LasIndex {
+        let (width, height) = ((header.max_x - header.min_x).max(0f64), (header.max_y - header.min_y).max(0f64));
+        let num_cells = (header.number_of_points as f64 / POINTS_PER_CELL).max(1f64);
+        let mut cell_size = (width * height / num_cells).sqrt();
+        if !(cell_size > 0f64) {
+            // the points lie on a line
+            cell_size = width.max(height) / num_cells;
+        }
+        if !(cell_size > 0f64) || !cell_size.is_finite() {
+            cell_size = 1f64;
+        }
+        let columns = (width / cell_size).floor() as usize + 1;
+        let rows = (height / cell_size).floor() as usize + 1;
+        LasIndex {
+            min_x: header.min_x,
+            max_x: header.max_x,
+            min_y: header.min_y,
+            max_y: header.max_y,
+            cell_size: cell_size,
+            columns: columns,
+            rows: rows,
+            file_size: file_size,
+            number_of_points: header.number_of_points,
+            cells: vec![vec![]; columns * rows],
+        }
+    }
+
+    /// Returns the name of the sidecar file holding the index of a LAS file, which
+    /// appends '.wbi' to the file's name, extension included, so that e.g. the
+    /// indices of 'tile.las' and 'tile.laz' do not share a file.
+    pub fn get_file_name(las_file_name: &str) -> String {
+        format!("{}.wbi", las_file_name)
+    }
+
+    /// Reads the index of a LAS file, whose header is needed to verify that the
+    /// index is up to date. An error of kind NotFound is returned if the file
+    /// has no index, and of kind InvalidData if the index is out of date.
+    pub fn read(las_file_name: &str, header: &LasHeader) -> Result<LasIndex, Error> {
+        let file_name = LasIndex::get_file_name(las_file_name);
+        if !Path::new(&file_name).exists() {
+            return Err(Error::new(ErrorKind::NotFound, format!("The LAS file has no spatial index ({}).", file_name)));
+        }
+        let mut reader = BufReader::new(File::open(&file_name)?);
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC || reader.read_u16::<LittleEndian>()? != VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, format!("{} is not a supported spatial index file.", file_name)));
+        }
+        let mut index: LasIndex = Default::default();
+        index.file_size = reader.read_u64::<LittleEndian>()?;
+        index.number_of_points = reader.read_u64::<LittleEndian>()?;
+        index.min_x = reader.read_f64::<LittleEndian>()?;
+        index.max_x = reader.read_f64::<LittleEndian>()?;
+        index.min_y = reader.read_f64::<LittleEndian>()?;
+        index.max_y = reader.read_f64::<LittleEndian>()?;
+        index.cell_size = reader.read_f64::<LittleEndian>()?;
+        index.columns = reader.read_u32::<LittleEndian>()? as usize;
+        index.rows = reader.read_u32::<LittleEndian>()? as usize;
+        if index.file_size != fs::metadata(las_file_name)?.len() ||
+            index.number_of_points != header.number_of_points ||
+            index.min_x != header.min_x || index.max_x != header.max_x ||
+            index.min_y != header.min_y || index.max_y != header.max_y {
+            return Err(Error::new(ErrorKind::InvalidData, format!("The spatial index {} is out of date.", file_name)));
+        }
+        index.cells = Vec::with_capacity(index.columns * index.rows);
+        for _ in 0..index.columns * index.rows {
+            let num_intervals = reader.read_u32::<LittleEndian>()? as usize;
+            let mut intervals = Vec::with_capacity(num_intervals);
+            for _ in 0..num_intervals {
+                let start = reader.read_u64::<LittleEndian>()?;
+                let end = reader.read_u64::<LittleEndian>()?;
+                intervals.push((start, end));
+            }
+            index.cells.push(intervals);
+        }
+        Ok(index)
+    }
+
+    /// Writes the index to the sidecar file of a LAS file.
+    pub fn write(&self, las_file_name: &str) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(LasIndex::get_file_name(las_file_name))?);
+        writer.write_all(MAGIC)?;
+        writer.write_u16::<LittleEndian>(VERSION)?;
+        writer.write_u64::<LittleEndian>(self.file_size)?;
+        writer.write_u64::<LittleEndian>(self.number_of_points)?;
+        writer.write_f64::<LittleEndian>(self.min_x)?;
+        writer.write_f64::<LittleEndian>(self.max_x)?;
+        writer.write_f64::<LittleEndian>(self.min_y)?;
+        writer.write_f64::<LittleEndian>(self.max_y)?;
+        writer.write_f64::<LittleEndian>(self.cell_size)?;
+        writer.write_u32::<LittleEndian>(self.columns as u32)?;
+        writer.write_u32::<LittleEndian>(self.rows as u32)?;
+        for intervals in &self.cells {
+            writer.write_u32::<LittleEndian>(intervals.len() as u32)?;
+            for &(start, end) in intervals {
+                writer.write_u64::<LittleEndian>(start)?;
+                writer.write_u64::<LittleEndian>(end)?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Deletes the sidecar file of a LAS file, if there is one. It is called
+    /// whenever a LAS file is written, since its index would then be out of date.
+    pub fn remove(las_file_name: &str) {
+        let file_name = LasIndex::get_file_name(las_file_name);
+        if Path::new(&file_name).exists() {
+            let _ = fs::remove_file(file_name);
+        }
+    }
+
+    /// The number of point intervals held by the index.
+    pub fn num_intervals(&self) -> usize {
+        self.cells.iter().map(|intervals| intervals.len()).sum()
+    }
+
+    /// Returns the sorted, non-overlapping intervals [start, end) of the indices
+    /// of the points in the cells overlapping a bounding box. They include all of
+    /// the points within the box, as well as others near it.
+    pub fn query(&self, bb: &BoundingBox) -> Vec<(u64, u64)> {
+        let mut intervals = vec![];
+        let (min_col, min_row) = self.get_column_and_row(bb.min_x, bb.min_y);
+        let (max_col, max_row) = self.get_column_and_row(bb.max_x, bb.max_y);
+        for row in min_row..max_row + 1 {
+            for col in min_col..max_col + 1 {
+                intervals.extend_from_slice(&self.cells[row * self.columns + col]);
+            }
+        }
+        intervals.sort();
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(intervals.len());
+        for (start, end) in intervals {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => {
+                    last.1 = cmp::max(last.1, end);
+                    continue;
+                },
+                _ => (),
+            }
+            merged.push((start, end));
+        }
+        merged
+    }
+
+    fn get_column_and_row(&self, x: f64, y: f64) -> (usize, usize) {
+        let col = ((x - self.min_x) / self.cell_size).floor().max(0f64).min((self.columns - 1) as f64) as usize;
+        let row = ((y - self.min_y) / self.cell_size).floor().max(0f64).min((self.rows - 1) as f64) as usize;
+        (col, row)
+    }
+
+    fn get_cell(&self, x: f64, y: f64) -> usize {
+        let (col, row) = self.get_column_and_row(x, y);
+        row * self.columns + col
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LasIndex;
+    use lidar::header::LasHeader;
+    use lidar::las::{LasFile, LidarPointRecord};
+    use lidar::point_data::PointData;
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+    use structures::{BoundingBox, Point2D};
+
+    // Writes a file of points along back-and-forth scan lines across a 300 by 200
+    // area, in the order of their acquisition.
+    fn write_scan(file_name: &str) {
+        let mut output = LasFile::new(file_name, "w").unwrap();
+        output.add_header(LasHeader { project_id_used: true, point_format: 1, ..Default::default() });
+        let mut state = 3u64;
+        for line in 0..200 {
+            for i in 0..300 {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                let jitter = (state >> 40) as f64 / (1u64 << 24) as f64;
+                let x = if line % 2 == 0 { i as f64 } else { 299f64 - i as f64 } + jitter;
+                let point_data = PointData { x: x, y: line as f64 + 0.5 * jitter, z: jitter, ..Default::default() };
+                output.add_point_record(LidarPointRecord::PointRecord1 { point_data: point_data, gps_data: (line * 300 + i) as f64 });
+            }
+        }
+        output.write().unwrap();
+    }
+
+    fn coordinates(lf: &LasFile) -> Vec<(f64, f64, f64)> {
+        (0..lf.header.number_of_points as usize).map(|i| (lf[i].x, lf[i].y, lf[i].z)).collect()
+    }
+
+    fn check_indexed_reads(file_name: &str) {
+        let full = LasFile::new(file_name, "r").unwrap();
+        let all = coordinates(&full);
+        let bb = BoundingBox::new(52.3, 40.2, 131.7, 95.9);
+        let rings = vec![
+            vec![Point2D::new(20.0, 20.0), Point2D::new(280.0, 30.0), Point2D::new(150.0, 180.0), Point2D::new(20.0, 20.0)],
+            vec![Point2D::new(120.0, 50.0), Point2D::new(160.0, 50.0), Point2D::new(140.0, 90.0), Point2D::new(120.0, 50.0)],
+        ];
+
+        // reads without an index scan the whole file
+        LasIndex::remove(file_name);
+        let scanned_box = LasFile::read_within_box(file_name, &bb).unwrap();
+        let scanned_polygon = LasFile::read_within_polygon(file_name, &rings).unwrap();
+        let expected: Vec<(f64, f64, f64)> = all.iter().cloned().filter(|p| bb.contains_point(p.0, p.1)).collect();
+        assert!(expected.len() > 1_000);
+        assert_eq!(coordinates(&scanned_box), expected);
+
+        let index = LasIndex::create(file_name).unwrap();
+        assert!(index.columns * index.rows > 1);
+        index.write(file_name).unwrap();
+        let index = LasIndex::read(file_name, &full.header).unwrap();
+        let num_read: u64 = index.query(&bb).iter().map(|&(start, end)| end - start).sum();
+        assert!(num_read < full.header.number_of_points / 2);
+
+        let indexed_box = LasFile::read_within_box(file_name, &bb).unwrap();
+        let indexed_polygon = LasFile::read_within_polygon(file_name, &rings).unwrap();
+        assert_eq!(coordinates(&indexed_box), expected);
+        assert_eq!(indexed_box.header.number_of_points, scanned_box.header.number_of_points);
+        assert_eq!((indexed_box.header.min_x, indexed_box.header.max_y), (scanned_box.header.min_x, scanned_box.header.max_y));
+        assert_eq!(coordinates(&indexed_polygon), coordinates(&scanned_polygon));
+        assert!(indexed_polygon.header.number_of_points > 1_000);
+        for i in 0..indexed_box.header.number_of_points as usize {
+            assert_eq!(indexed_box.get_record(i).get_gps_time(), scanned_box.get_record(i).get_gps_time());
+        }
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(LasIndex::get_file_name("/data/tile.las"), "/data/tile.las.wbi");
+        assert_eq!(LasIndex::get_file_name("/data/tile.laz"), "/data/tile.laz.wbi");
+        assert_eq!(LasIndex::get_file_name("tile.las.zip"), "tile.las.zip.wbi");
+    }
+
+    #[test]
+    fn test_indexed_reads_match_full_scans() {
+        let dir = env::temp_dir();
+        let las_file = dir.join("wbt_las_index_test.las").to_str().unwrap().to_string();
+        let laz_file = dir.join("wbt_las_index_test.laz").to_str().unwrap().to_string();
+        write_scan(&las_file);
+        write_scan(&laz_file);
+        check_indexed_reads(&las_file);
+        check_indexed_reads(&laz_file);
+
+        // the LAS and LAZ files with the same stem keep separate, valid indices
+        let las = LasFile::new(&las_file, "r").unwrap();
+        let laz = LasFile::new(&laz_file, "r").unwrap();
+        assert!(LasIndex::read(&las_file, &las.header).is_ok());
+        assert!(LasIndex::read(&laz_file, &laz.header).is_ok());
+
+        // writing a file removes its index
+        write_scan(&las_file);
+        assert!(!Path::new(&LasIndex::get_file_name(&las_file)).exists());
+        assert!(Path::new(&LasIndex::get_file_name(&laz_file)).exists());
+
+        LasIndex::remove(&laz_file);
+        fs::remove_file(&las_file).unwrap();
+        fs::remove_file(&laz_file).unwrap();
+    }
+}
@@ -0,0 +1,569 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+
+// Streaming access to the point records of LAS and LAZ files. Unlike LasFile,
+// which holds all of a file's points in memory, LasReader decodes the points a
+// chunk at a time and LasWriter writes them as they are added, updating the
+// header once the point counts and bounds are known. Single-pass tools can
+// therefore process files that are larger than the available memory.
+
+extern crate zip;
+
+use std::f64;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::io::{BufReader, BufWriter, Cursor, Error, ErrorKind, SeekFrom};
+use std::mem;
+use std::path::Path;
+use byteorder::{LittleEndian, WriteBytesExt};
+use io_utils::{ByteOrderReader, Endianness};
+use lidar::extra_bytes::{ExtraBytesDescriptor, is_extra_bytes_vlr, no_data_extra_bytes, read_extra_bytes_descriptors};
+use lidar::header::LasHeader;
+use lidar::las::*;
+use lidar::las_index::LasIndex;
+use lidar::laszip::{LasZipChunk, LasZipCompressor, LasZipDecompressor};
+use lidar::vlr::Vlr;
+use raster::geotiff::geokeys::GeoKeys;
+use self::zip::CompressionMethod;
+use self::zip::write::{FileOptions, ZipWriter};
+
+/// The number of uncompressed point records that are read at a time.
+const RECORDS_PER_READ: usize = 50_000;
+
+trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+/// Reads the point records of a LAS or LAZ file sequentially. The reader is an
+/// iterator over the records, which are decoded a chunk at a time, so that its
+/// memory use does not depend on the size of the file. Zipped LAS files are the
+/// exception; they cannot be read incrementally and are decompressed into memory.
+pub struct LasReader {
+    file_name: String,
+    pub header: LasHeader,
+    pub vlr_data: Vec<Vlr>,
+    pub evlr_data: Vec<Vlr>,
+    pub geokeys: GeoKeys,
+    use_point_intensity: bool,
+    use_point_userdata: bool,
+    extra_bytes: Vec<ExtraBytesDescriptor>,
+    num_extra_bytes: usize,
+    source: Box<ReadSeek>,
+    decompressor: Option<LasZipDecompressor>,
+    laz_chunks: Vec<LasZipChunk>,
+    // the index of the first point of each LAZ chunk
+    chunk_starts: Vec<u64>,
+    next_chunk: usize,
+    records: ByteOrderReader,
+    // the index of the first point in the record buffer
+    buffer_start: u64,
+    // the position of the last record read within the record buffer
+    last_record: Option<usize>,
+    next_point: u64,
+    end_point: u64,
+}
+
+impl LasReader {
+    /// Opens a LAS, LAZ or zipped LAS file and reads its header, VLRs and EVLRs.
+    pub fn new(file_name: &str) -> Result<LasReader, Error> {
+        let mut source: Box<ReadSeek> = if file_name.to_lowercase().ends_with(".zip") {
+            let file = File::open(file_name)?;
+            let mut zip = zip::ZipArchive::new(file)?;
+            let mut f = zip.by_index(0)?;
+            if !f.name().to_lowercase().ends_with(".las") {
+                return Err(Error::new(ErrorKind::InvalidData,
+                    "The data file contained within zipped archive does not have the proper 'las' extension."));
+            }
+            let mut buffer = Vec::with_capacity(f.size() as usize);
+            f.read_to_end(&mut buffer)?;
+            Box::new(Cursor::new(buffer))
+        } else {
+            Box::new(BufReader::new(File::open(file_name)?))
+        };
+        let file_size = source.seek(SeekFrom::End(0))?;
+
+        // the header is read first, to find the size of the VLRs that follow it
+        let mut header: LasHeader = Default::default();
+        let mut buffer = vec![0u8; file_size.min(375) as usize];
+        source.seek(SeekFrom::Start(0))?;
+        source.read_exact(&mut buffer)?;
+        read_header(&mut header, &buffer)?;
+        if header.offset_to_points as u64 > file_size {
+            return Err(Error::new(ErrorKind::InvalidData, "The file is shorter than its VLRs; it may be truncated."));
+        }
+        buffer = vec![0u8; header.offset_to_points as usize];
+        source.seek(SeekFrom::Start(0))?;
+        source.read_exact(&mut buffer)?;
+        let mut vlr_data = vec![];
+        let mut geokeys: GeoKeys = Default::default();
+        let laszip_vlr = read_vlrs(&mut header, &buffer, &mut vlr_data, &mut geokeys)?;
+
+        let mut evlr_data = vec![];
+        if header.number_of_evlrs > 0 {
+            buffer.clear();
+            source.seek(SeekFrom::Start(header.start_of_first_evlr.min(file_size)))?;
+            source.read_to_end(&mut buffer)?;
+            read_evlrs(&header, &buffer, &mut evlr_data, &mut geokeys)?;
+        }
+
+        let num_points = header.number_of_points;
+        let record_length = header.point_record_length as u64;
+        let mut extra_bytes = find_extra_bytes_descriptors(&vlr_data);
+        let (use_point_intensity, use_point_userdata) = optional_point_fields(&header, &extra_bytes);
+        let num_extra_bytes = num_extra_bytes(&header, use_point_intensity, use_point_userdata);
+        let mut described = 0usize;
+        extra_bytes.retain(|eb| { described += eb.size(); described <= num_extra_bytes });
+        let mut decompressor = None;
+        let mut laz_chunks = vec![];
+        let mut chunk_starts = vec![];
+        match laszip_vlr {
+            Some(ref vlr) => {
+                let d = LasZipDecompressor::new(vlr)?;
+                if d.record_length() as u64 != record_length {
+                    return Err(Error::new(ErrorKind::InvalidData, "The point record length of the LAZ file does not match its LASzip VLR."));
+                }
+                let mut end_of_points = file_size;
+                if header.number_of_evlrs > 0 && header.start_of_first_evlr > header.offset_to_points as u64 {
+                    end_of_points = end_of_points.min(header.start_of_first_evlr);
+                }
+                laz_chunks = d.read_chunk_table(&mut source, header.offset_to_points as u64, end_of_points, num_points)?;
+                if laz_chunks.iter().any(|chunk| chunk.offset + chunk.byte_count > file_size) {
+                    return Err(Error::new(ErrorKind::InvalidData, "The file is shorter than its compressed points; it may be truncated."));
+                }
+                let mut start = 0u64;
+                for chunk in &laz_chunks {
+                    chunk_starts.push(start);
+                    start += chunk.num_points;
+                }
+                decompressor = Some(d);
+            },
+            None => {
+                if header.offset_to_points as u64 + num_points * record_length > file_size {
+                    return Err(Error::new(ErrorKind::InvalidData, "The file is shorter than its point records; it may be truncated."));
+                }
+                source.seek(SeekFrom::Start(header.offset_to_points as u64))?;
+            },
+        }
+
+        Ok(LasReader {
+            file_name: file_name.to_string(),
+            header: header,
+            vlr_data: vlr_data,
+            evlr_data: evlr_data,
+            geokeys: geokeys,
+            use_point_intensity: use_point_intensity,
+            use_point_userdata: use_point_userdata,
+            extra_bytes: extra_bytes,
+            num_extra_bytes: num_extra_bytes,
+            source: source,
+            decompressor: decompressor,
+            laz_chunks: laz_chunks,
+            chunk_starts: chunk_starts,
+            next_chunk: 0,
+            records: ByteOrderReader::new(vec![], Endianness::LittleEndian),
+            buffer_start: 0,
+            last_record: None,
+            next_point: 0,
+            end_point: num_points,
+        })
+    }
+
+    pub fn get_file_name(&self) -> String {
+        self.file_name.clone()
+    }
+
+    /// The index of the next point record, which is the number of records that
+    /// have been read so far unless the reader has been positioned by seek_range.
+    pub fn num_points_read(&self) -> u64 {
+        self.next_point
+    }
+
+    /// Positions the reader so that it iterates over the point records with
+    /// indices from start up to, but not including, end. For LAZ files, only
+    /// the chunks containing these records are decompressed.
+    pub fn seek_range(&mut self, start: u64, end: u64) -> Result<(), Error> {
+        let end = end.min(self.header.number_of_points);
+        let start = start.min(end);
+        self.next_point = start;
+        self.end_point = end;
+        self.last_record = None;
+        let record_length = self.header.point_record_length as u64;
+        let num_buffered = self.records.len() as u64 / record_length;
+        if start >= self.buffer_start && start < self.buffer_start + num_buffered {
+            // the record is already decoded
+            self.records.seek(((start - self.buffer_start) * record_length) as usize);
+            return Ok(());
+        }
+        self.records.buffer.clear();
+        self.records.seek(0);
+        if self.decompressor.is_some() {
+            self.next_chunk = match self.chunk_starts.binary_search(&start) {
+                Ok(i) => i,
+                Err(i) => i.max(1) - 1,
+            };
+        } else {
+            self.source.seek(SeekFrom::Start(self.header.offset_to_points as u64 + start * record_length))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the descriptors of the extra bytes attributes of the point records.
+    pub fn get_extra_byte_attributes(&self) -> &[ExtraBytesDescriptor] {
+        &self.extra_bytes
+    }
+
+    /// Returns the extra bytes of the point record that was read last.
+    pub fn get_extra_bytes(&self) -> &[u8] {
+        match self.last_record {
+            Some(start) => {
+                let end = start + self.header.point_record_length as usize;
+                &self.records.buffer[end - self.num_extra_bytes..end]
+            },
+            None => &[],
+        }
+    }
+
+    // Decodes the next chunk of point records, which includes the next point,
+    // into the record buffer.
+    fn read_records(&mut self) -> Result<(), Error> {
+        let mut buffer = mem::replace(&mut self.records.buffer, vec![]);
+        buffer.clear();
+        match self.decompressor {
+            Some(ref decompressor) => {
+                let chunk = match self.laz_chunks.get(self.next_chunk) {
+                    Some(chunk) => *chunk,
+                    None => return Err(Error::new(ErrorKind::InvalidData, "The LAZ file contains fewer points than its header indicates.")),
+                };
+                let mut data = vec![0u8; chunk.byte_count as usize];
+                self.source.seek(SeekFrom::Start(chunk.offset))?;
+                self.source.read_exact(&mut data)?;
+                decompressor.decompress_chunk(&data, chunk.num_points as usize, &mut buffer)?;
+                self.buffer_start = self.chunk_starts[self.next_chunk];
+                self.next_chunk += 1;
+            },
+            None => {
+                let num_records = RECORDS_PER_READ.min((self.end_point - self.next_point) as usize);
+                buffer.resize(num_records * self.header.point_record_length as usize, 0u8);
+                self.source.read_exact(&mut buffer)?;
+                self.buffer_start = self.next_point;
+            },
+        }
+        let position = ((self.next_point - self.buffer_start) * self.header.point_record_length as u64) as usize;
+        if position >= buffer.len() {
+            return Err(Error::new(ErrorKind::InvalidData, "The file contains fewer points than its header indicates."));
+        }
+        self.records = ByteOrderReader::new(buffer, Endianness::LittleEndian);
+        self.records.seek(position);
+        Ok(())
+    }
+}
+
+impl Iterator for LasReader {
+    type Item = Result<LidarPointRecord, Error>;
+
+    fn next(&mut self) -> Option<Result<LidarPointRecord, Error>> {
+        if self.next_point >= self.end_point {
+            return None;
+        }
+        if self.records.pos >= self.records.len() {
+            if let Err(e) = self.read_records() {
+                // a read error ends the iteration
+                self.next_point = self.end_point;
+                return Some(Err(e));
+            }
+        }
+        let start = self.records.pos;
+        let record = read_point_record(&mut self.records, &self.header, self.use_point_intensity, self.use_point_userdata);
+        self.records.seek(start + self.header.point_record_length as usize);
+        self.last_record = Some(start);
+        self.next_point += 1;
+        Some(Ok(record))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end_point - self.next_point) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Writes a LAS or LAZ file one point record at a time. The header, which is
+/// written with the first point record, is updated with the point counts and
+/// bounds when the writer is closed. Because the bounds are not known while the
+/// points are written, the scale factors and offsets of the header passed to
+/// the writer are used as they are, rather than derived from the bounds as
+/// LasFile does. Zipped LAS files are written to a temporary LAS file, which is
+/// compressed into the archive on closing.
+pub struct LasWriter {
+    file_name: String,
+    pub header: LasHeader,
+    pub vlr_data: Vec<Vlr>,
+    pub evlr_data: Vec<Vlr>,
+    pub use_point_intensity: bool,
+    pub use_point_userdata: bool,
+    extra_bytes: Vec<ExtraBytesDescriptor>,
+    num_extra_bytes: usize,
+    las_file_name: String,
+    writer: Option<BufWriter<File>>,
+    compressor: Option<LasZipCompressor>,
+    laszip_vlr: Option<Vlr>,
+    record: Vec<u8>,
+}
+
+impl LasWriter {
+    /// Creates a writer using a header, e.g. that of an existing file. The point
+    /// counts and bounds of the header are reset and its VLRs are not copied.
+    pub fn new(file_name: &str, header: LasHeader) -> LasWriter {
+        let mut header = header;
+        header.number_of_vlrs = 0;
+        header.number_of_evlrs = 0;
+        header.number_of_points = 0;
+        header.number_of_points_by_return = [0; 15];
+        header.version_major = 1;
+        header.version_minor = 3;
+        header.min_x = f64::INFINITY;
+        header.max_x = f64::NEG_INFINITY;
+        header.min_y = f64::INFINITY;
+        header.max_y = f64::NEG_INFINITY;
+        header.min_z = f64::INFINITY;
+        header.max_z = f64::NEG_INFINITY;
+        header.system_id = "WhiteboxTools by John Lindsay   ".to_string();
+        header.generating_software = "WhiteboxTools                   ".to_string();
+        if header.x_scale_factor == 0f64 { header.x_scale_factor = 0.0001; }
+        if header.y_scale_factor == 0f64 { header.y_scale_factor = 0.0001; }
+        if header.z_scale_factor == 0f64 { header.z_scale_factor = 0.0001; }
+
+        let las_file_name = if file_name.to_lowercase().ends_with(".zip") {
+            format!("{}.tmp", file_name)
+        } else {
+            file_name.to_string()
+        };
+        LasWriter {
+            file_name: file_name.to_string(),
+            header: header,
+            vlr_data: vec![],
+            evlr_data: vec![],
+            use_point_intensity: true,
+            use_point_userdata: true,
+            extra_bytes: vec![],
+            num_extra_bytes: 0,
+            las_file_name: las_file_name,
+            writer: None,
+            compressor: None,
+            laszip_vlr: None,
+            record: vec![],
+        }
+    }
+
+    /// Creates a writer initialized with the header, VLRs, EVLRs and extra bytes
+    /// attributes of the file being read by a LasReader.
+    pub fn initialize_using_reader(file_name: &str, input: &LasReader) -> LasWriter {
+        let mut output = LasWriter::new(file_name, input.header.clone());
+        for vlr in &input.vlr_data {
+            output.add_vlr(vlr.clone());
+        }
+        for evlr in &input.evlr_data {
+            output.add_evlr(evlr.clone());
+        }
+        // including any extra bytes that the Extra Bytes VLR does not describe
+        output.num_extra_bytes = input.num_extra_bytes;
+        output
+    }
+
+    pub fn get_file_name(&self) -> String {
+        self.file_name.clone()
+    }
+
+    pub fn add_vlr(&mut self, vlr: Vlr) {
+        if self.writer.is_some() {
+            panic!("The VLRs of a LAS file must be added before any point records.");
+        }
+        if is_extra_bytes_vlr(&vlr) {
+            self.extra_bytes = read_extra_bytes_descriptors(&vlr).unwrap_or(vec![]);
+            self.num_extra_bytes = self.extra_bytes.iter().map(|eb| eb.size()).sum();
+        }
+        self.vlr_data.push(vlr);
+        self.header.number_of_vlrs += 1;
+    }
+
+    /// Adds an extended VLR, which is written after the point records. Files
+    /// containing EVLRs are written as LAS 1.4.
+    pub fn add_evlr(&mut self, evlr: Vlr) {
+        if self.writer.is_some() {
+            panic!("The EVLRs of a LAS file must be added before any point records.");
+        }
+        self.evlr_data.push(evlr);
+        self.header.number_of_evlrs += 1;
+    }
+
+    /// Adds a point record. Its extra bytes attributes, if the file has any, are
+    /// set to their no-data values, or zero for those without one.
+    pub fn add_point_record(&mut self, point: LidarPointRecord) -> Result<(), Error> {
+        self.add_point_record_with_extra_bytes(point, &[])
+    }
+
+    /// Adds a point record along with its extra bytes, e.g. those returned by
+    /// LasReader::get_extra_bytes for a file with the same extra bytes attributes.
+    pub fn add_point_record_with_extra_bytes(&mut self, point: LidarPointRecord, extra_bytes: &[u8]) -> Result<(), Error> {
+        if self.writer.is_none() {
+            self.begin()?;
+        }
+        let mut extra_bytes = extra_bytes;
+        let no_data;
+        if extra_bytes.len() != self.num_extra_bytes {
+            no_data = {
+                let mut bytes = no_data_extra_bytes(&self.extra_bytes, self.num_extra_bytes);
+                let n = extra_bytes.len().min(self.num_extra_bytes);
+                bytes[0..n].copy_from_slice(&extra_bytes[0..n]);
+                bytes
+            };
+            extra_bytes = &no_data;
+        }
+        if let Some(ref mut writer) = self.writer {
+            match self.compressor {
+                Some(ref mut compressor) => {
+                    self.record.clear();
+                    write_point_record(&mut self.record, &self.header, self.use_point_intensity, self.use_point_userdata, &point, extra_bytes)?;
+                    compressor.compress(&self.record);
+                    let chunks = compressor.take_chunks();
+                    if !chunks.is_empty() {
+                        writer.write_all(&chunks)?;
+                    }
+                },
+                None => write_point_record(writer, &self.header, self.use_point_intensity, self.use_point_userdata, &point, extra_bytes)?,
+            }
+        }
+
+        self.header.add_point(&point.get_point_data());
+        Ok(())
+    }
+
+    // Creates the file and writes a provisional header, which is replaced when
+    // the writer is closed.
+    fn begin(&mut self) -> Result<(), Error> {
+        let format = self.header.point_format;
+        if format > 10 {
+            return Err(Error::new(ErrorKind::Other, "Unsupported point format"));
+        }
+        let is_laz = self.file_name.to_lowercase().ends_with(".laz");
+        set_point_record_length(&mut self.header, &mut self.use_point_intensity, &mut self.use_point_userdata, is_laz, self.num_extra_bytes);
+        if is_laz {
+            let compressor = LasZipCompressor::new(format, self.header.point_record_length)?;
+            self.laszip_vlr = Some(compressor.vlr());
+            self.compressor = Some(compressor);
+        }
+
+        let f = File::create(&self.las_file_name)?;
+        let mut writer = BufWriter::new(f);
+        write_header(&mut writer, &mut self.header, &mut self.vlr_data, &mut self.evlr_data, self.laszip_vlr.as_ref(), 0)?;
+        if is_laz {
+            // the position of the chunk table, which is known once the points are written
+            writer.write_i64::<LittleEndian>(-1)?;
+        }
+        self.writer = Some(writer);
+        Ok(())
+    }
+
+    /// Finishes writing the file, updating its header. The file is incomplete
+    /// until the writer is closed.
+    pub fn close(mut self) -> Result<(), Error> {
+        LasIndex::remove(&self.file_name);
+        if self.writer.is_none() {
+            self.begin()?;
+        }
+        let mut writer = self.writer.take().unwrap();
+        let offset_to_points = self.header.offset_to_points as u64;
+
+        let mut point_data_size = self.header.number_of_points * self.header.point_record_length as u64;
+        if let Some(compressor) = self.compressor.take() {
+            let (chunks, chunk_table) = compressor.finish();
+            writer.write_all(&chunks)?;
+            let chunk_table_start = writer.seek(SeekFrom::Current(0))?;
+            writer.write_all(&chunk_table)?;
+            point_data_size = chunk_table_start + chunk_table.len() as u64 - offset_to_points;
+            writer.seek(SeekFrom::Start(offset_to_points))?;
+            writer.write_i64::<LittleEndian>(chunk_table_start as i64)?;
+            writer.seek(SeekFrom::End(0))?;
+        }
+
+        // The header is rewritten in place, so its size cannot change. A LAS 1.3
+        // header cannot hold a count of more than 2^32 points.
+        let mut header_data = vec![];
+        write_header(&mut header_data, &mut self.header, &mut self.vlr_data, &mut self.evlr_data, self.laszip_vlr.as_ref(), point_data_size)?;
+        if header_data.len() as u64 != offset_to_points {
+            return Err(Error::new(ErrorKind::Other, "The number of points is too large for a LAS 1.3 file; please use one of the extended point formats (6-10)."));
+        }
+        write_evlrs(&mut writer, &self.evlr_data)?;
+        writer.seek(SeekFrom::Start(0))?;
+        writer.write_all(&header_data)?;
+        writer.flush()?;
+        drop(writer);
+
+        if self.las_file_name != self.file_name {
+            let f = File::create(&self.file_name)?;
+            let mut zip_writer = ZipWriter::new(f);
+            let path = Path::new(&self.file_name);
+            let lasfile_name = if self.file_name.to_lowercase().ends_with(".las.zip") {
+                path.file_stem().unwrap().to_str().unwrap().to_owned()
+            } else {
+                path.file_stem().unwrap().to_str().unwrap().to_owned() + ".las"
+            };
+            let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+            zip_writer.start_file(lasfile_name, options)?;
+            let mut las_file = File::open(&self.las_file_name)?;
+            io::copy(&mut las_file, &mut zip_writer)?;
+            zip_writer.finish()?;
+            fs::remove_file(&self.las_file_name)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LasReader, LasWriter};
+    use lidar::header::LasHeader;
+    use lidar::las::LidarPointRecord;
+    use lidar::point_data::{PointBitField, PointData};
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn test_return_number_zero_is_counted_as_first_return() {
+        let file_name = env::temp_dir().join("wbt_las_stream_test_return_zero.las").to_str().unwrap().to_string();
+        let header = LasHeader {
+            project_id_used: true,
+            point_format: 1,
+            x_scale_factor: 0.01,
+            y_scale_factor: 0.01,
+            z_scale_factor: 0.01,
+            ..Default::default()
+        };
+        let mut output = LasWriter::new(&file_name, header);
+        for (i, ret) in [0u16, 0, 1, 3].iter().enumerate() {
+            let point_data = PointData { x: i as f64, y: 2.0 * i as f64, z: 1.0, bit_field: PointBitField { value: ret | 3 << 4 }, ..Default::default() };
+            output.add_point_record(LidarPointRecord::PointRecord1 { point_data: point_data, gps_data: i as f64 }).unwrap();
+        }
+        assert_eq!(output.header.number_of_points_by_return[0], 3);
+        assert_eq!(output.header.number_of_points_by_return[2], 1);
+        output.close().unwrap();
+
+        let input = LasReader::new(&file_name).unwrap();
+        assert_eq!(input.header.number_of_points, 4);
+        assert_eq!(input.header.number_of_points_by_return[0], 3);
+        assert_eq!(input.header.number_of_points_by_return[2], 1);
+        assert_eq!((input.header.max_x, input.header.max_y), (3.0, 6.0));
+        let points: Vec<LidarPointRecord> = input.map(|p| p.unwrap()).collect();
+        fs::remove_file(&file_name).unwrap();
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[0].get_point_data().return_number(), 1);
+        assert_eq!(points[3].get_point_data().return_number(), 3);
+    }
+}
@@ -0,0 +1,129 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+
+// Support for processing a LiDAR data set that has been split into tiles, e.g.
+// by the LidarTile tool. Neighbourhood-based tools that process each tile in
+// isolation produce artefacts along the tile edges, where the neighbourhoods of
+// points are cut short. A tile is therefore read along with a buffer of the
+// points of its neighbours, so that the points (or raster cells) within its own
+// extent, its core, are processed with the same context as if the whole data
+// set had been processed at once.
+
+use std::fs;
+use std::fs::DirBuilder;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use lidar::las::LasFile;
+use lidar::las_stream::LasReader;
+use structures::BoundingBox;
+
+/// The LAS and LAZ tiles of a data set and their extents.
+#[derive(Default, Clone, Debug)]
+pub struct LasTileSet {
+    pub file_names: Vec<String>,
+    pub extents: Vec<BoundingBox>,
+}
+
+impl LasTileSet {
+    /// Finds the LAS and LAZ files within a directory, in name order, and reads
+    /// their extents from their headers. Files without points are left out.
+    pub fn new(directory: &str) -> Result<LasTileSet, Error> {
+        let mut file_names = vec![];
+        for entry in fs::read_dir(directory)? {
+            let s = format!("{}", entry?.path().display());
+            if s.to_lowercase().ends_with(".las") || s.to_lowercase().ends_with(".laz") {
+                file_names.push(s);
+            }
+        }
+        file_names.sort();
+        if file_names.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("No LAS files were found in {}.", directory)));
+        }
+        LasTileSet::from_files(&file_names)
+    }
+
+    /// Creates a tile set from a list of LAS files.
+    pub fn from_files(file_names: &[String]) -> Result<LasTileSet, Error> {
+        let mut tiles = LasTileSet::default();
+        for file_name in file_names {
+            let header = match LasReader::new(file_name) {
+                Ok(reader) => reader.header,
+                Err(err) => return Err(Error::new(err.kind(), format!("Error reading file {}: {}", file_name, err))),
+            };
+            if header.number_of_points > 0 {
+                tiles.file_names.push(file_name.clone());
+                tiles.extents.push(BoundingBox::new(header.min_x, header.min_y, header.max_x, header.max_y));
+            }
+        }
+        Ok(tiles)
+    }
+
+    /// The number of tiles.
+    pub fn len(&self) -> usize {
+        self.file_names.len()
+    }
+
+    /// Returns whether the set contains no tiles.
+    pub fn is_empty(&self) -> bool {
+        self.file_names.is_empty()
+    }
+
+    /// Returns the tiles, other than the tile itself, whose extents lie within a
+    /// distance of the extent of a tile.
+    pub fn get_neighbours(&self, tile: usize, buffer: f64) -> Vec<usize> {
+        let bb = self.extents[tile].buffer(buffer);
+        (0..self.len()).filter(|&i| i != tile && bb.overlaps(&self.extents[i])).collect()
+    }
+
+    /// Reads a tile along with the points of its neighbours that lie within a
+    /// buffer distance of its extent, which are converted to the point format of
+    /// the tile. The points of the tile itself come first; the number of them is
+    /// returned with the file, so that points with a lower index are core points
+    /// and the others are buffer points. The neighbouring tiles are read using
+    /// their spatial indices (see LasIndex), if they have been indexed.
+    pub fn read_with_buffer(&self, tile: usize, buffer: f64) -> Result<(LasFile, usize), Error> {
+        let mut las = LasFile::new(&self.file_names[tile], "r")?;
+        let num_core_points = las.header.number_of_points as usize;
+        let bb = self.extents[tile].buffer(buffer);
+        for i in self.get_neighbours(tile, buffer) {
+            let neighbour = match LasFile::read_within_box(&self.file_names[i], &bb) {
+                Ok(lf) => lf,
+                Err(err) => return Err(Error::new(err.kind(), format!("Error reading file {}: {}", self.file_names[i], err))),
+            };
+            las.append_points(&neighbour);
+        }
+        Ok((las, num_core_points))
+    }
+
+    /// Returns the names of the files within an output directory to which the
+    /// processed tiles are written, which have the names of the tiles. The
+    /// directory is created if it does not exist; it may not be the directory of
+    /// any of the tiles, which would otherwise be overwritten.
+    pub fn get_output_file_names(&self, output_directory: &str) -> Result<Vec<String>, Error> {
+        DirBuilder::new().recursive(true).create(output_directory)?;
+        let directory = fs::canonicalize(output_directory)?;
+        let mut output_files = vec![];
+        for file_name in &self.file_names {
+            let path = Path::new(file_name);
+            let tile_directory = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => fs::canonicalize(parent)?,
+                _ => fs::canonicalize(".")?,
+            };
+            if tile_directory == directory {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "The output directory must differ from the directory containing the tiles."));
+            }
+            let name = match path.file_name() {
+                Some(name) => name,
+                None => return Err(Error::new(ErrorKind::InvalidInput, format!("{} is not a file name.", file_name))),
+            };
+            output_files.push(format!("{}", directory.join(name).display()));
+        }
+        Ok(output_files)
+    }
+}
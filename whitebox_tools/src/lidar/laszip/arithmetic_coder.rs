@@ -0,0 +1,431 @@
+// The adaptive arithmetic coder used by LASzip, which is derived from
+// Amir Said's FastAC. The encoder and decoder must update their models
+// identically for the streams to remain in sync, so the constants and the
+// order of operations below follow the LASzip implementation exactly.
+
+const AC_MIN_LENGTH: u32 = 0x0100_0000; // threshold for renormalization
+const AC_MAX_LENGTH: u32 = 0xFFFF_FFFF; // maximum AC interval length
+
+const BM_LENGTH_SHIFT: u32 = 13; // length bits discarded before mult.
+const BM_MAX_COUNT: u32 = 1 << BM_LENGTH_SHIFT; // for adaptive models
+
+const DM_LENGTH_SHIFT: u32 = 15; // length bits discarded before mult.
+const DM_MAX_COUNT: u32 = 1 << DM_LENGTH_SHIFT; // for adaptive models
+
+/// An adaptive model of a binary symbol.
+#[derive(Clone, Debug)]
+pub struct ArithmeticBitModel {
+    bit_0_count: u32,
+    bit_count: u32,
+    bit_0_prob: u32,
+    bits_until_update: u32,
+    update_cycle: u32,
+}
+
+impl ArithmeticBitModel {
+    pub fn new() -> ArithmeticBitModel {
+        // start with frequent updates
+        ArithmeticBitModel {
+            bit_0_count: 1,
+            bit_count: 2,
+            bit_0_prob: 1 << (BM_LENGTH_SHIFT - 1),
+            bits_until_update: 4,
+            update_cycle: 4,
+        }
+    }
+
+    fn update(&mut self) {
+        // halve counts when a threshold is reached
+        self.bit_count += self.update_cycle;
+        if self.bit_count > BM_MAX_COUNT {
+            self.bit_count = (self.bit_count + 1) >> 1;
+            self.bit_0_count = (self.bit_0_count + 1) >> 1;
+            if self.bit_0_count == self.bit_count {
+                self.bit_count += 1;
+            }
+        }
+        // compute scaled bit 0 probability
+        let scale = 0x8000_0000u32 / self.bit_count;
+        self.bit_0_prob = (self.bit_0_count * scale) >> (31 - BM_LENGTH_SHIFT);
+        // set frequency of model updates
+        self.update_cycle = (5 * self.update_cycle) >> 2;
+        if self.update_cycle > 64 {
+            self.update_cycle = 64;
+        }
+        self.bits_until_update = self.update_cycle;
+    }
+}
+
+/// An adaptive model of a symbol from an alphabet of up to 2048 symbols.
+#[derive(Clone, Debug)]
+pub struct ArithmeticModel {
+    symbols: u32,
+    last_symbol: u32,
+    distribution: Vec<u32>,
+    symbol_count: Vec<u32>,
+    total_count: u32,
+    update_cycle: u32,
+    symbols_until_update: u32,
+}
+
+impl ArithmeticModel {
+    pub fn new(symbols: u32) -> ArithmeticModel {
+        debug_assert!(symbols >= 2 && symbols <= (1 << 11));
+        let mut model = ArithmeticModel {
+            symbols: symbols,
+            last_symbol: symbols - 1,
+            distribution: vec![0u32; symbols as usize],
+            symbol_count: vec![1u32; symbols as usize],
+            total_count: 0,
+            update_cycle: symbols,
+            symbols_until_update: 0,
+        };
+        model.update();
+        model.update_cycle = (symbols + 6) >> 1;
+        model.symbols_until_update = model.update_cycle;
+        model
+    }
+
+    fn update(&mut self) {
+        // halve counts when a threshold is reached
+        self.total_count += self.update_cycle;
+        if self.total_count > DM_MAX_COUNT {
+            self.total_count = 0;
+            for n in 0..self.symbols as usize {
+                self.symbol_count[n] = (self.symbol_count[n] + 1) >> 1;
+                self.total_count += self.symbol_count[n];
+            }
+        }
+        // compute cumulative distribution
+        let mut sum = 0u32;
+        let scale = 0x8000_0000u32 / self.total_count;
+        for k in 0..self.symbols as usize {
+            self.distribution[k] = (scale * sum) >> (31 - DM_LENGTH_SHIFT);
+            sum += self.symbol_count[k];
+        }
+        // set frequency of model updates
+        self.update_cycle = (5 * self.update_cycle) >> 2;
+        let max_cycle = (self.symbols + 6) << 3;
+        if self.update_cycle > max_cycle {
+            self.update_cycle = max_cycle;
+        }
+        self.symbols_until_update = self.update_cycle;
+    }
+}
+
+/// Encodes symbols into a growing byte buffer.
+pub struct ArithmeticEncoder {
+    base: u32,
+    length: u32,
+    out: Vec<u8>,
+}
+
+impl ArithmeticEncoder {
+    pub fn new() -> ArithmeticEncoder {
+        ArithmeticEncoder {
+            base: 0,
+            length: AC_MAX_LENGTH,
+            out: vec![],
+        }
+    }
+
+    /// Flushes the coder and returns the encoded bytes.
+    pub fn done(mut self) -> Vec<u8> {
+        let init_base = self.base;
+        let another_byte = if self.length > 2 * AC_MIN_LENGTH {
+            self.base = self.base.wrapping_add(AC_MIN_LENGTH);
+            self.length = AC_MIN_LENGTH >> 1;
+            true
+        } else {
+            self.base = self.base.wrapping_add(AC_MIN_LENGTH >> 1);
+            self.length = AC_MIN_LENGTH >> 9;
+            false
+        };
+        if init_base > self.base {
+            self.propagate_carry();
+        }
+        self.renorm_enc_interval();
+        // two or three zero bytes keep the output in sync with the decoder's reads
+        self.out.push(0);
+        self.out.push(0);
+        if another_byte {
+            self.out.push(0);
+        }
+        self.out
+    }
+
+    pub fn encode_bit(&mut self, m: &mut ArithmeticBitModel, sym: u32) {
+        let x = m.bit_0_prob * (self.length >> BM_LENGTH_SHIFT);
+        if sym == 0 {
+            self.length = x;
+            m.bit_0_count += 1;
+        } else {
+            let init_base = self.base;
+            self.base = self.base.wrapping_add(x);
+            self.length -= x;
+            if init_base > self.base {
+                self.propagate_carry();
+            }
+        }
+        if self.length < AC_MIN_LENGTH {
+            self.renorm_enc_interval();
+        }
+        m.bits_until_update -= 1;
+        if m.bits_until_update == 0 {
+            m.update();
+        }
+    }
+
+    pub fn encode_symbol(&mut self, m: &mut ArithmeticModel, sym: u32) {
+        let init_base = self.base;
+        if sym == m.last_symbol {
+            let x = m.distribution[sym as usize] * (self.length >> DM_LENGTH_SHIFT);
+            self.base = self.base.wrapping_add(x);
+            self.length -= x;
+        } else {
+            self.length >>= DM_LENGTH_SHIFT;
+            let x = m.distribution[sym as usize] * self.length;
+            self.base = self.base.wrapping_add(x);
+            self.length = m.distribution[sym as usize + 1] * self.length - x;
+        }
+        if init_base > self.base {
+            self.propagate_carry();
+        }
+        if self.length < AC_MIN_LENGTH {
+            self.renorm_enc_interval();
+        }
+        m.symbol_count[sym as usize] += 1;
+        m.symbols_until_update -= 1;
+        if m.symbols_until_update == 0 {
+            m.update();
+        }
+    }
+
+    /// Writes a raw value of up to 32 bits.
+    pub fn write_bits(&mut self, mut bits: u32, mut sym: u32) {
+        if bits > 19 {
+            self.write_short((sym & 0xFFFF) as u16);
+            sym >>= 16;
+            bits -= 16;
+        }
+        let init_base = self.base;
+        self.length >>= bits;
+        self.base = self.base.wrapping_add(sym * self.length);
+        if init_base > self.base {
+            self.propagate_carry();
+        }
+        if self.length < AC_MIN_LENGTH {
+            self.renorm_enc_interval();
+        }
+    }
+
+    pub fn write_short(&mut self, sym: u16) {
+        let init_base = self.base;
+        self.length >>= 16;
+        self.base = self.base.wrapping_add(sym as u32 * self.length);
+        if init_base > self.base {
+            self.propagate_carry();
+        }
+        self.renorm_enc_interval();
+    }
+
+    pub fn write_int(&mut self, sym: u32) {
+        self.write_short((sym & 0xFFFF) as u16); // lower 16 bits
+        self.write_short((sym >> 16) as u16); // upper 16 bits
+    }
+
+    fn propagate_carry(&mut self) {
+        for b in self.out.iter_mut().rev() {
+            if *b == 0xFF {
+                *b = 0;
+            } else {
+                *b += 1;
+                break;
+            }
+        }
+    }
+
+    fn renorm_enc_interval(&mut self) {
+        loop {
+            self.out.push((self.base >> 24) as u8);
+            self.base <<= 8;
+            self.length <<= 8;
+            if self.length >= AC_MIN_LENGTH {
+                break;
+            }
+        }
+    }
+}
+
+/// Decodes symbols from a byte slice.
+pub struct ArithmeticDecoder<'a> {
+    value: u32,
+    length: u32,
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ArithmeticDecoder<'a> {
+    pub fn new(data: &'a [u8]) -> ArithmeticDecoder<'a> {
+        let mut dec = ArithmeticDecoder {
+            value: 0,
+            length: AC_MAX_LENGTH,
+            data: data,
+            pos: 0,
+        };
+        for _ in 0..4 {
+            dec.value = (dec.value << 8) | dec.get_byte() as u32;
+        }
+        dec
+    }
+
+    fn get_byte(&mut self) -> u8 {
+        // reading past the end of the data is harmless in a well-formed
+        // stream; the encoder pads its output for the reads of its final bytes
+        let b = if self.pos < self.data.len() { self.data[self.pos] } else { 0u8 };
+        self.pos += 1;
+        b
+    }
+
+    pub fn decode_bit(&mut self, m: &mut ArithmeticBitModel) -> u32 {
+        let x = m.bit_0_prob * (self.length >> BM_LENGTH_SHIFT);
+        let sym = if self.value >= x { 1 } else { 0 };
+        if sym == 0 {
+            self.length = x;
+            m.bit_0_count += 1;
+        } else {
+            self.value -= x;
+            self.length -= x;
+        }
+        if self.length < AC_MIN_LENGTH {
+            self.renorm_dec_interval();
+        }
+        m.bits_until_update -= 1;
+        if m.bits_until_update == 0 {
+            m.update();
+        }
+        sym
+    }
+
+    pub fn decode_symbol(&mut self, m: &mut ArithmeticModel) -> u32 {
+        // bisection search of the interval containing the value
+        let mut y = self.length;
+        let mut x = 0u32;
+        let mut sym = 0u32;
+        self.length >>= DM_LENGTH_SHIFT;
+        let mut n = m.symbols;
+        let mut k = n >> 1;
+        loop {
+            let z = self.length.wrapping_mul(m.distribution[k as usize]);
+            if z > self.value {
+                n = k;
+                y = z;
+            } else {
+                sym = k;
+                x = z;
+            }
+            k = (sym + n) >> 1;
+            if k == sym {
+                break;
+            }
+        }
+        self.value -= x;
+        self.length = y - x;
+        if self.length < AC_MIN_LENGTH {
+            self.renorm_dec_interval();
+        }
+        m.symbol_count[sym as usize] += 1;
+        m.symbols_until_update -= 1;
+        if m.symbols_until_update == 0 {
+            m.update();
+        }
+        sym
+    }
+
+    /// Reads a raw value of up to 32 bits.
+    pub fn read_bits(&mut self, mut bits: u32) -> u32 {
+        if bits > 19 {
+            let lower = self.read_short() as u32;
+            bits -= 16;
+            let upper = self.read_bits(bits) << 16;
+            return upper | lower;
+        }
+        self.length >>= bits;
+        let sym = self.value / self.length;
+        self.value -= self.length * sym;
+        if self.length < AC_MIN_LENGTH {
+            self.renorm_dec_interval();
+        }
+        sym
+    }
+
+    pub fn read_short(&mut self) -> u16 {
+        self.length >>= 16;
+        let sym = self.value / self.length;
+        self.value -= self.length * sym;
+        self.renorm_dec_interval();
+        sym as u16
+    }
+
+    pub fn read_int(&mut self) -> u32 {
+        let lower = self.read_short() as u32;
+        let upper = self.read_short() as u32;
+        (upper << 16) | lower
+    }
+
+    fn renorm_dec_interval(&mut self) {
+        loop {
+            self.value = (self.value << 8) | self.get_byte() as u32;
+            self.length <<= 8;
+            if self.length >= AC_MIN_LENGTH {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut state = 7u64;
+        let mut values = vec![];
+        for _ in 0..20_000 {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            values.push((state >> 32) as u32);
+        }
+
+        let mut enc = ArithmeticEncoder::new();
+        let mut bit_model = ArithmeticBitModel::new();
+        let mut model = ArithmeticModel::new(37);
+        for (i, v) in values.iter().enumerate() {
+            // skewed bits and symbols, so that the models adapt
+            enc.encode_bit(&mut bit_model, if v % 10 == 0 { 1 } else { 0 });
+            enc.encode_symbol(&mut model, if i % 3 == 0 { v % 37 } else { v % 4 });
+            enc.write_bits(1 + v % 32, v >> (31 - v % 32));
+            enc.write_short(*v as u16);
+            enc.write_int(*v);
+        }
+        let data = enc.done();
+
+        let mut dec = ArithmeticDecoder::new(&data);
+        let mut bit_model = ArithmeticBitModel::new();
+        let mut model = ArithmeticModel::new(37);
+        for (i, v) in values.iter().enumerate() {
+            assert_eq!(dec.decode_bit(&mut bit_model), if v % 10 == 0 { 1 } else { 0 });
+            assert_eq!(dec.decode_symbol(&mut model), if i % 3 == 0 { v % 37 } else { v % 4 });
+            assert_eq!(dec.read_bits(1 + v % 32), v >> (31 - v % 32));
+            assert_eq!(dec.read_short(), *v as u16);
+            assert_eq!(dec.read_int(), *v);
+        }
+    }
+
+    #[test]
+    fn test_empty_stream() {
+        let data = ArithmeticEncoder::new().done();
+        assert!(data.len() >= 2);
+        assert!(data.len() <= 8);
+    }
+}
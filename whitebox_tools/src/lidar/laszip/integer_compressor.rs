@@ -0,0 +1,198 @@
+use lidar::laszip::arithmetic_coder::*;
+
+// Compresses an integer as the corrector, i.e. the difference, between it and
+// a prediction. The corrector is coded in two parts: the number of bits k
+// needed to hold it, which is modelled separately for each context, and the
+// k bits themselves, the highest of which are modelled and the remainder of
+// which are written raw.
+
+pub struct IntegerCompressor {
+    k: u32,
+    corr_range: u32,
+    corr_min: i32,
+    bits_high: u32,
+    m_bits: Vec<ArithmeticModel>,
+    m_corrector0: ArithmeticBitModel,
+    m_corrector: Vec<ArithmeticModel>,
+}
+
+impl IntegerCompressor {
+    /// Creates a compressor of values of the specified number of bits (32 for
+    /// unbounded values) using the specified number of contexts.
+    pub fn new(bits: u32, contexts: u32) -> IntegerCompressor {
+        IntegerCompressor::with_range(bits, contexts, 8, 0)
+    }
+
+    pub fn with_range(bits: u32, contexts: u32, bits_high: u32, range: u32) -> IntegerCompressor {
+        let (corr_bits, corr_range, corr_min) = if range != 0 {
+            // the corrector's significant bits and range
+            let mut corr_bits = 0u32;
+            let mut r = range;
+            while r != 0 {
+                r >>= 1;
+                corr_bits += 1;
+            }
+            if range == 1u32 << (corr_bits - 1) {
+                corr_bits -= 1;
+            }
+            (corr_bits, range, -((range / 2) as i32))
+        } else if bits > 0 && bits < 32 {
+            (bits, 1u32 << bits, -(((1u32 << bits) / 2) as i32))
+        } else {
+            (32, 0, i32::min_value())
+        };
+
+        let m_bits = (0..contexts).map(|_| ArithmeticModel::new(corr_bits + 1)).collect();
+        // a zero-bit corrector uses the bit model, m_corrector0, so the
+        // first symbol model is never used
+        let mut m_corrector = vec![ArithmeticModel::new(2)];
+        for i in 1..(corr_bits + 1) {
+            if i <= bits_high {
+                m_corrector.push(ArithmeticModel::new(1 << i));
+            } else {
+                m_corrector.push(ArithmeticModel::new(1 << bits_high));
+            }
+        }
+
+        IntegerCompressor {
+            k: 0,
+            corr_range: corr_range,
+            corr_min: corr_min,
+            bits_high: bits_high,
+            m_bits: m_bits,
+            m_corrector0: ArithmeticBitModel::new(),
+            m_corrector: m_corrector,
+        }
+    }
+
+    /// The number of bits of the last corrector, which some items use as a context.
+    pub fn get_k(&self) -> u32 {
+        self.k
+    }
+
+    pub fn compress(&mut self, enc: &mut ArithmeticEncoder, pred: i32, real: i32, context: u32) {
+        let corr_max = self.corr_min.wrapping_add(self.corr_range.wrapping_sub(1) as i32);
+        let mut corr = real.wrapping_sub(pred);
+        if corr < self.corr_min {
+            corr = corr.wrapping_add(self.corr_range as i32);
+        } else if corr > corr_max {
+            corr = corr.wrapping_sub(self.corr_range as i32);
+        }
+        self.write_corrector(enc, corr, context as usize);
+    }
+
+    pub fn decompress(&mut self, dec: &mut ArithmeticDecoder, pred: i32, context: u32) -> i32 {
+        let mut real = pred.wrapping_add(self.read_corrector(dec, context as usize));
+        if real < 0 {
+            real = real.wrapping_add(self.corr_range as i32);
+        } else if real as u32 >= self.corr_range {
+            real = real.wrapping_sub(self.corr_range as i32);
+        }
+        real
+    }
+
+    fn write_corrector(&mut self, enc: &mut ArithmeticEncoder, c: i32, context: usize) {
+        // find the tightest interval [-(2^k - 1) ... +(2^k)] that contains c
+        let mut c1 = if c <= 0 { (c as i64).abs() as u32 } else { (c - 1) as u32 };
+        self.k = 0;
+        while c1 != 0 {
+            c1 >>= 1;
+            self.k += 1;
+        }
+        let k = self.k;
+        // the number k is between 0 and corr_bits and describes the interval
+        enc.encode_symbol(&mut self.m_bits[context], k);
+
+        if k != 0 {
+            if k < 32 {
+                // translate c into the interval [0 ... 2^k - 1]
+                let c = (if c < 0 { c.wrapping_add(((1u32 << k) - 1) as i32) } else { c - 1 }) as u32;
+                if k <= self.bits_high {
+                    enc.encode_symbol(&mut self.m_corrector[k as usize], c);
+                } else {
+                    // for larger k, the lower bits are written raw
+                    let k1 = k - self.bits_high;
+                    let c1 = c & ((1u32 << k1) - 1);
+                    enc.encode_symbol(&mut self.m_corrector[k as usize], c >> k1);
+                    enc.write_bits(k1, c1);
+                }
+            }
+        } else {
+            // c is either 0 or 1
+            enc.encode_bit(&mut self.m_corrector0, c as u32);
+        }
+    }
+
+    fn read_corrector(&mut self, dec: &mut ArithmeticDecoder, context: usize) -> i32 {
+        self.k = dec.decode_symbol(&mut self.m_bits[context]);
+        let k = self.k;
+        if k != 0 {
+            if k < 32 {
+                let c = if k <= self.bits_high {
+                    dec.decode_symbol(&mut self.m_corrector[k as usize])
+                } else {
+                    let k1 = k - self.bits_high;
+                    let c = dec.decode_symbol(&mut self.m_corrector[k as usize]);
+                    let c1 = dec.read_bits(k1);
+                    (c << k1) | c1
+                };
+                // translate c back into its correct interval
+                if c >= (1u32 << (k - 1)) {
+                    c.wrapping_add(1) as i32
+                } else {
+                    c.wrapping_sub((1u32 << k) - 1) as i32
+                }
+            } else {
+                self.corr_min
+            }
+        } else {
+            dec.decode_bit(&mut self.m_corrector0) as i32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntegerCompressor;
+    use lidar::laszip::arithmetic_coder::{ArithmeticDecoder, ArithmeticEncoder};
+
+    fn round_trip(bits: u32, contexts: u32, values: &[(i32, i32)]) {
+        let mut enc = ArithmeticEncoder::new();
+        let mut ic = IntegerCompressor::new(bits, contexts);
+        for (i, &(pred, real)) in values.iter().enumerate() {
+            ic.compress(&mut enc, pred, real, i as u32 % contexts);
+        }
+        let data = enc.done();
+
+        let mut dec = ArithmeticDecoder::new(&data);
+        let mut ic = IntegerCompressor::new(bits, contexts);
+        for (i, &(pred, real)) in values.iter().enumerate() {
+            assert_eq!(ic.decompress(&mut dec, pred, i as u32 % contexts), real);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_of_32_bit_values() {
+        let mut state = 11u64;
+        let mut values = vec![(0, 0), (0, i32::max_value()), (0, i32::min_value()), (i32::max_value(), i32::min_value()), (i32::min_value(), i32::max_value())];
+        for i in 0..20_000 {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let pred = (state >> 32) as i32;
+            // small correctors are the common case, but all sizes are coded
+            let corrector = (state as i32) >> (i % 32);
+            values.push((pred, pred.wrapping_add(corrector)));
+        }
+        round_trip(32, 4, &values);
+    }
+
+    #[test]
+    fn test_round_trip_of_16_bit_values() {
+        let mut values = vec![];
+        for pred in (0..65_536).step_by(97) {
+            for &real in &[0, 1, 255, 32_767, 65_535] {
+                values.push((pred, real));
+            }
+        }
+        round_trip(16, 1, &values);
+    }
+}
@@ -0,0 +1,710 @@
+use byteorder::{ByteOrder, LittleEndian};
+use lidar::laszip::arithmetic_coder::*;
+use lidar::laszip::integer_compressor::IntegerCompressor;
+
+/// Compresses and decompresses one item, i.e. a group of fields, of a point
+/// record. The first record of each chunk is stored raw and initializes the
+/// codec; each later record is coded relative to the ones before it.
+pub trait ItemCodec {
+    fn init_first(&mut self, item: &[u8]);
+    fn compress(&mut self, enc: &mut ArithmeticEncoder, item: &[u8]);
+    fn decompress(&mut self, dec: &mut ArithmeticDecoder, item: &mut [u8]);
+}
+
+// Maps the number of returns (rows) and return number (columns) of a point
+// onto the contexts used for its intensity and coordinate differences, and
+// onto the level of the point within its pulse, used to predict its height.
+const NUMBER_RETURN_MAP: [[u8; 8]; 8] = [[15, 14, 13, 12, 11, 10, 9, 8],
+                                         [14, 0, 1, 3, 6, 10, 10, 9],
+                                         [13, 1, 2, 4, 7, 11, 11, 10],
+                                         [12, 3, 4, 5, 8, 12, 12, 11],
+                                         [11, 6, 7, 8, 9, 13, 13, 12],
+                                         [10, 10, 11, 12, 13, 14, 14, 13],
+                                         [9, 10, 11, 12, 13, 14, 15, 14],
+                                         [8, 9, 10, 11, 12, 13, 14, 15]];
+
+const NUMBER_RETURN_LEVEL: [[u8; 8]; 8] = [[0, 1, 2, 3, 4, 5, 6, 7],
+                                           [1, 0, 1, 2, 3, 4, 5, 6],
+                                           [2, 1, 0, 1, 2, 3, 4, 5],
+                                           [3, 2, 1, 0, 1, 2, 3, 4],
+                                           [4, 3, 2, 1, 0, 1, 2, 3],
+                                           [5, 4, 3, 2, 1, 0, 1, 2],
+                                           [6, 5, 4, 3, 2, 1, 0, 1],
+                                           [7, 6, 5, 4, 3, 2, 1, 0]];
+
+/// The running median of the last five values.
+#[derive(Clone, Copy)]
+pub struct StreamingMedian5 {
+    values: [i32; 5],
+    high: bool,
+}
+
+impl StreamingMedian5 {
+    pub fn new() -> StreamingMedian5 {
+        StreamingMedian5 { values: [0; 5], high: true }
+    }
+
+    pub fn add(&mut self, v: i32) {
+        let values = &mut self.values;
+        if self.high {
+            if v < values[2] {
+                values[4] = values[3];
+                values[3] = values[2];
+                if v < values[0] {
+                    values[2] = values[1];
+                    values[1] = values[0];
+                    values[0] = v;
+                } else if v < values[1] {
+                    values[2] = values[1];
+                    values[1] = v;
+                } else {
+                    values[2] = v;
+                }
+            } else {
+                if v < values[3] {
+                    values[4] = values[3];
+                    values[3] = v;
+                } else {
+                    values[4] = v;
+                }
+                self.high = false;
+            }
+        } else {
+            if values[2] < v {
+                values[0] = values[1];
+                values[1] = values[2];
+                if values[4] < v {
+                    values[2] = values[3];
+                    values[3] = values[4];
+                    values[4] = v;
+                } else if values[3] < v {
+                    values[2] = values[3];
+                    values[3] = v;
+                } else {
+                    values[2] = v;
+                }
+            } else {
+                if values[1] < v {
+                    values[0] = values[1];
+                    values[1] = v;
+                } else {
+                    values[0] = v;
+                }
+                self.high = true;
+            }
+        }
+    }
+
+    pub fn get(&self) -> i32 {
+        self.values[2]
+    }
+}
+
+/// Returns the symbol model for a context, creating it on first use; models
+/// conditioned on the previous value of a byte are only created as needed.
+pub fn context_model<'a>(models: &'a mut Vec<Option<ArithmeticModel>>, context: usize, symbols: u32) -> &'a mut ArithmeticModel {
+    let slot = &mut models[context];
+    if slot.is_none() {
+        *slot = Some(ArithmeticModel::new(symbols));
+    }
+    slot.as_mut().unwrap()
+}
+
+/// The 20 bytes of the core fields of point formats 0-5 (version 2).
+pub struct Point10Codec {
+    last_item: [u8; 20],
+    last_intensity: [u16; 16],
+    last_x_diff_median5: [StreamingMedian5; 16],
+    last_y_diff_median5: [StreamingMedian5; 16],
+    last_height: [i32; 8],
+    m_changed_values: ArithmeticModel,
+    ic_intensity: IntegerCompressor,
+    m_scan_angle_rank: Vec<ArithmeticModel>,
+    ic_point_source_id: IntegerCompressor,
+    m_bit_byte: Vec<Option<ArithmeticModel>>,
+    m_classification: Vec<Option<ArithmeticModel>>,
+    m_user_data: Vec<Option<ArithmeticModel>>,
+    ic_dx: IntegerCompressor,
+    ic_dy: IntegerCompressor,
+    ic_z: IntegerCompressor,
+}
+
+impl Point10Codec {
+    pub fn new() -> Point10Codec {
+        Point10Codec {
+            last_item: [0u8; 20],
+            last_intensity: [0u16; 16],
+            last_x_diff_median5: [StreamingMedian5::new(); 16],
+            last_y_diff_median5: [StreamingMedian5::new(); 16],
+            last_height: [0i32; 8],
+            m_changed_values: ArithmeticModel::new(64),
+            ic_intensity: IntegerCompressor::new(16, 4),
+            m_scan_angle_rank: vec![ArithmeticModel::new(256), ArithmeticModel::new(256)],
+            ic_point_source_id: IntegerCompressor::new(16, 1),
+            m_bit_byte: vec![None; 256],
+            m_classification: vec![None; 256],
+            m_user_data: vec![None; 256],
+            ic_dx: IntegerCompressor::new(32, 2),
+            ic_dy: IntegerCompressor::new(32, 22),
+            ic_z: IntegerCompressor::new(32, 20),
+        }
+    }
+}
+
+// The contexts of the y and z coordinates depend on whether the point is a
+// single return and on the magnitudes of the preceding coordinate correctors.
+pub fn dy_context(n: usize, ic_dx: &IntegerCompressor) -> u32 {
+    let k_bits = ic_dx.get_k();
+    (n == 1) as u32 + if k_bits < 20 { k_bits & !1u32 } else { 20 }
+}
+
+pub fn z_context(n: usize, ic_dx: &IntegerCompressor, ic_dy: &IntegerCompressor) -> u32 {
+    let k_bits = (ic_dx.get_k() + ic_dy.get_k()) / 2;
+    (n == 1) as u32 + if k_bits < 18 { k_bits & !1u32 } else { 18 }
+}
+
+impl ItemCodec for Point10Codec {
+    fn init_first(&mut self, item: &[u8]) {
+        self.last_item.copy_from_slice(&item[0..20]);
+    }
+
+    fn compress(&mut self, enc: &mut ArithmeticEncoder, item: &[u8]) {
+        let r = (item[14] & 0b111) as usize;
+        let n = ((item[14] >> 3) & 0b111) as usize;
+        let m = NUMBER_RETURN_MAP[n][r] as usize;
+        let l = NUMBER_RETURN_LEVEL[n][r] as usize;
+        let intensity = LittleEndian::read_u16(&item[12..14]);
+        let last = self.last_item;
+
+        // which of the other values have changed
+        let changed_values = ((last[14] != item[14]) as u32) << 5 |
+                             ((self.last_intensity[m] != intensity) as u32) << 4 |
+                             ((last[15] != item[15]) as u32) << 3 |
+                             ((last[16] != item[16]) as u32) << 2 |
+                             ((last[17] != item[17]) as u32) << 1 |
+                             (last[18..20] != item[18..20]) as u32;
+        enc.encode_symbol(&mut self.m_changed_values, changed_values);
+
+        // the return number, number of returns, scan direction and edge of flight line
+        if changed_values & 32 != 0 {
+            enc.encode_symbol(context_model(&mut self.m_bit_byte, last[14] as usize, 256), item[14] as u32);
+        }
+        if changed_values & 16 != 0 {
+            self.ic_intensity.compress(enc, self.last_intensity[m] as i32, intensity as i32, m.min(3) as u32);
+            self.last_intensity[m] = intensity;
+        }
+        if changed_values & 8 != 0 {
+            enc.encode_symbol(context_model(&mut self.m_classification, last[15] as usize, 256), item[15] as u32);
+        }
+        if changed_values & 4 != 0 {
+            let scan_direction = ((item[14] >> 6) & 1) as usize;
+            enc.encode_symbol(&mut self.m_scan_angle_rank[scan_direction], item[16].wrapping_sub(last[16]) as u32);
+        }
+        if changed_values & 2 != 0 {
+            enc.encode_symbol(context_model(&mut self.m_user_data, last[17] as usize, 256), item[17] as u32);
+        }
+        if changed_values & 1 != 0 {
+            self.ic_point_source_id.compress(enc,
+                                             LittleEndian::read_u16(&last[18..20]) as i32,
+                                             LittleEndian::read_u16(&item[18..20]) as i32,
+                                             0);
+        }
+
+        // the coordinates
+        let median = self.last_x_diff_median5[m].get();
+        let diff = LittleEndian::read_i32(&item[0..4]).wrapping_sub(LittleEndian::read_i32(&last[0..4]));
+        self.ic_dx.compress(enc, median, diff, (n == 1) as u32);
+        self.last_x_diff_median5[m].add(diff);
+
+        let context = dy_context(n, &self.ic_dx);
+        let median = self.last_y_diff_median5[m].get();
+        let diff = LittleEndian::read_i32(&item[4..8]).wrapping_sub(LittleEndian::read_i32(&last[4..8]));
+        self.ic_dy.compress(enc, median, diff, context);
+        self.last_y_diff_median5[m].add(diff);
+
+        let context = z_context(n, &self.ic_dx, &self.ic_dy);
+        let z = LittleEndian::read_i32(&item[8..12]);
+        self.ic_z.compress(enc, self.last_height[l], z, context);
+        self.last_height[l] = z;
+
+        self.last_item.copy_from_slice(&item[0..20]);
+    }
+
+    fn decompress(&mut self, dec: &mut ArithmeticDecoder, item: &mut [u8]) {
+        let changed_values = dec.decode_symbol(&mut self.m_changed_values);
+        let last = &mut self.last_item;
+        if changed_values & 32 != 0 {
+            last[14] = dec.decode_symbol(context_model(&mut self.m_bit_byte, last[14] as usize, 256)) as u8;
+        }
+        let r = (last[14] & 0b111) as usize;
+        let n = ((last[14] >> 3) & 0b111) as usize;
+        let m = NUMBER_RETURN_MAP[n][r] as usize;
+        let l = NUMBER_RETURN_LEVEL[n][r] as usize;
+
+        if changed_values & 16 != 0 {
+            self.last_intensity[m] = self.ic_intensity.decompress(dec, self.last_intensity[m] as i32, m.min(3) as u32) as u16;
+        }
+        LittleEndian::write_u16(&mut last[12..14], self.last_intensity[m]);
+        if changed_values & 8 != 0 {
+            last[15] = dec.decode_symbol(context_model(&mut self.m_classification, last[15] as usize, 256)) as u8;
+        }
+        if changed_values & 4 != 0 {
+            let scan_direction = ((last[14] >> 6) & 1) as usize;
+            let val = dec.decode_symbol(&mut self.m_scan_angle_rank[scan_direction]) as u8;
+            last[16] = last[16].wrapping_add(val);
+        }
+        if changed_values & 2 != 0 {
+            last[17] = dec.decode_symbol(context_model(&mut self.m_user_data, last[17] as usize, 256)) as u8;
+        }
+        if changed_values & 1 != 0 {
+            let point_source_id = self.ic_point_source_id.decompress(dec, LittleEndian::read_u16(&last[18..20]) as i32, 0);
+            LittleEndian::write_u16(&mut last[18..20], point_source_id as u16);
+        }
+
+        let median = self.last_x_diff_median5[m].get();
+        let diff = self.ic_dx.decompress(dec, median, (n == 1) as u32);
+        let x = LittleEndian::read_i32(&last[0..4]).wrapping_add(diff);
+        LittleEndian::write_i32(&mut last[0..4], x);
+        self.last_x_diff_median5[m].add(diff);
+
+        let context = dy_context(n, &self.ic_dx);
+        let median = self.last_y_diff_median5[m].get();
+        let diff = self.ic_dy.decompress(dec, median, context);
+        let y = LittleEndian::read_i32(&last[4..8]).wrapping_add(diff);
+        LittleEndian::write_i32(&mut last[4..8], y);
+        self.last_y_diff_median5[m].add(diff);
+
+        let context = z_context(n, &self.ic_dx, &self.ic_dy);
+        let z = self.ic_z.decompress(dec, self.last_height[l], context);
+        LittleEndian::write_i32(&mut last[8..12], z);
+        self.last_height[l] = z;
+
+        item[0..20].copy_from_slice(&last[..]);
+    }
+}
+
+pub const GPSTIME_MULTI: i32 = 500;
+pub const GPSTIME_MULTI_MINUS: i32 = -10;
+const GPSTIME_MULTI_UNCHANGED: i32 = GPSTIME_MULTI - GPSTIME_MULTI_MINUS + 1;
+pub const GPSTIME_MULTI_CODE_FULL: i32 = GPSTIME_MULTI - GPSTIME_MULTI_MINUS + 2;
+pub const GPSTIME_MULTI_TOTAL: i32 = GPSTIME_MULTI - GPSTIME_MULTI_MINUS + 6;
+
+/// The GPS time of point formats 1 and 3-5 (version 2). The time stamps are
+/// coded as the differences between the integer representations of the
+/// doubles, predicted as multiples of the previous difference, and up to four
+/// interleaved sequences of times are tracked.
+pub struct GpsTime11Codec {
+    last: usize,
+    next: usize,
+    last_gpstime: [i64; 4],
+    last_gpstime_diff: [i32; 4],
+    multi_extreme_counter: [i32; 4],
+    m_gpstime_multi: ArithmeticModel,
+    m_gpstime_0diff: ArithmeticModel,
+    ic_gpstime: IntegerCompressor,
+}
+
+impl GpsTime11Codec {
+    pub fn new() -> GpsTime11Codec {
+        GpsTime11Codec {
+            last: 0,
+            next: 0,
+            last_gpstime: [0i64; 4],
+            last_gpstime_diff: [0i32; 4],
+            multi_extreme_counter: [0i32; 4],
+            m_gpstime_multi: ArithmeticModel::new(GPSTIME_MULTI_TOTAL as u32),
+            m_gpstime_0diff: ArithmeticModel::new(6),
+            ic_gpstime: IntegerCompressor::new(32, 9),
+        }
+    }
+
+    // counts the predictions that were far off and, after too many, adopts
+    // the latest difference
+    fn count_extreme(&mut self, diff: i32) {
+        let last = self.last;
+        self.multi_extreme_counter[last] += 1;
+        if self.multi_extreme_counter[last] > 3 {
+            self.last_gpstime_diff[last] = diff;
+            self.multi_extreme_counter[last] = 0;
+        }
+    }
+
+    // the index of another sequence that the time can be coded against
+    fn other_sequence(&self, this_gpstime: i64) -> Option<usize> {
+        (1..4).find(|&i| {
+            let diff = this_gpstime.wrapping_sub(self.last_gpstime[(self.last + i) & 3]);
+            diff == diff as i32 as i64
+        })
+    }
+
+    fn compress_new_sequence(&mut self, enc: &mut ArithmeticEncoder, this_gpstime: i64) {
+        self.ic_gpstime.compress(enc,
+                                 (self.last_gpstime[self.last] as u64 >> 32) as i32,
+                                 (this_gpstime as u64 >> 32) as i32,
+                                 8);
+        enc.write_int(this_gpstime as u64 as u32);
+        self.next = (self.next + 1) & 3;
+        self.last = self.next;
+        self.last_gpstime_diff[self.last] = 0;
+        self.multi_extreme_counter[self.last] = 0;
+    }
+
+    fn decompress_new_sequence(&mut self, dec: &mut ArithmeticDecoder) {
+        self.next = (self.next + 1) & 3;
+        let upper = self.ic_gpstime.decompress(dec, (self.last_gpstime[self.last] as u64 >> 32) as i32, 8);
+        let lower = dec.read_int();
+        self.last_gpstime[self.next] = (((upper as u32 as u64) << 32) | lower as u64) as i64;
+        self.last = self.next;
+        self.last_gpstime_diff[self.last] = 0;
+        self.multi_extreme_counter[self.last] = 0;
+    }
+}
+
+impl ItemCodec for GpsTime11Codec {
+    fn init_first(&mut self, item: &[u8]) {
+        self.last_gpstime[0] = LittleEndian::read_i64(&item[0..8]);
+    }
+
+    fn compress(&mut self, enc: &mut ArithmeticEncoder, item: &[u8]) {
+        let this_gpstime = LittleEndian::read_i64(&item[0..8]);
+        let last = self.last;
+        if self.last_gpstime_diff[last] == 0 {
+            // the last integer difference was zero
+            if this_gpstime == self.last_gpstime[last] {
+                enc.encode_symbol(&mut self.m_gpstime_0diff, 0);
+                return;
+            }
+            let curr_gpstime_diff_64 = this_gpstime.wrapping_sub(self.last_gpstime[last]);
+            let curr_gpstime_diff = curr_gpstime_diff_64 as i32;
+            if curr_gpstime_diff_64 == curr_gpstime_diff as i64 {
+                // the difference can be represented with 32 bits
+                enc.encode_symbol(&mut self.m_gpstime_0diff, 1);
+                self.ic_gpstime.compress(enc, 0, curr_gpstime_diff, 0);
+                self.last_gpstime_diff[last] = curr_gpstime_diff;
+                self.multi_extreme_counter[last] = 0;
+            } else if let Some(i) = self.other_sequence(this_gpstime) {
+                // the time belongs to another sequence
+                enc.encode_symbol(&mut self.m_gpstime_0diff, i as u32 + 2);
+                self.last = (last + i) & 3;
+                return self.compress(enc, item);
+            } else {
+                enc.encode_symbol(&mut self.m_gpstime_0diff, 2);
+                self.compress_new_sequence(enc, this_gpstime);
+            }
+            let last = self.last;
+            self.last_gpstime[last] = this_gpstime;
+        } else {
+            // the last integer difference was not zero
+            if this_gpstime == self.last_gpstime[last] {
+                enc.encode_symbol(&mut self.m_gpstime_multi, GPSTIME_MULTI_UNCHANGED as u32);
+                return;
+            }
+            let curr_gpstime_diff_64 = this_gpstime.wrapping_sub(self.last_gpstime[last]);
+            let curr_gpstime_diff = curr_gpstime_diff_64 as i32;
+            if curr_gpstime_diff_64 == curr_gpstime_diff as i64 {
+                // the multiplier between the current and last integer differences
+                let last_diff = self.last_gpstime_diff[last];
+                let multi_f = curr_gpstime_diff as f32 / last_diff as f32;
+                let multi = if multi_f >= 0f32 { (multi_f + 0.5f32) as i32 } else { (multi_f - 0.5f32) as i32 };
+                if multi == 1 {
+                    // the most common case of regularly spaced pulses
+                    enc.encode_symbol(&mut self.m_gpstime_multi, 1);
+                    self.ic_gpstime.compress(enc, last_diff, curr_gpstime_diff, 1);
+                    self.multi_extreme_counter[last] = 0;
+                } else if multi > 0 {
+                    if multi < GPSTIME_MULTI {
+                        enc.encode_symbol(&mut self.m_gpstime_multi, multi as u32);
+                        let context = if multi < 10 { 2 } else { 3 };
+                        self.ic_gpstime.compress(enc, multi.wrapping_mul(last_diff), curr_gpstime_diff, context);
+                    } else {
+                        enc.encode_symbol(&mut self.m_gpstime_multi, GPSTIME_MULTI as u32);
+                        self.ic_gpstime.compress(enc, GPSTIME_MULTI.wrapping_mul(last_diff), curr_gpstime_diff, 4);
+                        self.count_extreme(curr_gpstime_diff);
+                    }
+                } else if multi < 0 {
+                    if multi > GPSTIME_MULTI_MINUS {
+                        enc.encode_symbol(&mut self.m_gpstime_multi, (GPSTIME_MULTI - multi) as u32);
+                        self.ic_gpstime.compress(enc, multi.wrapping_mul(last_diff), curr_gpstime_diff, 5);
+                    } else {
+                        enc.encode_symbol(&mut self.m_gpstime_multi, (GPSTIME_MULTI - GPSTIME_MULTI_MINUS) as u32);
+                        self.ic_gpstime.compress(enc, GPSTIME_MULTI_MINUS.wrapping_mul(last_diff), curr_gpstime_diff, 6);
+                        self.count_extreme(curr_gpstime_diff);
+                    }
+                } else {
+                    enc.encode_symbol(&mut self.m_gpstime_multi, 0);
+                    self.ic_gpstime.compress(enc, 0, curr_gpstime_diff, 7);
+                    self.count_extreme(curr_gpstime_diff);
+                }
+            } else if let Some(i) = self.other_sequence(this_gpstime) {
+                enc.encode_symbol(&mut self.m_gpstime_multi, (GPSTIME_MULTI_CODE_FULL + i as i32) as u32);
+                self.last = (last + i) & 3;
+                return self.compress(enc, item);
+            } else {
+                enc.encode_symbol(&mut self.m_gpstime_multi, GPSTIME_MULTI_CODE_FULL as u32);
+                self.compress_new_sequence(enc, this_gpstime);
+            }
+            let last = self.last;
+            self.last_gpstime[last] = this_gpstime;
+        }
+    }
+
+    fn decompress(&mut self, dec: &mut ArithmeticDecoder, item: &mut [u8]) {
+        let last = self.last;
+        if self.last_gpstime_diff[last] == 0 {
+            let multi = dec.decode_symbol(&mut self.m_gpstime_0diff) as i32;
+            if multi == 1 {
+                self.last_gpstime_diff[last] = self.ic_gpstime.decompress(dec, 0, 0);
+                self.last_gpstime[last] = self.last_gpstime[last].wrapping_add(self.last_gpstime_diff[last] as i64);
+                self.multi_extreme_counter[last] = 0;
+            } else if multi == 2 {
+                self.decompress_new_sequence(dec);
+            } else if multi > 2 {
+                self.last = (last + multi as usize - 2) & 3;
+                return self.decompress(dec, item);
+            }
+        } else {
+            let multi = dec.decode_symbol(&mut self.m_gpstime_multi) as i32;
+            let last_diff = self.last_gpstime_diff[last];
+            if multi == 1 {
+                let diff = self.ic_gpstime.decompress(dec, last_diff, 1);
+                self.last_gpstime[last] = self.last_gpstime[last].wrapping_add(diff as i64);
+                self.multi_extreme_counter[last] = 0;
+            } else if multi < GPSTIME_MULTI_UNCHANGED {
+                let gpstime_diff;
+                if multi == 0 {
+                    gpstime_diff = self.ic_gpstime.decompress(dec, 0, 7);
+                    self.count_extreme(gpstime_diff);
+                } else if multi < GPSTIME_MULTI {
+                    let context = if multi < 10 { 2 } else { 3 };
+                    gpstime_diff = self.ic_gpstime.decompress(dec, multi.wrapping_mul(last_diff), context);
+                } else if multi == GPSTIME_MULTI {
+                    gpstime_diff = self.ic_gpstime.decompress(dec, GPSTIME_MULTI.wrapping_mul(last_diff), 4);
+                    self.count_extreme(gpstime_diff);
+                } else {
+                    let multi = GPSTIME_MULTI - multi;
+                    if multi > GPSTIME_MULTI_MINUS {
+                        gpstime_diff = self.ic_gpstime.decompress(dec, multi.wrapping_mul(last_diff), 5);
+                    } else {
+                        gpstime_diff = self.ic_gpstime.decompress(dec, GPSTIME_MULTI_MINUS.wrapping_mul(last_diff), 6);
+                        self.count_extreme(gpstime_diff);
+                    }
+                }
+                self.last_gpstime[last] = self.last_gpstime[last].wrapping_add(gpstime_diff as i64);
+            } else if multi == GPSTIME_MULTI_CODE_FULL {
+                self.decompress_new_sequence(dec);
+            } else if multi > GPSTIME_MULTI_CODE_FULL {
+                self.last = (last + (multi - GPSTIME_MULTI_CODE_FULL) as usize) & 3;
+                return self.decompress(dec, item);
+            }
+        }
+        LittleEndian::write_i64(&mut item[0..8], self.last_gpstime[self.last]);
+    }
+}
+
+/// The red, green and blue channels of point formats 2, 3 and 5 (version 2).
+pub struct Rgb12Codec {
+    last_item: [u16; 3],
+    models: RgbModels,
+}
+
+impl Rgb12Codec {
+    pub fn new() -> Rgb12Codec {
+        Rgb12Codec {
+            last_item: [0u16; 3],
+            models: RgbModels::new(),
+        }
+    }
+}
+
+impl ItemCodec for Rgb12Codec {
+    fn init_first(&mut self, item: &[u8]) {
+        self.last_item = read_rgb(item);
+    }
+
+    fn compress(&mut self, enc: &mut ArithmeticEncoder, item: &[u8]) {
+        let rgb = read_rgb(item);
+        self.models.compress(enc, &self.last_item, &rgb);
+        self.last_item = rgb;
+    }
+
+    fn decompress(&mut self, dec: &mut ArithmeticDecoder, item: &mut [u8]) {
+        let rgb = self.models.decompress(dec, &self.last_item);
+        self.last_item = rgb;
+        write_rgb(&rgb, item);
+    }
+}
+
+/// The models of the colour channels, which are shared by the RGB items of
+/// both the pointwise and layered compressors. The bytes of each channel are
+/// coded separately, and the green and blue channels are predicted from the
+/// change in the red channel.
+pub struct RgbModels {
+    m_byte_used: ArithmeticModel,
+    m_rgb_diff: Vec<ArithmeticModel>,
+}
+
+pub fn u8_clamp(n: i32) -> i32 {
+    if n < 0 { 0 } else if n > 255 { 255 } else { n }
+}
+
+pub fn u8_fold(n: i32) -> u32 {
+    (n & 0xFF) as u32
+}
+
+pub fn read_rgb(item: &[u8]) -> [u16; 3] {
+    [LittleEndian::read_u16(&item[0..2]),
+     LittleEndian::read_u16(&item[2..4]),
+     LittleEndian::read_u16(&item[4..6])]
+}
+
+pub fn write_rgb(rgb: &[u16; 3], item: &mut [u8]) {
+    for i in 0..3 {
+        LittleEndian::write_u16(&mut item[i * 2..i * 2 + 2], rgb[i]);
+    }
+}
+
+impl RgbModels {
+    pub fn new() -> RgbModels {
+        RgbModels {
+            m_byte_used: ArithmeticModel::new(128),
+            m_rgb_diff: (0..6).map(|_| ArithmeticModel::new(256)).collect(),
+        }
+    }
+
+    pub fn compress(&mut self, enc: &mut ArithmeticEncoder, last: &[u16; 3], rgb: &[u16; 3]) {
+        let lo = |v: u16| (v & 0x00FF) as i32;
+        let hi = |v: u16| (v >> 8) as i32;
+        let mut sym = (lo(last[0]) != lo(rgb[0])) as u32;
+        sym |= ((hi(last[0]) != hi(rgb[0])) as u32) << 1;
+        sym |= ((lo(last[1]) != lo(rgb[1])) as u32) << 2;
+        sym |= ((hi(last[1]) != hi(rgb[1])) as u32) << 3;
+        sym |= ((lo(last[2]) != lo(rgb[2])) as u32) << 4;
+        sym |= ((hi(last[2]) != hi(rgb[2])) as u32) << 5;
+        // whether the channels differ, i.e. the point is not grey
+        sym |= ((lo(rgb[0]) != lo(rgb[1]) || lo(rgb[0]) != lo(rgb[2]) ||
+                 hi(rgb[0]) != hi(rgb[1]) || hi(rgb[0]) != hi(rgb[2])) as u32) << 6;
+        enc.encode_symbol(&mut self.m_byte_used, sym);
+
+        let mut diff_l = 0i32;
+        let mut diff_h = 0i32;
+        if sym & (1 << 0) != 0 {
+            diff_l = lo(rgb[0]) - lo(last[0]);
+            enc.encode_symbol(&mut self.m_rgb_diff[0], u8_fold(diff_l));
+        }
+        if sym & (1 << 1) != 0 {
+            diff_h = hi(rgb[0]) - hi(last[0]);
+            enc.encode_symbol(&mut self.m_rgb_diff[1], u8_fold(diff_h));
+        }
+        if sym & (1 << 6) != 0 {
+            if sym & (1 << 2) != 0 {
+                let corr = lo(rgb[1]) - u8_clamp(diff_l + lo(last[1]));
+                enc.encode_symbol(&mut self.m_rgb_diff[2], u8_fold(corr));
+            }
+            if sym & (1 << 4) != 0 {
+                diff_l = (diff_l + lo(rgb[1]) - lo(last[1])) / 2;
+                let corr = lo(rgb[2]) - u8_clamp(diff_l + lo(last[2]));
+                enc.encode_symbol(&mut self.m_rgb_diff[4], u8_fold(corr));
+            }
+            if sym & (1 << 3) != 0 {
+                let corr = hi(rgb[1]) - u8_clamp(diff_h + hi(last[1]));
+                enc.encode_symbol(&mut self.m_rgb_diff[3], u8_fold(corr));
+            }
+            if sym & (1 << 5) != 0 {
+                diff_h = (diff_h + hi(rgb[1]) - hi(last[1])) / 2;
+                let corr = hi(rgb[2]) - u8_clamp(diff_h + hi(last[2]));
+                enc.encode_symbol(&mut self.m_rgb_diff[5], u8_fold(corr));
+            }
+        }
+    }
+
+    pub fn decompress(&mut self, dec: &mut ArithmeticDecoder, last: &[u16; 3]) -> [u16; 3] {
+        let lo = |v: u16| (v & 0x00FF) as i32;
+        let hi = |v: u16| (v >> 8) as i32;
+        let mut rgb = [0u16; 3];
+        let sym = dec.decode_symbol(&mut self.m_byte_used);
+        if sym & (1 << 0) != 0 {
+            let corr = dec.decode_symbol(&mut self.m_rgb_diff[0]) as i32;
+            rgb[0] = u8_fold(corr + lo(last[0])) as u16;
+        } else {
+            rgb[0] = last[0] & 0x00FF;
+        }
+        if sym & (1 << 1) != 0 {
+            let corr = dec.decode_symbol(&mut self.m_rgb_diff[1]) as i32;
+            rgb[0] |= (u8_fold(corr + hi(last[0])) as u16) << 8;
+        } else {
+            rgb[0] |= last[0] & 0xFF00;
+        }
+        if sym & (1 << 6) != 0 {
+            let mut diff = lo(rgb[0]) - lo(last[0]);
+            if sym & (1 << 2) != 0 {
+                let corr = dec.decode_symbol(&mut self.m_rgb_diff[2]) as i32;
+                rgb[1] = u8_fold(corr + u8_clamp(diff + lo(last[1]))) as u16;
+            } else {
+                rgb[1] = last[1] & 0x00FF;
+            }
+            if sym & (1 << 4) != 0 {
+                let corr = dec.decode_symbol(&mut self.m_rgb_diff[4]) as i32;
+                diff = (diff + lo(rgb[1]) - lo(last[1])) / 2;
+                rgb[2] = u8_fold(corr + u8_clamp(diff + lo(last[2]))) as u16;
+            } else {
+                rgb[2] = last[2] & 0x00FF;
+            }
+            diff = hi(rgb[0]) - hi(last[0]);
+            if sym & (1 << 3) != 0 {
+                let corr = dec.decode_symbol(&mut self.m_rgb_diff[3]) as i32;
+                rgb[1] |= (u8_fold(corr + u8_clamp(diff + hi(last[1]))) as u16) << 8;
+            } else {
+                rgb[1] |= last[1] & 0xFF00;
+            }
+            if sym & (1 << 5) != 0 {
+                let corr = dec.decode_symbol(&mut self.m_rgb_diff[5]) as i32;
+                diff = (diff + hi(rgb[1]) - hi(last[1])) / 2;
+                rgb[2] |= (u8_fold(corr + u8_clamp(diff + hi(last[2]))) as u16) << 8;
+            } else {
+                rgb[2] |= last[2] & 0xFF00;
+            }
+        } else {
+            rgb[1] = rgb[0];
+            rgb[2] = rgb[0];
+        }
+        rgb
+    }
+}
+
+/// Extra bytes following the standard fields of a point record (version 2),
+/// each coded as its difference from the previous record.
+pub struct ByteCodec {
+    last_item: Vec<u8>,
+    m_byte: Vec<ArithmeticModel>,
+}
+
+impl ByteCodec {
+    pub fn new(number: usize) -> ByteCodec {
+        ByteCodec {
+            last_item: vec![0u8; number],
+            m_byte: (0..number).map(|_| ArithmeticModel::new(256)).collect(),
+        }
+    }
+}
+
+impl ItemCodec for ByteCodec {
+    fn init_first(&mut self, item: &[u8]) {
+        let n = self.last_item.len();
+        self.last_item.copy_from_slice(&item[0..n]);
+    }
+
+    fn compress(&mut self, enc: &mut ArithmeticEncoder, item: &[u8]) {
+        for i in 0..self.last_item.len() {
+            enc.encode_symbol(&mut self.m_byte[i], item[i].wrapping_sub(self.last_item[i]) as u32);
+            self.last_item[i] = item[i];
+        }
+    }
+
+    fn decompress(&mut self, dec: &mut ArithmeticDecoder, item: &mut [u8]) {
+        for i in 0..self.last_item.len() {
+            let diff = dec.decode_symbol(&mut self.m_byte[i]) as u8;
+            self.last_item[i] = self.last_item[i].wrapping_add(diff);
+            item[i] = self.last_item[i];
+        }
+    }
+}
@@ -0,0 +1,997 @@
+use byteorder::{ByteOrder, LittleEndian};
+use lidar::laszip::arithmetic_coder::*;
+use lidar::laszip::integer_compressor::IntegerCompressor;
+use lidar::laszip::items::*;
+
+// The items of the layered compressor used for the LAS 1.4 point formats
+// (version 3). Each item codes its fields into one or more layers, each with
+// its own arithmetic coder, so that a reader may skip the fields it does not
+// need and a layer whose values never change within a chunk is omitted. The
+// items also keep separate state, or contexts, for each of the four scanner
+// channels; the point item selects the context and the other items follow it.
+
+/// Compresses one item of the layered point formats.
+pub trait LayeredItemCompressor {
+    fn init_first(&mut self, item: &[u8], context: &mut usize);
+    fn compress(&mut self, item: &[u8], context: &mut usize);
+    /// Finishes the chunk and returns the bytes of each layer, which are
+    /// empty for the layers that do not need to be stored.
+    fn finish_layers(&mut self) -> Vec<Vec<u8>>;
+}
+
+/// Decompresses one item of the layered point formats from its layers.
+pub trait LayeredItemDecompressor {
+    fn init_first(&mut self, item: &[u8], context: &mut usize);
+    fn decompress(&mut self, item: &mut [u8], context: &mut usize);
+}
+
+// As with the earlier point item, the contexts of the coordinates and height
+// depend on the number of returns (rows) and the return number (columns).
+const NUMBER_RETURN_MAP_6CTX: [[u8; 16]; 16] = [[0, 1, 2, 3, 4, 5, 3, 4, 4, 5, 5, 5, 5, 5, 5, 5],
+                                                [1, 0, 1, 3, 4, 5, 3, 4, 4, 5, 5, 5, 5, 5, 5, 5],
+                                                [2, 1, 2, 4, 5, 3, 4, 4, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [3, 3, 4, 5, 4, 5, 4, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [4, 4, 5, 4, 5, 4, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [5, 5, 3, 5, 4, 5, 4, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [3, 3, 4, 4, 5, 4, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [4, 4, 4, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [4, 4, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+                                                [5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5]];
+
+// the difference between the number of returns and the return number, up to 7
+fn number_return_level_8ctx(n: u32, r: u32) -> usize {
+    let level = (n as i32 - r as i32).abs() as usize;
+    if level > 7 { 7 } else { level }
+}
+
+/// The fields of a point of formats 6-10.
+#[derive(Clone, Copy, Default)]
+struct Point14 {
+    x: i32,
+    y: i32,
+    z: i32,
+    intensity: u16,
+    return_number: u8,
+    number_of_returns: u8,
+    classification_flags: u8,
+    scanner_channel: u8,
+    scan_direction_flag: u8,
+    edge_of_flight_line: u8,
+    classification: u8,
+    user_data: u8,
+    scan_angle: i16,
+    point_source_id: u16,
+    gps_time: i64,
+    // whether the GPS time changed from the previous point in the context
+    gps_time_change: bool,
+}
+
+impl Point14 {
+    fn read(item: &[u8]) -> Point14 {
+        Point14 {
+            x: LittleEndian::read_i32(&item[0..4]),
+            y: LittleEndian::read_i32(&item[4..8]),
+            z: LittleEndian::read_i32(&item[8..12]),
+            intensity: LittleEndian::read_u16(&item[12..14]),
+            return_number: item[14] & 0x0F,
+            number_of_returns: item[14] >> 4,
+            classification_flags: item[15] & 0x0F,
+            scanner_channel: (item[15] >> 4) & 0x03,
+            scan_direction_flag: (item[15] >> 6) & 0x01,
+            edge_of_flight_line: item[15] >> 7,
+            classification: item[16],
+            user_data: item[17],
+            scan_angle: LittleEndian::read_i16(&item[18..20]),
+            point_source_id: LittleEndian::read_u16(&item[20..22]),
+            gps_time: LittleEndian::read_i64(&item[22..30]),
+            gps_time_change: false,
+        }
+    }
+
+    fn write(&self, item: &mut [u8]) {
+        LittleEndian::write_i32(&mut item[0..4], self.x);
+        LittleEndian::write_i32(&mut item[4..8], self.y);
+        LittleEndian::write_i32(&mut item[8..12], self.z);
+        LittleEndian::write_u16(&mut item[12..14], self.intensity);
+        item[14] = (self.number_of_returns << 4) | self.return_number;
+        item[15] = (self.edge_of_flight_line << 7) | (self.scan_direction_flag << 6) |
+                   (self.scanner_channel << 4) | self.classification_flags;
+        item[16] = self.classification;
+        item[17] = self.user_data;
+        LittleEndian::write_i16(&mut item[18..20], self.scan_angle);
+        LittleEndian::write_u16(&mut item[20..22], self.point_source_id);
+        LittleEndian::write_i64(&mut item[22..30], self.gps_time);
+    }
+
+    fn flags(&self) -> u32 {
+        ((self.edge_of_flight_line as u32) << 5) | ((self.scan_direction_flag as u32) << 4) |
+        self.classification_flags as u32
+    }
+
+    // a context from whether the point is the first and/or the last return
+    // and whether its GPS time changed
+    fn last_point_return_context(&self) -> usize {
+        let mut lpr = (self.return_number == 1) as usize;
+        lpr += ((self.return_number >= self.number_of_returns) as usize) << 1;
+        lpr + ((self.gps_time_change as usize) << 2)
+    }
+}
+
+/// The GPS time state of a scanner channel. Unlike the pointwise item, a time
+/// is only coded when it differs from that of the previous point.
+struct GpsTime14 {
+    last: usize,
+    next: usize,
+    last_gpstime: [i64; 4],
+    last_gpstime_diff: [i32; 4],
+    multi_extreme_counter: [i32; 4],
+    m_gpstime_multi: ArithmeticModel,
+    m_gpstime_0diff: ArithmeticModel,
+    ic_gpstime: IntegerCompressor,
+}
+
+impl GpsTime14 {
+    fn new(gps_time: i64) -> GpsTime14 {
+        GpsTime14 {
+            last: 0,
+            next: 0,
+            last_gpstime: [gps_time, 0, 0, 0],
+            last_gpstime_diff: [0i32; 4],
+            multi_extreme_counter: [0i32; 4],
+            m_gpstime_multi: ArithmeticModel::new(GPSTIME_MULTI_TOTAL as u32),
+            m_gpstime_0diff: ArithmeticModel::new(5),
+            ic_gpstime: IntegerCompressor::new(32, 9),
+        }
+    }
+
+    // counts the multipliers that could not be represented; after several in a
+    // row, the current difference becomes the new reference
+    fn extreme(&mut self, diff: i32) {
+        let last = self.last;
+        self.multi_extreme_counter[last] += 1;
+        if self.multi_extreme_counter[last] > 3 {
+            self.last_gpstime_diff[last] = diff;
+            self.multi_extreme_counter[last] = 0;
+        }
+    }
+
+    fn start_sequence(&mut self, enc: &mut ArithmeticEncoder, gps_time: i64) {
+        let last = self.last;
+        self.ic_gpstime.compress(enc, (self.last_gpstime[last] >> 32) as i32, (gps_time >> 32) as i32, 8);
+        enc.write_int(gps_time as u32);
+        self.next = (self.next + 1) & 3;
+        self.last = self.next;
+        self.last_gpstime_diff[self.last] = 0;
+        self.multi_extreme_counter[self.last] = 0;
+    }
+
+    fn compress(&mut self, enc: &mut ArithmeticEncoder, gps_time: i64) {
+        let last = self.last;
+        let diff64 = gps_time.wrapping_sub(self.last_gpstime[last]);
+        let diff = diff64 as i32;
+        let fits = diff64 == diff as i64;
+        if self.last_gpstime_diff[last] == 0 {
+            if fits {
+                enc.encode_symbol(&mut self.m_gpstime_0diff, 0);
+                self.ic_gpstime.compress(enc, 0, diff, 0);
+                self.last_gpstime_diff[last] = diff;
+                self.multi_extreme_counter[last] = 0;
+            } else {
+                // the time may belong to another sequence
+                for i in 1..4 {
+                    let other = (last + i) & 3;
+                    let other_diff64 = gps_time.wrapping_sub(self.last_gpstime[other]);
+                    if other_diff64 == other_diff64 as i32 as i64 {
+                        enc.encode_symbol(&mut self.m_gpstime_0diff, i as u32 + 1);
+                        self.last = other;
+                        return self.compress(enc, gps_time);
+                    }
+                }
+                enc.encode_symbol(&mut self.m_gpstime_0diff, 1);
+                self.start_sequence(enc, gps_time);
+            }
+        } else {
+            if fits {
+                let last_diff = self.last_gpstime_diff[last];
+                let multi = quantize(diff as f32 / last_diff as f32);
+                if multi == 1 {
+                    enc.encode_symbol(&mut self.m_gpstime_multi, 1);
+                    self.ic_gpstime.compress(enc, last_diff, diff, 1);
+                    self.multi_extreme_counter[last] = 0;
+                } else if multi > 0 {
+                    if multi < GPSTIME_MULTI {
+                        enc.encode_symbol(&mut self.m_gpstime_multi, multi as u32);
+                        let context = if multi < 10 { 2 } else { 3 };
+                        self.ic_gpstime.compress(enc, multi.wrapping_mul(last_diff), diff, context);
+                    } else {
+                        enc.encode_symbol(&mut self.m_gpstime_multi, GPSTIME_MULTI as u32);
+                        self.ic_gpstime.compress(enc, GPSTIME_MULTI.wrapping_mul(last_diff), diff, 4);
+                        self.extreme(diff);
+                    }
+                } else if multi < 0 {
+                    if multi > GPSTIME_MULTI_MINUS {
+                        enc.encode_symbol(&mut self.m_gpstime_multi, (GPSTIME_MULTI - multi) as u32);
+                        self.ic_gpstime.compress(enc, multi.wrapping_mul(last_diff), diff, 5);
+                    } else {
+                        enc.encode_symbol(&mut self.m_gpstime_multi, (GPSTIME_MULTI - GPSTIME_MULTI_MINUS) as u32);
+                        self.ic_gpstime.compress(enc, GPSTIME_MULTI_MINUS.wrapping_mul(last_diff), diff, 6);
+                        self.extreme(diff);
+                    }
+                } else {
+                    enc.encode_symbol(&mut self.m_gpstime_multi, 0);
+                    self.ic_gpstime.compress(enc, 0, diff, 7);
+                    self.extreme(diff);
+                }
+            } else {
+                for i in 1..4 {
+                    let other = (last + i) & 3;
+                    let other_diff64 = gps_time.wrapping_sub(self.last_gpstime[other]);
+                    if other_diff64 == other_diff64 as i32 as i64 {
+                        enc.encode_symbol(&mut self.m_gpstime_multi, (GPSTIME_MULTI_CODE_FULL + i as i32) as u32);
+                        self.last = other;
+                        return self.compress(enc, gps_time);
+                    }
+                }
+                enc.encode_symbol(&mut self.m_gpstime_multi, GPSTIME_MULTI_CODE_FULL as u32);
+                self.start_sequence(enc, gps_time);
+            }
+        }
+        self.last_gpstime[self.last] = gps_time;
+    }
+
+    fn decompress(&mut self, dec: &mut ArithmeticDecoder) -> i64 {
+        let last = self.last;
+        if self.last_gpstime_diff[last] == 0 {
+            let multi = dec.decode_symbol(&mut self.m_gpstime_0diff) as i32;
+            if multi == 0 {
+                let diff = self.ic_gpstime.decompress(dec, 0, 0);
+                self.last_gpstime_diff[last] = diff;
+                self.last_gpstime[last] = self.last_gpstime[last].wrapping_add(diff as i64);
+                self.multi_extreme_counter[last] = 0;
+            } else if multi == 1 {
+                self.read_sequence(dec);
+            } else {
+                self.last = (last + multi as usize - 1) & 3;
+                return self.decompress(dec);
+            }
+        } else {
+            let multi = dec.decode_symbol(&mut self.m_gpstime_multi) as i32;
+            let last_diff = self.last_gpstime_diff[last];
+            if multi == 1 {
+                let diff = self.ic_gpstime.decompress(dec, last_diff, 1);
+                self.last_gpstime[last] = self.last_gpstime[last].wrapping_add(diff as i64);
+                self.multi_extreme_counter[last] = 0;
+            } else if multi < GPSTIME_MULTI_CODE_FULL {
+                let diff;
+                if multi == 0 {
+                    diff = self.ic_gpstime.decompress(dec, 0, 7);
+                    self.extreme(diff);
+                } else if multi < GPSTIME_MULTI {
+                    let context = if multi < 10 { 2 } else { 3 };
+                    diff = self.ic_gpstime.decompress(dec, multi.wrapping_mul(last_diff), context);
+                } else if multi == GPSTIME_MULTI {
+                    diff = self.ic_gpstime.decompress(dec, GPSTIME_MULTI.wrapping_mul(last_diff), 4);
+                    self.extreme(diff);
+                } else {
+                    let multi = GPSTIME_MULTI - multi;
+                    if multi > GPSTIME_MULTI_MINUS {
+                        diff = self.ic_gpstime.decompress(dec, multi.wrapping_mul(last_diff), 5);
+                    } else {
+                        diff = self.ic_gpstime.decompress(dec, GPSTIME_MULTI_MINUS.wrapping_mul(last_diff), 6);
+                        self.extreme(diff);
+                    }
+                }
+                self.last_gpstime[last] = self.last_gpstime[last].wrapping_add(diff as i64);
+            } else if multi == GPSTIME_MULTI_CODE_FULL {
+                self.read_sequence(dec);
+            } else {
+                self.last = (last + (multi - GPSTIME_MULTI_CODE_FULL) as usize) & 3;
+                return self.decompress(dec);
+            }
+        }
+        self.last_gpstime[self.last]
+    }
+
+    fn read_sequence(&mut self, dec: &mut ArithmeticDecoder) {
+        self.next = (self.next + 1) & 3;
+        let high = self.ic_gpstime.decompress(dec, (self.last_gpstime[self.last] >> 32) as i32, 8) as u32;
+        let low = dec.read_int();
+        self.last_gpstime[self.next] = (((high as u64) << 32) | low as u64) as i64;
+        self.last = self.next;
+        self.last_gpstime_diff[self.last] = 0;
+        self.multi_extreme_counter[self.last] = 0;
+    }
+}
+
+fn quantize(n: f32) -> i32 {
+    if n >= 0f32 { (n + 0.5f32) as i32 } else { (n - 0.5f32) as i32 }
+}
+
+/// The state of the point item for one scanner channel.
+struct Point14Context {
+    last_item: Point14,
+    last_intensity: [u16; 8],
+    last_x_diff_median5: [StreamingMedian5; 12],
+    last_y_diff_median5: [StreamingMedian5; 12],
+    last_z: [i32; 8],
+    m_changed_values: Vec<ArithmeticModel>,
+    m_scanner_channel: ArithmeticModel,
+    m_number_of_returns: Vec<Option<ArithmeticModel>>,
+    m_return_number: Vec<Option<ArithmeticModel>>,
+    m_return_number_gps_same: ArithmeticModel,
+    ic_dx: IntegerCompressor,
+    ic_dy: IntegerCompressor,
+    ic_z: IntegerCompressor,
+    m_classification: Vec<Option<ArithmeticModel>>,
+    m_flags: Vec<Option<ArithmeticModel>>,
+    m_user_data: Vec<Option<ArithmeticModel>>,
+    ic_intensity: IntegerCompressor,
+    ic_scan_angle: IntegerCompressor,
+    ic_point_source_id: IntegerCompressor,
+    gps: GpsTime14,
+}
+
+impl Point14Context {
+    fn new(item: &Point14) -> Point14Context {
+        let mut last_item = *item;
+        last_item.gps_time_change = false;
+        Point14Context {
+            last_item: last_item,
+            last_intensity: [item.intensity; 8],
+            last_x_diff_median5: [StreamingMedian5::new(); 12],
+            last_y_diff_median5: [StreamingMedian5::new(); 12],
+            last_z: [item.z; 8],
+            m_changed_values: (0..8).map(|_| ArithmeticModel::new(128)).collect(),
+            m_scanner_channel: ArithmeticModel::new(3),
+            m_number_of_returns: vec![None; 16],
+            m_return_number: vec![None; 16],
+            m_return_number_gps_same: ArithmeticModel::new(13),
+            ic_dx: IntegerCompressor::new(32, 2),
+            ic_dy: IntegerCompressor::new(32, 22),
+            ic_z: IntegerCompressor::new(32, 20),
+            m_classification: vec![None; 64],
+            m_flags: vec![None; 64],
+            m_user_data: vec![None; 64],
+            ic_intensity: IntegerCompressor::new(16, 4),
+            ic_scan_angle: IntegerCompressor::new(16, 2),
+            ic_point_source_id: IntegerCompressor::new(16, 1),
+            gps: GpsTime14::new(item.gps_time),
+        }
+    }
+}
+
+// the layers of the point item
+const LAYER_CHANNEL_RETURNS_XY: usize = 0;
+const LAYER_Z: usize = 1;
+const LAYER_CLASSIFICATION: usize = 2;
+const LAYER_FLAGS: usize = 3;
+const LAYER_INTENSITY: usize = 4;
+const LAYER_SCAN_ANGLE: usize = 5;
+const LAYER_USER_DATA: usize = 6;
+const LAYER_POINT_SOURCE: usize = 7;
+const LAYER_GPS_TIME: usize = 8;
+const NUM_POINT14_LAYERS: usize = 9;
+
+/// The core fields of point formats 6-10 (version 3).
+pub struct Point14Compressor {
+    contexts: Vec<Option<Point14Context>>,
+    current_context: usize,
+    encoders: Vec<ArithmeticEncoder>,
+    changed: [bool; NUM_POINT14_LAYERS],
+}
+
+impl Point14Compressor {
+    pub fn new() -> Point14Compressor {
+        Point14Compressor {
+            contexts: (0..4).map(|_| None).collect(),
+            current_context: 0,
+            encoders: (0..NUM_POINT14_LAYERS).map(|_| ArithmeticEncoder::new()).collect(),
+            changed: [false; NUM_POINT14_LAYERS],
+        }
+    }
+}
+
+impl LayeredItemCompressor for Point14Compressor {
+    fn init_first(&mut self, item: &[u8], context: &mut usize) {
+        let point = Point14::read(item);
+        self.current_context = point.scanner_channel as usize;
+        *context = self.current_context;
+        self.contexts[self.current_context] = Some(Point14Context::new(&point));
+    }
+
+    fn compress(&mut self, item: &[u8], context: &mut usize) {
+        let point = Point14::read(item);
+        let current = self.current_context;
+        let (lpr, mut last) = {
+            let c = self.contexts[current].as_ref().unwrap();
+            (c.last_item.last_point_return_context(), c.last_item)
+        };
+        let scanner_channel = point.scanner_channel as usize;
+        if scanner_channel != current {
+            if let Some(ref c) = self.contexts[scanner_channel] {
+                last = c.last_item;
+            }
+        }
+
+        let point_source_change = point.point_source_id != last.point_source_id;
+        let gps_time_change = point.gps_time != last.gps_time;
+        let scan_angle_change = point.scan_angle != last.scan_angle;
+        let (last_n, last_r) = (last.number_of_returns as u32, last.return_number as u32);
+        let (n, r) = (point.number_of_returns as u32, point.return_number as u32);
+
+        // a mask of the fields that changed from the last point of the channel
+        let mut changed_values = ((scanner_channel != current) as u32) << 6 |
+                                 (point_source_change as u32) << 5 |
+                                 (gps_time_change as u32) << 4 |
+                                 (scan_angle_change as u32) << 3 |
+                                 ((n != last_n) as u32) << 2;
+        if r != last_r {
+            if r == (last_r + 1) % 16 {
+                changed_values |= 1;
+            } else if r == (last_r + 15) % 16 {
+                changed_values |= 2;
+            } else {
+                changed_values |= 3;
+            }
+        }
+
+        {
+            let enc = &mut self.encoders[LAYER_CHANNEL_RETURNS_XY];
+            let c = self.contexts[current].as_mut().unwrap();
+            enc.encode_symbol(&mut c.m_changed_values[lpr], changed_values);
+            if changed_values & (1 << 6) != 0 {
+                let diff = scanner_channel as i32 - current as i32;
+                let sym = if diff > 0 { diff - 1 } else { diff + 4 - 1 };
+                enc.encode_symbol(&mut c.m_scanner_channel, sym as u32);
+            }
+        }
+        if scanner_channel != current {
+            if self.contexts[scanner_channel].is_none() {
+                // a new channel starts from the last point of the previous one
+                let previous = self.contexts[current].as_ref().unwrap().last_item;
+                self.contexts[scanner_channel] = Some(Point14Context::new(&previous));
+                last = self.contexts[scanner_channel].as_ref().unwrap().last_item;
+            }
+            self.current_context = scanner_channel;
+        }
+        *context = self.current_context;
+
+        let c = self.contexts[self.current_context].as_mut().unwrap();
+        let encoders = &mut self.encoders;
+        let changed = &mut self.changed;
+
+        {
+            let enc = &mut encoders[LAYER_CHANNEL_RETURNS_XY];
+            if changed_values & (1 << 2) != 0 {
+                enc.encode_symbol(context_model(&mut c.m_number_of_returns, last_n as usize, 16), n);
+            }
+            if changed_values & 3 == 3 {
+                if gps_time_change {
+                    enc.encode_symbol(context_model(&mut c.m_return_number, last_r as usize, 16), r);
+                } else {
+                    let diff = r as i32 - last_r as i32;
+                    let sym = if diff > 1 { diff - 2 } else { diff + 16 - 2 };
+                    enc.encode_symbol(&mut c.m_return_number_gps_same, sym as u32);
+                }
+            }
+        }
+
+        let m = NUMBER_RETURN_MAP_6CTX[n as usize][r as usize] as usize;
+        let l = number_return_level_8ctx(n, r);
+        // whether the point is a first (2) and/or last (1) return
+        let cpr = (((r == 1) as usize) << 1) + (r >= n) as usize;
+        let median_context = (m << 1) | gps_time_change as usize;
+
+        {
+            let enc = &mut encoders[LAYER_CHANNEL_RETURNS_XY];
+            let median = c.last_x_diff_median5[median_context].get();
+            let diff = point.x.wrapping_sub(last.x);
+            c.ic_dx.compress(enc, median, diff, (n == 1) as u32);
+            c.last_x_diff_median5[median_context].add(diff);
+
+            let median = c.last_y_diff_median5[median_context].get();
+            let diff = point.y.wrapping_sub(last.y);
+            let context = dy_context(n as usize, &c.ic_dx);
+            c.ic_dy.compress(enc, median, diff, context);
+            c.last_y_diff_median5[median_context].add(diff);
+        }
+
+        let context = z_context(n as usize, &c.ic_dx, &c.ic_dy);
+        c.ic_z.compress(&mut encoders[LAYER_Z], c.last_z[l], point.z, context);
+        c.last_z[l] = point.z;
+
+        changed[LAYER_CLASSIFICATION] |= point.classification != last.classification;
+        let ccc = (((last.classification & 0x1F) as usize) << 1) + (cpr == 3) as usize;
+        encoders[LAYER_CLASSIFICATION].encode_symbol(context_model(&mut c.m_classification, ccc, 256),
+                                                     point.classification as u32);
+
+        changed[LAYER_FLAGS] |= point.flags() != last.flags();
+        encoders[LAYER_FLAGS].encode_symbol(context_model(&mut c.m_flags, last.flags() as usize, 64), point.flags());
+
+        changed[LAYER_INTENSITY] |= point.intensity != last.intensity;
+        let context = (cpr << 1) | gps_time_change as usize;
+        c.ic_intensity.compress(&mut encoders[LAYER_INTENSITY],
+                                c.last_intensity[context] as i32,
+                                point.intensity as i32,
+                                cpr as u32);
+        c.last_intensity[context] = point.intensity;
+
+        if scan_angle_change {
+            changed[LAYER_SCAN_ANGLE] = true;
+            c.ic_scan_angle.compress(&mut encoders[LAYER_SCAN_ANGLE],
+                                     last.scan_angle as i32,
+                                     point.scan_angle as i32,
+                                     gps_time_change as u32);
+        }
+
+        changed[LAYER_USER_DATA] |= point.user_data != last.user_data;
+        encoders[LAYER_USER_DATA].encode_symbol(context_model(&mut c.m_user_data, last.user_data as usize / 4, 256),
+                                                point.user_data as u32);
+
+        if point_source_change {
+            changed[LAYER_POINT_SOURCE] = true;
+            c.ic_point_source_id.compress(&mut encoders[LAYER_POINT_SOURCE],
+                                          last.point_source_id as i32,
+                                          point.point_source_id as i32,
+                                          0);
+        }
+
+        if gps_time_change {
+            changed[LAYER_GPS_TIME] = true;
+            c.gps.compress(&mut encoders[LAYER_GPS_TIME], point.gps_time);
+        }
+
+        c.last_item = point;
+        c.last_item.gps_time_change = gps_time_change;
+    }
+
+    fn finish_layers(&mut self) -> Vec<Vec<u8>> {
+        let mut layers = vec![];
+        for (i, enc) in self.encoders.drain(..).enumerate() {
+            if i == LAYER_CHANNEL_RETURNS_XY || i == LAYER_Z || self.changed[i] {
+                layers.push(enc.done());
+            } else {
+                layers.push(vec![]);
+            }
+        }
+        layers
+    }
+}
+
+pub struct Point14Decompressor<'a> {
+    contexts: Vec<Option<Point14Context>>,
+    current_context: usize,
+    // the decoders of the layers that are present
+    decoders: Vec<Option<ArithmeticDecoder<'a>>>,
+}
+
+impl<'a> Point14Decompressor<'a> {
+    pub fn new(layers: Vec<&'a [u8]>) -> Point14Decompressor<'a> {
+        Point14Decompressor {
+            contexts: (0..4).map(|_| None).collect(),
+            current_context: 0,
+            decoders: layers.into_iter()
+                .map(|layer| if layer.len() > 0 { Some(ArithmeticDecoder::new(layer)) } else { None })
+                .collect(),
+        }
+    }
+}
+
+impl<'a> LayeredItemDecompressor for Point14Decompressor<'a> {
+    fn init_first(&mut self, item: &[u8], context: &mut usize) {
+        let point = Point14::read(item);
+        self.current_context = point.scanner_channel as usize;
+        *context = self.current_context;
+        self.contexts[self.current_context] = Some(Point14Context::new(&point));
+    }
+
+    fn decompress(&mut self, item: &mut [u8], context: &mut usize) {
+        let decoders = &mut self.decoders;
+        let mut current = self.current_context;
+        let changed_values = {
+            let dec = match decoders[LAYER_CHANNEL_RETURNS_XY] {
+                Some(ref mut dec) => dec,
+                None => {
+                    // a corrupt chunk; repeat the last point
+                    self.contexts[current].as_ref().unwrap().last_item.write(item);
+                    return;
+                }
+            };
+            let c = self.contexts[current].as_mut().unwrap();
+            let lpr = c.last_item.last_point_return_context();
+            let changed_values = dec.decode_symbol(&mut c.m_changed_values[lpr]);
+            if changed_values & (1 << 6) != 0 {
+                let diff = dec.decode_symbol(&mut c.m_scanner_channel) as usize;
+                let scanner_channel = (current + diff + 1) % 4;
+                let previous = c.last_item;
+                if self.contexts[scanner_channel].is_none() {
+                    self.contexts[scanner_channel] = Some(Point14Context::new(&previous));
+                }
+                current = scanner_channel;
+                self.current_context = current;
+                self.contexts[current].as_mut().unwrap().last_item.scanner_channel = scanner_channel as u8;
+            }
+            changed_values
+        };
+        *context = current;
+
+        let c = self.contexts[current].as_mut().unwrap();
+        let mut last = c.last_item;
+        let point_source_change = changed_values & (1 << 5) != 0;
+        let gps_time_change = changed_values & (1 << 4) != 0;
+        let scan_angle_change = changed_values & (1 << 3) != 0;
+        let (last_n, last_r) = (last.number_of_returns as u32, last.return_number as u32);
+
+        let (n, l, cpr) = {
+            let dec = decoders[LAYER_CHANNEL_RETURNS_XY].as_mut().unwrap();
+            let n = if changed_values & (1 << 2) != 0 {
+                dec.decode_symbol(context_model(&mut c.m_number_of_returns, last_n as usize, 16))
+            } else {
+                last_n
+            };
+            let r = match changed_values & 3 {
+                0 => last_r,
+                1 => (last_r + 1) % 16,
+                2 => (last_r + 15) % 16,
+                _ => {
+                    if gps_time_change {
+                        dec.decode_symbol(context_model(&mut c.m_return_number, last_r as usize, 16))
+                    } else {
+                        let sym = dec.decode_symbol(&mut c.m_return_number_gps_same);
+                        (last_r + sym + 2) % 16
+                    }
+                }
+            };
+            last.number_of_returns = n as u8;
+            last.return_number = r as u8;
+
+            let m = NUMBER_RETURN_MAP_6CTX[n as usize][r as usize] as usize;
+            let l = number_return_level_8ctx(n, r);
+            let cpr = (((r == 1) as usize) << 1) + (r >= n) as usize;
+            let median_context = (m << 1) | gps_time_change as usize;
+
+            let median = c.last_x_diff_median5[median_context].get();
+            let diff = c.ic_dx.decompress(dec, median, (n == 1) as u32);
+            last.x = last.x.wrapping_add(diff);
+            c.last_x_diff_median5[median_context].add(diff);
+
+            let median = c.last_y_diff_median5[median_context].get();
+            let context = dy_context(n as usize, &c.ic_dx);
+            let diff = c.ic_dy.decompress(dec, median, context);
+            last.y = last.y.wrapping_add(diff);
+            c.last_y_diff_median5[median_context].add(diff);
+            (n, l, cpr)
+        };
+
+        if let Some(ref mut dec) = decoders[LAYER_Z] {
+            let context = z_context(n as usize, &c.ic_dx, &c.ic_dy);
+            last.z = c.ic_z.decompress(dec, c.last_z[l], context);
+            c.last_z[l] = last.z;
+        }
+
+        if let Some(ref mut dec) = decoders[LAYER_CLASSIFICATION] {
+            let ccc = (((last.classification & 0x1F) as usize) << 1) + (cpr == 3) as usize;
+            last.classification = dec.decode_symbol(context_model(&mut c.m_classification, ccc, 256)) as u8;
+        }
+
+        if let Some(ref mut dec) = decoders[LAYER_FLAGS] {
+            let flags = dec.decode_symbol(context_model(&mut c.m_flags, last.flags() as usize, 64));
+            last.edge_of_flight_line = ((flags >> 5) & 1) as u8;
+            last.scan_direction_flag = ((flags >> 4) & 1) as u8;
+            last.classification_flags = (flags & 0x0F) as u8;
+        }
+
+        if let Some(ref mut dec) = decoders[LAYER_INTENSITY] {
+            let context = (cpr << 1) | gps_time_change as usize;
+            let intensity = c.ic_intensity.decompress(dec, c.last_intensity[context] as i32, cpr as u32) as u16;
+            c.last_intensity[context] = intensity;
+            last.intensity = intensity;
+        }
+
+        if let Some(ref mut dec) = decoders[LAYER_SCAN_ANGLE] {
+            if scan_angle_change {
+                last.scan_angle = c.ic_scan_angle.decompress(dec, last.scan_angle as i32, gps_time_change as u32) as i16;
+            }
+        }
+
+        if let Some(ref mut dec) = decoders[LAYER_USER_DATA] {
+            last.user_data = dec.decode_symbol(context_model(&mut c.m_user_data, last.user_data as usize / 4, 256)) as u8;
+        }
+
+        if let Some(ref mut dec) = decoders[LAYER_POINT_SOURCE] {
+            if point_source_change {
+                last.point_source_id = c.ic_point_source_id.decompress(dec, last.point_source_id as i32, 0) as u16;
+            }
+        }
+
+        if let Some(ref mut dec) = decoders[LAYER_GPS_TIME] {
+            if gps_time_change {
+                last.gps_time = c.gps.decompress(dec);
+            }
+        }
+
+        last.write(item);
+        last.gps_time_change = gps_time_change;
+        c.last_item = last;
+    }
+}
+
+/// The state of the colour item for one scanner channel.
+struct Rgb14Context {
+    last_item: [u16; 4],
+    rgb_models: RgbModels,
+    m_nir_bytes_used: ArithmeticModel,
+    m_nir_diff: Vec<ArithmeticModel>,
+}
+
+impl Rgb14Context {
+    fn new(last_item: [u16; 4]) -> Rgb14Context {
+        Rgb14Context {
+            last_item: last_item,
+            rgb_models: RgbModels::new(),
+            m_nir_bytes_used: ArithmeticModel::new(4),
+            m_nir_diff: vec![ArithmeticModel::new(256), ArithmeticModel::new(256)],
+        }
+    }
+}
+
+fn read_rgbnir(item: &[u8], nir: bool) -> [u16; 4] {
+    let rgb = read_rgb(item);
+    let nir = if nir { LittleEndian::read_u16(&item[6..8]) } else { 0 };
+    [rgb[0], rgb[1], rgb[2], nir]
+}
+
+// switches to the context selected by the point item, creating it from the
+// last values of the previous context if it is new
+fn switch_context<T, F>(contexts: &mut Vec<Option<T>>, current: &mut usize, context: usize, create: F)
+    where F: Fn(&T) -> T
+{
+    if *current != context {
+        if contexts[context].is_none() {
+            let new_context = create(contexts[*current].as_ref().unwrap());
+            contexts[context] = Some(new_context);
+        }
+        *current = context;
+    }
+}
+
+/// The colour channels of point formats 7 and 8, the latter with the near
+/// infrared channel in a layer of its own (version 3).
+pub struct Rgb14Compressor {
+    nir: bool,
+    contexts: Vec<Option<Rgb14Context>>,
+    current_context: usize,
+    enc_rgb: ArithmeticEncoder,
+    enc_nir: ArithmeticEncoder,
+    changed_rgb: bool,
+    changed_nir: bool,
+}
+
+impl Rgb14Compressor {
+    pub fn new(nir: bool) -> Rgb14Compressor {
+        Rgb14Compressor {
+            nir: nir,
+            contexts: (0..4).map(|_| None).collect(),
+            current_context: 0,
+            enc_rgb: ArithmeticEncoder::new(),
+            enc_nir: ArithmeticEncoder::new(),
+            changed_rgb: false,
+            changed_nir: false,
+        }
+    }
+}
+
+impl LayeredItemCompressor for Rgb14Compressor {
+    fn init_first(&mut self, item: &[u8], context: &mut usize) {
+        self.current_context = *context;
+        self.contexts[*context] = Some(Rgb14Context::new(read_rgbnir(item, self.nir)));
+    }
+
+    fn compress(&mut self, item: &[u8], context: &mut usize) {
+        switch_context(&mut self.contexts, &mut self.current_context, *context, |c| Rgb14Context::new(c.last_item));
+        let c = self.contexts[self.current_context].as_mut().unwrap();
+        let value = read_rgbnir(item, self.nir);
+        let last = c.last_item;
+        let (rgb, last_rgb) = ([value[0], value[1], value[2]], [last[0], last[1], last[2]]);
+        self.changed_rgb |= rgb != last_rgb;
+        c.rgb_models.compress(&mut self.enc_rgb, &last_rgb, &rgb);
+
+        if self.nir {
+            let enc = &mut self.enc_nir;
+            let (nir, last_nir) = (value[3], last[3]);
+            let mut sym = ((last_nir & 0x00FF) != (nir & 0x00FF)) as u32;
+            sym |= (((last_nir & 0xFF00) != (nir & 0xFF00)) as u32) << 1;
+            self.changed_nir |= sym != 0;
+            enc.encode_symbol(&mut c.m_nir_bytes_used, sym);
+            if sym & 1 != 0 {
+                let diff = (nir & 0xFF) as i32 - (last_nir & 0xFF) as i32;
+                enc.encode_symbol(&mut c.m_nir_diff[0], u8_fold(diff));
+            }
+            if sym & 2 != 0 {
+                let diff = (nir >> 8) as i32 - (last_nir >> 8) as i32;
+                enc.encode_symbol(&mut c.m_nir_diff[1], u8_fold(diff));
+            }
+        }
+        c.last_item = value;
+    }
+
+    fn finish_layers(&mut self) -> Vec<Vec<u8>> {
+        let enc_rgb = ::std::mem::replace(&mut self.enc_rgb, ArithmeticEncoder::new());
+        let mut layers = vec![if self.changed_rgb { enc_rgb.done() } else { vec![] }];
+        if self.nir {
+            let enc_nir = ::std::mem::replace(&mut self.enc_nir, ArithmeticEncoder::new());
+            layers.push(if self.changed_nir { enc_nir.done() } else { vec![] });
+        }
+        layers
+    }
+}
+
+pub struct Rgb14Decompressor<'a> {
+    nir: bool,
+    contexts: Vec<Option<Rgb14Context>>,
+    current_context: usize,
+    dec_rgb: Option<ArithmeticDecoder<'a>>,
+    dec_nir: Option<ArithmeticDecoder<'a>>,
+}
+
+impl<'a> Rgb14Decompressor<'a> {
+    pub fn new(nir: bool, layers: Vec<&'a [u8]>) -> Rgb14Decompressor<'a> {
+        let decoder = |i: usize| if i < layers.len() && layers[i].len() > 0 { Some(ArithmeticDecoder::new(layers[i])) } else { None };
+        Rgb14Decompressor {
+            nir: nir,
+            contexts: (0..4).map(|_| None).collect(),
+            current_context: 0,
+            dec_rgb: decoder(0),
+            dec_nir: decoder(1),
+        }
+    }
+}
+
+impl<'a> LayeredItemDecompressor for Rgb14Decompressor<'a> {
+    fn init_first(&mut self, item: &[u8], context: &mut usize) {
+        self.current_context = *context;
+        self.contexts[*context] = Some(Rgb14Context::new(read_rgbnir(item, self.nir)));
+    }
+
+    fn decompress(&mut self, item: &mut [u8], context: &mut usize) {
+        switch_context(&mut self.contexts, &mut self.current_context, *context, |c| Rgb14Context::new(c.last_item));
+        let c = self.contexts[self.current_context].as_mut().unwrap();
+        let last = c.last_item;
+        let mut value = last;
+        if let Some(ref mut dec) = self.dec_rgb {
+            let rgb = c.rgb_models.decompress(dec, &[last[0], last[1], last[2]]);
+            value[0] = rgb[0];
+            value[1] = rgb[1];
+            value[2] = rgb[2];
+        }
+        if self.nir {
+            if let Some(ref mut dec) = self.dec_nir {
+                let sym = dec.decode_symbol(&mut c.m_nir_bytes_used);
+                let mut nir = last[3];
+                if sym & 1 != 0 {
+                    let corr = dec.decode_symbol(&mut c.m_nir_diff[0]) as i32;
+                    nir = (nir & 0xFF00) | u8_fold(corr + (last[3] & 0xFF) as i32) as u16;
+                }
+                if sym & 2 != 0 {
+                    let corr = dec.decode_symbol(&mut c.m_nir_diff[1]) as i32;
+                    nir = (nir & 0x00FF) | (u8_fold(corr + (last[3] >> 8) as i32) as u16) << 8;
+                }
+                value[3] = nir;
+            }
+            LittleEndian::write_u16(&mut item[6..8], value[3]);
+        }
+        write_rgb(&[value[0], value[1], value[2]], item);
+        c.last_item = value;
+    }
+}
+
+/// The state of the extra bytes item for one scanner channel.
+struct Byte14Context {
+    last_item: Vec<u8>,
+    m_bytes: Vec<ArithmeticModel>,
+}
+
+impl Byte14Context {
+    fn new(last_item: &[u8]) -> Byte14Context {
+        Byte14Context {
+            last_item: last_item.to_vec(),
+            m_bytes: (0..last_item.len()).map(|_| ArithmeticModel::new(256)).collect(),
+        }
+    }
+}
+
+/// Extra bytes following the standard fields of a point record, each in a
+/// layer of its own (version 3).
+pub struct Byte14Compressor {
+    number: usize,
+    contexts: Vec<Option<Byte14Context>>,
+    current_context: usize,
+    encoders: Vec<ArithmeticEncoder>,
+    changed: Vec<bool>,
+}
+
+impl Byte14Compressor {
+    pub fn new(number: usize) -> Byte14Compressor {
+        Byte14Compressor {
+            number: number,
+            contexts: (0..4).map(|_| None).collect(),
+            current_context: 0,
+            encoders: (0..number).map(|_| ArithmeticEncoder::new()).collect(),
+            changed: vec![false; number],
+        }
+    }
+}
+
+impl LayeredItemCompressor for Byte14Compressor {
+    fn init_first(&mut self, item: &[u8], context: &mut usize) {
+        self.current_context = *context;
+        self.contexts[*context] = Some(Byte14Context::new(&item[0..self.number]));
+    }
+
+    fn compress(&mut self, item: &[u8], context: &mut usize) {
+        switch_context(&mut self.contexts, &mut self.current_context, *context, |c| Byte14Context::new(&c.last_item));
+        let c = self.contexts[self.current_context].as_mut().unwrap();
+        for i in 0..self.number {
+            let diff = item[i].wrapping_sub(c.last_item[i]);
+            self.encoders[i].encode_symbol(&mut c.m_bytes[i], diff as u32);
+            if diff != 0 {
+                self.changed[i] = true;
+                c.last_item[i] = item[i];
+            }
+        }
+    }
+
+    fn finish_layers(&mut self) -> Vec<Vec<u8>> {
+        let changed = &self.changed;
+        self.encoders
+            .drain(..)
+            .enumerate()
+            .map(|(i, enc)| if changed[i] { enc.done() } else { vec![] })
+            .collect()
+    }
+}
+
+pub struct Byte14Decompressor<'a> {
+    number: usize,
+    contexts: Vec<Option<Byte14Context>>,
+    current_context: usize,
+    decoders: Vec<Option<ArithmeticDecoder<'a>>>,
+}
+
+impl<'a> Byte14Decompressor<'a> {
+    pub fn new(number: usize, layers: Vec<&'a [u8]>) -> Byte14Decompressor<'a> {
+        Byte14Decompressor {
+            number: number,
+            contexts: (0..4).map(|_| None).collect(),
+            current_context: 0,
+            decoders: layers.into_iter()
+                .map(|layer| if layer.len() > 0 { Some(ArithmeticDecoder::new(layer)) } else { None })
+                .collect(),
+        }
+    }
+}
+
+impl<'a> LayeredItemDecompressor for Byte14Decompressor<'a> {
+    fn init_first(&mut self, item: &[u8], context: &mut usize) {
+        self.current_context = *context;
+        self.contexts[*context] = Some(Byte14Context::new(&item[0..self.number]));
+    }
+
+    fn decompress(&mut self, item: &mut [u8], context: &mut usize) {
+        switch_context(&mut self.contexts, &mut self.current_context, *context, |c| Byte14Context::new(&c.last_item));
+        let c = self.contexts[self.current_context].as_mut().unwrap();
+        for i in 0..self.number {
+            if let Some(ref mut dec) = self.decoders[i] {
+                let diff = dec.decode_symbol(&mut c.m_bytes[i]) as u8;
+                c.last_item[i] = c.last_item[i].wrapping_add(diff);
+            }
+            item[i] = c.last_item[i];
+        }
+    }
+}
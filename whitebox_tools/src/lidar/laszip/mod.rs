@@ -0,0 +1,803 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+
+// A pure-Rust implementation of the LASzip compression of LAS point records,
+// i.e. the LAZ format. The points are split into chunks, each of which is
+// compressed independently. In the pointwise scheme used for point formats
+// 0-3, the fields of each point are coded in turn into a single arithmetic-
+// coded stream. In the layered scheme used for the LAS 1.4 point formats 6-8,
+// each group of fields is coded into its own stream, or layer, so that a
+// reader can skip the layers that it does not need.
+
+mod arithmetic_coder;
+mod integer_compressor;
+mod items;
+mod items14;
+
+use std::mem;
+use std::io::{Cursor, Error, ErrorKind, Read, Seek, SeekFrom};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use lidar::vlr::Vlr;
+use self::arithmetic_coder::{ArithmeticDecoder, ArithmeticEncoder};
+use self::integer_compressor::IntegerCompressor;
+use self::items::*;
+use self::items14::*;
+
+pub const LASZIP_USER_ID: &'static str = "laszip encoded";
+pub const LASZIP_RECORD_ID: u16 = 22_204;
+
+/// The number of points in each chunk that is written.
+const CHUNK_SIZE: u32 = 50_000;
+/// The chunk size of files with chunks of varying size.
+const VARIABLE_CHUNK_SIZE: u32 = u32::max_value();
+
+const COMPRESSOR_POINTWISE: u16 = 1;
+const COMPRESSOR_POINTWISE_CHUNKED: u16 = 2;
+const COMPRESSOR_LAYERED_CHUNKED: u16 = 3;
+
+const ITEM_BYTE: u16 = 0;
+const ITEM_POINT10: u16 = 6;
+const ITEM_GPSTIME11: u16 = 7;
+const ITEM_RGB12: u16 = 8;
+const ITEM_WAVEPACKET13: u16 = 9;
+const ITEM_POINT14: u16 = 10;
+const ITEM_RGB14: u16 = 11;
+const ITEM_RGBNIR14: u16 = 12;
+const ITEM_WAVEPACKET14: u16 = 13;
+const ITEM_BYTE14: u16 = 14;
+
+/// Returns true if a VLR is the one describing the compression of a LAZ file.
+pub fn is_laszip_vlr(vlr: &Vlr) -> bool {
+    vlr.user_id.trim_matches('\0') == LASZIP_USER_ID && vlr.record_id == LASZIP_RECORD_ID
+}
+
+#[derive(Clone, Debug)]
+struct LasZipItem {
+    item_type: u16,
+    size: u16,
+    version: u16,
+}
+
+/// The contents of the LASzip VLR, which describes the compressor and the
+/// items, i.e. the groups of fields, that make up each point record.
+#[derive(Clone, Debug)]
+struct LasZipVlr {
+    compressor: u16,
+    coder: u16,
+    version_major: u8,
+    version_minor: u8,
+    version_revision: u16,
+    options: u32,
+    chunk_size: u32,
+    number_of_special_evlrs: i64,
+    offset_to_special_evlrs: i64,
+    items: Vec<LasZipItem>,
+}
+
+impl LasZipVlr {
+    fn from_point_format(point_format: u8, point_record_length: u16) -> Result<LasZipVlr, Error> {
+        let mut items = vec![];
+        let standard_length: u16;
+        let compressor;
+        match point_format {
+            0 | 1 | 2 | 3 => {
+                compressor = COMPRESSOR_POINTWISE_CHUNKED;
+                items.push(LasZipItem { item_type: ITEM_POINT10, size: 20, version: 2 });
+                if point_format == 1 || point_format == 3 {
+                    items.push(LasZipItem { item_type: ITEM_GPSTIME11, size: 8, version: 2 });
+                }
+                if point_format == 2 || point_format == 3 {
+                    items.push(LasZipItem { item_type: ITEM_RGB12, size: 6, version: 2 });
+                }
+                standard_length = items.iter().map(|item| item.size).sum();
+                if point_record_length > standard_length {
+                    items.push(LasZipItem { item_type: ITEM_BYTE, size: point_record_length - standard_length, version: 2 });
+                }
+            },
+            6 | 7 | 8 => {
+                compressor = COMPRESSOR_LAYERED_CHUNKED;
+                items.push(LasZipItem { item_type: ITEM_POINT14, size: 30, version: 3 });
+                if point_format == 7 {
+                    items.push(LasZipItem { item_type: ITEM_RGB14, size: 6, version: 3 });
+                } else if point_format == 8 {
+                    items.push(LasZipItem { item_type: ITEM_RGBNIR14, size: 8, version: 3 });
+                }
+                standard_length = items.iter().map(|item| item.size).sum();
+                if point_record_length > standard_length {
+                    items.push(LasZipItem { item_type: ITEM_BYTE14, size: point_record_length - standard_length, version: 3 });
+                }
+            },
+            _ => {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    format!("LAZ compression of point format {}, which contains waveform data, is not supported.", point_format)));
+            },
+        }
+        if point_record_length < standard_length {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "LAZ compression requires point records that contain all of the fields of the point format."));
+        }
+        Ok(LasZipVlr {
+            compressor: compressor,
+            coder: 0,
+            version_major: 3,
+            version_minor: 4,
+            version_revision: 3,
+            options: 0,
+            chunk_size: CHUNK_SIZE,
+            number_of_special_evlrs: -1,
+            offset_to_special_evlrs: -1,
+            items: items,
+        })
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<LasZipVlr, Error> {
+        let mut reader = Cursor::new(data);
+        let mut vlr = LasZipVlr {
+            compressor: try!(reader.read_u16::<LittleEndian>()),
+            coder: try!(reader.read_u16::<LittleEndian>()),
+            version_major: try!(reader.read_u8()),
+            version_minor: try!(reader.read_u8()),
+            version_revision: try!(reader.read_u16::<LittleEndian>()),
+            options: try!(reader.read_u32::<LittleEndian>()),
+            chunk_size: try!(reader.read_u32::<LittleEndian>()),
+            number_of_special_evlrs: try!(reader.read_i64::<LittleEndian>()),
+            offset_to_special_evlrs: try!(reader.read_i64::<LittleEndian>()),
+            items: vec![],
+        };
+        let num_items = try!(reader.read_u16::<LittleEndian>());
+        for _ in 0..num_items {
+            vlr.items.push(LasZipItem {
+                item_type: try!(reader.read_u16::<LittleEndian>()),
+                size: try!(reader.read_u16::<LittleEndian>()),
+                version: try!(reader.read_u16::<LittleEndian>()),
+            });
+        }
+        Ok(vlr)
+    }
+
+    fn to_vlr(&self) -> Vlr {
+        let mut data: Vec<u8> = Vec::with_capacity(34 + 6 * self.items.len());
+        data.write_u16::<LittleEndian>(self.compressor).unwrap();
+        data.write_u16::<LittleEndian>(self.coder).unwrap();
+        data.write_u8(self.version_major).unwrap();
+        data.write_u8(self.version_minor).unwrap();
+        data.write_u16::<LittleEndian>(self.version_revision).unwrap();
+        data.write_u32::<LittleEndian>(self.options).unwrap();
+        data.write_u32::<LittleEndian>(self.chunk_size).unwrap();
+        data.write_i64::<LittleEndian>(self.number_of_special_evlrs).unwrap();
+        data.write_i64::<LittleEndian>(self.offset_to_special_evlrs).unwrap();
+        data.write_u16::<LittleEndian>(self.items.len() as u16).unwrap();
+        for item in &self.items {
+            data.write_u16::<LittleEndian>(item.item_type).unwrap();
+            data.write_u16::<LittleEndian>(item.size).unwrap();
+            data.write_u16::<LittleEndian>(item.version).unwrap();
+        }
+        Vlr {
+            reserved: 0,
+            user_id: LASZIP_USER_ID.to_string(),
+            record_id: LASZIP_RECORD_ID,
+            record_length_after_header: data.len() as u64,
+            description: "by laszip of LAStools".to_string(),
+            binary_data: data,
+        }
+    }
+
+    fn record_length(&self) -> usize {
+        self.items.iter().map(|item| item.size as usize).sum()
+    }
+
+    fn is_layered(&self) -> bool {
+        self.compressor == COMPRESSOR_LAYERED_CHUNKED
+    }
+
+    // checks that each item can be decompressed
+    fn validate(&self) -> Result<(), Error> {
+        if self.coder != 0 {
+            return Err(Error::new(ErrorKind::InvalidData, "The LAZ file uses an unknown entropy coder."));
+        }
+        if self.compressor < COMPRESSOR_POINTWISE || self.compressor > COMPRESSOR_LAYERED_CHUNKED {
+            return Err(Error::new(ErrorKind::InvalidData, "The LAZ file uses an unknown compressor."));
+        }
+        for item in &self.items {
+            let supported = match item.item_type {
+                ITEM_POINT10 => item.size == 20 && item.version == 2,
+                ITEM_GPSTIME11 => item.size == 8 && item.version == 2,
+                ITEM_RGB12 => item.size == 6 && item.version == 2,
+                ITEM_BYTE => item.version == 2,
+                ITEM_POINT14 => item.size == 30 && item.version == 3,
+                ITEM_RGB14 => item.size == 6 && item.version == 3,
+                ITEM_RGBNIR14 => item.size == 8 && item.version == 3,
+                ITEM_BYTE14 => item.version == 3,
+                _ => false,
+            };
+            if !supported {
+                let msg = match item.item_type {
+                    ITEM_WAVEPACKET13 | ITEM_WAVEPACKET14 => "The LAZ file contains compressed waveform data, which is not supported.".to_string(),
+                    _ => format!("The LAZ file contains an item (type {}, version {}) that is not supported; it may have been created with an early version of LASzip.",
+                                 item.item_type, item.version),
+                };
+                return Err(Error::new(ErrorKind::InvalidData, msg));
+            }
+            // the layered items may only be used with the layered compressor
+            if (item.item_type >= ITEM_POINT14) != self.is_layered() {
+                return Err(Error::new(ErrorKind::InvalidData, "The LAZ file's items do not match its compressor."));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the chunk table, which records the number of bytes of each chunk
+/// (and the number of points, if they vary) in compressed form.
+fn write_chunk_table(out: &mut Vec<u8>, chunk_points: &[u32], chunk_bytes: &[u32], variable_size: bool) {
+    out.write_u32::<LittleEndian>(0).unwrap(); // version
+    out.write_u32::<LittleEndian>(chunk_bytes.len() as u32).unwrap();
+    if chunk_bytes.len() > 0 {
+        let mut enc = ArithmeticEncoder::new();
+        let mut ic = IntegerCompressor::new(32, 2);
+        for i in 0..chunk_bytes.len() {
+            if variable_size {
+                let pred = if i > 0 { chunk_points[i - 1] } else { 0 };
+                ic.compress(&mut enc, pred as i32, chunk_points[i] as i32, 0);
+            }
+            let pred = if i > 0 { chunk_bytes[i - 1] } else { 0 };
+            ic.compress(&mut enc, pred as i32, chunk_bytes[i] as i32, 1);
+        }
+        out.extend_from_slice(&enc.done());
+    }
+}
+
+/// A chunk of compressed point records.
+#[derive(Clone, Copy, Debug)]
+pub struct LasZipChunk {
+    /// The position of the chunk within the file.
+    pub offset: u64,
+    /// The size of the chunk in bytes.
+    pub byte_count: u64,
+    /// The number of point records in the chunk.
+    pub num_points: u64,
+}
+
+/// Decompresses the point records of a LAZ file.
+pub struct LasZipDecompressor {
+    vlr: LasZipVlr,
+}
+
+impl LasZipDecompressor {
+    /// Creates a decompressor from the LASzip VLR of a file.
+    pub fn new(vlr: &Vlr) -> Result<LasZipDecompressor, Error> {
+        let vlr = try!(LasZipVlr::from_bytes(&vlr.binary_data)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "The LASzip VLR of the LAZ file is malformed.")));
+        try!(vlr.validate());
+        Ok(LasZipDecompressor { vlr: vlr })
+    }
+
+    /// The length of the decompressed point records.
+    pub fn record_length(&self) -> usize {
+        self.vlr.record_length()
+    }
+
+    /// Locates the chunks of compressed points. The compressed data begin at
+    /// the offset to the point data and end at `end_of_points`, i.e. at the
+    /// first EVLR or the end of the file.
+    pub fn read_chunk_table<R: Read + Seek>(&self,
+                                            reader: &mut R,
+                                            offset_to_points: u64,
+                                            end_of_points: u64,
+                                            num_points: u64)
+                                            -> Result<Vec<LasZipChunk>, Error> {
+        if self.vlr.compressor == COMPRESSOR_POINTWISE {
+            // the points were compressed as a single chunk
+            return Ok(vec![LasZipChunk {
+                offset: offset_to_points,
+                byte_count: end_of_points - offset_to_points,
+                num_points: num_points,
+            }]);
+        }
+
+        let err = || Error::new(ErrorKind::InvalidData, "The chunk table of the LAZ file is missing or corrupt.");
+        try!(reader.seek(SeekFrom::Start(offset_to_points)));
+        let mut chunk_table_start = try!(reader.read_i64::<LittleEndian>());
+        let chunks_start = offset_to_points + 8;
+        if chunk_table_start == -1 {
+            // the table was written by a streaming compressor, which stores its
+            // position at the end of the file
+            try!(reader.seek(SeekFrom::End(-8)));
+            chunk_table_start = try!(reader.read_i64::<LittleEndian>());
+        }
+        if chunk_table_start <= chunks_start as i64 {
+            return Err(err());
+        }
+        try!(reader.seek(SeekFrom::Start(chunk_table_start as u64)));
+        let version = try!(reader.read_u32::<LittleEndian>());
+        let num_chunks = try!(reader.read_u32::<LittleEndian>()) as usize;
+        if version != 0 {
+            return Err(err());
+        }
+        let mut data = vec![];
+        try!(reader.read_to_end(&mut data));
+
+        let variable_size = self.vlr.chunk_size == VARIABLE_CHUNK_SIZE;
+        let mut chunks = Vec::with_capacity(num_chunks);
+        if num_chunks > 0 {
+            let mut dec = ArithmeticDecoder::new(&data);
+            let mut ic = IntegerCompressor::new(32, 2);
+            let (mut last_points, mut last_bytes) = (0i32, 0i32);
+            for _ in 0..num_chunks {
+                if variable_size {
+                    last_points = ic.decompress(&mut dec, last_points, 0);
+                }
+                last_bytes = ic.decompress(&mut dec, last_bytes, 1);
+                chunks.push(LasZipChunk {
+                    offset: 0,
+                    byte_count: last_bytes as u32 as u64,
+                    num_points: if variable_size { last_points as u32 as u64 } else { self.vlr.chunk_size as u64 },
+                });
+            }
+        }
+
+        // the chunks follow one another; the last may hold fewer points
+        let mut offset = chunks_start;
+        let mut points_remaining = num_points;
+        for chunk in &mut chunks {
+            chunk.offset = offset;
+            offset += chunk.byte_count;
+            chunk.num_points = chunk.num_points.min(points_remaining);
+            points_remaining -= chunk.num_points;
+        }
+        if points_remaining > 0 || offset > end_of_points.max(chunk_table_start as u64) {
+            return Err(err());
+        }
+        Ok(chunks)
+    }
+
+    /// Decompresses a chunk, appending its point records to `out`.
+    pub fn decompress_chunk(&self, data: &[u8], num_points: usize, out: &mut Vec<u8>) -> Result<(), Error> {
+        if num_points == 0 {
+            return Ok(());
+        }
+        let record_length = self.record_length();
+        if data.len() < record_length {
+            return Err(Error::new(ErrorKind::InvalidData, "A chunk of the LAZ file is truncated."));
+        }
+        let start = out.len();
+        out.resize(start + num_points * record_length, 0u8);
+        let records = &mut out[start..];
+
+        // the first point is stored raw
+        records[0..record_length].copy_from_slice(&data[0..record_length]);
+        let data = &data[record_length..];
+
+        if !self.vlr.is_layered() {
+            let mut codecs: Vec<Box<ItemCodec>> = self.vlr.items.iter().map(|item| pointwise_codec(item)).collect();
+            let mut offset = 0;
+            for (codec, item) in codecs.iter_mut().zip(self.vlr.items.iter()) {
+                codec.init_first(&records[offset..offset + item.size as usize]);
+                offset += item.size as usize;
+            }
+            let mut dec = ArithmeticDecoder::new(data);
+            for record in records.chunks_mut(record_length).skip(1) {
+                let mut offset = 0;
+                for (codec, item) in codecs.iter_mut().zip(self.vlr.items.iter()) {
+                    codec.decompress(&mut dec, &mut record[offset..offset + item.size as usize]);
+                    offset += item.size as usize;
+                }
+            }
+        } else {
+            // the number of points, followed by the sizes of each item's layers
+            // and then the layers themselves
+            let err = || Error::new(ErrorKind::InvalidData, "A chunk of the LAZ file is truncated.");
+            let mut reader = Cursor::new(data);
+            try!(reader.read_u32::<LittleEndian>().map_err(|_| err()));
+            let mut layer_sizes = vec![];
+            for item in &self.vlr.items {
+                let mut sizes = vec![];
+                for _ in 0..num_layers(item) {
+                    sizes.push(try!(reader.read_u32::<LittleEndian>().map_err(|_| err())) as usize);
+                }
+                layer_sizes.push(sizes);
+            }
+            let mut offset = reader.position() as usize;
+            let mut codecs: Vec<Box<LayeredItemDecompressor>> = vec![];
+            for (item, sizes) in self.vlr.items.iter().zip(layer_sizes.iter()) {
+                let mut layers = vec![];
+                for &size in sizes {
+                    if offset + size > data.len() {
+                        return Err(err());
+                    }
+                    layers.push(&data[offset..offset + size]);
+                    offset += size;
+                }
+                codecs.push(layered_decompressor(item, layers));
+            }
+
+            let mut context = 0usize;
+            let mut offset = 0;
+            for (codec, item) in codecs.iter_mut().zip(self.vlr.items.iter()) {
+                codec.init_first(&records[offset..offset + item.size as usize], &mut context);
+                offset += item.size as usize;
+            }
+            for record in records.chunks_mut(record_length).skip(1) {
+                let mut offset = 0;
+                for (codec, item) in codecs.iter_mut().zip(self.vlr.items.iter()) {
+                    codec.decompress(&mut record[offset..offset + item.size as usize], &mut context);
+                    offset += item.size as usize;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compresses point records into the LAZ format.
+pub struct LasZipCompressor {
+    vlr: LasZipVlr,
+    record_length: usize,
+    pointwise: Option<(ArithmeticEncoder, Vec<Box<ItemCodec>>)>,
+    layered: Vec<Box<LayeredItemCompressor>>,
+    context: usize,
+    chunk_count: u32,
+    chunk: Vec<u8>,
+    chunks: Vec<u8>,
+    chunk_points: Vec<u32>,
+    chunk_bytes: Vec<u32>,
+}
+
+impl LasZipCompressor {
+    pub fn new(point_format: u8, point_record_length: u16) -> Result<LasZipCompressor, Error> {
+        let vlr = try!(LasZipVlr::from_point_format(point_format, point_record_length));
+        Ok(LasZipCompressor {
+            record_length: vlr.record_length(),
+            vlr: vlr,
+            pointwise: None,
+            layered: vec![],
+            context: 0,
+            chunk_count: 0,
+            chunk: vec![],
+            chunks: vec![],
+            chunk_points: vec![],
+            chunk_bytes: vec![],
+        })
+    }
+
+    /// The LASzip VLR, which must be included in the file's header.
+    pub fn vlr(&self) -> Vlr {
+        self.vlr.to_vlr()
+    }
+
+    /// Compresses a point record.
+    pub fn compress(&mut self, record: &[u8]) {
+        if self.chunk_count == self.vlr.chunk_size {
+            self.finish_chunk();
+        }
+        let items = &self.vlr.items;
+        if self.chunk_count == 0 {
+            // the first point is stored raw and initializes the item codecs
+            self.chunk.extend_from_slice(&record[0..self.record_length]);
+            let mut offset = 0;
+            if !self.vlr.is_layered() {
+                let mut codecs: Vec<Box<ItemCodec>> = items.iter().map(|item| pointwise_codec(item)).collect();
+                for (codec, item) in codecs.iter_mut().zip(items.iter()) {
+                    codec.init_first(&record[offset..offset + item.size as usize]);
+                    offset += item.size as usize;
+                }
+                self.pointwise = Some((ArithmeticEncoder::new(), codecs));
+            } else {
+                self.layered = items.iter().map(|item| layered_compressor(item)).collect();
+                for (codec, item) in self.layered.iter_mut().zip(items.iter()) {
+                    codec.init_first(&record[offset..offset + item.size as usize], &mut self.context);
+                    offset += item.size as usize;
+                }
+            }
+        } else if let Some((ref mut enc, ref mut codecs)) = self.pointwise {
+            let mut offset = 0;
+            for (codec, item) in codecs.iter_mut().zip(items.iter()) {
+                codec.compress(enc, &record[offset..offset + item.size as usize]);
+                offset += item.size as usize;
+            }
+        } else {
+            let mut offset = 0;
+            for (codec, item) in self.layered.iter_mut().zip(items.iter()) {
+                codec.compress(&record[offset..offset + item.size as usize], &mut self.context);
+                offset += item.size as usize;
+            }
+        }
+        self.chunk_count += 1;
+    }
+
+    fn finish_chunk(&mut self) {
+        if self.chunk_count == 0 {
+            return;
+        }
+        if let Some((enc, _)) = self.pointwise.take() {
+            self.chunk.extend_from_slice(&enc.done());
+        } else {
+            self.chunk.write_u32::<LittleEndian>(self.chunk_count).unwrap();
+            let layers: Vec<Vec<Vec<u8>>> = self.layered.iter_mut().map(|codec| codec.finish_layers()).collect();
+            for layer in layers.iter().flat_map(|item_layers| item_layers.iter()) {
+                self.chunk.write_u32::<LittleEndian>(layer.len() as u32).unwrap();
+            }
+            for layer in layers.iter().flat_map(|item_layers| item_layers.iter()) {
+                self.chunk.extend_from_slice(layer);
+            }
+            self.layered.clear();
+        }
+        self.chunk_points.push(self.chunk_count);
+        self.chunk_bytes.push(self.chunk.len() as u32);
+        self.chunks.append(&mut self.chunk);
+        self.chunk_count = 0;
+    }
+
+    /// Returns the compressed data of the chunks completed since the last call,
+    /// so that they can be written out while compression continues.
+    pub fn take_chunks(&mut self) -> Vec<u8> {
+        mem::replace(&mut self.chunks, vec![])
+    }
+
+    /// Finishes the compression, returning the compressed data of the chunks
+    /// that have not been taken and the chunk table, which follows them. In the
+    /// file, the chunks are preceded by the 64-bit position of the chunk table.
+    pub fn finish(mut self) -> (Vec<u8>, Vec<u8>) {
+        self.finish_chunk();
+        let mut chunk_table = Vec::with_capacity(16 + 8 * self.chunk_bytes.len());
+        write_chunk_table(&mut chunk_table, &self.chunk_points, &self.chunk_bytes, false);
+        (self.chunks, chunk_table)
+    }
+}
+
+fn pointwise_codec(item: &LasZipItem) -> Box<ItemCodec> {
+    match item.item_type {
+        ITEM_POINT10 => Box::new(Point10Codec::new()),
+        ITEM_GPSTIME11 => Box::new(GpsTime11Codec::new()),
+        ITEM_RGB12 => Box::new(Rgb12Codec::new()),
+        _ => Box::new(ByteCodec::new(item.size as usize)),
+    }
+}
+
+fn num_layers(item: &LasZipItem) -> usize {
+    match item.item_type {
+        ITEM_POINT14 => 9,
+        ITEM_RGB14 => 1,
+        ITEM_RGBNIR14 => 2,
+        _ => item.size as usize,
+    }
+}
+
+fn layered_compressor(item: &LasZipItem) -> Box<LayeredItemCompressor> {
+    match item.item_type {
+        ITEM_POINT14 => Box::new(Point14Compressor::new()),
+        ITEM_RGB14 => Box::new(Rgb14Compressor::new(false)),
+        ITEM_RGBNIR14 => Box::new(Rgb14Compressor::new(true)),
+        _ => Box::new(Byte14Compressor::new(item.size as usize)),
+    }
+}
+
+fn layered_decompressor<'a>(item: &LasZipItem, layers: Vec<&'a [u8]>) -> Box<LayeredItemDecompressor + 'a> {
+    match item.item_type {
+        ITEM_POINT14 => Box::new(Point14Decompressor::new(layers)),
+        ITEM_RGB14 => Box::new(Rgb14Decompressor::new(false, layers)),
+        ITEM_RGBNIR14 => Box::new(Rgb14Decompressor::new(true, layers)),
+        _ => Box::new(Byte14Decompressor::new(item.size as usize, layers)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LasZipCompressor, LasZipDecompressor};
+    use byteorder::{LittleEndian, WriteBytesExt};
+    use lidar::las::LasFile;
+    use std::env;
+    use std::io::{Cursor, Error};
+
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> u32 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (self.0 >> 32) as u32
+        }
+    }
+
+    // Creates point records that resemble those of a scan, i.e. nearby points
+    // with runs of returns and slowly increasing GPS times, along with repeated
+    // points and jumps that exercise the less common code paths.
+    fn point_records(point_format: u8, num_extra_bytes: usize, num_points: usize) -> (u16, Vec<u8>) {
+        let mut rng = Lcg(point_format as u64 + 100 * num_extra_bytes as u64);
+        let mut out = vec![];
+        let (mut x, mut y, mut z) = (100_000i32, 200_000i32, 5_000i32);
+        let mut gps_time = 1000f64;
+        let (mut ret, mut num_returns) = (1u8, 1u8);
+        let mut rgb = [1000u16, 2000, 3000, 4000];
+        for i in 0..num_points {
+            let r = rng.next();
+            match r % 50 {
+                0 => (), // a repeated point
+                1 => { x = rng.next() as i32; y = rng.next() as i32; z = rng.next() as i32; },
+                _ => { x += (r % 200) as i32 - 60; y += (r >> 8) as i32 % 30 - 15; z += (r >> 16) as i32 % 50 - 25; },
+            }
+            if ret >= num_returns {
+                ret = 1;
+                num_returns = if point_format >= 6 { 1 + (r >> 20) as u8 % 15 } else { 1 + (r >> 20) as u8 % 7 };
+                gps_time += if r % 97 == 0 { 1.0e6 * (r % 7) as f64 } else { 0.000_01 * (r % 13) as f64 };
+            } else {
+                ret += 1;
+            }
+            if r % 13 == 0 {
+                for c in rgb.iter_mut() {
+                    *c = c.wrapping_add((rng.next() % 512) as u16).wrapping_sub(256);
+                }
+            }
+            let intensity = (r >> 4) as u16 % 4096;
+            let class = if r % 9 == 0 { 2u8 } else { 1u8 + (i % 3) as u8 };
+            let user_data = (r >> 24) as u8;
+            let point_source_id = 1 + (i / 20_000) as u16;
+
+            out.write_i32::<LittleEndian>(x).unwrap();
+            out.write_i32::<LittleEndian>(y).unwrap();
+            out.write_i32::<LittleEndian>(z).unwrap();
+            out.write_u16::<LittleEndian>(intensity).unwrap();
+            let edge = ((r >> 28) & 1) as u8;
+            let direction = ((i / 500) % 2) as u8;
+            if point_format < 6 {
+                out.push(ret | num_returns << 3 | direction << 6 | edge << 7);
+                out.push(class | ((r >> 29) as u8 & 0b111) << 5);
+                out.push(((r >> 12) % 60) as u8); // the scan angle, as an i8
+                out.push(user_data);
+                out.write_u16::<LittleEndian>(point_source_id).unwrap();
+                if point_format == 1 || point_format == 3 {
+                    out.write_f64::<LittleEndian>(gps_time).unwrap();
+                }
+                if point_format == 2 || point_format == 3 {
+                    for c in &rgb[0..3] {
+                        out.write_u16::<LittleEndian>(*c).unwrap();
+                    }
+                }
+            } else {
+                let channel = ((i / 7_000) % 4) as u8;
+                out.push(ret | num_returns << 4);
+                out.push((r >> 29) as u8 & 0b1111 | channel << 4 | direction << 6 | edge << 7);
+                out.push(class);
+                out.push(user_data);
+                out.write_i16::<LittleEndian>(((r >> 12) % 10_000) as i16 - 5_000).unwrap();
+                out.write_u16::<LittleEndian>(point_source_id).unwrap();
+                out.write_f64::<LittleEndian>(gps_time).unwrap();
+                if point_format == 7 || point_format == 8 {
+                    let num_colours = if point_format == 8 { 4 } else { 3 };
+                    for c in &rgb[0..num_colours] {
+                        out.write_u16::<LittleEndian>(*c).unwrap();
+                    }
+                }
+            }
+            for j in 0..num_extra_bytes {
+                // a slowly changing attribute followed by noise
+                out.push(if j % 2 == 0 { (i / 100 + j) as u8 } else { rng.next() as u8 });
+            }
+        }
+        ((out.len() / num_points) as u16, out)
+    }
+
+    // Compresses the records into the layout of a LAZ file's point data, i.e. the
+    // position of the chunk table, the chunks and the chunk table, optionally
+    // taking the completed chunks as they are written, as LasWriter does.
+    fn compress(point_format: u8, record_length: u16, records: &[u8], streaming: bool) -> (LasZipCompressor, Vec<u8>) {
+        let mut compressor = LasZipCompressor::new(point_format, record_length).unwrap();
+        let codec = LasZipCompressor::new(point_format, record_length).unwrap();
+        let mut chunks = vec![];
+        for record in records.chunks(record_length as usize) {
+            compressor.compress(record);
+            if streaming {
+                chunks.extend(compressor.take_chunks());
+            }
+        }
+        let (remaining_chunks, chunk_table) = compressor.finish();
+        chunks.extend(remaining_chunks);
+        let mut data = vec![];
+        data.write_i64::<LittleEndian>(8 + chunks.len() as i64).unwrap();
+        data.extend(chunks);
+        data.extend(chunk_table);
+        (codec, data)
+    }
+
+    fn decompress(codec: &LasZipCompressor, data: &[u8], num_points: u64) -> Result<Vec<u8>, Error> {
+        let decompressor = try!(LasZipDecompressor::new(&codec.vlr()));
+        let chunks = try!(decompressor.read_chunk_table(&mut Cursor::new(data), 0, data.len() as u64, num_points));
+        let mut out = vec![];
+        for chunk in chunks {
+            let start = chunk.offset as usize;
+            try!(decompressor.decompress_chunk(&data[start..start + chunk.byte_count as usize], chunk.num_points as usize, &mut out));
+        }
+        Ok(out)
+    }
+
+    fn check_round_trip(point_format: u8, num_extra_bytes: usize, num_points: usize) {
+        let (record_length, records) = point_records(point_format, num_extra_bytes, num_points);
+        for &streaming in &[false, true] {
+            let (codec, data) = compress(point_format, record_length, &records, streaming);
+            if num_points > 1 {
+                assert!(data.len() < records.len());
+            }
+            let decompressed = decompress(&codec, &data, num_points as u64).unwrap();
+            assert_eq!(decompressed.len(), records.len());
+            for (i, (a, b)) in decompressed.chunks(record_length as usize).zip(records.chunks(record_length as usize)).enumerate() {
+                assert_eq!(a, b, "point {} of format {} differs", i, point_format);
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_of_legacy_formats() {
+        for point_format in 0..4 {
+            check_round_trip(point_format, 0, 5_000);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_of_extended_formats() {
+        for point_format in 6..9 {
+            check_round_trip(point_format, 0, 5_000);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_with_extra_bytes() {
+        for &point_format in &[0u8, 1, 2, 3, 6, 7, 8] {
+            check_round_trip(point_format, 7, 3_000);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_of_several_chunks() {
+        // the points are compressed in chunks of 50,000
+        check_round_trip(3, 2, 120_001);
+        check_round_trip(8, 3, 120_001);
+    }
+
+    #[test]
+    fn test_single_point() {
+        check_round_trip(1, 0, 1);
+        check_round_trip(6, 1, 1);
+    }
+
+    #[test]
+    fn test_waveform_formats_are_rejected() {
+        assert!(LasZipCompressor::new(4, 57).is_err());
+        assert!(LasZipCompressor::new(9, 59).is_err());
+        assert!(LasZipCompressor::new(6, 20).is_err());
+    }
+
+    #[test]
+    fn test_truncated_chunk_is_an_error() {
+        let (record_length, records) = point_records(7, 0, 1_000);
+        let (codec, data) = compress(7, record_length, &records, false);
+        let decompressor = LasZipDecompressor::new(&codec.vlr()).unwrap();
+        let chunks = decompressor.read_chunk_table(&mut Cursor::new(&data), 0, data.len() as u64, 1_000).unwrap();
+        let mut out = vec![];
+        assert!(decompressor.decompress_chunk(&data[8..8 + record_length as usize + 2], 1_000, &mut out).is_err());
+        assert_eq!(chunks.len(), 1);
+    }
+
+    // Checks the decoding of a LAZ file compressed by the reference LASzip
+    // implementation against the uncompressed LAS file, e.g. one produced by
+    // `laszip -i file.laz -o file.las`. The files are not distributed with the
+    // source; run with `WBT_LASZIP_REFERENCE=/path/to/file cargo test -- --ignored`,
+    // where file.laz and file.las exist.
+    #[test]
+    #[ignore]
+    fn test_decoding_of_reference_file() {
+        let base = env::var("WBT_LASZIP_REFERENCE").expect("WBT_LASZIP_REFERENCE is not set");
+        let laz = LasFile::new(&format!("{}.laz", base), "r").unwrap();
+        let las = LasFile::new(&format!("{}.las", base), "r").unwrap();
+        assert_eq!(laz.header.number_of_points, las.header.number_of_points);
+        assert_eq!(laz.header.point_format, las.header.point_format);
+        for i in 0..las.header.number_of_points as usize {
+            let (a, b) = (laz.get_record(i), las.get_record(i));
+            assert_eq!(format!("{:?}", a), format!("{:?}", b), "point {} differs", i);
+            assert_eq!(laz.get_extra_bytes(i), las.get_extra_bytes(i), "the extra bytes of point {} differ", i);
+        }
+    }
+}
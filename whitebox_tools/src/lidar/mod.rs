@@ -1,10 +1,15 @@
 // pub mod header;
 // pub mod las;
+mod las_index;
+mod las_stream;
+mod las_tiles;
 // pub mod point_data;
 // pub mod vlr;
 
 // private sub-module defined in other files
+mod extra_bytes;
 mod header;
+mod laszip;
 mod las;
 mod point_data;
 mod vlr;
@@ -13,8 +18,14 @@ mod vlr;
 pub use self::las::CoordinateReferenceSystem;
 pub use self::las::GlobalEncodingField;
 pub use self::las::GpsTimeType;
+pub use self::extra_bytes::ExtraByteType;
+pub use self::extra_bytes::ExtraBytesDescriptor;
 pub use self::header::LasHeader;
 pub use self::las::LasFile;
+pub use self::las_index::LasIndex;
+pub use self::las_stream::LasReader;
+pub use self::las_stream::LasWriter;
+pub use self::las_tiles::LasTileSet;
 pub use self::las::LidarPointRecord;
 pub use self::las::PointRecord0;
 pub use self::las::PointRecord1;
@@ -22,6 +33,11 @@ pub use self::las::PointRecord2;
 pub use self::las::PointRecord3;
 pub use self::las::PointRecord4;
 pub use self::las::PointRecord5;
+pub use self::las::PointRecord6;
+pub use self::las::PointRecord7;
+pub use self::las::PointRecord8;
+pub use self::las::PointRecord9;
+pub use self::las::PointRecord10;
 pub use self::point_data::PointBitField;
 pub use self::point_data::ClassificationBitField;
 pub use self::point_data::PointData;
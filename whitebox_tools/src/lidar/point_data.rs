@@ -1,58 +1,100 @@
 use std::fmt;
 
+/// The return number, number of returns, scanner channel, scan direction and
+/// edge of flight line fields of a point. These are stored in the same layout
+/// for all point formats, with four bits each for the return number and the
+/// number of returns (bits 0-3 and 4-7), allowing the 15 returns of the LAS 1.4
+/// extended formats (6-10), followed by the scanner channel (bits 8-9), scan
+/// direction flag (bit 10) and edge of flight line flag (bit 11).
 #[derive(Default, Debug, Clone, Copy)]
 pub struct PointBitField {
-    pub value: u8,
+    pub value: u16,
 }
 
 impl PointBitField {
     /// Return number
     pub fn return_number(&self) -> u8 {
-        let mut ret = self.value & 0b0000_0111u8;
+        let mut ret = (self.value & 0b0000_1111u16) as u8;
         if ret == 0 { ret = 1; }
         ret
     }
 
+    pub fn set_return_number(&mut self, value: u8) {
+        self.value = (self.value & !0b0000_1111u16) | (value as u16 & 0b0000_1111u16);
+    }
+
     /// Number of returns
     pub fn number_of_returns(&self) -> u8 {
-        let mut ret = (self.value & 0b0011_1000u8) >> 3u8;
+        let mut ret = ((self.value & 0b1111_0000u16) >> 4u16) as u8;
         if ret == 0 { ret = 1; }
         ret
     }
 
+    pub fn set_number_of_returns(&mut self, value: u8) {
+        self.value = (self.value & !0b1111_0000u16) | ((value as u16 & 0b0000_1111u16) << 4u16);
+    }
+
+    /// Scanner channel, i.e. the channel (0-3) of a multi-channel system.
+    /// This is always zero for the legacy point formats (0-5).
+    pub fn scanner_channel(&self) -> u8 {
+        ((self.value >> 8u16) & 0b11u16) as u8
+    }
+
+    pub fn set_scanner_channel(&mut self, value: u8) {
+        self.value = (self.value & !(0b11u16 << 8u16)) | ((value as u16 & 0b11u16) << 8u16);
+    }
+
     /// Scan direction flag, `true` if moving from the left side of the
     /// in-track direction to the right side and false the opposite.
     pub fn scan_direction_flag(&self) -> bool {
-        //((self.value >> 6_u8) & 1_u8) == 1_u8
-        (self.value & 0b0100_0000u8) == 0b0100_0000u8
+        (self.value & (1u16 << 10u16)) != 0u16
+    }
+
+    pub fn set_scan_direction_flag(&mut self, val: bool) {
+        if val {
+            self.value = self.value | (1u16 << 10u16);
+        } else {
+            self.value = self.value & !(1u16 << 10u16);
+        }
     }
 
     /// Edge of flightline flag
     pub fn edge_of_flightline_flag(&self) -> bool {
-        (self.value & 0b1000_0000u8) == 0b1000_0000u8
+        (self.value & (1u16 << 11u16)) != 0u16
+    }
+
+    pub fn set_edge_of_flightline_flag(&mut self, val: bool) {
+        if val {
+            self.value = self.value | (1u16 << 11u16);
+        } else {
+            self.value = self.value & !(1u16 << 11u16);
+        }
     }
 }
 
 impl fmt::Display for PointBitField {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "return={}, num. returns={}, scan direction={}, edge of flightline={}", self.return_number(), self.number_of_returns(), self.scan_direction_flag(), self.edge_of_flightline_flag())
+        write!(f, "return={}, num. returns={}, scanner channel={}, scan direction={}, edge of flightline={}", self.return_number(), self.number_of_returns(), self.scanner_channel(), self.scan_direction_flag(), self.edge_of_flightline_flag())
     }
 }
 
+/// The classification of a point and its synthetic, key-point, withheld and
+/// overlap flags. These are stored in the same layout for all point formats,
+/// with the classification in the low byte, allowing the 256 classes of the
+/// LAS 1.4 extended formats (6-10), and the flags in bits 8-11.
 #[derive(Default, Debug, Clone, Copy)]
 pub struct ClassificationBitField {
-    pub value: u8,
+    pub value: u16,
 }
 
 impl ClassificationBitField {
-    /// Returns the classification name associated with the ClassificationBitField
+    /// Returns the classification value.
     pub fn classification(&self) -> u8 {
-        //self.value & 15_u8
-        self.value & 0b0001_1111u8
+        (self.value & 0b1111_1111u16) as u8
     }
 
     pub fn set_classification(&mut self, value: u8) {
-        self.value = (self.value & 0b1110_0000_u8) | (value & 0b0001_1111u8);
+        self.value = (self.value & !0b1111_1111u16) | value as u16;
     }
 
     /// Returns a string represenation of the classiciation type.
@@ -85,50 +127,58 @@ impl ClassificationBitField {
 
     /// Returns `true` if the point is synthetic, `false` otherwise
     pub fn synthetic(&self) -> bool {
-        //((self.value >> 5_u8) & 1_u8) == 1_u8
-        (self.value & 0b0010_0000u8) == 0b0010_0000u8
+        self.flag(8)
     }
 
     pub fn set_synthetic(&mut self, val: bool) {
-        if val {
-            self.value = self.value | 0b0010_0000u8; //(1 << 5_u8);
-        } else {
-            self.value = self.value & 0b1101_1111u8;
-        }
+        self.set_flag(8, val);
     }
 
     /// Returns `true` if the point is a keypoint, `false` otherwise
     pub fn keypoint(&self) -> bool {
-        //((self.value >> 6_u8) & 1_u8) == 1_u8
-        (self.value & 0b0100_0000u8) == 0b0100_0000u8
+        self.flag(9)
     }
 
     pub fn set_keypoint(&mut self, val: bool) {
-        if val {
-            self.value = self.value | 0b0100_0000u8;
-        } else {
-            self.value = self.value & 0b1011_1111u8;;
-        }
+        self.set_flag(9, val);
     }
 
     /// Returns `true` if the point is withehld, `false` otherwise
     pub fn withheld(&self) -> bool {
-        //((self.value >> 7_u8) & 1_u8) == 1_u8
-        (self.value & 0b1000_0000u8) == 0b1000_0000u8
+        self.flag(10)
     }
 
     pub fn set_withheld(&mut self, val: bool) {
+        self.set_flag(10, val);
+    }
+
+    /// Returns `true` if the point is within the overlap region of two or more
+    /// swaths, `false` otherwise. The legacy point formats (0-5) have no
+    /// overlap flag and use classification value 12 instead.
+    pub fn overlap(&self) -> bool {
+        self.flag(11)
+    }
+
+    pub fn set_overlap(&mut self, val: bool) {
+        self.set_flag(11, val);
+    }
+
+    fn flag(&self, bit: u16) -> bool {
+        (self.value & (1u16 << bit)) != 0u16
+    }
+
+    fn set_flag(&mut self, bit: u16, val: bool) {
         if val {
-            self.value = self.value | 0b1000_0000u8;
+            self.value = self.value | (1u16 << bit);
         } else {
-            self.value = self.value & 0b0111_1111u8;
+            self.value = self.value & !(1u16 << bit);
         }
     }
 }
 
 impl fmt::Display for ClassificationBitField {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "class={}, synthetic={}, keypoint={}, withheld={}", self.classification_string(), self.synthetic(), self.keypoint(), self.withheld())
+        write!(f, "class={}, synthetic={}, keypoint={}, withheld={}, overlap={}", self.classification_string(), self.synthetic(), self.keypoint(), self.withheld(), self.overlap())
     }
 }
 
@@ -168,7 +218,9 @@ pub struct PointData {
     pub intensity: u16,
     pub bit_field: PointBitField,
     pub class_bit_field: ClassificationBitField,
-    pub scan_angle: i8,
+    /// The scan angle, in the 0.006 degree units of the extended point formats
+    /// (6-10), whatever the format of the file (see `scan_angle_degrees`).
+    pub scan_angle: i16,
     pub user_data: u8,
     pub point_source_id: u16,
 }
@@ -176,7 +228,7 @@ pub struct PointData {
 impl fmt::Display for PointData {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "(x={}, y={}, z={}, i={}\n{}\n{}\nscan_angle={}, user_data={}, source_id={})",
-        self.x, self.y, self.z, self.intensity, self.bit_field, self.class_bit_field, self.scan_angle,
+        self.x, self.y, self.z, self.intensity, self.bit_field, self.class_bit_field, self.scan_angle_degrees(),
         self.user_data, self.point_source_id)
     }
 }
@@ -282,6 +334,30 @@ impl PointData {
     pub fn set_withheld(&mut self, val: bool) {
         self.class_bit_field.set_withheld(val);
     }
+
+    /// Returns `true` if the point is within the overlap region of two or more swaths, `false` otherwise
+    pub fn overlap(&self) -> bool {
+        self.class_bit_field.overlap()
+    }
+
+    pub fn set_overlap(&mut self, val: bool) {
+        self.class_bit_field.set_overlap(val);
+    }
+
+    /// Returns the scanner channel of the point.
+    pub fn scanner_channel(&self) -> u8 {
+        self.bit_field.scanner_channel()
+    }
+
+    /// Returns the scan angle of the point in degrees.
+    pub fn scan_angle_degrees(&self) -> f64 {
+        self.scan_angle as f64 * 0.006f64
+    }
+
+    /// Sets the scan angle of the point in degrees.
+    pub fn set_scan_angle_degrees(&mut self, value: f64) {
+        self.scan_angle = (value / 0.006f64).round().max(-32768f64).min(32767f64) as i16;
+    }
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -289,6 +365,8 @@ pub struct RgbData {
     pub red: u16,
     pub green: u16,
     pub blue: u16,
+    /// The near infrared channel, which is only stored by point formats 8 and 10.
+    pub nir: u16,
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -1,12 +1,15 @@
 use std::fmt;
 use std::mem;
+use lidar::extra_bytes::{is_extra_bytes_vlr, read_extra_bytes_descriptors};
 
 #[derive(Default, Clone, Debug)]
 pub struct Vlr {
     pub reserved: u16,
     pub user_id: String,
     pub record_id: u16,
-    pub record_length_after_header: u16,
+    /// The length of the record's data, which is a 16-bit value for VLRs and
+    /// a 64-bit value for the extended VLRs (EVLRs) of LAS 1.3 and 1.4.
+    pub record_length_after_header: u64,
     pub description: String,
     pub binary_data: Vec<u8>,
 }
@@ -46,6 +49,13 @@ impl fmt::Display for Vlr {
                     s = s + &format!("{}]", k);
                 }
             }
+        } else if is_extra_bytes_vlr(self) {
+            // list the extra bytes attributes
+            let descriptors = match read_extra_bytes_descriptors(self) {
+                Ok(d) => d.iter().map(|eb| format!("{}", eb)).collect::<Vec<String>>().join(", "),
+                Err(_) => "uninterpreted data".to_string(),
+            };
+            s = s + &descriptors + "]";
         } else {
             // convert the data to a string
             s = s + String::from_utf8_lossy(&self.binary_data[0..self.record_length_after_header as usize]).trim() + "]";
@@ -10,12 +10,14 @@ extern crate byteorder;
 extern crate serde;
 extern crate serde_json;
 
+pub mod algorithms;
 pub mod io_utils;
 pub mod lidar;
 pub mod raster;
 pub mod rendering;
 pub mod tools;
 pub mod structures;
+pub mod vector;
 
 use std::io::Error;
 use std::env;
@@ -431,7 +431,7 @@ impl Raster {
     }
 
     pub fn get_x_from_column(&self, column: isize) -> f64 {
-        self.configs.west - self.configs.resolution_x / 2f64 +
+        self.configs.west + self.configs.resolution_x / 2f64 +
         column as f64 * self.configs.resolution_x
     }
 
@@ -448,6 +448,48 @@ impl Raster {
         ((self.configs.north - y) / self.configs.resolution_y).floor() as isize
     }
 
+    /// Returns the value at (x, y) interpolated bilinearly from the four surrounding
+    /// cell centres. NoData neighbours are ignored and the remaining weights are
+    /// renormalized; NoData is returned if the point is off the grid or within a NoData cell.
+    pub fn get_value_bilinear(&self, x: f64, y: f64) -> f64 {
+        let nodata = self.configs.nodata;
+        if x < self.configs.west || x > self.configs.east || y < self.configs.south || y > self.configs.north {
+            return nodata;
+        }
+        let row = self.get_row_from_y(y).min(self.configs.rows as isize - 1);
+        let column = self.get_column_from_x(x).min(self.configs.columns as isize - 1);
+        if self.get_value(row, column) == nodata {
+            return nodata;
+        }
+        let col_f = (x - self.configs.west) / self.configs.resolution_x - 0.5;
+        let row_f = (self.configs.north - y) / self.configs.resolution_y - 0.5;
+        let col0 = col_f.floor() as isize;
+        let row0 = row_f.floor() as isize;
+        let dx = col_f - col0 as f64;
+        let dy = row_f - row0 as f64;
+        let neighbours = [(row0, col0, (1f64 - dx) * (1f64 - dy)),
+                          (row0, col0 + 1, dx * (1f64 - dy)),
+                          (row0 + 1, col0, (1f64 - dx) * dy),
+                          (row0 + 1, col0 + 1, dx * dy)];
+        let mut sum = 0f64;
+        let mut sum_weights = 0f64;
+        for &(r, c, w) in neighbours.iter() {
+            if r < 0 || c < 0 || r >= self.configs.rows as isize || c >= self.configs.columns as isize {
+                continue;
+            }
+            let z = self.data[r as usize * self.configs.columns + c as usize];
+            if z != nodata && w > 0f64 {
+                sum += z * w;
+                sum_weights += w;
+            }
+        }
+        if sum_weights > 0f64 {
+            sum / sum_weights
+        } else {
+            nodata
+        }
+    }
+
     pub fn clip_display_min_max(&mut self, percent: f64) {
         let t = (percent / 100.0 * (self.configs.rows * self.configs.columns) as f64) as usize;
         let mut d = self.data.clone();
@@ -0,0 +1,166 @@
+/////////////////////////////////////////////
+// An axis-aligned bounding box (envelope) //
+/////////////////////////////////////////////
+use std::f64;
+use std::fmt;
+use structures::Point2D;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl Default for BoundingBox {
+    /// The default bounding box is 'empty', i.e. it contains nothing and
+    /// will take on the extent of the first box or point it is expanded to.
+    fn default() -> BoundingBox {
+        BoundingBox {
+            min_x: f64::INFINITY,
+            min_y: f64::INFINITY,
+            max_x: f64::NEG_INFINITY,
+            max_y: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl BoundingBox {
+    pub fn new(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> BoundingBox {
+        BoundingBox {
+            min_x: min_x,
+            min_y: min_y,
+            max_x: max_x,
+            max_y: max_y,
+        }
+    }
+
+    /// Creates the bounding box of a set of points.
+    pub fn from_points(points: &[Point2D]) -> BoundingBox {
+        let mut bb = BoundingBox::default();
+        for p in points {
+            bb.expand_to_point(p.x, p.y);
+        }
+        bb
+    }
+
+    /// Returns true if the box contains nothing.
+    pub fn is_empty(&self) -> bool {
+        self.min_x > self.max_x || self.min_y > self.max_y
+    }
+
+    pub fn width(&self) -> f64 {
+        if self.is_empty() {
+            return 0f64;
+        }
+        self.max_x - self.min_x
+    }
+
+    pub fn height(&self) -> f64 {
+        if self.is_empty() {
+            return 0f64;
+        }
+        self.max_y - self.min_y
+    }
+
+    pub fn area(&self) -> f64 {
+        self.width() * self.height()
+    }
+
+    /// Half of the perimeter; commonly used as a cost measure in spatial indexes.
+    pub fn margin(&self) -> f64 {
+        self.width() + self.height()
+    }
+
+    pub fn center(&self) -> Point2D {
+        Point2D::new((self.min_x + self.max_x) / 2f64, (self.min_y + self.max_y) / 2f64)
+    }
+
+    pub fn expand_to_point(&mut self, x: f64, y: f64) {
+        if x < self.min_x {
+            self.min_x = x;
+        }
+        if x > self.max_x {
+            self.max_x = x;
+        }
+        if y < self.min_y {
+            self.min_y = y;
+        }
+        if y > self.max_y {
+            self.max_y = y;
+        }
+    }
+
+    pub fn expand_to(&mut self, other: &BoundingBox) {
+        if other.is_empty() {
+            return;
+        }
+        self.expand_to_point(other.min_x, other.min_y);
+        self.expand_to_point(other.max_x, other.max_y);
+    }
+
+    /// Returns a copy of the box grown by `distance` on every side.
+    pub fn buffer(&self, distance: f64) -> BoundingBox {
+        BoundingBox::new(self.min_x - distance,
+                         self.min_y - distance,
+                         self.max_x + distance,
+                         self.max_y + distance)
+    }
+
+    pub fn contains_point(&self, x: f64, y: f64) -> bool {
+        x >= self.min_x && x <= self.max_x && y >= self.min_y && y <= self.max_y
+    }
+
+    /// Returns true if `other` lies entirely within this box.
+    pub fn contains(&self, other: &BoundingBox) -> bool {
+        other.min_x >= self.min_x && other.max_x <= self.max_x && other.min_y >= self.min_y &&
+        other.max_y <= self.max_y
+    }
+
+    pub fn overlaps(&self, other: &BoundingBox) -> bool {
+        !(other.min_x > self.max_x || other.max_x < self.min_x || other.min_y > self.max_y ||
+          other.max_y < self.min_y)
+    }
+
+    /// Returns the overlapping region of two boxes, or None if they are disjoint.
+    pub fn intersection(&self, other: &BoundingBox) -> Option<BoundingBox> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        Some(BoundingBox::new(self.min_x.max(other.min_x),
+                              self.min_y.max(other.min_y),
+                              self.max_x.min(other.max_x),
+                              self.max_y.min(other.max_y)))
+    }
+
+    /// The minimum distance from a point to the box; zero if the point is inside.
+    pub fn distance_to_point(&self, x: f64, y: f64) -> f64 {
+        let dx = if x < self.min_x {
+            self.min_x - x
+        } else if x > self.max_x {
+            x - self.max_x
+        } else {
+            0f64
+        };
+        let dy = if y < self.min_y {
+            self.min_y - y
+        } else if y > self.max_y {
+            y - self.max_y
+        } else {
+            0f64
+        };
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+impl fmt::Display for BoundingBox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "(min_x={}, min_y={}, max_x={}, max_y={})",
+               self.min_x,
+               self.min_y,
+               self.max_x,
+               self.max_y)
+    }
+}
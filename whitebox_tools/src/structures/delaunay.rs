@@ -0,0 +1,544 @@
+////////////////////////////////////////
+// A 2D Delaunay triangulation of points
+////////////////////////////////////////
+use std::cmp::Ordering;
+use std::f64;
+use std::usize;
+use structures::Point2D;
+
+/// Marks a half-edge that has no opposite, i.e. one that lies on the convex hull.
+pub const EMPTY: usize = usize::MAX;
+
+/// A Delaunay triangulation of a set of points, built using a sweep-hull
+/// algorithm: points are added in order of increasing distance from a seed
+/// triangle, each being connected to the visible edges of the convex hull
+/// of the points added before it, and edges are flipped until every triangle
+/// satisfies the empty circumcircle condition.
+///
+/// Triangles are stored as triples of point indices in counter-clockwise
+/// order, such that triangle `t` has vertices `triangles[3 * t]`,
+/// `triangles[3 * t + 1]`, and `triangles[3 * t + 2]`. Each index into
+/// `triangles` also identifies a half-edge, running from that vertex to the
+/// next vertex of the triangle, and `halfedges` gives the opposite half-edge
+/// in the adjacent triangle (or `EMPTY` on the convex hull). Points that
+/// duplicate an earlier point are left out of the triangulation.
+pub struct Triangulation {
+    pub triangles: Vec<usize>,
+    pub halfedges: Vec<usize>,
+    /// The points on the convex hull, in counter-clockwise order.
+    pub hull: Vec<usize>,
+}
+
+impl Triangulation {
+    /// Triangulates a set of points. Returns None if there are fewer than
+    /// three points or all of the points are collinear.
+    pub fn new(points: &[Point2D]) -> Option<Triangulation> {
+        let n = points.len();
+        if n < 3 {
+            return None;
+        }
+        // work relative to the centre of the points, which preserves precision
+        // for projected coordinates with large offsets
+        let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+        let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for p in points {
+            min_x = min_x.min(p.x);
+            min_y = min_y.min(p.y);
+            max_x = max_x.max(p.x);
+            max_y = max_y.max(p.y);
+        }
+        let (cx, cy) = ((min_x + max_x) / 2f64, (min_y + max_y) / 2f64);
+        let coords: Vec<Point2D> = points.iter().map(|p| Point2D::new(p.x - cx, p.y - cy)).collect();
+        let origin = Point2D::new(0f64, 0f64);
+
+        // the seed triangle is formed by the point nearest the centre, the point
+        // nearest to it, and the point that gives the smallest circumcircle
+        let mut i0 = 0;
+        let mut min_dist = f64::INFINITY;
+        for i in 0..n {
+            let d = coords[i].distance_squared(&origin);
+            if d < min_dist {
+                i0 = i;
+                min_dist = d;
+            }
+        }
+        let mut i1 = EMPTY;
+        min_dist = f64::INFINITY;
+        for i in 0..n {
+            let d = coords[i].distance_squared(&coords[i0]);
+            if d > 0f64 && d < min_dist {
+                i1 = i;
+                min_dist = d;
+            }
+        }
+        if i1 == EMPTY {
+            return None;
+        }
+        let mut i2 = EMPTY;
+        let mut min_radius = f64::INFINITY;
+        for i in 0..n {
+            if i == i0 || i == i1 {
+                continue;
+            }
+            let r = circumradius_squared(&coords[i0], &coords[i1], &coords[i]);
+            if r < min_radius {
+                i2 = i;
+                min_radius = r;
+            }
+        }
+        if i2 == EMPTY {
+            return None;
+        }
+        if orient(&coords[i0], &coords[i1], &coords[i2]) < 0f64 {
+            let tmp = i1;
+            i1 = i2;
+            i2 = tmp;
+        }
+        let centre = circumcentre(&coords[i0], &coords[i1], &coords[i2]);
+
+        // add the remaining points in order of distance from the seed circumcircle
+        let dists: Vec<f64> = coords.iter().map(|p| p.distance_squared(&centre)).collect();
+        let mut ids: Vec<usize> = (0..n).collect();
+        ids.sort_by(|a, b| dists[*a].partial_cmp(&dists[*b]).unwrap_or(Ordering::Equal));
+
+        let max_triangles = 2 * n - 5;
+        let mut t = Triangulation {
+            triangles: Vec::with_capacity(max_triangles * 3),
+            halfedges: Vec::with_capacity(max_triangles * 3),
+            hull: vec![],
+        };
+        let mut hull = Hull::new(n, &centre);
+        hull.start = i0;
+        hull.next[i0] = i1;
+        hull.prev[i2] = i1;
+        hull.next[i1] = i2;
+        hull.prev[i0] = i2;
+        hull.next[i2] = i0;
+        hull.prev[i1] = i0;
+        hull.tri[i0] = 0;
+        hull.tri[i1] = 1;
+        hull.tri[i2] = 2;
+        hull.hash_edge(&coords[i0], i0);
+        hull.hash_edge(&coords[i1], i1);
+        hull.hash_edge(&coords[i2], i2);
+        t.add_triangle(i0, i1, i2, EMPTY, EMPTY, EMPTY);
+
+        let mut previous: Option<Point2D> = None;
+        for &i in &ids {
+            let p = coords[i];
+            // skip duplicates of the previous point, and the seed points
+            if let Some(q) = previous {
+                if (p.x - q.x).abs() <= f64::EPSILON && (p.y - q.y).abs() <= f64::EPSILON {
+                    continue;
+                }
+            }
+            previous = Some(p);
+            if i == i0 || i == i1 || i == i2 {
+                continue;
+            }
+
+            // find an edge of the hull that is visible from the point, i.e. that
+            // the point lies to the right of, starting from a nearby hull point
+            let mut start = 0;
+            let key = hull.hash_key(&p);
+            for j in 0..hull.hash.len() {
+                start = hull.hash[(key + j) % hull.hash.len()];
+                if start != EMPTY && start != hull.next[start] {
+                    break;
+                }
+            }
+            start = hull.prev[start];
+            let mut e = start;
+            loop {
+                let q = hull.next[e];
+                if orient(&p, &coords[e], &coords[q]) < 0f64 {
+                    break;
+                }
+                e = q;
+                if e == start {
+                    e = EMPTY;
+                    break;
+                }
+            }
+            if e == EMPTY {
+                continue; // a near-duplicate point
+            }
+
+            // connect the point to the visible edge, then walk forwards and
+            // backwards along the hull, connecting any other visible edges
+            let tri = t.add_triangle(e, i, hull.next[e], EMPTY, EMPTY, hull.tri[e]);
+            hull.tri[i] = t.legalize(tri + 2, &coords, &mut hull);
+            hull.tri[e] = tri;
+
+            let mut n = hull.next[e];
+            loop {
+                let q = hull.next[n];
+                if orient(&p, &coords[n], &coords[q]) >= 0f64 {
+                    break;
+                }
+                let tri = t.add_triangle(n, i, q, hull.tri[i], EMPTY, hull.tri[n]);
+                hull.tri[i] = t.legalize(tri + 2, &coords, &mut hull);
+                hull.next[n] = n; // removed from the hull
+                n = q;
+            }
+            if e == start {
+                loop {
+                    let q = hull.prev[e];
+                    if orient(&p, &coords[q], &coords[e]) >= 0f64 {
+                        break;
+                    }
+                    let tri = t.add_triangle(q, i, e, EMPTY, hull.tri[e], hull.tri[q]);
+                    t.legalize(tri + 2, &coords, &mut hull);
+                    hull.tri[q] = tri;
+                    hull.next[e] = e; // removed from the hull
+                    e = q;
+                }
+            }
+
+            hull.start = e;
+            hull.prev[i] = e;
+            hull.next[e] = i;
+            hull.prev[n] = i;
+            hull.next[i] = n;
+            hull.hash_edge(&p, i);
+            hull.hash_edge(&coords[e], e);
+        }
+
+        let mut e = hull.start;
+        loop {
+            t.hull.push(e);
+            e = hull.next[e];
+            if e == hull.start {
+                break;
+            }
+        }
+        t.triangles.shrink_to_fit();
+        t.halfedges.shrink_to_fit();
+        Some(t)
+    }
+
+    pub fn num_triangles(&self) -> usize {
+        self.triangles.len() / 3
+    }
+
+    /// Returns the half-edge following `edge` within its triangle.
+    pub fn next_halfedge(edge: usize) -> usize {
+        if edge % 3 == 2 {
+            edge - 2
+        } else {
+            edge + 1
+        }
+    }
+
+    /// Returns the half-edge preceding `edge` within its triangle.
+    pub fn prev_halfedge(edge: usize) -> usize {
+        if edge % 3 == 0 {
+            edge + 2
+        } else {
+            edge - 1
+        }
+    }
+
+    fn add_triangle(&mut self, i0: usize, i1: usize, i2: usize, a: usize, b: usize, c: usize) -> usize {
+        let t = self.triangles.len();
+        self.triangles.push(i0);
+        self.triangles.push(i1);
+        self.triangles.push(i2);
+        self.halfedges.push(EMPTY);
+        self.halfedges.push(EMPTY);
+        self.halfedges.push(EMPTY);
+        self.link(t, a);
+        self.link(t + 1, b);
+        self.link(t + 2, c);
+        t
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        self.halfedges[a] = b;
+        if b != EMPTY {
+            self.halfedges[b] = a;
+        }
+    }
+
+    // Flips the edge `a` and, recursively, the edges of the resulting triangles,
+    // until they satisfy the Delaunay condition. The two triangles sharing an
+    // edge are flipped when the far vertex of one (p1) lies within the
+    // circumcircle of the other (p0, pr, pl):
+    //
+    //           pl                    pl
+    //          /||\                  /  \
+    //       al/ || \bl            al/    \a
+    //        /  ||  \              /      \
+    //       /  a||b  \    flip    /___ar___\
+    //     p0\   ||   /p1   =>   p0\---bl---/p1
+    //        \  ||  /              \      /
+    //       ar\ || /br             b\    /br
+    //          \||/                  \  /
+    //           pr                    pr
+    //
+    // Returns the half-edge that ends up in the position of `ar`.
+    fn legalize(&mut self, mut a: usize, coords: &[Point2D], hull: &mut Hull) -> usize {
+        let mut stack: Vec<usize> = vec![];
+        let mut ar;
+        loop {
+            let b = self.halfedges[a];
+            let a0 = a - a % 3;
+            ar = a0 + (a + 2) % 3;
+            if b == EMPTY {
+                match stack.pop() {
+                    Some(edge) => {
+                        a = edge;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+            let b0 = b - b % 3;
+            let al = a0 + (a + 1) % 3;
+            let bl = b0 + (b + 2) % 3;
+            let p0 = self.triangles[ar];
+            let pr = self.triangles[a];
+            let pl = self.triangles[al];
+            let p1 = self.triangles[bl];
+            if in_circle(&coords[p0], &coords[pr], &coords[pl], &coords[p1]) {
+                self.triangles[a] = p1;
+                self.triangles[b] = p0;
+                let hbl = self.halfedges[bl];
+                if hbl == EMPTY {
+                    // the flipped edge was on the hull; update the hull's reference
+                    let mut e = hull.start;
+                    loop {
+                        if hull.tri[e] == bl {
+                            hull.tri[e] = a;
+                            break;
+                        }
+                        e = hull.prev[e];
+                        if e == hull.start {
+                            break;
+                        }
+                    }
+                }
+                self.link(a, hbl);
+                let har = self.halfedges[ar];
+                self.link(b, har);
+                self.link(ar, bl);
+                stack.push(b0 + (b + 1) % 3);
+            } else {
+                match stack.pop() {
+                    Some(edge) => a = edge,
+                    None => break,
+                }
+            }
+        }
+        ar
+    }
+}
+
+// The advancing convex hull of the points added so far, as a doubly linked
+// list with, for each hull point, the half-edge of the hull edge that starts
+// there. A hash of hull points by angle around the seed speeds up the search
+// for visible edges.
+struct Hull {
+    start: usize,
+    next: Vec<usize>,
+    prev: Vec<usize>,
+    tri: Vec<usize>,
+    hash: Vec<usize>,
+    centre: Point2D,
+}
+
+impl Hull {
+    fn new(n: usize, centre: &Point2D) -> Hull {
+        let hash_size = (n as f64).sqrt().ceil() as usize;
+        Hull {
+            start: 0,
+            next: vec![EMPTY; n],
+            prev: vec![EMPTY; n],
+            tri: vec![EMPTY; n],
+            hash: vec![EMPTY; hash_size],
+            centre: *centre,
+        }
+    }
+
+    fn hash_key(&self, p: &Point2D) -> usize {
+        let (dx, dy) = (p.x - self.centre.x, p.y - self.centre.y);
+        // a monotonic function of the angle, in the range [0, 1]
+        let q = dx / (dx.abs() + dy.abs());
+        let angle = (if dy > 0f64 { 3f64 - q } else { 1f64 + q }) / 4f64;
+        let key = (angle * self.hash.len() as f64).floor();
+        if key.is_nan() {
+            return 0;
+        }
+        (key as usize) % self.hash.len()
+    }
+
+    fn hash_edge(&mut self, p: &Point2D, i: usize) {
+        let key = self.hash_key(p);
+        self.hash[key] = i;
+    }
+}
+
+// Positive if r lies to the left of the line from p to q, i.e. if p, q, r
+// are in counter-clockwise order.
+fn orient(p: &Point2D, q: &Point2D, r: &Point2D) -> f64 {
+    (q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x)
+}
+
+// True if p lies within the circumcircle of the counter-clockwise triangle a, b, c.
+fn in_circle(a: &Point2D, b: &Point2D, c: &Point2D, p: &Point2D) -> bool {
+    let (dx, dy) = (a.x - p.x, a.y - p.y);
+    let (ex, ey) = (b.x - p.x, b.y - p.y);
+    let (fx, fy) = (c.x - p.x, c.y - p.y);
+    let ap = dx * dx + dy * dy;
+    let bp = ex * ex + ey * ey;
+    let cp = fx * fx + fy * fy;
+    dx * (ey * cp - bp * fy) - dy * (ex * cp - bp * fx) + ap * (ex * fy - ey * fx) > 0f64
+}
+
+fn circumradius_squared(a: &Point2D, b: &Point2D, c: &Point2D) -> f64 {
+    let centre = circumcentre(a, b, c);
+    let r = a.distance_squared(&centre);
+    if r.is_nan() {
+        return f64::INFINITY;
+    }
+    r
+}
+
+fn circumcentre(a: &Point2D, b: &Point2D, c: &Point2D) -> Point2D {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let (ex, ey) = (c.x - a.x, c.y - a.y);
+    let bl = dx * dx + dy * dy;
+    let cl = ex * ex + ey * ey;
+    let d = 0.5f64 / (dx * ey - dy * ex);
+    Point2D::new(a.x + (ey * bl - dy * cl) * d, a.y + (dx * cl - ex * bl) * d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{orient, Triangulation, EMPTY};
+    use structures::Point2D;
+
+    // a simple linear congruential generator, so that the tests are repeatable
+    fn random_points(n: usize, seed: u64) -> Vec<Point2D> {
+        let mut state = seed;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..n).map(|_| Point2D::new(500000f64 + next() * 1000f64, 4800000f64 + next() * 1000f64)).collect()
+    }
+
+    // checks the structure of a triangulation and that every triangle is counter-clockwise
+    fn check_topology(t: &Triangulation, points: &[Point2D]) {
+        assert_eq!(t.triangles.len(), t.halfedges.len());
+        let mut num_hull_edges = 0;
+        for e in 0..t.halfedges.len() {
+            if t.halfedges[e] == EMPTY {
+                num_hull_edges += 1;
+            } else {
+                let opposite = t.halfedges[e];
+                assert_eq!(t.halfedges[opposite], e);
+                // opposite half-edges join the same two points in reverse
+                assert_eq!(t.triangles[e], t.triangles[Triangulation::next_halfedge(opposite)]);
+                assert_eq!(t.triangles[opposite], t.triangles[Triangulation::next_halfedge(e)]);
+            }
+        }
+        assert_eq!(num_hull_edges, t.hull.len());
+        for k in 0..t.num_triangles() {
+            let (a, b, c) = (points[t.triangles[3 * k]], points[t.triangles[3 * k + 1]], points[t.triangles[3 * k + 2]]);
+            assert!(orient(&a, &b, &c) > 0f64);
+        }
+        // the hull is convex and counter-clockwise, and contains every point
+        let h = t.hull.len();
+        for i in 0..h {
+            let (a, b) = (points[t.hull[i]], points[t.hull[(i + 1) % h]]);
+            for p in points {
+                assert!(orient(&a, &b, p) >= -1e-6);
+            }
+        }
+    }
+
+    // the empty circumcircle property, tested against every point
+    fn check_delaunay(t: &Triangulation, points: &[Point2D]) {
+        for k in 0..t.num_triangles() {
+            let (a, b, c) = (points[t.triangles[3 * k]], points[t.triangles[3 * k + 1]], points[t.triangles[3 * k + 2]]);
+            let centre = circumcentre_of(&a, &b, &c);
+            let radius = centre.distance(&a);
+            for p in points {
+                assert!(centre.distance(p) >= radius * (1f64 - 1e-9));
+            }
+        }
+    }
+
+    fn circumcentre_of(a: &Point2D, b: &Point2D, c: &Point2D) -> Point2D {
+        let (bx, by, cx, cy) = (b.x - a.x, b.y - a.y, c.x - a.x, c.y - a.y);
+        let d = 2f64 * (bx * cy - by * cx);
+        let (b2, c2) = (bx * bx + by * by, cx * cx + cy * cy);
+        Point2D::new(a.x + (cy * b2 - by * c2) / d, a.y + (bx * c2 - cx * b2) / d)
+    }
+
+    #[test]
+    fn test_random_points() {
+        let points = random_points(500, 1);
+        let t = Triangulation::new(&points).unwrap();
+        check_topology(&t, &points);
+        check_delaunay(&t, &points);
+        // Euler's formula for a triangulated point set
+        assert_eq!(t.num_triangles(), 2 * points.len() - 2 - t.hull.len());
+    }
+
+    #[test]
+    fn test_grid_points() {
+        // every square of the grid has four co-circular corners
+        let mut points = vec![];
+        for row in 0..10 {
+            for col in 0..10 {
+                points.push(Point2D::new(col as f64, row as f64));
+            }
+        }
+        let t = Triangulation::new(&points).unwrap();
+        check_topology(&t, &points);
+        check_delaunay(&t, &points);
+        assert_eq!(t.num_triangles(), 162);
+        assert_eq!(t.hull.len(), 36);
+    }
+
+    #[test]
+    fn test_too_few_or_collinear_points() {
+        assert!(Triangulation::new(&[]).is_none());
+        assert!(Triangulation::new(&[Point2D::new(0f64, 0f64), Point2D::new(1f64, 1f64)]).is_none());
+        let collinear: Vec<Point2D> = (0..10).map(|i| Point2D::new(i as f64, 2f64 * i as f64)).collect();
+        assert!(Triangulation::new(&collinear).is_none());
+        assert!(Triangulation::new(&vec![Point2D::new(5f64, 5f64); 4]).is_none());
+    }
+
+    #[test]
+    fn test_collinear_points_with_apex() {
+        let mut points: Vec<Point2D> = (0..10).map(|i| Point2D::new(i as f64, 0f64)).collect();
+        points.push(Point2D::new(4.5, 3f64));
+        let t = Triangulation::new(&points).unwrap();
+        check_topology(&t, &points);
+        check_delaunay(&t, &points);
+        assert_eq!(t.num_triangles(), 9);
+        assert_eq!(t.hull.len(), 11);
+    }
+
+    #[test]
+    fn test_duplicate_points() {
+        let unique = random_points(200, 2);
+        let mut points = unique.clone();
+        for i in 0..50 {
+            points.push(unique[i * 3]);
+        }
+        let t = Triangulation::new(&points).unwrap();
+        check_topology(&t, &points);
+        check_delaunay(&t, &points);
+        // the duplicates are left out
+        for &i in &t.triangles {
+            assert!(i < unique.len());
+        }
+        let expected = Triangulation::new(&unique).unwrap();
+        assert_eq!(t.num_triangles(), expected.num_triangles());
+        assert_eq!(t.hull.len(), expected.hull.len());
+    }
+}
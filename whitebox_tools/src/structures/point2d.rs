@@ -0,0 +1,60 @@
+/////////////////////////////////////////////
+// A simple 2-dimensional point structure  //
+/////////////////////////////////////////////
+use std::fmt;
+use std::ops::{Add, Sub};
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub struct Point2D {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point2D {
+    /// Creates a new Point2D.
+    pub fn new(x: f64, y: f64) -> Point2D {
+        Point2D { x: x, y: y }
+    }
+
+    /// Calculates the Euclidean distance to another point.
+    pub fn distance(&self, other: &Point2D) -> f64 {
+        self.distance_squared(other).sqrt()
+    }
+
+    /// Calculates the squared Euclidean distance to another point.
+    pub fn distance_squared(&self, other: &Point2D) -> f64 {
+        (self.x - other.x) * (self.x - other.x) + (self.y - other.y) * (self.y - other.y)
+    }
+
+    /// Returns the point midway between this point and another.
+    pub fn midpoint(&self, other: &Point2D) -> Point2D {
+        Point2D::new((self.x + other.x) / 2f64, (self.y + other.y) / 2f64)
+    }
+
+    /// Returns true if the two points are within a threshold distance of each other.
+    pub fn nearly_equals(&self, other: &Point2D, threshold: f64) -> bool {
+        (self.x - other.x).abs() <= threshold && (self.y - other.y).abs() <= threshold
+    }
+}
+
+impl Add for Point2D {
+    type Output = Point2D;
+
+    fn add(self, other: Point2D) -> Point2D {
+        Point2D::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point2D {
+    type Output = Point2D;
+
+    fn sub(self, other: Point2D) -> Point2D {
+        Point2D::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl fmt::Display for Point2D {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
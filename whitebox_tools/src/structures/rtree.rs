@@ -0,0 +1,519 @@
+/////////////////////////////////////////////////////////
+// An R-tree spatial index of axis-aligned bounding boxes
+/////////////////////////////////////////////////////////
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::f64;
+use std::mem;
+use structures::BoundingBox;
+use structures::kd_tree::HeapElement;
+
+const MAX_ENTRIES: usize = 16;
+const MIN_ENTRIES: usize = 6;
+
+/// An R-tree, i.e. a balanced tree of nested bounding boxes, used to index
+/// items (typically feature or record numbers) by their envelopes. A tree can
+/// be bulk loaded using the Sort-Tile-Recursive (STR) algorithm, which gives
+/// nearly full, minimally overlapping nodes, and then modified by insertion
+/// and removal. Queries return the items whose envelopes intersect a box or
+/// contain a point, or the items nearest to a point; since only envelopes are
+/// stored, it is up to the caller to test candidates against the actual
+/// geometries.
+pub struct RTree<T> {
+    root: RTreeNode<T>,
+    size: usize,
+}
+
+struct RTreeNode<T> {
+    bb: BoundingBox,
+    // leaves have a level of zero and hold entries; other nodes hold children
+    level: usize,
+    entries: Vec<(BoundingBox, T)>,
+    children: Vec<RTreeNode<T>>,
+}
+
+enum NearestElement<'a, T: 'a> {
+    Node(&'a RTreeNode<T>),
+    Item(&'a T),
+}
+
+impl<T> RTree<T> {
+    /// Creates an empty tree.
+    pub fn new() -> RTree<T> {
+        RTree {
+            root: RTreeNode::new_leaf(vec![]),
+            size: 0,
+        }
+    }
+
+    /// Creates a tree containing a set of items using the Sort-Tile-Recursive
+    /// algorithm.
+    pub fn bulk_load(items: Vec<(BoundingBox, T)>) -> RTree<T> {
+        let size = items.len();
+        if size == 0 {
+            return RTree::new();
+        }
+        let mut nodes: Vec<RTreeNode<T>> = str_pack(items, |item| item.0)
+            .into_iter()
+            .map(|entries| RTreeNode::new_leaf(entries))
+            .collect();
+        let mut level = 0;
+        while nodes.len() > 1 {
+            level += 1;
+            nodes = str_pack(nodes, |node| node.bb)
+                .into_iter()
+                .map(|children| RTreeNode::new_internal(level, children))
+                .collect();
+        }
+        RTree {
+            root: nodes.pop().unwrap(),
+            size: size,
+        }
+    }
+
+    /// Returns the number of items in the tree.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns the bounding box of all of the items in the tree.
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.root.bb
+    }
+
+    /// Adds an item with the given envelope to the tree.
+    pub fn insert(&mut self, bb: BoundingBox, value: T) {
+        if let Some(sibling) = self.root.insert(bb, value) {
+            // the root was split, so the tree grows by a level
+            let level = self.root.level + 1;
+            let old_root = mem::replace(&mut self.root, RTreeNode::new_leaf(vec![]));
+            self.root = RTreeNode::new_internal(level, vec![old_root, sibling]);
+        }
+        self.size += 1;
+    }
+
+    /// Returns the items whose envelopes intersect a box.
+    pub fn search(&self, bb: &BoundingBox) -> Vec<&T> {
+        let mut ret = vec![];
+        if self.size > 0 {
+            self.root.search(bb, &mut ret);
+        }
+        ret
+    }
+
+    /// Returns the items whose envelopes contain a point.
+    pub fn search_point(&self, x: f64, y: f64) -> Vec<&T> {
+        self.search(&BoundingBox::new(x, y, x, y))
+    }
+
+    /// Returns up to `num` items nearest to a point, in order of increasing
+    /// distance from the point to their envelopes.
+    pub fn nearest(&self, x: f64, y: f64, num: usize) -> Vec<(f64, &T)> {
+        self.nearest_by(x, y, num, |bb, _| bb.distance_to_point(x, y))
+    }
+
+    /// Returns up to `num` items nearest to a point, measured by a distance
+    /// function of each item and its envelope, in order of increasing
+    /// distance. The distance to an item must never be less than the distance
+    /// from the point to its envelope, which holds for the exact distance to
+    /// any geometry contained by the envelope.
+    pub fn nearest_by<F>(&self, x: f64, y: f64, num: usize, distance: F) -> Vec<(f64, &T)>
+        where F: Fn(&BoundingBox, &T) -> f64
+    {
+        let mut ret = vec![];
+        if self.size == 0 || num == 0 {
+            return ret;
+        }
+        // a best-first search; distances are negated since BinaryHeap is a max-heap
+        let mut pending = BinaryHeap::new();
+        pending.push(HeapElement {
+            distance: -self.root.bb.distance_to_point(x, y),
+            element: NearestElement::Node(&self.root),
+        });
+        while let Some(candidate) = pending.pop() {
+            match candidate.element {
+                NearestElement::Item(value) => {
+                    ret.push((-candidate.distance, value));
+                    if ret.len() == num {
+                        break;
+                    }
+                }
+                NearestElement::Node(node) => {
+                    for &(ref bb, ref value) in &node.entries {
+                        pending.push(HeapElement {
+                            distance: -distance(bb, value),
+                            element: NearestElement::Item(value),
+                        });
+                    }
+                    for child in &node.children {
+                        pending.push(HeapElement {
+                            distance: -child.bb.distance_to_point(x, y),
+                            element: NearestElement::Node(child),
+                        });
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
+
+impl<T: PartialEq> RTree<T> {
+    /// Removes an item, identified by its envelope and value, from the tree.
+    /// Returns false if the item was not found.
+    pub fn remove(&mut self, bb: &BoundingBox, value: &T) -> bool {
+        let mut orphans = vec![];
+        if !self.root.remove(bb, value, &mut orphans) {
+            return false;
+        }
+        self.size -= 1;
+        // shorten the tree while the root has a single child
+        while self.root.level > 0 && self.root.children.len() == 1 {
+            self.root = self.root.children.pop().unwrap();
+        }
+        if self.root.level > 0 && self.root.children.is_empty() {
+            self.root = RTreeNode::new_leaf(vec![]);
+        }
+        // the entries of under-full nodes that were removed are reinserted
+        self.size -= orphans.len();
+        for (bb, value) in orphans {
+            self.insert(bb, value);
+        }
+        true
+    }
+}
+
+impl<T> RTreeNode<T> {
+    fn new_leaf(entries: Vec<(BoundingBox, T)>) -> RTreeNode<T> {
+        let mut bb = BoundingBox::default();
+        for entry in &entries {
+            bb.expand_to(&entry.0);
+        }
+        RTreeNode {
+            bb: bb,
+            level: 0,
+            entries: entries,
+            children: vec![],
+        }
+    }
+
+    fn new_internal(level: usize, children: Vec<RTreeNode<T>>) -> RTreeNode<T> {
+        let mut bb = BoundingBox::default();
+        for child in &children {
+            bb.expand_to(&child.bb);
+        }
+        RTreeNode {
+            bb: bb,
+            level: level,
+            entries: vec![],
+            children: children,
+        }
+    }
+
+    fn update_bounding_box(&mut self) {
+        let mut bb = BoundingBox::default();
+        for entry in &self.entries {
+            bb.expand_to(&entry.0);
+        }
+        for child in &self.children {
+            bb.expand_to(&child.bb);
+        }
+        self.bb = bb;
+    }
+
+    fn len(&self) -> usize {
+        if self.level == 0 {
+            self.entries.len()
+        } else {
+            self.children.len()
+        }
+    }
+
+    // Inserts an entry below this node, returning the new sibling node if this
+    // node had to be split.
+    fn insert(&mut self, bb: BoundingBox, value: T) -> Option<RTreeNode<T>> {
+        self.bb.expand_to(&bb);
+        if self.level == 0 {
+            self.entries.push((bb, value));
+            if self.entries.len() > MAX_ENTRIES {
+                let entries = mem::replace(&mut self.entries, vec![]);
+                let (a, b) = split(entries, |entry| entry.0);
+                *self = RTreeNode::new_leaf(a);
+                return Some(RTreeNode::new_leaf(b));
+            }
+            return None;
+        }
+        // descend into the child needing the least enlargement, then the smallest
+        let mut best = 0;
+        let mut best_enlargement = f64::INFINITY;
+        let mut best_area = f64::INFINITY;
+        for (i, child) in self.children.iter().enumerate() {
+            let mut enlarged = child.bb;
+            enlarged.expand_to(&bb);
+            let area = child.bb.area();
+            let enlargement = enlarged.area() - area;
+            if enlargement < best_enlargement || (enlargement == best_enlargement && area < best_area) {
+                best = i;
+                best_enlargement = enlargement;
+                best_area = area;
+            }
+        }
+        if let Some(sibling) = self.children[best].insert(bb, value) {
+            self.children.push(sibling);
+            if self.children.len() > MAX_ENTRIES {
+                let level = self.level;
+                let children = mem::replace(&mut self.children, vec![]);
+                let (a, b) = split(children, |child| child.bb);
+                *self = RTreeNode::new_internal(level, a);
+                return Some(RTreeNode::new_internal(level, b));
+            }
+        }
+        None
+    }
+
+    fn search<'a>(&'a self, bb: &BoundingBox, ret: &mut Vec<&'a T>) {
+        for entry in &self.entries {
+            if entry.0.overlaps(bb) {
+                ret.push(&entry.1);
+            }
+        }
+        for child in &self.children {
+            if child.bb.overlaps(bb) {
+                child.search(bb, ret);
+            }
+        }
+    }
+
+    // Moves all of the entries below this node into `entries`.
+    fn collect_entries(self, entries: &mut Vec<(BoundingBox, T)>) {
+        entries.extend(self.entries);
+        for child in self.children {
+            child.collect_entries(entries);
+        }
+    }
+}
+
+impl<T: PartialEq> RTreeNode<T> {
+    // Removes an entry below this node. Children left with too few entries are
+    // removed and their entries added to `orphans` for reinsertion.
+    fn remove(&mut self, bb: &BoundingBox, value: &T, orphans: &mut Vec<(BoundingBox, T)>) -> bool {
+        if !self.bb.contains(bb) {
+            return false;
+        }
+        if self.level == 0 {
+            match self.entries.iter().position(|entry| entry.0 == *bb && entry.1 == *value) {
+                Some(i) => {
+                    self.entries.remove(i);
+                    self.update_bounding_box();
+                    return true;
+                }
+                None => return false,
+            }
+        }
+        for i in 0..self.children.len() {
+            if self.children[i].remove(bb, value, orphans) {
+                if self.children[i].len() < MIN_ENTRIES {
+                    self.children.remove(i).collect_entries(orphans);
+                }
+                self.update_bounding_box();
+                return true;
+            }
+        }
+        false
+    }
+}
+
+// Partitions items into groups of at most MAX_ENTRIES using the
+// Sort-Tile-Recursive method, i.e. the items are sorted by x and divided into
+// vertical slices, each of which is sorted by y and divided into groups.
+fn str_pack<X, F>(mut items: Vec<X>, bb_of: F) -> Vec<Vec<X>>
+    where F: Fn(&X) -> BoundingBox
+{
+    let num_groups = (items.len() + MAX_ENTRIES - 1) / MAX_ENTRIES;
+    let num_slices = (num_groups as f64).sqrt().ceil() as usize;
+    let slice_size = num_slices * MAX_ENTRIES;
+    sort_by_center(&mut items, &bb_of, true);
+    let mut groups = vec![];
+    let mut iter = items.into_iter().peekable();
+    while iter.peek().is_some() {
+        let mut slice: Vec<X> = iter.by_ref().take(slice_size).collect();
+        sort_by_center(&mut slice, &bb_of, false);
+        let mut slice_iter = slice.into_iter().peekable();
+        while slice_iter.peek().is_some() {
+            groups.push(slice_iter.by_ref().take(MAX_ENTRIES).collect());
+        }
+    }
+    groups
+}
+
+// Splits the items of an overflowing node in two, choosing the axis along
+// which the sum of the group margins is least and then the division along that
+// axis with the least overlap between the groups (as in the R*-tree).
+fn split<X, F>(mut items: Vec<X>, bb_of: F) -> (Vec<X>, Vec<X>)
+    where F: Fn(&X) -> BoundingBox
+{
+    let group_boxes = |items: &[X], k: usize| -> (BoundingBox, BoundingBox) {
+        let mut a = BoundingBox::default();
+        let mut b = BoundingBox::default();
+        for item in &items[..k] {
+            a.expand_to(&bb_of(item));
+        }
+        for item in &items[k..] {
+            b.expand_to(&bb_of(item));
+        }
+        (a, b)
+    };
+    let total_margin = |items: &[X]| -> f64 {
+        (MIN_ENTRIES..items.len() - MIN_ENTRIES + 1)
+            .map(|k| {
+                let (a, b) = group_boxes(items, k);
+                a.margin() + b.margin()
+            })
+            .sum()
+    };
+    sort_by_center(&mut items, &bb_of, false);
+    let margin_y = total_margin(&items);
+    sort_by_center(&mut items, &bb_of, true);
+    let margin_x = total_margin(&items);
+    if margin_y < margin_x {
+        sort_by_center(&mut items, &bb_of, false);
+    }
+
+    let mut best_k = MIN_ENTRIES;
+    let mut best_overlap = f64::INFINITY;
+    let mut best_area = f64::INFINITY;
+    for k in MIN_ENTRIES..items.len() - MIN_ENTRIES + 1 {
+        let (a, b) = group_boxes(&items, k);
+        let overlap = match a.intersection(&b) {
+            Some(bb) => bb.area(),
+            None => 0f64,
+        };
+        let area = a.area() + b.area();
+        if overlap < best_overlap || (overlap == best_overlap && area < best_area) {
+            best_k = k;
+            best_overlap = overlap;
+            best_area = area;
+        }
+    }
+    let b = items.split_off(best_k);
+    (items, b)
+}
+
+fn sort_by_center<X, F>(items: &mut Vec<X>, bb_of: &F, by_x: bool)
+    where F: Fn(&X) -> BoundingBox
+{
+    items.sort_by(|a, b| {
+        let (ca, cb) = (bb_of(a).center(), bb_of(b).center());
+        let ord = if by_x {
+            ca.x.partial_cmp(&cb.x)
+        } else {
+            ca.y.partial_cmp(&cb.y)
+        };
+        ord.unwrap_or(Ordering::Equal)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RTree;
+    use structures::BoundingBox;
+
+    // a simple linear congruential generator, so that the tests are repeatable
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> f64 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (self.0 >> 11) as f64 / (1u64 << 53) as f64
+        }
+    }
+
+    fn random_boxes(n: usize, seed: u64) -> Vec<BoundingBox> {
+        let mut rng = Lcg(seed);
+        (0..n).map(|_| {
+            let (x, y) = (rng.next() * 1000f64, rng.next() * 1000f64);
+            let (w, h) = (rng.next() * 20f64, rng.next() * 20f64);
+            BoundingBox::new(x, y, x + w, y + h)
+        }).collect()
+    }
+
+    fn sorted(values: Vec<&usize>) -> Vec<usize> {
+        let mut ret: Vec<usize> = values.into_iter().cloned().collect();
+        ret.sort();
+        ret
+    }
+
+    fn brute_force_search(boxes: &[BoundingBox], present: &[bool], bb: &BoundingBox) -> Vec<usize> {
+        (0..boxes.len()).filter(|&i| present[i] && boxes[i].overlaps(bb)).collect()
+    }
+
+    fn check_queries(tree: &RTree<usize>, boxes: &[BoundingBox], present: &[bool], seed: u64) {
+        let mut rng = Lcg(seed);
+        for _ in 0..50 {
+            let (x, y) = (rng.next() * 1000f64, rng.next() * 1000f64);
+            let query = BoundingBox::new(x, y, x + rng.next() * 100f64, y + rng.next() * 100f64);
+            assert_eq!(sorted(tree.search(&query)), brute_force_search(boxes, present, &query));
+            assert_eq!(sorted(tree.search_point(x, y)), brute_force_search(boxes, present, &BoundingBox::new(x, y, x, y)));
+
+            let nearest = tree.nearest(x, y, 10);
+            let mut distances: Vec<f64> = (0..boxes.len()).filter(|&i| present[i]).map(|i| boxes[i].distance_to_point(x, y)).collect();
+            distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            distances.truncate(10);
+            assert_eq!(nearest.len(), distances.len());
+            for k in 0..nearest.len() {
+                assert_eq!(nearest[k].0, distances[k]);
+                assert_eq!(boxes[*nearest[k].1].distance_to_point(x, y), nearest[k].0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bulk_load() {
+        let boxes = random_boxes(2000, 1);
+        let tree = RTree::bulk_load(boxes.iter().cloned().zip(0..boxes.len()).collect());
+        assert_eq!(tree.size(), boxes.len());
+        check_queries(&tree, &boxes, &vec![true; boxes.len()], 2);
+    }
+
+    #[test]
+    fn test_insert() {
+        let boxes = random_boxes(2000, 3);
+        let mut tree = RTree::new();
+        assert!(tree.is_empty());
+        assert!(tree.search(&BoundingBox::new(0f64, 0f64, 1000f64, 1000f64)).is_empty());
+        assert!(tree.nearest(0f64, 0f64, 5).is_empty());
+        for i in 0..boxes.len() {
+            tree.insert(boxes[i], i);
+        }
+        assert_eq!(tree.size(), boxes.len());
+        check_queries(&tree, &boxes, &vec![true; boxes.len()], 4);
+    }
+
+    #[test]
+    fn test_remove() {
+        let boxes = random_boxes(2000, 5);
+        let mut tree = RTree::bulk_load(boxes.iter().cloned().zip(0..boxes.len()).collect());
+        let mut present = vec![true; boxes.len()];
+        // items are found by both envelope and value
+        assert!(!tree.remove(&boxes[0], &1));
+        for i in (0..boxes.len()).filter(|i| i % 3 != 0) {
+            assert!(tree.remove(&boxes[i], &i));
+            present[i] = false;
+        }
+        assert!(!tree.remove(&boxes[1], &1));
+        assert_eq!(tree.size(), present.iter().filter(|&&p| p).count());
+        check_queries(&tree, &boxes, &present, 6);
+
+        // the tree remains usable after being emptied
+        for i in (0..boxes.len()).filter(|i| i % 3 == 0) {
+            assert!(tree.remove(&boxes[i], &i));
+        }
+        assert!(tree.is_empty());
+        tree.insert(boxes[0], 0);
+        assert_eq!(sorted(tree.search(&boxes[0])), vec![0]);
+    }
+}
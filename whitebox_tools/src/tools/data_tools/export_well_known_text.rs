@@ -0,0 +1,212 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::prelude::*;
+use vector::*;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Exports the geometries of a vector file as OGC well-known text (WKT). The
+/// output is a CSV table with one row per record, containing the record
+/// number (FID) and its geometry (WKT), quoted, followed by the record's
+/// attributes. Such tables can be read directly by most GIS and spatial
+/// databases. Null shapes are written as GEOMETRYCOLLECTION EMPTY.
+pub struct ExportWellKnownText {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExportWellKnownText {
+    pub fn new() -> ExportWellKnownText { // public constructor
+        let name = "ExportWellKnownText".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description = "Exports the geometries of a vector file as well-known text (WKT).".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output CSV file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: false
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=watersheds.shp -o=watersheds.csv", short_exe, name).replace("*", &sep);
+
+        ExportWellKnownText {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for ExportWellKnownText {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading data...") };
+        let input = Shapefile::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        let f = File::create(&output_file)?;
+        let mut writer = BufWriter::new(f);
+        let mut header = String::from("FID,WKT");
+        for field in &input.attributes.fields {
+            header.push_str(&format!(",{}", csv_value(&field.name)));
+        }
+        writer.write_all(format!("{}\n", header).as_bytes())?;
+
+        let num_records = input.num_records;
+        let num_fields = input.attributes.get_num_fields();
+        for record_num in 0..num_records {
+            let mut line = format!("{},\"{}\"", record_num + 1, geometry_to_wkt(input.get_record(record_num)));
+            if record_num < input.attributes.get_num_records() {
+                for value in input.attributes.get_record(record_num) {
+                    match value {
+                        FieldData::Null => line.push_str(","),
+                        FieldData::Text(s) => line.push_str(&format!(",{}", csv_value(&s))),
+                        v => line.push_str(&format!(",{}", v)),
+                    }
+                }
+            } else {
+                line.push_str(&",".repeat(num_fields));
+            }
+            writer.write_all(format!("{}\n", line).as_bytes())?;
+
+            if verbose {
+                progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        let _ = writer.flush();
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Output file written") };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+/// Quotes a text value if it contains a delimiter, quote or line break.
+fn csv_value(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') {
+        format!("\"{}\"", s.replace("\"", "\"\""))
+    } else {
+        s.to_string()
+    }
+}
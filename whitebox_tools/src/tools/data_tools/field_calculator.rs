@@ -0,0 +1,375 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use vector::*;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Calculates the values of an attribute field from an expression evaluated
+/// for each record of a vector file, e.g. `POP / ($area / 1000000)` or
+/// `upper(NAME) + ' (' + str(round($length, 1)) + ' m)'`. The expression may
+/// refer to other fields, to geometric properties such as `$area`, `$length`
+/// and `$centroid_x`, and may use arithmetic, comparison and logical
+/// operators and functions (see *ExtractByAttribute*).
+///
+/// If the field does not exist it is added to the attribute table, with the
+/// type given by `--type` (real, integer, text or boolean), or, if no type is
+/// given, the type of the calculated values. An existing field keeps its type
+/// and the values are converted to it. Records for which the expression has
+/// no value (e.g. because of missing attributes or division by zero) are left
+/// null. The table is updated in place unless an output file is specified.
+pub struct FieldCalculator {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl FieldCalculator {
+    pub fn new() -> FieldCalculator { // public constructor
+        let name = "FieldCalculator".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description = "Calculates the values of an attribute field from an expression.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Name of the new or existing field to calculate.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Expression".to_owned(),
+            flags: vec!["--expression".to_owned()],
+            description: "Expression used to calculate the field values.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Field Type (optional)".to_owned(),
+            flags: vec!["--type".to_owned()],
+            description: "Data type of a new field; inferred from the calculated values if unspecified.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["real".to_owned(), "integer".to_owned(), "text".to_owned(), "boolean".to_owned()]),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File (optional)".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Optional output vector file; the input file is updated if unspecified.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=counties.shp --field=DENSITY --expression=\"POP / ($area / 1000000)\"
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=roads.shp -o=roads_labelled.shp --field=LABEL --type=text --expression=\"upper(NAME) + ' ' + str(round($length))\"", short_exe, name).replace("*", &sep);
+
+        FieldCalculator {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for FieldCalculator {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut field_name = String::new();
+        let mut expression_str = String::new();
+        let mut field_type = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-field" || vec[0].to_lowercase() == "--field" {
+                if keyval {
+                    field_name = vec[1].to_string();
+                } else {
+                    field_name = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-type" || vec[0].to_lowercase() == "--type" {
+                if keyval {
+                    field_type = vec[1].to_lowercase();
+                } else {
+                    field_type = args[i+1].to_lowercase();
+                }
+            } else if vec[0].to_lowercase() == "-expression" || vec[0].to_lowercase() == "--expression" {
+                // taken verbatim, since the expression may contain quotes and equals signs
+                if keyval {
+                    expression_str = args[i].splitn(2, '=').nth(1).unwrap().to_string();
+                } else {
+                    expression_str = args[i+1].to_string();
+                }
+                if expression_str.len() >= 2 && expression_str.starts_with('"') && expression_str.ends_with('"') {
+                    expression_str = expression_str[1..expression_str.len() - 1].to_string();
+                }
+            }
+        }
+
+        let field_name = field_name.trim().to_string();
+        if field_name.is_empty() || field_name.len() > 10 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "A field name (--field) of no more than 10 characters must be specified."));
+        }
+        if expression_str.trim().is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "An expression (--expression) must be specified."));
+        }
+        let new_type = match field_type.as_ref() {
+            "" => None,
+            "real" | "float" | "double" => Some(FieldDataType::Real),
+            "integer" | "int" => Some(FieldDataType::Int),
+            "text" | "string" => Some(FieldDataType::Text),
+            "boolean" | "bool" => Some(FieldDataType::Bool),
+            _ => return Err(Error::new(ErrorKind::InvalidInput,
+                                "The field type (--type) must be one of real, integer, text or boolean.")),
+        };
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if output_file.is_empty() {
+            output_file = input_file.clone();
+        } else if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading data...") };
+        let input = Shapefile::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        let expression = Expression::new(&expression_str, &input.attributes.fields)?;
+
+        let num_records = input.num_records;
+        let mut values = Vec::with_capacity(num_records);
+        for record_num in 0..num_records {
+            let atts = if record_num < input.attributes.get_num_records() {
+                input.attributes.get_record(record_num)
+            } else {
+                vec![]
+            };
+            values.push(expression.evaluate(&atts, input.get_record(record_num), record_num)?);
+
+            if verbose {
+                progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Calculating values: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut output = Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+        let field_num = match output.attributes.get_field_num(&field_name) {
+            Some(i) => {
+                if new_type.is_some() && new_type != Some(output.attributes.fields[i].field_type) && verbose {
+                    println!("Warning: the existing field {} keeps its data type.", field_name);
+                }
+                i
+            }
+            None => {
+                let data_type = match new_type {
+                    Some(t) => t,
+                    None => infer_type(&values),
+                };
+                // text fields are widened to fit the values below
+                let (length, decimals) = match data_type {
+                    FieldDataType::Int => (10u8, 0u8),
+                    FieldDataType::Text => (1u8, 0u8),
+                    _ => (18u8, 6u8),
+                };
+                output.attributes.add_field(&AttributeField::new(&field_name, data_type, length, decimals));
+                output.attributes.get_num_fields() - 1
+            }
+        };
+
+        let data_type = output.attributes.fields[field_num].field_type;
+        let mut num_null = 0;
+        for record_num in 0..num_records {
+            output.add_record(input.get_record(record_num).clone());
+            let mut atts = if record_num < input.attributes.get_num_records() {
+                input.attributes.get_record(record_num)
+            } else {
+                vec![]
+            };
+            atts.resize(output.attributes.get_num_fields(), FieldData::Null);
+            let value = to_field_data(&values[record_num], data_type);
+            if let FieldData::Text(ref s) = value {
+                let field = &mut output.attributes.fields[field_num];
+                field.field_length = field.field_length.max(s.len().min(254) as u8);
+            }
+            if value.is_null() {
+                num_null += 1;
+            }
+            atts[field_num] = value;
+            output.attributes.add_record(atts, false);
+        }
+
+        if verbose && num_null > 0 {
+            println!("Warning: {} of {} records have no value.", num_null, num_records);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+/// The field type suited to a set of values: boolean if they are all
+/// boolean, real if they are all numbers, and otherwise text.
+fn infer_type(values: &[ExpressionValue]) -> FieldDataType {
+    let mut data_type = None;
+    for value in values {
+        let t = match *value {
+            ExpressionValue::Null => continue,
+            ExpressionValue::Bool(_) => FieldDataType::Bool,
+            ExpressionValue::Number(_) => FieldDataType::Real,
+            ExpressionValue::Text(_) => FieldDataType::Text,
+        };
+        if data_type.is_some() && data_type != Some(t) {
+            return FieldDataType::Text;
+        }
+        data_type = Some(t);
+    }
+    data_type.unwrap_or(FieldDataType::Real)
+}
+
+/// Converts a calculated value to the data type of a field, giving null if it
+/// cannot be converted.
+fn to_field_data(value: &ExpressionValue, data_type: FieldDataType) -> FieldData {
+    if *value == ExpressionValue::Null {
+        return FieldData::Null;
+    }
+    let number = match *value {
+        ExpressionValue::Number(v) => Some(v),
+        ExpressionValue::Bool(b) => Some(if b { 1f64 } else { 0f64 }),
+        ExpressionValue::Text(ref s) => s.trim().parse::<f64>().ok(),
+        ExpressionValue::Null => None,
+    };
+    match data_type {
+        FieldDataType::Real => number.map(FieldData::Real).unwrap_or(FieldData::Null),
+        FieldDataType::Int => {
+            match number {
+                Some(v) if v.abs() < i32::max_value() as f64 => FieldData::Int(v.round() as i32),
+                _ => FieldData::Null,
+            }
+        }
+        FieldDataType::Bool => FieldData::Bool(value.is_true()),
+        FieldDataType::Text => FieldData::Text(format!("{}", value)),
+        FieldDataType::Date => {
+            // dates are stored as YYYYMMDD
+            let s = format!("{}", value).replace("-", "");
+            if s.len() == 8 && s.chars().all(|c| c.is_digit(10)) {
+                FieldData::Date(s)
+            } else {
+                FieldData::Null
+            }
+        }
+    }
+}
@@ -1,11 +1,23 @@
 // private sub-module defined in other files
 mod convert_nodata_to_zero;
 mod convert_raster_format;
+mod export_well_known_text;
+mod field_calculator;
 mod new_raster;
+mod raster_to_vector_polygons;
 mod set_nodata_value;
+mod vector_lines_to_raster;
+mod vector_points_to_raster;
+mod vector_polygons_to_raster;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::convert_nodata_to_zero::ConvertNodataToZero;
 pub use self::convert_raster_format::ConvertRasterFormat;
+pub use self::export_well_known_text::ExportWellKnownText;
+pub use self::field_calculator::FieldCalculator;
 pub use self::new_raster::NewRasterFromBase;
-pub use self::set_nodata_value::SetNodataValue;
\ No newline at end of file
+pub use self::raster_to_vector_polygons::RasterToVectorPolygons;
+pub use self::set_nodata_value::SetNodataValue;
+pub use self::vector_lines_to_raster::VectorLinesToRaster;
+pub use self::vector_points_to_raster::VectorPointsToRaster;
+pub use self::vector_polygons_to_raster::VectorPolygonsToRaster;
\ No newline at end of file
@@ -0,0 +1,363 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::f64;
+use raster::*;
+use vector::*;
+use algorithms::{chaikin_smoothing, douglas_peucker, polygon_area, polyline_length,
+                 signed_polygon_area, trace_region_boundaries};
+use structures::Array2D;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Converts the contiguous regions of equal value within a raster into
+/// polygons. Each region, which may be defined using either four- or
+/// eight-neighbour connectivity (`--diag`), becomes one output polygon, with
+/// holes wherever the region surrounds cells of a different value, unless
+/// `--fill_holes` is specified. Region boundaries follow the cell edges; the
+/// resulting stair-step outlines can be simplified using the Douglas-Peucker
+/// algorithm (`--simplify`, a distance tolerance in map units) and/or smoothed
+/// using Chaikin's corner-cutting algorithm (`--smooth`, the number of
+/// iterations). Simplification and smoothing are applied to each ring
+/// independently, so the shared edges of neighbouring polygons may no longer
+/// coincide exactly.
+///
+/// The output attribute table contains the region identifier (FID), the
+/// raster cell value (VALUE), and the area (AREA) and perimeter (PERIMETER)
+/// of the output polygon. NoData cells are not vectorized.
+pub struct RasterToVectorPolygons {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl RasterToVectorPolygons {
+    pub fn new() -> RasterToVectorPolygons { // public constructor
+        let name = "RasterToVectorPolygons".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description = "Converts raster regions of equal value into vector polygons.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polygon file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Include diagonal connections?".to_owned(),
+            flags: vec!["--diag".to_owned()],
+            description: "Flag indicating whether diagonal connections should be considered.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Fill holes?".to_owned(),
+            flags: vec!["--fill_holes".to_owned()],
+            description: "Flag indicating whether polygon holes should be removed.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Simplification Tolerance".to_owned(),
+            flags: vec!["--simplify".to_owned()],
+            description: "Douglas-Peucker simplification tolerance, in map units (0 for no simplification).".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Smoothing Iterations".to_owned(),
+            flags: vec!["--smooth".to_owned()],
+            description: "Number of Chaikin smoothing iterations (0 for no smoothing).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("0".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=clumps.dep -o=polygons.shp --diag --simplify=5.0 --smooth=2", short_exe, name).replace("*", &sep);
+
+        RasterToVectorPolygons {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for RasterToVectorPolygons {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut diag = false;
+        let mut fill_holes = false;
+        let mut tolerance = 0f64;
+        let mut smooth_iterations = 0usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-diag" || vec[0].to_lowercase() == "--diag" {
+                diag = true;
+            } else if vec[0].to_lowercase() == "-fill_holes" || vec[0].to_lowercase() == "--fill_holes" {
+                fill_holes = true;
+            } else if vec[0].to_lowercase() == "-simplify" || vec[0].to_lowercase() == "--simplify" {
+                if keyval {
+                    tolerance = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    tolerance = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-smooth" || vec[0].to_lowercase() == "--smooth" {
+                if keyval {
+                    smooth_iterations = vec[1].to_string().parse::<usize>().unwrap();
+                } else {
+                    smooth_iterations = args[i+1].to_string().parse::<usize>().unwrap();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading data...") };
+
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        let nodata = input.configs.nodata;
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+
+        // label the contiguous regions of equal value
+        let mut labels: Array2D<i32> = Array2D::new(rows, columns, -1, -1)?;
+        let mut region_values = vec![];
+        let mut dx = [ 1, 1, 1, 0, -1, -1, -1, 0 ];
+        let mut dy = [ -1, 0, 1, 1, 1, 0, -1, -1 ];
+        let mut num_neighbours = 8;
+        if !diag {
+            dx = [ 0, 1, 0, -1, 0, 0, 0, 0 ];
+            dy = [ -1, 0, 1, 0, 0, 0, 0, 0 ];
+            num_neighbours = 4;
+        }
+        let (mut zin, mut zn): (f64, f64);
+        let (mut r, mut c): (isize, isize);
+        let mut stack = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                zin = input[(row, col)];
+                if zin != nodata && labels.get_value(row, col) < 0 {
+                    let label = region_values.len() as i32;
+                    region_values.push(zin);
+                    labels.set_value(row, col, label);
+                    stack.push((row, col));
+                    while let Some(cell) = stack.pop() {
+                        r = cell.0;
+                        c = cell.1;
+                        for i in 0..num_neighbours {
+                            zn = input[(r + dy[i], c + dx[i])];
+                            if zn == zin && labels.get_value(r + dy[i], c + dx[i]) < 0 {
+                                labels.set_value(r + dy[i], c + dx[i], label);
+                                stack.push((r + dy[i], c + dx[i]));
+                            }
+                        }
+                    }
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Labelling regions: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose { println!("Tracing region boundaries...") };
+        let regions = trace_region_boundaries(&labels,
+                                              region_values.len(),
+                                              input.configs.west,
+                                              input.configs.north,
+                                              input.configs.resolution_x,
+                                              input.configs.resolution_y);
+
+        let mut output = Shapefile::new(&output_file, "w")?;
+        output.header.shape_type = ShapeType::Polygon;
+        if input.configs.coordinate_ref_system_wkt.to_lowercase() != "not specified" {
+            output.projection = input.configs.coordinate_ref_system_wkt.clone();
+        }
+
+        let is_integer_data = match input.configs.data_type {
+            DataType::F64 | DataType::F32 => false,
+            _ => true,
+        };
+        output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+        if is_integer_data {
+            output.attributes.add_field(&AttributeField::new("VALUE", FieldDataType::Int, 10u8, 0u8));
+        } else {
+            output.attributes.add_field(&AttributeField::new("VALUE", FieldDataType::Real, 18u8, 6u8));
+        }
+        output.attributes.add_field(&AttributeField::new("AREA", FieldDataType::Real, 18u8, 4u8));
+        output.attributes.add_field(&AttributeField::new("PERIMETER", FieldDataType::Real, 18u8, 4u8));
+
+        let num_regions = regions.len();
+        for (label, rings) in regions.into_iter().enumerate() {
+            let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+            let mut area = 0f64;
+            let mut perimeter = 0f64;
+            for ring in rings {
+                let is_hole = signed_polygon_area(&ring) > 0f64;
+                if is_hole && fill_holes {
+                    continue;
+                }
+                let mut points = ring;
+                if tolerance > 0f64 {
+                    points = douglas_peucker(&points, tolerance);
+                }
+                if smooth_iterations > 0 {
+                    points = chaikin_smoothing(&points, smooth_iterations);
+                }
+                if is_hole {
+                    area -= polygon_area(&points);
+                } else {
+                    area += polygon_area(&points);
+                }
+                perimeter += polyline_length(&points);
+                geom.add_part(&points);
+            }
+            output.add_record(geom);
+
+            let value = if is_integer_data {
+                FieldData::Int(region_values[label] as i32)
+            } else {
+                FieldData::Real(region_values[label])
+            };
+            output.attributes.add_record(vec![FieldData::Int(label as i32 + 1),
+                                              value,
+                                              FieldData::Real(area),
+                                              FieldData::Real(perimeter)],
+                                         false);
+
+            if verbose {
+                progress = (100.0_f64 * label as f64 / (num_regions - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Creating polygons: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,385 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::f64;
+use raster::*;
+use vector::*;
+use algorithms::{find_line_cells, find_polygon_cells};
+use structures::Point2D;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Converts a vector of polygons into a raster. The burned value is either an
+/// attribute field, a constant numeric value, or the feature ID ('FID', the
+/// default). By default, a cell is assigned to a polygon if its centre falls
+/// within the polygon (holes excluded); with `--all_touched`, every cell that
+/// the polygon touches is assigned. Where polygons overlap, the last feature
+/// in the file is retained. The output grid either matches an existing base
+/// raster (`--base`), or covers the extent of the vector at the specified
+/// cell size (`--cell_size`).
+pub struct VectorPolygonsToRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl VectorPolygonsToRaster {
+    pub fn new() -> VectorPolygonsToRaster { // public constructor
+        let name = "VectorPolygonsToRaster".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description = "Converts a vector containing polygons into a raster.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Vector Polygon File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Input field name in attribute table, 'FID' for the feature ID, or a constant numeric value.".to_owned(),
+            parameter_type: ParameterType::StringOrNumber,
+            default_value: Some("FID".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Assign all touched cells?".to_owned(),
+            flags: vec!["--all_touched".to_owned()],
+            description: "Flag indicating whether all cells touched by a polygon, rather than only those with centres inside it, are assigned.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Background value is zero?".to_owned(),
+            flags: vec!["--zero_background".to_owned()],
+            description: "Flag indicating whether a background value of zero should be used.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Cell Size (optional)".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Optionally specified cell size of output raster. Not used when base raster is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Base Raster File (optional)".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Optionally specified input base raster file. The output raster will match its grid; the cell size is then ignored.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=lakes.shp --field=ELEV -o=output.dep --base=dem.dep
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=lakes.shp -o=output.dep --cell_size=10.0 --all_touched --zero_background", short_exe, name).replace("*", &sep);
+
+        VectorPolygonsToRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for VectorPolygonsToRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut field_name = String::from("FID");
+        let mut output_file = String::new();
+        let mut base_file = String::new();
+        let mut cell_size = 0f64;
+        let mut all_touched = false;
+        let mut zero_background = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-field" || vec[0].to_lowercase() == "--field" {
+                if keyval {
+                    field_name = vec[1].to_string();
+                } else {
+                    field_name = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-cell_size" || vec[0].to_lowercase() == "--cell_size" {
+                if keyval {
+                    cell_size = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    cell_size = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-base" || vec[0].to_lowercase() == "--base" {
+                if keyval {
+                    base_file = vec[1].to_string();
+                } else {
+                    base_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-all_touched" || vec[0].to_lowercase() == "--all_touched" {
+                all_touched = true;
+            } else if vec[0].to_lowercase() == "-zero_background" || vec[0].to_lowercase() == "--zero_background" {
+                zero_background = true;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if cell_size <= 0f64 && base_file.trim().is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Either a cell size or a base raster must be specified."));
+        }
+        if !base_file.trim().is_empty() && !base_file.contains(&sep) {
+            base_file = format!("{}{}", working_directory, base_file);
+        }
+
+        if verbose { println!("Reading data...") };
+        let vector_data = Shapefile::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        // make sure the input vector file is of polygon type
+        if vector_data.header.shape_type.base_shape_type() != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of polygon base shape type."));
+        }
+
+        // the value burned for each feature; features with null values are skipped
+        let num_records = vector_data.num_records;
+        let mut values: Vec<Option<f64>> = Vec::with_capacity(num_records);
+        let mut is_categorical = false;
+        if field_name.to_lowercase() == "fid" {
+            is_categorical = true;
+            for record_num in 0..num_records {
+                values.push(Some((record_num + 1) as f64));
+            }
+        } else if let Ok(constant) = field_name.parse::<f64>() {
+            for _ in 0..num_records {
+                values.push(Some(constant));
+            }
+        } else {
+            let field_num = match vector_data.attributes.get_field_num(&field_name) {
+                Some(i) => i,
+                None => return Err(Error::new(ErrorKind::InvalidInput,
+                    format!("The attribute field '{}' could not be found.", field_name))),
+            };
+            if !vector_data.attributes.is_field_numeric(field_num) {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "Non-numeric attribute fields are not currently supported."));
+            }
+            for record_num in 0..num_records {
+                values.push(vector_data.attributes.get_value(record_num, &field_name).as_f64());
+            }
+        }
+
+        let nodata = -32768.0f64;
+        let background_val = if zero_background { 0f64 } else { nodata };
+
+        let mut output = if !base_file.trim().is_empty() {
+            let base = Raster::new(&base_file, "r")?;
+            let mut output = Raster::initialize_using_file(&output_file, &base);
+            output.configs.nodata = nodata;
+            output.reinitialize_values(background_val);
+            output
+        } else {
+            // base the output grid on the extent of the vector and the cell size;
+            // the extra row and column ensure that features on the east and south
+            // edges are captured
+            let west = vector_data.header.x_min;
+            let north = vector_data.header.y_max;
+            let rows = ((north - vector_data.header.y_min) / cell_size).floor() as usize + 1;
+            let columns = ((vector_data.header.x_max - west) / cell_size).floor() as usize + 1;
+            let mut configs = RasterConfigs { ..Default::default() };
+            configs.rows = rows;
+            configs.columns = columns;
+            configs.north = north;
+            configs.south = north - rows as f64 * cell_size;
+            configs.east = west + columns as f64 * cell_size;
+            configs.west = west;
+            configs.resolution_x = cell_size;
+            configs.resolution_y = cell_size;
+            configs.nodata = nodata;
+            if !vector_data.projection.is_empty() {
+                configs.coordinate_ref_system_wkt = vector_data.projection.clone();
+            }
+            let mut output = Raster::initialize_using_config(&output_file, &configs);
+            output.reinitialize_values(background_val);
+            output
+        };
+        output.configs.data_type = DataType::F32;
+        if is_categorical {
+            output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+            output.configs.palette = "qual.plt".to_string();
+        } else {
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.configs.palette = "spectrum.plt".to_string();
+        }
+
+        let rows = output.configs.rows as isize;
+        let columns = output.configs.columns as isize;
+        let west = output.configs.west;
+        let north = output.configs.north;
+        let res_x = output.configs.resolution_x;
+        let res_y = output.configs.resolution_y;
+
+        for record_num in 0..num_records {
+            let record = vector_data.get_record(record_num);
+            let value = match values[record_num] {
+                Some(v) => v,
+                None => continue,
+            };
+            if record.shape_type == ShapeType::Null || record.num_points == 0 {
+                continue;
+            }
+
+            let rings: Vec<&[Point2D]> = (0..record.num_parts as usize).map(|part| record.get_part(part)).collect();
+            for (row, col) in find_polygon_cells(&rings, west, north, res_x, res_y, rows, columns) {
+                output.set_value(row, col, value);
+            }
+
+            if all_touched {
+                // add the cells crossed by the polygon's boundary
+                for part in 0..record.num_parts as usize {
+                    let points = record.get_part(part);
+                    for i in 1..points.len() {
+                        let cells = find_line_cells((points[i - 1].x - west) / res_x,
+                                                    (north - points[i - 1].y) / res_y,
+                                                    (points[i].x - west) / res_x,
+                                                    (north - points[i].y) / res_y);
+                        for (row, col) in cells {
+                            output.set_value(row, col, value);
+                        }
+                    }
+                }
+            }
+
+            if verbose {
+                progress = (100.0_f64 * record_num as f64 / (num_records - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+        output.add_metadata_entry(format!("Created by whitebox_tools\' {} tool", self.get_tool_name()));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Field: {}", field_name));
+        output.add_metadata_entry(format!("All touched: {}", all_touched));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,528 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use vector::*;
+use std::f64;
+use std::f64::consts::PI;
+use algorithms::{is_clockwise_order, PolygonOverlay};
+use structures::Point2D;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+
+/// Creates polygon buffers around the features of a vector of points, lines
+/// or polygons, i.e. the areas lying within a distance (`--dist`) of each
+/// feature. The ends of lines are given round, flat or square caps (`--cap`)
+/// and the vertices at which a line or polygon boundary changes direction are
+/// given round or mitred joins (`--join`). Mitres that would extend further
+/// from the vertex than `--mitre_limit` times the buffer distance are
+/// bevelled. Points are buffered with circles, or with squares where
+/// `--cap=square`. Circular arcs are approximated using 8 segments per
+/// quarter circle.
+///
+/// A negative buffer distance shrinks polygons inwards, and polygons that are
+/// narrower than twice the distance disappear; negative distances are not
+/// defined for points and lines. Each output polygon retains the attributes of
+/// the input feature from which it was derived. When `--dissolve` is used,
+/// overlapping buffers are merged and each of the resulting disjoint polygons
+/// is output with only an FID attribute.
+pub struct BufferVector {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl BufferVector {
+    pub fn new() -> BufferVector { // public constructor
+        let name = "BufferVector".to_string();
+        let toolbox = "GIS Analysis/Distance Tools".to_string();
+        let description = "Creates polygon buffers around vector points, lines, or polygons.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector points, lines or polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polygons file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Buffer Distance".to_owned(),
+            flags: vec!["--dist".to_owned(), "--distance".to_owned()],
+            description: "Buffer distance, in map units; negative values shrink polygons.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Cap Style".to_owned(),
+            flags: vec!["--cap".to_owned()],
+            description: "Style of the buffer at line ends; options include 'round' (default), 'flat', and 'square'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["round".to_owned(), "flat".to_owned(), "square".to_owned()]),
+            default_value: Some("round".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Join Style".to_owned(),
+            flags: vec!["--join".to_owned()],
+            description: "Style of the buffer at line and polygon vertices; options include 'round' (default) and 'mitre'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["round".to_owned(), "mitre".to_owned()]),
+            default_value: Some("round".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Mitre Limit".to_owned(),
+            flags: vec!["--mitre_limit".to_owned()],
+            description: "Maximum length of a mitre, as a multiple of the buffer distance, beyond which it is bevelled.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("5.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Dissolve overlapping buffers?".to_owned(),
+            flags: vec!["--dissolve".to_owned()],
+            description: "Dissolve the buffers of all features into disjoint polygons?".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=streams.shp -o=riparian.shp --dist=30.0 --cap=flat --dissolve
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=parcels.shp -o=setbacks.shp --dist=-5.0 --join=mitre", short_exe, name).replace("*", &sep);
+
+        BufferVector {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for BufferVector {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut distance = f64::NAN;
+        let mut cap_str = String::from("round");
+        let mut join_str = String::from("round");
+        let mut mitre_limit = 5f64;
+        let mut dissolve = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-dist" || flag_val == "-distance" {
+                if keyval {
+                    distance = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    distance = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if flag_val == "-cap" {
+                if keyval {
+                    cap_str = vec[1].to_lowercase();
+                } else {
+                    cap_str = args[i+1].to_lowercase();
+                }
+            } else if flag_val == "-join" {
+                if keyval {
+                    join_str = vec[1].to_lowercase();
+                } else {
+                    join_str = args[i+1].to_lowercase();
+                }
+            } else if flag_val == "-mitre_limit" {
+                if keyval {
+                    mitre_limit = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    mitre_limit = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if flag_val == "-dissolve" {
+                dissolve = true;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if distance.is_nan() || distance == 0f64 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "A non-zero buffer distance must be specified."));
+        }
+        let cap = match cap_str.as_ref() {
+            "round" => CapStyle::Round,
+            "flat" => CapStyle::Flat,
+            "square" => CapStyle::Square,
+            _ => return Err(Error::new(ErrorKind::InvalidInput,
+                    "The cap style must be one of 'round', 'flat', or 'square'.")),
+        };
+        let join = if join_str.starts_with("mit") {
+            JoinStyle::Mitre
+        } else if join_str == "round" {
+            JoinStyle::Round
+        } else {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The join style must be either 'round' or 'mitre'."));
+        };
+        let style = BufferStyle {
+            distance: distance.abs(),
+            cap: cap,
+            join: join,
+            mitre_limit: mitre_limit.max(1f64),
+        };
+
+        if verbose { println!("Reading data...") };
+        let input = Shapefile::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        let shape_type = input.header.shape_type.base_shape_type();
+        if shape_type != ShapeType::Point && shape_type != ShapeType::MultiPoint
+            && shape_type != ShapeType::PolyLine && shape_type != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of point, polyline, or polygon base shape type."));
+        }
+        if distance < 0f64 && shape_type != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "Negative buffer distances may only be used with polygon inputs."));
+        }
+
+        // the buffer of each feature is the union of a set of simple pieces, plus
+        // the feature itself for polygons, less the pieces for negative buffers
+        let mut buffers: Vec<Vec<Vec<Point2D>>> = vec![vec![]; input.num_records];
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            let mut pieces: Vec<Vec<Vec<Point2D>>> = vec![];
+            let mut polygon: Vec<Vec<Vec<Point2D>>> = vec![];
+            match shape_type {
+                ShapeType::Point | ShapeType::MultiPoint => {
+                    for p in &record.points {
+                        add_point_piece(*p, &style, &mut pieces);
+                    }
+                },
+                ShapeType::PolyLine => {
+                    for part in 0..record.num_parts as usize {
+                        add_line_pieces(record.get_part(part), false, &style, &mut pieces);
+                    }
+                },
+                _ => {
+                    let rings: Vec<Vec<Point2D>> = (0..record.num_parts as usize).map(|part| record.get_part(part).to_vec()).collect();
+                    for ring in &rings {
+                        add_line_pieces(ring, true, &style, &mut pieces);
+                    }
+                    polygon.push(rings);
+                },
+            }
+            if pieces.is_empty() {
+                continue;
+            }
+            let overlay = PolygonOverlay::new(&[&pieces, &polygon], 0f64);
+            let faces: Vec<usize> = (0..overlay.num_faces()).filter(|&face| {
+                let in_pieces = !overlay.get_face_features(face, 0).is_empty();
+                let in_polygon = !overlay.get_face_features(face, 1).is_empty();
+                if distance > 0f64 {
+                    in_pieces || in_polygon
+                } else {
+                    in_polygon && !in_pieces
+                }
+            }).collect();
+            if !faces.is_empty() {
+                buffers[record_num] = overlay.dissolve(&faces);
+            }
+            if verbose {
+                progress = (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Buffering features: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut output: Shapefile;
+        if dissolve {
+            if verbose { println!("Dissolving buffers...") };
+            let features: Vec<Vec<Vec<Point2D>>> = buffers.into_iter().filter(|rings| !rings.is_empty()).collect();
+            let overlay = PolygonOverlay::new(&[&features], 0f64);
+            let faces: Vec<usize> = (0..overlay.num_faces()).filter(|&face| {
+                !overlay.get_face_features(face, 0).is_empty()
+            }).collect();
+            output = Shapefile::initialize_using_file(&output_file, &input, ShapeType::Polygon, false)?;
+            output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+            // each clockwise hull starts a new polygon, followed by its holes
+            let mut polygons: Vec<Vec<Vec<Point2D>>> = vec![];
+            for ring in overlay.dissolve(&faces) {
+                if is_clockwise_order(&ring) || polygons.is_empty() {
+                    polygons.push(vec![ring]);
+                } else {
+                    let last = polygons.len() - 1;
+                    polygons[last].push(ring);
+                }
+            }
+            for (fid, rings) in polygons.iter().enumerate() {
+                let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+                for ring in rings {
+                    geom.add_part(ring);
+                }
+                output.add_record(geom);
+                output.attributes.add_record(vec![FieldData::Int(fid as i32 + 1)], false);
+            }
+        } else {
+            output = Shapefile::initialize_using_file(&output_file, &input, ShapeType::Polygon, true)?;
+            for record_num in 0..input.num_records {
+                if buffers[record_num].is_empty() {
+                    continue;
+                }
+                let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+                for ring in &buffers[record_num] {
+                    geom.add_part(ring);
+                }
+                output.add_record(geom);
+                output.attributes.add_record(input.attributes.get_record(record_num), false);
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+/// The number of segments used to approximate a quarter circle.
+const QUADRANT_SEGMENTS: usize = 8;
+
+#[derive(Clone, Copy, PartialEq)]
+enum CapStyle {
+    Round,
+    Flat,
+    Square,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum JoinStyle {
+    Round,
+    Mitre,
+}
+
+struct BufferStyle {
+    distance: f64,
+    cap: CapStyle,
+    join: JoinStyle,
+    mitre_limit: f64,
+}
+
+// A closed ring approximating a circle.
+fn circle(centre: Point2D, radius: f64) -> Vec<Point2D> {
+    let n = 4 * QUADRANT_SEGMENTS;
+    let mut ring: Vec<Point2D> = (0..n).map(|i| {
+        let a = 2f64 * PI * i as f64 / n as f64;
+        Point2D::new(centre.x + radius * a.cos(), centre.y + radius * a.sin())
+    }).collect();
+    ring.push(ring[0]);
+    ring
+}
+
+fn add_point_piece(p: Point2D, style: &BufferStyle, pieces: &mut Vec<Vec<Vec<Point2D>>>) {
+    let d = style.distance;
+    if style.cap == CapStyle::Square {
+        pieces.push(vec![vec![Point2D::new(p.x - d, p.y - d),
+                              Point2D::new(p.x - d, p.y + d),
+                              Point2D::new(p.x + d, p.y + d),
+                              Point2D::new(p.x + d, p.y - d),
+                              Point2D::new(p.x - d, p.y - d)]]);
+    } else {
+        pieces.push(vec![circle(p, d)]);
+    }
+}
+
+// Adds the pieces whose union is the buffer of a polyline part, or of the
+// boundary of a polygon ring where `closed` is true: a rectangle for each
+// segment, caps at the line ends, and a join at each vertex.
+fn add_line_pieces(line: &[Point2D], closed: bool, style: &BufferStyle, pieces: &mut Vec<Vec<Vec<Point2D>>>) {
+    let mut points: Vec<Point2D> = vec![];
+    for p in line {
+        if points.is_empty() || points[points.len() - 1] != *p {
+            points.push(*p);
+        }
+    }
+    if closed && points.len() > 1 && points[0] == points[points.len() - 1] {
+        points.pop();
+    }
+    let n = points.len();
+    if n == 0 {
+        return;
+    }
+    if n == 1 {
+        add_point_piece(points[0], style, pieces);
+        return;
+    }
+
+    let d = style.distance;
+    let num_segments = if closed { n } else { n - 1 };
+    let directions: Vec<Point2D> = (0..num_segments).map(|i| {
+        let v = points[(i + 1) % n] - points[i];
+        let len = (v.x * v.x + v.y * v.y).sqrt();
+        Point2D::new(v.x / len, v.y / len)
+    }).collect();
+
+    for i in 0..num_segments {
+        let u = directions[i];
+        let mut p1 = points[i];
+        let mut p2 = points[(i + 1) % n];
+        if !closed && style.cap == CapStyle::Square {
+            if i == 0 {
+                p1 = Point2D::new(p1.x - u.x * d, p1.y - u.y * d);
+            }
+            if i == num_segments - 1 {
+                p2 = Point2D::new(p2.x + u.x * d, p2.y + u.y * d);
+            }
+        }
+        let normal = Point2D::new(-u.y * d, u.x * d);
+        pieces.push(vec![vec![p1 + normal, p2 + normal, p2 - normal, p1 - normal, p1 + normal]]);
+    }
+
+    if !closed && style.cap == CapStyle::Round {
+        pieces.push(vec![circle(points[0], d)]);
+        pieces.push(vec![circle(points[n - 1], d)]);
+    }
+
+    // joins are needed at the interior vertices of lines and all vertices of rings
+    let (first, last) = if closed { (0, n) } else { (1, n - 1) };
+    for v in first..last {
+        let u1 = directions[(v + num_segments - 1) % num_segments];
+        let u2 = directions[v % num_segments];
+        let cross = u1.x * u2.y - u1.y * u2.x;
+        let dot = u1.x * u2.x + u1.y * u2.y;
+        if cross.abs() < 1e-12 && dot > 0f64 {
+            continue; // no change in direction
+        }
+        let p = points[v];
+        match style.join {
+            JoinStyle::Round => pieces.push(vec![circle(p, d)]),
+            JoinStyle::Mitre => {
+                if 1f64 + dot < 1e-12 {
+                    continue; // the line doubles back; there is no outer side
+                }
+                // the gap to be filled is on the outside of the turn, i.e. on the
+                // right of a left turn
+                let side = if cross > 0f64 { -d } else { d };
+                let a = Point2D::new(p.x - u1.y * side, p.y + u1.x * side);
+                let b = Point2D::new(p.x - u2.y * side, p.y + u2.x * side);
+                // the mitre point is at a distance of d / cos(theta / 2) from the
+                // vertex, where theta is the angle of the turn
+                if (2f64 / (1f64 + dot)).sqrt() <= style.mitre_limit {
+                    let s = 1f64 / (1f64 + dot);
+                    let m = Point2D::new(p.x + (a.x + b.x - 2f64 * p.x) * s, p.y + (a.y + b.y - 2f64 * p.y) * s);
+                    pieces.push(vec![vec![p, a, m, b, p]]);
+                } else {
+                    pieces.push(vec![vec![p, a, b, p]]);
+                }
+            },
+        }
+    }
+}
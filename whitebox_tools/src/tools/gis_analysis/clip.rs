@@ -0,0 +1,263 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use vector::*;
+use algorithms::{overlay_lines_on_polygons, PolygonOverlay};
+use structures::Point2D;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Extracts the parts of the features of an input vector (lines or polygons)
+/// that fall within the polygons of a clip vector. Each output feature
+/// retains the attributes of the input feature from which it was derived;
+/// features that fall entirely outside of the clip polygons are removed.
+/// Vertices of the two layers that are within the snap tolerance (`--snap`)
+/// of one another are merged. Z and M values are not retained.
+pub struct Clip {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl Clip {
+    pub fn new() -> Clip { // public constructor
+        let name = "Clip".to_string();
+        let toolbox = "GIS Analysis/Overlay Tools".to_string();
+        let description = "Extracts all the features, or parts of features, that overlap with the features of the clip vector.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector lines or polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input Clip Polygon File".to_owned(),
+            flags: vec!["--clip".to_owned()],
+            description: "Input clip polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Snap Tolerance".to_owned(),
+            flags: vec!["--snap".to_owned()],
+            description: "Snap tolerance, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=streams.shp --clip=watershed.shp -o=output.shp", short_exe, name).replace("*", &sep);
+
+        Clip {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for Clip {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut clip_file = String::new();
+        let mut output_file = String::new();
+        let mut snap_tolerance = 0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-clip" {
+                if keyval {
+                    clip_file = vec[1].to_string();
+                } else {
+                    clip_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-snap" {
+                if keyval {
+                    snap_tolerance = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    snap_tolerance = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !clip_file.contains(&sep) {
+            clip_file = format!("{}{}", working_directory, clip_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading data...") };
+        let input = Shapefile::new(&input_file, "r")?;
+        let clip = Shapefile::new(&clip_file, "r")?;
+
+        let start = time::now();
+
+        let shape_type = input.header.shape_type.base_shape_type();
+        if shape_type != ShapeType::Polygon && shape_type != ShapeType::PolyLine {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of polyline or polygon base shape type."));
+        }
+        if clip.header.shape_type.base_shape_type() != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The clip vector data must be of polygon base shape type."));
+        }
+
+        let get_parts = |sf: &Shapefile| -> Vec<Vec<Vec<Point2D>>> {
+            sf.records.iter().map(|record| {
+                (0..record.num_parts as usize).map(|part| record.get_part(part).to_vec()).collect()
+            }).collect()
+        };
+        let input_features = get_parts(&input);
+        let clip_features = get_parts(&clip);
+
+        if verbose { println!("Performing overlay...") };
+        // the clipped parts of each input feature
+        let mut output_parts: Vec<Vec<Vec<Point2D>>> = vec![vec![]; input.num_records];
+        if shape_type == ShapeType::Polygon {
+            let overlay = PolygonOverlay::new(&[&input_features, &clip_features], snap_tolerance);
+            let mut feature_faces: Vec<Vec<usize>> = vec![vec![]; input.num_records];
+            for face in 0..overlay.num_faces() {
+                if !overlay.get_face_features(face, 1).is_empty() {
+                    for &i in overlay.get_face_features(face, 0) {
+                        feature_faces[i].push(face);
+                    }
+                }
+            }
+            for i in 0..input.num_records {
+                if !feature_faces[i].is_empty() {
+                    output_parts[i] = overlay.dissolve(&feature_faces[i]);
+                }
+            }
+        } else {
+            for piece in overlay_lines_on_polygons(&input_features, &clip_features, snap_tolerance) {
+                if !piece.polygons.is_empty() {
+                    output_parts[piece.feature].push(piece.points);
+                }
+            }
+        }
+
+        let mut output = Shapefile::initialize_using_file(&output_file, &input, shape_type, true)?;
+        for i in 0..input.num_records {
+            if output_parts[i].is_empty() {
+                continue;
+            }
+            let mut geom = ShapefileGeometry::new(shape_type);
+            for part in &output_parts[i] {
+                geom.add_part(part);
+            }
+            output.add_record(geom);
+            output.attributes.add_record(input.attributes.get_record(i), false);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::f64;
+use raster::*;
+use structures::BoundingBox;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Clips a raster to a rectangular extent, given either as coordinates
+/// (`--extent`, in the order min x, min y, max x, max y) or as the extent of
+/// another raster (`--base`). The output contains all of the input grid
+/// cells that overlap the extent, i.e. the extent is snapped outwards to the
+/// input grid and cells are never resampled.
+pub struct ClipRasterToExtent {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ClipRasterToExtent {
+    pub fn new() -> ClipRasterToExtent { // public constructor
+        let name = "ClipRasterToExtent".to_string();
+        let toolbox = "GIS Analysis/Overlay Tools".to_string();
+        let description = "Clips a raster to a coordinate extent or the extent of another raster.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Clipping Extent".to_owned(),
+            flags: vec!["--extent".to_owned()],
+            description: "Clipping extent, as 'min_x,min_y,max_x,max_y'.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Base Raster File".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Raster file whose extent is used for clipping.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif -o=output.tif --extent='575000.0,4830000.0,580000.0,4835000.0'
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif -o=output.tif --base=study_area.tif", short_exe, name).replace("*", &sep);
+
+        ClipRasterToExtent {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for ClipRasterToExtent {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut extent_str = String::new();
+        let mut base_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-extent" {
+                if keyval {
+                    extent_str = vec[1].to_string();
+                } else {
+                    extent_str = args[i+1].to_string();
+                }
+            } else if flag_val == "-base" {
+                if keyval {
+                    base_file = vec[1].to_string();
+                } else {
+                    base_file = args[i+1].to_string();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let extent = if !base_file.trim().is_empty() {
+            if !base_file.contains(&sep) {
+                base_file = format!("{}{}", working_directory, base_file);
+            }
+            let base = Raster::new(&base_file, "r")?;
+            BoundingBox::new(base.configs.west, base.configs.south, base.configs.east, base.configs.north)
+        } else if !extent_str.trim().is_empty() {
+            let coords: Vec<f64> = extent_str.split(",")
+                .filter_map(|s| s.trim().parse::<f64>().ok())
+                .collect();
+            if coords.len() != 4 || coords[0] >= coords[2] || coords[1] >= coords[3] {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "The extent must be specified as 'min_x,min_y,max_x,max_y'."));
+            }
+            BoundingBox::new(coords[0], coords[1], coords[2], coords[3])
+        } else {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "Either --extent or --base must be specified."));
+        };
+
+        if verbose { println!("Reading data...") };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let res_x = input.configs.resolution_x;
+        let res_y = input.configs.resolution_y;
+
+        // the window of input cells that overlap the extent; the small tolerance
+        // keeps an extent that coincides with cell edges from picking up an
+        // extra row or column due to rounding
+        let tolerance = 1e-6;
+        let top_row = ((input.configs.north - extent.max_y) / res_y + tolerance).floor().max(0f64) as isize;
+        let bottom_row = (((input.configs.north - extent.min_y) / res_y - tolerance).ceil() as isize - 1).min(rows - 1);
+        let left_col = ((extent.min_x - input.configs.west) / res_x + tolerance).floor().max(0f64) as isize;
+        let right_col = (((extent.max_x - input.configs.west) / res_x - tolerance).ceil() as isize - 1).min(columns - 1);
+        if top_row > bottom_row || left_col > right_col {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The clipping extent does not overlap the input raster."));
+        }
+
+        let mut configs = input.configs.clone();
+        configs.rows = (bottom_row - top_row + 1) as usize;
+        configs.columns = (right_col - left_col + 1) as usize;
+        configs.north = input.configs.north - top_row as f64 * res_y;
+        configs.south = configs.north - configs.rows as f64 * res_y;
+        configs.west = input.configs.west + left_col as f64 * res_x;
+        configs.east = configs.west + configs.columns as f64 * res_x;
+        configs.minimum = f64::INFINITY;
+        configs.maximum = f64::NEG_INFINITY;
+        configs.display_min = f64::INFINITY;
+        configs.display_max = f64::NEG_INFINITY;
+        configs.metadata = vec![];
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        for row in top_row..bottom_row + 1 {
+            for col in left_col..right_col + 1 {
+                output.set_value(row - top_row, col - left_col, input.get_value(row, col));
+            }
+            if verbose {
+                progress = (100.0_f64 * (row - top_row) as f64 / (bottom_row - top_row + 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+        output.add_metadata_entry(format!("Created by whitebox_tools\' {} tool", self.get_tool_name()));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Extent: {}", extent));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
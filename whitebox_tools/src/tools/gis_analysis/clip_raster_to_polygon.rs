@@ -0,0 +1,292 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::f64;
+use raster::*;
+use vector::*;
+use algorithms::find_polygon_cells;
+use structures::{Array2D, Point2D};
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Clips a raster to the features of a vector polygon file, i.e. grid cells
+/// whose centres fall outside of all of the polygons (or within their holes)
+/// are assigned NoData. By default the output has the same extent as the input
+/// raster; with `--crop`, the output extent is reduced to the rows and columns
+/// that overlap the bounding box of the polygons. Cells are never resampled.
+pub struct ClipRasterToPolygon {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ClipRasterToPolygon {
+    pub fn new() -> ClipRasterToPolygon { // public constructor
+        let name = "ClipRasterToPolygon".to_string();
+        let toolbox = "GIS Analysis/Overlay Tools".to_string();
+        let description = "Clips a raster to a vector polygon.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input Vector Polygon File".to_owned(),
+            flags: vec!["--polygons".to_owned()],
+            description: "Input vector polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Crop output to polygons extent?".to_owned(),
+            flags: vec!["--crop".to_owned()],
+            description: "Crop the output raster to the bounding box of the polygons?".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=dem.tif --polygons=watershed.shp -o=output.tif --crop", short_exe, name).replace("*", &sep);
+
+        ClipRasterToPolygon {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for ClipRasterToPolygon {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut polygons_file = String::new();
+        let mut output_file = String::new();
+        let mut crop = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-polygons" {
+                if keyval {
+                    polygons_file = vec[1].to_string();
+                } else {
+                    polygons_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-crop" {
+                crop = true;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !polygons_file.contains(&sep) {
+            polygons_file = format!("{}{}", working_directory, polygons_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading data...") };
+        let input = Raster::new(&input_file, "r")?;
+        let polygons = Shapefile::new(&polygons_file, "r")?;
+
+        let start = time::now();
+
+        if polygons.header.shape_type.base_shape_type() != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of polygon base shape type."));
+        }
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        let mut inside: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        let num_records = polygons.num_records;
+        for record_num in 0..num_records {
+            let record = polygons.get_record(record_num);
+            if record.shape_type == ShapeType::Null {
+                continue;
+            }
+            let rings: Vec<&[Point2D]> = (0..record.num_parts as usize).map(|part| record.get_part(part)).collect();
+            for (row, col) in find_polygon_cells(&rings,
+                                                 input.configs.west,
+                                                 input.configs.north,
+                                                 input.configs.resolution_x,
+                                                 input.configs.resolution_y,
+                                                 rows,
+                                                 columns) {
+                inside.set_value(row, col, 1u8);
+            }
+            if verbose {
+                progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Finding polygon cells: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // the window of the input grid that is output
+        let (mut top_row, mut bottom_row, mut left_col, mut right_col) = (0isize, rows - 1, 0isize, columns - 1);
+        if crop {
+            let res_x = input.configs.resolution_x;
+            let res_y = input.configs.resolution_y;
+            let tolerance = 1e-6; // so that edges coinciding with cell edges don't add a row or column
+            top_row = ((input.configs.north - polygons.header.y_max) / res_y + tolerance).floor().max(0f64) as isize;
+            bottom_row = (((input.configs.north - polygons.header.y_min) / res_y - tolerance).ceil() as isize - 1).min(rows - 1);
+            left_col = ((polygons.header.x_min - input.configs.west) / res_x + tolerance).floor().max(0f64) as isize;
+            right_col = (((polygons.header.x_max - input.configs.west) / res_x - tolerance).ceil() as isize - 1).min(columns - 1);
+            if top_row > bottom_row || left_col > right_col {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "The polygons do not overlap the input raster."));
+            }
+        }
+
+        let mut configs = input.configs.clone();
+        configs.rows = (bottom_row - top_row + 1) as usize;
+        configs.columns = (right_col - left_col + 1) as usize;
+        configs.north = input.configs.north - top_row as f64 * input.configs.resolution_y;
+        configs.south = configs.north - configs.rows as f64 * input.configs.resolution_y;
+        configs.west = input.configs.west + left_col as f64 * input.configs.resolution_x;
+        configs.east = configs.west + configs.columns as f64 * input.configs.resolution_x;
+        configs.minimum = f64::INFINITY;
+        configs.maximum = f64::NEG_INFINITY;
+        configs.display_min = f64::INFINITY;
+        configs.display_max = f64::NEG_INFINITY;
+        configs.metadata = vec![];
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        for row in top_row..bottom_row + 1 {
+            for col in left_col..right_col + 1 {
+                if inside.get_value(row, col) == 1u8 {
+                    output.set_value(row - top_row, col - left_col, input.get_value(row, col));
+                } else {
+                    output.set_value(row - top_row, col - left_col, nodata);
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * (row - top_row) as f64 / (bottom_row - top_row + 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+        output.add_metadata_entry(format!("Created by whitebox_tools\' {} tool", self.get_tool_name()));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Polygons file: {}", polygons_file));
+        output.add_metadata_entry(format!("Crop: {}", crop));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
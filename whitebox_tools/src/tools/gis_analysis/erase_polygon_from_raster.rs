@@ -0,0 +1,249 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use raster::*;
+use vector::*;
+use algorithms::find_polygon_cells;
+use structures::{Array2D, Point2D};
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Erases (cuts out) the features of a vector polygon file from a raster, i.e.
+/// grid cells whose centres fall within any of the polygons (but not within
+/// their holes) are assigned NoData. This is the complement of
+/// ClipRasterToPolygon, and the output has the same extent as the input.
+pub struct ErasePolygonFromRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ErasePolygonFromRaster {
+    pub fn new() -> ErasePolygonFromRaster { // public constructor
+        let name = "ErasePolygonFromRaster".to_string();
+        let toolbox = "GIS Analysis/Overlay Tools".to_string();
+        let description = "Erases (cuts out) a vector polygon from a raster.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input Vector Polygon File".to_owned(),
+            flags: vec!["--polygons".to_owned()],
+            description: "Input vector polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=dem.tif --polygons=watershed.shp -o=output.tif", short_exe, name).replace("*", &sep);
+
+        ErasePolygonFromRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for ErasePolygonFromRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut polygons_file = String::new();
+        let mut output_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-polygons" {
+                if keyval {
+                    polygons_file = vec[1].to_string();
+                } else {
+                    polygons_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !polygons_file.contains(&sep) {
+            polygons_file = format!("{}{}", working_directory, polygons_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading data...") };
+        let input = Raster::new(&input_file, "r")?;
+        let polygons = Shapefile::new(&polygons_file, "r")?;
+
+        let start = time::now();
+
+        if polygons.header.shape_type.base_shape_type() != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of polygon base shape type."));
+        }
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        let mut inside: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        let num_records = polygons.num_records;
+        for record_num in 0..num_records {
+            let record = polygons.get_record(record_num);
+            if record.shape_type == ShapeType::Null {
+                continue;
+            }
+            let rings: Vec<&[Point2D]> = (0..record.num_parts as usize).map(|part| record.get_part(part)).collect();
+            for (row, col) in find_polygon_cells(&rings,
+                                                 input.configs.west,
+                                                 input.configs.north,
+                                                 input.configs.resolution_x,
+                                                 input.configs.resolution_y,
+                                                 rows,
+                                                 columns) {
+                inside.set_value(row, col, 1u8);
+            }
+            if verbose {
+                progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Finding polygon cells: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        for row in 0..rows {
+            for col in 0..columns {
+                if inside.get_value(row, col) == 1u8 {
+                    output.set_value(row, col, nodata);
+                } else {
+                    output.set_value(row, col, input.get_value(row, col));
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+        output.add_metadata_entry(format!("Created by whitebox_tools\' {} tool", self.get_tool_name()));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Polygons file: {}", polygons_file));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
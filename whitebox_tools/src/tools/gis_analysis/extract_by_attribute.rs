@@ -0,0 +1,230 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use vector::*;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Extracts the features of a vector file that satisfy a conditional
+/// statement, e.g. `AREA > 1000 and upper(TYPE) == 'WETLAND'`, copying them,
+/// with their attributes, to a new file. The statement may refer to fields by
+/// name (within square brackets if the name contains spaces or other
+/// characters), to geometric properties such as `$area`, `$length` and
+/// `$centroid_x`, and may use arithmetic, comparison and logical operators and
+/// functions. Features for which the statement is true, or a non-zero number
+/// or non-empty text, are selected. Because the statement usually contains
+/// spaces, quotes and equals signs, it should be enclosed in double quotes on
+/// the command line.
+///
+/// See also *FieldCalculator*.
+pub struct ExtractByAttribute {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExtractByAttribute {
+    pub fn new() -> ExtractByAttribute { // public constructor
+        let name = "ExtractByAttribute".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description = "Extracts the features of a vector file that satisfy an attribute query.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Statement".to_owned(),
+            flags: vec!["--statement".to_owned()],
+            description: "Conditional statement, e.g. \"AREA > 1000 and TYPE == 'wetland'\".".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: false
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=lakes.shp -o=large_lakes.shp --statement=\"$area > 1000000 and NAME != ''\"", short_exe, name).replace("*", &sep);
+
+        ExtractByAttribute {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for ExtractByAttribute {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut statement = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-statement" {
+                // taken verbatim, since the statement may contain quotes and equals signs
+                if keyval {
+                    statement = args[i].splitn(2, '=').nth(1).unwrap().to_string();
+                } else {
+                    statement = args[i+1].to_string();
+                }
+                if statement.len() >= 2 && statement.starts_with('"') && statement.ends_with('"') {
+                    statement = statement[1..statement.len() - 1].to_string();
+                }
+            }
+        }
+
+        if statement.trim().is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "A conditional statement (--statement) must be specified."));
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading data...") };
+        let input = Shapefile::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        let expression = Expression::new(&statement, &input.attributes.fields)?;
+
+        let mut output = Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+
+        let num_records = input.num_records;
+        let mut num_selected = 0;
+        for record_num in 0..num_records {
+            let record = input.get_record(record_num);
+            let atts = input.attributes.get_record(record_num);
+            if expression.evaluate(&atts, record, record_num)?.is_true() {
+                output.add_record(record.clone());
+                output.attributes.add_record(atts, false);
+                num_selected += 1;
+            }
+
+            if verbose {
+                progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("{} of {} features selected", num_selected, num_records);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
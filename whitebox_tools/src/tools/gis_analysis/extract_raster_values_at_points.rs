@@ -0,0 +1,435 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::prelude::*;
+use raster::*;
+use vector::*;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Samples the values of one or more rasters at the locations of a vector
+/// points file. With the `nearest` method (the default), each point takes the
+/// value of the grid cell that contains it. With `bilinear`, the value is
+/// interpolated from the four nearest cell centres; NoData neighbours are
+/// ignored and the weights of the remaining cells renormalized. Points that
+/// fall outside of a raster, or on NoData, receive a null value.
+///
+/// Results are appended as fields to a copy of the points (`--output`) and/or
+/// written to a CSV table (`--out_csv`) with the columns FID, X, Y. In both,
+/// the values of each raster are named after the raster file (e.g. dem for
+/// dem.tif). Field names are truncated to the 10 character limit of dBase
+/// fields, and numbered if two rasters would otherwise share a name. If the
+/// input points already contain a field of the same name it is overwritten.
+/// MultiPoint records are sampled at their first point.
+pub struct ExtractRasterValuesAtPoints {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExtractRasterValuesAtPoints {
+    pub fn new() -> ExtractRasterValuesAtPoints { // public constructor
+        let name = "ExtractRasterValuesAtPoints".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description = "Extracts the values of one or more rasters at the locations of vector points.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Raster Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input raster files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input Vector Points File".to_owned(),
+            flags: vec!["--points".to_owned()],
+            description: "Input vector points file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector Points File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector points file, a copy of the input with the raster values appended as fields.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output CSV File".to_owned(),
+            flags: vec!["--out_csv".to_owned()],
+            description: "Output CSV table file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Sampling Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Sampling method; options are 'nearest' and 'bilinear'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["nearest".to_owned(), "bilinear".to_owned()]),
+            default_value: Some("nearest".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='dem.tif;slope.tif' --points=sites.shp -o=sites_values.shp
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif --points=sites.shp --out_csv=sites.csv --method=bilinear", short_exe, name).replace("*", &sep);
+
+        ExtractRasterValuesAtPoints {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for ExtractRasterValuesAtPoints {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_files = String::new();
+        let mut points_file = String::new();
+        let mut output_file = String::new();
+        let mut csv_file = String::new();
+        let mut bilinear = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-inputs" {
+                if keyval {
+                    input_files = vec[1].to_string();
+                } else {
+                    input_files = args[i+1].to_string();
+                }
+            } else if flag_val == "-points" {
+                if keyval {
+                    points_file = vec[1].to_string();
+                } else {
+                    points_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-out_csv" {
+                if keyval {
+                    csv_file = vec[1].to_string();
+                } else {
+                    csv_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-method" {
+                let method = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i+1].to_lowercase()
+                };
+                bilinear = method.contains("bilinear");
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !points_file.contains(&sep) {
+            points_file = format!("{}{}", working_directory, points_file);
+        }
+        if !output_file.is_empty() && !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !csv_file.is_empty() && !csv_file.contains(&sep) {
+            csv_file = format!("{}{}", working_directory, csv_file);
+        }
+        if output_file.is_empty() && csv_file.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "At least one of --output or --out_csv must be specified."));
+        }
+
+        let mut cmd = input_files.split(";");
+        let mut vec = cmd.collect::<Vec<&str>>();
+        if vec.len() == 1 {
+            cmd = input_files.split(",");
+            vec = cmd.collect::<Vec<&str>>();
+        }
+        let mut raster_files = vec![];
+        for value in vec {
+            if !value.trim().is_empty() {
+                let mut input_file = value.trim().to_owned();
+                if !input_file.contains(&sep) {
+                    input_file = format!("{}{}", working_directory, input_file);
+                }
+                raster_files.push(input_file);
+            }
+        }
+        if raster_files.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "At least one input raster must be specified."));
+        }
+
+        if verbose { println!("Reading data...") };
+        let points = Shapefile::new(&points_file, "r")?;
+
+        if points.header.shape_type.base_shape_type() != ShapeType::Point &&
+            points.header.shape_type.base_shape_type() != ShapeType::MultiPoint {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of point base shape type."));
+        }
+
+        let start = time::now();
+
+        let num_records = points.num_records;
+        let num_rasters = raster_files.len();
+        let mut locations: Vec<Option<(f64, f64)>> = Vec::with_capacity(num_records);
+        for record_num in 0..num_records {
+            let record = points.get_record(record_num);
+            if record.shape_type != ShapeType::Null && record.points.len() > 0 {
+                locations.push(Some((record.points[0].x, record.points[0].y)));
+            } else {
+                locations.push(None);
+            }
+        }
+
+        // results[raster][record]
+        let mut results: Vec<Vec<Option<f64>>> = Vec::with_capacity(num_rasters);
+        for (r, raster_file) in raster_files.iter().enumerate() {
+            let input = Raster::new(raster_file, "r")?;
+            let nodata = input.configs.nodata;
+            let mut raster_results = Vec::with_capacity(num_records);
+            for record_num in 0..num_records {
+                let value = match locations[record_num] {
+                    Some((x, y)) => {
+                        let z = if bilinear {
+                            input.get_value_bilinear(x, y)
+                        } else {
+                            input.get_value(input.get_row_from_y(y), input.get_column_from_x(x))
+                        };
+                        if z != nodata { Some(z) } else { None }
+                    },
+                    None => None,
+                };
+                raster_results.push(value);
+
+                if verbose {
+                    progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
+                    if progress != old_progress {
+                        println!("Raster {} of {}: {}%", r + 1, num_rasters, progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            results.push(raster_results);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        // the fields and CSV columns are named after the raster files
+        let stems: Vec<String> = raster_files.iter().map(|raster_file| {
+            match path::Path::new(raster_file).file_stem() {
+                Some(s) => s.to_string_lossy().to_string(),
+                None => raster_file.clone(),
+            }
+        }).collect();
+
+        if !output_file.is_empty() {
+            let mut output = Shapefile::initialize_using_file(&output_file, &points, points.header.shape_type, true)?;
+            for record_num in 0..num_records {
+                output.add_record(points.get_record(record_num).clone());
+                output.attributes.add_record(points.attributes.get_record(record_num),
+                                             points.attributes.is_deleted(record_num));
+            }
+            let mut field_names: Vec<String> = vec![];
+            for r in 0..num_rasters {
+                let field_name = get_prefixed_field_name(&stems[r], "", &field_names);
+                field_names.push(field_name.clone());
+                if verbose && field_name != stems[r] {
+                    println!("{} is written to the field {}", stems[r], field_name);
+                }
+                if output.attributes.get_field_num(&field_name).is_none() {
+                    output.attributes.add_field(&AttributeField::new(&field_name, FieldDataType::Real, 18u8, 6u8));
+                }
+                for record_num in 0..num_records {
+                    let value = match results[r][record_num] {
+                        Some(v) => FieldData::Real(v),
+                        None => FieldData::Null,
+                    };
+                    output.attributes.set_value(record_num, &field_name, value);
+                }
+            }
+
+            if verbose { println!("Saving data...") };
+            let _ = match output.write() {
+                Ok(_) => if verbose { println!("Output file written") },
+                Err(e) => return Err(e),
+            };
+        }
+
+        if !csv_file.is_empty() {
+            let f = File::create(&csv_file)?;
+            let mut writer = BufWriter::new(f);
+            let mut header = String::from("FID,X,Y");
+            for stem in &stems {
+                header.push_str(&format!(",{}", stem));
+            }
+            writer.write_all(format!("{}\n", header).as_bytes())?;
+            for record_num in 0..num_records {
+                let mut line = match locations[record_num] {
+                    Some((x, y)) => format!("{},{},{}", record_num + 1, x, y),
+                    None => format!("{},,", record_num + 1),
+                };
+                for r in 0..num_rasters {
+                    match results[r][record_num] {
+                        Some(v) => line.push_str(&format!(",{}", v)),
+                        None => line.push_str(","),
+                    }
+                }
+                writer.write_all(format!("{}\n", line).as_bytes())?;
+            }
+            let _ = writer.flush();
+            if verbose { println!("CSV file written") };
+        }
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExtractRasterValuesAtPoints;
+    use raster::*;
+    use std::env;
+    use std::fs;
+    use std::io::Read;
+    use tools::WhiteboxTool;
+    use vector::*;
+
+    #[test]
+    fn test_fields_and_columns_are_named_after_the_rasters() {
+        let dir = env::temp_dir().join("wbt_extract_raster_values_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let file = |name: &str| dir.join(name).to_str().unwrap().to_string();
+
+        // three 2 x 2 rasters, two of which share their first ten characters
+        let mut configs = RasterConfigs { ..Default::default() };
+        configs.rows = 2;
+        configs.columns = 2;
+        configs.north = 2.0;
+        configs.south = 0.0;
+        configs.east = 2.0;
+        configs.west = 0.0;
+        configs.resolution_x = 1.0;
+        configs.resolution_y = 1.0;
+        configs.data_type = DataType::F32;
+        let rasters = vec![("dem.dep", 1.0), ("elevation_2017.dep", 2.0), ("elevation_2018.dep", 3.0)];
+        for &(name, value) in &rasters {
+            let mut raster = Raster::initialize_using_config(&file(name), &configs);
+            for row in 0..2 {
+                for col in 0..2 {
+                    raster.set_value(row, col, value);
+                }
+            }
+            raster.write().unwrap();
+        }
+        let mut points = Shapefile::new(&file("points.shp"), "w").unwrap();
+        points.header.shape_type = ShapeType::Point;
+        points.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+        points.add_point_record(0.5, 1.5);
+        points.attributes.add_record(vec![FieldData::Int(1)], false);
+        points.write().unwrap();
+
+        let inputs: Vec<String> = rasters.iter().map(|&(name, _)| file(name)).collect();
+        let args = vec![format!("-i={}", inputs.join(";")),
+                        format!("--points={}", file("points.shp")),
+                        format!("-o={}", file("output.shp")),
+                        format!("--out_csv={}", file("output.csv"))];
+        ExtractRasterValuesAtPoints::new().run(args, "", false).unwrap();
+
+        let output = Shapefile::new(&file("output.shp"), "r").unwrap();
+        let mut csv = String::new();
+        fs::File::open(&file("output.csv")).unwrap().read_to_string(&mut csv).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<String> = output.attributes.fields.iter().map(|f| f.name.clone()).collect();
+        assert_eq!(names, vec!["FID", "dem", "elevation_", "elevation2"]);
+        assert_eq!(output.attributes.get_value(0, "elevation2"), FieldData::Real(3.0));
+        let header = csv.lines().next().unwrap();
+        assert_eq!(header, "FID,X,Y,dem,elevation_2017,elevation_2018");
+    }
+}
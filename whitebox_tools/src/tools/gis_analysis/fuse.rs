@@ -0,0 +1,307 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::f64;
+use vector::*;
+use algorithms::{is_clockwise_order, perpendicular_distance, point_in_poly, PolygonOverlay};
+use structures::{BoundingBox, Point2D};
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Fuses the polygons of a vector, and optionally those of an overlay vector,
+/// into the smallest set of non-overlapping polygons covering the same area.
+/// Features that overlap or share a boundary are merged, and one output
+/// feature is created for each connected region, along with its holes. Unlike
+/// the *Fuse* tool, the input attributes are not retained; the output carries
+/// only an FID and the number of input features (`NUM_FUSED`) in each region.
+/// Vertices that are within the snap tolerance (`--snap`) of one another are
+/// merged, which can be used to close small gaps and slivers between adjacent
+/// features. Z and M values are not retained.
+pub struct Fuse {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl Fuse {
+    pub fn new() -> Fuse { // public constructor
+        let name = "Fuse".to_string();
+        let toolbox = "GIS Analysis/Overlay Tools".to_string();
+        let description = "Merges the overlapping and adjacent polygons of one or two vector layers.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Vector Polygon File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input Overlay Polygon File".to_owned(),
+            flags: vec!["--overlay".to_owned()],
+            description: "Optional input overlay polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Snap Tolerance".to_owned(),
+            flags: vec!["--snap".to_owned()],
+            description: "Snap tolerance, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=parcels.shp -o=output.shp --snap=0.01", short_exe, name).replace("*", &sep);
+
+        Fuse {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for Fuse {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut overlay_file = String::new();
+        let mut output_file = String::new();
+        let mut snap_tolerance = 0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-overlay" {
+                if keyval {
+                    overlay_file = vec[1].to_string();
+                } else {
+                    overlay_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-snap" {
+                if keyval {
+                    snap_tolerance = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    snap_tolerance = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !overlay_file.is_empty() && !overlay_file.contains(&sep) {
+            overlay_file = format!("{}{}", working_directory, overlay_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading data...") };
+        let input = Shapefile::new(&input_file, "r")?;
+        let overlay_vector = if !overlay_file.is_empty() {
+            Some(Shapefile::new(&overlay_file, "r")?)
+        } else {
+            None
+        };
+
+        let start = time::now();
+
+        let shape_type = input.header.shape_type.base_shape_type();
+        if shape_type != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of polygon base shape type."));
+        }
+        if let Some(ref sf) = overlay_vector {
+            if sf.header.shape_type.base_shape_type() != ShapeType::Polygon {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "The overlay vector data must be of polygon base shape type."));
+            }
+        }
+
+        let get_parts = |sf: &Shapefile| -> Vec<Vec<Vec<Point2D>>> {
+            sf.records.iter().map(|record| {
+                (0..record.num_parts as usize).map(|part| record.get_part(part).to_vec()).collect()
+            }).collect()
+        };
+        let mut features = get_parts(&input);
+        if let Some(ref sf) = overlay_vector {
+            features.extend(get_parts(sf));
+        }
+
+        if verbose { println!("Performing overlay...") };
+        let overlay = PolygonOverlay::new(&[&features], snap_tolerance);
+        let covered: Vec<usize> = (0..overlay.num_faces()).filter(|&f| !overlay.get_face_features(f, 0).is_empty()).collect();
+        // the dissolved rings are ordered hull first, followed by its holes
+        let mut regions: Vec<Vec<Vec<Point2D>>> = vec![];
+        for ring in overlay.dissolve(&covered) {
+            if is_clockwise_order(&ring) || regions.is_empty() {
+                regions.push(vec![ring]);
+            } else {
+                let n = regions.len();
+                regions[n - 1].push(ring);
+            }
+        }
+
+        // count the features with a vertex in each region; every feature
+        // lies entirely within one region
+        let mut num_fused = vec![0i32; regions.len()];
+        let region_extents: Vec<BoundingBox> = regions.iter().map(|r| BoundingBox::from_points(&r[0]).buffer(snap_tolerance)).collect();
+        for feature in &features {
+            let p = match feature.iter().filter(|ring| !ring.is_empty()).next() {
+                Some(ring) => ring[0],
+                None => continue,
+            };
+            // a vertex on a region boundary may be snapped, so the nearest region is found
+            let mut which = None;
+            let mut min_dist = f64::INFINITY;
+            for r in 0..regions.len() {
+                if !region_extents[r].contains_point(p.x, p.y) {
+                    continue;
+                }
+                let dist = region_distance(&regions[r], &p);
+                if dist < min_dist {
+                    min_dist = dist;
+                    which = Some(r);
+                }
+            }
+            if let Some(r) = which {
+                num_fused[r] += 1;
+            }
+        }
+
+        let mut output = Shapefile::initialize_using_file(&output_file, &input, shape_type, false)?;
+        output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("NUM_FUSED", FieldDataType::Int, 10u8, 0u8));
+        for r in 0..regions.len() {
+            let mut geom = ShapefileGeometry::new(shape_type);
+            for part in &regions[r] {
+                geom.add_part(part);
+            }
+            output.add_record(geom);
+            output.attributes.add_record(vec![FieldData::Int(r as i32 + 1), FieldData::Int(num_fused[r])], false);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+// The distance from a point to a region, zero if the point is within it.
+fn region_distance(rings: &[Vec<Point2D>], p: &Point2D) -> f64 {
+    let mut inside = false;
+    let mut min_dist = f64::INFINITY;
+    for ring in rings {
+        if point_in_poly(p, ring) {
+            inside = !inside;
+        }
+        for k in 0..ring.len() - 1 {
+            let dist = perpendicular_distance(p, &ring[k], &ring[k + 1]);
+            if dist < min_dist {
+                min_dist = dist;
+            }
+        }
+    }
+    if inside { 0f64 } else { min_dist }
+}
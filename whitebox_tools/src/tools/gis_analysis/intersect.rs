@@ -0,0 +1,275 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::collections::BTreeMap;
+use vector::*;
+use algorithms::{overlay_lines_on_polygons, PolygonOverlay};
+use structures::Point2D;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Identifies the parts of the features of an input vector (lines or
+/// polygons) that overlap the polygons of an overlay vector. One output
+/// feature is created for each overlapping pair of input and overlay features,
+/// carrying the attributes of both; field names of the overlay that are
+/// already used by the input are given a numeric suffix.
+/// Vertices of the two layers that are within the snap tolerance (`--snap`)
+/// of one another are merged. Z and M values are not retained.
+pub struct Intersect {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl Intersect {
+    pub fn new() -> Intersect { // public constructor
+        let name = "Intersect".to_string();
+        let toolbox = "GIS Analysis/Overlay Tools".to_string();
+        let description = "Identifies the parts of features in common between two input vector layers.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector lines or polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input Overlay Polygon File".to_owned(),
+            flags: vec!["--overlay".to_owned()],
+            description: "Input overlay polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Snap Tolerance".to_owned(),
+            flags: vec!["--snap".to_owned()],
+            description: "Snap tolerance, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=roads.shp --overlay=wetlands.shp -o=output.shp", short_exe, name).replace("*", &sep);
+
+        Intersect {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for Intersect {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut overlay_file = String::new();
+        let mut output_file = String::new();
+        let mut snap_tolerance = 0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-overlay" {
+                if keyval {
+                    overlay_file = vec[1].to_string();
+                } else {
+                    overlay_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-snap" {
+                if keyval {
+                    snap_tolerance = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    snap_tolerance = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !overlay_file.contains(&sep) {
+            overlay_file = format!("{}{}", working_directory, overlay_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading data...") };
+        let input = Shapefile::new(&input_file, "r")?;
+        let overlay_vector = Shapefile::new(&overlay_file, "r")?;
+
+        let start = time::now();
+
+        let shape_type = input.header.shape_type.base_shape_type();
+        if shape_type != ShapeType::Polygon && shape_type != ShapeType::PolyLine {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of polyline or polygon base shape type."));
+        }
+        if overlay_vector.header.shape_type.base_shape_type() != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The overlay vector data must be of polygon base shape type."));
+        }
+
+        let get_parts = |sf: &Shapefile| -> Vec<Vec<Vec<Point2D>>> {
+            sf.records.iter().map(|record| {
+                (0..record.num_parts as usize).map(|part| record.get_part(part).to_vec()).collect()
+            }).collect()
+        };
+        let input_features = get_parts(&input);
+        let overlay_features = get_parts(&overlay_vector);
+
+        if verbose { println!("Performing overlay...") };
+        // the parts of each intersecting pair of input and overlay features
+        let mut output_parts: BTreeMap<(usize, usize), Vec<Vec<Point2D>>> = BTreeMap::new();
+        if shape_type == ShapeType::Polygon {
+            let overlay = PolygonOverlay::new(&[&input_features, &overlay_features], snap_tolerance);
+            let mut pair_faces: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+            for face in 0..overlay.num_faces() {
+                for &i in overlay.get_face_features(face, 0) {
+                    for &j in overlay.get_face_features(face, 1) {
+                        pair_faces.entry((i, j)).or_insert(vec![]).push(face);
+                    }
+                }
+            }
+            for (pair, faces) in pair_faces {
+                let rings = overlay.dissolve(&faces);
+                if !rings.is_empty() {
+                    output_parts.insert(pair, rings);
+                }
+            }
+        } else {
+            for piece in overlay_lines_on_polygons(&input_features, &overlay_features, snap_tolerance) {
+                for &j in &piece.polygons {
+                    output_parts.entry((piece.feature, j)).or_insert(vec![]).push(piece.points.clone());
+                }
+            }
+        }
+
+        let mut output = Shapefile::initialize_using_file(&output_file, &input, shape_type, false)?;
+        output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+        // the input and overlay fields follow, renamed where a name is already in use
+        for field in input.attributes.fields.iter().chain(overlay_vector.attributes.fields.iter()) {
+            let mut new_field = field.clone();
+            new_field.name = output.attributes.get_unique_field_name(&field.name);
+            output.attributes.add_field(&new_field);
+        }
+        let mut fid = 0i32;
+        for ((i, j), parts) in output_parts {
+            let mut geom = ShapefileGeometry::new(shape_type);
+            for part in &parts {
+                geom.add_part(part);
+            }
+            output.add_record(geom);
+            fid += 1;
+            let mut record = vec![FieldData::Int(fid)];
+            record.extend(input.attributes.get_record(i));
+            record.extend(overlay_vector.attributes.get_record(j));
+            output.attributes.add_record(record, false);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
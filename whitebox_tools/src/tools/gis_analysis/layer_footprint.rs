@@ -0,0 +1,260 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use raster::*;
+use vector::*;
+use algorithms::{polygon_area, signed_polygon_area, trace_region_boundaries};
+use structures::{Array2D, Point2D};
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Creates a vector polygon footprint of the area covered by a raster or
+/// vector layer. The footprint of a raster is the outline of its valid data,
+/// i.e. the cells that are not NoData, such that NoData collars around the
+/// edges of a scene or map sheet are excluded; it follows the cell edges and
+/// may consist of several parts and contain holes. The output attribute table
+/// contains the number of valid cells (NUM_CELLS) and the footprint area
+/// (AREA). The footprint of a vector layer is the rectangle of its extent, and
+/// its attribute table contains the number of features (NUM_FEAT) and the area.
+///
+/// See also *MinimumConvexHull*, *MinimumBoundingBox* and *LidarFootprint*.
+pub struct LayerFootprint {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LayerFootprint {
+    pub fn new() -> LayerFootprint { // public constructor
+        let name = "LayerFootprint".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description = "Creates a vector polygon footprint of the area covered by a raster or vector layer.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster or vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polygons file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=scene.tif -o=footprint.shp", short_exe, name).replace("*", &sep);
+
+        LayerFootprint {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for LayerFootprint {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let start;
+        let mut output: Shapefile;
+        if get_vector_type_from_file(&input_file) != VectorType::Unknown {
+            if verbose { println!("Reading data...") };
+            let input = Shapefile::new(&input_file, "r")?;
+
+            start = time::now();
+
+            let (west, south) = (input.header.x_min, input.header.y_min);
+            let (east, north) = (input.header.x_max, input.header.y_max);
+            let extent = vec![Point2D::new(west, north),
+                              Point2D::new(east, north),
+                              Point2D::new(east, south),
+                              Point2D::new(west, south),
+                              Point2D::new(west, north)];
+            output = Shapefile::initialize_using_file(&output_file, &input, ShapeType::Polygon, false)?;
+            output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+            output.attributes.add_field(&AttributeField::new("NUM_FEAT", FieldDataType::Int, 10u8, 0u8));
+            output.attributes.add_field(&AttributeField::new("AREA", FieldDataType::Real, 18u8, 4u8));
+            let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+            geom.add_part(&extent);
+            output.add_record(geom);
+            output.attributes.add_record(vec![FieldData::Int(1),
+                                              FieldData::Int(input.num_records as i32),
+                                              FieldData::Real(polygon_area(&extent))], false);
+        } else {
+            if verbose { println!("Reading data...") };
+            let input = Raster::new(&input_file, "r")?;
+
+            start = time::now();
+
+            let rows = input.configs.rows as isize;
+            let columns = input.configs.columns as isize;
+            let nodata = input.configs.nodata;
+            let mut labels: Array2D<i32> = Array2D::new(rows, columns, -1, -1)?;
+            let mut num_cells = 0usize;
+            for row in 0..rows {
+                for col in 0..columns {
+                    if input.get_value(row, col) != nodata {
+                        labels.set_value(row, col, 0);
+                        num_cells += 1;
+                    }
+                }
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                    if progress != old_progress {
+                        println!("Finding valid cells: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            if num_cells == 0 {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "The input raster does not contain any valid (non-NoData) cells."));
+            }
+
+            if verbose { println!("Tracing footprint...") };
+            let rings = trace_region_boundaries(&labels,
+                                                1,
+                                                input.configs.west,
+                                                input.configs.north,
+                                                input.configs.resolution_x,
+                                                input.configs.resolution_y)
+                                                .pop()
+                                                .unwrap();
+
+            output = Shapefile::new(&output_file, "w")?;
+            output.header.shape_type = ShapeType::Polygon;
+            if input.configs.coordinate_ref_system_wkt.to_lowercase() != "not specified" {
+                output.projection = input.configs.coordinate_ref_system_wkt.clone();
+            }
+            output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+            output.attributes.add_field(&AttributeField::new("NUM_CELLS", FieldDataType::Int, 10u8, 0u8));
+            output.attributes.add_field(&AttributeField::new("AREA", FieldDataType::Real, 18u8, 4u8));
+            let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+            let mut area = 0f64;
+            for ring in &rings {
+                // hulls are clockwise, with a negative signed area
+                area -= signed_polygon_area(ring);
+                geom.add_part(ring);
+            }
+            output.add_record(geom);
+            output.attributes.add_record(vec![FieldData::Int(1),
+                                              FieldData::Int(num_cells as i32),
+                                              FieldData::Real(area)], false);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,287 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use vector::*;
+use algorithms::{minimum_bounding_box, MinimizationCriterion};
+use structures::Point2D;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Creates the minimum bounding box of a vector layer, i.e. the smallest
+/// rectangle, in any orientation, that contains all of the vertices of its
+/// points, lines or polygons. The box may be chosen to minimize its area,
+/// perimeter, length (long side) or width (short side) (`--criterion`). When
+/// `--features` is specified, a box is created for each individual feature
+/// instead, and the feature's attributes are copied to the output; features
+/// whose vertices are all collinear have no box and are left out. The output
+/// attribute table also contains the length (LENGTH), width (WIDTH) and area
+/// (AREA) of each box, along with the orientation of its long axis (ORIENT),
+/// in degrees clockwise from north (0-180).
+pub struct MinimumBoundingBox {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl MinimumBoundingBox {
+    pub fn new() -> MinimumBoundingBox { // public constructor
+        let name = "MinimumBoundingBox".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description = "Creates the minimum bounding box of a vector layer or of each of its features.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector points, lines or polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polygons file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Minimization Criterion".to_owned(),
+            flags: vec!["--criterion".to_owned()],
+            description: "Property of the box to minimize; options include 'area' (default), 'perimeter', 'length', and 'width'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["area".to_owned(), "perimeter".to_owned(), "length".to_owned(), "width".to_owned()]),
+            default_value: Some("area".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Find boxes around individual features?".to_owned(),
+            flags: vec!["--features".to_owned()],
+            description: "Find the boxes of individual features rather than the entire layer?".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=buildings.shp -o=extent.shp
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=buildings.shp -o=boxes.shp --criterion=width --features", short_exe, name).replace("*", &sep);
+
+        MinimumBoundingBox {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for MinimumBoundingBox {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut criterion_str = String::from("area");
+        let mut individual_features = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-criterion" {
+                if keyval {
+                    criterion_str = vec[1].to_lowercase();
+                } else {
+                    criterion_str = args[i+1].to_lowercase();
+                }
+            } else if flag_val == "-features" {
+                individual_features = true;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let criterion = match criterion_str.as_ref() {
+            "area" => MinimizationCriterion::Area,
+            "perimeter" => MinimizationCriterion::Perimeter,
+            "length" => MinimizationCriterion::Length,
+            "width" => MinimizationCriterion::Width,
+            _ => return Err(Error::new(ErrorKind::InvalidInput,
+                    "The criterion must be one of 'area', 'perimeter', 'length', or 'width'.")),
+        };
+
+        if verbose { println!("Reading data...") };
+        let input = Shapefile::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        let mut output = Shapefile::initialize_using_file(&output_file, &input, ShapeType::Polygon, individual_features)?;
+        let field_names: Vec<String>;
+        if individual_features {
+            field_names = ["LENGTH", "WIDTH", "AREA", "ORIENT"].iter().map(|f| output.attributes.get_unique_field_name(f)).collect();
+        } else {
+            field_names = ["LENGTH", "WIDTH", "AREA", "ORIENT"].iter().map(|f| f.to_string()).collect();
+            output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+        }
+        for field_name in &field_names {
+            output.attributes.add_field(&AttributeField::new(field_name, FieldDataType::Real, 18u8, 4u8));
+        }
+
+        if individual_features {
+            for record_num in 0..input.num_records {
+                if let Some(bounding_box) = minimum_bounding_box(&input.get_record(record_num).points, criterion) {
+                    let mut atts = input.attributes.get_record(record_num);
+                    atts.extend(box_attributes(&bounding_box));
+                    let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+                    geom.add_part(&bounding_box);
+                    output.add_record(geom);
+                    output.attributes.add_record(atts, false);
+                }
+                if verbose {
+                    progress = (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                    if progress != old_progress {
+                        println!("Progress: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        } else {
+            let mut points: Vec<Point2D> = vec![];
+            for record_num in 0..input.num_records {
+                points.extend(input.get_record(record_num).points.iter().cloned());
+            }
+            let bounding_box = match minimum_bounding_box(&points, criterion) {
+                Some(b) => b,
+                None => return Err(Error::new(ErrorKind::InvalidInput,
+                    "The box could not be created; at least three non-collinear points are required.")),
+            };
+            let mut atts = vec![FieldData::Int(1)];
+            atts.extend(box_attributes(&bounding_box));
+            let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+            geom.add_part(&bounding_box);
+            output.add_record(geom);
+            output.attributes.add_record(atts, false);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+/// The length, width, area and long-axis orientation of a box.
+fn box_attributes(bounding_box: &[Point2D]) -> Vec<FieldData> {
+    let side1 = bounding_box[1] - bounding_box[0];
+    let side2 = bounding_box[2] - bounding_box[1];
+    let length1 = (side1.x * side1.x + side1.y * side1.y).sqrt();
+    let length2 = (side2.x * side2.x + side2.y * side2.y).sqrt();
+    let (length, width, axis) = if length1 >= length2 {
+        (length1, length2, side1)
+    } else {
+        (length2, length1, side2)
+    };
+    let mut orientation = axis.x.atan2(axis.y).to_degrees();
+    if orientation < 0f64 {
+        orientation += 180f64;
+    }
+    if orientation >= 180f64 {
+        orientation -= 180f64;
+    }
+    vec![FieldData::Real(length),
+         FieldData::Real(width),
+         FieldData::Real(length * width),
+         FieldData::Real(orientation)]
+}
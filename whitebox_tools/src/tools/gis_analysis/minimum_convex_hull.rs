@@ -0,0 +1,242 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use vector::*;
+use algorithms::{convex_hull, polygon_area, polyline_length};
+use structures::Point2D;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Creates the convex hull of a vector layer, i.e. the smallest convex polygon
+/// that contains all of the vertices of its points, lines or polygons. When
+/// `--features` is specified, a hull is created for each individual feature
+/// instead, and the feature's attributes are copied to the output; features
+/// whose vertices are all collinear have no hull and are left out. The output
+/// attribute table also contains the area (AREA) and perimeter (PERIMETER) of
+/// each hull, and, for a whole-layer hull, the number of vertices within it
+/// (NUM_PNTS).
+pub struct MinimumConvexHull {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl MinimumConvexHull {
+    pub fn new() -> MinimumConvexHull { // public constructor
+        let name = "MinimumConvexHull".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description = "Creates the convex hull of a vector layer or of each of its features.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector points, lines or polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polygons file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Find hulls around individual features?".to_owned(),
+            flags: vec!["--features".to_owned()],
+            description: "Find the hulls of individual features rather than the entire layer?".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=sightings.shp -o=range.shp
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=lakes.shp -o=hulls.shp --features", short_exe, name).replace("*", &sep);
+
+        MinimumConvexHull {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for MinimumConvexHull {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut individual_features = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-features" {
+                individual_features = true;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading data...") };
+        let input = Shapefile::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        let mut output = Shapefile::initialize_using_file(&output_file, &input, ShapeType::Polygon, individual_features)?;
+        let area_field;
+        let perimeter_field;
+        if individual_features {
+            area_field = output.attributes.get_unique_field_name("AREA");
+            perimeter_field = output.attributes.get_unique_field_name("PERIMETER");
+        } else {
+            area_field = "AREA".to_string();
+            perimeter_field = "PERIMETER".to_string();
+            output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+            output.attributes.add_field(&AttributeField::new("NUM_PNTS", FieldDataType::Int, 10u8, 0u8));
+        }
+        output.attributes.add_field(&AttributeField::new(&area_field, FieldDataType::Real, 18u8, 4u8));
+        output.attributes.add_field(&AttributeField::new(&perimeter_field, FieldDataType::Real, 18u8, 4u8));
+
+        if individual_features {
+            for record_num in 0..input.num_records {
+                let hull = convex_hull(&input.get_record(record_num).points);
+                if hull.len() >= 4 {
+                    let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+                    geom.add_part(&hull);
+                    output.add_record(geom);
+                    let mut atts = input.attributes.get_record(record_num);
+                    atts.push(FieldData::Real(polygon_area(&hull)));
+                    atts.push(FieldData::Real(polyline_length(&hull)));
+                    output.attributes.add_record(atts, false);
+                }
+                if verbose {
+                    progress = (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                    if progress != old_progress {
+                        println!("Progress: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        } else {
+            let mut points: Vec<Point2D> = vec![];
+            for record_num in 0..input.num_records {
+                points.extend(input.get_record(record_num).points.iter().cloned());
+            }
+            let hull = convex_hull(&points);
+            if hull.len() < 4 {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "The hull could not be created; at least three non-collinear points are required."));
+            }
+            let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+            geom.add_part(&hull);
+            output.add_record(geom);
+            output.attributes.add_record(vec![FieldData::Int(1),
+                                              FieldData::Int(points.len() as i32),
+                                              FieldData::Real(polygon_area(&hull)),
+                                              FieldData::Real(polyline_length(&hull))], false);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
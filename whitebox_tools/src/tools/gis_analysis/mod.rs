@@ -2,22 +2,35 @@
 mod aggregate_raster;
 mod average_overlay;
 mod buffer_raster;
+mod buffer_vector;
 mod centroid;
+mod clip;
+mod clip_raster_to_extent;
+mod clip_raster_to_polygon;
 mod clump;
 mod cost_allocation;
 mod cost_distance;
 mod cost_pathway;
 mod create_plane;
+mod difference;
 mod edge_proportion;
+mod erase_polygon_from_raster;
 mod euclidean_allocation;
 mod euclidean_distance;
+mod extract_by_attribute;
+mod extract_raster_values_at_points;
 mod find_patch_edge_cells;
+mod fuse;
 mod highest_pos;
+mod intersect;
+mod layer_footprint;
 mod lowest_pos;
 mod max_abs_overlay;
 mod max_overlay;
 mod min_abs_overlay;
 mod min_overlay;
+mod minimum_bounding_box;
+mod minimum_convex_hull;
 mod percent_equal_to;
 mod percent_greater_than;
 mod percent_less_than;
@@ -27,28 +40,45 @@ mod raster_cell_assignment;
 mod reclass;
 mod reclass_equal_interval;
 mod reclass_from_file;
+mod simplify_line_or_polygon;
+mod smooth_vectors;
+mod symmetric_difference;
+mod union;
 mod weighted_sum;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::aggregate_raster::AggregateRaster;
 pub use self::average_overlay::AverageOverlay;
 pub use self::buffer_raster::BufferRaster;
+pub use self::buffer_vector::BufferVector;
 pub use self::centroid::Centroid;
+pub use self::clip::Clip;
+pub use self::clip_raster_to_extent::ClipRasterToExtent;
+pub use self::clip_raster_to_polygon::ClipRasterToPolygon;
 pub use self::clump::Clump;
 pub use self::cost_allocation::CostAllocation;
 pub use self::cost_distance::CostDistance;
 pub use self::cost_pathway::CostPathway;
 pub use self::create_plane::CreatePlane;
+pub use self::difference::Difference;
 pub use self::edge_proportion::EdgeProportion;
+pub use self::erase_polygon_from_raster::ErasePolygonFromRaster;
 pub use self::euclidean_allocation::EuclideanAllocation;
 pub use self::euclidean_distance::EuclideanDistance;
+pub use self::extract_by_attribute::ExtractByAttribute;
+pub use self::extract_raster_values_at_points::ExtractRasterValuesAtPoints;
 pub use self::find_patch_edge_cells::FindPatchOrClassEdgeCells;
+pub use self::fuse::Fuse;
 pub use self::highest_pos::HighestPosition;
+pub use self::intersect::Intersect;
+pub use self::layer_footprint::LayerFootprint;
 pub use self::lowest_pos::LowestPosition;
 pub use self::max_abs_overlay::MaxAbsoluteOverlay;
 pub use self::max_overlay::MaxOverlay;
 pub use self::min_abs_overlay::MinAbsoluteOverlay;
 pub use self::min_overlay::MinOverlay;
+pub use self::minimum_bounding_box::MinimumBoundingBox;
+pub use self::minimum_convex_hull::MinimumConvexHull;
 pub use self::percent_equal_to::PercentEqualTo;
 pub use self::percent_greater_than::PercentGreaterThan;
 pub use self::percent_less_than::PercentLessThan;
@@ -58,4 +88,8 @@ pub use self::raster_cell_assignment::RasterCellAssignment;
 pub use self::reclass::Reclass;
 pub use self::reclass_equal_interval::ReclassEqualInterval;
 pub use self::reclass_from_file::ReclassFromFile;
+pub use self::simplify_line_or_polygon::SimplifyLineOrPolygon;
+pub use self::smooth_vectors::SmoothVectors;
+pub use self::symmetric_difference::SymmetricDifference;
+pub use self::union::Union;
 pub use self::weighted_sum::WeightedSum;
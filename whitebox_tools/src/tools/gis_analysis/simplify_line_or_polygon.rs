@@ -0,0 +1,273 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use vector::*;
+use std::f64;
+use algorithms::{douglas_peucker, visvalingam_whyatt, ArcTopology};
+use structures::Point2D;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Reduces the number of vertices in vector lines or polygons, such as the
+/// dense, stair-stepped boundaries produced by raster-to-vector conversion.
+/// Two methods are available (`--method`). The Douglas-Peucker method retains
+/// the vertices needed to keep the simplified line within a distance
+/// (`--tolerance`, in map units) of the original, while the Visvalingam-Whyatt
+/// method repeatedly removes the vertex forming the smallest triangle with its
+/// neighbours until every remaining triangle has an area of at least
+/// `--tolerance` (in squared map units). Visvalingam-Whyatt tends to give
+/// smoother, more natural-looking results.
+///
+/// Simplification preserves topology. The lines are split into arcs where
+/// they meet one another and each arc is simplified once, with its end points
+/// fixed, so that the boundaries shared by adjacent polygons remain coincident,
+/// lines that join remain joined, and no gaps or slivers are introduced.
+/// Vertices are considered shared only where their coordinates are identical.
+/// Polygon rings that would collapse are left unsimplified. Attributes are
+/// copied to the output; z and m values are not retained.
+pub struct SimplifyLineOrPolygon {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SimplifyLineOrPolygon {
+    pub fn new() -> SimplifyLineOrPolygon { // public constructor
+        let name = "SimplifyLineOrPolygon".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description = "Simplifies vector lines or polygons while preserving their topology.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector lines or polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Simplification Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Simplification method; options include 'douglas_peucker' (default) and 'visvalingam'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["douglas_peucker".to_owned(), "visvalingam".to_owned()]),
+            default_value: Some("douglas_peucker".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Tolerance".to_owned(),
+            flags: vec!["--tolerance".to_owned()],
+            description: "Distance tolerance (Douglas-Peucker), in map units, or minimum triangle area (Visvalingam-Whyatt), in squared map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: false
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=landcover.shp -o=simplified.shp --tolerance=5.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=contours.shp -o=simplified.shp --method=visvalingam --tolerance=25.0", short_exe, name).replace("*", &sep);
+
+        SimplifyLineOrPolygon {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for SimplifyLineOrPolygon {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut method = String::from("douglas_peucker");
+        let mut tolerance = f64::NAN;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-method" {
+                if keyval {
+                    method = vec[1].to_lowercase();
+                } else {
+                    method = args[i+1].to_lowercase();
+                }
+            } else if flag_val == "-tolerance" {
+                if keyval {
+                    tolerance = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    tolerance = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if tolerance.is_nan() || tolerance <= 0f64 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "A positive simplification tolerance must be specified."));
+        }
+        let use_visvalingam = if method.starts_with("vis") {
+            true
+        } else if method.starts_with("douglas") || method == "dp" {
+            false
+        } else {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The method must be either 'douglas_peucker' or 'visvalingam'."));
+        };
+
+        if verbose { println!("Reading data...") };
+        let input = Shapefile::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        let shape_type = input.header.shape_type.base_shape_type();
+        if shape_type != ShapeType::PolyLine && shape_type != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of polyline or polygon base shape type."));
+        }
+
+        // gather the parts of all features, so that shared boundaries are found
+        let mut parts: Vec<Vec<Point2D>> = vec![];
+        let mut first_part = Vec::with_capacity(input.num_records + 1);
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            first_part.push(parts.len());
+            for part in 0..record.num_parts as usize {
+                parts.push(record.get_part(part).to_vec());
+            }
+        }
+        first_part.push(parts.len());
+
+        if verbose { println!("Building topology...") };
+        let topology = ArcTopology::new(&parts);
+        if verbose { println!("Simplifying {} arcs...", topology.arcs.len()) };
+        let simplified = if use_visvalingam {
+            topology.generalize(|arc| visvalingam_whyatt(arc, tolerance))
+        } else {
+            topology.generalize(|arc| douglas_peucker(arc, tolerance))
+        };
+
+        let mut output = Shapefile::initialize_using_file(&output_file, &input, shape_type, true)?;
+        let (mut num_vertices_in, mut num_vertices_out) = (0usize, 0usize);
+        for record_num in 0..input.num_records {
+            let mut geom = ShapefileGeometry::new(shape_type);
+            for part in first_part[record_num]..first_part[record_num + 1] {
+                num_vertices_in += parts[part].len();
+                // a part without two distinct points has no arcs
+                let points = if simplified[part].len() > 1 { &simplified[part] } else { &parts[part] };
+                num_vertices_out += points.len();
+                geom.add_part(points);
+            }
+            output.add_record(geom);
+            output.attributes.add_record(input.attributes.get_record(record_num), false);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose {
+            println!("Vertices reduced from {} to {}", num_vertices_in, num_vertices_out);
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
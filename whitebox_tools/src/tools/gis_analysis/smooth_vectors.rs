@@ -0,0 +1,279 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use vector::*;
+use algorithms::{chaikin_smoothing, moving_average_smoothing, ArcTopology};
+use structures::Point2D;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Smooths vector lines or polygons, such as the stair-stepped boundaries
+/// produced by raster-to-vector conversion. Two methods are available
+/// (`--method`). Chaikin's corner-cutting method replaces each vertex with two
+/// new vertices a quarter of the way along the adjoining segments, doubling
+/// the number of vertices with each of the `--iterations`, and converges on a
+/// smooth curve. The moving-average method replaces each vertex with the mean
+/// position of the `--filter` vertices centred on it, without adding vertices,
+/// and is applied `--iterations` times.
+///
+/// As with *SimplifyLineOrPolygon*, the lines are split into arcs where they
+/// meet one another and each arc is smoothed once with its end points fixed,
+/// so that the boundaries shared by adjacent polygons remain coincident and
+/// lines that join remain joined. Attributes are copied to the output; z and m
+/// values are not retained.
+pub struct SmoothVectors {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SmoothVectors {
+    pub fn new() -> SmoothVectors { // public constructor
+        let name = "SmoothVectors".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description = "Smooths vector lines or polygons while preserving their topology.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector lines or polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Smoothing Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Smoothing method; options include 'chaikin' (default) and 'moving_average'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["chaikin".to_owned(), "moving_average".to_owned()]),
+            default_value: Some("chaikin".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Iterations".to_owned(),
+            flags: vec!["--iterations".to_owned()],
+            description: "Number of smoothing iterations.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Filter Size".to_owned(),
+            flags: vec!["--filter".to_owned()],
+            description: "Size of the moving-average window, in vertices; an odd number.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("5".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=landcover.shp -o=smoothed.shp --iterations=2
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=contours.shp -o=smoothed.shp --method=moving_average --filter=7", short_exe, name).replace("*", &sep);
+
+        SmoothVectors {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for SmoothVectors {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut method = String::from("chaikin");
+        let mut iterations = 3usize;
+        let mut filter_size = 5usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-method" {
+                if keyval {
+                    method = vec[1].to_lowercase();
+                } else {
+                    method = args[i+1].to_lowercase();
+                }
+            } else if flag_val == "-iterations" {
+                if keyval {
+                    iterations = vec[1].to_string().parse::<usize>().unwrap();
+                } else {
+                    iterations = args[i+1].to_string().parse::<usize>().unwrap();
+                }
+            } else if flag_val == "-filter" {
+                if keyval {
+                    filter_size = vec[1].to_string().parse::<usize>().unwrap();
+                } else {
+                    filter_size = args[i+1].to_string().parse::<usize>().unwrap();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let use_moving_average = if method.starts_with("moving") || method == "ma" {
+            true
+        } else if method == "chaikin" {
+            false
+        } else {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The method must be either 'chaikin' or 'moving_average'."));
+        };
+        if use_moving_average && filter_size < 3 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The filter size must be at least 3 vertices."));
+        }
+
+        if verbose { println!("Reading data...") };
+        let input = Shapefile::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        let shape_type = input.header.shape_type.base_shape_type();
+        if shape_type != ShapeType::PolyLine && shape_type != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of polyline or polygon base shape type."));
+        }
+
+        // gather the parts of all features, so that shared boundaries are found
+        let mut parts: Vec<Vec<Point2D>> = vec![];
+        let mut first_part = Vec::with_capacity(input.num_records + 1);
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            first_part.push(parts.len());
+            for part in 0..record.num_parts as usize {
+                parts.push(record.get_part(part).to_vec());
+            }
+        }
+        first_part.push(parts.len());
+
+        if verbose { println!("Building topology...") };
+        let topology = ArcTopology::new(&parts);
+        if verbose { println!("Smoothing {} arcs...", topology.arcs.len()) };
+        let smoothed = if use_moving_average {
+            topology.generalize(|arc| moving_average_smoothing(arc, filter_size, iterations))
+        } else {
+            topology.generalize(|arc| chaikin_smoothing(arc, iterations))
+        };
+
+        let mut output = Shapefile::initialize_using_file(&output_file, &input, shape_type, true)?;
+        for record_num in 0..input.num_records {
+            let mut geom = ShapefileGeometry::new(shape_type);
+            for part in first_part[record_num]..first_part[record_num + 1] {
+                // a part without two distinct points has no arcs
+                let points = if smoothed[part].len() > 1 { &smoothed[part] } else { &parts[part] };
+                geom.add_part(points);
+            }
+            output.add_record(geom);
+            output.attributes.add_record(input.attributes.get_record(record_num), false);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::io::{Error, ErrorKind};
+use lidar::*;
+use vector::*;
+use algorithms::point_in_poly;
+use structures::{BoundingBox, Point2D, RTree};
+use tools::*;
+
+/// Clips the points of a LiDAR (LAS) file to the polygons of a vector file,
+/// i.e. the output contains the points that lie within any of the polygons,
+/// but not within their holes. If the input file has a spatial index (see
+/// *LidarIndex*), only the parts of the file near the polygons are read, which
+/// makes clipping a small area from a large file much quicker.
+pub struct ClipLidarToPolygon {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ClipLidarToPolygon {
+    pub fn new() -> ClipLidarToPolygon { // public constructor
+        let name = "ClipLidarToPolygon".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Clips a LiDAR point cloud to a vector polygon or polygons.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input Vector Polygon File".to_owned(),
+            flags: vec!["--polygons".to_owned()],
+            description: "Input vector polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output LiDAR file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=lidar.las --polygons=lakes.shp -o=output.las", short_exe, name).replace("*", &sep);
+
+        ClipLidarToPolygon {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for ClipLidarToPolygon {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut polygons_file = String::new();
+        let mut output_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-polygons" {
+                if keyval {
+                    polygons_file = vec[1].to_string();
+                } else {
+                    polygons_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !polygons_file.contains(&sep) {
+            polygons_file = format!("{}{}", working_directory, polygons_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let start = time::now();
+
+        let polygons = Shapefile::new(&polygons_file, "r")?;
+        if polygons.header.shape_type.base_shape_type() != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of polygon base shape type."));
+        }
+
+        let mut rings: Vec<Vec<Vec<Point2D>>> = vec![];
+        let mut bounds: Vec<BoundingBox> = vec![];
+        let mut extent = BoundingBox::default();
+        for record_num in 0..polygons.num_records {
+            let record = polygons.get_record(record_num);
+            if record.shape_type == ShapeType::Null || record.num_points == 0 {
+                continue;
+            }
+            rings.push((0..record.num_parts as usize).map(|part| record.get_part(part).to_vec()).collect());
+            bounds.push(record.get_bounding_box());
+            extent.expand_to(&record.get_bounding_box());
+        }
+        if rings.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "The input vector file does not contain any polygons."));
+        }
+
+        // the polygons whose envelopes contain each point are found using an R-tree
+        let tree = RTree::bulk_load(bounds.into_iter().zip(0..rings.len()).collect());
+
+        if verbose { println!("Reading points within the polygons..."); }
+        // a point is within a polygon if it is within an odd number of its rings
+        let input = LasFile::read_within(&input_file, &extent, |x, y| {
+            let p = Point2D::new(x, y);
+            tree.search_point(x, y).into_iter().any(|&i| {
+                rings[i].iter().filter(|ring| point_in_poly(&p, ring)).count() % 2 == 1
+            })
+        })?;
+
+        let n_points = input.header.number_of_points as usize;
+        if n_points == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "None of the points lie within the polygons."));
+        }
+
+        let mut output = LasFile::initialize_using_file(&output_file, &input);
+        for i in 0..n_points {
+            output.add_point_record_with_extra_bytes(input.get_record(i), input.get_extra_bytes(i));
+        }
+
+        if verbose { println!("Writing output LAS file..."); }
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Complete!")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let end = time::now();
+        let elapsed_time = end - start;
+        if verbose {
+            println!("Number of points clipped: {}", n_points);
+        }
+        println!("{}", &format!("Elapsed Time (including I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: September 17, 2017
-Last Modified: Dec. 15, 2017
+Last Modified: October 18, 2026
 License: MIT
 
 NOTES: 1. This tool outputs a LAS file, compared with the original Whitebox GAT tool, which output a Shapefile.
@@ -117,7 +117,7 @@ impl WhiteboxTool for FilterLidarScanAngles {
     fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
         let mut input_file: String = "".to_string();
         let mut output_file: String = "".to_string();
-        let mut threshold = 0i8;
+        let mut threshold = 0f64;
         
         // read the arguments
         if args.len() == 0 {
@@ -144,9 +144,9 @@ impl WhiteboxTool for FilterLidarScanAngles {
                 }
             } else if vec[0].to_lowercase() == "-threshold" || vec[0].to_lowercase() == "--threshold" {
                 if keyval {
-                    threshold = vec[1].to_string().parse::<i8>().unwrap().abs();
+                    threshold = vec[1].to_string().parse::<f64>().unwrap().abs();
                 } else {
-                    threshold = args[i+1].to_string().parse::<i8>().unwrap().abs();
+                    threshold = args[i+1].to_string().parse::<f64>().unwrap().abs();
                 }
             }
         }
@@ -165,47 +165,32 @@ impl WhiteboxTool for FilterLidarScanAngles {
             output_file = format!("{}{}", working_directory, output_file);
         }
 
+        let start = time::now();
+
         if verbose { println!("Reading input LAS file..."); }
-        let input = match LasFile::new(&input_file, "r") {
-            Ok(lf) => lf,
+        let mut input = match LasReader::new(&input_file) {
+            Ok(lr) => lr,
             Err(err) => panic!("Error reading file {}: {}", input_file, err),
         };
 
-        let start = time::now();
-
         if verbose { println!("Performing analysis..."); }
 
-        let n_points = input.header.number_of_points as usize;
         let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
 
         let mut progress: i32;
         let mut old_progress: i32 = -1;
         
         // now output the data
-        let mut output = LasFile::initialize_using_file(&output_file, &input);
+        let mut output = LasWriter::initialize_using_reader(&output_file, &input);
         output.header.system_id = "EXTRACTION".to_string();
 
-        for i in 0..n_points {
-            if input[i].scan_angle.abs() <= threshold {
-                let pr = input.get_record(i);
-                let pr2 = match pr {
-                    LidarPointRecord::PointRecord0 { point_data }  => {
-                        LidarPointRecord::PointRecord0 { point_data: point_data }
-                    },
-                    LidarPointRecord::PointRecord1 { point_data, gps_data } => {
-                        LidarPointRecord::PointRecord1 { point_data: point_data, gps_data: gps_data }
-                    },
-                    LidarPointRecord::PointRecord2 { point_data, rgb_data } => {
-                        LidarPointRecord::PointRecord2 { point_data: point_data, rgb_data: rgb_data }
-                    },
-                    LidarPointRecord::PointRecord3 { point_data, gps_data, rgb_data } => {
-                        LidarPointRecord::PointRecord3 { point_data: point_data, gps_data: gps_data, rgb_data: rgb_data }
-                    },
-                };
-                output.add_point_record(pr2);
+        while let Some(pr) = input.next() {
+            let pr = pr?;
+            if pr.get_point_data().scan_angle_degrees().abs() <= threshold {
+                output.add_point_record_with_extra_bytes(pr, input.get_extra_bytes())?;
             }
             if verbose {
-                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                progress = (100.0_f64 * (input.num_points_read() - 1) as f64 / num_points) as i32;
                 if progress != old_progress {
                     println!("Progress: {}%", progress);
                     old_progress = progress;
@@ -213,16 +198,15 @@ impl WhiteboxTool for FilterLidarScanAngles {
             }
         }
 
-        let end = time::now();
-        let elapsed_time = end - start;
-
         if verbose { println!("Writing output LAS file..."); }
-        let _ = match output.write() {
+        let _ = match output.close() {
             Ok(_) => println!("Complete!"),
             Err(e) => println!("error while writing: {:?}", e),
         };
 
-        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+        let end = time::now();
+        let elapsed_time = end - start;
+        println!("{}", &format!("Elapsed Time (including I/O): {}", elapsed_time).replace("PT", ""));
 
         Ok(())
     }
@@ -224,26 +224,15 @@ impl WhiteboxTool for FlightlineOverlap {
         let mut progress: usize;
         let mut old_progress: usize = 1;
         for i in 0..n_points {
-            match input.get_record(i) {
-                LidarPointRecord::PointRecord1 {
-                    point_data,
-                    gps_data,
-                } => {
+            let pr = input.get_record(i);
+            match pr.get_gps_time() {
+                Some(t) => {
+                    let point_data = pr.get_point_data();
                     x = point_data.x;
                     y = point_data.y;
-                    gps_time = gps_data;
+                    gps_time = t;
                 }
-                LidarPointRecord::PointRecord3 {
-                    point_data,
-                    gps_data,
-                    rgb_data,
-                } => {
-                    x = point_data.x;
-                    y = point_data.y;
-                    gps_time = gps_data;
-                    let _ = rgb_data; // just to kill the 'unused variable' warning
-                }
-                _ => {
+                None => {
                     panic!("The input file has a Point Format that does not include GPS time, which is required for the operation of this tool.");
                 }
             };
@@ -0,0 +1,665 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+extern crate num_cpus;
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::env;
+use std::f64;
+use std::f64::consts::PI;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread;
+use algorithms::{polygon_area, signed_polygon_area, trace_region_boundaries};
+use lidar::*;
+use raster::*;
+use structures::Array2D;
+use vector::*;
+use tools::*;
+
+/// Locates individual trees within a canopy height model (CHM), e.g. one created
+/// by the *LidarCanopyHeightModel* tool, and delineates their crowns. Treetops are
+/// identified as the local maxima of the CHM that are at least as high as the
+/// minimum tree height (`--min_height`), within a circular window whose diameter
+/// increases with the height of the cell (`--window_intercept` + `--window_slope`
+/// x height), because taller trees have wider crowns. A window that is too narrow
+/// splits crowns into several trees, while a window that is too wide merges
+/// neighbouring trees.
+///
+/// Crowns are segmented by a marker-controlled watershed: the treetops are used as
+/// markers, from which regions are grown into neighbouring cells in order of
+/// decreasing height, so that each cell is assigned to the tree that reaches it
+/// first. A cell is only added to a crown if it is at least as high as the minimum
+/// tree height and the crown ratio (`--crown_ratio`) times the height of the tree,
+/// which separates crowns from the gaps and understorey between them, and if it is
+/// within the maximum crown radius (`--max_crown_radius`) of the treetop.
+///
+/// The output vector file contains a point at each treetop with the tree's
+/// height, crown area and crown diameter (the diameter of the circle with the area
+/// of the crown). Optionally, the crowns may be output as polygons (`--crowns`) and
+/// as a raster of tree identifiers (`--labels`). As in *LidarSegmentation*, the
+/// points of a LiDAR file (`--lidar`) may also be labelled, with a 'TreeID'
+/// extra-bytes attribute that is zero for points outside of the crowns and for
+/// ground (class 2) points, and output to a new LAS file (`--lidar_output`).
+///
+/// # See Also
+/// *LidarCanopyHeightModel*, *LidarSegmentation*
+pub struct IndividualTreeDetection {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl IndividualTreeDetection {
+    pub fn new() -> IndividualTreeDetection { // public constructor
+        let name = "IndividualTreeDetection".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Identifies treetops in a canopy height model and segments their crowns.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Canopy Height Model File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input canopy height model raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Treetops File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector points file of treetops.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Crowns File (optional)".to_owned(),
+            flags: vec!["--crowns".to_owned()],
+            description: "Optional output vector polygons file of tree crowns.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Crown Labels File (optional)".to_owned(),
+            flags: vec!["--labels".to_owned()],
+            description: "Optional output raster file of tree crown identifiers.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input LiDAR File (optional)".to_owned(),
+            flags: vec!["--lidar".to_owned()],
+            description: "Optional input LiDAR file, the points of which are labelled with tree identifiers.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output LiDAR File (optional)".to_owned(),
+            flags: vec!["--lidar_output".to_owned()],
+            description: "Output LiDAR file of labelled points; required if an input LiDAR file is specified.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Minimum Tree Height".to_owned(),
+            flags: vec!["--min_height".to_owned()],
+            description: "Minimum height of treetops and crowns.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Window Diameter Intercept".to_owned(),
+            flags: vec!["--window_intercept".to_owned()],
+            description: "Diameter of the local maximum search window at a height of zero.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Window Diameter Slope".to_owned(),
+            flags: vec!["--window_slope".to_owned()],
+            description: "Increase in the diameter of the local maximum search window per unit of height.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.1".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Crown Ratio".to_owned(),
+            flags: vec!["--crown_ratio".to_owned()],
+            description: "Minimum height of crown cells, as a proportion of the tree height (0-1).".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Maximum Crown Radius".to_owned(),
+            flags: vec!["--max_crown_radius".to_owned()],
+            description: "Maximum distance of crown cells from the treetop.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("10.0".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=chm.tif -o=treetops.shp --crowns=crowns.shp --min_height=3.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=chm.tif -o=treetops.shp --labels=crowns.tif --lidar=points.las --lidar_output=trees.las --window_intercept=2.5 --window_slope=0.08 --crown_ratio=0.6", short_exe, name).replace("*", &sep);
+
+        IndividualTreeDetection {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for IndividualTreeDetection {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut crowns_file = String::new();
+        let mut labels_file = String::new();
+        let mut lidar_file = String::new();
+        let mut lidar_output_file = String::new();
+        let mut min_height = 2f64;
+        let mut window_intercept = 2f64;
+        let mut window_slope = 0.1f64;
+        let mut crown_ratio = 0.5f64;
+        let mut max_crown_radius = 10f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-crowns" || vec[0].to_lowercase() == "--crowns" {
+                if keyval {
+                    crowns_file = vec[1].to_string();
+                } else {
+                    crowns_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-labels" || vec[0].to_lowercase() == "--labels" {
+                if keyval {
+                    labels_file = vec[1].to_string();
+                } else {
+                    labels_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-lidar" || vec[0].to_lowercase() == "--lidar" {
+                if keyval {
+                    lidar_file = vec[1].to_string();
+                } else {
+                    lidar_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-lidar_output" || vec[0].to_lowercase() == "--lidar_output" {
+                if keyval {
+                    lidar_output_file = vec[1].to_string();
+                } else {
+                    lidar_output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-min_height" || vec[0].to_lowercase() == "--min_height" {
+                if keyval {
+                    min_height = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    min_height = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-window_intercept" || vec[0].to_lowercase() == "--window_intercept" {
+                if keyval {
+                    window_intercept = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    window_intercept = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-window_slope" || vec[0].to_lowercase() == "--window_slope" {
+                if keyval {
+                    window_slope = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    window_slope = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-crown_ratio" || vec[0].to_lowercase() == "--crown_ratio" {
+                if keyval {
+                    crown_ratio = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    crown_ratio = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-max_crown_radius" || vec[0].to_lowercase() == "--max_crown_radius" {
+                if keyval {
+                    max_crown_radius = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    max_crown_radius = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            }
+        }
+
+        if !lidar_file.is_empty() && lidar_output_file.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "An output LiDAR file (--lidar_output) must be specified along with the input LiDAR file."));
+        }
+        if crown_ratio < 0f64 || crown_ratio > 1f64 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The crown ratio must be between 0 and 1."));
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !crowns_file.is_empty() && !crowns_file.contains(&sep) {
+            crowns_file = format!("{}{}", working_directory, crowns_file);
+        }
+        if !labels_file.is_empty() && !labels_file.contains(&sep) {
+            labels_file = format!("{}{}", working_directory, labels_file);
+        }
+        if !lidar_file.is_empty() && !lidar_file.contains(&sep) {
+            lidar_file = format!("{}{}", working_directory, lidar_file);
+        }
+        if !lidar_output_file.is_empty() && !lidar_output_file.contains(&sep) {
+            lidar_output_file = format!("{}{}", working_directory, lidar_output_file);
+        }
+
+        if verbose { println!("Reading data...") };
+
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = time::now();
+
+        let nodata = input.configs.nodata;
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let res_x = input.configs.resolution_x;
+        let res_y = input.configs.resolution_y;
+
+        // find the treetops
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut z: f64;
+                let mut zn: f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut treetops = vec![];
+                    for col in 0..columns {
+                        z = input[(row, col)];
+                        if z == nodata || z < min_height {
+                            continue;
+                        }
+                        // the window always includes the eight neighbouring cells
+                        let radius = ((window_intercept + window_slope * z) / 2f64).max(1.5 * res_x.max(res_y));
+                        let rc = (radius / res_x).floor() as isize;
+                        let rr = (radius / res_y).floor() as isize;
+                        let mut is_max = true;
+                        'window: for r in (row - rr)..(row + rr + 1) {
+                            for c in (col - rc)..(col + rc + 1) {
+                                if r == row && c == col {
+                                    continue;
+                                }
+                                let dist = ((((c - col) as f64) * res_x).powi(2) + (((r - row) as f64) * res_y).powi(2)).sqrt();
+                                if dist > radius {
+                                    continue;
+                                }
+                                zn = input[(r, c)];
+                                // ties within flat-topped crowns are resolved in favour of the first cell
+                                if zn != nodata && (zn > z || (zn == z && (r, c) < (row, col))) {
+                                    is_max = false;
+                                    break 'window;
+                                }
+                            }
+                        }
+                        if is_max {
+                            treetops.push(col);
+                        }
+                    }
+                    tx.send((row, treetops)).unwrap();
+                }
+            });
+        }
+
+        let mut treetop_cells = vec![vec![]; rows as usize];
+        for r in 0..rows {
+            let (row, treetops) = rx.recv().unwrap();
+            treetop_cells[row as usize] = treetops;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Finding treetops: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // grow the crowns from the treetops, which are numbered in row-major order
+        let mut labels: Array2D<i32> = Array2D::new(rows, columns, -1, -1)?;
+        let mut treetops = vec![];
+        let mut heights = vec![];
+        let mut queue = BinaryHeap::new();
+        let mut order = 0usize;
+        for row in 0..rows {
+            for &col in &treetop_cells[row as usize] {
+                let label = treetops.len() as i32;
+                treetops.push((row, col));
+                heights.push(input[(row, col)]);
+                labels.set_value(row, col, label);
+                queue.push(GridCell { row: row, column: col, priority: input[(row, col)], order: order });
+                order += 1;
+            }
+        }
+        let num_trees = treetops.len();
+        if verbose { println!("Number of trees: {}", num_trees); }
+
+        let dx = [ 1, 1, 1, 0, -1, -1, -1, 0 ];
+        let dy = [ -1, 0, 1, 1, 1, 0, -1, -1 ];
+        let mut crown_cells = vec![1usize; num_trees];
+        let mut num_solved = 0usize;
+        let (mut r, mut c): (isize, isize);
+        let mut zn: f64;
+        while let Some(cell) = queue.pop() {
+            let label = labels.get_value(cell.row, cell.column);
+            let (top_row, top_col) = treetops[label as usize];
+            let min_crown_height = min_height.max(crown_ratio * heights[label as usize]);
+            for i in 0..8 {
+                r = cell.row + dy[i];
+                c = cell.column + dx[i];
+                if r < 0 || c < 0 || r >= rows || c >= columns || labels.get_value(r, c) >= 0 {
+                    continue;
+                }
+                zn = input[(r, c)];
+                if zn == nodata || zn < min_crown_height {
+                    continue;
+                }
+                let dist = ((((c - top_col) as f64) * res_x).powi(2) + (((r - top_row) as f64) * res_y).powi(2)).sqrt();
+                if dist <= max_crown_radius {
+                    labels.set_value(r, c, label);
+                    crown_cells[label as usize] += 1;
+                    queue.push(GridCell { row: r, column: c, priority: zn, order: order });
+                    order += 1;
+                }
+            }
+            num_solved += 1;
+            if verbose {
+                progress = (100.0_f64 * num_solved as f64 / order.max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Segmenting crowns: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let crown_areas: Vec<f64> = crown_cells.iter().map(|&n| n as f64 * res_x * res_y).collect();
+
+        // the treetops
+        let mut output = Shapefile::new(&output_file, "w")?;
+        output.header.shape_type = ShapeType::Point;
+        if input.configs.coordinate_ref_system_wkt.to_lowercase() != "not specified" {
+            output.projection = input.configs.coordinate_ref_system_wkt.clone();
+        }
+        output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("HEIGHT", FieldDataType::Real, 12u8, 4u8));
+        output.attributes.add_field(&AttributeField::new("CROWN_AREA", FieldDataType::Real, 18u8, 4u8));
+        output.attributes.add_field(&AttributeField::new("CROWN_DIAM", FieldDataType::Real, 12u8, 4u8));
+        for i in 0..num_trees {
+            let (row, col) = treetops[i];
+            output.add_point_record(input.get_x_from_column(col), input.get_y_from_row(row));
+            output.attributes.add_record(vec![FieldData::Int(i as i32 + 1),
+                                              FieldData::Real(heights[i]),
+                                              FieldData::Real(crown_areas[i]),
+                                              FieldData::Real(2f64 * (crown_areas[i] / PI).sqrt())],
+                                         false);
+        }
+
+        // the crown polygons
+        let mut crowns = None;
+        if !crowns_file.is_empty() {
+            if verbose { println!("Tracing crown boundaries...") };
+            let regions = trace_region_boundaries(&labels,
+                                                  num_trees,
+                                                  input.configs.west,
+                                                  input.configs.north,
+                                                  res_x,
+                                                  res_y);
+            let mut sf = Shapefile::new(&crowns_file, "w")?;
+            sf.header.shape_type = ShapeType::Polygon;
+            sf.projection = output.projection.clone();
+            sf.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+            sf.attributes.add_field(&AttributeField::new("HEIGHT", FieldDataType::Real, 12u8, 4u8));
+            sf.attributes.add_field(&AttributeField::new("CROWN_AREA", FieldDataType::Real, 18u8, 4u8));
+            sf.attributes.add_field(&AttributeField::new("CROWN_DIAM", FieldDataType::Real, 12u8, 4u8));
+            for (i, rings) in regions.into_iter().enumerate() {
+                let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+                let mut area = 0f64;
+                for ring in rings {
+                    if signed_polygon_area(&ring) > 0f64 {
+                        area -= polygon_area(&ring);
+                    } else {
+                        area += polygon_area(&ring);
+                    }
+                    geom.add_part(&ring);
+                }
+                sf.add_record(geom);
+                sf.attributes.add_record(vec![FieldData::Int(i as i32 + 1),
+                                              FieldData::Real(heights[i]),
+                                              FieldData::Real(area),
+                                              FieldData::Real(2f64 * (area / PI).sqrt())],
+                                         false);
+            }
+            crowns = Some(sf);
+        }
+
+        // the crown label raster, in which zero marks cells outside of the crowns
+        let mut label_raster = None;
+        if !labels_file.is_empty() {
+            let mut raster = Raster::initialize_using_file(&labels_file, &input);
+            raster.configs.data_type = DataType::I32;
+            raster.configs.palette = "qual.plt".to_string();
+            raster.configs.photometric_interp = PhotometricInterpretation::Categorical;
+            for row in 0..rows {
+                let mut data = vec![nodata; columns as usize];
+                for col in 0..columns {
+                    if input[(row, col)] != nodata {
+                        data[col as usize] = (labels.get_value(row, col) + 1) as f64;
+                    }
+                }
+                raster.set_row_data(row, data);
+            }
+            raster.add_metadata_entry(format!("Created by whitebox_tools\' {} tool", self.get_tool_name()));
+            raster.add_metadata_entry(format!("Input file: {}", input_file));
+            raster.add_metadata_entry(format!("Minimum tree height: {}", min_height));
+            raster.add_metadata_entry(format!("Window diameter: {} + {} x height", window_intercept, window_slope));
+            raster.add_metadata_entry(format!("Crown ratio: {}", crown_ratio));
+            raster.add_metadata_entry(format!("Maximum crown radius: {}", max_crown_radius));
+            label_raster = Some(raster);
+        }
+
+        // the LiDAR points, labelled by crown
+        let mut lidar_output = None;
+        if !lidar_file.is_empty() {
+            if verbose { println!("Reading input LAS file..."); }
+            let las = match LasFile::new(&lidar_file, "r") {
+                Ok(lf) => lf,
+                Err(err) => return Err(Error::new(err.kind(), format!("Error reading file {}: {}", lidar_file, err))),
+            };
+            let mut las_output = LasFile::initialize_using_file(&lidar_output_file, &las);
+            let attribute = ExtraBytesDescriptor::new("TreeID", ExtraByteType::U32, "Tree crown identifier");
+            las_output.ensure_extra_byte_attribute(attribute)?;
+            let n_points = las.header.number_of_points as usize;
+            for point_num in 0..n_points {
+                let p: PointData = las.get_point_info(point_num);
+                let row = input.get_row_from_y(p.y);
+                let col = input.get_column_from_x(p.x);
+                let mut tree_id = 0f64;
+                if p.classification() != 2 && row >= 0 && col >= 0 && row < rows && col < columns {
+                    tree_id = (labels.get_value(row, col) + 1) as f64;
+                }
+                las_output.add_point_record_with_extra_bytes(las.get_record(point_num), las.get_extra_bytes(point_num));
+                las_output.set_extra_byte_value(point_num, "TreeID", tree_id)?;
+                if verbose {
+                    progress = (100.0_f64 * point_num as f64 / (n_points - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        println!("Labelling points: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            lidar_output = Some(las_output);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+        if let Some(mut sf) = crowns {
+            let _ = match sf.write() {
+                Ok(_) => if verbose { println!("Crowns file written") },
+                Err(e) => return Err(e),
+            };
+        }
+        if let Some(mut raster) = label_raster {
+            raster.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+            let _ = match raster.write() {
+                Ok(_) => if verbose { println!("Labels file written") },
+                Err(e) => return Err(e),
+            };
+        }
+        if let Some(mut las_output) = lidar_output {
+            let _ = match las_output.write() {
+                Ok(_) => if verbose { println!("LiDAR file written") },
+                Err(e) => return Err(e),
+            };
+        }
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+// A crown cell waiting to be expanded. Higher cells are expanded first, and cells
+// of equal height in the order in which they were reached.
+#[derive(PartialEq, Debug)]
+struct GridCell {
+    row: isize,
+    column: isize,
+    priority: f64,
+    order: usize,
+}
+
+impl Eq for GridCell {}
+
+impl PartialOrd for GridCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GridCell {
+    fn cmp(&self, other: &GridCell) -> Ordering {
+        match self.priority.partial_cmp(&other.priority) {
+            Some(Ordering::Equal) | None => other.order.cmp(&self.order),
+            Some(ord) => ord,
+        }
+    }
+}
+
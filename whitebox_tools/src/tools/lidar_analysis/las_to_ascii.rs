@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: July 16, 2017
-Last Modified: Dec. 15, 2017
+Last Modified: October 18, 2026
 License: MIT
 */
 extern crate time;
@@ -146,13 +146,15 @@ impl WhiteboxTool for LasToAscii {
                     input_file = format!("{}{}", working_directory, input_file);
                 }
 
-                let input: LasFile = match LasFile::new(&input_file, "r") {
-                    Ok(lf) => lf,
+                let input = match LasReader::new(&input_file) {
+                    Ok(lr) => lr,
                     Err(_) => return Err(Error::new(ErrorKind::NotFound, format!("No such file or directory ({})", input_file))),
                 };
                 
                 let output_file = if input_file.to_lowercase().ends_with(".las") {
                     input_file.replace(".las", ".txt")
+                } else if input_file.to_lowercase().ends_with(".laz") {
+                    input_file.replace(".laz", ".txt")
                 } else if input_file.to_lowercase().ends_with(".zip") {
                     input_file.replace(".zip", ".txt")
                 } else {
@@ -166,8 +168,8 @@ impl WhiteboxTool for LasToAscii {
                 let n_points = input.header.number_of_points as usize;
 
                 writer.write_all("X Y Z Intensity Class Return Num_returns\n".as_bytes())?;
-                for k in 0..n_points {
-                    let pd = input[k];
+                for (k, pr) in input.enumerate() {
+                    let pd = pr?.get_point_data();
                     let s = format!("{} {} {} {} {} {} {}\n", pd.x, pd.y, pd.z, pd.intensity, pd.classification(), pd.return_number(), pd.number_of_returns());
                     writer.write_all(s.as_bytes())?;
 
@@ -0,0 +1,518 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+extern crate num_cpus;
+
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread;
+use lidar::*;
+use raster::*;
+use structures::{BoundingBox, FixedRadiusSearch2D, Point2D, RTree, Triangulation};
+use tools::*;
+
+/// Creates a canopy height model (CHM) raster from a LiDAR (LAS) file whose
+/// elevations have been normalized to heights above the ground, e.g. by
+/// *LidarHeightAboveGround*. Only first returns are used, and points that are
+/// withheld, classified as noise, or higher than `--max_height` are excluded.
+/// Negative heights are set to zero.
+///
+/// Two gridding methods are available:
+///
+/// * 'pit-free' (the default) is the method of Khosravipour et al. (2014). A
+///   TIN is gridded from the first returns above each of a series of height
+///   thresholds (`--height_thresholds`), and the CHM is the maximum of these
+///   partial CHMs. Triangles with an edge longer than the freeze distance
+///   (`--max_triangle_edge_length`) are left out of all but the lowest partial
+///   CHM, so that the deep, narrow pits of a first-return TIN, where laser
+///   pulses penetrated the upper canopy, are filled by the higher layers. The
+///   freeze distance defaults to three times the mean first-return spacing.
+/// * 'highest' assigns each cell the height of its highest first return, as in
+///   *BlockMaximum*, and fills empty cells with the height of the nearest first
+///   return within the search radius (`--radius`), as in
+///   *LidarNearestNeighbourGridding*.
+///
+/// Reference:
+///
+/// Khosravipour, A., Skidmore, A. K., Isenburg, M., Wang, T., and Hussin, Y. A.
+/// (2014). Generating pit-free canopy height models from airborne lidar.
+/// Photogrammetric Engineering & Remote Sensing, 80(9), 863-872.
+pub struct LidarCanopyHeightModel {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarCanopyHeightModel {
+    pub fn new() -> LidarCanopyHeightModel { // public constructor
+        let name = "LidarCanopyHeightModel".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Creates a pit-free canopy height model (CHM) raster from height-normalized LiDAR points.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file, with heights above the ground as elevations.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Grid Resolution".to_owned(),
+            flags: vec!["--resolution".to_owned()],
+            description: "Output raster's grid resolution.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Gridding Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Gridding method; options are 'pit-free' (default) and 'highest'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["pit-free".to_owned(), "highest".to_owned()]),
+            default_value: Some("pit-free".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Height Thresholds".to_owned(),
+            flags: vec!["--height_thresholds".to_owned()],
+            description: "Heights of the partial CHMs of the pit-free method, e.g. '0.0,2.0,5.0,10.0,15.0,20.0,25.0,30.0'.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: Some("0.0,2.0,5.0,10.0,15.0,20.0,25.0,30.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Freeze Distance (optional)".to_owned(),
+            flags: vec!["--max_triangle_edge_length".to_owned()],
+            description: "Optional maximum triangle edge length of the partial CHMs above the lowest height threshold; defaults to three times the mean point spacing.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Search Radius".to_owned(),
+            flags: vec!["--radius".to_owned()],
+            description: "Search radius used to fill empty cells with the 'highest' method.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Maximum Height (optional)".to_owned(),
+            flags: vec!["--max_height".to_owned()],
+            description: "Optional maximum height; higher points, e.g. birds, are excluded.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=normalized.las -o=chm.tif --resolution=0.5
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=normalized.las -o=chm.tif --resolution=0.5 --height_thresholds='0,2,5,10,15,20' --max_triangle_edge_length=1.5 --max_height=60.0", short_exe, name).replace("*", &sep);
+
+        LidarCanopyHeightModel {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for LidarCanopyHeightModel {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file: String = "".to_string();
+        let mut output_file: String = "".to_string();
+        let mut grid_res: f64 = 1.0;
+        let mut method = "pit-free".to_string();
+        let mut thresholds_str = "0.0,2.0,5.0,10.0,15.0,20.0,25.0,30.0".to_string();
+        let mut freeze_distance = f64::NAN;
+        let mut search_radius = 2.0;
+        let mut max_height = f64::INFINITY;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 { keyval = true; }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-resolution" || vec[0].to_lowercase() == "--resolution" {
+                if keyval {
+                    grid_res = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    grid_res = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-method" || vec[0].to_lowercase() == "--method" {
+                if keyval {
+                    method = vec[1].to_lowercase();
+                } else {
+                    method = args[i+1].to_lowercase();
+                }
+            } else if vec[0].to_lowercase() == "-height_thresholds" || vec[0].to_lowercase() == "--height_thresholds" {
+                if keyval {
+                    thresholds_str = vec[1].to_string();
+                } else {
+                    thresholds_str = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-max_triangle_edge_length" || vec[0].to_lowercase() == "--max_triangle_edge_length" {
+                if keyval {
+                    freeze_distance = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    freeze_distance = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-radius" || vec[0].to_lowercase() == "--radius" {
+                if keyval {
+                    search_radius = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    search_radius = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-max_height" || vec[0].to_lowercase() == "--max_height" {
+                if keyval {
+                    max_height = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    max_height = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            }
+        }
+
+        if method != "pit-free" && method != "highest" {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The gridding method must be either 'pit-free' or 'highest'."));
+        }
+
+        let mut thresholds = vec![];
+        for value in thresholds_str.split(|c| c == ',' || c == ';') {
+            if !value.trim().is_empty() {
+                match value.trim().parse::<f64>() {
+                    Ok(t) => thresholds.push(t),
+                    Err(_) => return Err(Error::new(ErrorKind::InvalidInput,
+                                format!("Invalid height threshold ({}).", value.trim()))),
+                }
+            }
+        }
+        if thresholds.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "At least one height threshold must be specified."));
+        }
+        thresholds.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep = path::MAIN_SEPARATOR;
+        if !input_file.contains(sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading input LAS file..."); }
+        let input = match LasFile::new(&input_file, "r") {
+            Ok(lf) => lf,
+            Err(err) => return Err(Error::new(err.kind(), format!("Error reading file {}: {}", input_file, err))),
+        };
+
+        let start = time::now();
+
+        if verbose { println!("Performing analysis..."); }
+
+        let n_points = input.header.number_of_points as usize;
+        let mut points: Vec<Point2D> = vec![];
+        let mut heights: Vec<f64> = vec![];
+        for i in 0..n_points {
+            let p: PointData = input.get_point_info(i);
+            if p.return_number() == 1 && !p.class_bit_field.withheld() && !p.is_classified_noise() && p.z <= max_height {
+                points.push(Point2D::new(p.x, p.y));
+                heights.push(p.z.max(0f64));
+            }
+        }
+        if points.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "The input file does not contain any first-return points."));
+        }
+
+        let west: f64 = input.header.min_x;
+        let north: f64 = input.header.max_y;
+        let rows: isize = ((((north - input.header.min_y) / grid_res).ceil()) as isize).max(1);
+        let columns: isize = ((((input.header.max_x - west) / grid_res).ceil()) as isize).max(1);
+        let south: f64 = north - rows as f64 * grid_res;
+        let east = west + columns as f64 * grid_res;
+        let nodata = -32768.0f64;
+
+        let mut configs = RasterConfigs { ..Default::default() };
+        configs.rows = rows as usize;
+        configs.columns = columns as usize;
+        configs.north = north;
+        configs.south = south;
+        configs.east = east;
+        configs.west = west;
+        configs.resolution_x = grid_res;
+        configs.resolution_y = grid_res;
+        configs.nodata = nodata;
+        configs.data_type = DataType::F32;
+        configs.photometric_interp = PhotometricInterpretation::Continuous;
+
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+        if method == "pit-free" {
+            if freeze_distance.is_nan() {
+                let area = (input.header.max_x - input.header.min_x) * (input.header.max_y - input.header.min_y);
+                freeze_distance = 3f64 * (area / points.len() as f64).sqrt();
+                if verbose { println!("Freeze distance: {:.3}", freeze_distance); }
+            }
+            for k in 0..thresholds.len() {
+                let mut layer_points = vec![];
+                let mut layer_heights = vec![];
+                for i in 0..points.len() {
+                    if heights[i] >= thresholds[k] {
+                        layer_points.push(points[i]);
+                        layer_heights.push(heights[i]);
+                    }
+                }
+                let max_edge_length = if k == 0 { f64::INFINITY } else { freeze_distance };
+                let layer = match grid_tin(layer_points, layer_heights, max_edge_length, west, north, rows, columns, grid_res, nodata) {
+                    Some(layer) => layer,
+                    None => break, // there are too few points above the threshold
+                };
+                for row in 0..rows {
+                    for col in 0..columns {
+                        let z = layer[row as usize][col as usize];
+                        if z != nodata && (output[(row, col)] == nodata || z > output[(row, col)]) {
+                            output.set_value(row, col, z);
+                        }
+                    }
+                }
+                if verbose {
+                    progress = (100.0_f64 * (k + 1) as f64 / thresholds.len() as f64) as i32;
+                    if progress != old_progress {
+                        println!("Gridding partial CHMs: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        } else {
+            // the highest first return within each cell, i.e. the block maximum
+            let mut frs: FixedRadiusSearch2D<usize> = FixedRadiusSearch2D::new(search_radius);
+            for i in 0..points.len() {
+                let col = (((points[i].x - west) / grid_res).floor() as isize).max(0).min(columns - 1);
+                let row = (((north - points[i].y) / grid_res).floor() as isize).max(0).min(rows - 1);
+                if output[(row, col)] == nodata || heights[i] > output[(row, col)] {
+                    output.set_value(row, col, heights[i]);
+                }
+                frs.insert(points[i].x, points[i].y, i);
+            }
+            // with the empty cells filled by nearest-neighbour gridding
+            for row in 0..rows {
+                for col in 0..columns {
+                    if output[(row, col)] == nodata {
+                        let x = west + (col as f64 + 0.5) * grid_res;
+                        let y = north - (row as f64 + 0.5) * grid_res;
+                        let ret = frs.search(x, y);
+                        let mut min_dist = f64::INFINITY;
+                        for j in 0..ret.len() {
+                            if ret[j].1 < min_dist {
+                                min_dist = ret[j].1;
+                                output.set_value(row, col, heights[ret[j].0]);
+                            }
+                        }
+                    }
+                }
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as i32;
+                    if progress != old_progress {
+                        println!("Filling empty cells: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+        output.add_metadata_entry(format!("Created by whitebox_tools\' {} tool", self.get_tool_name()));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Grid resolution: {}", grid_res));
+        output.add_metadata_entry(format!("Method: {}", method));
+        if method == "pit-free" {
+            output.add_metadata_entry(format!("Height thresholds: {}", thresholds_str));
+            output.add_metadata_entry(format!("Freeze distance: {}", freeze_distance));
+        } else {
+            output.add_metadata_entry(format!("Search radius: {}", search_radius));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+// Grids the TIN of a set of points, in the same way as LidarTINGridding, leaving
+// out triangles with an edge longer than max_edge_length. Returns the rows of the
+// grid, or None if the points cannot be triangulated.
+fn grid_tin(points: Vec<Point2D>, values: Vec<f64>, max_edge_length: f64, west: f64, north: f64,
+            rows: isize, columns: isize, grid_res: f64, nodata: f64) -> Option<Vec<Vec<f64>>> {
+    let triangulation = match Triangulation::new(&points) {
+        Some(t) => t,
+        None => return None,
+    };
+    let mut triangles = vec![];
+    for t in 0..triangulation.num_triangles() {
+        let (p1, p2, p3) = (points[triangulation.triangles[3 * t]],
+                            points[triangulation.triangles[3 * t + 1]],
+                            points[triangulation.triangles[3 * t + 2]]);
+        if p1.distance(&p2) <= max_edge_length &&
+           p2.distance(&p3) <= max_edge_length &&
+           p3.distance(&p1) <= max_edge_length {
+            triangles.push((BoundingBox::from_points(&[p1, p2, p3]), t));
+        }
+    }
+    let triangle_tree = Arc::new(RTree::bulk_load(triangles));
+    let east = west + columns as f64 * grid_res;
+
+    let points = Arc::new(points);
+    let values = Arc::new(values);
+    let triangulation = Arc::new(triangulation);
+    let num_procs = num_cpus::get() as isize;
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let points = points.clone();
+        let values = values.clone();
+        let triangulation = triangulation.clone();
+        let triangle_tree = triangle_tree.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for row in (0..rows).filter(|r| r % num_procs == tid) {
+                let mut data = vec![nodata; columns as usize];
+                let y = north - (row as f64 + 0.5) * grid_res;
+                for &t in triangle_tree.search(&BoundingBox::new(west, y, east, y)) {
+                    let (i1, i2, i3) = (triangulation.triangles[3 * t],
+                                        triangulation.triangles[3 * t + 1],
+                                        triangulation.triangles[3 * t + 2]);
+                    let (p1, p2, p3) = (points[i1], points[i2], points[i3]);
+                    let det = (p2.y - p3.y) * (p1.x - p3.x) + (p3.x - p2.x) * (p1.y - p3.y);
+                    if det == 0f64 {
+                        continue;
+                    }
+                    let min_x = p1.x.min(p2.x).min(p3.x);
+                    let max_x = p1.x.max(p2.x).max(p3.x);
+                    let start_col = (((min_x - west) / grid_res - 0.5).ceil() as isize).max(0);
+                    let end_col = (((max_x - west) / grid_res - 0.5).floor() as isize).min(columns - 1);
+                    for col in start_col..end_col + 1 {
+                        let x = west + (col as f64 + 0.5) * grid_res;
+                        // barycentric coordinates of the cell centre
+                        let l1 = ((p2.y - p3.y) * (x - p3.x) + (p3.x - p2.x) * (y - p3.y)) / det;
+                        let l2 = ((p3.y - p1.y) * (x - p3.x) + (p1.x - p3.x) * (y - p3.y)) / det;
+                        let l3 = 1f64 - l1 - l2;
+                        if l1 >= -1e-9 && l2 >= -1e-9 && l3 >= -1e-9 {
+                            data[col as usize] = l1 * values[i1] + l2 * values[i2] + l3 * values[i3];
+                        }
+                    }
+                }
+                tx.send((row, data)).unwrap();
+            }
+        });
+    }
+
+    let mut grid = vec![vec![]; rows as usize];
+    for _ in 0..rows {
+        let (row, data) = rx.recv().unwrap();
+        grid[row as usize] = data;
+    }
+    Some(grid)
+}
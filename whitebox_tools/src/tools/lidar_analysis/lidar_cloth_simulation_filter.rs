@@ -0,0 +1,477 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::collections::VecDeque;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use lidar::*;
+use tools::*;
+
+/// Classifies the ground points within a LiDAR (LAS) file using the cloth
+/// simulation filter (CSF) of Zhang et al. (2016). The point cloud is turned upside
+/// down and a cloth, a grid of particles (`--resolution`) joined by springs, is
+/// dropped onto it under gravity. Particles stop moving when they reach the
+/// inverted surface, while the springs keep the cloth from sagging into the gaps
+/// beneath buildings and vegetation, which are pits in the inverted surface. The
+/// rigidness of the cloth (`--rigidness`) should be 1 for steep terrain, 2 for
+/// gently rolling terrain, and 3 for flat terrain with large buildings; a softer
+/// cloth follows the terrain more closely but sags further into off-terrain
+/// objects. The simulation ends when the cloth stops moving, or after a maximum
+/// number of iterations (`--max_iterations`). On steep slopes the cloth may stay
+/// above the ground surface along the edges of terraces and cliffs; the slope
+/// smoothing option (`--slope_smooth`) lowers the particles of these areas onto
+/// the surface wherever it is continuous with the neighbouring parts of the cloth
+/// that rest upon the surface. Points within a distance (`--threshold`) of the
+/// final cloth are classified as ground points.
+///
+/// As with *LidarProgressiveMorphologicalFilter*, all of the points are output in
+/// their original order, with the ground points assigned class 2 and the other
+/// points class 1 (unclassified). Points that were previously assigned any other
+/// class, e.g. buildings, water or noise, keep their existing classes, as do
+/// withheld points; noise and withheld points are not used in the analysis. The
+/// output can therefore be compared with other ground classifications of the same
+/// file using *LidarKappaIndex*.
+///
+/// # Reference
+/// Zhang, W., Qi, J., Wan, P., Wang, H., Xie, D., Wang, X., & Yan, G. (2016). An
+/// easy-to-use airborne LiDAR data filtering method based on cloth simulation.
+/// Remote Sensing, 8(6), 501.
+///
+/// # See Also
+/// *LidarProgressiveMorphologicalFilter*, *LidarGroundPointFilter*, *LidarKappaIndex*
+pub struct LidarClothSimulationFilter {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarClothSimulationFilter {
+    pub fn new() -> LidarClothSimulationFilter { // public constructor
+        let name = "LidarClothSimulationFilter".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Classifies the ground points within a LiDAR dataset using the cloth simulation filter (CSF).".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output LiDAR file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Cloth Resolution".to_owned(),
+            flags: vec!["--resolution".to_owned()],
+            description: "Spacing of the particles of the cloth.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Cloth Rigidness".to_owned(),
+            flags: vec!["--rigidness".to_owned()],
+            description: "Rigidness of the cloth; 1 (steep terrain), 2 (rolling terrain) or 3 (flat terrain).".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["1".to_owned(), "2".to_owned(), "3".to_owned()]),
+            default_value: Some("2".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Classification Threshold".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description: "Maximum distance of ground points from the cloth.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Maximum Number of Iterations".to_owned(),
+            flags: vec!["--max_iterations".to_owned()],
+            description: "Maximum number of iterations of the simulation.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("500".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Smooth steep slopes?".to_owned(),
+            flags: vec!["--slope_smooth".to_owned()],
+            description: "Flag indicating whether the cloth should be lowered onto steep slopes after the simulation.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=input.las -o=output.las --resolution=0.5 --rigidness=1 --slope_smooth", short_exe, name).replace("*", &sep);
+
+        LidarClothSimulationFilter {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for LidarClothSimulationFilter {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file: String = "".to_string();
+        let mut output_file: String = "".to_string();
+        let mut cloth_res = 1f64;
+        let mut rigidness = 2i32;
+        let mut threshold = 0.5f64;
+        let mut max_iterations = 500usize;
+        let mut slope_smooth = false;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 { keyval = true; }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-resolution" || vec[0].to_lowercase() == "--resolution" {
+                if keyval {
+                    cloth_res = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    cloth_res = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-rigidness" || vec[0].to_lowercase() == "--rigidness" {
+                if keyval {
+                    rigidness = vec[1].to_string().parse::<i32>().unwrap();
+                } else {
+                    rigidness = args[i+1].to_string().parse::<i32>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-threshold" || vec[0].to_lowercase() == "--threshold" {
+                if keyval {
+                    threshold = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    threshold = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-max_iterations" || vec[0].to_lowercase() == "--max_iterations" {
+                if keyval {
+                    max_iterations = vec[1].to_string().parse::<usize>().unwrap();
+                } else {
+                    max_iterations = args[i+1].to_string().parse::<usize>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-slope_smooth" || vec[0].to_lowercase() == "--slope_smooth" {
+                slope_smooth = !(keyval && vec[1].to_lowercase() == "false");
+            }
+        }
+
+        if cloth_res <= 0f64 {
+            return Err(Error::new(ErrorKind::InvalidInput, "The cloth resolution must be greater than zero."));
+        }
+        if rigidness < 1 || rigidness > 3 {
+            return Err(Error::new(ErrorKind::InvalidInput, "The cloth rigidness must be 1, 2 or 3."));
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep = path::MAIN_SEPARATOR;
+        if !input_file.contains(sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading input LAS file..."); }
+        let input = match LasFile::new(&input_file, "r") {
+            Ok(lf) => lf,
+            Err(err) => return Err(Error::new(err.kind(), format!("Error reading file {}: {}", input_file, err))),
+        };
+
+        let start = time::now();
+
+        if verbose { println!("Performing analysis..."); }
+
+        let n_points = input.header.number_of_points as usize;
+        let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
+
+        // The particles lie at the nodes of a grid covering the points. Elevations
+        // are negated, so that the inverted point cloud lies beneath the cloth.
+        let west = input.header.min_x;
+        let north = input.header.max_y;
+        let rows = ((input.header.max_y - input.header.min_y) / cloth_res).round() as usize + 1;
+        let columns = ((input.header.max_x - input.header.min_x) / cloth_res).round() as usize + 1;
+        let num_particles = rows * columns;
+
+        // Each particle collides with the highest of the inverted points nearest to it.
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+        let mut is_candidate = vec![false; n_points];
+        let mut surface = vec![f64::NEG_INFINITY; num_particles];
+        for i in 0..n_points {
+            let p: PointData = input.get_point_info(i);
+            if !p.class_bit_field.withheld() && !p.is_classified_noise() {
+                let row = (((north - p.y) / cloth_res).round() as usize).min(rows - 1);
+                let col = (((p.x - west) / cloth_res).round() as usize).min(columns - 1);
+                is_candidate[i] = true;
+                if -p.z > surface[row * columns + col] {
+                    surface[row * columns + col] = -p.z;
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Gridding points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        fill_empty_nodes(&mut surface, rows, columns);
+
+        let (dr, dc) = ([0isize, 1, 0, -1], [1isize, 0, -1, 0]);
+        let neighbour = |i: usize, k: usize| -> Option<usize> {
+            let r = (i / columns) as isize + dr[k];
+            let c = (i % columns) as isize + dc[k];
+            if r < 0 || c < 0 || r >= rows as isize || c >= columns as isize {
+                None
+            } else {
+                Some(r as usize * columns + c as usize)
+            }
+        };
+
+        // The cloth starts just above the highest of the inverted points.
+        let initial_height = surface.iter().cloned().fold(f64::NEG_INFINITY, f64::max) + 0.05;
+        if initial_height == f64::NEG_INFINITY {
+            return Err(Error::new(ErrorKind::InvalidInput, "The input file does not contain any points that can be classified."));
+        }
+        let mut height = vec![initial_height; num_particles];
+        let mut previous_height = height.clone();
+        let mut is_movable = vec![true; num_particles];
+        let time_step = 0.65f64;
+        let displacement = -0.2 * time_step * time_step; // gravity
+        let damping = 0.01f64;
+        // the proportion of the height difference between two particles closed by their spring
+        let spring_factor = 1f64 - 0.6f64.powi(rigidness);
+        // the springs are relaxed several times during each time step, which keeps
+        // the cloth taut over wide pits, i.e. beneath large buildings
+        let spring_passes = 4;
+        let mut iteration = 0;
+        while iteration < max_iterations {
+            for i in 0..num_particles {
+                if is_movable[i] {
+                    let h = height[i];
+                    height[i] += (height[i] - previous_height[i]) * (1f64 - damping) + displacement;
+                    previous_height[i] = h;
+                }
+            }
+            for _ in 0..spring_passes {
+                for i in 0..num_particles {
+                    // each spring is visited once per pass, from its particle to the east or south
+                    for k in 0..2 {
+                        if let Some(j) = neighbour(i, k) {
+                            let diff = height[j] - height[i];
+                            if is_movable[i] && is_movable[j] {
+                                height[i] += 0.5 * spring_factor * diff;
+                                height[j] -= 0.5 * spring_factor * diff;
+                            } else if is_movable[i] {
+                                height[i] += spring_factor * diff;
+                            } else if is_movable[j] {
+                                height[j] -= spring_factor * diff;
+                            }
+                        }
+                    }
+                }
+            }
+            let mut max_movement = 0f64;
+            for i in 0..num_particles {
+                if is_movable[i] {
+                    if height[i] <= surface[i] {
+                        height[i] = surface[i];
+                        is_movable[i] = false;
+                    } else if (height[i] - previous_height[i]).abs() > max_movement {
+                        max_movement = (height[i] - previous_height[i]).abs();
+                    }
+                }
+            }
+            iteration += 1;
+            if verbose {
+                progress = (100.0_f64 * iteration as f64 / max_iterations as f64) as i32;
+                if progress != old_progress {
+                    println!("Simulating cloth: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+            if max_movement < 0.005 {
+                break;
+            }
+        }
+        if verbose { println!("Number of iterations: {}", iteration); }
+
+        if slope_smooth {
+            // lower the cloth onto the surface wherever the surface beneath the
+            // movable particles continues smoothly from the cloth that rests upon it
+            let max_step = 0.3f64;
+            let mut queue: VecDeque<usize> = (0..num_particles).filter(|&i| !is_movable[i]).collect();
+            while let Some(i) = queue.pop_front() {
+                for k in 0..4 {
+                    if let Some(j) = neighbour(i, k) {
+                        if is_movable[j] && (surface[j] - height[i]).abs() < max_step {
+                            height[j] = surface[j];
+                            is_movable[j] = false;
+                            queue.push_back(j);
+                        }
+                    }
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        // output all of the points, reclassifying only the unclassified and ground points
+        let mut output = LasFile::initialize_using_file(&output_file, &input);
+        let mut num_ground_points = 0usize;
+        for i in 0..n_points {
+            let mut pr = input.get_record(i);
+            let mut point_data = pr.get_point_data();
+            if is_candidate[i] && point_data.classification() <= 2 {
+                // the height of the cloth at the point, interpolated bilinearly
+                let y = ((north - point_data.y) / cloth_res).max(0f64).min((rows - 1) as f64);
+                let x = ((point_data.x - west) / cloth_res).max(0f64).min((columns - 1) as f64);
+                let (r0, c0) = (y.floor() as usize, x.floor() as usize);
+                let (r1, c1) = ((r0 + 1).min(rows - 1), (c0 + 1).min(columns - 1));
+                let (fy, fx) = (y - r0 as f64, x - c0 as f64);
+                let cloth_height = height[r0 * columns + c0] * (1f64 - fx) * (1f64 - fy)
+                    + height[r0 * columns + c1] * fx * (1f64 - fy)
+                    + height[r1 * columns + c0] * (1f64 - fx) * fy
+                    + height[r1 * columns + c1] * fx * fy;
+                if (-point_data.z - cloth_height).abs() < threshold {
+                    point_data.set_classification(2);
+                    num_ground_points += 1;
+                } else {
+                    point_data.set_classification(1);
+                }
+                pr.set_point_data(point_data);
+            }
+            output.add_point_record_with_extra_bytes(pr, input.get_extra_bytes(i));
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Saving data: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        if verbose { println!("Number of ground points: {}", num_ground_points); }
+
+        if verbose { println!("Writing output LAS file..."); }
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Complete!") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+// Assigns the grid nodes without points the value of the nearest (in terms of the
+// number of steps to a queen's move) node with points.
+fn fill_empty_nodes(values: &mut [f64], rows: usize, columns: usize) {
+    let dx = [ 1, 1, 1, 0, -1, -1, -1, 0 ];
+    let dy = [ -1, 0, 1, 1, 1, 0, -1, -1 ];
+    let mut queue: VecDeque<usize> = (0..values.len()).filter(|&i| values[i] != f64::NEG_INFINITY).collect();
+    while let Some(i) = queue.pop_front() {
+        let (row, col) = ((i / columns) as isize, (i % columns) as isize);
+        for k in 0..8 {
+            let (r, c) = (row + dy[k], col + dx[k]);
+            if r >= 0 && c >= 0 && r < rows as isize && c < columns as isize {
+                let j = r as usize * columns + c as usize;
+                if values[j] == f64::NEG_INFINITY {
+                    values[j] = values[i];
+                    queue.push_back(j);
+                }
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: June 2, 2017
-Last Modified: Dec. 15, 2017
+Last Modified: October 18, 2026
 License: MIT
 */
 
@@ -236,11 +236,11 @@ impl WhiteboxTool for LidarElevationSlice {
         }
 
         if verbose { println!("Reading input LAS file..."); }
-        let input: LasFile = match LasFile::new(&input_file, "r") {
-            Ok(lf) => lf,
+        let mut input = match LasReader::new(&input_file) {
+            Ok(lr) => lr,
             Err(_) => return Err(Error::new(ErrorKind::NotFound, format!("No such file or directory ({})", input_file))),
         };
-        let mut output = LasFile::initialize_using_file(&output_file, &input);
+        let mut output = LasWriter::initialize_using_reader(&output_file, &input);
         output.header.system_id = "EXTRACTION".to_string();
 
         if verbose { println!("Performing analysis..."); }
@@ -251,14 +251,15 @@ impl WhiteboxTool for LidarElevationSlice {
         let num_points: f64 = (input.header.number_of_points - 1) as f64;
 
         if filter {
-            for i in 0..input.header.number_of_points as usize {
-                z = input.get_point_info(i).z;
+            while let Some(pr) = input.next() {
+                let pr = pr?;
+                z = pr.get_point_data().z;
                 if z >= minz && z <= maxz {
-                    output.add_point_record(input.get_record(i));
+                    output.add_point_record_with_extra_bytes(pr, input.get_extra_bytes())?;
                     num_points_filtered += 1;
                 }
                 if verbose {
-                    progress = (100.0_f64 * i as f64 / num_points) as i32;
+                    progress = (100.0_f64 * (input.num_points_read() - 1) as f64 / num_points) as i32;
                     if progress != old_progress {
                         println!("Progress: {}%", progress);
                         old_progress = progress;
@@ -266,37 +267,19 @@ impl WhiteboxTool for LidarElevationSlice {
                 }
             }
         } else {
-            for i in 0..input.header.number_of_points as usize {
+            while let Some(pr) = input.next() {
+                let mut pr = pr?;
                 let mut class_val = out_class_value; // outside elevation slice
-                z = input.get_point_info(i).z;
+                let mut point_data = pr.get_point_data();
+                z = point_data.z;
                 if z >= minz && z <= maxz {
                     class_val = in_class_value; // inside elevation slice
                 }
-                let pr = input.get_record(i);
-                let pr2: LidarPointRecord;
-                match pr {
-                    LidarPointRecord::PointRecord0 { mut point_data }  => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord0 { point_data: point_data };
-
-                    },
-                    LidarPointRecord::PointRecord1 { mut point_data, gps_data } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord1 { point_data: point_data, gps_data: gps_data };
-                    },
-                    LidarPointRecord::PointRecord2 { mut point_data, rgb_data } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord2 { point_data: point_data, rgb_data: rgb_data };
-                    },
-                    LidarPointRecord::PointRecord3 { mut point_data, gps_data, rgb_data } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord3 { point_data: point_data,
-                            gps_data: gps_data, rgb_data: rgb_data};
-                    },
-                }
-                output.add_point_record(pr2);
+                point_data.set_classification(class_val);
+                pr.set_point_data(point_data);
+                output.add_point_record_with_extra_bytes(pr, input.get_extra_bytes())?;
                 if verbose {
-                    progress = (100.0_f64 * i as f64 / num_points) as i32;
+                    progress = (100.0_f64 * (input.num_points_read() - 1) as f64 / num_points) as i32;
                     if progress != old_progress {
                         println!("Saving data: {}%", progress);
                         old_progress = progress;
@@ -308,7 +291,7 @@ impl WhiteboxTool for LidarElevationSlice {
 
         if num_points_filtered > 0 {
             if verbose { println!("Writing output LAS file..."); }
-            let _ = match output.write() {
+            let _ = match output.close() {
                 Ok(_) => println!("Complete!"),
                 Err(e) => println!("error while writing: {:?}", e),
             };
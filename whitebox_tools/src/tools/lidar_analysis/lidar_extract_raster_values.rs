@@ -0,0 +1,324 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::io::{Error, ErrorKind};
+use lidar::*;
+use raster::*;
+use tools::*;
+
+/// Samples a raster at the location of each point in a LAS file and stores the
+/// value in the point's user data byte. Because user data is an unsigned 8-bit
+/// value, the raster value is first transformed to `(value - offset) / scale`,
+/// rounded, and clamped to the range 0-255; the default scale of 1 and offset
+/// of 0 suit rasters that already hold small integer values, such as class or
+/// zone maps. Points that fall outside of the raster, or on NoData, are
+/// assigned the `--background` value. Sampling is either by nearest cell
+/// (the default) or bilinear interpolation of the four nearest cell centres.
+pub struct LidarExtractRasterValues {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarExtractRasterValues {
+    pub fn new() -> LidarExtractRasterValues { // public constructor
+        let name = "LidarExtractRasterValues".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Attaches the values of a raster to the points of a LiDAR (LAS) file as user data.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input Raster File".to_owned(),
+            flags: vec!["--raster".to_owned()],
+            description: "Input raster file to sample.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output LiDAR file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Sampling Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Sampling method; options are 'nearest' and 'bilinear'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["nearest".to_owned(), "bilinear".to_owned()]),
+            default_value: Some("nearest".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Scale".to_owned(),
+            flags: vec!["--scale".to_owned()],
+            description: "Raster units per user data unit.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Offset".to_owned(),
+            flags: vec!["--offset".to_owned()],
+            description: "Raster value corresponding to a user data value of zero.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Background Value".to_owned(),
+            flags: vec!["--background".to_owned()],
+            description: "User data value assigned to points outside of the raster or on NoData cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("0".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las --raster=landcover.tif -o=output.las
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las --raster=dem.tif -o=output.las --method=bilinear --scale=2.0 --offset=100.0 --background=255", short_exe, name).replace("*", &sep);
+
+        LidarExtractRasterValues {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LidarExtractRasterValues {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self,
+               args: Vec<String>,
+               working_directory: &'a str,
+               verbose: bool)
+               -> Result<(), Error> {
+        let mut input_file: String = "".to_string();
+        let mut raster_file: String = "".to_string();
+        let mut output_file: String = "".to_string();
+        let mut bilinear = false;
+        let mut scale = 1f64;
+        let mut offset = 0f64;
+        let mut background = 0u8;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i + 1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-raster" || vec[0].to_lowercase() == "--raster" {
+                if keyval {
+                    raster_file = vec[1].to_string();
+                } else {
+                    raster_file = args[i + 1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i + 1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-method" || vec[0].to_lowercase() == "--method" {
+                let method = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                bilinear = method.contains("bilinear");
+            } else if vec[0].to_lowercase() == "-scale" || vec[0].to_lowercase() == "--scale" {
+                if keyval {
+                    scale = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    scale = args[i + 1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-offset" || vec[0].to_lowercase() == "--offset" {
+                if keyval {
+                    offset = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    offset = args[i + 1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-background" || vec[0].to_lowercase() == "--background" {
+                if keyval {
+                    background = vec[1].to_string().parse::<u8>().unwrap();
+                } else {
+                    background = args[i + 1].to_string().parse::<u8>().unwrap();
+                }
+            }
+        }
+
+        if scale == 0f64 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  "The scale parameter must be non-zero."));
+        }
+
+        if !input_file.contains(path::MAIN_SEPARATOR) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !raster_file.contains(path::MAIN_SEPARATOR) {
+            raster_file = format!("{}{}", working_directory, raster_file);
+        }
+        if !output_file.contains(path::MAIN_SEPARATOR) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if verbose {
+            println!("Reading input LAS file...");
+        }
+        let input = match LasFile::new(&input_file, "r") {
+            Ok(lf) => lf,
+            Err(err) => panic!("Error reading file {}: {}", input_file, err),
+        };
+        if verbose {
+            println!("Reading input raster file...");
+        }
+        let raster = Raster::new(&raster_file, "r")?;
+        let nodata = raster.configs.nodata;
+
+        let start = time::now();
+
+        let mut output = LasFile::initialize_using_file(&output_file, &input);
+
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+        let n_points = input.header.number_of_points as usize;
+        let mut num_background = 0usize;
+        for i in 0..n_points {
+            let p = input.get_point_info(i);
+            let z = if bilinear {
+                raster.get_value_bilinear(p.x, p.y)
+            } else {
+                raster.get_value(raster.get_row_from_y(p.y), raster.get_column_from_x(p.x))
+            };
+            let user_data = if z != nodata {
+                ((z - offset) / scale).round().max(0f64).min(255f64) as u8
+            } else {
+                num_background += 1;
+                background
+            };
+            let mut pr = input.get_record(i);
+            let mut point_data = pr.get_point_data();
+            point_data.user_data = user_data;
+            pr.set_point_data(point_data);
+            output.add_point_record(pr);
+            if verbose {
+                progress = (100.0_f64 * i as f64 / (n_points - 1) as f64) as i32;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose && num_background > 0 {
+            println!("{} points were outside of the raster or on NoData cells.", num_background);
+        }
+
+        if verbose {
+            println!("Writing output LAS file...");
+        }
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Complete!")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        println!("{}",
+                 &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
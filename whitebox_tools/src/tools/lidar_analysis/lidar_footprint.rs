@@ -0,0 +1,290 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::f64;
+use std::fs;
+use std::path;
+use std::io::{Error, ErrorKind};
+use lidar::*;
+use vector::*;
+use algorithms::{concave_hull, convex_hull, polygon_area};
+use structures::Point2D;
+use tools::*;
+
+/// Creates a vector polygon of the area covered by the points in a LiDAR (LAS)
+/// file. By default this is the convex hull of the points. If a maximum edge
+/// length (`--max_edge_length`) is specified, a concave footprint is created
+/// instead, by eroding the Delaunay triangulation of the points from the
+/// outside inwards, removing boundary triangles with an outer edge longer than
+/// this length, so that the footprint follows irregular tile edges and
+/// excludes large bays without data. The footprint is always a single polygon
+/// without holes. Withheld points are ignored.
+///
+/// If no input file is specified, the footprints of all of the LAS files in
+/// the working directory are created, one polygon per file, providing an
+/// index of a tiled data set. The output attribute table contains the file
+/// name (FILE_NAME), the number of points (NUM_PNTS) and the footprint area
+/// (AREA).
+pub struct LidarFootprint {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarFootprint {
+    pub fn new() -> LidarFootprint { // public constructor
+        let name = "LidarFootprint".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Creates a vector polygon of the convex hull or concave footprint of a LiDAR (LAS) file.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file; if omitted, all LAS files in the working directory are used.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polygons file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Maximum Edge Length (optional)".to_owned(),
+            flags: vec!["--max_edge_length".to_owned()],
+            description: "Optional maximum boundary edge length of a concave footprint; the convex hull is used if unspecified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las -o=footprint.shp
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -o=tile_index.shp --max_edge_length=10.0", short_exe, name).replace("*", &sep);
+
+        LidarFootprint {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LidarFootprint {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self,
+               args: Vec<String>,
+               working_directory: &'a str,
+               verbose: bool)
+               -> Result<(), Error> {
+        let mut input_file: String = "".to_string();
+        let mut output_file: String = "".to_string();
+        let mut max_edge_length = f64::INFINITY;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i + 1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i + 1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-max_edge_length" ||
+                      vec[0].to_lowercase() == "--max_edge_length" {
+                if keyval {
+                    max_edge_length = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    max_edge_length = args[i + 1].to_string().parse::<f64>().unwrap();
+                }
+            }
+        }
+
+        if max_edge_length <= 0f64 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  "The maximum edge length must be greater than zero."));
+        }
+
+        if !output_file.contains(path::MAIN_SEPARATOR) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let start = time::now();
+
+        let mut inputs = vec![];
+        if input_file.is_empty() {
+            if working_directory.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "This tool must be run by specifying either an individual input file or a working directory."));
+            }
+            for entry in fs::read_dir(working_directory)? {
+                let s = format!("{}", entry?.path().display());
+                if s.to_lowercase().ends_with(".las") || s.to_lowercase().ends_with(".laz") {
+                    inputs.push(s);
+                }
+            }
+            inputs.sort();
+        } else {
+            if !input_file.contains(path::MAIN_SEPARATOR) {
+                input_file = format!("{}{}", working_directory, input_file);
+            }
+            inputs.push(input_file.clone());
+        }
+        if inputs.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  "No LAS files were found in the working directory."));
+        }
+
+        let mut output = Shapefile::new(&output_file, "w")?;
+        output.header.shape_type = ShapeType::Polygon;
+        output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("FILE_NAME", FieldDataType::Text, 50u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("NUM_PNTS", FieldDataType::Int, 10u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("AREA", FieldDataType::Real, 18u8, 4u8));
+
+        for k in 0..inputs.len() {
+            input_file = inputs[k].clone();
+            if verbose {
+                println!("Creating footprint {} of {}: {}", k + 1, inputs.len(), input_file);
+            }
+            let input = match LasFile::new(&input_file, "r") {
+                Ok(lf) => lf,
+                Err(err) => panic!("Error reading file {}: {}", input_file, err),
+            };
+
+            let n_points = input.header.number_of_points as usize;
+            let mut points: Vec<Point2D> = Vec::with_capacity(n_points);
+            for i in 0..n_points {
+                let p: PointData = input[i];
+                if !p.class_bit_field.withheld() {
+                    points.push(Point2D::new(p.x, p.y));
+                }
+            }
+
+            let footprint = if max_edge_length.is_finite() {
+                concave_hull(&points, max_edge_length)
+            } else {
+                convex_hull(&points)
+            };
+            if footprint.len() < 4 {
+                if verbose {
+                    println!("Warning: {} does not contain enough points to create a footprint.", input_file);
+                }
+                continue;
+            }
+
+            let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+            geom.add_part(&footprint);
+            output.add_record(geom);
+            let short_name = path::Path::new(&input_file)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string();
+            output.attributes.add_record(vec![FieldData::Int(output.num_records as i32),
+                                              FieldData::Text(short_name),
+                                              FieldData::Int(points.len() as i32),
+                                              FieldData::Real(polygon_area(&footprint))], false);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose {
+            println!("Writing output file...");
+        }
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Complete!")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        println!("{}",
+                 &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
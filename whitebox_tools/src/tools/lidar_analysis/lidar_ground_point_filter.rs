@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: June 2, 2017
-Last Modified: December 15, 2017
+Last Modified: October 18, 2026
 License: MIT
 */
 extern crate time;
@@ -20,6 +20,15 @@ use lidar::*;
 use tools::*;
 use structures::FixedRadiusSearch2D;
 
+/// Identifies the ground points within a LiDAR (LAS) file using a slope-based
+/// method, outputting the ground points.
+///
+/// If no input file is specified, the LAS files in the working directory are
+/// treated as the tiles of a larger data set, e.g. those created by *LidarTile*,
+/// and the filtered tiles are written, with the same names, to the output
+/// directory (`--outdir`). Each tile is filtered along with the points of the
+/// neighbouring tiles lying within three times the search radius of its edges,
+/// which avoids edge artefacts, and only the tile's own points are output.
 pub struct LidarGroundPointFilter {
     name: String,
     description: String,
@@ -38,10 +47,10 @@ impl LidarGroundPointFilter {
         parameters.push(ToolParameter{
             name: "Input File".to_owned(), 
             flags: vec!["-i".to_owned(), "--input".to_owned()], 
-            description: "Input LiDAR file.".to_owned(),
+            description: "Input LiDAR file; if omitted, all of the LAS files in the working directory are filtered as tiles.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
             default_value: None,
-            optional: false
+            optional: true
         });
 
         parameters.push(ToolParameter{
@@ -50,7 +59,16 @@ impl LidarGroundPointFilter {
             description: "Output LiDAR file.".to_owned(),
             parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
             default_value: None,
-            optional: false
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Directory".to_owned(), 
+            flags: vec!["--outdir".to_owned()], 
+            description: "Output directory for the tiles, when no input file is specified.".to_owned(),
+            parameter_type: ParameterType::Directory,
+            default_value: None,
+            optional: true
         });
 
         parameters.push(ToolParameter{
@@ -87,7 +105,8 @@ impl LidarGroundPointFilter {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=\"input.las\" -o=\"output.las\" --radius=10.0", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=\"input.las\" -o=\"output.las\" --radius=10.0
+>>.*{0} -r={1} -v --wd=\"*path*to*tiles*\" --outdir=\"*path*to*ground*\" --radius=10.0", short_exe, name).replace("*", &sep);
     
         LidarGroundPointFilter { 
             name: name, 
@@ -137,6 +156,7 @@ impl WhiteboxTool for LidarGroundPointFilter {
     fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
         let mut input_file: String = "".to_string();
         let mut output_file: String = "".to_string();
+        let mut output_dir: String = "".to_string();
         let mut search_radius: f64 = -1.0;
         let mut height_threshold: f64 = 1.0;
         let mut slope_threshold: f64 = 15.0;
@@ -164,6 +184,12 @@ impl WhiteboxTool for LidarGroundPointFilter {
                 } else {
                     output_file = args[i+1].to_string();
                 }
+            } else if vec[0].to_lowercase() == "-outdir" || vec[0].to_lowercase() == "--outdir" {
+                if keyval {
+                    output_dir = vec[1].to_string();
+                } else {
+                    output_dir = args[i+1].to_string();
+                }
             } else if vec[0].to_lowercase() == "-radius" || vec[0].to_lowercase() == "--radius" {
                 if keyval {
                     search_radius = vec[1].to_string().parse::<f64>().unwrap();
@@ -192,25 +218,88 @@ impl WhiteboxTool for LidarGroundPointFilter {
         }
 
         let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) {
-            output_file = format!("{}{}", working_directory, output_file);
+        let mut tiles = LasTileSet::default();
+        let mut inputs = vec![];
+        let mut outputs = vec![];
+        if input_file.is_empty() {
+            if working_directory.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "This tool must be run by specifying either an individual input file or a working directory."));
+            }
+            if output_dir.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "An output directory (--outdir) must be specified when filtering the tiles of the working directory."));
+            }
+            if !output_dir.contains(sep) {
+                output_dir = format!("{}{}", working_directory, output_dir);
+            }
+            tiles = LasTileSet::new(working_directory)?;
+            inputs = tiles.file_names.clone();
+            outputs = tiles.get_output_file_names(&output_dir)?;
+        } else {
+            if output_file.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "An output file (--output) must be specified when filtering an input file."));
+            }
+            if !input_file.contains(sep) {
+                input_file = format!("{}{}", working_directory, input_file);
+            }
+            if !output_file.contains(sep) {
+                output_file = format!("{}{}", working_directory, output_file);
+            }
+            inputs.push(input_file.clone());
+            outputs.push(output_file.clone());
         }
 
-        if verbose { println!("Reading input LAS file..."); }
-        let input = match LasFile::new(&input_file, "r") {
-            Ok(lf) => lf,
-            Err(err) => panic!("Error reading file {}: {}", input_file, err),
-        };
+        // the filter compares the residuals of the points within the search radius,
+        // which are found by an erosion and a dilation using the same radius, such
+        // that it depends upon the points within three times the radius
+        let buffer = 3f64 * search_radius;
+
+        slope_threshold = slope_threshold.to_radians().tan();
 
         let start = time::now();
 
-        if verbose { println!("Performing analysis..."); }
+        for k in 0..inputs.len() {
+            input_file = inputs[k].clone();
+            output_file = outputs[k].clone();
 
-        slope_threshold = slope_threshold.to_radians().tan();
+            if verbose && inputs.len() > 1 {
+                println!("Filtering {} of {} ({:.2}%) {}", k+1, inputs.len(), (k+1) as f64 / inputs.len() as f64 * 100f64, input_file);
+            }
+
+            if verbose && inputs.len() == 1 { println!("Reading input LAS file..."); }
+            let (input, num_core_points) = if tiles.is_empty() {
+                match LasFile::new(&input_file, "r") {
+                    Ok(lf) => {
+                        let n = lf.header.number_of_points as usize;
+                        (lf, n)
+                    },
+                    Err(err) => panic!("Error reading file {}: {}", input_file, err),
+                }
+            } else {
+                tiles.read_with_buffer(k, buffer)?
+            };
+
+            if verbose && inputs.len() == 1 { println!("Performing analysis..."); }
+            self.filter_points(input, num_core_points, &output_file, search_radius, height_threshold, slope_threshold, verbose)?;
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
 
+        println!("{}", &format!("Elapsed Time (including I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+impl LidarGroundPointFilter {
+    // Filters the points of a file, which may be followed by buffer points read
+    // from neighbouring tiles, writing the ground points among the first
+    // num_core_points points to the output file.
+    fn filter_points(&self, input: LasFile, num_core_points: usize, output_file: &str, search_radius: f64,
+                     height_threshold: f64, slope_threshold: f64, verbose: bool) -> Result<(), Error> {
         let n_points = input.header.number_of_points as usize;
         let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
 
@@ -386,13 +475,13 @@ impl WhiteboxTool for LidarGroundPointFilter {
         }
 
 
-        // now output the data
+        // now output the data; buffer points are only used as context
         let mut output = LasFile::initialize_using_file(&output_file, &input);
         output.header.system_id = "EXTRACTION".to_string();
 
-        for i in 0..n_points {
+        for i in 0..num_core_points {
             if !is_off_terrain[i] {
-                output.add_point_record(input.get_record(i));
+                output.add_point_record_with_extra_bytes(input.get_record(i), input.get_extra_bytes(i));
             }
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
@@ -403,18 +492,56 @@ impl WhiteboxTool for LidarGroundPointFilter {
             }
         }
 
-        let end = time::now();
-        let elapsed_time = end - start;
-
         if verbose { println!("Writing output LAS file..."); }
         let _ = match output.write() {
-            Ok(_) => println!("Complete!"),
-            Err(e) => println!("error while writing: {:?}", e),
+            Ok(_) => if verbose { println!("Complete!") },
+            Err(e) => return Err(e),
         };
 
-        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
-
         Ok(())
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::LidarGroundPointFilter;
+    use lidar::*;
+    use std::env;
+    use std::fs;
+    use std::io::ErrorKind;
+    use tools::WhiteboxTool;
+
+    #[test]
+    fn test_missing_outputs_are_rejected() {
+        let dir = env::temp_dir();
+        let input_file = dir.join("wbt_ground_filter_missing_output_test.las").to_str().unwrap().to_string();
+        let tool = LidarGroundPointFilter::new();
+        // both are rejected before the input is read, which here does not exist
+        let err = tool.run(vec![format!("-i={}", input_file), "--radius=2.0".to_string()], "", false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let working_directory = format!("{}{}", dir.to_str().unwrap(), ::std::path::MAIN_SEPARATOR);
+        let err = tool.run(vec!["--radius=2.0".to_string()], &working_directory, false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_write_errors_are_returned() {
+        let dir = env::temp_dir();
+        let input_file = dir.join("wbt_ground_filter_write_error_test.las").to_str().unwrap().to_string();
+        let mut las = LasFile::new(&input_file, "w").unwrap();
+        las.add_header(LasHeader { project_id_used: true, ..Default::default() });
+        for row in 0..30 {
+            for col in 0..30 {
+                let point_data = PointData { x: col as f64, y: row as f64, z: 0.1 * col as f64, ..Default::default() };
+                las.add_point_record(LidarPointRecord::PointRecord0 { point_data: point_data });
+            }
+        }
+        las.write().unwrap();
+
+        // the output is a directory, which cannot be written
+        let output_file = dir.to_str().unwrap().to_string();
+        let result = LidarGroundPointFilter::new().run(vec![format!("-i={}", input_file), format!("-o={}", output_file), "--radius=2.0".to_string()], "", false);
+        fs::remove_file(&input_file).unwrap();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,410 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+extern crate num_cpus;
+
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread;
+use lidar::*;
+use structures::{BoundingBox, FixedRadiusSearch2D, Point2D, RTree, Triangulation};
+use tools::*;
+
+/// Normalizes the elevations of the points in a LiDAR (LAS) file to heights
+/// above the ground. The ground surface is interpolated from the points
+/// classified as ground (class 2), e.g. by *LidarGroundPointFilter*, either
+/// linearly within the triangles of their triangulated irregular network (TIN;
+/// the default), or by inverse-distance weighting (IDW) of the ground points
+/// within the search radius (`--radius`). Points beyond the edges of the TIN, or
+/// with no ground points within the search radius, take the elevation of their
+/// nearest ground point.
+///
+/// By default, the elevation (z) of each point is replaced with its height above
+/// the ground. Alternatively, with the `--attribute` flag, the elevations are kept
+/// and the heights are written to a 'HeightAboveGround' extra bytes attribute.
+/// The output of this tool is the usual input of *LidarCanopyHeightModel*.
+pub struct LidarHeightAboveGround {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarHeightAboveGround {
+    pub fn new() -> LidarHeightAboveGround { // public constructor
+        let name = "LidarHeightAboveGround".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Normalizes LiDAR points to heights above a ground surface interpolated from the ground-classified points.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file, with classified ground points.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output LiDAR file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Ground Interpolation Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Ground surface interpolation method; options are 'tin' (default) and 'idw'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["tin".to_owned(), "idw".to_owned()]),
+            default_value: Some("tin".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Search Radius".to_owned(),
+            flags: vec!["--radius".to_owned()],
+            description: "Search radius for IDW interpolation of the ground points.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("5.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "IDW Weight (Exponent) Value".to_owned(),
+            flags: vec!["--weight".to_owned()],
+            description: "IDW weight value.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output heights as an extra bytes attribute?".to_owned(),
+            flags: vec!["--attribute".to_owned()],
+            description: "Optional boolean flag indicating whether the heights should be written to a 'HeightAboveGround' attribute rather than replacing the elevations.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las -o=normalized.las
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las -o=output.las --method=idw --radius=10.0 --attribute", short_exe, name).replace("*", &sep);
+
+        LidarHeightAboveGround {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for LidarHeightAboveGround {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file: String = "".to_string();
+        let mut output_file: String = "".to_string();
+        let mut method = "tin".to_string();
+        let mut search_radius = 5.0;
+        let mut weight = 2.0;
+        let mut use_attribute = false;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 { keyval = true; }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-method" || vec[0].to_lowercase() == "--method" {
+                if keyval {
+                    method = vec[1].to_lowercase();
+                } else {
+                    method = args[i+1].to_lowercase();
+                }
+            } else if vec[0].to_lowercase() == "-radius" || vec[0].to_lowercase() == "--radius" {
+                if keyval {
+                    search_radius = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    search_radius = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-weight" || vec[0].to_lowercase() == "--weight" {
+                if keyval {
+                    weight = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    weight = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-attribute" || vec[0].to_lowercase() == "--attribute" {
+                use_attribute = !(keyval && vec[1].to_lowercase() == "false");
+            }
+        }
+
+        if method != "tin" && method != "idw" {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The interpolation method must be either 'tin' or 'idw'."));
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep = path::MAIN_SEPARATOR;
+        if !input_file.contains(sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading input LAS file..."); }
+        let input = match LasFile::new(&input_file, "r") {
+            Ok(lf) => lf,
+            Err(err) => return Err(Error::new(err.kind(), format!("Error reading file {}: {}", input_file, err))),
+        };
+
+        let start = time::now();
+
+        if verbose { println!("Performing analysis..."); }
+
+        let n_points = input.header.number_of_points as usize;
+        let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
+
+        let mut ground_points: Vec<Point2D> = vec![];
+        let mut ground_z: Vec<f64> = vec![];
+        for i in 0..n_points {
+            let p: PointData = input.get_point_info(i);
+            if p.classification() == 2 && !p.class_bit_field.withheld() {
+                ground_points.push(Point2D::new(p.x, p.y));
+                ground_z.push(p.z);
+            }
+        }
+        if ground_points.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input file does not contain any ground-classified (class 2) points."));
+        }
+
+        // the nearest ground point gives the ground elevation wherever the
+        // interpolation method does not
+        let nearest_tree = RTree::bulk_load(ground_points.iter().enumerate().map(|(i, p)| (BoundingBox::new(p.x, p.y, p.x, p.y), i)).collect());
+
+        let mut triangulation = None;
+        let mut triangle_tree = RTree::new();
+        let mut frs: FixedRadiusSearch2D<usize> = FixedRadiusSearch2D::new(search_radius);
+        if method == "tin" {
+            if verbose { println!("Triangulating ground points..."); }
+            let t = match Triangulation::new(&ground_points) {
+                Some(t) => t,
+                None => return Err(Error::new(ErrorKind::InvalidInput,
+                            "The ground points could not be triangulated; at least three non-collinear points are required.")),
+            };
+            triangle_tree = RTree::bulk_load((0..t.num_triangles()).map(|tri| {
+                let (p1, p2, p3) = (ground_points[t.triangles[3 * tri]],
+                                    ground_points[t.triangles[3 * tri + 1]],
+                                    ground_points[t.triangles[3 * tri + 2]]);
+                (BoundingBox::from_points(&[p1, p2, p3]), tri)
+            }).collect());
+            triangulation = Some(t);
+        } else {
+            for i in 0..ground_points.len() {
+                frs.insert(ground_points[i].x, ground_points[i].y, i);
+            }
+        }
+
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+        let input = Arc::new(input);
+        let ground_points = Arc::new(ground_points);
+        let ground_z = Arc::new(ground_z);
+        let nearest_tree = Arc::new(nearest_tree);
+        let triangulation = Arc::new(triangulation);
+        let triangle_tree = Arc::new(triangle_tree);
+        let frs = Arc::new(frs);
+        let num_procs = num_cpus::get();
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let ground_points = ground_points.clone();
+            let ground_z = ground_z.clone();
+            let nearest_tree = nearest_tree.clone();
+            let triangulation = triangulation.clone();
+            let triangle_tree = triangle_tree.clone();
+            let frs = frs.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for point_num in (0..n_points).filter(|point_num| point_num % num_procs == tid) {
+                    let p: PointData = input.get_point_info(point_num);
+                    let mut ground = f64::NAN;
+                    match *triangulation {
+                        Some(ref t) => {
+                            for &tri in triangle_tree.search_point(p.x, p.y) {
+                                let (i1, i2, i3) = (t.triangles[3 * tri], t.triangles[3 * tri + 1], t.triangles[3 * tri + 2]);
+                                let (p1, p2, p3) = (ground_points[i1], ground_points[i2], ground_points[i3]);
+                                let det = (p2.y - p3.y) * (p1.x - p3.x) + (p3.x - p2.x) * (p1.y - p3.y);
+                                if det == 0f64 {
+                                    continue;
+                                }
+                                // barycentric coordinates of the point
+                                let l1 = ((p2.y - p3.y) * (p.x - p3.x) + (p3.x - p2.x) * (p.y - p3.y)) / det;
+                                let l2 = ((p3.y - p1.y) * (p.x - p3.x) + (p1.x - p3.x) * (p.y - p3.y)) / det;
+                                let l3 = 1f64 - l1 - l2;
+                                if l1 >= -1e-9 && l2 >= -1e-9 && l3 >= -1e-9 {
+                                    ground = l1 * ground_z[i1] + l2 * ground_z[i2] + l3 * ground_z[i3];
+                                    break;
+                                }
+                            }
+                        },
+                        None => {
+                            let ret = frs.search(p.x, p.y);
+                            let mut val = 0f64;
+                            let mut sum_weights = 0f64;
+                            for j in 0..ret.len() {
+                                let dist = ret[j].1;
+                                if dist > 0f64 {
+                                    val += ground_z[ret[j].0] / dist.powf(weight);
+                                    sum_weights += 1f64 / dist.powf(weight);
+                                } else {
+                                    val = ground_z[ret[j].0];
+                                    sum_weights = 1f64;
+                                    break;
+                                }
+                            }
+                            if sum_weights > 0f64 {
+                                ground = val / sum_weights;
+                            }
+                        },
+                    }
+                    if ground.is_nan() {
+                        ground = ground_z[*nearest_tree.nearest(p.x, p.y, 1)[0].1];
+                    }
+                    tx.send((point_num, p.z - ground)).unwrap();
+                }
+            });
+        }
+
+        let mut heights = vec![0f64; n_points];
+        for i in 0..n_points {
+            let data = rx.recv().unwrap();
+            heights[data.0] = data.1;
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Interpolating ground: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // now output the data
+        let attribute_name = "HeightAboveGround";
+        let mut output = LasFile::initialize_using_file(&output_file, &input);
+        if use_attribute {
+            output.ensure_extra_byte_attribute(ExtraBytesDescriptor::new(attribute_name, ExtraByteType::F32, "Height above ground"))?;
+        }
+
+        for i in 0..n_points {
+            if use_attribute {
+                output.add_point_record_with_extra_bytes(input.get_record(i), input.get_extra_bytes(i));
+                output.set_extra_byte_value(i, attribute_name, heights[i])?;
+            } else {
+                let mut pr = input.get_record(i);
+                let mut point_data = pr.get_point_data();
+                point_data.z = heights[i];
+                pr.set_point_data(point_data);
+                output.add_point_record_with_extra_bytes(pr, input.get_extra_bytes(i));
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Saving data: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Writing output LAS file..."); }
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Complete!") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
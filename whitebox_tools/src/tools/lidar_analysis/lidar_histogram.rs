@@ -230,7 +230,7 @@ impl WhiteboxTool for LidarHistogram {
             z = match parameter_mode {
                 0 => val.z,
                 1 => val.intensity as f64,
-                2 => val.scan_angle as f64,
+                2 => val.scan_angle_degrees(),
                 _ => val.classification() as f64,
             };
             if z < min { min = z; }
@@ -257,7 +257,7 @@ impl WhiteboxTool for LidarHistogram {
                 z = match parameter_mode {
                     0 => val.z,
                     1 => val.intensity as f64,
-                    _ => val.scan_angle as f64,
+                    _ => val.scan_angle_degrees(),
                 };
                 bin = ((z - min) / bin_width).floor() as isize;
                 freq_data[bin as usize] += 1;
@@ -318,7 +318,7 @@ impl WhiteboxTool for LidarHistogram {
                 z = match parameter_mode {
                     0 => val.z,
                     1 => val.intensity as f64,
-                    _ => val.scan_angle as f64,
+                    _ => val.scan_angle_degrees(),
                 };
                 bin = ((z - min) / bin_width).floor() as isize;
                 if bin >= 0 && bin < num_bins as isize {
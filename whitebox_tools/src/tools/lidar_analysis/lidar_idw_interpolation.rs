@@ -2,18 +2,16 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: July 3, 2017
-Last Modified: December 23, 2017
+Last Modified: October 18, 2026
 License: MIT
 
 NOTES: Add the ability to:
 Exclude points based on max scan angle divation
-Interpolate all LAS files within a directory (i.e. directory input rather than single file).
 */
 extern crate time;
 extern crate num_cpus;
 
 use std::env;
-use std::fs;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
@@ -25,6 +23,16 @@ use raster::*;
 use structures::FixedRadiusSearch2D;
 use tools::*;
 
+/// Interpolates a raster from the points of a LiDAR (LAS) file using an
+/// inverse-distance weighted (IDW) scheme.
+///
+/// If no input file is specified, the LAS files in the working directory are
+/// treated as the tiles of a larger data set, e.g. those created by *LidarTile*,
+/// and each is interpolated to a raster of the same name. Each tile is read
+/// along with the points of the neighbouring tiles lying within the search
+/// radius of its edges, and the grids of the output rasters are aligned to
+/// multiples of the grid resolution, so that the rasters can be mosaicked
+/// without edge artefacts.
 pub struct LidarIdwInterpolation {
     name: String,
     description: String,
@@ -45,7 +53,7 @@ impl LidarIdwInterpolation {
         parameters.push(ToolParameter{
             name: "Input File".to_owned(), 
             flags: vec!["-i".to_owned(), "--input".to_owned()], 
-            description: "Input LiDAR file (including extension).".to_owned(),
+            description: "Input LiDAR file (including extension); if omitted, all of the LAS files in the working directory are interpolated as tiles.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
             default_value: None,
             optional: true
@@ -336,6 +344,7 @@ impl WhiteboxTool for LidarIdwInterpolation {
 
         let start = time::now();
 
+        let mut tiles = LasTileSet::default();
         let mut inputs = vec![];
         let mut outputs = vec![];
         if input_file.is_empty() {
@@ -343,21 +352,17 @@ impl WhiteboxTool for LidarIdwInterpolation {
                 return Err(Error::new(ErrorKind::InvalidInput,
                     "This tool must be run by specifying either an individual input file or a working directory."));
             }
-            match fs::read_dir(working_directory) {
-                Err(why) => println!("! {:?}", why.kind()),
-                Ok(paths) => for path in paths {
-                    let s = format!("{:?}", path.unwrap().path());
-                    if s.replace("\"", "").to_lowercase().ends_with(".las") {
-                        inputs.push(format!("{:?}", s.replace("\"", "")));
-                        outputs.push(inputs[inputs.len()-1].replace(".las", ".tif").replace(".LAS", ".tif"))
-                    }
-                },
+            // the files of the working directory are treated as tiles, each of which is
+            // interpolated using the points of its neighbours near its edges
+            tiles = LasTileSet::new(working_directory)?;
+            for file_name in &tiles.file_names {
+                inputs.push(file_name.clone());
+                outputs.push(file_name.replace(".las", ".tif").replace(".LAS", ".tif").replace(".laz", ".tif").replace(".LAZ", ".tif"));
             }
-            // return Ok(());
         } else {
             inputs.push(input_file.clone());
             if output_file.is_empty() {
-                output_file = input_file.clone().replace(".las", ".tif").replace(".LAS", ".tif");
+                output_file = input_file.clone().replace(".las", ".tif").replace(".LAS", ".tif").replace(".laz", ".tif").replace(".LAZ", ".tif");
             }
             outputs.push(output_file);
         }
@@ -380,9 +385,14 @@ impl WhiteboxTool for LidarIdwInterpolation {
             if verbose && inputs.len() == 1 {
                 println!("Reading input LAS file...");
             }
-            let input = match LasFile::new(&input_file, "r") {
-                Ok(lf) => lf,
-                Err(err) => panic!("Error reading file {}: {}", input_file, err),
+            let input = if tiles.is_empty() {
+                match LasFile::new(&input_file, "r") {
+                    Ok(lf) => lf,
+                    Err(err) => panic!("Error reading file {}: {}", input_file, err),
+                }
+            } else {
+                // the buffer holds the points within the search radius of any of the tile's cells
+                tiles.read_with_buffer(k, search_radius + grid_res)?.0
             };
 
             let start_run = time::now();
@@ -458,7 +468,7 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                 }
                             }
                         }
-                        interp_vals.push(p.scan_angle as f64);
+                        interp_vals.push(p.scan_angle_degrees());
                         if verbose {
                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                             if progress != old_progress {
@@ -517,10 +527,21 @@ impl WhiteboxTool for LidarIdwInterpolation {
                 }
             }
 
-            let west: f64 = input.header.min_x;
-            let north: f64 = input.header.max_y;
-            let rows: isize = (((north - input.header.min_y) / grid_res).ceil()) as isize;
-            let columns: isize = (((input.header.max_x - west) / grid_res).ceil()) as isize;
+            let (west, north, rows, columns): (f64, f64, isize, isize);
+            if tiles.is_empty() {
+                west = input.header.min_x;
+                north = input.header.max_y;
+                rows = (((north - input.header.min_y) / grid_res).ceil()) as isize;
+                columns = (((input.header.max_x - west) / grid_res).ceil()) as isize;
+            } else {
+                // the grids of the tiles are aligned to multiples of the resolution, so
+                // that the output rasters can be mosaicked
+                let extent = tiles.extents[k];
+                west = (extent.min_x / grid_res).floor() * grid_res;
+                north = (extent.max_y / grid_res).ceil() * grid_res;
+                rows = (((north - extent.min_y) / grid_res).ceil() as isize).max(1);
+                columns = (((extent.max_x - west) / grid_res).ceil() as isize).max(1);
+            }
             let south: f64 = north - rows as f64 * grid_res;
             let east = west + columns as f64 * grid_res;
             let nodata = -32768.0f64;
@@ -569,8 +590,8 @@ impl WhiteboxTool for LidarIdwInterpolation {
                     for row in starting_row..ending_row {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            x = west + col as f64 * grid_res + 0.5;
-                            y = north - row as f64 * grid_res - 0.5;
+                            x = west + (col as f64 + 0.5) * grid_res;
+                            y = north - (row as f64 + 0.5) * grid_res;
                             let ret = frs.search(x, y);
                             if ret.len() > 0 {
                                 sum_weights = 0.0;
@@ -0,0 +1,193 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::fs;
+use std::path;
+use std::io::{Error, ErrorKind};
+use lidar::*;
+use tools::*;
+
+/// Creates a spatial index of the points in a LiDAR (LAS or LAZ) file, which is
+/// stored in a sidecar file named by appending '.wbi' to the file name, in the
+/// spirit of the '.lax' files of LASindex. The index divides the extent of the
+/// file into a grid of square cells, each holding about 5,000 points on
+/// average, and records the ranges of point records falling in each cell.
+/// Tools that read the points within an area, e.g. *ClipLidarToPolygon*, use
+/// the index, when it exists, to read only the records of the cells
+/// overlapping the area rather than the whole file. This is most effective
+/// when the points of a file are stored in acquisition or spatially sorted
+/// order, as they usually are.
+///
+/// If no input file is specified, all of the LAS files in the working directory
+/// are indexed. An index is deleted whenever its file is overwritten by a tool,
+/// and an index that no longer matches its file is ignored.
+pub struct LidarIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarIndex {
+    pub fn new() -> LidarIndex { // public constructor
+        let name = "LidarIndex".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Creates a spatial index sidecar file for LiDAR (LAS) files, allowing the points within an area to be read quickly.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file; if omitted, all LAS files in the working directory are indexed.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\"", short_exe, name).replace("*", &sep);
+
+        LidarIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LidarIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self,
+               args: Vec<String>,
+               working_directory: &'a str,
+               verbose: bool)
+               -> Result<(), Error> {
+        let mut input_file: String = "".to_string();
+
+        // read the arguments
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i + 1].to_string();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let start = time::now();
+
+        let mut inputs = vec![];
+        if input_file.is_empty() {
+            if working_directory.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "This tool must be run by specifying either an individual input file or a working directory."));
+            }
+            for entry in fs::read_dir(working_directory)? {
+                let s = format!("{}", entry?.path().display());
+                if s.to_lowercase().ends_with(".las") || s.to_lowercase().ends_with(".laz") {
+                    inputs.push(s);
+                }
+            }
+            inputs.sort();
+        } else {
+            if !input_file.contains(path::MAIN_SEPARATOR) {
+                input_file = format!("{}{}", working_directory, input_file);
+            }
+            inputs.push(input_file.clone());
+        }
+        if inputs.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  "No LAS files were found in the working directory."));
+        }
+
+        for k in 0..inputs.len() {
+            input_file = inputs[k].clone();
+            let index = match LasIndex::create(&input_file) {
+                Ok(index) => index,
+                Err(err) => return Err(Error::new(err.kind(), format!("Error reading file {}: {}", input_file, err))),
+            };
+            index.write(&input_file)?;
+            if verbose {
+                println!("Indexed file {} of {}: {} ({} x {} cells, {} point ranges)",
+                         k + 1, inputs.len(), input_file, index.columns, index.rows, index.num_intervals());
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose {
+            println!("Complete!");
+        }
+
+        println!("{}",
+                 &format!("Elapsed Time (including I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
@@ -192,7 +192,7 @@ impl WhiteboxTool for LidarInfo {
             input_file = format!("{}{}", working_directory, input_file);
         }
 
-        if output_file.len() == 0 { output_file = input_file.replace(".las", "_summary.html"); }
+        if output_file.len() == 0 { output_file = input_file.replace(".las", "_summary.html").replace(".laz", "_summary.html"); }
 
 
         let f = File::create(output_file.clone())?;
@@ -282,14 +282,14 @@ impl WhiteboxTool for LidarInfo {
         let mut ret: u8;
         let mut nrets: u8;
         let mut p: PointData;
-        let mut ret_array: [i32; 5] = [0; 5];
+        let mut ret_array: [i32; 15] = [0; 15];
         let mut class_array: [i32; 256] = [0; 256];
         for i in 0..input.header.number_of_points as usize {
             p = input[i]; //.get_point_info(i);
             ret = p.return_number();
-            if ret > 5 {
+            if ret > 15 {
                 // Return is too high
-                ret = 5;
+                ret = 15;
             }
             ret_array[(ret - 1) as usize] += 1;
             nrets = p.number_of_returns();
@@ -325,7 +325,7 @@ impl WhiteboxTool for LidarInfo {
         </tr>";
         writer.write_all(s.as_bytes())?;
 
-        for i in 0..5 {
+        for i in 0..15 {
             if ret_array[i] > 0 {
                 let s1 = &format!("<tr>
                     <td>{}</td>
@@ -430,7 +430,7 @@ impl WhiteboxTool for LidarNearestNeighbourGridding {
                             }
                         }
                     }
-                    interp_vals.push(p.scan_angle as f64);
+                    interp_vals.push(p.scan_angle_degrees());
                     if verbose {
                         progress = (100.0_f64 * i as f64 / num_points) as i32;
                         if progress != old_progress {
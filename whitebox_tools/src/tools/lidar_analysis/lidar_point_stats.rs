@@ -0,0 +1,561 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+extern crate num_cpus;
+
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread;
+use lidar::*;
+use raster::*;
+use tools::*;
+
+/// Calculates a suite of gridded point-cloud metrics, of the kind used in
+/// forest inventory modelling, from a LiDAR (LAS) file whose elevations have been
+/// normalized to heights above the ground (see *LidarHeightAboveGround*). The
+/// points are binned into grid cells in a single pass and one raster is output
+/// for each of the chosen metrics (`--metrics`), named after the output file
+/// with the metric appended, e.g. 'metrics_p95.tif' for the output 'metrics.tif'.
+/// The available metrics are:
+///
+/// * count: the number of points.
+/// * min, max, mean, stdev, skewness, kurtosis: statistics of the point heights.
+///   Skewness and kurtosis are the standardized third and fourth moments (the
+///   kurtosis of a normal distribution is 3).
+/// * median and p1 to p99, e.g. p10, p25, p95: height percentiles, i.e. the
+///   smallest height at or below which the given percentage of the heights lie.
+/// * cover: canopy cover, the percentage of first returns higher than the cover
+///   height (`--cover_height`).
+/// * first_ratio, last_ratio, single_ratio: the proportions of the points that
+///   are first (or only) returns, last (or only) returns, and only returns.
+/// * int_min, int_max, int_mean, int_stdev, int_median and int_p1 to int_p99:
+///   statistics of the point intensities.
+///
+/// Points that are withheld or classified as noise are excluded. The height
+/// statistics only include the points at least as high as the minimum height
+/// (`--min_height`), e.g. a 2 m height break that excludes ground and
+/// understorey points; the other metrics include all of the points. Cells
+/// without points are assigned NoData, as are the skewness and kurtosis of
+/// cells with no variation in height. The grid is aligned to multiples of the
+/// grid resolution, so that the rasters of adjacent tiles can be mosaicked.
+pub struct LidarPointStats {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarPointStats {
+    pub fn new() -> LidarPointStats { // public constructor
+        let name = "LidarPointStats".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Calculates gridded height, cover, return and intensity metrics of height-normalized LiDAR points, outputting one raster per metric.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file, with heights above the ground as elevations.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file name, to which the name of each metric is appended.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Metrics".to_owned(),
+            flags: vec!["--metrics".to_owned()],
+            description: "Comma-separated list of metrics: count, min, max, mean, stdev, skewness, kurtosis, median, pNN, cover, first_ratio, last_ratio, single_ratio, int_min, int_max, int_mean, int_stdev, int_median and int_pNN.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: Some("mean,stdev,skewness,kurtosis,p10,p25,p50,p75,p90,p95,p99,cover".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Grid Resolution".to_owned(),
+            flags: vec!["--resolution".to_owned()],
+            description: "Output raster's grid resolution.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("10.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Minimum Height (optional)".to_owned(),
+            flags: vec!["--min_height".to_owned()],
+            description: "Optional minimum height of the points included in the height statistics.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Cover Height".to_owned(),
+            flags: vec!["--cover_height".to_owned()],
+            description: "Height above which first returns are counted as canopy in the cover metric.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=normalized.las -o=metrics.tif --resolution=20.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=normalized.las -o=metrics.tif --metrics='p50,p95,cover,first_ratio,int_mean' --min_height=2.0", short_exe, name).replace("*", &sep);
+
+        LidarPointStats {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for LidarPointStats {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file: String = "".to_string();
+        let mut output_file: String = "".to_string();
+        let mut metrics_list = "mean,stdev,skewness,kurtosis,p10,p25,p50,p75,p90,p95,p99,cover".to_string();
+        let mut grid_res: f64 = 10.0;
+        let mut min_height = f64::NEG_INFINITY;
+        let mut cover_height = 2.0;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 { keyval = true; }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-metrics" || vec[0].to_lowercase() == "--metrics" {
+                if keyval {
+                    metrics_list = vec[1].to_string();
+                } else {
+                    metrics_list = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-resolution" || vec[0].to_lowercase() == "--resolution" {
+                if keyval {
+                    grid_res = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    grid_res = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-min_height" || vec[0].to_lowercase() == "--min_height" {
+                if keyval {
+                    min_height = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    min_height = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-cover_height" || vec[0].to_lowercase() == "--cover_height" {
+                if keyval {
+                    cover_height = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    cover_height = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            }
+        }
+
+        let mut metrics: Vec<Metric> = vec![];
+        for s in metrics_list.split(",") {
+            let s = s.trim().to_lowercase();
+            if s.is_empty() {
+                continue;
+            }
+            let metric = match Metric::from_name(&s) {
+                Some(metric) => metric,
+                None => return Err(Error::new(ErrorKind::InvalidInput,
+                            format!("Unrecognized metric '{}'.", s))),
+            };
+            if !metrics.contains(&metric) {
+                metrics.push(metric);
+            }
+        }
+        if metrics.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "At least one metric must be specified."));
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep = path::MAIN_SEPARATOR;
+        if !input_file.contains(sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading input LAS file..."); }
+        let input = match LasFile::new(&input_file, "r") {
+            Ok(lf) => lf,
+            Err(err) => return Err(Error::new(err.kind(), format!("Error reading file {}: {}", input_file, err))),
+        };
+
+        let start = time::now();
+
+        if verbose { println!("Performing analysis..."); }
+
+        let n_points = input.header.number_of_points as usize;
+        let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
+
+        let west = (input.header.min_x / grid_res).floor() * grid_res;
+        let north = (input.header.max_y / grid_res).ceil() * grid_res;
+        let rows = ((((north - input.header.min_y) / grid_res).ceil()) as isize).max(1);
+        let columns = ((((input.header.max_x - west) / grid_res).ceil()) as isize).max(1);
+        let south = north - rows as f64 * grid_res;
+        let east = west + columns as f64 * grid_res;
+        let nodata = -32768.0f64;
+
+        // bin the points by grid cell
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+        let mut cells: Vec<Vec<usize>> = vec![vec![]; (rows * columns) as usize];
+        for i in 0..n_points {
+            let p: PointData = input.get_point_info(i);
+            if !p.class_bit_field.withheld() && !p.is_classified_noise() {
+                let col = (((p.x - west) / grid_res).floor() as isize).max(0).min(columns - 1);
+                let row = (((north - p.y) / grid_res).floor() as isize).max(0).min(rows - 1);
+                cells[(row * columns + col) as usize].push(i);
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Binning points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let input = Arc::new(input);
+        let cells = Arc::new(cells);
+        let metrics = Arc::new(metrics);
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let cells = cells.clone();
+            let metrics = metrics.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![vec![nodata; columns as usize]; metrics.len()];
+                    for col in 0..columns {
+                        let cell = &cells[(row * columns + col) as usize];
+                        if cell.is_empty() {
+                            continue;
+                        }
+                        let mut heights = vec![];
+                        let mut intensities = vec![];
+                        let (mut num_early, mut num_late, mut num_only, mut num_cover) = (0usize, 0usize, 0usize, 0usize);
+                        for &i in cell {
+                            let p: PointData = input.get_point_info(i);
+                            if p.z >= min_height {
+                                heights.push(p.z);
+                            }
+                            intensities.push(p.intensity as f64);
+                            if p.is_early_return() {
+                                num_early += 1;
+                                if p.z > cover_height {
+                                    num_cover += 1;
+                                }
+                            }
+                            if p.is_late_return() {
+                                num_late += 1;
+                            }
+                            if p.is_only_return() {
+                                num_only += 1;
+                            }
+                        }
+                        let height_moments = Moments::new(&mut heights);
+                        let intensity_moments = Moments::new(&mut intensities);
+                        let n = cell.len() as f64;
+                        for m in 0..metrics.len() {
+                            let value = match metrics[m] {
+                                Metric::Count => Some(n),
+                                Metric::Height(stat) => height_moments.get(stat, &heights),
+                                Metric::Intensity(stat) => intensity_moments.get(stat, &intensities),
+                                Metric::Cover => if num_early > 0 { Some(100f64 * num_cover as f64 / num_early as f64) } else { None },
+                                Metric::FirstRatio => Some(num_early as f64 / n),
+                                Metric::LastRatio => Some(num_late as f64 / n),
+                                Metric::SingleRatio => Some(num_only as f64 / n),
+                            };
+                            if let Some(v) = value {
+                                data[m][col as usize] = v;
+                            }
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut configs = RasterConfigs { ..Default::default() };
+        configs.rows = rows as usize;
+        configs.columns = columns as usize;
+        configs.north = north;
+        configs.south = south;
+        configs.east = east;
+        configs.west = west;
+        configs.resolution_x = grid_res;
+        configs.resolution_y = grid_res;
+        configs.nodata = nodata;
+        configs.data_type = DataType::F32;
+        configs.photometric_interp = PhotometricInterpretation::Continuous;
+
+        let extension = match Path::new(&output_file).extension() {
+            Some(ext) => format!(".{}", ext.to_string_lossy()),
+            None => String::new(),
+        };
+        let base_name = output_file[0..output_file.len() - extension.len()].to_string();
+        let mut outputs = vec![];
+        for metric in metrics.iter() {
+            let file_name = format!("{}_{}{}", base_name, metric.get_name(), extension);
+            outputs.push(Raster::initialize_using_config(&file_name, &configs));
+        }
+
+        for r in 0..rows {
+            let (row, data) = rx.recv().unwrap();
+            for m in 0..metrics.len() {
+                outputs[m].set_row_data(row, data[m].clone());
+            }
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as i32;
+                if progress != old_progress {
+                    println!("Calculating metrics: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        for m in 0..metrics.len() {
+            outputs[m].add_metadata_entry(format!("Created by whitebox_tools\' {} tool", self.get_tool_name()));
+            outputs[m].add_metadata_entry(format!("Input file: {}", input_file));
+            outputs[m].add_metadata_entry(format!("Metric: {}", metrics[m].get_name()));
+            outputs[m].add_metadata_entry(format!("Grid resolution: {}", grid_res));
+            if min_height.is_finite() {
+                outputs[m].add_metadata_entry(format!("Minimum height: {}", min_height));
+            }
+            if metrics[m] == Metric::Cover {
+                outputs[m].add_metadata_entry(format!("Cover height: {}", cover_height));
+            }
+            outputs[m].add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+            let _ = match outputs[m].write() {
+                Ok(_) => if verbose { println!("Output file written: {}", outputs[m].file_name) },
+                Err(e) => return Err(e),
+            };
+        }
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Statistic {
+    Min,
+    Max,
+    Mean,
+    StdDev,
+    Skewness,
+    Kurtosis,
+    Percentile(f64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Metric {
+    Count,
+    Height(Statistic),
+    Intensity(Statistic),
+    Cover,
+    FirstRatio,
+    LastRatio,
+    SingleRatio,
+}
+
+impl Metric {
+    fn from_name(s: &str) -> Option<Metric> {
+        let metric = match s {
+            "count" => Metric::Count,
+            "cover" => Metric::Cover,
+            "first_ratio" => Metric::FirstRatio,
+            "last_ratio" => Metric::LastRatio,
+            "single_ratio" => Metric::SingleRatio,
+            _ => {
+                let (intensity, s) = if s.starts_with("int_") { (true, &s[4..]) } else { (false, s) };
+                let stat = match s {
+                    "min" | "minimum" => Statistic::Min,
+                    "max" | "maximum" => Statistic::Max,
+                    "mean" | "average" => Statistic::Mean,
+                    "stdev" | "std_dev" => Statistic::StdDev,
+                    "skewness" => Statistic::Skewness,
+                    "kurtosis" => Statistic::Kurtosis,
+                    "median" => Statistic::Percentile(50f64),
+                    _ => {
+                        let p = if s.starts_with("p") { s[1..].parse::<f64>().unwrap_or(-1f64) } else { -1f64 };
+                        if p < 0f64 || p > 100f64 {
+                            return None;
+                        }
+                        Statistic::Percentile(p)
+                    }
+                };
+                if intensity { Metric::Intensity(stat) } else { Metric::Height(stat) }
+            }
+        };
+        Some(metric)
+    }
+
+    fn get_name(&self) -> String {
+        let stat_name = |stat: Statistic| match stat {
+            Statistic::Min => "min".to_string(),
+            Statistic::Max => "max".to_string(),
+            Statistic::Mean => "mean".to_string(),
+            Statistic::StdDev => "stdev".to_string(),
+            Statistic::Skewness => "skewness".to_string(),
+            Statistic::Kurtosis => "kurtosis".to_string(),
+            Statistic::Percentile(p) => if p == 50f64 {
+                "median".to_string()
+            } else {
+                format!("p{}", p)
+            },
+        };
+        match *self {
+            Metric::Count => "count".to_string(),
+            Metric::Height(stat) => stat_name(stat),
+            Metric::Intensity(stat) => format!("int_{}", stat_name(stat)),
+            Metric::Cover => "cover".to_string(),
+            Metric::FirstRatio => "first_ratio".to_string(),
+            Metric::LastRatio => "last_ratio".to_string(),
+            Metric::SingleRatio => "single_ratio".to_string(),
+        }
+    }
+}
+
+// The central moments of a set of values, which is sorted so that percentiles
+// can be read from it.
+struct Moments {
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+}
+
+impl Moments {
+    fn new(values: &mut Vec<f64>) -> Moments {
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let (mut m2, mut m3, mut m4) = (0f64, 0f64, 0f64);
+        for v in values.iter() {
+            let d = v - mean;
+            m2 += d * d;
+            m3 += d * d * d;
+            m4 += d * d * d * d;
+        }
+        Moments { mean: mean, m2: m2 / n, m3: m3 / n, m4: m4 / n }
+    }
+
+    // Returns a statistic of the sorted values from which the moments were
+    // calculated, or None if it is undefined.
+    fn get(&self, stat: Statistic, values: &[f64]) -> Option<f64> {
+        if values.is_empty() {
+            return None;
+        }
+        match stat {
+            Statistic::Min => Some(values[0]),
+            Statistic::Max => Some(values[values.len() - 1]),
+            Statistic::Mean => Some(self.mean),
+            Statistic::StdDev => Some(self.m2.sqrt()),
+            Statistic::Skewness => if self.m2 > 0f64 { Some(self.m3 / self.m2.powf(1.5)) } else { None },
+            Statistic::Kurtosis => if self.m2 > 0f64 { Some(self.m4 / (self.m2 * self.m2)) } else { None },
+            Statistic::Percentile(p) => {
+                // the smallest value at or below which p percent of the values lie
+                let rank = (p / 100f64 * values.len() as f64 - 1e-9).ceil().max(1f64) as usize;
+                Some(values[rank.min(values.len()) - 1])
+            },
+        }
+    }
+}
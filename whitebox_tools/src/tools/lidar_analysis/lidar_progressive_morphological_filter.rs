@@ -0,0 +1,435 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::collections::VecDeque;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use lidar::*;
+use structures::Array2D;
+use tools::*;
+
+/// Classifies the ground points within a LiDAR (LAS) file using the progressive
+/// morphological filter of Zhang et al. (2003). The lowest point elevations are
+/// gridded (`--resolution`) and the grid is repeatedly smoothed by morphological
+/// openings (an erosion followed by a dilation) with square windows that double in
+/// size, from three cells up to the maximum window size (`--max_window`), which
+/// should be wider than the largest building or other off-terrain object. A point
+/// is an off-terrain point if it lies higher above any of the opened surfaces than
+/// an elevation-difference threshold, which starts at the initial height threshold
+/// (`--initial_height`) and increases with the window size and the terrain slope
+/// (`--slope`), up to the maximum height threshold (`--max_height`). Because the
+/// threshold allows for the slope of the terrain, the filter removes large objects
+/// without cutting off the tops of hills and ridges, as a single opening with a
+/// fixed threshold would.
+///
+/// Unlike *LidarGroundPointFilter*, which outputs only the ground points, all of
+/// the points are output in their original order, with the ground points assigned
+/// class 2 and the other points class 1 (unclassified). Points that were
+/// previously assigned any other class, e.g. buildings, water or noise, keep their
+/// existing classes, as do withheld points; noise and withheld points are not used
+/// in the analysis. The output can therefore be compared with another ground
+/// classification of the same file, e.g. from *LidarClothSimulationFilter*, using
+/// *LidarKappaIndex*.
+///
+/// # Reference
+/// Zhang, K., Chen, S. C., Whitman, D., Shyu, M. L., Yan, J., & Zhang, C. (2003). A
+/// progressive morphological filter for removing nonground measurements from airborne
+/// LIDAR data. IEEE Transactions on Geoscience and Remote Sensing, 41(4), 872-882.
+///
+/// # See Also
+/// *LidarClothSimulationFilter*, *LidarGroundPointFilter*, *LidarKappaIndex*
+pub struct LidarProgressiveMorphologicalFilter {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarProgressiveMorphologicalFilter {
+    pub fn new() -> LidarProgressiveMorphologicalFilter { // public constructor
+        let name = "LidarProgressiveMorphologicalFilter".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Classifies the ground points within a LiDAR dataset using a progressive morphological filter.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output LiDAR file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Grid Resolution".to_owned(),
+            flags: vec!["--resolution".to_owned()],
+            description: "Resolution of the grid of minimum elevations.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Maximum Window Size".to_owned(),
+            flags: vec!["--max_window".to_owned()],
+            description: "Maximum width of the filter window, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("20.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Terrain Slope".to_owned(),
+            flags: vec!["--slope".to_owned()],
+            description: "Terrain slope, in degrees, used to increase the height threshold with the window size.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("10.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Initial Height Threshold".to_owned(),
+            flags: vec!["--initial_height".to_owned()],
+            description: "Initial height threshold of off-terrain points.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Maximum Height Threshold".to_owned(),
+            flags: vec!["--max_height".to_owned()],
+            description: "Maximum height threshold of off-terrain points.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("3.0".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=input.las -o=output.las --resolution=1.0 --max_window=30.0 --slope=15.0", short_exe, name).replace("*", &sep);
+
+        LidarProgressiveMorphologicalFilter {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for LidarProgressiveMorphologicalFilter {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file: String = "".to_string();
+        let mut output_file: String = "".to_string();
+        let mut grid_res = 1f64;
+        let mut max_window = 20f64;
+        let mut slope = 10f64;
+        let mut initial_height = 0.5f64;
+        let mut max_height = 3f64;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 { keyval = true; }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-resolution" || vec[0].to_lowercase() == "--resolution" {
+                if keyval {
+                    grid_res = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    grid_res = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-max_window" || vec[0].to_lowercase() == "--max_window" {
+                if keyval {
+                    max_window = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    max_window = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-slope" || vec[0].to_lowercase() == "--slope" {
+                if keyval {
+                    slope = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    slope = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-initial_height" || vec[0].to_lowercase() == "--initial_height" {
+                if keyval {
+                    initial_height = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    initial_height = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-max_height" || vec[0].to_lowercase() == "--max_height" {
+                if keyval {
+                    max_height = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    max_height = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            }
+        }
+
+        if grid_res <= 0f64 {
+            return Err(Error::new(ErrorKind::InvalidInput, "The grid resolution must be greater than zero."));
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep = path::MAIN_SEPARATOR;
+        if !input_file.contains(sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading input LAS file..."); }
+        let input = match LasFile::new(&input_file, "r") {
+            Ok(lf) => lf,
+            Err(err) => return Err(Error::new(err.kind(), format!("Error reading file {}: {}", input_file, err))),
+        };
+
+        let start = time::now();
+
+        if verbose { println!("Performing analysis..."); }
+
+        let n_points = input.header.number_of_points as usize;
+        let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
+
+        let west = input.header.min_x;
+        let north = input.header.max_y;
+        let rows = ((input.header.max_y - input.header.min_y) / grid_res).floor() as isize + 1;
+        let columns = ((input.header.max_x - input.header.min_x) / grid_res).floor() as isize + 1;
+        let nodata = f64::MAX;
+
+        // grid the lowest elevations
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+        let mut is_candidate = vec![false; n_points];
+        let mut cells = vec![(0isize, 0isize); n_points];
+        let mut surface: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        for i in 0..n_points {
+            let p: PointData = input.get_point_info(i);
+            if !p.class_bit_field.withheld() && !p.is_classified_noise() {
+                let row = ((north - p.y) / grid_res).floor() as isize;
+                let col = ((p.x - west) / grid_res).floor() as isize;
+                is_candidate[i] = true;
+                cells[i] = (row, col);
+                if p.z < surface.get_value(row, col) {
+                    surface.set_value(row, col, p.z);
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Gridding points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        fill_empty_cells(&mut surface, nodata);
+
+        // open the surface with increasingly large windows
+        let slope = slope.to_radians().tan();
+        let mut is_off_terrain = vec![false; n_points];
+        let mut window_size = 3isize; // in cells
+        let mut last_window_size = 1isize;
+        while window_size as f64 * grid_res <= max_window || window_size == 3 {
+            let mut height_threshold = initial_height;
+            if window_size > 3 {
+                height_threshold += slope * (window_size - last_window_size) as f64 * grid_res;
+            }
+            if height_threshold > max_height {
+                height_threshold = max_height;
+            }
+            if verbose {
+                println!("Window size: {} x {} cells, height threshold: {:.3}", window_size, window_size, height_threshold);
+            }
+
+            let erosion = filter_window(&surface, window_size / 2, false)?;
+            surface = filter_window(&erosion, window_size / 2, true)?;
+            for i in 0..n_points {
+                if is_candidate[i] && !is_off_terrain[i] {
+                    let (row, col) = cells[i];
+                    if input.get_point_info(i).z - surface.get_value(row, col) > height_threshold {
+                        is_off_terrain[i] = true;
+                    }
+                }
+            }
+
+            last_window_size = window_size;
+            window_size = 2 * window_size - 1;
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        // output all of the points, reclassifying only the unclassified and ground points
+        let mut output = LasFile::initialize_using_file(&output_file, &input);
+        let mut num_ground_points = 0usize;
+        for i in 0..n_points {
+            let mut pr = input.get_record(i);
+            let mut point_data = pr.get_point_data();
+            if is_candidate[i] && point_data.classification() <= 2 {
+                if is_off_terrain[i] {
+                    point_data.set_classification(1);
+                } else {
+                    point_data.set_classification(2);
+                    num_ground_points += 1;
+                }
+                pr.set_point_data(point_data);
+            }
+            output.add_point_record_with_extra_bytes(pr, input.get_extra_bytes(i));
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Saving data: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        if verbose { println!("Number of ground points: {}", num_ground_points); }
+
+        if verbose { println!("Writing output LAS file..."); }
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Complete!") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+// Assigns the cells without a value the value of the nearest (in terms of the
+// number of steps to a queen's move) cell with a value.
+fn fill_empty_cells(grid: &mut Array2D<f64>, nodata: f64) {
+    let dx = [ 1, 1, 1, 0, -1, -1, -1, 0 ];
+    let dy = [ -1, 0, 1, 1, 1, 0, -1, -1 ];
+    let mut queue = VecDeque::new();
+    for row in 0..grid.rows {
+        for col in 0..grid.columns {
+            if grid.get_value(row, col) != nodata {
+                queue.push_back((row, col));
+            }
+        }
+    }
+    while let Some((row, col)) = queue.pop_front() {
+        let z = grid.get_value(row, col);
+        for i in 0..8 {
+            let (r, c) = (row + dy[i], col + dx[i]);
+            if r >= 0 && c >= 0 && r < grid.rows && c < grid.columns && grid.get_value(r, c) == nodata {
+                grid.set_value(r, c, z);
+                queue.push_back((r, c));
+            }
+        }
+    }
+}
+
+// Performs a square minimum (erosion) or maximum (dilation) filter, as a
+// separable filter of the rows and then the columns of the grid.
+fn filter_window(grid: &Array2D<f64>, half_size: isize, is_max: bool) -> Result<Array2D<f64>, Error> {
+    let rows = grid.rows;
+    let columns = grid.columns;
+    let select = |a: f64, b: f64| if is_max { a.max(b) } else { a.min(b) };
+    let mut horizontal: Array2D<f64> = Array2D::new(rows, columns, grid.nodata, grid.nodata)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            let mut z = grid.get_value(row, col);
+            for c in (col - half_size).max(0)..(col + half_size + 1).min(columns) {
+                z = select(z, grid.get_value(row, c));
+            }
+            horizontal.set_value(row, col, z);
+        }
+    }
+    let mut output: Array2D<f64> = Array2D::new(rows, columns, grid.nodata, grid.nodata)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            let mut z = horizontal.get_value(row, col);
+            for r in (row - half_size).max(0)..(row + half_size + 1).min(rows) {
+                z = select(z, horizontal.get_value(r, col));
+            }
+            output.set_value(row, col, z);
+        }
+    }
+    Ok(output)
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 5/12/2017, 2017
-Last Modified: 15/12/2017, 2017
+Last Modified: October 18, 2026
 License: MIT
 
 Notes: The 3D space-filling nature of point clouds under heavy forest cover do not
@@ -13,9 +13,7 @@ Notes: The 3D space-filling nature of point clouds under heavy forest cover do n
 extern crate time;
 extern crate nalgebra as na;
 extern crate num_cpus;
-extern crate rand;
 
-use std::cmp;
 use std::env;
 use std::f64;
 use std::f64::NEG_INFINITY;
@@ -28,7 +26,6 @@ use lidar::*;
 use tools::*;
 use self::na::Vector3;
 use structures::FixedRadiusSearch3D;
-use self::rand::Rng;
 
 pub struct LidarSegmentation {
     name: String,
@@ -42,7 +39,7 @@ impl LidarSegmentation {
     pub fn new() -> LidarSegmentation { // public constructor
         let name = "LidarSegmentation".to_string();
         let toolbox = "LiDAR Tools".to_string();
-        let description = "Segments a LiDAR point cloud based on normal vectors.".to_string();
+        let description = "Segments a LiDAR point cloud based on normal vectors, storing the segment of each point as the SegmentID extra bytes attribute.".to_string();
         
         let mut parameters = vec![];
         parameters.push(ToolParameter{
@@ -351,32 +348,12 @@ impl WhiteboxTool for LidarSegmentation {
         // Output the data //
         /////////////////////
 
-        let mut clrs: Vec<(u16, u16, u16)> = Vec::new();
-        let mut rng = rand::thread_rng();
-        let (mut r, mut g, mut b): (u16, u16, u16) = (0u16, 0u16, 0u16);
-        for _ in 0..current_segment+1 as usize {
-            let mut flag = false;
-            while !flag {
-                r = rng.gen::<u8>() as u16 * 256u16;
-                g = rng.gen::<u8>() as u16 * 256u16;
-                b = rng.gen::<u8>() as u16 * 256u16;
-                let max_val = cmp::max(cmp::max(r, g), b);
-                //let min_val = cmp::min(cmp::min(r, g), b);
-                if max_val >= u16::max_value() / 2 { // && min_val >= u16::max_value() / 4 {
-                    flag = true;
-                }
-            }
-            clrs.push((r, g, b));
-        }
-
         let mut output = LasFile::initialize_using_file(&output_file, &input);
-        output.header.point_format = 2;
+        let attribute = ExtraBytesDescriptor::new("SegmentID", ExtraByteType::U32, "Point cloud segment identifier");
+        output.ensure_extra_byte_attribute(attribute)?;
         for point_num in 0..n_points {
-            let p: PointData = input[point_num];
-            let seg_val = segment_id[point_num];
-            let rgb: RgbData = RgbData{ red: clrs[seg_val].0, green: clrs[seg_val].1, blue: clrs[seg_val].2 };
-            let lpr: LidarPointRecord = LidarPointRecord::PointRecord2 { point_data: p, rgb_data: rgb };
-            output.add_point_record(lpr);
+            output.add_point_record_with_extra_bytes(input.get_record(point_num), input.get_extra_bytes(point_num));
+            output.set_extra_byte_value(point_num, "SegmentID", segment_id[point_num] as f64)?;
             if verbose {
                 progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                 if progress != old_progress {
@@ -392,7 +369,7 @@ impl WhiteboxTool for LidarSegmentation {
         //     g = ((1.0 + normal_values[i].y) / 2.0 * 255.0) as u16 * 256u16; //((1.0 + normal_values[i].y) / 2.0 * 65535.0) as u16;
         //     b = ((1.0 + normal_values[i].z) / 2.0 * 255.0) as u16 * 256u16; //((1.0 + normal_values[i].z) / 2.0 * 65535.0) as u16;
         
-        //     let rgb: RgbData = RgbData{ red: r, green: g, blue: b };
+        //     let rgb: RgbData = RgbData { red: r, green: g, blue: b };
         //     let lpr = LidarPointRecord::PointRecord2 { point_data: p, rgb_data: rgb };
         //     output.add_point_record(lpr);
         //     if verbose {
@@ -446,7 +423,8 @@ impl Normal {
         let denom1 = (self.a * self.a + self.b * self.b + self.c * self.c).sqrt();
         let denom2 = (other.a * other.a + other.b * other.b + other.c * other.c).sqrt();
         if denom1*denom2 != 0f64 {
-            return (numerator / (denom1 * denom2)).acos();
+            // rounding can place the cosine of nearly parallel normals just outside [-1, 1]
+            return (numerator / (denom1 * denom2)).max(-1f64).min(1f64).acos();
         }
         NEG_INFINITY
     }
@@ -511,4 +489,55 @@ fn plane_from_points(points: &Vec<Vector3<f64>>) -> Normal {
 fn normalize(v: Vector3<f64>) -> Normal {
     let norm = (v.x * v.x + v.y * v.y + v.z * v.z).sqrt();
     Normal { a: v.x/norm, b: v.y/norm, c: v.z/norm }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LidarSegmentation;
+    use lidar::*;
+    use std::env;
+    use std::fs;
+    use tools::WhiteboxTool;
+
+    #[test]
+    fn test_segments_of_two_surfaces() {
+        let dir = env::temp_dir();
+        let input_file = dir.join("wbt_lidar_segmentation_test_input.las").to_str().unwrap().to_string();
+        let output_file = dir.join("wbt_lidar_segmentation_test_output.las").to_str().unwrap().to_string();
+
+        // a flat roof beside a sloping surface, each 20 by 20 points
+        let mut las = LasFile::new(&input_file, "w").unwrap();
+        las.add_header(LasHeader { project_id_used: true, ..Default::default() });
+        for row in 0..20 {
+            for col in 0..40 {
+                let z = if col < 20 { 10f64 } else { 0.7 * (col - 20) as f64 };
+                let point_data = PointData { x: col as f64, y: row as f64, z: z, ..Default::default() };
+                las.add_point_record(LidarPointRecord::PointRecord0 { point_data: point_data });
+            }
+        }
+        las.write().unwrap();
+
+        let tool = LidarSegmentation::new();
+        let args = vec![format!("-i={}", input_file), format!("-o={}", output_file), "--radius=2.5".to_string(),
+            "--norm_diff=5.0".to_string(), "--maxzdiff=1.0".to_string()];
+        tool.run(args, "", false).unwrap();
+
+        let output = LasFile::new(&output_file, "r").unwrap();
+        fs::remove_file(&input_file).unwrap();
+        fs::remove_file(&output_file).unwrap();
+        assert_eq!(output.get_extra_byte_attributes().len(), 1);
+        assert_eq!(output.get_extra_byte_attributes()[0].name, "SegmentID");
+        assert_eq!(output.header.number_of_points, 800);
+
+        // points away from the edges of the surfaces belong to one segment per surface
+        let segment = |row: usize, col: usize| output.get_extra_byte_value(row * 40 + col, "SegmentID").unwrap();
+        let (roof, slope) = (segment(10, 10), segment(10, 30));
+        assert!(roof > 0f64 && slope > 0f64 && roof != slope);
+        for row in 3..17 {
+            for col in 3..17 {
+                assert_eq!(segment(row, col), roof);
+                assert_eq!(segment(row, col + 20), slope);
+            }
+        }
+    }
+}
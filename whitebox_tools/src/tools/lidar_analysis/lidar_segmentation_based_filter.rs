@@ -486,29 +486,11 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
                     true => ground_class_value,
                     false => otp_class_value,
                 };
-                let pr = input.get_record(point_num);
-                let pr2: LidarPointRecord;
-                match pr {
-                    LidarPointRecord::PointRecord0 { mut point_data }  => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord0 { point_data: point_data };
-
-                    },
-                    LidarPointRecord::PointRecord1 { mut point_data, gps_data } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord1 { point_data: point_data, gps_data: gps_data };
-                    },
-                    LidarPointRecord::PointRecord2 { mut point_data, rgb_data } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord2 { point_data: point_data, rgb_data: rgb_data };
-                    },
-                    LidarPointRecord::PointRecord3 { mut point_data, gps_data, rgb_data } => {
-                        point_data.set_classification(class_val);
-                        pr2 = LidarPointRecord::PointRecord3 { point_data: point_data,
-                            gps_data: gps_data, rgb_data: rgb_data};
-                    },
-                }
-                output.add_point_record(pr2);
+                let mut pr = input.get_record(point_num);
+                let mut point_data = pr.get_point_data();
+                point_data.set_classification(class_val);
+                pr.set_point_data(point_data);
+                output.add_point_record(pr);
                 if verbose {
                     progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                     if progress != old_progress {
@@ -0,0 +1,512 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+extern crate num_cpus;
+
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread;
+use lidar::*;
+use raster::*;
+use structures::{BoundingBox, Point2D, RTree, Triangulation};
+use tools::*;
+
+/// Interpolates a raster from a LAS file by triangulated irregular network
+/// (TIN) gridding, i.e. the points are connected by a Delaunay triangulation
+/// and the value of each grid cell is interpolated linearly from the plane of
+/// the triangle containing the cell's centre. Unlike inverse-distance weighting
+/// and nearest-neighbour gridding, this honours the point values exactly and
+/// introduces no pits or bumps between points.
+///
+/// Triangles with an edge longer than `--max_triangle_edge_length` are left
+/// out, such that gaps in the data (e.g. water bodies and areas outside of the
+/// survey) are assigned NoData rather than being interpolated across. Points
+/// are filtered by return (`--returns`), class (`--exclude_cls`), and elevation
+/// range (`--minz`, `--maxz`) in the same way as the other lidar gridding tools.
+pub struct LidarTINGridding {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarTINGridding {
+    pub fn new() -> LidarTINGridding {
+        // public constructor
+        let name = "LidarTINGridding".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Creates a raster grid based on a Delaunay triangular irregular network (TIN) fitted to LiDAR points.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input File".to_owned(), 
+            flags: vec!["-i".to_owned(), "--input".to_owned()], 
+            description: "Input LiDAR file (including extension).".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(), 
+            flags: vec!["-o".to_owned(), "--output".to_owned()], 
+            description: "Output raster file (including extension).".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Interpolation Parameter".to_owned(), 
+            flags: vec!["--parameter".to_owned()], 
+            description: "Interpolation parameter; options are 'elevation' (default), 'intensity', 'class', 'scan angle', 'user data'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["elevation".to_owned(), "intensity".to_owned(), "class".to_owned(), "scan angle".to_owned(), "user data".to_owned()]),
+            default_value: Some("elevation".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Point Returns Included".to_owned(), 
+            flags: vec!["--returns".to_owned()], 
+            description: "Point return types to include; options are 'all' (default), 'last', 'first'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["all".to_owned(), "last".to_owned(), "first".to_owned()]),
+            default_value: Some("all".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Grid Resolution".to_owned(), 
+            flags: vec!["--resolution".to_owned()], 
+            description: "Output raster's grid resolution.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Exclusion Classes (0-18, based on LAS spec; e.g. 3,4,5,6,7)".to_owned(), 
+            flags: vec!["--exclude_cls".to_owned()], 
+            description: "Optional exclude classes from interpolation; Valid class values range from 0 to 18, based on LAS specifications. Example, --exclude_cls='3,4,5,6,7,18'.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true
+        });
+        
+        parameters.push(ToolParameter{
+            name: "Minimum Elevation Value (optional)".to_owned(), 
+            flags: vec!["--minz".to_owned()], 
+            description: "Optional minimum elevation for inclusion in interpolation.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true
+        });
+        
+        parameters.push(ToolParameter{
+            name: "Maximum Elevation Value (optional)".to_owned(), 
+            flags: vec!["--maxz".to_owned()], 
+            description: "Optional maximum elevation for inclusion in interpolation.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Maximum Triangle Edge Length (optional)".to_owned(), 
+            flags: vec!["--max_triangle_edge_length".to_owned()], 
+            description: "Optional maximum triangle edge length; triangles larger than this size will not be gridded.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=file.las -o=outfile.dep --resolution=2.0
+.*{0} -r={1} --wd=\"*path*to*data*\" -i=file.las -o=outfile.dep --resolution=1.0 --returns=last --exclude_cls='3,4,5,6,7,18' --max_triangle_edge_length=15.0", short_exe, name).replace("*", &sep);
+
+        LidarTINGridding {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LidarTINGridding {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+    
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self,
+               args: Vec<String>,
+               working_directory: &'a str,
+               verbose: bool)
+               -> Result<(), Error> {
+        let mut input_file: String = "".to_string();
+        let mut output_file: String = "".to_string();
+        let mut interp_parameter = "elevation".to_string();
+        let mut return_type = "all".to_string();
+        let mut grid_res: f64 = 1.0;
+        let mut include_class_vals = vec![true; 256];
+        let mut exclude_cls_str = String::new();
+        let mut max_z = f64::INFINITY;
+        let mut min_z = f64::NEG_INFINITY;
+        let mut max_triangle_edge_length = f64::INFINITY;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i + 1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i + 1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-parameter" ||
+                      vec[0].to_lowercase() == "--parameter" {
+                if keyval {
+                    interp_parameter = vec[1].to_string();
+                } else {
+                    interp_parameter = args[i + 1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-returns" || vec[0].to_lowercase() == "--returns" {
+                if keyval {
+                    return_type = vec[1].to_string();
+                } else {
+                    return_type = args[i + 1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-resolution" ||
+                      vec[0].to_lowercase() == "--resolution" {
+                if keyval {
+                    grid_res = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    grid_res = args[i + 1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-exclude_cls" ||
+                      vec[0].to_lowercase() == "--exclude_cls" {
+                if keyval {
+                    exclude_cls_str = vec[1].to_string();
+                } else {
+                    exclude_cls_str = args[i + 1].to_string();
+                }
+                let mut cmd = exclude_cls_str.split(",");
+                let mut vec = cmd.collect::<Vec<&str>>();
+                if vec.len() == 1 {
+                    cmd = exclude_cls_str.split(";");
+                    vec = cmd.collect::<Vec<&str>>();
+                }
+                for value in vec {
+                    if !value.trim().is_empty() {
+                        let c = value.trim().parse::<usize>().unwrap();
+                        include_class_vals[c] = false;
+                    }
+                }
+            } else if vec[0].to_lowercase() == "-minz" || vec[0].to_lowercase() == "--minz" {
+                if keyval {
+                    min_z = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    min_z = args[i + 1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-maxz" || vec[0].to_lowercase() == "--maxz" {
+                if keyval {
+                    max_z = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    max_z = args[i + 1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-max_triangle_edge_length" ||
+                      vec[0].to_lowercase() == "--max_triangle_edge_length" {
+                if keyval {
+                    max_triangle_edge_length = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    max_triangle_edge_length = args[i + 1].to_string().parse::<f64>().unwrap();
+                }
+            }
+        }
+
+        let (all_returns, late_returns, early_returns): (bool, bool, bool);
+        if return_type.contains("last") {
+            all_returns = false;
+            late_returns = true;
+            early_returns = false;
+        } else if return_type.contains("first") {
+            all_returns = false;
+            late_returns = false;
+            early_returns = true;
+        } else {
+            // all
+            all_returns = true;
+            late_returns = false;
+            early_returns = false;
+        }
+
+        if !input_file.contains(path::MAIN_SEPARATOR) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(path::MAIN_SEPARATOR) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if verbose {
+            println!("Reading input LAS file...");
+        }
+        let input = match LasFile::new(&input_file, "r") {
+            Ok(lf) => lf,
+            Err(err) => panic!("Error reading file {}: {}", input_file, err),
+        };
+
+        let start = time::now();
+
+        if verbose {
+            println!("Performing analysis...");
+        }
+
+        let n_points = input.header.number_of_points as usize;
+        let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
+
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+        let mut points: Vec<Point2D> = vec![];
+        let mut interp_vals: Vec<f64> = vec![];
+        for i in 0..n_points {
+            let p: PointData = input[i];
+            if !p.class_bit_field.withheld() {
+                if all_returns || (p.is_late_return() & late_returns) ||
+                   (p.is_early_return() & early_returns) {
+                    if include_class_vals[p.classification() as usize] {
+                        if p.z >= min_z && p.z <= max_z {
+                            points.push(Point2D::new(p.x, p.y));
+                            interp_vals.push(match &interp_parameter as &str {
+                                "elevation" | "z" => p.z,
+                                "intensity" => p.intensity as f64,
+                                "class" | "classification" => p.classification() as f64,
+                                "scan angle" => p.scan_angle_degrees(),
+                                _ => p.user_data as f64, // user data
+                            });
+                        }
+                    }
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Reading points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Triangulating points...");
+        }
+        let triangulation = match Triangulation::new(&points) {
+            Some(t) => t,
+            None => return Err(Error::new(ErrorKind::InvalidInput,
+                        "The points could not be triangulated; at least three non-collinear points are required.")),
+        };
+
+        // index the triangles that are small enough to be gridded by their extents
+        let mut triangles = vec![];
+        for t in 0..triangulation.num_triangles() {
+            let (p1, p2, p3) = (points[triangulation.triangles[3 * t]],
+                                points[triangulation.triangles[3 * t + 1]],
+                                points[triangulation.triangles[3 * t + 2]]);
+            if p1.distance(&p2) <= max_triangle_edge_length &&
+               p2.distance(&p3) <= max_triangle_edge_length &&
+               p3.distance(&p1) <= max_triangle_edge_length {
+                triangles.push((BoundingBox::from_points(&[p1, p2, p3]), t));
+            }
+        }
+        let triangle_tree = RTree::bulk_load(triangles);
+
+        let west: f64 = input.header.min_x;
+        let north: f64 = input.header.max_y;
+        let rows: isize = (((north - input.header.min_y) / grid_res).ceil()) as isize;
+        let columns: isize = (((input.header.max_x - west) / grid_res).ceil()) as isize;
+        let south: f64 = north - rows as f64 * grid_res;
+        let east = west + columns as f64 * grid_res;
+        let nodata = -32768.0f64;
+
+        let mut configs = RasterConfigs { ..Default::default() };
+        configs.rows = rows as usize;
+        configs.columns = columns as usize;
+        configs.north = north;
+        configs.south = south;
+        configs.east = east;
+        configs.west = west;
+        configs.resolution_x = grid_res;
+        configs.resolution_y = grid_res;
+        configs.nodata = nodata;
+        configs.data_type = DataType::F64;
+        configs.photometric_interp = PhotometricInterpretation::Continuous;
+
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+
+        let points = Arc::new(points);
+        let interp_vals = Arc::new(interp_vals);
+        let triangulation = Arc::new(triangulation);
+        let triangle_tree = Arc::new(triangle_tree);
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let points = points.clone();
+            let interp_vals = interp_vals.clone();
+            let triangulation = triangulation.clone();
+            let triangle_tree = triangle_tree.clone();
+            let tx1 = tx.clone();
+            thread::spawn(move || {
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    let y = north - (row as f64 + 0.5) * grid_res;
+                    // the triangles that span the row's cell centres
+                    for &t in triangle_tree.search(&BoundingBox::new(west, y, east, y)) {
+                        let (i1, i2, i3) = (triangulation.triangles[3 * t],
+                                            triangulation.triangles[3 * t + 1],
+                                            triangulation.triangles[3 * t + 2]);
+                        let (p1, p2, p3) = (points[i1], points[i2], points[i3]);
+                        let det = (p2.y - p3.y) * (p1.x - p3.x) + (p3.x - p2.x) * (p1.y - p3.y);
+                        if det == 0f64 {
+                            continue;
+                        }
+                        let min_x = p1.x.min(p2.x).min(p3.x);
+                        let max_x = p1.x.max(p2.x).max(p3.x);
+                        let mut start_col = ((min_x - west) / grid_res - 0.5).ceil() as isize;
+                        let mut end_col = ((max_x - west) / grid_res - 0.5).floor() as isize;
+                        if start_col < 0 {
+                            start_col = 0;
+                        }
+                        if end_col > columns - 1 {
+                            end_col = columns - 1;
+                        }
+                        for col in start_col..end_col + 1 {
+                            let x = west + (col as f64 + 0.5) * grid_res;
+                            // barycentric coordinates of the cell centre
+                            let l1 = ((p2.y - p3.y) * (x - p3.x) + (p3.x - p2.x) * (y - p3.y)) / det;
+                            let l2 = ((p3.y - p1.y) * (x - p3.x) + (p1.x - p3.x) * (y - p3.y)) / det;
+                            let l3 = 1f64 - l1 - l2;
+                            if l1 >= -1e-9 && l2 >= -1e-9 && l3 >= -1e-9 {
+                                data[col as usize] = l1 * interp_vals[i1] + l2 * interp_vals[i2] + l3 * interp_vals[i3];
+                            }
+                        }
+                    }
+                    tx1.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        for row in 0..rows {
+            let data = rx.recv().unwrap();
+            output.set_row_data(data.0, data.1);
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+        output.add_metadata_entry(format!("Created by whitebox_tools\' {} tool",
+                                          self.get_tool_name()));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Grid resolution: {}", grid_res));
+        output.add_metadata_entry(format!("Maximum triangle edge length: {}", max_triangle_edge_length));
+        output.add_metadata_entry(format!("Interpolation parameter: {}", interp_parameter));
+        output.add_metadata_entry(format!("Returns: {}", return_type));
+        output.add_metadata_entry(format!("Excluded classes: {}", exclude_cls_str));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+                                      .replace("PT", ""));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        println!("{}",
+                 &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: June 22, 2017
-Last Modified: Dec. 15, 2017
+Last Modified: October 18, 2026
 License: MIT
 */
 extern crate time;
@@ -19,6 +19,16 @@ use lidar::*;
 use tools::*;
 use structures::FixedRadiusSearch2D;
 
+/// Performs a white top-hat transform on a LiDAR (LAS) file, replacing the
+/// elevation of each point with its height above the opening of the point
+/// cloud, as an estimate of its height above the ground.
+///
+/// If no input file is specified, the LAS files in the working directory are
+/// treated as the tiles of a larger data set, e.g. those created by *LidarTile*,
+/// and the transformed tiles are written, with the same names, to the output
+/// directory (`--outdir`). Each tile is transformed along with the points of
+/// the neighbouring tiles lying within twice the search radius of its edges,
+/// which avoids edge artefacts, and only the tile's own points are output.
 pub struct LidarTophatTransform {
     name: String,
     description: String,
@@ -37,10 +47,10 @@ impl LidarTophatTransform {
         parameters.push(ToolParameter{
             name: "Input File".to_owned(), 
             flags: vec!["-i".to_owned(), "--input".to_owned()], 
-            description: "Input LiDAR file.".to_owned(),
+            description: "Input LiDAR file; if omitted, all of the LAS files in the working directory are transformed as tiles.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
             default_value: None,
-            optional: false
+            optional: true
         });
 
         parameters.push(ToolParameter{
@@ -49,7 +59,16 @@ impl LidarTophatTransform {
             description: "Output LiDAR file.".to_owned(),
             parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
             default_value: None,
-            optional: false
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Directory".to_owned(), 
+            flags: vec!["--outdir".to_owned()], 
+            description: "Output directory for the tiles, when no input file is specified.".to_owned(),
+            parameter_type: ParameterType::Directory,
+            default_value: None,
+            optional: true
         });
 
         parameters.push(ToolParameter{
@@ -68,7 +87,8 @@ impl LidarTophatTransform {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=\"input.las\" -o=\"output.las\" --radius=10.0", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=\"input.las\" -o=\"output.las\" --radius=10.0
+>>.*{0} -r={1} -v --wd=\"*path*to*tiles*\" --outdir=\"*path*to*tophat*\" --radius=10.0", short_exe, name).replace("*", &sep);
     
         LidarTophatTransform { 
             name: name, 
@@ -118,6 +138,7 @@ impl WhiteboxTool for LidarTophatTransform {
     fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
         let mut input_file: String = "".to_string();
         let mut output_file: String = "".to_string();
+        let mut output_dir: String = "".to_string();
         let mut search_radius: f64 = -1.0;
         
         // read the arguments
@@ -143,6 +164,12 @@ impl WhiteboxTool for LidarTophatTransform {
                 } else {
                     output_file = args[i+1].to_string();
                 }
+            } else if vec[0].to_lowercase() == "-outdir" || vec[0].to_lowercase() == "--outdir" {
+                if keyval {
+                    output_dir = vec[1].to_string();
+                } else {
+                    output_dir = args[i+1].to_string();
+                }
             } else if vec[0].to_lowercase() == "-radius" || vec[0].to_lowercase() == "--radius" {
                 if keyval {
                     search_radius = vec[1].to_string().parse::<f64>().unwrap();
@@ -159,23 +186,83 @@ impl WhiteboxTool for LidarTophatTransform {
         }
 
         let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) {
-            output_file = format!("{}{}", working_directory, output_file);
+        let mut tiles = LasTileSet::default();
+        let mut inputs = vec![];
+        let mut outputs = vec![];
+        if input_file.is_empty() {
+            if working_directory.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "This tool must be run by specifying either an individual input file or a working directory."));
+            }
+            if output_dir.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "An output directory (--outdir) must be specified when transforming the tiles of the working directory."));
+            }
+            if !output_dir.contains(sep) {
+                output_dir = format!("{}{}", working_directory, output_dir);
+            }
+            tiles = LasTileSet::new(working_directory)?;
+            inputs = tiles.file_names.clone();
+            outputs = tiles.get_output_file_names(&output_dir)?;
+        } else {
+            if output_file.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "An output file (--output) must be specified when transforming an input file."));
+            }
+            if !input_file.contains(sep) {
+                input_file = format!("{}{}", working_directory, input_file);
+            }
+            if !output_file.contains(sep) {
+                output_file = format!("{}{}", working_directory, output_file);
+            }
+            inputs.push(input_file.clone());
+            outputs.push(output_file.clone());
         }
 
-        if verbose { println!("Reading input LAS file..."); }
-        let input = match LasFile::new(&input_file, "r") {
-            Ok(lf) => lf,
-            Err(err) => panic!("Error reading file {}: {}", input_file, err),
-        };
+        // the opening (an erosion followed by a dilation) of a point depends upon
+        // the points within twice the search radius
+        let buffer = 2f64 * search_radius;
 
         let start = time::now();
 
-        if verbose { println!("Performing analysis..."); }
+        for k in 0..inputs.len() {
+            input_file = inputs[k].clone();
+            output_file = outputs[k].clone();
+
+            if verbose && inputs.len() > 1 {
+                println!("Transforming {} of {} ({:.2}%) {}", k+1, inputs.len(), (k+1) as f64 / inputs.len() as f64 * 100f64, input_file);
+            }
+
+            if verbose && inputs.len() == 1 { println!("Reading input LAS file..."); }
+            let (input, num_core_points) = if tiles.is_empty() {
+                match LasFile::new(&input_file, "r") {
+                    Ok(lf) => {
+                        let n = lf.header.number_of_points as usize;
+                        (lf, n)
+                    },
+                    Err(err) => panic!("Error reading file {}: {}", input_file, err),
+                }
+            } else {
+                tiles.read_with_buffer(k, buffer)?
+            };
+
+            if verbose && inputs.len() == 1 { println!("Performing analysis..."); }
+            self.transform_points(input, num_core_points, &output_file, search_radius, verbose)?;
+        }
 
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        println!("{}", &format!("Elapsed Time (including I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+impl LidarTophatTransform {
+    // Transforms the points of a file, which may be followed by buffer points read
+    // from neighbouring tiles, writing the first num_core_points points to the output file.
+    fn transform_points(&self, input: LasFile, num_core_points: usize, output_file: &str, search_radius: f64, verbose: bool) -> Result<(), Error> {
         let n_points = input.header.number_of_points as usize;
         let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
 
@@ -302,34 +389,16 @@ impl WhiteboxTool for LidarTophatTransform {
             }
         }
 
-        // now output the data
+        // now output the data; buffer points are only used as context
         let mut output = LasFile::initialize_using_file(&output_file, &input);
         output.header.system_id = "EXTRACTION".to_string();
 
-        for i in 0..n_points {
-            let pr = input.get_record(i);
-            let pr2: LidarPointRecord;
-            match pr {
-                LidarPointRecord::PointRecord0 { mut point_data }  => {
-                    point_data.z = residuals[i];
-                    pr2 = LidarPointRecord::PointRecord0 { point_data: point_data };
-
-                },
-                LidarPointRecord::PointRecord1 { mut point_data, gps_data } => {
-                    point_data.z = residuals[i];
-                    pr2 = LidarPointRecord::PointRecord1 { point_data: point_data, gps_data: gps_data };
-                },
-                LidarPointRecord::PointRecord2 { mut point_data, rgb_data } => {
-                    point_data.z = residuals[i];
-                    pr2 = LidarPointRecord::PointRecord2 { point_data: point_data, rgb_data: rgb_data };
-                },
-                LidarPointRecord::PointRecord3 { mut point_data, gps_data, rgb_data } => {
-                    point_data.z = residuals[i];
-                    pr2 = LidarPointRecord::PointRecord3 { point_data: point_data,
-                        gps_data: gps_data, rgb_data: rgb_data};
-                },
-            }
-            output.add_point_record(pr2);
+        for i in 0..num_core_points {
+            let mut pr = input.get_record(i);
+            let mut point_data = pr.get_point_data();
+            point_data.z = residuals[i];
+            pr.set_point_data(point_data);
+            output.add_point_record_with_extra_bytes(pr, input.get_extra_bytes(i));
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
@@ -339,18 +408,56 @@ impl WhiteboxTool for LidarTophatTransform {
             }
         }
 
-        let end = time::now();
-        let elapsed_time = end - start;
-
         if verbose { println!("Writing output LAS file..."); }
         let _ = match output.write() {
-            Ok(_) => println!("Complete!"),
-            Err(e) => println!("error while writing: {:?}", e),
+            Ok(_) => if verbose { println!("Complete!") },
+            Err(e) => return Err(e),
         };
 
-        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
-
         Ok(())
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::LidarTophatTransform;
+    use lidar::*;
+    use std::env;
+    use std::fs;
+    use std::io::ErrorKind;
+    use tools::WhiteboxTool;
+
+    #[test]
+    fn test_missing_outputs_are_rejected() {
+        let dir = env::temp_dir();
+        let input_file = dir.join("wbt_tophat_missing_output_test.las").to_str().unwrap().to_string();
+        let tool = LidarTophatTransform::new();
+        // both are rejected before the input is read, which here does not exist
+        let err = tool.run(vec![format!("-i={}", input_file), "--radius=2.0".to_string()], "", false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let working_directory = format!("{}{}", dir.to_str().unwrap(), ::std::path::MAIN_SEPARATOR);
+        let err = tool.run(vec!["--radius=2.0".to_string()], &working_directory, false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_write_errors_are_returned() {
+        let dir = env::temp_dir();
+        let input_file = dir.join("wbt_tophat_write_error_test.las").to_str().unwrap().to_string();
+        let mut las = LasFile::new(&input_file, "w").unwrap();
+        las.add_header(LasHeader { project_id_used: true, ..Default::default() });
+        for row in 0..30 {
+            for col in 0..30 {
+                let point_data = PointData { x: col as f64, y: row as f64, z: 0.1 * col as f64, ..Default::default() };
+                las.add_point_record(LidarPointRecord::PointRecord0 { point_data: point_data });
+            }
+        }
+        las.write().unwrap();
+
+        // the output is a directory, which cannot be written
+        let output_file = dir.to_str().unwrap().to_string();
+        let result = LidarTophatTransform::new().run(vec![format!("-i={}", input_file), format!("-o={}", output_file), "--radius=2.0".to_string()], "", false);
+        fs::remove_file(&input_file).unwrap();
+        assert!(result.is_err());
+    }
+}
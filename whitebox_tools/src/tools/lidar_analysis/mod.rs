@@ -1,45 +1,67 @@
 // private sub-module defined in other files
 mod block_maximum;
 mod block_minimum;
+mod clip_lidar_to_polygon;
 mod filter_lidar_scan_angles;
 mod find_flightline_edge_points;
 mod flightline_overlap;
+mod individual_tree_detection;
 mod las_to_ascii;
+mod lidar_canopy_height_model;
+mod lidar_cloth_simulation_filter;
 mod lidar_elevation_slice; 
+mod lidar_extract_raster_values;
+mod lidar_footprint;
 mod lidar_ground_point_filter;
+mod lidar_height_above_ground;
 mod lidar_hillshade;
 mod lidar_histogram;
 mod lidar_idw_interpolation;
+mod lidar_index;
 mod lidar_info;
 mod lidar_join;
 mod lidar_kappa;
 mod lidar_nn_gridding;
 mod lidar_point_density;
+mod lidar_point_stats;
+mod lidar_progressive_morphological_filter;
 mod lidar_segmentation;
 mod lidar_segmentation_based_filter;
 mod lidar_tile;
+mod lidar_tin_gridding;
 mod lidar_tophat_transform;
 mod normal_vectors;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::block_maximum::BlockMaximum;
 pub use self::block_minimum::BlockMinimum;
+pub use self::clip_lidar_to_polygon::ClipLidarToPolygon;
 pub use self::filter_lidar_scan_angles::FilterLidarScanAngles;
 pub use self::find_flightline_edge_points::FindFlightlineEdgePoints;
 pub use self::flightline_overlap::FlightlineOverlap;
+pub use self::individual_tree_detection::IndividualTreeDetection;
 pub use self::las_to_ascii::LasToAscii;
+pub use self::lidar_canopy_height_model::LidarCanopyHeightModel;
+pub use self::lidar_cloth_simulation_filter::LidarClothSimulationFilter;
 pub use self::lidar_elevation_slice::LidarElevationSlice;
+pub use self::lidar_extract_raster_values::LidarExtractRasterValues;
+pub use self::lidar_footprint::LidarFootprint;
 pub use self::lidar_ground_point_filter::LidarGroundPointFilter;
+pub use self::lidar_height_above_ground::LidarHeightAboveGround;
 pub use self::lidar_hillshade::LidarHillshade;
 pub use self::lidar_histogram::LidarHistogram;
 pub use self::lidar_idw_interpolation::LidarIdwInterpolation;
+pub use self::lidar_index::LidarIndex;
 pub use self::lidar_info::LidarInfo;
 pub use self::lidar_join::LidarJoin;
 pub use self::lidar_kappa::LidarKappaIndex;
 pub use self::lidar_nn_gridding::LidarNearestNeighbourGridding;
 pub use self::lidar_point_density::LidarPointDensity;
+pub use self::lidar_point_stats::LidarPointStats;
+pub use self::lidar_progressive_morphological_filter::LidarProgressiveMorphologicalFilter;
 pub use self::lidar_segmentation::LidarSegmentation;
 pub use self::lidar_segmentation_based_filter::LidarSegmentationBasedFilter;
 pub use self::lidar_tile::LidarTile;
+pub use self::lidar_tin_gridding::LidarTINGridding;
 pub use self::lidar_tophat_transform::LidarTophatTransform;
 pub use self::normal_vectors::NormalVectors;
\ No newline at end of file
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: June 26, 2017
-Last Modified: Dec. 16, 2017
+Last Modified: October 18, 2026
 License: MIT
 */
 extern crate time;
@@ -33,7 +33,7 @@ impl NormalVectors {
     pub fn new() -> NormalVectors { // public constructor
         let name = "NormalVectors".to_string();
         let toolbox = "LiDAR Tools".to_string();
-        let description = "Calculates normal vectors for points within a LAS file and stores these data (XYZ vector components) in the RGB field.".to_string();
+        let description = "Calculates normal vectors for points within a LAS file and stores these data (XYZ vector components) as the NormalX, NormalY and NormalZ extra bytes attributes.".to_string();
 
         let mut parameters = vec![];
         parameters.push(ToolParameter{
@@ -244,20 +244,20 @@ impl WhiteboxTool for NormalVectors {
             }
         }
 
-        // now output the data
+        // now output the data; the normals are stored as extra bytes attributes, which
+        // already exist if the input is itself the output of this tool
         let mut output = LasFile::initialize_using_file(&output_file, &input);
-        output.header.point_format = 2;
+        let names = ["NormalX", "NormalY", "NormalZ"];
+        for (name, component) in names.iter().zip(["X", "Y", "Z"].iter()) {
+            let description = format!("{} component of normal vector", component);
+            output.ensure_extra_byte_attribute(ExtraBytesDescriptor::new(name, ExtraByteType::F32, &description))?;
+        }
 
-        let (mut r, mut g, mut b): (u16, u16, u16);
         for i in 0..n_points {
-            let p: PointData = input.get_point_info(i);
-            r = ((1.0 + normal_values[i].x) / 2.0 * 255.0) as u16 * 256u16; //((1.0 + normal_values[i].x) / 2.0 * 65535.0) as u16;
-            g = ((1.0 + normal_values[i].y) / 2.0 * 255.0) as u16 * 256u16; //((1.0 + normal_values[i].y) / 2.0 * 65535.0) as u16;
-            b = ((1.0 + normal_values[i].z) / 2.0 * 255.0) as u16 * 256u16; //((1.0 + normal_values[i].z) / 2.0 * 65535.0) as u16;
-        
-            let rgb: RgbData = RgbData{ red: r, green: g, blue: b };
-            let lpr = LidarPointRecord::PointRecord2 { point_data: p, rgb_data: rgb };
-            output.add_point_record(lpr);
+            output.add_point_record_with_extra_bytes(input.get_record(i), input.get_extra_bytes(i));
+            output.set_extra_byte_value(i, names[0], normal_values[i].x)?;
+            output.set_extra_byte_value(i, names[1], normal_values[i].y)?;
+            output.set_extra_byte_value(i, names[2], normal_values[i].z)?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
@@ -344,4 +344,55 @@ fn plane_from_points(points: &Vec<Vector3<f64>>) -> Vector3<f64> {
 fn normalize(v: Vector3<f64>) -> Vector3<f64> {
     let norm = (v.x * v.x + v.y * v.y + v.z * v.z).sqrt();
     Vector3 { x: v.x/norm, y: v.y/norm, z: v.z/norm }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NormalVectors;
+    use lidar::*;
+    use std::env;
+    use std::fs;
+    use tools::WhiteboxTool;
+
+    #[test]
+    fn test_normals_of_a_plane() {
+        let dir = env::temp_dir();
+        let input_file = dir.join("wbt_normal_vectors_test_input.las").to_str().unwrap().to_string();
+        let output_file = dir.join("wbt_normal_vectors_test_output.las").to_str().unwrap().to_string();
+        let output_file2 = dir.join("wbt_normal_vectors_test_output2.las").to_str().unwrap().to_string();
+
+        // the plane z = 0.5x, whose unit normal is (-1, 0, 2) / 5^0.5, up to its sign
+        let mut las = LasFile::new(&input_file, "w").unwrap();
+        las.add_header(LasHeader { project_id_used: true, ..Default::default() });
+        for row in 0..30 {
+            for col in 0..30 {
+                let point_data = PointData { x: col as f64, y: row as f64, z: 0.5 * col as f64, ..Default::default() };
+                las.add_point_record(LidarPointRecord::PointRecord0 { point_data: point_data });
+            }
+        }
+        las.write().unwrap();
+
+        let tool = NormalVectors::new();
+        tool.run(vec![format!("-i={}", input_file), format!("-o={}", output_file), "--radius=2.5".to_string()], "", false).unwrap();
+        // the attributes of an input that already has them are reused
+        tool.run(vec![format!("-i={}", output_file), format!("-o={}", output_file2), "--radius=2.5".to_string()], "", false).unwrap();
+
+        let output = LasFile::new(&output_file2, "r").unwrap();
+        for file in &[&input_file, &output_file, &output_file2] {
+            fs::remove_file(file).unwrap();
+        }
+        let names: Vec<String> = output.get_extra_byte_attributes().iter().map(|eb| eb.name.clone()).collect();
+        assert_eq!(names, vec!["NormalX", "NormalY", "NormalZ"]);
+        assert_eq!(output.header.number_of_points, 900);
+        let (nx, nz) = (-1f64 / 5f64.sqrt(), 2f64 / 5f64.sqrt());
+        for i in 0..900 {
+            let x = output.get_extra_byte_value(i, "NormalX").unwrap();
+            let y = output.get_extra_byte_value(i, "NormalY").unwrap();
+            let z = output.get_extra_byte_value(i, "NormalZ").unwrap();
+            let sign = if z < 0f64 { -1f64 } else { 1f64 };
+            assert!((sign * x - nx).abs() < 1e-4, "point {}: {} {} {}", i, x, y, z);
+            assert!(y.abs() < 1e-4);
+            assert!((sign * z - nz).abs() < 1e-4);
+        }
+    }
+}
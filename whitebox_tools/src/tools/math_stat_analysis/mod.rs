@@ -63,6 +63,7 @@ mod to_radians;
 mod truncate;
 mod turning_bands;
 mod xor;
+mod zonal_statistics;
 mod zscores;
 
 
@@ -131,4 +132,5 @@ pub use self::to_radians::ToRadians;
 pub use self::truncate::Truncate;
 pub use self::turning_bands::TurningBandsSimulation;
 pub use self::xor::Xor;
+pub use self::zonal_statistics::ZonalStatistics;
 pub use self::zscores::ZScores;
\ No newline at end of file
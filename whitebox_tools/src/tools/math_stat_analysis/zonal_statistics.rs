@@ -0,0 +1,572 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+
+Notes: Unlike ExtractRasterStatistics, the zones are defined by vector polygons
+rather than a raster, and cells that are only partly within a polygon contribute
+to its statistics in proportion to the fraction of the cell that is covered.
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::f64;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::prelude::*;
+use std::collections::HashMap;
+use raster::*;
+use vector::*;
+use algorithms::polygon_cell_coverage;
+use structures::Point2D;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Calculates descriptive statistics of one or more rasters within each
+/// feature of a vector polygon file. Each grid cell is weighted by the
+/// fraction of its area that is covered by the polygon (holes excluded), such
+/// that the statistics of small features are not biased by the cell-centre
+/// rule. The available statistics (`--stats`) are: count (the weighted number
+/// of cells), mean, min, max, sum (the coverage-weighted total), stdev (the
+/// weighted population standard deviation), median, majority (the value with
+/// the greatest covered area), and any percentile, written as pNN (e.g. p10,
+/// p90). NoData cells are ignored.
+///
+/// Results are appended as attribute fields to a copy of the polygons
+/// (`--output`) and/or written to a CSV table (`--out_csv`). Both fields and
+/// columns are named after the raster file and the statistic (e.g. dem_MEAN
+/// for dem.tif). Field names are shortened to the 10 character limit of dBase
+/// fields by truncating the file name, which is numbered if two rasters would
+/// otherwise share a name (e.g. eleva_MEAN and elev2_MEAN).
+pub struct ZonalStatistics {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ZonalStatistics {
+    pub fn new() -> ZonalStatistics { // public constructor
+        let name = "ZonalStatistics".to_string();
+        let toolbox = "Math and Stats Tools".to_string();
+        let description = "Calculates coverage-weighted statistics of one or more rasters within vector polygons.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Raster Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input raster files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input Vector Polygon File".to_owned(),
+            flags: vec!["--polygons".to_owned()],
+            description: "Input vector polygons file defining the zones.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector Polygon File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polygons file, a copy of the input with the statistics appended as fields.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output CSV File".to_owned(),
+            flags: vec!["--out_csv".to_owned()],
+            description: "Output CSV table file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Statistics".to_owned(),
+            flags: vec!["--stats".to_owned()],
+            description: "Comma-separated list of statistics: count, mean, min, max, sum, stdev, median, majority, and pNN percentiles.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: Some("mean,min,max,sum,stdev".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='dem.tif;slope.tif' --polygons=watersheds.shp -o=watershed_stats.shp --stats='mean,min,max,stdev,p90'
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=landcover.tif --polygons=parcels.shp --out_csv=parcels.csv --stats=majority", short_exe, name).replace("*", &sep);
+
+        ZonalStatistics {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for ZonalStatistics {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_files = String::new();
+        let mut polygons_file = String::new();
+        let mut output_file = String::new();
+        let mut csv_file = String::new();
+        let mut stats_list = String::from("mean,min,max,sum,stdev");
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-inputs" {
+                if keyval {
+                    input_files = vec[1].to_string();
+                } else {
+                    input_files = args[i+1].to_string();
+                }
+            } else if flag_val == "-polygons" {
+                if keyval {
+                    polygons_file = vec[1].to_string();
+                } else {
+                    polygons_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-out_csv" {
+                if keyval {
+                    csv_file = vec[1].to_string();
+                } else {
+                    csv_file = args[i+1].to_string();
+                }
+            } else if flag_val == "-stats" {
+                if keyval {
+                    stats_list = vec[1].to_string();
+                } else {
+                    stats_list = args[i+1].to_string();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !polygons_file.contains(&sep) {
+            polygons_file = format!("{}{}", working_directory, polygons_file);
+        }
+        if !output_file.is_empty() && !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !csv_file.is_empty() && !csv_file.contains(&sep) {
+            csv_file = format!("{}{}", working_directory, csv_file);
+        }
+        if output_file.is_empty() && csv_file.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "At least one of --output or --out_csv must be specified."));
+        }
+
+        let mut cmd = input_files.split(";");
+        let mut vec = cmd.collect::<Vec<&str>>();
+        if vec.len() == 1 {
+            cmd = input_files.split(",");
+            vec = cmd.collect::<Vec<&str>>();
+        }
+        let mut raster_files = vec![];
+        for value in vec {
+            if !value.trim().is_empty() {
+                let mut input_file = value.trim().to_owned();
+                if !input_file.contains(&sep) {
+                    input_file = format!("{}{}", working_directory, input_file);
+                }
+                raster_files.push(input_file);
+            }
+        }
+        if raster_files.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "At least one input raster must be specified."));
+        }
+
+        let mut stats: Vec<Statistic> = vec![];
+        for s in stats_list.split(",") {
+            let s = s.trim().to_lowercase();
+            if s.is_empty() {
+                continue;
+            }
+            let stat = match s.as_ref() {
+                "count" => Statistic::Count,
+                "mean" | "average" => Statistic::Mean,
+                "min" | "minimum" => Statistic::Min,
+                "max" | "maximum" => Statistic::Max,
+                "sum" | "total" => Statistic::Sum,
+                "stdev" | "std_dev" | "standard deviation" => Statistic::StdDev,
+                "median" => Statistic::Percentile(50f64),
+                "majority" => Statistic::Majority,
+                _ => {
+                    let p = if s.starts_with("p") { s[1..].parse::<f64>().unwrap_or(-1f64) } else { -1f64 };
+                    if p < 0f64 || p > 100f64 {
+                        return Err(Error::new(ErrorKind::InvalidInput,
+                            format!("Unrecognized statistic '{}'.", s)));
+                    }
+                    Statistic::Percentile(p)
+                }
+            };
+            stats.push(stat);
+        }
+        if stats.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "At least one statistic must be specified."));
+        }
+
+        if verbose { println!("Reading data...") };
+        let polygons = Shapefile::new(&polygons_file, "r")?;
+
+        if polygons.header.shape_type.base_shape_type() != ShapeType::Polygon {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "The input vector data must be of polygon base shape type."));
+        }
+
+        let start = time::now();
+
+        let num_records = polygons.num_records;
+        let num_rasters = raster_files.len();
+        // results[raster][record][stat]
+        let mut results: Vec<Vec<Vec<Option<f64>>>> = Vec::with_capacity(num_rasters);
+        for (r, raster_file) in raster_files.iter().enumerate() {
+            let input = Raster::new(raster_file, "r")?;
+            let nodata = input.configs.nodata;
+            let rows = input.configs.rows as isize;
+            let columns = input.configs.columns as isize;
+            let mut raster_results = Vec::with_capacity(num_records);
+            let mut values: Vec<(f64, f64)> = vec![];
+            for record_num in 0..num_records {
+                let record = polygons.get_record(record_num);
+                values.clear();
+                if record.shape_type != ShapeType::Null {
+                    let rings: Vec<&[Point2D]> = (0..record.num_parts as usize).map(|part| record.get_part(part)).collect();
+                    let coverage = polygon_cell_coverage(&rings,
+                                                         input.configs.west,
+                                                         input.configs.north,
+                                                         input.configs.resolution_x,
+                                                         input.configs.resolution_y,
+                                                         rows,
+                                                         columns);
+                    for (row, col, weight) in coverage {
+                        let z = input.get_value(row, col);
+                        if z != nodata {
+                            values.push((z, weight));
+                        }
+                    }
+                }
+                raster_results.push(weighted_statistics(&mut values, &stats));
+
+                if verbose {
+                    progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
+                    if progress != old_progress {
+                        println!("Raster {} of {}: {}%", r + 1, num_rasters, progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            results.push(raster_results);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        // the fields and CSV columns are named after the raster files
+        let stems: Vec<String> = raster_files.iter().map(|raster_file| {
+            match path::Path::new(raster_file).file_stem() {
+                Some(s) => s.to_string_lossy().to_string(),
+                None => raster_file.clone(),
+            }
+        }).collect();
+
+        if !output_file.is_empty() {
+            let mut output = Shapefile::initialize_using_file(&output_file, &polygons, polygons.header.shape_type, true)?;
+            for record_num in 0..num_records {
+                output.add_record(polygons.get_record(record_num).clone());
+                output.attributes.add_record(polygons.attributes.get_record(record_num),
+                                             polygons.attributes.is_deleted(record_num));
+            }
+            let mut field_names: Vec<String> = vec![];
+            for r in 0..num_rasters {
+                for (s, stat) in stats.iter().enumerate() {
+                    let suffix = format!("_{}", stat.get_name());
+                    let field_name = get_prefixed_field_name(&stems[r], &suffix, &field_names);
+                    field_names.push(field_name.clone());
+                    if verbose && field_name != format!("{}{}", stems[r], suffix) {
+                        println!("{}{} is written to the field {}", stems[r], suffix, field_name);
+                    }
+                    // re-running the tool overwrites rather than duplicates fields
+                    if output.attributes.get_field_num(&field_name).is_none() {
+                        output.attributes.add_field(&AttributeField::new(&field_name, FieldDataType::Real, 18u8, 6u8));
+                    }
+                    for record_num in 0..num_records {
+                        let value = match results[r][record_num][s] {
+                            Some(v) => FieldData::Real(v),
+                            None => FieldData::Null,
+                        };
+                        output.attributes.set_value(record_num, &field_name, value);
+                    }
+                }
+            }
+
+            if verbose { println!("Saving data...") };
+            let _ = match output.write() {
+                Ok(_) => if verbose { println!("Output file written") },
+                Err(e) => return Err(e),
+            };
+        }
+
+        if !csv_file.is_empty() {
+            let f = File::create(&csv_file)?;
+            let mut writer = BufWriter::new(f);
+            let mut header = String::from("FID");
+            for stem in &stems {
+                for stat in &stats {
+                    header.push_str(&format!(",{}_{}", stem, stat.get_name()));
+                }
+            }
+            writer.write_all(format!("{}\n", header).as_bytes())?;
+            for record_num in 0..num_records {
+                let mut line = format!("{}", record_num + 1);
+                for r in 0..num_rasters {
+                    for s in 0..stats.len() {
+                        match results[r][record_num][s] {
+                            Some(v) => line.push_str(&format!(",{}", v)),
+                            None => line.push_str(","),
+                        }
+                    }
+                }
+                writer.write_all(format!("{}\n", line).as_bytes())?;
+            }
+            let _ = writer.flush();
+            if verbose { println!("CSV file written") };
+        }
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Statistic {
+    Count,
+    Mean,
+    Min,
+    Max,
+    Sum,
+    StdDev,
+    Majority,
+    Percentile(f64),
+}
+
+impl Statistic {
+    fn get_name(&self) -> String {
+        match *self {
+            Statistic::Count => "COUNT".to_string(),
+            Statistic::Mean => "MEAN".to_string(),
+            Statistic::Min => "MIN".to_string(),
+            Statistic::Max => "MAX".to_string(),
+            Statistic::Sum => "SUM".to_string(),
+            Statistic::StdDev => "STDEV".to_string(),
+            Statistic::Majority => "MAJORITY".to_string(),
+            Statistic::Percentile(p) => if p == 50f64 {
+                "MEDIAN".to_string()
+            } else {
+                format!("P{}", p)
+            },
+        }
+    }
+}
+
+/// Calculates the requested statistics of a set of (value, weight) pairs. The
+/// values are sorted in place. All statistics are None if the total weight is zero.
+fn weighted_statistics(values: &mut Vec<(f64, f64)>, stats: &[Statistic]) -> Vec<Option<f64>> {
+    let total_weight: f64 = values.iter().map(|v| v.1).sum();
+    if values.is_empty() || total_weight <= 0f64 {
+        return vec![None; stats.len()];
+    }
+    values.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let sum: f64 = values.iter().map(|v| v.0 * v.1).sum();
+    let mean = sum / total_weight;
+    let mut ret = Vec::with_capacity(stats.len());
+    for stat in stats {
+        let value = match *stat {
+            Statistic::Count => total_weight,
+            Statistic::Mean => mean,
+            Statistic::Min => values[0].0,
+            Statistic::Max => values[values.len() - 1].0,
+            Statistic::Sum => sum,
+            Statistic::StdDev => {
+                let ss: f64 = values.iter().map(|v| v.1 * (v.0 - mean) * (v.0 - mean)).sum();
+                (ss / total_weight).sqrt()
+            }
+            Statistic::Majority => {
+                let mut areas: HashMap<u64, f64> = HashMap::new();
+                for v in values.iter() {
+                    *areas.entry(v.0.to_bits()).or_insert(0f64) += v.1;
+                }
+                // values are sorted, so ties go to the smallest value
+                let mut majority = values[0].0;
+                let mut max_area = 0f64;
+                for v in values.iter() {
+                    let area = areas[&v.0.to_bits()];
+                    if area > max_area {
+                        max_area = area;
+                        majority = v.0;
+                    }
+                }
+                majority
+            }
+            Statistic::Percentile(p) => {
+                // the smallest value at which the cumulative weight reaches p percent of the total
+                let target = p / 100f64 * total_weight;
+                let mut cumulative = 0f64;
+                let mut percentile = values[values.len() - 1].0;
+                for v in values.iter() {
+                    cumulative += v.1;
+                    if cumulative >= target - 1e-9 * total_weight {
+                        percentile = v.0;
+                        break;
+                    }
+                }
+                percentile
+            }
+        };
+        ret.push(Some(value));
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ZonalStatistics;
+    use raster::*;
+    use std::env;
+    use std::fs;
+    use std::io::Read;
+    use structures::Point2D;
+    use tools::WhiteboxTool;
+    use vector::*;
+
+    #[test]
+    fn test_fields_and_columns_are_named_after_the_rasters() {
+        let dir = env::temp_dir().join("wbt_zonal_statistics_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let file = |name: &str| dir.join(name).to_str().unwrap().to_string();
+
+        // two 2 x 2 rasters and a polygon covering them
+        let mut configs = RasterConfigs { ..Default::default() };
+        configs.rows = 2;
+        configs.columns = 2;
+        configs.north = 2.0;
+        configs.south = 0.0;
+        configs.east = 2.0;
+        configs.west = 0.0;
+        configs.resolution_x = 1.0;
+        configs.resolution_y = 1.0;
+        configs.data_type = DataType::F32;
+        for (name, value) in vec![("dem.dep", 1.0), ("elevation.dep", 2.0)] {
+            let mut raster = Raster::initialize_using_config(&file(name), &configs);
+            for row in 0..2 {
+                for col in 0..2 {
+                    raster.set_value(row, col, value);
+                }
+            }
+            raster.write().unwrap();
+        }
+        let mut polygons = Shapefile::new(&file("polygons.shp"), "w").unwrap();
+        polygons.header.shape_type = ShapeType::Polygon;
+        polygons.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+        let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+        geom.add_part(&[Point2D::new(0.0, 0.0), Point2D::new(0.0, 2.0), Point2D::new(2.0, 2.0),
+                        Point2D::new(2.0, 0.0), Point2D::new(0.0, 0.0)]);
+        polygons.add_record(geom);
+        polygons.attributes.add_record(vec![FieldData::Int(1)], false);
+        polygons.write().unwrap();
+
+        let args = vec![format!("-i={};{}", file("dem.dep"), file("elevation.dep")),
+                        format!("--polygons={}", file("polygons.shp")),
+                        format!("-o={}", file("output.shp")),
+                        format!("--out_csv={}", file("output.csv")),
+                        "--stats=mean,majority".to_string()];
+        ZonalStatistics::new().run(args, "", false).unwrap();
+
+        let output = Shapefile::new(&file("output.shp"), "r").unwrap();
+        let mut csv = String::new();
+        fs::File::open(&file("output.csv")).unwrap().read_to_string(&mut csv).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<String> = output.attributes.fields.iter().map(|f| f.name.clone()).collect();
+        assert_eq!(names, vec!["FID", "dem_MEAN", "d_MAJORITY", "eleva_MEAN", "e_MAJORITY"]);
+        assert_eq!(output.attributes.get_value(0, "eleva_MEAN"), FieldData::Real(2.0));
+        let header = csv.lines().next().unwrap();
+        assert_eq!(header, "FID,dem_MEAN,dem_MAJORITY,elevation_MEAN,elevation_MAJORITY");
+    }
+}
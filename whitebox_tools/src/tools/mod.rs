@@ -27,29 +27,48 @@ impl ToolManager {
         // data_tools
         tool_names.push("ConvertNodataToZero".to_string());
         tool_names.push("ConvertRasterFormat".to_string());
+        tool_names.push("ExportWellKnownText".to_string());
+        tool_names.push("FieldCalculator".to_string());
         tool_names.push("NewRasterFromBase".to_string());
+        tool_names.push("RasterToVectorPolygons".to_string());
         tool_names.push("SetNodataValue".to_string());
+        tool_names.push("VectorLinesToRaster".to_string());
+        tool_names.push("VectorPointsToRaster".to_string());
+        tool_names.push("VectorPolygonsToRaster".to_string());
 
         // gis_analysis
         tool_names.push("AggregateRaster".to_string());
         tool_names.push("AverageOverlay".to_string());
         tool_names.push("BufferRaster".to_string());
+        tool_names.push("BufferVector".to_string());
         tool_names.push("Centroid".to_string());
+        tool_names.push("Clip".to_string());
+        tool_names.push("ClipRasterToExtent".to_string());
+        tool_names.push("ClipRasterToPolygon".to_string());
         tool_names.push("Clump".to_string());
         tool_names.push("CostAllocation".to_string());
         tool_names.push("CostDistance".to_string());
         tool_names.push("CostPathway".to_string());
         tool_names.push("CreatePlane".to_string());
+        tool_names.push("Difference".to_string());
         tool_names.push("EdgeProportion".to_string());
+        tool_names.push("ErasePolygonFromRaster".to_string());
         tool_names.push("EuclideanAllocation".to_string());
         tool_names.push("EuclideanDistance".to_string());
+        tool_names.push("ExtractByAttribute".to_string());
+        tool_names.push("ExtractRasterValuesAtPoints".to_string());
         tool_names.push("FindPatchOrClassEdgeCells".to_string());
+        tool_names.push("Fuse".to_string());
         tool_names.push("HighestPosition".to_string());
+        tool_names.push("Intersect".to_string());
+        tool_names.push("LayerFootprint".to_string());
         tool_names.push("LowestPosition".to_string());
         tool_names.push("MaxAbsoluteOverlay".to_string());
         tool_names.push("MaxOverlay".to_string());
         tool_names.push("MinAbsoluteOverlay".to_string());
         tool_names.push("MinOverlay".to_string());
+        tool_names.push("MinimumBoundingBox".to_string());
+        tool_names.push("MinimumConvexHull".to_string());
         tool_names.push("PercentEqualTo".to_string());
         tool_names.push("PercentGreaterThan".to_string());
         tool_names.push("PercentLessThan".to_string());
@@ -59,6 +78,10 @@ impl ToolManager {
         tool_names.push("Reclass".to_string());
         tool_names.push("ReclassEqualInterval".to_string());
         tool_names.push("ReclassFromFile".to_string());
+        tool_names.push("SimplifyLineOrPolygon".to_string());
+        tool_names.push("SmoothVectors".to_string());
+        tool_names.push("SymmetricDifference".to_string());
+        tool_names.push("Union".to_string());
         tool_names.push("WeightedSum".to_string());
 
         // hydro_analysis
@@ -159,23 +182,34 @@ impl ToolManager {
         // lidar_analysis
         tool_names.push("BlockMaximum".to_string());
         tool_names.push("BlockMinimum".to_string());
+        tool_names.push("ClipLidarToPolygon".to_string());
         tool_names.push("FilterLidarScanAngles".to_string());
         tool_names.push("FindFlightlineEdgePoints".to_string());
         tool_names.push("FlightlineOverlap".to_string());
+        tool_names.push("IndividualTreeDetection".to_string());
         tool_names.push("LasToAscii".to_string());
+        tool_names.push("LidarCanopyHeightModel".to_string());
+        tool_names.push("LidarClothSimulationFilter".to_string());
         tool_names.push("LidarElevationSlice".to_string());
+        tool_names.push("LidarExtractRasterValues".to_string());
+        tool_names.push("LidarFootprint".to_string());
         tool_names.push("LidarGroundPointFilter".to_string());
+        tool_names.push("LidarHeightAboveGround".to_string());
         tool_names.push("LidarHillshade".to_string());
         tool_names.push("LidarHistogram".to_string());
         tool_names.push("LidarIdwInterpolation".to_string());
+        tool_names.push("LidarIndex".to_string());
         tool_names.push("LidarInfo".to_string());
         tool_names.push("LidarJoin".to_string());
         tool_names.push("LidarKappaIndex".to_string());
         tool_names.push("LidarNearestNeighbourGridding".to_string());
         tool_names.push("LidarPointDensity".to_string());
+        tool_names.push("LidarPointStats".to_string());
+        tool_names.push("LidarProgressiveMorphologicalFilter".to_string());
         tool_names.push("LidarSegmentation".to_string());
         tool_names.push("LidarSegmentationBasedFilter".to_string());
         tool_names.push("LidarTile".to_string());
+        tool_names.push("LidarTINGridding".to_string());
         tool_names.push("LidarTophatTransform".to_string());
         tool_names.push("NormalVectors".to_string());
 
@@ -244,6 +278,7 @@ impl ToolManager {
         tool_names.push("Truncate".to_string());
         tool_names.push("TurningBandsSimulation".to_string());
         tool_names.push("Xor".to_string());
+        tool_names.push("ZonalStatistics".to_string());
         tool_names.push("ZScores".to_string());
 
         // stream_network_analysis
@@ -255,6 +290,7 @@ impl ToolManager {
         tool_names.push("HackStreamOrder".to_string());
         tool_names.push("HortonStreamOrder".to_string());
         tool_names.push("LengthOfUpstreamChannels".to_string());
+        tool_names.push("RasterStreamsToVector".to_string());
         tool_names.push("RemoveShortStreams".to_string());
         tool_names.push("ShreveStreamMagnitude".to_string());
         tool_names.push("StrahlerStreamOrder".to_string());
@@ -268,6 +304,7 @@ impl ToolManager {
 
         // terrain_analysis
         tool_names.push("Aspect".to_string());
+        tool_names.push("ContourLines".to_string());
         tool_names.push("FeaturePreservingDenoise".to_string());
         tool_names.push("DevFromMeanElev".to_string());
         tool_names.push("DiffFromMeanElev".to_string());
@@ -320,31 +357,50 @@ impl ToolManager {
             // data_tools
             "convertnodatatozero" => Some(Box::new(tools::data_tools::ConvertNodataToZero::new())),
             "convertrasterformat" => Some(Box::new(tools::data_tools::ConvertRasterFormat::new())),
+            "exportwellknowntext" => Some(Box::new(tools::data_tools::ExportWellKnownText::new())),
+            "fieldcalculator" => Some(Box::new(tools::data_tools::FieldCalculator::new())),
             "newrasterfrombase" => Some(Box::new(tools::data_tools::NewRasterFromBase::new())),
+            "rastertovectorpolygons" => Some(Box::new(tools::data_tools::RasterToVectorPolygons::new())),
             "setnodatavalue" => Some(Box::new(tools::data_tools::SetNodataValue::new())),
+            "vectorlinestoraster" => Some(Box::new(tools::data_tools::VectorLinesToRaster::new())),
+            "vectorpointstoraster" => Some(Box::new(tools::data_tools::VectorPointsToRaster::new())),
+            "vectorpolygonstoraster" => Some(Box::new(tools::data_tools::VectorPolygonsToRaster::new())),
 
             // gis_analysis
             "aggregateraster" => Some(Box::new(tools::gis_analysis::AggregateRaster::new())),
             "averageoverlay" => Some(Box::new(tools::gis_analysis::AverageOverlay::new())),
             "bufferraster" => Some(Box::new(tools::gis_analysis::BufferRaster::new())),
+            "buffervector" => Some(Box::new(tools::gis_analysis::BufferVector::new())),
             "centroid" => Some(Box::new(tools::gis_analysis::Centroid::new())),
+            "clip" => Some(Box::new(tools::gis_analysis::Clip::new())),
+            "cliprastertoextent" => Some(Box::new(tools::gis_analysis::ClipRasterToExtent::new())),
+            "cliprastertopolygon" => Some(Box::new(tools::gis_analysis::ClipRasterToPolygon::new())),
             "clump" => Some(Box::new(tools::gis_analysis::Clump::new())),
             "costallocation" => Some(Box::new(tools::gis_analysis::CostAllocation::new())),
             "costdistance" => Some(Box::new(tools::gis_analysis::CostDistance::new())),
             "costpathway" => Some(Box::new(tools::gis_analysis::CostPathway::new())),
             "createplane" => Some(Box::new(tools::gis_analysis::CreatePlane::new())),
+            "difference" => Some(Box::new(tools::gis_analysis::Difference::new())),
             "edgeproportion" => Some(Box::new(tools::gis_analysis::EdgeProportion::new())),
+            "erasepolygonfromraster" => Some(Box::new(tools::gis_analysis::ErasePolygonFromRaster::new())),
             "euclideanallocation" => {
                 Some(Box::new(tools::gis_analysis::EuclideanAllocation::new()))
             }
             "euclideandistance" => Some(Box::new(tools::gis_analysis::EuclideanDistance::new())),
+            "extractbyattribute" => Some(Box::new(tools::gis_analysis::ExtractByAttribute::new())),
+            "extractrastervaluesatpoints" => Some(Box::new(tools::gis_analysis::ExtractRasterValuesAtPoints::new())),
             "findpatchorclassedgecells" => Some(Box::new(tools::gis_analysis::FindPatchOrClassEdgeCells::new())),
+            "fuse" => Some(Box::new(tools::gis_analysis::Fuse::new())),
             "highestposition" => Some(Box::new(tools::gis_analysis::HighestPosition::new())),
+            "intersect" => Some(Box::new(tools::gis_analysis::Intersect::new())),
+            "layerfootprint" => Some(Box::new(tools::gis_analysis::LayerFootprint::new())),
             "lowestposition" => Some(Box::new(tools::gis_analysis::LowestPosition::new())),
             "maxabsoluteoverlay" => Some(Box::new(tools::gis_analysis::MaxAbsoluteOverlay::new())),
             "maxoverlay" => Some(Box::new(tools::gis_analysis::MaxOverlay::new())),
             "minabsoluteoverlay" => Some(Box::new(tools::gis_analysis::MinAbsoluteOverlay::new())),
             "minoverlay" => Some(Box::new(tools::gis_analysis::MinOverlay::new())),
+            "minimumboundingbox" => Some(Box::new(tools::gis_analysis::MinimumBoundingBox::new())),
+            "minimumconvexhull" => Some(Box::new(tools::gis_analysis::MinimumConvexHull::new())),
             "percentequalto" => Some(Box::new(tools::gis_analysis::PercentEqualTo::new())),
             "percentgreaterthan" => Some(Box::new(tools::gis_analysis::PercentGreaterThan::new())),
             "percentlessthan" => Some(Box::new(tools::gis_analysis::PercentLessThan::new())),
@@ -356,6 +412,10 @@ impl ToolManager {
                 Some(Box::new(tools::gis_analysis::ReclassEqualInterval::new()))
             }
             "reclassfromfile" => Some(Box::new(tools::gis_analysis::ReclassFromFile::new())),
+            "simplifylineorpolygon" => Some(Box::new(tools::gis_analysis::SimplifyLineOrPolygon::new())),
+            "smoothvectors" => Some(Box::new(tools::gis_analysis::SmoothVectors::new())),
+            "symmetricdifference" => Some(Box::new(tools::gis_analysis::SymmetricDifference::new())),
+            "union" => Some(Box::new(tools::gis_analysis::Union::new())),
             "weightedsum" => Some(Box::new(tools::gis_analysis::WeightedSum::new())),
 
 
@@ -494,21 +554,29 @@ impl ToolManager {
             // lidar_analysis
             "blockmaximum" => Some(Box::new(tools::lidar_analysis::BlockMaximum::new())),
             "blockminimum" => Some(Box::new(tools::lidar_analysis::BlockMinimum::new())),
+            "cliplidartopolygon" => Some(Box::new(tools::lidar_analysis::ClipLidarToPolygon::new())),
             "filterlidarscanangles" => Some(Box::new(tools::lidar_analysis::FilterLidarScanAngles::new())),
             "findflightlineedgepoints" => Some(Box::new(tools::lidar_analysis::FindFlightlineEdgePoints::new())),
             "flightlineoverlap" => Some(Box::new(tools::lidar_analysis::FlightlineOverlap::new())),
+            "individualtreedetection" => Some(Box::new(tools::lidar_analysis::IndividualTreeDetection::new())),
             "lastoascii" => Some(Box::new(tools::lidar_analysis::LasToAscii::new())),
+            "lidarcanopyheightmodel" => Some(Box::new(tools::lidar_analysis::LidarCanopyHeightModel::new())),
+            "lidarclothsimulationfilter" => Some(Box::new(tools::lidar_analysis::LidarClothSimulationFilter::new())),
             "lidarelevationslice" => {
                 Some(Box::new(tools::lidar_analysis::LidarElevationSlice::new()))
             }
+            "lidarextractrastervalues" => Some(Box::new(tools::lidar_analysis::LidarExtractRasterValues::new())),
+            "lidarfootprint" => Some(Box::new(tools::lidar_analysis::LidarFootprint::new())),
             "lidargroundpointfilter" => {
                 Some(Box::new(tools::lidar_analysis::LidarGroundPointFilter::new()))
             }
+            "lidarheightaboveground" => Some(Box::new(tools::lidar_analysis::LidarHeightAboveGround::new())),
             "lidarhillshade" => Some(Box::new(tools::lidar_analysis::LidarHillshade::new())),
             "lidarhistogram" => Some(Box::new(tools::lidar_analysis::LidarHistogram::new())),
             "lidaridwinterpolation" => {
                 Some(Box::new(tools::lidar_analysis::LidarIdwInterpolation::new()))
             }
+            "lidarindex" => Some(Box::new(tools::lidar_analysis::LidarIndex::new())),
             "lidarinfo" => Some(Box::new(tools::lidar_analysis::LidarInfo::new())),
             "lidarjoin" => Some(Box::new(tools::lidar_analysis::LidarJoin::new())),
             "lidarkappaindex" => Some(Box::new(tools::lidar_analysis::LidarKappaIndex::new())),
@@ -516,9 +584,12 @@ impl ToolManager {
                 Some(Box::new(tools::lidar_analysis::LidarNearestNeighbourGridding::new()))
             }
             "lidarpointdensity" => Some(Box::new(tools::lidar_analysis::LidarPointDensity::new())),
+            "lidarpointstats" => Some(Box::new(tools::lidar_analysis::LidarPointStats::new())),
+            "lidarprogressivemorphologicalfilter" => Some(Box::new(tools::lidar_analysis::LidarProgressiveMorphologicalFilter::new())),
             "lidarsegmentation" => Some(Box::new(tools::lidar_analysis::LidarSegmentation::new())),
             "lidarsegmentationbasedfilter" => Some(Box::new(tools::lidar_analysis::LidarSegmentationBasedFilter::new())),
             "lidartile" => Some(Box::new(tools::lidar_analysis::LidarTile::new())),
+            "lidartingridding" => Some(Box::new(tools::lidar_analysis::LidarTINGridding::new())),
             "lidartophattransform" => {
                 Some(Box::new(tools::lidar_analysis::LidarTophatTransform::new()))
             }
@@ -589,6 +660,7 @@ impl ToolManager {
             "truncate" => Some(Box::new(tools::math_stat_analysis::Truncate::new())),
             "turningbandssimulation" => Some(Box::new(tools::math_stat_analysis::TurningBandsSimulation::new())),
             "xor" => Some(Box::new(tools::math_stat_analysis::Xor::new())),
+            "zonalstatistics" => Some(Box::new(tools::math_stat_analysis::ZonalStatistics::new())),
             "zscores" => Some(Box::new(tools::math_stat_analysis::ZScores::new())),
 
             // stream_network_analysis
@@ -612,6 +684,9 @@ impl ToolManager {
             "lengthofupstreamchannels" => {
                 Some(Box::new(tools::stream_network_analysis::LengthOfUpstreamChannels::new()))
             }
+            "rasterstreamstovector" => {
+                Some(Box::new(tools::stream_network_analysis::RasterStreamsToVector::new()))
+            }
             "removeshortstreams" => {
                 Some(Box::new(tools::stream_network_analysis::RemoveShortStreams::new()))
             }
@@ -645,6 +720,7 @@ impl ToolManager {
 
             // terrain_analysis
             "aspect" => Some(Box::new(tools::terrain_analysis::Aspect::new())),
+            "contourlines" => Some(Box::new(tools::terrain_analysis::ContourLines::new())),
             "featurepreservingdenoise" => Some(Box::new(tools::terrain_analysis::FeaturePreservingDenoise::new())),
             "devfrommeanelev" => Some(Box::new(tools::terrain_analysis::DevFromMeanElev::new())),
             "difffrommeanelev" => Some(Box::new(tools::terrain_analysis::DiffFromMeanElev::new())),
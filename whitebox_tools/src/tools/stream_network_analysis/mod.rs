@@ -6,6 +6,7 @@ mod farthest_channel_head;
 mod find_main_stem;
 mod hack_order;
 mod horton_order;
+mod raster_streams_to_vector;
 mod remove_short_streams;
 mod shreve_magnitude;
 mod strahler_order;
@@ -27,6 +28,7 @@ pub use self::farthest_channel_head::FarthestChannelHead;
 pub use self::find_main_stem::FindMainStem;
 pub use self::hack_order::HackStreamOrder;
 pub use self::horton_order::HortonStreamOrder;
+pub use self::raster_streams_to_vector::RasterStreamsToVector;
 pub use self::remove_short_streams::RemoveShortStreams;
 pub use self::shreve_magnitude::ShreveStreamMagnitude;
 pub use self::strahler_order::StrahlerStreamOrder;
@@ -0,0 +1,518 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::f64;
+use std::collections::VecDeque;
+use raster::*;
+use vector::*;
+use std::io::{Error, ErrorKind};
+use structures::{Array2D, Point2D};
+use tools::*;
+
+/// Converts a raster stream network into a vector of polylines, one per stream
+/// link. Each line is ordered from upstream to downstream and ends at the first
+/// cell of the link that it drains into, so that the lines of the network meet
+/// at confluences. The attribute table contains the link identifier (LINK_ID),
+/// the downstream link identifier (DS_LINK; 0 at outlets), a comma-separated
+/// list of the upstream link identifiers (US_LINKS), the Strahler, Shreve and
+/// Horton orders, the link length (LENGTH) and, if a DEM is supplied, the
+/// average slope of the link, in percent (SLOPE). Single-cell outlet links are
+/// extended to the edge of the cell in the direction of flow; those without a
+/// flow direction are output as null shapes, preserving the network topology.
+pub struct RasterStreamsToVector {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl RasterStreamsToVector {
+    pub fn new() -> RasterStreamsToVector { // public constructor
+        let name = "RasterStreamsToVector".to_string();
+        let toolbox = "Stream Network Analysis".to_string();
+        let description = "Converts a raster stream network into vector polylines attributed with link topology and stream order.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Streams File".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input D8 Pointer File".to_owned(),
+            flags: vec!["--d8_pntr".to_owned()],
+            description: "Input raster D8 pointer file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Input DEM File (optional)".to_owned(),
+            flags: vec!["--dem".to_owned()],
+            description: "Input raster DEM file, used to calculate link slopes.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polyline file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
+            flags: vec!["--esri_pntr".to_owned()],
+            description: "D8 pointer uses the ESRI style scheme.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --streams=streams.dep --d8_pntr=D8.dep -o=streams.shp
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --streams=streams.flt --d8_pntr=D8.flt --dem=dem.flt -o=streams.shp --esri_pntr", short_exe, name).replace("*", &sep);
+
+        RasterStreamsToVector {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for RasterStreamsToVector {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut streams_file = String::new();
+        let mut d8_file = String::new();
+        let mut dem_file = String::new();
+        let mut output_file = String::new();
+        let mut esri_style = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            if vec[0].to_lowercase() == "-streams" || vec[0].to_lowercase() == "--streams" {
+                if keyval {
+                    streams_file = vec[1].to_string();
+                } else {
+                    streams_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-d8_pntr" || vec[0].to_lowercase() == "--d8_pntr" {
+                if keyval {
+                    d8_file = vec[1].to_string();
+                } else {
+                    d8_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-dem" || vec[0].to_lowercase() == "--dem" {
+                if keyval {
+                    dem_file = vec[1].to_string();
+                } else {
+                    dem_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-esri_pntr" || vec[0].to_lowercase() == "--esri_pntr" || vec[0].to_lowercase() == "--esri_style" {
+                esri_style = true;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !streams_file.contains(&sep) {
+            streams_file = format!("{}{}", working_directory, streams_file);
+        }
+        if !d8_file.contains(&sep) {
+            d8_file = format!("{}{}", working_directory, d8_file);
+        }
+        let use_dem = !dem_file.trim().is_empty();
+        if use_dem && !dem_file.contains(&sep) {
+            dem_file = format!("{}{}", working_directory, dem_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose { println!("Reading streams data...") };
+        let streams = Raster::new(&streams_file, "r")?;
+        if verbose { println!("Reading pointer data...") };
+        let pntr = Raster::new(&d8_file, "r")?;
+        let pntr_nodata = pntr.configs.nodata;
+        let dem = if use_dem {
+            if verbose { println!("Reading DEM data...") };
+            let dem = Raster::new(&dem_file, "r")?;
+            if streams.configs.rows != dem.configs.rows || streams.configs.columns != dem.configs.columns {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                                    "The input files must have the same number of rows and columns and spatial extent."));
+            }
+            Some(dem)
+        } else {
+            None
+        };
+
+        let start = time::now();
+
+        let rows = streams.configs.rows as isize;
+        let columns = streams.configs.columns as isize;
+        let nodata = streams.configs.nodata;
+
+        // make sure the input files have the same size
+        if streams.configs.rows != pntr.configs.rows || streams.configs.columns != pntr.configs.columns {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "The input files must have the same number of rows and columns and spatial extent."));
+        }
+
+        let dx = [ 1, 1, 1, 0, -1, -1, -1, 0 ];
+        let dy = [ -1, 0, 1, 1, 1, 0, -1, -1 ];
+        let mut inflowing_vals = [ 16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64, 8f64 ];
+        if esri_style {
+            inflowing_vals = [ 8f64, 16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64 ];
+        }
+
+        let mut pntr_matches: [usize; 129] = [999usize; 129];
+        if !esri_style {
+            // This maps Whitebox-style D8 pointer values
+            // onto the cell offsets in dx and dy.
+            pntr_matches[1] = 0usize;
+            pntr_matches[2] = 1usize;
+            pntr_matches[4] = 2usize;
+            pntr_matches[8] = 3usize;
+            pntr_matches[16] = 4usize;
+            pntr_matches[32] = 5usize;
+            pntr_matches[64] = 6usize;
+            pntr_matches[128] = 7usize;
+        } else {
+            // This maps Esri-style D8 pointer values
+            // onto the cell offsets in dx and dy.
+            pntr_matches[1] = 1usize;
+            pntr_matches[2] = 2usize;
+            pntr_matches[4] = 3usize;
+            pntr_matches[8] = 4usize;
+            pntr_matches[16] = 5usize;
+            pntr_matches[32] = 6usize;
+            pntr_matches[64] = 7usize;
+            pntr_matches[128] = 0usize;
+        }
+
+        let is_stream = |row: isize, col: isize| -> bool {
+            let z = streams[(row, col)];
+            z > 0f64 && z != nodata
+        };
+
+        // Links begin at channel heads and at confluences, i.e. at any stream
+        // cell that does not have exactly one inflowing stream cell.
+        let mut link_id: Array2D<i32> = Array2D::new(rows, columns, 0, 0)?;
+        let mut link_starts = vec![];
+        let mut count: usize;
+        for row in 0..rows {
+            for col in 0..columns {
+                if is_stream(row, col) {
+                    count = 0;
+                    for i in 0..8 {
+                        if is_stream(row + dy[i], col + dx[i]) &&
+                            pntr[(row + dy[i], col + dx[i])] == inflowing_vals[i] {
+                            count += 1;
+                        }
+                    }
+                    if count != 1 {
+                        link_starts.push((row, col));
+                        link_id[(row, col)] = link_starts.len() as i32;
+                    }
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Finding links: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // trace each link downstream
+        let num_links = link_starts.len();
+        let mut vertices: Vec<Vec<Point2D>> = Vec::with_capacity(num_links);
+        let mut end_cells = Vec::with_capacity(num_links);
+        let mut downstream_link: Vec<Option<usize>> = vec![None; num_links];
+        let mut upstream_links: Vec<Vec<usize>> = vec![vec![]; num_links];
+        let (mut row, mut col): (isize, isize);
+        let (mut row_n, mut col_n): (isize, isize);
+        let mut dir: usize;
+        for link in 0..num_links {
+            row = link_starts[link].0;
+            col = link_starts[link].1;
+            let mut points = vec![Point2D::new(streams.get_x_from_column(col), streams.get_y_from_row(row))];
+            loop {
+                dir = pntr[(row, col)] as usize;
+                if pntr[(row, col)] == pntr_nodata || dir == 0 {
+                    break;
+                }
+                if dir > 128 || pntr_matches[dir] == 999 {
+                    return Err(Error::new(ErrorKind::InvalidInput,
+                        "An unexpected value has been identified in the pointer image. This tool requires a pointer grid that has been created using either the D8 or Rho8 tools."));
+                }
+                row_n = row + dy[pntr_matches[dir]];
+                col_n = col + dx[pntr_matches[dir]];
+                if !is_stream(row_n, col_n) {
+                    if points.len() == 1 {
+                        // a single-cell outlet link; extend it to the edge of the cell
+                        points.push(Point2D::new((streams.get_x_from_column(col) + streams.get_x_from_column(col_n)) / 2f64,
+                                                 (streams.get_y_from_row(row) + streams.get_y_from_row(row_n)) / 2f64));
+                    }
+                    break;
+                }
+                row = row_n;
+                col = col_n;
+                points.push(Point2D::new(streams.get_x_from_column(col), streams.get_y_from_row(row)));
+                if link_id[(row, col)] > 0 {
+                    // the link ends at the first cell of its downstream link
+                    let ds = link_id[(row, col)] as usize - 1;
+                    downstream_link[link] = Some(ds);
+                    upstream_links[ds].push(link);
+                    break;
+                }
+                if points.len() > (rows * columns) as usize {
+                    return Err(Error::new(ErrorKind::InvalidInput,
+                        "A cycle has been detected in the pointer image."));
+                }
+            }
+            vertices.push(points);
+            end_cells.push((row, col));
+
+            if verbose {
+                progress = (100.0_f64 * link as f64 / (num_links - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Tracing links: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut length = vec![0f64; num_links];
+        for link in 0..num_links {
+            for i in 1..vertices[link].len() {
+                length[link] += vertices[link][i].distance(&vertices[link][i - 1]);
+            }
+        }
+
+        // Visit the links from the headwaters downstream, such that each link
+        // is visited after all of the links that drain into it.
+        let mut num_unvisited_upstream: Vec<usize> = upstream_links.iter().map(|u| u.len()).collect();
+        let mut queue: VecDeque<usize> = (0..num_links).filter(|l| upstream_links[*l].is_empty()).collect();
+        let mut visit_order = Vec::with_capacity(num_links);
+        let mut strahler = vec![1usize; num_links];
+        let mut shreve = vec![1usize; num_links];
+        let mut upstream_length = length.clone(); // length of the longest flowpath to the link's end
+        while let Some(link) = queue.pop_front() {
+            visit_order.push(link);
+            if !upstream_links[link].is_empty() {
+                let mut max_order = 0usize;
+                let mut num_with_max = 0usize;
+                let mut magnitude = 0usize;
+                let mut longest = 0f64;
+                for &us in &upstream_links[link] {
+                    if strahler[us] > max_order {
+                        max_order = strahler[us];
+                        num_with_max = 1;
+                    } else if strahler[us] == max_order {
+                        num_with_max += 1;
+                    }
+                    magnitude += shreve[us];
+                    if upstream_length[us] > longest {
+                        longest = upstream_length[us];
+                    }
+                }
+                strahler[link] = if num_with_max > 1 { max_order + 1 } else { max_order };
+                shreve[link] = magnitude;
+                upstream_length[link] += longest;
+            }
+            if let Some(ds) = downstream_link[link] {
+                num_unvisited_upstream[ds] -= 1;
+                if num_unvisited_upstream[ds] == 0 {
+                    queue.push_back(ds);
+                }
+            }
+        }
+        if visit_order.len() < num_links {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "A cycle has been detected in the stream network."));
+        }
+
+        // Horton order is assigned from the outlets upstream; at each confluence
+        // the main stem follows the tributary of highest Strahler order, or the
+        // longest one where there is a tie.
+        let mut horton = strahler.clone();
+        for &link in visit_order.iter().rev() {
+            let mut main_stem = None;
+            for &us in &upstream_links[link] {
+                main_stem = match main_stem {
+                    None => Some(us),
+                    Some(m) => {
+                        if strahler[us] > strahler[m] ||
+                            (strahler[us] == strahler[m] && upstream_length[us] > upstream_length[m]) {
+                            Some(us)
+                        } else {
+                            Some(m)
+                        }
+                    }
+                };
+            }
+            if let Some(m) = main_stem {
+                horton[m] = horton[link];
+            }
+        }
+
+        // output the links
+        let mut output = Shapefile::new(&output_file, "w")?;
+        output.header.shape_type = ShapeType::PolyLine;
+        if streams.configs.coordinate_ref_system_wkt.to_lowercase() != "not specified" {
+            output.projection = streams.configs.coordinate_ref_system_wkt.clone();
+        }
+
+        let us_link_strings: Vec<String> = upstream_links.iter()
+            .map(|u| u.iter().map(|l| (l + 1).to_string()).collect::<Vec<String>>().join(","))
+            .collect();
+        let mut us_field_length = 10usize;
+        for s in &us_link_strings {
+            if s.len() > us_field_length {
+                us_field_length = s.len();
+            }
+        }
+        if us_field_length > 254 {
+            us_field_length = 254;
+        }
+
+        output.attributes.add_field(&AttributeField::new("LINK_ID", FieldDataType::Int, 10u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("DS_LINK", FieldDataType::Int, 10u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("US_LINKS", FieldDataType::Text, us_field_length as u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("STRAHLER", FieldDataType::Int, 6u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("SHREVE", FieldDataType::Int, 10u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("HORTON", FieldDataType::Int, 6u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("LENGTH", FieldDataType::Real, 18u8, 4u8));
+        if use_dem {
+            output.attributes.add_field(&AttributeField::new("SLOPE", FieldDataType::Real, 12u8, 6u8));
+        }
+
+        for link in 0..num_links {
+            if vertices[link].len() > 1 {
+                let mut geom = ShapefileGeometry::new(ShapeType::PolyLine);
+                geom.add_part(&vertices[link]);
+                output.add_record(geom);
+            } else {
+                // a single cell without a flow direction cannot be represented as a line
+                output.add_record(ShapefileGeometry::new(ShapeType::Null));
+            }
+
+            let ds = match downstream_link[link] {
+                Some(d) => d as i32 + 1,
+                None => 0i32,
+            };
+            let mut rec = vec![FieldData::Int(link as i32 + 1),
+                               FieldData::Int(ds),
+                               FieldData::Text(us_link_strings[link].clone()),
+                               FieldData::Int(strahler[link] as i32),
+                               FieldData::Int(shreve[link] as i32),
+                               FieldData::Int(horton[link] as i32),
+                               FieldData::Real(length[link])];
+            if let Some(ref dem) = dem {
+                let z1 = dem[link_starts[link]];
+                let z2 = dem[end_cells[link]];
+                if z1 != dem.configs.nodata && z2 != dem.configs.nodata && length[link] > 0f64 {
+                    rec.push(FieldData::Real((z1 - z2) / length[link] * 100f64));
+                } else {
+                    rec.push(FieldData::Null);
+                }
+            }
+            output.attributes.add_record(rec, false);
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,445 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+extern crate time;
+
+use std::env;
+use std::path;
+use std::f64;
+use std::collections::HashMap;
+use raster::*;
+use vector::*;
+use algorithms::{chaikin_smoothing, douglas_peucker};
+use structures::Point2D;
+use std::io::{Error, ErrorKind};
+use tools::*;
+
+/// Creates a vector contour coverage from a raster surface model (DEM). Contours
+/// are traced using the marching squares method on the grid of cell centres,
+/// at elevations of `--base` plus a multiple of `--interval`; ambiguous saddle
+/// cells are resolved using the average of the four corner values. Cells
+/// adjacent to NoData values are not contoured, so that contours end at the
+/// edges of data gaps. Contour segments are joined into continuous polylines,
+/// which may optionally be simplified (`--simplify`, a Douglas-Peucker
+/// tolerance in map units) and smoothed (`--smooth`, the number of Chaikin
+/// iterations). Each line is attributed with its elevation (ELEV) and a flag
+/// (INDEX) that is 1 for every `--index`th contour counted from the base.
+pub struct ContourLines {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ContourLines {
+    pub fn new() -> ContourLines { // public constructor
+        let name = "ContourLines".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Derives a vector contour coverage from a raster surface.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter{
+            name: "Input Raster DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster surface file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Vector Lines File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector contour file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector),
+            default_value: None,
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Contour Interval".to_owned(),
+            flags: vec!["--interval".to_owned()],
+            description: "Contour interval.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("10.0".to_owned()),
+            optional: false
+        });
+
+        parameters.push(ToolParameter{
+            name: "Base Contour".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Base contour height.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Index Contour Frequency".to_owned(),
+            flags: vec!["--index".to_owned()],
+            description: "Every nth contour, counted from the base contour, is flagged as an index contour.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("5".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Simplification Tolerance".to_owned(),
+            flags: vec!["--simplify".to_owned()],
+            description: "Douglas-Peucker simplification tolerance, in map units (0 for no simplification).".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Smoothing Iterations".to_owned(),
+            flags: vec!["--smooth".to_owned()],
+            description: "Number of Chaikin smoothing iterations (0 for no smoothing).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("0".to_owned()),
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e.replace(&p, "").replace(".exe", "").replace(".", "").replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=DEM.tif -o=contours.shp --interval=10.0 --base=0.0 --index=5 --smooth=2", short_exe, name).replace("*", &sep);
+
+        ContourLines {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage
+        }
+    }
+}
+
+impl WhiteboxTool for ContourLines {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut interval = 10f64;
+        let mut base_contour = 0f64;
+        let mut index_frequency = 5i64;
+        let mut tolerance = 0f64;
+        let mut smooth_iterations = 0usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "Tool run with no paramters."));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i+1].to_string();
+                }
+            } else if vec[0].to_lowercase() == "-interval" || vec[0].to_lowercase() == "--interval" {
+                if keyval {
+                    interval = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    interval = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-base" || vec[0].to_lowercase() == "--base" {
+                if keyval {
+                    base_contour = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    base_contour = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-index" || vec[0].to_lowercase() == "--index" {
+                if keyval {
+                    index_frequency = vec[1].to_string().parse::<f64>().unwrap() as i64;
+                } else {
+                    index_frequency = args[i+1].to_string().parse::<f64>().unwrap() as i64;
+                }
+            } else if vec[0].to_lowercase() == "-simplify" || vec[0].to_lowercase() == "--simplify" {
+                if keyval {
+                    tolerance = vec[1].to_string().parse::<f64>().unwrap();
+                } else {
+                    tolerance = args[i+1].to_string().parse::<f64>().unwrap();
+                }
+            } else if vec[0].to_lowercase() == "-smooth" || vec[0].to_lowercase() == "--smooth" {
+                if keyval {
+                    smooth_iterations = vec[1].to_string().parse::<usize>().unwrap();
+                } else {
+                    smooth_iterations = args[i+1].to_string().parse::<usize>().unwrap();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if interval <= 0f64 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "The contour interval must be greater than zero."));
+        }
+        if index_frequency < 1 {
+            index_frequency = 1;
+        }
+
+        if verbose { println!("Reading data...") };
+
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = time::now();
+
+        let nodata = input.configs.nodata;
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+
+        // The corners of each marching square are the centres of the cells
+        // (row, col), (row, col + 1), (row + 1, col + 1) and (row + 1, col).
+        // Contour crossings are identified by the grid edge on which they fall,
+        // keyed as 2 * cell index for the edge between a cell and its eastern
+        // neighbour, and 2 * cell index + 1 for the edge to its southern neighbour.
+        let corner_dr = [0isize, 0, 1, 1];
+        let corner_dc = [0isize, 1, 1, 0];
+        let edge_keys = |row: isize, col: isize| -> [i64; 4] {
+            let top = (row * columns + col) as i64;
+            let bottom = ((row + 1) * columns + col) as i64;
+            // top, right, bottom and left edges of the square
+            [2 * top, 2 * (top + 1) + 1, 2 * bottom, 2 * top + 1]
+        };
+        let edge_corners = [(0usize, 1usize), (1, 2), (3, 2), (0, 3)];
+
+        let mut segments: HashMap<i64, Vec<(i64, i64)>> = HashMap::new();
+        let mut z = [0f64; 4];
+        for row in 0..rows - 1 {
+            for col in 0..columns - 1 {
+                let mut has_nodata = false;
+                for i in 0..4 {
+                    z[i] = input[(row + corner_dr[i], col + corner_dc[i])];
+                    if z[i] == nodata {
+                        has_nodata = true;
+                    }
+                }
+                if has_nodata {
+                    continue;
+                }
+                let mut z_min = z[0];
+                let mut z_max = z[0];
+                for i in 1..4 {
+                    if z[i] < z_min { z_min = z[i]; }
+                    if z[i] > z_max { z_max = z[i]; }
+                }
+                let keys = edge_keys(row, col);
+                let first_level = ((z_min - base_contour) / interval).ceil() as i64;
+                let last_level = ((z_max - base_contour) / interval).floor() as i64;
+                for level in first_level..last_level + 1 {
+                    let level_z = base_contour + level as f64 * interval;
+                    let above = [z[0] >= level_z, z[1] >= level_z, z[2] >= level_z, z[3] >= level_z];
+                    let mut crossed = vec![];
+                    for e in 0..4 {
+                        if above[edge_corners[e].0] != above[edge_corners[e].1] {
+                            crossed.push(e);
+                        }
+                    }
+                    let level_segments = segments.entry(level).or_insert(vec![]);
+                    if crossed.len() == 2 {
+                        level_segments.push((keys[crossed[0]], keys[crossed[1]]));
+                    } else if crossed.len() == 4 {
+                        // a saddle; decide whether the diagonal through the top-left corner is connected
+                        let centre_above = (z[0] + z[1] + z[2] + z[3]) / 4f64 >= level_z;
+                        if centre_above == above[0] {
+                            level_segments.push((keys[0], keys[1]));
+                            level_segments.push((keys[2], keys[3]));
+                        } else {
+                            level_segments.push((keys[0], keys[3]));
+                            level_segments.push((keys[1], keys[2]));
+                        }
+                    }
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Tracing contours: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // the location at which a contour crosses a grid edge
+        let crossing_point = |key: i64, level_z: f64| -> Point2D {
+            let cell = key / 2;
+            let row = cell as isize / columns;
+            let col = cell as isize % columns;
+            let (row2, col2) = if key % 2 == 0 { (row, col + 1) } else { (row + 1, col) };
+            let (z1, z2) = (input[(row, col)], input[(row2, col2)]);
+            let t = (level_z - z1) / (z2 - z1);
+            let (x1, y1) = (input.get_x_from_column(col), input.get_y_from_row(row));
+            let (x2, y2) = (input.get_x_from_column(col2), input.get_y_from_row(row2));
+            Point2D::new(x1 + t * (x2 - x1), y1 + t * (y2 - y1))
+        };
+
+        let mut output = Shapefile::new(&output_file, "w")?;
+        output.header.shape_type = ShapeType::PolyLine;
+        if input.configs.coordinate_ref_system_wkt.to_lowercase() != "not specified" {
+            output.projection = input.configs.coordinate_ref_system_wkt.clone();
+        }
+        output.attributes.add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("ELEV", FieldDataType::Real, 14u8, 4u8));
+        output.attributes.add_field(&AttributeField::new("INDEX", FieldDataType::Int, 1u8, 0u8));
+
+        // join the segments of each level into continuous lines
+        let mut levels: Vec<i64> = segments.keys().cloned().collect();
+        levels.sort();
+        let num_levels = levels.len();
+        let mut fid = 0i32;
+        for (n, level) in levels.into_iter().enumerate() {
+            let level_z = base_contour + level as f64 * interval;
+            let level_segments = &segments[&level];
+            let mut segments_at_edge: HashMap<i64, Vec<usize>> = HashMap::new();
+            for (s, seg) in level_segments.iter().enumerate() {
+                segments_at_edge.entry(seg.0).or_insert(vec![]).push(s);
+                segments_at_edge.entry(seg.1).or_insert(vec![]).push(s);
+            }
+            let mut used = vec![false; level_segments.len()];
+            // open lines must be started from one of their ends, so do these first
+            for pass in 0..2 {
+                for s in 0..level_segments.len() {
+                    if used[s] {
+                        continue;
+                    }
+                    let (a, b) = level_segments[s];
+                    let (start_key, mut key) = if segments_at_edge[&a].len() == 1 {
+                        (a, b)
+                    } else if segments_at_edge[&b].len() == 1 {
+                        (b, a)
+                    } else if pass == 1 {
+                        (a, b)
+                    } else {
+                        continue;
+                    };
+                    used[s] = true;
+                    let mut keys = vec![start_key, key];
+                    loop {
+                        let mut next = None;
+                        for &s2 in &segments_at_edge[&key] {
+                            if !used[s2] {
+                                next = Some(s2);
+                                break;
+                            }
+                        }
+                        match next {
+                            Some(s2) => {
+                                used[s2] = true;
+                                let (a2, b2) = level_segments[s2];
+                                key = if a2 == key { b2 } else { a2 };
+                                keys.push(key);
+                            }
+                            None => break,
+                        }
+                    }
+
+                    let mut points: Vec<Point2D> = keys.iter().map(|k| crossing_point(*k, level_z)).collect();
+                    if tolerance > 0f64 {
+                        points = douglas_peucker(&points, tolerance);
+                    }
+                    if smooth_iterations > 0 {
+                        points = chaikin_smoothing(&points, smooth_iterations);
+                    }
+                    let mut geom = ShapefileGeometry::new(ShapeType::PolyLine);
+                    geom.add_part(&points);
+                    output.add_record(geom);
+
+                    fid += 1;
+                    let is_index = if level % index_frequency == 0 { 1i32 } else { 0i32 };
+                    output.attributes.add_record(vec![FieldData::Int(fid),
+                                                      FieldData::Real(level_z),
+                                                      FieldData::Int(is_index)],
+                                                 false);
+                }
+            }
+
+            if verbose {
+                progress = (100.0_f64 * n as f64 / (num_levels - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Joining contour segments: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let end = time::now();
+        let elapsed_time = end - start;
+
+        if verbose { println!("Saving data...") };
+        let _ = match output.write() {
+            Ok(_) => if verbose { println!("Output file written") },
+            Err(e) => return Err(e),
+        };
+
+        println!("{}", &format!("Elapsed Time (excluding I/O): {}", elapsed_time).replace("PT", ""));
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 // private sub-module defined in other files
 mod aspect;
+mod contour_lines;
 mod feature_preserving_denoise;
 mod dev_from_mean_elev; 
 mod diff_from_mean_elev;
@@ -39,6 +40,7 @@ mod wetness_index;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::aspect::Aspect;
+pub use self::contour_lines::ContourLines;
 pub use self::feature_preserving_denoise::FeaturePreservingDenoise;
 pub use self::dev_from_mean_elev::DevFromMeanElev;
 pub use self::diff_from_mean_elev::DiffFromMeanElev;
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// The data types supported by dBase attribute tables.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FieldDataType {
+    Int,
+    Real,
+    Text,
+    Date,
+    Bool,
+}
+
+impl FieldDataType {
+    /// Returns the single-character dBase field type code.
+    pub fn to_char(&self) -> char {
+        match *self {
+            FieldDataType::Int | FieldDataType::Real => 'N',
+            FieldDataType::Text => 'C',
+            FieldDataType::Date => 'D',
+            FieldDataType::Bool => 'L',
+        }
+    }
+}
+
+/// A single value within an attribute table. Dates are stored as 'YYYYMMDD' text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldData {
+    Int(i32),
+    Real(f64),
+    Text(String),
+    Date(String),
+    Bool(bool),
+    Null,
+}
+
+impl FieldData {
+    /// Returns the value as a float, if it is numeric or boolean.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            FieldData::Int(v) => Some(v as f64),
+            FieldData::Real(v) => Some(v),
+            FieldData::Bool(v) => Some(if v { 1f64 } else { 0f64 }),
+            FieldData::Text(ref s) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        *self == FieldData::Null
+    }
+}
+
+impl fmt::Display for FieldData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FieldData::Int(v) => write!(f, "{}", v),
+            FieldData::Real(v) => write!(f, "{}", v),
+            FieldData::Text(ref s) | FieldData::Date(ref s) => write!(f, "{}", s),
+            FieldData::Bool(v) => write!(f, "{}", v),
+            FieldData::Null => write!(f, "null"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeField {
+    pub name: String,
+    pub field_type: FieldDataType,
+    pub field_length: u8,
+    pub decimal_count: u8,
+}
+
+impl AttributeField {
+    /// Creates a new field. dBase limits field names to 10 characters; longer
+    /// names are truncated when the table is written.
+    pub fn new(name: &str,
+               field_type: FieldDataType,
+               field_length: u8,
+               decimal_count: u8)
+               -> AttributeField {
+        let (length, decimals) = match field_type {
+            FieldDataType::Date => (8u8, 0u8),
+            FieldDataType::Bool => (1u8, 0u8),
+            FieldDataType::Int => (field_length, 0u8),
+            _ => (field_length, decimal_count),
+        };
+        AttributeField {
+            name: name.to_string(),
+            field_type: field_type,
+            field_length: length,
+            decimal_count: decimals,
+        }
+    }
+}
+
+/// The attribute table (.dbf) associated with a vector file.
+#[derive(Default, Debug, Clone)]
+pub struct ShapefileAttributes {
+    pub fields: Vec<AttributeField>,
+    data: Vec<Vec<FieldData>>,
+    deleted: Vec<bool>,
+    field_map: HashMap<String, usize>,
+}
+
+impl ShapefileAttributes {
+    pub fn get_num_fields(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn get_num_records(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn add_field(&mut self, field: &AttributeField) {
+        self.field_map.insert(field.name.clone(), self.fields.len());
+        self.fields.push(field.clone());
+        for rec in &mut self.data {
+            rec.push(FieldData::Null);
+        }
+    }
+
+    pub fn get_field(&self, index: usize) -> &AttributeField {
+        &self.fields[index]
+    }
+
+    /// Returns the index of a named field, if it exists.
+    pub fn get_field_num(&self, name: &str) -> Option<usize> {
+        match self.field_map.get(name) {
+            Some(i) => Some(*i),
+            None => {
+                // dBase field names are case-insensitive.
+                let lc = name.to_lowercase();
+                self.fields.iter().position(|f| f.name.to_lowercase() == lc)
+            }
+        }
+    }
+
+    /// Returns the name if no field of that name exists, and otherwise a
+    /// modified name that is unique within the table and fits within the 10
+    /// characters allowed for dBase field names.
+    pub fn get_unique_field_name(&self, name: &str) -> String {
+        if self.get_field_num(name).is_none() {
+            return name.to_string();
+        }
+        let mut i = 1;
+        loop {
+            let suffix = format!("_{}", i);
+            let mut unique: String = name.chars().take(10 - suffix.len()).collect();
+            unique.push_str(&suffix);
+            if self.get_field_num(&unique).is_none() {
+                return unique;
+            }
+            i += 1;
+        }
+    }
+
+    pub fn is_field_numeric(&self, index: usize) -> bool {
+        match self.fields[index].field_type {
+            FieldDataType::Int | FieldDataType::Real => true,
+            _ => false,
+        }
+    }
+
+    /// Appends a record to the table. The record is padded with nulls or
+    /// truncated to the number of fields.
+    pub fn add_record(&mut self, record: Vec<FieldData>, deleted: bool) {
+        let mut record = record;
+        record.resize(self.fields.len(), FieldData::Null);
+        self.data.push(record);
+        self.deleted.push(deleted);
+    }
+
+    pub fn get_record(&self, index: usize) -> Vec<FieldData> {
+        self.data[index].clone()
+    }
+
+    pub fn is_deleted(&self, index: usize) -> bool {
+        self.deleted[index]
+    }
+
+    pub fn get_value(&self, record: usize, field_name: &str) -> FieldData {
+        match self.get_field_num(field_name) {
+            Some(i) => self.data[record][i].clone(),
+            None => FieldData::Null,
+        }
+    }
+
+    pub fn set_value(&mut self, record: usize, field_name: &str, value: FieldData) {
+        if let Some(i) = self.get_field_num(field_name) {
+            self.data[record][i] = value;
+        }
+    }
+}
+
+/// Forms a dBase field name from a prefix, e.g. the stem of a file name, and
+/// a suffix, e.g. "_MEAN", truncating the prefix as needed so that the name
+/// fits within the 10 characters allowed for field names. If the name is
+/// among those already `used`, e.g. by a file whose name begins with the same
+/// characters, the truncated prefix is numbered to make the name unique.
+pub fn get_prefixed_field_name(prefix: &str, suffix: &str, used: &[String]) -> String {
+    let form_name = |number: &str| -> String {
+        let suffix: String = suffix.chars().take(10 - number.len()).collect();
+        let mut name: String = prefix.chars().take(10 - number.len() - suffix.chars().count()).collect();
+        name.push_str(number);
+        name.push_str(&suffix);
+        name
+    };
+    let is_used = |name: &str| used.iter().any(|u| u.to_lowercase() == name.to_lowercase());
+    let mut name = form_name("");
+    let mut i = 1;
+    while is_used(&name) {
+        i += 1;
+        name = form_name(&i.to_string());
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_prefixed_field_name;
+
+    #[test]
+    fn test_prefixed_field_names() {
+        let used: Vec<String> = vec![];
+        assert_eq!(get_prefixed_field_name("dem", "_MEAN", &used), "dem_MEAN");
+        assert_eq!(get_prefixed_field_name("elevation", "_MEAN", &used), "eleva_MEAN");
+        assert_eq!(get_prefixed_field_name("elevation", "_MAJORITY", &used), "e_MAJORITY");
+        assert_eq!(get_prefixed_field_name("elevation_2017", "", &used), "elevation_");
+
+        // names are unique, ignoring case, among those already used
+        let used = vec!["eleva_MEAN".to_string(), "ELEV2_MEAN".to_string()];
+        assert_eq!(get_prefixed_field_name("elevation", "_MEAN", &used), "elev3_MEAN");
+        let used = vec!["elevation_".to_string()];
+        assert_eq!(get_prefixed_field_name("elevation_2018", "", &used), "elevation2");
+    }
+}
@@ -0,0 +1,217 @@
+extern crate time;
+
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind};
+use byteorder::{LittleEndian, WriteBytesExt};
+use io_utils::{ByteOrderReader, Endianness};
+use vector::*;
+
+/// Reads a dBase III attribute table (.dbf).
+pub fn read_dbf(file_name: &str) -> Result<ShapefileAttributes, Error> {
+    let mut f = File::open(file_name)?;
+    let mut buffer = vec![];
+    f.read_to_end(&mut buffer)?;
+    if buffer.len() < 32 {
+        return Err(Error::new(ErrorKind::InvalidData,
+                              format!("The attribute table {} is improperly formatted.",
+                                      file_name)));
+    }
+    let mut bor = ByteOrderReader::new(buffer, Endianness::LittleEndian);
+    bor.seek(4);
+    let num_records = bor.read_u32() as usize;
+    let header_length = bor.read_u16() as usize;
+    let _record_length = bor.read_u16() as usize;
+
+    let mut attributes = ShapefileAttributes::default();
+
+    // read the field descriptors
+    let mut offset = 32usize;
+    while offset + 32 <= header_length && bor.buffer[offset] != 0x0D {
+        let name = String::from_utf8_lossy(&bor.buffer[offset..offset + 11])
+            .trim_matches(char::from(0))
+            .trim()
+            .to_string();
+        let type_char = bor.buffer[offset + 11] as char;
+        let field_length = bor.buffer[offset + 16];
+        let decimal_count = bor.buffer[offset + 17];
+        let field_type = match type_char {
+            'N' | 'F' => {
+                if decimal_count == 0 {
+                    FieldDataType::Int
+                } else {
+                    FieldDataType::Real
+                }
+            }
+            'D' => FieldDataType::Date,
+            'L' => FieldDataType::Bool,
+            _ => FieldDataType::Text,
+        };
+        let mut field = AttributeField::new(&name, field_type, field_length, decimal_count);
+        // keep the stored width, even for fixed-width types
+        field.field_length = field_length;
+        attributes.add_field(&field);
+        offset += 32;
+    }
+
+    // read the records
+    offset = header_length;
+    for _ in 0..num_records {
+        if offset >= bor.buffer.len() || bor.buffer[offset] == 0x1A {
+            break;
+        }
+        let deleted = bor.buffer[offset] == 0x2A; // '*'
+        offset += 1;
+        let mut rec = Vec::with_capacity(attributes.get_num_fields());
+        for i in 0..attributes.get_num_fields() {
+            let fl = attributes.fields[i].field_length as usize;
+            let end = if offset + fl <= bor.buffer.len() { offset + fl } else { bor.buffer.len() };
+            let s = String::from_utf8_lossy(&bor.buffer[offset..end]).trim().to_string();
+            offset += fl;
+            rec.push(parse_field_value(&s, attributes.fields[i].field_type));
+        }
+        attributes.add_record(rec, deleted);
+    }
+
+    Ok(attributes)
+}
+
+fn parse_field_value(s: &str, field_type: FieldDataType) -> FieldData {
+    if s.is_empty() {
+        return FieldData::Null;
+    }
+    match field_type {
+        FieldDataType::Int => {
+            match s.parse::<i64>() {
+                Ok(v) => {
+                    if v >= i32::min_value() as i64 && v <= i32::max_value() as i64 {
+                        FieldData::Int(v as i32)
+                    } else {
+                        FieldData::Real(v as f64)
+                    }
+                }
+                Err(_) => {
+                    match s.parse::<f64>() {
+                        Ok(v) => FieldData::Real(v),
+                        Err(_) => FieldData::Null,
+                    }
+                }
+            }
+        }
+        FieldDataType::Real => {
+            match s.parse::<f64>() {
+                Ok(v) => FieldData::Real(v),
+                Err(_) => FieldData::Null,
+            }
+        }
+        FieldDataType::Bool => {
+            match s {
+                "T" | "t" | "Y" | "y" => FieldData::Bool(true),
+                "F" | "f" | "N" | "n" => FieldData::Bool(false),
+                _ => FieldData::Null,
+            }
+        }
+        FieldDataType::Date => FieldData::Date(s.to_string()),
+        FieldDataType::Text => FieldData::Text(s.to_string()),
+    }
+}
+
+/// Writes a dBase III attribute table (.dbf).
+pub fn write_dbf(file_name: &str, attributes: &ShapefileAttributes) -> Result<(), Error> {
+    let f = File::create(file_name)?;
+    let mut writer = BufWriter::new(f);
+
+    let num_fields = attributes.get_num_fields();
+    let num_records = attributes.get_num_records();
+    let header_length = 32 + 32 * num_fields + 1;
+    let mut record_length = 1usize; // deletion flag
+    for field in &attributes.fields {
+        record_length += field.field_length as usize;
+    }
+
+    let now = time::now();
+    writer.write_u8(0x03)?;
+    writer.write_u8((now.tm_year % 100) as u8)?;
+    writer.write_u8((now.tm_mon + 1) as u8)?;
+    writer.write_u8(now.tm_mday as u8)?;
+    writer.write_u32::<LittleEndian>(num_records as u32)?;
+    writer.write_u16::<LittleEndian>(header_length as u16)?;
+    writer.write_u16::<LittleEndian>(record_length as u16)?;
+    writer.write_all(&[0u8; 20])?;
+
+    for field in &attributes.fields {
+        let mut name_bytes = [0u8; 11];
+        for (i, b) in field.name.bytes().take(10).enumerate() {
+            name_bytes[i] = b;
+        }
+        writer.write_all(&name_bytes)?;
+        writer.write_u8(field.field_type.to_char() as u8)?;
+        writer.write_all(&[0u8; 4])?;
+        writer.write_u8(field.field_length)?;
+        writer.write_u8(field.decimal_count)?;
+        writer.write_all(&[0u8; 14])?;
+    }
+    writer.write_u8(0x0D)?;
+
+    for r in 0..num_records {
+        writer.write_u8(if attributes.is_deleted(r) { 0x2A } else { 0x20 })?;
+        let rec = attributes.get_record(r);
+        for i in 0..num_fields {
+            let field = &attributes.fields[i];
+            let s = format_field_value(&rec[i], field);
+            writer.write_all(s.as_bytes())?;
+        }
+    }
+    writer.write_u8(0x1A)?;
+
+    Ok(())
+}
+
+fn format_field_value(value: &FieldData, field: &AttributeField) -> String {
+    let width = field.field_length as usize;
+    let s = match *value {
+        FieldData::Int(v) => format!("{:>width$}", v, width = width),
+        FieldData::Real(v) => {
+            if v.is_finite() {
+                let mut dec = field.decimal_count as usize;
+                let mut s = format!("{:>width$.prec$}", v, width = width, prec = dec);
+                // give up precision rather than overflowing the field
+                while s.len() > width && dec > 0 {
+                    dec -= 1;
+                    s = format!("{:>width$.prec$}", v, width = width, prec = dec);
+                }
+                if s.len() > width {
+                    s = "*".repeat(width);
+                }
+                s
+            } else {
+                " ".repeat(width)
+            }
+        }
+        FieldData::Text(ref t) => {
+            let mut t = t.clone();
+            while t.len() > width {
+                t.pop();
+            }
+            format!("{:<width$}", t, width = width)
+        }
+        FieldData::Date(ref t) => format!("{:<width$}", t, width = width),
+        FieldData::Bool(v) => format!("{:<width$}", if v { "T" } else { "F" }, width = width),
+        FieldData::Null => {
+            if field.field_type == FieldDataType::Bool {
+                format!("{:<width$}", "?", width = width)
+            } else {
+                " ".repeat(width)
+            }
+        }
+    };
+    if s.len() > width {
+        // keep the table aligned no matter what
+        let mut t = s;
+        while t.len() > width {
+            t.pop();
+        }
+        return t;
+    }
+    s
+}
@@ -0,0 +1,997 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+
+use std::f64;
+use std::f64::consts::PI;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use algorithms::{polyline_length, signed_polygon_area};
+use structures::Point2D;
+use vector::{AttributeField, FieldData, ShapeType, ShapefileGeometry};
+
+/// An attribute expression, evaluated for each record of a vector file, e.g.
+///
+/// `POP / $area * 1000000 > 50 and upper(TYPE) == 'URBAN'`
+///
+/// Expressions are made up of:
+///
+/// - Literals: numbers, text in single or double quotes, `true`, `false` and `null`.
+/// - Fields, named directly (case-insensitively) or within square brackets,
+///   e.g. `[LAND USE]`, which is needed for names that are not simple identifiers.
+/// - Geometry properties: `$area`, `$perimeter`, `$length` (line length, or
+///   polygon perimeter), `$centroid_x` and `$centroid_y` (or `$x` and `$y`),
+///   `$num_points`, `$num_parts`, and `$fid`, the one-based record number.
+/// - Arithmetic operators `+ - * / % ^`, where `+` joins text.
+/// - Comparisons `== != < <= > >=` (also `=` and `<>`), which compare numbers
+///   numerically, text alphabetically, and text with numbers numerically if
+///   the text is a number.
+/// - Logical operators `and`, `or` and `not` (also `&& || !`).
+/// - Functions (see below).
+///
+/// Null values (missing attributes) propagate through arithmetic and
+/// functions, are equal only to null, and are otherwise treated as false.
+/// Division by zero gives null.
+///
+/// The functions are: `abs`, `sqrt`, `exp`, `ln`, `log10`, `floor`, `ceil`,
+/// `round(x[, digits])`, `min(...)`, `max(...)`, `pow(x, y)`, `sin`, `cos`,
+/// `tan`, `asin`, `acos`, `atan`, `atan2(y, x)`, `pi()`; the text functions
+/// `upper`, `lower`, `trim`, `len`, `substr(s, start[, length])` (with the
+/// first character at position 1), `replace(s, from, to)`, `concat(...)`,
+/// `contains(s, t)`, `starts_with(s, t)`, `ends_with(s, t)`, `str(x)` and
+/// `num(s)`; and `if(condition, a, b)`, `is_null(x)` and `coalesce(...)`.
+pub struct Expression {
+    root: Node,
+}
+
+/// The value of an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionValue {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Null,
+}
+
+impl ExpressionValue {
+    /// The truth of a value, which is false for null, zero and empty text.
+    pub fn is_true(&self) -> bool {
+        match *self {
+            ExpressionValue::Bool(b) => b,
+            ExpressionValue::Number(v) => v != 0f64 && !v.is_nan(),
+            ExpressionValue::Text(ref s) => !s.is_empty(),
+            ExpressionValue::Null => false,
+        }
+    }
+
+    fn from_field_data(value: &FieldData) -> ExpressionValue {
+        match *value {
+            FieldData::Int(v) => ExpressionValue::Number(v as f64),
+            FieldData::Real(v) => ExpressionValue::Number(v),
+            FieldData::Text(ref s) | FieldData::Date(ref s) => ExpressionValue::Text(s.clone()),
+            FieldData::Bool(b) => ExpressionValue::Bool(b),
+            FieldData::Null => ExpressionValue::Null,
+        }
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match *self {
+            ExpressionValue::Number(v) => Some(v),
+            ExpressionValue::Bool(b) => Some(if b { 1f64 } else { 0f64 }),
+            ExpressionValue::Text(ref s) => s.trim().parse::<f64>().ok(),
+            ExpressionValue::Null => None,
+        }
+    }
+}
+
+impl fmt::Display for ExpressionValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ExpressionValue::Number(v) => write!(f, "{}", v),
+            ExpressionValue::Text(ref s) => write!(f, "{}", s),
+            ExpressionValue::Bool(b) => write!(f, "{}", b),
+            ExpressionValue::Null => write!(f, "null"),
+        }
+    }
+}
+
+impl Expression {
+    /// Parses an expression, resolving its field names against the fields of
+    /// an attribute table.
+    pub fn new(expression: &str, fields: &[AttributeField]) -> Result<Expression, Error> {
+        let mut parser = Parser {
+            tokens: tokenize(expression)?,
+            pos: 0,
+            fields: fields,
+        };
+        let root = parser.parse_or()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(parse_error(format!("unexpected '{}'", parser.tokens[parser.pos])));
+        }
+        Ok(Expression { root: root })
+    }
+
+    /// Evaluates the expression for a record, given its attributes, geometry
+    /// and (zero-based) record number.
+    pub fn evaluate(&self,
+                    record: &[FieldData],
+                    geometry: &ShapefileGeometry,
+                    record_num: usize)
+                    -> Result<ExpressionValue, Error> {
+        let context = Context {
+            record: record,
+            geometry: geometry,
+            record_num: record_num,
+        };
+        evaluate(&self.root, &context)
+    }
+}
+
+fn parse_error(message: String) -> Error {
+    Error::new(ErrorKind::InvalidInput, format!("Invalid expression: {}.", message))
+}
+
+fn evaluation_error(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("Error evaluating expression: {}.", message))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Identifier(String),
+    Field(String),
+    Property(String),
+    Operator(&'static str),
+    Open,
+    Close,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Token::Number(v) => write!(f, "{}", v),
+            Token::Text(ref s) => write!(f, "'{}'", s),
+            Token::Identifier(ref s) => write!(f, "{}", s),
+            Token::Field(ref s) => write!(f, "[{}]", s),
+            Token::Property(ref s) => write!(f, "${}", s),
+            Token::Operator(s) => write!(f, "{}", s),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+            Token::Comma => write!(f, ","),
+        }
+    }
+}
+
+const OPERATORS: [&'static str; 18] = ["==", "!=", "<>", "<=", ">=", "&&", "||", "=", "<", ">", "+", "-",
+                                       "*", "/", "%", "^", "!", "&"];
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, Error> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::Open);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::Close);
+            i += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            i += 1;
+        } else if c.is_digit(10) || (c == '.' && i + 1 < chars.len() && chars[i + 1].is_digit(10)) {
+            let start = i;
+            while i < chars.len() &&
+                  (chars[i].is_digit(10) || chars[i] == '.' ||
+                   ((chars[i] == 'e' || chars[i] == 'E') && i + 1 < chars.len() &&
+                    (chars[i + 1].is_digit(10) || chars[i + 1] == '-' || chars[i + 1] == '+')) ||
+                   ((chars[i] == '-' || chars[i] == '+') && (chars[i - 1] == 'e' || chars[i - 1] == 'E'))) {
+                i += 1;
+            }
+            let s: String = chars[start..i].iter().collect();
+            match s.parse::<f64>() {
+                Ok(v) => tokens.push(Token::Number(v)),
+                Err(_) => return Err(parse_error(format!("bad number '{}'", s))),
+            }
+        } else if c == '\'' || c == '"' {
+            // a doubled quote within the text stands for the quote itself
+            let mut s = String::new();
+            i += 1;
+            loop {
+                if i >= chars.len() {
+                    return Err(parse_error("unterminated text".to_string()));
+                }
+                if chars[i] == c {
+                    if i + 1 < chars.len() && chars[i + 1] == c {
+                        s.push(c);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    break;
+                }
+                s.push(chars[i]);
+                i += 1;
+            }
+            tokens.push(Token::Text(s));
+        } else if c == '[' {
+            let start = i + 1;
+            while i < chars.len() && chars[i] != ']' {
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(parse_error("unterminated field name".to_string()));
+            }
+            tokens.push(Token::Field(chars[start..i].iter().collect()));
+            i += 1;
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if c == '$' {
+                tokens.push(Token::Property(word[1..].to_lowercase()));
+            } else {
+                tokens.push(Token::Identifier(word));
+            }
+        } else {
+            let rest: String = chars[i..].iter().take(2).collect();
+            match OPERATORS.iter().find(|op| rest.starts_with(*op)) {
+                Some(op) if *op != "&" => {
+                    tokens.push(Token::Operator(op));
+                    i += op.len();
+                }
+                _ => return Err(parse_error(format!("unexpected character '{}'", c))),
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Power,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GeometryProperty {
+    Area,
+    Perimeter,
+    Length,
+    CentroidX,
+    CentroidY,
+    NumPoints,
+    NumParts,
+    Fid,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Literal(ExpressionValue),
+    Field(usize),
+    Property(GeometryProperty),
+    Negate(Box<Node>),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+    Call(String, Vec<Node>),
+}
+
+/// A recursive-descent parser, from the lowest precedence (or) to the
+/// highest (literals, fields and bracketed expressions).
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    fields: &'a [AttributeField],
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(&Token::Identifier(ref w)) => w.to_lowercase() == keyword,
+            _ => false,
+        }
+    }
+
+    fn is_operator(&self, operators: &[&str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(&Token::Operator(op)) if operators.contains(&op) => Some(op),
+            _ => None,
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Node, Error> {
+        let mut left = self.parse_and()?;
+        while self.is_keyword("or") || self.is_operator(&["||"]).is_some() {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = Node::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Node, Error> {
+        let mut left = self.parse_not()?;
+        while self.is_keyword("and") || self.is_operator(&["&&"]).is_some() {
+            self.pos += 1;
+            let right = self.parse_not()?;
+            left = Node::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Node, Error> {
+        if self.is_keyword("not") || self.is_operator(&["!"]).is_some() {
+            self.pos += 1;
+            let operand = self.parse_not()?;
+            return Ok(Node::Not(Box::new(operand)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Node, Error> {
+        let left = self.parse_additive()?;
+        if let Some(op) = self.is_operator(&["==", "=", "!=", "<>", "<", "<=", ">", ">="]) {
+            self.pos += 1;
+            let right = self.parse_additive()?;
+            let op = match op {
+                "==" | "=" => BinaryOp::Equal,
+                "!=" | "<>" => BinaryOp::NotEqual,
+                "<" => BinaryOp::Less,
+                "<=" => BinaryOp::LessEqual,
+                ">" => BinaryOp::Greater,
+                _ => BinaryOp::GreaterEqual,
+            };
+            return Ok(Node::Binary(op, Box::new(left), Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn parse_additive(&mut self) -> Result<Node, Error> {
+        let mut left = self.parse_multiplicative()?;
+        while let Some(op) = self.is_operator(&["+", "-"]) {
+            self.pos += 1;
+            let right = self.parse_multiplicative()?;
+            let op = if op == "+" { BinaryOp::Add } else { BinaryOp::Subtract };
+            left = Node::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Node, Error> {
+        let mut left = self.parse_unary()?;
+        while let Some(op) = self.is_operator(&["*", "/", "%"]) {
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            let op = match op {
+                "*" => BinaryOp::Multiply,
+                "/" => BinaryOp::Divide,
+                _ => BinaryOp::Remainder,
+            };
+            left = Node::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Node, Error> {
+        if self.is_operator(&["-"]).is_some() {
+            self.pos += 1;
+            let operand = self.parse_unary()?;
+            return Ok(Node::Negate(Box::new(operand)));
+        }
+        if self.is_operator(&["+"]).is_some() {
+            self.pos += 1;
+            return self.parse_unary();
+        }
+        self.parse_power()
+    }
+
+    fn parse_power(&mut self) -> Result<Node, Error> {
+        let base = self.parse_primary()?;
+        if self.is_operator(&["^"]).is_some() {
+            self.pos += 1;
+            // right-associative, and binding more tightly than a leading minus
+            let exponent = self.parse_unary()?;
+            return Ok(Node::Binary(BinaryOp::Power, Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    fn parse_primary(&mut self) -> Result<Node, Error> {
+        match self.next() {
+            Some(Token::Number(v)) => Ok(Node::Literal(ExpressionValue::Number(v))),
+            Some(Token::Text(s)) => Ok(Node::Literal(ExpressionValue::Text(s))),
+            Some(Token::Open) => {
+                let node = self.parse_or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(node),
+                    _ => Err(parse_error("missing ')'".to_string())),
+                }
+            }
+            Some(Token::Field(name)) => self.field(&name),
+            Some(Token::Property(name)) => {
+                let property = match name.as_ref() {
+                    "area" => GeometryProperty::Area,
+                    "perimeter" => GeometryProperty::Perimeter,
+                    "length" => GeometryProperty::Length,
+                    "centroid_x" | "x" => GeometryProperty::CentroidX,
+                    "centroid_y" | "y" => GeometryProperty::CentroidY,
+                    "num_points" => GeometryProperty::NumPoints,
+                    "num_parts" => GeometryProperty::NumParts,
+                    "fid" => GeometryProperty::Fid,
+                    _ => return Err(parse_error(format!("unknown geometry property '${}'", name))),
+                };
+                Ok(Node::Property(property))
+            }
+            Some(Token::Identifier(word)) => {
+                if self.peek() == Some(&Token::Open) {
+                    self.pos += 1;
+                    let mut args = vec![];
+                    if self.peek() == Some(&Token::Close) {
+                        self.pos += 1;
+                    } else {
+                        loop {
+                            args.push(self.parse_or()?);
+                            match self.next() {
+                                Some(Token::Comma) => {}
+                                Some(Token::Close) => break,
+                                _ => return Err(parse_error(format!("missing ')' in call to '{}'", word))),
+                            }
+                        }
+                    }
+                    let name = word.to_lowercase();
+                    check_arity(&name, args.len())?;
+                    return Ok(Node::Call(name, args));
+                }
+                match word.to_lowercase().as_ref() {
+                    "true" => Ok(Node::Literal(ExpressionValue::Bool(true))),
+                    "false" => Ok(Node::Literal(ExpressionValue::Bool(false))),
+                    "null" => Ok(Node::Literal(ExpressionValue::Null)),
+                    _ => self.field(&word),
+                }
+            }
+            Some(t) => Err(parse_error(format!("unexpected '{}'", t))),
+            None => Err(parse_error("unexpected end of expression".to_string())),
+        }
+    }
+
+    fn field(&self, name: &str) -> Result<Node, Error> {
+        let lc = name.to_lowercase();
+        match self.fields.iter().position(|f| f.name.to_lowercase() == lc) {
+            Some(i) => Ok(Node::Field(i)),
+            None => Err(parse_error(format!("unknown field '{}'", name))),
+        }
+    }
+}
+
+/// Checks that a function exists and is called with a valid number of arguments.
+fn check_arity(name: &str, num_args: usize) -> Result<(), Error> {
+    let (min, max) = match name {
+        "pi" => (0, 0),
+        "abs" | "sqrt" | "exp" | "ln" | "log10" | "floor" | "ceil" | "sin" | "cos" | "tan" | "asin" |
+        "acos" | "atan" | "upper" | "lower" | "trim" | "len" | "str" | "num" | "is_null" => (1, 1),
+        "round" => (1, 2),
+        "pow" | "atan2" | "contains" | "starts_with" | "ends_with" => (2, 2),
+        "substr" => (2, 3),
+        "replace" | "if" => (3, 3),
+        "min" | "max" | "concat" | "coalesce" => (1, usize::max_value()),
+        _ => return Err(parse_error(format!("unknown function '{}'", name))),
+    };
+    if num_args < min || num_args > max {
+        return Err(parse_error(format!("wrong number of arguments to '{}'", name)));
+    }
+    Ok(())
+}
+
+struct Context<'a> {
+    record: &'a [FieldData],
+    geometry: &'a ShapefileGeometry,
+    record_num: usize,
+}
+
+fn evaluate(node: &Node, context: &Context) -> Result<ExpressionValue, Error> {
+    Ok(match *node {
+        Node::Literal(ref v) => v.clone(),
+        Node::Field(i) => {
+            match context.record.get(i) {
+                Some(value) => ExpressionValue::from_field_data(value),
+                None => ExpressionValue::Null,
+            }
+        }
+        Node::Property(property) => geometry_property(property, context),
+        Node::Negate(ref operand) => {
+            match evaluate(operand, context)? {
+                ExpressionValue::Null => ExpressionValue::Null,
+                v => ExpressionValue::Number(-number(&v, "-")?),
+            }
+        }
+        Node::Not(ref operand) => ExpressionValue::Bool(!evaluate(operand, context)?.is_true()),
+        Node::And(ref left, ref right) => {
+            ExpressionValue::Bool(evaluate(left, context)?.is_true() && evaluate(right, context)?.is_true())
+        }
+        Node::Or(ref left, ref right) => {
+            ExpressionValue::Bool(evaluate(left, context)?.is_true() || evaluate(right, context)?.is_true())
+        }
+        Node::Binary(op, ref left, ref right) => {
+            let a = evaluate(left, context)?;
+            let b = evaluate(right, context)?;
+            binary(op, &a, &b)?
+        }
+        Node::Call(ref name, ref args) => {
+            if name == "if" {
+                // only the selected branch is evaluated
+                return if evaluate(&args[0], context)?.is_true() {
+                    evaluate(&args[1], context)
+                } else {
+                    evaluate(&args[2], context)
+                };
+            }
+            let mut values = Vec::with_capacity(args.len());
+            for arg in args {
+                values.push(evaluate(arg, context)?);
+            }
+            call(name, &values)?
+        }
+    })
+}
+
+fn number(value: &ExpressionValue, operation: &str) -> Result<f64, Error> {
+    match value.as_number() {
+        Some(v) => Ok(v),
+        None => Err(evaluation_error(format!("'{}' requires a number but was given '{}'", operation, value))),
+    }
+}
+
+/// A numeric result, with infinite and undefined values (e.g. from division by zero) as null.
+fn number_value(v: f64) -> ExpressionValue {
+    if v.is_finite() {
+        ExpressionValue::Number(v)
+    } else {
+        ExpressionValue::Null
+    }
+}
+
+fn binary(op: BinaryOp, a: &ExpressionValue, b: &ExpressionValue) -> Result<ExpressionValue, Error> {
+    let is_null = *a == ExpressionValue::Null || *b == ExpressionValue::Null;
+    match op {
+        BinaryOp::Equal => return Ok(ExpressionValue::Bool(compare(a, b) == Some(0))),
+        BinaryOp::NotEqual => return Ok(ExpressionValue::Bool(compare(a, b) != Some(0))),
+        BinaryOp::Less | BinaryOp::LessEqual | BinaryOp::Greater | BinaryOp::GreaterEqual => {
+            let result = match compare(a, b) {
+                None => false,
+                Some(c) => {
+                    match op {
+                        BinaryOp::Less => c < 0,
+                        BinaryOp::LessEqual => c <= 0,
+                        BinaryOp::Greater => c > 0,
+                        _ => c >= 0,
+                    }
+                }
+            };
+            return Ok(ExpressionValue::Bool(result));
+        }
+        _ => {}
+    }
+    if is_null {
+        return Ok(ExpressionValue::Null);
+    }
+    if op == BinaryOp::Add {
+        if let (&ExpressionValue::Text(_), _) | (_, &ExpressionValue::Text(_)) = (a, b) {
+            return Ok(ExpressionValue::Text(format!("{}{}", a, b)));
+        }
+    }
+    let symbol = match op {
+        BinaryOp::Add => "+",
+        BinaryOp::Subtract => "-",
+        BinaryOp::Multiply => "*",
+        BinaryOp::Divide => "/",
+        BinaryOp::Remainder => "%",
+        _ => "^",
+    };
+    let (x, y) = (number(a, symbol)?, number(b, symbol)?);
+    Ok(number_value(match op {
+        BinaryOp::Add => x + y,
+        BinaryOp::Subtract => x - y,
+        BinaryOp::Multiply => x * y,
+        BinaryOp::Divide => x / y,
+        BinaryOp::Remainder => x % y,
+        _ => x.powf(y),
+    }))
+}
+
+/// Compares two values, returning -1, 0 or 1, or None if they cannot be
+/// ordered. Null is equal to null only.
+fn compare(a: &ExpressionValue, b: &ExpressionValue) -> Option<i32> {
+    let ordering = match (a, b) {
+        (&ExpressionValue::Null, &ExpressionValue::Null) => return Some(0),
+        (&ExpressionValue::Null, _) | (_, &ExpressionValue::Null) => return None,
+        (&ExpressionValue::Text(ref s), &ExpressionValue::Text(ref t)) => s.partial_cmp(t),
+        _ => {
+            match (a.as_number(), b.as_number()) {
+                (Some(x), Some(y)) => x.partial_cmp(&y),
+                // text that is not a number is compared as text
+                _ => format!("{}", a).partial_cmp(&format!("{}", b)),
+            }
+        }
+    };
+    ordering.map(|o| o as i32)
+}
+
+fn call(name: &str, args: &[ExpressionValue]) -> Result<ExpressionValue, Error> {
+    match name {
+        "is_null" => return Ok(ExpressionValue::Bool(args[0] == ExpressionValue::Null)),
+        "coalesce" => {
+            return Ok(args.iter().find(|v| **v != ExpressionValue::Null).cloned().unwrap_or(ExpressionValue::Null))
+        }
+        "str" => {
+            return Ok(match args[0] {
+                ExpressionValue::Null => ExpressionValue::Null,
+                ref v => ExpressionValue::Text(format!("{}", v)),
+            })
+        }
+        "num" => return Ok(args[0].as_number().map(number_value).unwrap_or(ExpressionValue::Null)),
+        "concat" => {
+            let s: Vec<String> = args.iter()
+                                     .filter(|v| **v != ExpressionValue::Null)
+                                     .map(|v| format!("{}", v))
+                                     .collect();
+            return Ok(ExpressionValue::Text(s.concat()));
+        }
+        "pi" => return Ok(ExpressionValue::Number(PI)),
+        _ => {}
+    }
+    if args.iter().any(|v| *v == ExpressionValue::Null) {
+        return Ok(ExpressionValue::Null);
+    }
+    let text = |i: usize| format!("{}", args[i]);
+    let num = |i: usize| number(&args[i], name);
+    Ok(match name {
+        "upper" => ExpressionValue::Text(text(0).to_uppercase()),
+        "lower" => ExpressionValue::Text(text(0).to_lowercase()),
+        "trim" => ExpressionValue::Text(text(0).trim().to_string()),
+        "len" => ExpressionValue::Number(text(0).chars().count() as f64),
+        "substr" => {
+            let s: Vec<char> = text(0).chars().collect();
+            let start = (num(1)?.round().max(1f64) as usize - 1).min(s.len());
+            let end = if args.len() > 2 {
+                start.saturating_add(num(2)?.round().max(0f64) as usize).min(s.len())
+            } else {
+                s.len()
+            };
+            ExpressionValue::Text(s[start..end].iter().collect())
+        }
+        "replace" => ExpressionValue::Text(text(0).replace(&text(1), &text(2))),
+        "contains" => ExpressionValue::Bool(text(0).contains(&text(1))),
+        "starts_with" => ExpressionValue::Bool(text(0).starts_with(&text(1))),
+        "ends_with" => ExpressionValue::Bool(text(0).ends_with(&text(1))),
+        "min" | "max" => {
+            let mut result = num(0)?;
+            for i in 1..args.len() {
+                let v = num(i)?;
+                result = if name == "min" { result.min(v) } else { result.max(v) };
+            }
+            ExpressionValue::Number(result)
+        }
+        "round" => {
+            let scale = if args.len() > 1 { 10f64.powf(num(1)?.round()) } else { 1f64 };
+            number_value((num(0)? * scale).round() / scale)
+        }
+        "pow" => number_value(num(0)?.powf(num(1)?)),
+        "atan2" => number_value(num(0)?.atan2(num(1)?)),
+        _ => {
+            let x = num(0)?;
+            number_value(match name {
+                "abs" => x.abs(),
+                "sqrt" => x.sqrt(),
+                "exp" => x.exp(),
+                "ln" => x.ln(),
+                "log10" => x.log10(),
+                "floor" => x.floor(),
+                "ceil" => x.ceil(),
+                "sin" => x.sin(),
+                "cos" => x.cos(),
+                "tan" => x.tan(),
+                "asin" => x.asin(),
+                "acos" => x.acos(),
+                _ => x.atan(),
+            })
+        }
+    })
+}
+
+fn geometry_property(property: GeometryProperty, context: &Context) -> ExpressionValue {
+    let geom = context.geometry;
+    let base = geom.shape_type.base_shape_type();
+    if property == GeometryProperty::Fid {
+        return ExpressionValue::Number((context.record_num + 1) as f64);
+    }
+    if base == ShapeType::Null || geom.num_points == 0 {
+        return match property {
+            GeometryProperty::NumPoints | GeometryProperty::NumParts => ExpressionValue::Number(0f64),
+            _ => ExpressionValue::Null,
+        };
+    }
+    let parts: Vec<&[Point2D]> = (0..geom.num_parts as usize).map(|part| geom.get_part(part)).collect();
+    match property {
+        GeometryProperty::NumPoints => ExpressionValue::Number(geom.num_points as f64),
+        GeometryProperty::NumParts => ExpressionValue::Number(geom.num_parts as f64),
+        GeometryProperty::Area => {
+            if base == ShapeType::Polygon {
+                // hulls are clockwise, with negative signed areas, and holes positive
+                let area: f64 = parts.iter().map(|p| -signed_polygon_area(p)).sum();
+                ExpressionValue::Number(area.abs())
+            } else {
+                ExpressionValue::Number(0f64)
+            }
+        }
+        GeometryProperty::Perimeter | GeometryProperty::Length => {
+            if base == ShapeType::Polygon || base == ShapeType::PolyLine {
+                ExpressionValue::Number(parts.iter().map(|p| polyline_length(p)).sum())
+            } else {
+                ExpressionValue::Number(0f64)
+            }
+        }
+        _ => {
+            let (x, y) = centroid(geom, base, &parts);
+            ExpressionValue::Number(if property == GeometryProperty::CentroidX { x } else { y })
+        }
+    }
+}
+
+/// The centroid of a shape: area-weighted for polygons, length-weighted for
+/// lines, and the mean location for points, falling back to the mean of the
+/// vertices if a polygon has no area or a line has no length.
+fn centroid(geom: &ShapefileGeometry,
+            base: ShapeType,
+            parts: &[&[Point2D]])
+            -> (f64, f64) {
+    let (mut sx, mut sy, mut sw) = (0f64, 0f64, 0f64);
+    if base == ShapeType::Polygon {
+        for part in parts {
+            for i in 0..part.len().saturating_sub(1) {
+                let (p, q) = (part[i], part[i + 1]);
+                let cross = p.x * q.y - q.x * p.y;
+                sx += (p.x + q.x) * cross;
+                sy += (p.y + q.y) * cross;
+                sw += cross;
+            }
+        }
+        if sw != 0f64 {
+            return (sx / (3f64 * sw), sy / (3f64 * sw));
+        }
+    } else if base == ShapeType::PolyLine {
+        for part in parts {
+            for i in 0..part.len().saturating_sub(1) {
+                let (p, q) = (part[i], part[i + 1]);
+                let length = p.distance(&q);
+                sx += (p.x + q.x) / 2f64 * length;
+                sy += (p.y + q.y) / 2f64 * length;
+                sw += length;
+            }
+        }
+        if sw > 0f64 {
+            return (sx / sw, sy / sw);
+        }
+    }
+    let n = geom.points.len() as f64;
+    (geom.points.iter().map(|p| p.x).sum::<f64>() / n, geom.points.iter().map(|p| p.y).sum::<f64>() / n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, Expression, ExpressionValue, Token};
+    use structures::Point2D;
+    use vector::{AttributeField, FieldData, FieldDataType, ShapeType, ShapefileGeometry};
+
+    fn fields() -> Vec<AttributeField> {
+        vec![AttributeField::new("POP", FieldDataType::Int, 10u8, 0u8),
+             AttributeField::new("NAME", FieldDataType::Text, 20u8, 0u8),
+             AttributeField::new("DENSITY", FieldDataType::Real, 12u8, 4u8),
+             AttributeField::new("LAND USE", FieldDataType::Text, 20u8, 0u8),
+             AttributeField::new("MISSING", FieldDataType::Real, 12u8, 4u8)]
+    }
+
+    fn record() -> Vec<FieldData> {
+        vec![FieldData::Int(1200),
+             FieldData::Text("Guelph".to_string()),
+             FieldData::Real(2.5),
+             FieldData::Text("Urban".to_string()),
+             FieldData::Null]
+    }
+
+    // a 10 x 20 rectangle with a 2 x 2 hole
+    fn polygon() -> ShapefileGeometry {
+        let mut geom = ShapefileGeometry::new(ShapeType::Polygon);
+        let hull = [(0.0, 0.0), (0.0, 20.0), (10.0, 20.0), (10.0, 0.0), (0.0, 0.0)];
+        let hole = [(4.0, 4.0), (6.0, 4.0), (6.0, 6.0), (4.0, 6.0), (4.0, 4.0)];
+        for ring in [&hull[..], &hole[..]].iter() {
+            let points: Vec<Point2D> = ring.iter().map(|&(x, y)| Point2D::new(x, y)).collect();
+            geom.add_part(&points);
+        }
+        geom
+    }
+
+    fn eval(expression: &str) -> ExpressionValue {
+        Expression::new(expression, &fields()).unwrap().evaluate(&record(), &polygon(), 4).unwrap()
+    }
+
+    fn number(expression: &str) -> f64 {
+        match eval(expression) {
+            ExpressionValue::Number(v) => v,
+            v => panic!("{} gave {:?}", expression, v),
+        }
+    }
+
+    fn text(v: &str) -> ExpressionValue {
+        ExpressionValue::Text(v.to_string())
+    }
+
+    fn parse_error(expression: &str) -> String {
+        match Expression::new(expression, &fields()) {
+            Ok(_) => panic!("{} should not parse", expression),
+            Err(e) => format!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_tokenizer() {
+        assert_eq!(tokenize("[LAND USE] <> 'it''s' && $Area >= 1.5e-3").unwrap(),
+                   vec![Token::Field("LAND USE".to_string()),
+                        Token::Operator("<>"),
+                        Token::Text("it's".to_string()),
+                        Token::Operator("&&"),
+                        Token::Property("area".to_string()),
+                        Token::Operator(">="),
+                        Token::Number(1.5e-3)]);
+        assert_eq!(tokenize("round(.5,2)-x").unwrap(),
+                   vec![Token::Identifier("round".to_string()),
+                        Token::Open,
+                        Token::Number(0.5),
+                        Token::Comma,
+                        Token::Number(2f64),
+                        Token::Close,
+                        Token::Operator("-"),
+                        Token::Identifier("x".to_string())]);
+        assert_eq!(tokenize("\"a\"\"b\"").unwrap(), vec![Token::Text("a\"b".to_string())]);
+        assert!(tokenize("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(number("1 + 2 * 3"), 7f64);
+        assert_eq!(number("(1 + 2) * 3"), 9f64);
+        assert_eq!(number("10 - 4 - 3"), 3f64);
+        assert_eq!(number("24 / 4 / 2"), 3f64);
+        assert_eq!(number("7 % 4 * 2"), 6f64);
+        // exponentiation is right-associative and binds more tightly than a leading minus
+        assert_eq!(number("2 ^ 3 ^ 2"), 512f64);
+        assert_eq!(number("-2 ^ 2"), -4f64);
+        assert_eq!(number("2 ^ -1"), 0.5);
+        assert_eq!(number("--3"), 3f64);
+        // comparisons bind less tightly than arithmetic, and 'not' less tightly than comparisons
+        assert_eq!(eval("1 + 1 == 2"), ExpressionValue::Bool(true));
+        assert_eq!(eval("not 1 > 2"), ExpressionValue::Bool(true));
+        // 'and' binds more tightly than 'or'
+        assert_eq!(eval("true or false and false"), ExpressionValue::Bool(true));
+        assert_eq!(eval("(true or false) and false"), ExpressionValue::Bool(false));
+        assert_eq!(eval("!false && 1 < 2 || 0"), ExpressionValue::Bool(true));
+    }
+
+    #[test]
+    fn test_fields_and_properties() {
+        assert_eq!(number("pop / density"), 480f64);
+        assert_eq!(eval("[LAND USE] = 'Urban' and upper(Name) == 'GUELPH'"), ExpressionValue::Bool(true));
+        assert_eq!(number("$area"), 196f64);
+        assert_eq!(number("$perimeter"), 68f64);
+        assert_eq!(number("$num_parts"), 2f64);
+        assert_eq!(number("$num_points"), 10f64);
+        assert_eq!(number("$fid"), 5f64);
+        assert!((number("$centroid_x") - 5f64).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_values_and_nulls() {
+        assert_eq!(eval("'a' + 1"), text("a1"));
+        assert_eq!(eval("'10' > 9"), ExpressionValue::Bool(true));
+        assert_eq!(eval("'b' > 'a'"), ExpressionValue::Bool(true));
+        assert_eq!(eval("missing + 1"), ExpressionValue::Null);
+        assert_eq!(eval("missing == null"), ExpressionValue::Bool(true));
+        assert_eq!(eval("missing != 0"), ExpressionValue::Bool(true));
+        assert_eq!(eval("missing < 1"), ExpressionValue::Bool(false));
+        assert_eq!(eval("1 / 0"), ExpressionValue::Null);
+        assert_eq!(eval("coalesce(missing, pop)"), ExpressionValue::Number(1200f64));
+        assert_eq!(eval("is_null(sqrt(-1))"), ExpressionValue::Bool(true));
+        assert_eq!(eval("if(pop > 1000, 'city', 'town')"), text("city"));
+        // only the selected branch is evaluated
+        assert_eq!(eval("if(true, 1, 'x' * 2)"), ExpressionValue::Number(1f64));
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(number("round(3.14159, 2)"), 3.14);
+        assert_eq!(number("min(3, 1, 2) + max(3, 1, 2)"), 4f64);
+        assert_eq!(number("len(name)"), 6f64);
+        assert_eq!(eval("substr(name, 2, 3)"), text("uel"));
+        assert_eq!(eval("substr(name, 4)"), text("lph"));
+        assert_eq!(eval("substr(name, -5, 1e300)"), text("Guelph"));
+        assert_eq!(eval("substr(name, 100)"), text(""));
+        assert_eq!(eval("replace(name, 'ph', 'f')"), text("Guelf"));
+        assert_eq!(eval("concat(name, missing, '-', 1)"), text("Guelph-1"));
+        assert_eq!(eval("str(pop) + num('5')"), text("12005"));
+        assert_eq!(number("num('5') + 1"), 6f64);
+        assert_eq!(eval("num('x')"), ExpressionValue::Null);
+        assert_eq!(eval("starts_with(name, 'Gu') and ends_with(name, 'ph') and contains(name, 'elp')"),
+                   ExpressionValue::Bool(true));
+        assert!((number("atan2(1, 1) * 4") - number("pi()")).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_error("pop +"), "Invalid expression: unexpected end of expression.");
+        assert_eq!(parse_error("(pop + 1"), "Invalid expression: missing ')'.");
+        assert_eq!(parse_error("pop 1"), "Invalid expression: unexpected '1'.");
+        assert_eq!(parse_error("area > 1"), "Invalid expression: unknown field 'area'.");
+        assert_eq!(parse_error("[NO SUCH FIELD]"), "Invalid expression: unknown field 'NO SUCH FIELD'.");
+        assert_eq!(parse_error("$volume"), "Invalid expression: unknown geometry property '$volume'.");
+        assert_eq!(parse_error("foo(1)"), "Invalid expression: unknown function 'foo'.");
+        assert_eq!(parse_error("sqrt(1, 2)"), "Invalid expression: wrong number of arguments to 'sqrt'.");
+        assert_eq!(parse_error("max(1, 2"), "Invalid expression: missing ')' in call to 'max'.");
+        assert_eq!(parse_error("'abc"), "Invalid expression: unterminated text.");
+        assert_eq!(parse_error("[POP"), "Invalid expression: unterminated field name.");
+        assert_eq!(parse_error("1.2.3"), "Invalid expression: bad number '1.2.3'.");
+        assert_eq!(parse_error("pop & 1"), "Invalid expression: unexpected character '&'.");
+        assert_eq!(parse_error("pop # 1"), "Invalid expression: unexpected character '#'.");
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        // none of these may panic
+        for expression in &["", " ", "(", ")", "()", ",", "1,", "+", "-", "!", "not", "and", "1 and",
+                            "1 == == 2", "1 < 2 < 3", "$", "$$", "[", "[]", "]", "'", "\"", "f(", "max(,)",
+                            "max()", "round(1,2,3)", "if(1, 2)", "1e", "1e+", ".", "..", "1 ^", "^ 1",
+                            "pop pop", "((1)", "(1))", "\u{e9}", "pop\u{0}"] {
+            assert!(Expression::new(expression, &fields()).is_err(), "{}", expression);
+        }
+        // evaluation errors are returned rather than panicking
+        let expression = Expression::new("name * 2", &fields()).unwrap();
+        let e = expression.evaluate(&record(), &polygon(), 0).unwrap_err();
+        assert_eq!(format!("{}", e), "Error evaluating expression: '*' requires a number but was given 'Guelph'.");
+        // a record without the field, and a null geometry
+        let expression = Expression::new("pop + $area", &fields()).unwrap();
+        let null_geom = ShapefileGeometry::new(ShapeType::Null);
+        assert_eq!(expression.evaluate(&[], &null_geom, 0).unwrap(), ExpressionValue::Null);
+    }
+}
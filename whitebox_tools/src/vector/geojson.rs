@@ -0,0 +1,504 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind};
+use serde_json;
+use serde_json::{Map, Number, Value};
+use vector::*;
+
+/// Reads a GeoJSON (RFC 7946) file. The root object may be a FeatureCollection,
+/// a single Feature, or a bare geometry. Because the vector model holds a
+/// single shape type, Point and MultiPoint features are read together as
+/// MultiPoint shapes, LineString and MultiLineString as PolyLine shapes, and
+/// Polygon and MultiPolygon as Polygon shapes; other mixtures of geometry
+/// types, and GeometryCollections, are not supported. If any position has an
+/// elevation, the Z variant of the shape type is used.
+///
+/// Feature properties become attribute fields. A field is Int if all of its
+/// values are integers, Real if they are all numbers, Bool if they are all
+/// booleans, and otherwise Text (with nested objects and arrays stored as JSON
+/// text). GeoJSON coordinates are by definition WGS84 longitude/latitude, but
+/// no projection is assigned so that files using other coordinate systems,
+/// common in practice, are not mislabelled.
+pub fn read_geojson(sf: &mut Shapefile) -> Result<(), Error> {
+    let mut f = File::open(&sf.file_name)?;
+    let mut contents = String::new();
+    f.read_to_string(&mut contents)?;
+    let root: Value = match serde_json::from_str(&contents) {
+        Ok(v) => v,
+        Err(e) => {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  format!("The GeoJSON file is improperly formatted ({}).", e)))
+        }
+    };
+
+    // (geometry, properties) of each feature
+    let mut features: Vec<(&Value, Option<&Map<String, Value>>)> = vec![];
+    match root["type"].as_str() {
+        Some("FeatureCollection") => {
+            let members = match root["features"].as_array() {
+                Some(a) => a,
+                None => {
+                    return Err(Error::new(ErrorKind::InvalidData,
+                                          "The GeoJSON FeatureCollection has no features member."))
+                }
+            };
+            for feature in members {
+                features.push((&feature["geometry"], feature["properties"].as_object()));
+            }
+        }
+        Some("Feature") => features.push((&root["geometry"], root["properties"].as_object())),
+        Some(_) => features.push((&root, None)),
+        None => {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  "The GeoJSON file is improperly formatted (missing type member)."))
+        }
+    }
+
+    // the shape type of the file
+    let mut base = ShapeType::Null;
+    let mut has_z = false;
+    for &(geometry, _) in &features {
+        if geometry.is_null() {
+            continue;
+        }
+        let feature_base = match geometry["type"].as_str() {
+            Some("Point") => ShapeType::Point,
+            Some("MultiPoint") => ShapeType::MultiPoint,
+            Some("LineString") | Some("MultiLineString") => ShapeType::PolyLine,
+            Some("Polygon") | Some("MultiPolygon") => ShapeType::Polygon,
+            Some(t) => {
+                return Err(Error::new(ErrorKind::InvalidData,
+                                      format!("Unsupported GeoJSON geometry type ({}).", t)))
+            }
+            None => {
+                return Err(Error::new(ErrorKind::InvalidData,
+                                      "The GeoJSON file contains a geometry without a type."))
+            }
+        };
+        base = match (base, feature_base) {
+            (ShapeType::Null, b) => b,
+            (ShapeType::Point, ShapeType::MultiPoint) |
+            (ShapeType::MultiPoint, ShapeType::Point) => ShapeType::MultiPoint,
+            (a, b) if a == b => a,
+            _ => {
+                return Err(Error::new(ErrorKind::InvalidData,
+                                      "The GeoJSON file contains a mixture of geometry types."))
+            }
+        };
+        if first_position_dimension(&geometry["coordinates"]) > 2 {
+            has_z = true;
+        }
+    }
+    sf.header.shape_type = ShapeType::from_base_shape_type(base, has_z, false);
+
+    for &(geometry, _) in &features {
+        if geometry.is_null() {
+            sf.records.push(ShapefileGeometry::new(ShapeType::Null));
+            continue;
+        }
+        let coordinates = &geometry["coordinates"];
+        // every type is read as a list of groups (polygons) of parts of coordinates
+        let groups = match geometry["type"].as_str() {
+            Some("Point") => vec![vec![vec![read_position(coordinates)?]]],
+            Some("MultiPoint") | Some("LineString") => vec![vec![read_positions(coordinates)?]],
+            Some("MultiLineString") | Some("Polygon") => vec![read_position_lists(coordinates)?],
+            _ => {
+                let mut polygons = vec![];
+                for polygon in as_array(coordinates)? {
+                    polygons.push(read_position_lists(polygon)?);
+                }
+                polygons
+            }
+        };
+        sf.records.push(ShapefileGeometry::from_coordinates(base, has_z, false, &groups));
+    }
+    sf.num_records = sf.records.len();
+
+    read_properties(sf, &features);
+    sf.update_header_extent();
+
+    Ok(())
+}
+
+/// Writes a vector as a GeoJSON (RFC 7946) FeatureCollection, with each
+/// record's attributes as the feature properties. See `geometry_to_geojson`
+/// for the encoding of the geometries. Dates are written as 'YYYY-MM-DD'
+/// strings. The projection is not written, since RFC 7946 assumes WGS84.
+pub fn write_geojson(sf: &mut Shapefile) -> Result<(), Error> {
+    sf.update_header_extent();
+    let mut features = Vec::with_capacity(sf.records.len());
+    for i in 0..sf.records.len() {
+        let mut properties = Map::new();
+        if i < sf.attributes.get_num_records() {
+            let record = sf.attributes.get_record(i);
+            for (field, value) in sf.attributes.fields.iter().zip(record.iter()) {
+                let v = match *value {
+                    FieldData::Int(v) => Value::from(v),
+                    FieldData::Real(v) => number(v),
+                    FieldData::Text(ref s) => Value::String(s.clone()),
+                    FieldData::Date(ref s) => {
+                        if s.len() == 8 {
+                            Value::String(format!("{}-{}-{}", &s[0..4], &s[4..6], &s[6..8]))
+                        } else {
+                            Value::String(s.clone())
+                        }
+                    }
+                    FieldData::Bool(v) => Value::Bool(v),
+                    FieldData::Null => Value::Null,
+                };
+                properties.insert(field.name.clone(), v);
+            }
+        }
+        let mut feature = Map::new();
+        feature.insert("type".to_string(), Value::String("Feature".to_string()));
+        feature.insert("properties".to_string(), Value::Object(properties));
+        feature.insert("geometry".to_string(), geometry_to_geojson(&sf.records[i]));
+        features.push(Value::Object(feature));
+    }
+    let mut root = Map::new();
+    root.insert("type".to_string(), Value::String("FeatureCollection".to_string()));
+    root.insert("features".to_string(), Value::Array(features));
+
+    let f = File::create(&sf.file_name)?;
+    let mut writer = BufWriter::new(f);
+    if let Err(e) = serde_json::to_writer(&mut writer, &Value::Object(root)) {
+        return Err(Error::new(ErrorKind::Other, format!("Error writing GeoJSON file ({}).", e)));
+    }
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Encodes a geometry as a GeoJSON geometry object. Single-part lines and
+/// polygons are written as LineString and Polygon, and multi-part shapes as
+/// MultiLineString and MultiPolygon. Following RFC 7946, polygon hulls are
+/// written counter-clockwise and holes clockwise. Z values are written as the
+/// third ordinate of each position; measures are not written. Null shapes
+/// are written as null.
+pub fn geometry_to_geojson(geom: &ShapefileGeometry) -> Value {
+    let base = geom.shape_type.base_shape_type();
+    if base == ShapeType::Null || geom.num_points == 0 {
+        return Value::Null;
+    }
+    let has_z = geom.shape_type.has_z_data();
+    let position = |i: usize| -> Value {
+        let mut p = vec![number(geom.points[i].x), number(geom.points[i].y)];
+        if has_z {
+            p.push(number(geom.z_array[i]));
+        }
+        Value::Array(p)
+    };
+    let positions = |indices: Vec<usize>| -> Value {
+        Value::Array(indices.into_iter().map(|i| position(i)).collect())
+    };
+    let (geometry_type, coordinates) = match base {
+        ShapeType::Point => ("Point", position(0)),
+        ShapeType::MultiPoint => ("MultiPoint", positions((0..geom.points.len()).collect())),
+        ShapeType::PolyLine => {
+            let mut lines: Vec<Value> = (0..geom.num_parts as usize).map(|part| {
+                let (start, end) = geom.part_range(part);
+                positions((start..end).collect())
+            }).collect();
+            if lines.len() == 1 {
+                ("LineString", lines.pop().unwrap())
+            } else {
+                ("MultiLineString", Value::Array(lines))
+            }
+        }
+        _ => {
+            let mut polygons: Vec<Value> = geom.get_polygon_parts().iter().map(|rings| {
+                Value::Array(rings.iter().enumerate().map(|(k, part)| {
+                    positions(geom.get_ring_indices(*part, k > 0))
+                }).collect())
+            }).collect();
+            if polygons.len() == 1 {
+                ("Polygon", polygons.pop().unwrap())
+            } else {
+                ("MultiPolygon", Value::Array(polygons))
+            }
+        }
+    };
+    let mut geometry = Map::new();
+    geometry.insert("type".to_string(), Value::String(geometry_type.to_string()));
+    geometry.insert("coordinates".to_string(), coordinates);
+    Value::Object(geometry)
+}
+
+fn number(v: f64) -> Value {
+    match Number::from_f64(v) {
+        Some(n) => Value::Number(n),
+        None => Value::Null, // NaN and infinite values
+    }
+}
+
+fn as_array(v: &Value) -> Result<&Vec<Value>, Error> {
+    match v.as_array() {
+        Some(a) => Ok(a),
+        None => Err(Error::new(ErrorKind::InvalidData,
+                               "The GeoJSON file contains improperly formatted coordinates.")),
+    }
+}
+
+fn read_position(v: &Value) -> Result<Vec<f64>, Error> {
+    let mut position = vec![];
+    for ordinate in as_array(v)? {
+        match ordinate.as_f64() {
+            Some(o) => position.push(o),
+            None => {
+                return Err(Error::new(ErrorKind::InvalidData,
+                                      "The GeoJSON file contains a non-numeric coordinate."))
+            }
+        }
+    }
+    if position.len() < 2 {
+        return Err(Error::new(ErrorKind::InvalidData,
+                              "GeoJSON positions must have at least two ordinates."));
+    }
+    Ok(position)
+}
+
+fn read_positions(v: &Value) -> Result<Vec<Vec<f64>>, Error> {
+    let mut positions = vec![];
+    for p in as_array(v)? {
+        positions.push(read_position(p)?);
+    }
+    Ok(positions)
+}
+
+fn read_position_lists(v: &Value) -> Result<Vec<Vec<Vec<f64>>>, Error> {
+    let mut lists = vec![];
+    for list in as_array(v)? {
+        lists.push(read_positions(list)?);
+    }
+    Ok(lists)
+}
+
+/// The number of ordinates of the first position within a coordinates array.
+fn first_position_dimension(v: &Value) -> usize {
+    let mut v = v;
+    loop {
+        match v.as_array() {
+            Some(a) if !a.is_empty() => {
+                if a[0].is_number() {
+                    return a.len();
+                }
+                v = &a[0];
+            }
+            _ => return 0,
+        }
+    }
+}
+
+/// Creates the attribute table from the feature properties.
+fn read_properties(sf: &mut Shapefile, features: &[(&Value, Option<&Map<String, Value>>)]) {
+    // field names in order of appearance
+    let mut names: Vec<String> = vec![];
+    for &(_, properties) in features {
+        if let Some(properties) = properties {
+            for key in properties.keys() {
+                if !names.contains(key) {
+                    names.push(key.clone());
+                }
+            }
+        }
+    }
+
+    let mut field_types = vec![];
+    for name in &names {
+        let (mut all_int, mut all_number, mut all_bool, mut any_value) = (true, true, true, false);
+        let mut max_length = 1usize;
+        let mut max_decimals = 0usize;
+        for &(_, properties) in features {
+            let value = match properties.and_then(|p| p.get(name)) {
+                Some(v) if !v.is_null() => v,
+                _ => continue,
+            };
+            any_value = true;
+            if let Some(i) = value.as_i64() {
+                all_bool = false;
+                if i < i32::min_value() as i64 || i > i32::max_value() as i64 {
+                    all_int = false;
+                }
+                max_length = max_length.max(format!("{}", i).len());
+            } else if let Some(f) = value.as_f64() {
+                all_bool = false;
+                all_int = false;
+                let s = format!("{}", f);
+                max_length = max_length.max(s.len());
+                if let Some(dot) = s.find('.') {
+                    max_decimals = max_decimals.max(s.len() - dot - 1);
+                }
+            } else {
+                all_int = false;
+                all_number = false;
+                if !value.is_boolean() {
+                    all_bool = false;
+                }
+                max_length = max_length.max(text_value(value).chars().count());
+            }
+        }
+        let field = if !any_value {
+            AttributeField::new(name, FieldDataType::Text, 1u8, 0u8)
+        } else if all_bool {
+            AttributeField::new(name, FieldDataType::Bool, 1u8, 0u8)
+        } else if all_int {
+            AttributeField::new(name, FieldDataType::Int, max_length.min(18) as u8, 0u8)
+        } else if all_number {
+            AttributeField::new(name, FieldDataType::Real, 18u8, max_decimals.max(1).min(8) as u8)
+        } else {
+            AttributeField::new(name, FieldDataType::Text, max_length.min(254) as u8, 0u8)
+        };
+        field_types.push(field.field_type);
+        sf.attributes.add_field(&field);
+    }
+
+    for &(_, properties) in features {
+        let mut record = Vec::with_capacity(names.len());
+        for (name, field_type) in names.iter().zip(field_types.iter()) {
+            let value = match properties.and_then(|p| p.get(name)) {
+                Some(v) if !v.is_null() => v,
+                _ => {
+                    record.push(FieldData::Null);
+                    continue;
+                }
+            };
+            record.push(match *field_type {
+                FieldDataType::Bool => FieldData::Bool(value.as_bool().unwrap_or(false)),
+                FieldDataType::Int => FieldData::Int(value.as_i64().unwrap_or(0) as i32),
+                FieldDataType::Real => FieldData::Real(value.as_f64().unwrap_or(0f64)),
+                _ => FieldData::Text(text_value(value)),
+            });
+        }
+        sf.attributes.add_record(record, false);
+    }
+}
+
+fn text_value(value: &Value) -> String {
+    match *value {
+        Value::String(ref s) => s.clone(),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::prelude::*;
+    use std::io::Error;
+    use vector::*;
+
+    fn temp_file(name: &str) -> String {
+        env::temp_dir().join(format!("wbt_geojson_test_{}.geojson", name)).to_str().unwrap().to_string()
+    }
+
+    // writes the geometries, with a null record at the end, and reads them back
+    fn round_trip(name: &str, wkts: &[&str]) -> Shapefile {
+        let file_name = temp_file(name);
+        let mut sf = Shapefile::new(&file_name, "w").unwrap();
+        sf.attributes.add_field(&AttributeField::new("ID", FieldDataType::Int, 4u8, 0u8));
+        sf.attributes.add_field(&AttributeField::new("NAME", FieldDataType::Text, 10u8, 0u8));
+        for (i, wkt) in wkts.iter().enumerate() {
+            sf.add_record(wkt_to_geometry(wkt).unwrap());
+            sf.attributes.add_record(vec![FieldData::Int(i as i32), FieldData::Text(format!("f{}", i))], false);
+        }
+        sf.add_record(ShapefileGeometry::new(ShapeType::Null));
+        sf.attributes.add_record(vec![FieldData::Int(-1), FieldData::Null], false);
+        sf.write().unwrap();
+        let input = Shapefile::new(&file_name, "r").unwrap();
+        fs::remove_file(&file_name).unwrap();
+
+        assert_eq!(input.num_records, wkts.len() + 1);
+        assert_eq!(input.attributes.get_num_fields(), 2);
+        for i in 0..wkts.len() {
+            assert_eq!(input.attributes.get_record(i), vec![FieldData::Int(i as i32), FieldData::Text(format!("f{}", i))]);
+        }
+        assert_eq!(input.attributes.get_record(wkts.len()), vec![FieldData::Int(-1), FieldData::Null]);
+        assert_eq!(input.get_record(wkts.len()).shape_type, ShapeType::Null);
+        input
+    }
+
+    fn check_round_trip(name: &str, shape_type: ShapeType, wkts: &[&str]) {
+        let input = round_trip(name, wkts);
+        assert_eq!(input.header.shape_type, shape_type);
+        for i in 0..wkts.len() {
+            assert_eq!(geometry_to_wkt(input.get_record(i)), wkts[i]);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        check_round_trip("points", ShapeType::MultiPoint, &["MULTIPOINT ((1.5 -2.25))", "MULTIPOINT ((0 0), (1 2))"]);
+        check_round_trip("points_z", ShapeType::MultiPointZ, &["MULTIPOINT Z ((1 2 3), (4 5 6))"]);
+        check_round_trip("lines", ShapeType::PolyLine, &["LINESTRING (0 0, 10 10, 20 0)", "MULTILINESTRING ((0 0, 1 1), (2 2, 3 3, 4 2))"]);
+        check_round_trip("lines_z", ShapeType::PolyLineZ, &["LINESTRING Z (0 0 1, 10 10 2)"]);
+        check_round_trip("polygons", ShapeType::Polygon,
+                         &["POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 4, 4 4, 4 2, 2 2))",
+                           "MULTIPOLYGON (((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 4, 4 4, 4 2, 2 2)), ((20 0, 30 0, 30 10, 20 10, 20 0)), ((3 3, 3.5 3, 3.5 3.5, 3 3.5, 3 3)))"]);
+        check_round_trip("polygons_z", ShapeType::PolygonZ,
+                         &["POLYGON Z ((0 0 1, 10 0 2, 10 10 3, 0 10 4, 0 0 1), (2 2 5, 2 4 6, 4 4 7, 4 2 8, 2 2 5))"]);
+    }
+
+    #[test]
+    fn test_measures_are_dropped() {
+        let input = round_trip("lines_m", &["LINESTRING M (0 0 1, 10 10 2)"]);
+        assert_eq!(input.header.shape_type, ShapeType::PolyLine);
+        assert_eq!(geometry_to_wkt(input.get_record(0)), "LINESTRING (0 0, 10 10)");
+    }
+
+    fn read_text(name: &str, text: &str) -> Result<Shapefile, Error> {
+        let file_name = temp_file(name);
+        File::create(&file_name).unwrap().write_all(text.as_bytes()).unwrap();
+        let ret = Shapefile::new(&file_name, "r");
+        fs::remove_file(&file_name).unwrap();
+        ret
+    }
+
+    #[test]
+    fn test_read_features_and_properties() {
+        let input = read_text("features", r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "geometry": {"type": "Point", "coordinates": [1, 2]},
+             "properties": {"a": 1, "b": 1.5, "c": true, "d": "x", "e": {"k": [1]}}},
+            {"type": "Feature", "geometry": {"type": "MultiPoint", "coordinates": [[3, 4, 5], [6, 7, 8]]},
+             "properties": {"a": 2, "b": 2, "c": false, "d": 3}},
+            {"type": "Feature", "geometry": null, "properties": null}]}"#).unwrap();
+        assert_eq!(input.header.shape_type, ShapeType::MultiPointZ);
+        assert_eq!(geometry_to_wkt(input.get_record(0)), "MULTIPOINT Z ((1 2 0))");
+        assert_eq!(geometry_to_wkt(input.get_record(1)), "MULTIPOINT Z ((3 4 5), (6 7 8))");
+        assert_eq!(input.get_record(2).shape_type, ShapeType::Null);
+        let types: Vec<FieldDataType> = input.attributes.fields.iter().map(|f| f.field_type).collect();
+        assert_eq!(types, vec![FieldDataType::Int, FieldDataType::Real, FieldDataType::Bool, FieldDataType::Text, FieldDataType::Text]);
+        assert_eq!(input.attributes.get_record(0),
+                   vec![FieldData::Int(1), FieldData::Real(1.5), FieldData::Bool(true), FieldData::Text("x".to_string()),
+                        FieldData::Text("{\"k\":[1]}".to_string())]);
+        assert_eq!(input.attributes.get_record(2), vec![FieldData::Null; 5]);
+
+        // a bare geometry
+        let input = read_text("bare", r#"{"type": "Polygon", "coordinates": [[[0, 0], [0, 1], [1, 1], [0, 0]]]}"#).unwrap();
+        assert_eq!(geometry_to_wkt(input.get_record(0)), "POLYGON ((0 0, 1 1, 0 1, 0 0))");
+    }
+
+    #[test]
+    fn test_malformed_geojson() {
+        for (i, text) in [r#"{"type": "FeatureCollection", "features": [}"#,
+                          r#"{"features": []}"#,
+                          r#"{"type": "FeatureCollection"}"#,
+                          r#"{"type": "Point", "coordinates": [1]}"#,
+                          r#"{"type": "Point", "coordinates": ["a", 2]}"#,
+                          r#"{"type": "LineString", "coordinates": 5}"#,
+                          r#"{"type": "GeometryCollection", "geometries": []}"#,
+                          r#"{"type": "FeatureCollection", "features": [
+                              {"type": "Feature", "geometry": {"type": "Point", "coordinates": [1, 2]}},
+                              {"type": "Feature", "geometry": {"type": "LineString", "coordinates": [[1, 2], [3, 4]]}}]}"#]
+                         .iter().enumerate() {
+            assert!(read_text(&format!("malformed_{}", i), text).is_err(), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,440 @@
+use algorithms::{is_clockwise_order, point_in_poly, polygon_area};
+use std::f64;
+use std::fmt;
+use structures::{BoundingBox, Point2D};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ShapeType {
+    Null,
+    Point,
+    PolyLine,
+    Polygon,
+    MultiPoint,
+    PointZ,
+    PolyLineZ,
+    PolygonZ,
+    MultiPointZ,
+    PointM,
+    PolyLineM,
+    PolygonM,
+    MultiPointM,
+    MultiPatch,
+}
+
+impl Default for ShapeType {
+    fn default() -> ShapeType {
+        ShapeType::Null
+    }
+}
+
+impl ShapeType {
+    pub fn from_int(value: i32) -> ShapeType {
+        match value {
+            1 => ShapeType::Point,
+            3 => ShapeType::PolyLine,
+            5 => ShapeType::Polygon,
+            8 => ShapeType::MultiPoint,
+            11 => ShapeType::PointZ,
+            13 => ShapeType::PolyLineZ,
+            15 => ShapeType::PolygonZ,
+            18 => ShapeType::MultiPointZ,
+            21 => ShapeType::PointM,
+            23 => ShapeType::PolyLineM,
+            25 => ShapeType::PolygonM,
+            28 => ShapeType::MultiPointM,
+            31 => ShapeType::MultiPatch,
+            _ => ShapeType::Null,
+        }
+    }
+
+    pub fn to_int(&self) -> i32 {
+        match *self {
+            ShapeType::Null => 0,
+            ShapeType::Point => 1,
+            ShapeType::PolyLine => 3,
+            ShapeType::Polygon => 5,
+            ShapeType::MultiPoint => 8,
+            ShapeType::PointZ => 11,
+            ShapeType::PolyLineZ => 13,
+            ShapeType::PolygonZ => 15,
+            ShapeType::MultiPointZ => 18,
+            ShapeType::PointM => 21,
+            ShapeType::PolyLineM => 23,
+            ShapeType::PolygonM => 25,
+            ShapeType::MultiPointM => 28,
+            ShapeType::MultiPatch => 31,
+        }
+    }
+
+    /// Returns the two-dimensional equivalent of the shape type, e.g. PolygonZ -> Polygon.
+    pub fn base_shape_type(&self) -> ShapeType {
+        match *self {
+            ShapeType::Point | ShapeType::PointZ | ShapeType::PointM => ShapeType::Point,
+            ShapeType::PolyLine | ShapeType::PolyLineZ | ShapeType::PolyLineM => {
+                ShapeType::PolyLine
+            }
+            ShapeType::Polygon | ShapeType::PolygonZ | ShapeType::PolygonM |
+            ShapeType::MultiPatch => ShapeType::Polygon,
+            ShapeType::MultiPoint | ShapeType::MultiPointZ | ShapeType::MultiPointM => {
+                ShapeType::MultiPoint
+            }
+            ShapeType::Null => ShapeType::Null,
+        }
+    }
+
+    /// Returns the shape type with the given base type and dimensions, e.g.
+    /// (Polygon, true, false) -> PolygonZ. Z types may also carry measures.
+    pub fn from_base_shape_type(base: ShapeType, has_z: bool, has_m: bool) -> ShapeType {
+        match (base.base_shape_type(), has_z, has_m) {
+            (ShapeType::Point, true, _) => ShapeType::PointZ,
+            (ShapeType::Point, false, true) => ShapeType::PointM,
+            (ShapeType::PolyLine, true, _) => ShapeType::PolyLineZ,
+            (ShapeType::PolyLine, false, true) => ShapeType::PolyLineM,
+            (ShapeType::Polygon, true, _) => ShapeType::PolygonZ,
+            (ShapeType::Polygon, false, true) => ShapeType::PolygonM,
+            (ShapeType::MultiPoint, true, _) => ShapeType::MultiPointZ,
+            (ShapeType::MultiPoint, false, true) => ShapeType::MultiPointM,
+            (b, _, _) => b,
+        }
+    }
+
+    pub fn has_z_data(&self) -> bool {
+        match *self {
+            ShapeType::PointZ | ShapeType::PolyLineZ | ShapeType::PolygonZ |
+            ShapeType::MultiPointZ | ShapeType::MultiPatch => true,
+            _ => false,
+        }
+    }
+
+    pub fn has_m_data(&self) -> bool {
+        match *self {
+            ShapeType::PointM | ShapeType::PolyLineM | ShapeType::PolygonM |
+            ShapeType::MultiPointM => true,
+            _ => self.has_z_data(), // measures are optional in z types
+        }
+    }
+}
+
+impl fmt::Display for ShapeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A single shape (record) within a vector file. Polylines and polygons may
+/// have several parts; the starting index of each part within `points` is
+/// stored in `parts`. Following the shapefile convention, polygon hulls are
+/// stored in clockwise order and holes in counter-clockwise order.
+#[derive(Default, Clone, Debug)]
+pub struct ShapefileGeometry {
+    pub shape_type: ShapeType,
+    pub num_parts: i32,
+    pub num_points: i32,
+    pub parts: Vec<i32>,
+    pub points: Vec<Point2D>,
+    pub z_array: Vec<f64>,
+    pub m_array: Vec<f64>,
+    pub x_min: f64,
+    pub x_max: f64,
+    pub y_min: f64,
+    pub y_max: f64,
+    pub z_min: f64,
+    pub z_max: f64,
+    pub m_min: f64,
+    pub m_max: f64,
+}
+
+impl ShapefileGeometry {
+    pub fn new(shape_type: ShapeType) -> ShapefileGeometry {
+        ShapefileGeometry {
+            shape_type: shape_type,
+            x_min: f64::INFINITY,
+            x_max: f64::NEG_INFINITY,
+            y_min: f64::INFINITY,
+            y_max: f64::NEG_INFINITY,
+            z_min: f64::INFINITY,
+            z_max: f64::NEG_INFINITY,
+            m_min: f64::INFINITY,
+            m_max: f64::NEG_INFINITY,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a geometry from a list of groups of parts of coordinate tuples,
+    /// in which the third ordinate is z (if has_z) or m, and the fourth m. Each
+    /// group is a polygon (a hull followed by its holes) for polygon types and is
+    /// otherwise just a list of parts.
+    pub fn from_coordinates(base: ShapeType,
+                            has_z: bool,
+                            has_m: bool,
+                            groups: &[Vec<Vec<Vec<f64>>>])
+                            -> ShapefileGeometry {
+        let shape_type = ShapeType::from_base_shape_type(base, has_z, has_m);
+        let mut geom = ShapefileGeometry::new(shape_type);
+        let split = |coords: &[Vec<f64>]| -> (Vec<Point2D>, Vec<f64>, Vec<f64>) {
+            let mut points = Vec::with_capacity(coords.len());
+            let mut z_values = Vec::with_capacity(coords.len());
+            let mut m_values = Vec::with_capacity(coords.len());
+            for c in coords {
+                points.push(Point2D::new(c[0], c[1]));
+                let third = if c.len() > 2 { c[2] } else { 0f64 };
+                let fourth = if c.len() > 3 { c[3] } else { 0f64 };
+                if has_z {
+                    z_values.push(third);
+                    m_values.push(fourth);
+                } else {
+                    m_values.push(third);
+                }
+            }
+            (points, z_values, m_values)
+        };
+        for group in groups {
+            for (k, part) in group.iter().enumerate() {
+                if part.is_empty() {
+                    continue;
+                }
+                let (points, z_values, m_values) = split(part);
+                match base {
+                    ShapeType::Point | ShapeType::MultiPoint => {
+                        for i in 0..points.len() {
+                            let z = if z_values.is_empty() { 0f64 } else { z_values[i] };
+                            geom.add_point_with_zm(points[i], z, m_values[i]);
+                        }
+                    }
+                    ShapeType::PolyLine => geom.add_part_with_zm(&points, &z_values, &m_values),
+                    _ => geom.add_ring(&points, &z_values, &m_values, k > 0),
+                }
+            }
+        }
+        if geom.num_points == 0 {
+            geom = ShapefileGeometry::new(ShapeType::Null);
+        }
+        geom
+    }
+
+    /// Adds a single point, e.g. to a Point or MultiPoint shape.
+    pub fn add_point(&mut self, p: Point2D) {
+        self.update_xy_extent(&p);
+        self.points.push(p);
+        self.num_points += 1;
+    }
+
+    pub fn add_pointz(&mut self, p: Point2D, z: f64, m: f64) {
+        self.add_point(p);
+        self.update_zm_extent(z, m);
+        self.z_array.push(z);
+        self.m_array.push(m);
+    }
+
+    pub fn add_pointm(&mut self, p: Point2D, m: f64) {
+        self.add_point(p);
+        self.update_zm_extent(f64::NAN, m);
+        self.m_array.push(m);
+    }
+
+    /// Adds a part (a polyline segment or polygon ring) to the shape.
+    pub fn add_part(&mut self, points: &[Point2D]) {
+        self.parts.push(self.points.len() as i32);
+        self.num_parts += 1;
+        for p in points {
+            self.add_point(*p);
+        }
+    }
+
+    pub fn add_partz(&mut self, points: &[Point2D], z_values: &[f64], m_values: &[f64]) {
+        self.parts.push(self.points.len() as i32);
+        self.num_parts += 1;
+        for i in 0..points.len() {
+            let m = if i < m_values.len() { m_values[i] } else { 0f64 };
+            self.add_pointz(points[i], z_values[i], m);
+        }
+    }
+
+    pub fn add_partm(&mut self, points: &[Point2D], m_values: &[f64]) {
+        self.parts.push(self.points.len() as i32);
+        self.num_parts += 1;
+        for i in 0..points.len() {
+            self.add_pointm(points[i], m_values[i]);
+        }
+    }
+
+    /// Adds a point using whichever of add_point, add_pointm and add_pointz
+    /// suits the shape type; z and m are ignored if the type doesn't store them.
+    pub fn add_point_with_zm(&mut self, p: Point2D, z: f64, m: f64) {
+        if self.shape_type.has_z_data() {
+            self.add_pointz(p, z, m);
+        } else if self.shape_type.has_m_data() {
+            self.add_pointm(p, m);
+        } else {
+            self.add_point(p);
+        }
+    }
+
+    /// Adds a part using whichever of add_part, add_partm and add_partz suits
+    /// the shape type. Missing z and m values are taken to be zero.
+    pub fn add_part_with_zm(&mut self, points: &[Point2D], z_values: &[f64], m_values: &[f64]) {
+        self.parts.push(self.points.len() as i32);
+        self.num_parts += 1;
+        for i in 0..points.len() {
+            let z = if i < z_values.len() { z_values[i] } else { 0f64 };
+            let m = if i < m_values.len() { m_values[i] } else { 0f64 };
+            self.add_point_with_zm(points[i], z, m);
+        }
+    }
+
+    /// Adds a polygon ring, closing it if need be and reversing the vertex
+    /// order if necessary so that hulls are clockwise and holes counter-clockwise.
+    pub fn add_ring(&mut self, points: &[Point2D], z_values: &[f64], m_values: &[f64], is_hole: bool) {
+        let mut points = points.to_vec();
+        let mut z_values = z_values.to_vec();
+        let mut m_values = m_values.to_vec();
+        if points.len() > 1 && points[0] != points[points.len() - 1] {
+            let p = points[0];
+            points.push(p);
+            if !z_values.is_empty() {
+                let z = z_values[0];
+                z_values.push(z);
+            }
+            if !m_values.is_empty() {
+                let m = m_values[0];
+                m_values.push(m);
+            }
+        }
+        if is_clockwise_order(&points) == is_hole {
+            points.reverse();
+            z_values.reverse();
+            m_values.reverse();
+        }
+        self.add_part_with_zm(&points, &z_values, &m_values);
+    }
+
+    /// Returns the indices of the vertices of a part within `points`, ordered
+    /// clockwise or counter-clockwise as requested.
+    pub fn get_ring_indices(&self, part: usize, clockwise: bool) -> Vec<usize> {
+        let (start, end) = self.part_range(part);
+        if is_clockwise_order(&self.points[start..end]) == clockwise {
+            (start..end).collect()
+        } else {
+            (start..end).rev().collect()
+        }
+    }
+
+    /// Groups the parts of a polygon into polygons, each a hull followed by the
+    /// holes that it contains. Holes that aren't within any hull are treated as
+    /// hulls themselves.
+    pub fn get_polygon_parts(&self) -> Vec<Vec<usize>> {
+        let mut polygons: Vec<Vec<usize>> = vec![];
+        let mut holes = vec![];
+        for part in 0..self.num_parts as usize {
+            if self.is_hole(part) {
+                holes.push(part);
+            } else {
+                polygons.push(vec![part]);
+            }
+        }
+        for hole in holes {
+            // the smallest hull containing the hole
+            let mut containing: Option<(usize, f64)> = None;
+            let ring = self.get_part(hole);
+            for (i, polygon) in polygons.iter().enumerate() {
+                let hull = self.get_part(polygon[0]);
+                if !ring.is_empty() && point_in_poly(&ring[0], hull) {
+                    let area = polygon_area(hull);
+                    if containing.map_or(true, |(_, a)| area < a) {
+                        containing = Some((i, area));
+                    }
+                }
+            }
+            match containing {
+                Some((i, _)) => polygons[i].push(hole),
+                None => polygons.push(vec![hole]),
+            }
+        }
+        polygons
+    }
+
+    fn update_xy_extent(&mut self, p: &Point2D) {
+        if p.x < self.x_min {
+            self.x_min = p.x;
+        }
+        if p.x > self.x_max {
+            self.x_max = p.x;
+        }
+        if p.y < self.y_min {
+            self.y_min = p.y;
+        }
+        if p.y > self.y_max {
+            self.y_max = p.y;
+        }
+    }
+
+    fn update_zm_extent(&mut self, z: f64, m: f64) {
+        if !z.is_nan() {
+            if z < self.z_min {
+                self.z_min = z;
+            }
+            if z > self.z_max {
+                self.z_max = z;
+            }
+        }
+        if m < self.m_min {
+            self.m_min = m;
+        }
+        if m > self.m_max {
+            self.m_max = m;
+        }
+    }
+
+    /// Returns the index range (start, end) of a part within `points`.
+    pub fn part_range(&self, part: usize) -> (usize, usize) {
+        if self.parts.is_empty() {
+            return (0, self.points.len());
+        }
+        let start = self.parts[part] as usize;
+        let end = if part < self.parts.len() - 1 {
+            self.parts[part + 1] as usize
+        } else {
+            self.points.len()
+        };
+        (start, end)
+    }
+
+    /// Returns the points of a part.
+    pub fn get_part(&self, part: usize) -> &[Point2D] {
+        let (start, end) = self.part_range(part);
+        &self.points[start..end]
+    }
+
+    pub fn get_bounding_box(&self) -> BoundingBox {
+        BoundingBox::new(self.x_min, self.y_min, self.x_max, self.y_max)
+    }
+
+    /// Returns true if a polygon part is a hole, i.e. its vertices are
+    /// in counter-clockwise order.
+    pub fn is_hole(&self, part: usize) -> bool {
+        if self.shape_type.base_shape_type() != ShapeType::Polygon {
+            return false;
+        }
+        !is_clockwise_order(self.get_part(part))
+    }
+
+    /// Returns the content length of the record in bytes, as it would be
+    /// written to a shapefile (excluding the 8-byte record header).
+    pub fn get_length(&self) -> i32 {
+        let n = self.num_points;
+        let p = self.num_parts;
+        match self.shape_type {
+            ShapeType::Null => 4,
+            ShapeType::Point => 20,
+            ShapeType::PointM => 28,
+            ShapeType::PointZ => 36,
+            ShapeType::MultiPoint => 40 + 16 * n,
+            ShapeType::MultiPointM => 56 + 24 * n,
+            ShapeType::MultiPointZ => 72 + 32 * n,
+            ShapeType::PolyLine | ShapeType::Polygon => 44 + 4 * p + 16 * n,
+            ShapeType::PolyLineM | ShapeType::PolygonM => 60 + 4 * p + 24 * n,
+            ShapeType::PolyLineZ | ShapeType::PolygonZ => 76 + 4 * p + 32 * n,
+            ShapeType::MultiPatch => 76 + 8 * p + 32 * n,
+        }
+    }
+}
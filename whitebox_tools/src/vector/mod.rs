@@ -0,0 +1,246 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+
+mod attributes;
+pub mod dbf;
+mod expression;
+pub mod geojson;
+mod geometry;
+pub mod shapefile;
+mod wkb;
+mod wkt;
+
+pub use self::attributes::AttributeField;
+pub use self::attributes::FieldData;
+pub use self::attributes::FieldDataType;
+pub use self::attributes::ShapefileAttributes;
+pub use self::attributes::get_prefixed_field_name;
+pub use self::expression::{Expression, ExpressionValue};
+pub use self::geometry::ShapeType;
+pub use self::geometry::ShapefileGeometry;
+pub use self::wkb::{geometry_to_wkb, wkb_to_geometry};
+pub use self::wkt::{geometry_to_wkt, wkt_to_geometry};
+
+use std::f64;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use structures::{BoundingBox, Point2D};
+use vector::geojson::{read_geojson, write_geojson};
+use vector::shapefile::{read_shapefile, write_shapefile};
+
+/// The in-memory vector data model; a set of geometries of a single shape
+/// type, each with an associated record in the attribute table. Despite the
+/// name, the file format is determined by the file extension (see `VectorType`).
+#[derive(Default, Clone)]
+pub struct Shapefile {
+    pub file_name: String,
+    file_mode: String,
+    pub vector_type: VectorType,
+    pub header: ShapefileHeader,
+    pub num_records: usize,
+    pub records: Vec<ShapefileGeometry>,
+    pub attributes: ShapefileAttributes,
+    pub projection: String,
+}
+
+impl Shapefile {
+    /// Opens a vector file for reading (file_mode "r") or creates a new, empty
+    /// one for writing (file_mode "w").
+    pub fn new<'a>(file_name: &'a str, file_mode: &'a str) -> Result<Shapefile, Error> {
+        let fm = file_mode.to_lowercase();
+        let mut sf = Shapefile {
+            file_name: file_name.to_string(),
+            file_mode: fm.clone(),
+            vector_type: get_vector_type_from_file(file_name),
+            ..Default::default()
+        };
+        if fm.contains("r") {
+            match sf.vector_type {
+                VectorType::Shapefile => read_shapefile(&mut sf)?,
+                VectorType::GeoJson => read_geojson(&mut sf)?,
+                VectorType::Unknown => {
+                    return Err(Error::new(ErrorKind::Other, "Unrecognized vector type"))
+                }
+            }
+        } else {
+            sf.file_mode = "w".to_string();
+            sf.header.file_code = 9994;
+            sf.header.version = 1000;
+        }
+        Ok(sf)
+    }
+
+    /// Creates a new, empty vector file for writing that shares the projection
+    /// of an existing file. If `copy_fields` is true, the attribute fields (but
+    /// not the records) of the input are also copied.
+    pub fn initialize_using_file<'a>(file_name: &'a str,
+                                     input: &'a Shapefile,
+                                     shape_type: ShapeType,
+                                     copy_fields: bool)
+                                     -> Result<Shapefile, Error> {
+        let mut output = Shapefile::new(file_name, "w")?;
+        output.header.shape_type = shape_type;
+        output.projection = input.projection.clone();
+        if copy_fields {
+            for field in &input.attributes.fields {
+                output.attributes.add_field(field);
+            }
+        }
+        Ok(output)
+    }
+
+    /// Adds a geometry record. The shape type of the file is set by the first
+    /// record added if it has not already been specified.
+    pub fn add_record(&mut self, geometry: ShapefileGeometry) {
+        if self.header.shape_type == ShapeType::Null {
+            self.header.shape_type = geometry.shape_type;
+        }
+        self.records.push(geometry);
+        self.num_records += 1;
+    }
+
+    pub fn add_point_record(&mut self, x: f64, y: f64) {
+        let mut geom = ShapefileGeometry::new(ShapeType::Point);
+        geom.add_point(Point2D::new(x, y));
+        self.add_record(geom);
+    }
+
+    pub fn get_record(&self, index: usize) -> &ShapefileGeometry {
+        &self.records[index]
+    }
+
+    pub fn get_total_num_parts(&self) -> usize {
+        self.records.iter().map(|r| r.num_parts as usize).sum()
+    }
+
+    pub fn get_total_num_points(&self) -> usize {
+        self.records.iter().map(|r| r.num_points as usize).sum()
+    }
+
+    pub fn get_bounding_box(&self) -> BoundingBox {
+        BoundingBox::new(self.header.x_min,
+                         self.header.y_min,
+                         self.header.x_max,
+                         self.header.y_max)
+    }
+
+    /// Recalculates the file extent from the records.
+    pub fn update_header_extent(&mut self) {
+        let mut bb = BoundingBox::default();
+        let (mut z_min, mut z_max) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut m_min, mut m_max) = (f64::INFINITY, f64::NEG_INFINITY);
+        for rec in &self.records {
+            if rec.shape_type == ShapeType::Null {
+                continue;
+            }
+            bb.expand_to(&rec.get_bounding_box());
+            if rec.z_min < z_min {
+                z_min = rec.z_min;
+            }
+            if rec.z_max > z_max {
+                z_max = rec.z_max;
+            }
+            if rec.m_min < m_min {
+                m_min = rec.m_min;
+            }
+            if rec.m_max > m_max {
+                m_max = rec.m_max;
+            }
+        }
+        if bb.is_empty() {
+            bb = BoundingBox::new(0f64, 0f64, 0f64, 0f64);
+        }
+        self.header.x_min = bb.min_x;
+        self.header.y_min = bb.min_y;
+        self.header.x_max = bb.max_x;
+        self.header.y_max = bb.max_y;
+        self.header.z_min = if z_min.is_finite() { z_min } else { 0f64 };
+        self.header.z_max = if z_max.is_finite() { z_max } else { 0f64 };
+        self.header.m_min = if m_min.is_finite() { m_min } else { 0f64 };
+        self.header.m_max = if m_max.is_finite() { m_max } else { 0f64 };
+    }
+
+    pub fn write(&mut self) -> Result<(), Error> {
+        if self.file_mode == "r" {
+            return Err(Error::new(ErrorKind::Other, "The file was opened in read-only mode"));
+        }
+        for rec in &self.records {
+            if rec.shape_type != ShapeType::Null &&
+               rec.shape_type != self.header.shape_type {
+                return Err(Error::new(ErrorKind::InvalidData,
+                                      format!("All records must be of the same shape type ({}).",
+                                              self.header.shape_type)));
+            }
+        }
+        match self.vector_type {
+            VectorType::Shapefile => write_shapefile(self),
+            VectorType::GeoJson => write_geojson(self),
+            VectorType::Unknown => Err(Error::new(ErrorKind::Other, "Unrecognized vector type")),
+        }
+    }
+}
+
+impl fmt::Display for Shapefile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = format!("File Name: {}", self.file_name);
+        s = s + &format!("\nFormat: {:?}", self.vector_type);
+        s = s + &format!("\nShape Type: {}", self.header.shape_type);
+        s = s + &format!("\nNum. of Records: {}", self.num_records);
+        s = s + &format!("\nNum. of Attribute Fields: {}",
+                         self.attributes.get_num_fields());
+        s = s + &format!("\nExtent: {}", self.get_bounding_box());
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct ShapefileHeader {
+    pub file_code: i32,
+    pub file_length: i32,
+    pub version: i32,
+    pub shape_type: ShapeType,
+    pub x_min: f64,
+    pub y_min: f64,
+    pub x_max: f64,
+    pub y_max: f64,
+    pub z_min: f64,
+    pub z_max: f64,
+    pub m_min: f64,
+    pub m_max: f64,
+}
+
+/// The supported vector file formats.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum VectorType {
+    Unknown,
+    Shapefile,
+    GeoJson,
+}
+
+impl Default for VectorType {
+    fn default() -> VectorType {
+        VectorType::Unknown
+    }
+}
+
+/// Identifies the vector format of a file from its extension.
+pub fn get_vector_type_from_file(file_name: &str) -> VectorType {
+    // get the file extension
+    let extension = match Path::new(file_name).extension() {
+        Some(e) => e.to_string_lossy().to_lowercase(),
+        None => "".to_string(),
+    };
+    if extension == "shp" {
+        VectorType::Shapefile
+    } else if extension == "geojson" || extension == "json" {
+        VectorType::GeoJson
+    } else {
+        VectorType::Unknown
+    }
+}
@@ -0,0 +1,288 @@
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind};
+use std::path::Path;
+use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
+use io_utils::{ByteOrderReader, Endianness};
+use structures::Point2D;
+use vector::*;
+
+/// Reads an ESRI shapefile (.shp), along with its attribute table (.dbf)
+/// and projection file (.prj), if present.
+pub fn read_shapefile(sf: &mut Shapefile) -> Result<(), Error> {
+    let mut f = File::open(&sf.file_name)?;
+    let mut buffer = vec![];
+    f.read_to_end(&mut buffer)?;
+    if buffer.len() < 100 {
+        return Err(Error::new(ErrorKind::InvalidData,
+                              "The shapefile is improperly formatted."));
+    }
+
+    let mut bor = ByteOrderReader::new(buffer, Endianness::BigEndian);
+    sf.header.file_code = bor.read_i32();
+    if sf.header.file_code != 9994 {
+        return Err(Error::new(ErrorKind::InvalidData,
+                              "The shapefile is improperly formatted (bad file code)."));
+    }
+    bor.seek(24);
+    sf.header.file_length = bor.read_i32();
+    bor.byte_order = Endianness::LittleEndian;
+    sf.header.version = bor.read_i32();
+    sf.header.shape_type = ShapeType::from_int(bor.read_i32());
+    sf.header.x_min = bor.read_f64();
+    sf.header.y_min = bor.read_f64();
+    sf.header.x_max = bor.read_f64();
+    sf.header.y_max = bor.read_f64();
+    sf.header.z_min = bor.read_f64();
+    sf.header.z_max = bor.read_f64();
+    sf.header.m_min = bor.read_f64();
+    sf.header.m_max = bor.read_f64();
+
+    // read the records
+    let file_end = ((sf.header.file_length as usize) * 2).min(bor.buffer.len());
+    let mut pos = 100usize;
+    while pos + 8 <= file_end {
+        bor.seek(pos);
+        bor.byte_order = Endianness::BigEndian;
+        let _record_number = bor.read_i32();
+        let content_length = bor.read_i32() as usize * 2;
+        bor.byte_order = Endianness::LittleEndian;
+        let shape_type = ShapeType::from_int(bor.read_i32());
+        let mut geom = ShapefileGeometry::new(shape_type);
+        match shape_type {
+            ShapeType::Null => {}
+            ShapeType::Point | ShapeType::PointM | ShapeType::PointZ => {
+                let p = Point2D::new(bor.read_f64(), bor.read_f64());
+                if shape_type == ShapeType::PointZ {
+                    let z = bor.read_f64();
+                    let m = if content_length >= 36 { bor.read_f64() } else { 0f64 };
+                    geom.add_pointz(p, z, m);
+                } else if shape_type == ShapeType::PointM {
+                    let m = bor.read_f64();
+                    geom.add_pointm(p, m);
+                } else {
+                    geom.add_point(p);
+                }
+            }
+            _ => {
+                // all remaining types start with a bounding box
+                bor.seek(bor.pos + 32);
+                let base_type = shape_type.base_shape_type();
+                let is_multipoint = base_type == ShapeType::MultiPoint;
+                let num_parts = if is_multipoint { 0 } else { bor.read_i32() as usize };
+                let num_points = bor.read_i32() as usize;
+                let mut parts = Vec::with_capacity(num_parts);
+                for _ in 0..num_parts {
+                    parts.push(bor.read_i32() as usize);
+                }
+                if shape_type == ShapeType::MultiPatch {
+                    // part types are not retained
+                    bor.seek(bor.pos + 4 * num_parts);
+                }
+                let mut points = Vec::with_capacity(num_points);
+                for _ in 0..num_points {
+                    points.push(Point2D::new(bor.read_f64(), bor.read_f64()));
+                }
+                let mut z_values = vec![];
+                let mut m_values = vec![];
+                let mut consumed = bor.pos - pos - 8;
+                if shape_type.has_z_data() {
+                    bor.seek(bor.pos + 16); // z range
+                    for _ in 0..num_points {
+                        z_values.push(bor.read_f64());
+                    }
+                    consumed = bor.pos - pos - 8;
+                }
+                if shape_type.has_m_data() && consumed + 16 + 8 * num_points <= content_length {
+                    bor.seek(bor.pos + 16); // m range
+                    for _ in 0..num_points {
+                        m_values.push(bor.read_f64());
+                    }
+                }
+                if m_values.is_empty() && shape_type.has_m_data() {
+                    m_values = vec![0f64; num_points];
+                }
+                if is_multipoint {
+                    for i in 0..num_points {
+                        if shape_type.has_z_data() {
+                            geom.add_pointz(points[i], z_values[i], m_values[i]);
+                        } else if shape_type.has_m_data() {
+                            geom.add_pointm(points[i], m_values[i]);
+                        } else {
+                            geom.add_point(points[i]);
+                        }
+                    }
+                } else {
+                    for part in 0..num_parts {
+                        let start = parts[part];
+                        let end = if part < num_parts - 1 { parts[part + 1] } else { num_points };
+                        if shape_type.has_z_data() {
+                            geom.add_partz(&points[start..end],
+                                           &z_values[start..end],
+                                           &m_values[start..end]);
+                        } else if shape_type.has_m_data() {
+                            geom.add_partm(&points[start..end], &m_values[start..end]);
+                        } else {
+                            geom.add_part(&points[start..end]);
+                        }
+                    }
+                }
+            }
+        }
+        sf.records.push(geom);
+        pos += 8 + content_length;
+    }
+    sf.num_records = sf.records.len();
+
+    // read the attribute table
+    let dbf_file = Path::new(&sf.file_name).with_extension("dbf");
+    if dbf_file.exists() {
+        sf.attributes = dbf::read_dbf(&dbf_file.to_string_lossy())?;
+    }
+
+    // read the projection file
+    let prj_file = Path::new(&sf.file_name).with_extension("prj");
+    if prj_file.exists() {
+        let mut f = File::open(prj_file)?;
+        let mut s = String::new();
+        f.read_to_string(&mut s)?;
+        sf.projection = s.trim().to_string();
+    }
+
+    Ok(())
+}
+
+/// Writes the geometry (.shp), index (.shx), attribute table (.dbf) and,
+/// if the projection is known, projection (.prj) files of a shapefile.
+pub fn write_shapefile(sf: &mut Shapefile) -> Result<(), Error> {
+    sf.update_header_extent();
+
+    // the file length is measured in 16-bit words
+    let mut file_length = 50i32;
+    for rec in &sf.records {
+        file_length += 4 + rec.get_length() / 2;
+    }
+    sf.header.file_length = file_length;
+
+    let f = File::create(&sf.file_name)?;
+    let mut writer = BufWriter::new(f);
+    write_header(&mut writer, &sf.header, file_length)?;
+
+    let shx_file = Path::new(&sf.file_name).with_extension("shx");
+    let f = File::create(&shx_file)?;
+    let mut shx_writer = BufWriter::new(f);
+    write_header(&mut shx_writer, &sf.header, 50 + 4 * sf.records.len() as i32)?;
+
+    let mut offset = 50i32;
+    for i in 0..sf.records.len() {
+        let rec = &sf.records[i];
+        let content_length = rec.get_length() / 2;
+
+        shx_writer.write_i32::<BigEndian>(offset)?;
+        shx_writer.write_i32::<BigEndian>(content_length)?;
+        offset += 4 + content_length;
+
+        writer.write_i32::<BigEndian>(i as i32 + 1)?;
+        writer.write_i32::<BigEndian>(content_length)?;
+        writer.write_i32::<LittleEndian>(rec.shape_type.to_int())?;
+        match rec.shape_type {
+            ShapeType::Null => {}
+            ShapeType::Point | ShapeType::PointM | ShapeType::PointZ => {
+                writer.write_f64::<LittleEndian>(rec.points[0].x)?;
+                writer.write_f64::<LittleEndian>(rec.points[0].y)?;
+                if rec.shape_type == ShapeType::PointZ {
+                    writer.write_f64::<LittleEndian>(rec.z_array[0])?;
+                }
+                if rec.shape_type.has_m_data() {
+                    writer.write_f64::<LittleEndian>(rec.m_array[0])?;
+                }
+            }
+            _ => {
+                writer.write_f64::<LittleEndian>(rec.x_min)?;
+                writer.write_f64::<LittleEndian>(rec.y_min)?;
+                writer.write_f64::<LittleEndian>(rec.x_max)?;
+                writer.write_f64::<LittleEndian>(rec.y_max)?;
+                if rec.shape_type.base_shape_type() != ShapeType::MultiPoint {
+                    writer.write_i32::<LittleEndian>(rec.num_parts)?;
+                }
+                writer.write_i32::<LittleEndian>(rec.num_points)?;
+                if rec.shape_type.base_shape_type() != ShapeType::MultiPoint {
+                    for part in &rec.parts {
+                        writer.write_i32::<LittleEndian>(*part)?;
+                    }
+                }
+                if rec.shape_type == ShapeType::MultiPatch {
+                    for _ in 0..rec.num_parts {
+                        writer.write_i32::<LittleEndian>(5)?; // ring
+                    }
+                }
+                for p in &rec.points {
+                    writer.write_f64::<LittleEndian>(p.x)?;
+                    writer.write_f64::<LittleEndian>(p.y)?;
+                }
+                if rec.shape_type.has_z_data() {
+                    writer.write_f64::<LittleEndian>(rec.z_min)?;
+                    writer.write_f64::<LittleEndian>(rec.z_max)?;
+                    for z in &rec.z_array {
+                        writer.write_f64::<LittleEndian>(*z)?;
+                    }
+                }
+                if rec.shape_type.has_m_data() {
+                    let (m_min, m_max) = if rec.m_array.is_empty() {
+                        (0f64, 0f64)
+                    } else {
+                        (rec.m_min, rec.m_max)
+                    };
+                    writer.write_f64::<LittleEndian>(m_min)?;
+                    writer.write_f64::<LittleEndian>(m_max)?;
+                    for i in 0..rec.num_points as usize {
+                        let m = if i < rec.m_array.len() { rec.m_array[i] } else { 0f64 };
+                        writer.write_f64::<LittleEndian>(m)?;
+                    }
+                }
+            }
+        }
+    }
+
+    // the attribute table must contain one entry for every record
+    while sf.attributes.get_num_records() < sf.records.len() {
+        sf.attributes.add_record(vec![], false);
+    }
+    let dbf_file = Path::new(&sf.file_name).with_extension("dbf");
+    dbf::write_dbf(&dbf_file.to_string_lossy(), &sf.attributes)?;
+
+    let prj_file = Path::new(&sf.file_name).with_extension("prj");
+    if !sf.projection.is_empty() {
+        let f = File::create(&prj_file)?;
+        let mut prj_writer = BufWriter::new(f);
+        prj_writer.write_all(sf.projection.as_bytes())?;
+    } else if prj_file.exists() {
+        // don't leave a stale projection from an earlier file of the same name
+        fs::remove_file(&prj_file)?;
+    }
+
+    Ok(())
+}
+
+fn write_header<W: Write>(writer: &mut W,
+                          header: &ShapefileHeader,
+                          file_length: i32)
+                          -> Result<(), Error> {
+    writer.write_i32::<BigEndian>(9994)?;
+    for _ in 0..5 {
+        writer.write_i32::<BigEndian>(0)?;
+    }
+    writer.write_i32::<BigEndian>(file_length)?;
+    writer.write_i32::<LittleEndian>(1000)?;
+    writer.write_i32::<LittleEndian>(header.shape_type.to_int())?;
+    writer.write_f64::<LittleEndian>(header.x_min)?;
+    writer.write_f64::<LittleEndian>(header.y_min)?;
+    writer.write_f64::<LittleEndian>(header.x_max)?;
+    writer.write_f64::<LittleEndian>(header.y_max)?;
+    writer.write_f64::<LittleEndian>(header.z_min)?;
+    writer.write_f64::<LittleEndian>(header.z_max)?;
+    writer.write_f64::<LittleEndian>(header.m_min)?;
+    writer.write_f64::<LittleEndian>(header.m_max)?;
+    Ok(())
+}
@@ -0,0 +1,322 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+
+use std::io::{Error, ErrorKind};
+use byteorder::{LittleEndian, WriteBytesExt};
+use io_utils::{ByteOrderReader, Endianness};
+use vector::{ShapeType, ShapefileGeometry};
+
+const WKB_POINT: u32 = 1;
+const WKB_LINESTRING: u32 = 2;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTIPOINT: u32 = 4;
+const WKB_MULTILINESTRING: u32 = 5;
+const WKB_MULTIPOLYGON: u32 = 6;
+const WKB_GEOMETRYCOLLECTION: u32 = 7;
+
+/// Encodes a geometry as little-endian OGC well-known binary (WKB), using
+/// the ISO type codes for Z (+1000) and M (+2000) geometries. The choice of
+/// single- or multi-part types, ring orientation, and handling of measures
+/// and null shapes follow `geometry_to_wkt`.
+pub fn geometry_to_wkb(geom: &ShapefileGeometry) -> Vec<u8> {
+    let mut bytes = vec![];
+    let base = geom.shape_type.base_shape_type();
+    if base == ShapeType::Null || geom.num_points == 0 {
+        write_header(&mut bytes, WKB_GEOMETRYCOLLECTION, 0);
+        bytes.write_u32::<LittleEndian>(0).unwrap();
+        return bytes;
+    }
+    let has_z = geom.shape_type.has_z_data();
+    let has_m = !has_z && geom.shape_type.has_m_data();
+    let dims = if has_z {
+        1000
+    } else if has_m {
+        2000
+    } else {
+        0
+    };
+    let write_coords = |bytes: &mut Vec<u8>, indices: &[usize]| {
+        for &i in indices {
+            bytes.write_f64::<LittleEndian>(geom.points[i].x).unwrap();
+            bytes.write_f64::<LittleEndian>(geom.points[i].y).unwrap();
+            if has_z {
+                bytes.write_f64::<LittleEndian>(geom.z_array[i]).unwrap();
+            } else if has_m {
+                bytes.write_f64::<LittleEndian>(geom.m_array[i]).unwrap();
+            }
+        }
+    };
+    match base {
+        ShapeType::Point => {
+            write_header(&mut bytes, WKB_POINT, dims);
+            write_coords(&mut bytes, &[0]);
+        }
+        ShapeType::MultiPoint => {
+            write_header(&mut bytes, WKB_MULTIPOINT, dims);
+            bytes.write_u32::<LittleEndian>(geom.points.len() as u32).unwrap();
+            for i in 0..geom.points.len() {
+                write_header(&mut bytes, WKB_POINT, dims);
+                write_coords(&mut bytes, &[i]);
+            }
+        }
+        ShapeType::PolyLine => {
+            let num_parts = geom.num_parts as usize;
+            if num_parts > 1 {
+                write_header(&mut bytes, WKB_MULTILINESTRING, dims);
+                bytes.write_u32::<LittleEndian>(num_parts as u32).unwrap();
+            }
+            for part in 0..num_parts {
+                let (start, end) = geom.part_range(part);
+                let indices: Vec<usize> = (start..end).collect();
+                write_header(&mut bytes, WKB_LINESTRING, dims);
+                bytes.write_u32::<LittleEndian>(indices.len() as u32).unwrap();
+                write_coords(&mut bytes, &indices);
+            }
+        }
+        _ => {
+            let polygons = geom.get_polygon_parts();
+            if polygons.len() > 1 {
+                write_header(&mut bytes, WKB_MULTIPOLYGON, dims);
+                bytes.write_u32::<LittleEndian>(polygons.len() as u32).unwrap();
+            }
+            for rings in &polygons {
+                write_header(&mut bytes, WKB_POLYGON, dims);
+                bytes.write_u32::<LittleEndian>(rings.len() as u32).unwrap();
+                for (k, part) in rings.iter().enumerate() {
+                    // hulls counter-clockwise, holes clockwise
+                    let indices = geom.get_ring_indices(*part, k > 0);
+                    bytes.write_u32::<LittleEndian>(indices.len() as u32).unwrap();
+                    write_coords(&mut bytes, &indices);
+                }
+            }
+        }
+    }
+    bytes
+}
+
+/// Decodes a geometry from OGC well-known binary (WKB) of either byte order.
+/// Both the ISO type codes and the extended (EWKB) Z, M and SRID flags used
+/// by PostGIS are recognized; any SRID is ignored. The supported types and
+/// treatment of polygon rings and empty geometries follow `wkt_to_geometry`.
+pub fn wkb_to_geometry(wkb: &[u8]) -> Result<ShapefileGeometry, Error> {
+    let mut bor = ByteOrderReader::new(wkb.to_vec(), Endianness::LittleEndian);
+    let (geometry_type, has_z, has_m) = read_header(&mut bor)?;
+    let dimension = 2 + has_z as usize + has_m as usize;
+
+    // every type is read as a list of groups (polygons) of parts of coordinates
+    let (base, groups) = match geometry_type {
+        WKB_POINT => {
+            let point = read_coords(&mut bor, 1, dimension)?;
+            if point[0][0].is_nan() {
+                // the conventional encoding of POINT EMPTY
+                return Ok(ShapefileGeometry::new(ShapeType::Null));
+            }
+            (ShapeType::Point, vec![vec![point]])
+        }
+        WKB_LINESTRING => (ShapeType::PolyLine, vec![vec![read_sequence(&mut bor, dimension)?]]),
+        WKB_POLYGON => (ShapeType::Polygon, vec![read_sequences(&mut bor, dimension)?]),
+        WKB_MULTIPOINT => {
+            let mut points = vec![];
+            for _ in 0..read_count(&mut bor)? {
+                read_member_header(&mut bor, WKB_POINT)?;
+                let point = read_coords(&mut bor, 1, dimension)?;
+                if !point[0][0].is_nan() {
+                    points.extend(point);
+                }
+            }
+            (ShapeType::MultiPoint, vec![vec![points]])
+        }
+        WKB_MULTILINESTRING => {
+            let mut lines = vec![];
+            for _ in 0..read_count(&mut bor)? {
+                read_member_header(&mut bor, WKB_LINESTRING)?;
+                lines.push(read_sequence(&mut bor, dimension)?);
+            }
+            (ShapeType::PolyLine, vec![lines])
+        }
+        WKB_MULTIPOLYGON => {
+            let mut polygons = vec![];
+            for _ in 0..read_count(&mut bor)? {
+                read_member_header(&mut bor, WKB_POLYGON)?;
+                polygons.push(read_sequences(&mut bor, dimension)?);
+            }
+            (ShapeType::Polygon, polygons)
+        }
+        WKB_GEOMETRYCOLLECTION => {
+            if read_count(&mut bor)? == 0 {
+                return Ok(ShapefileGeometry::new(ShapeType::Null));
+            }
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  "Unsupported WKB geometry type (GeometryCollection)."));
+        }
+        _ => {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  format!("Unsupported WKB geometry type ({}).", geometry_type)))
+        }
+    };
+    Ok(ShapefileGeometry::from_coordinates(base, has_z, has_m, &groups))
+}
+
+fn write_header(bytes: &mut Vec<u8>, geometry_type: u32, dims: u32) {
+    bytes.push(1u8); // little-endian
+    bytes.write_u32::<LittleEndian>(geometry_type + dims).unwrap();
+}
+
+fn check_length(bor: &mut ByteOrderReader, num_bytes: usize) -> Result<(), Error> {
+    if bor.pos + num_bytes > bor.len() {
+        return Err(Error::new(ErrorKind::InvalidData, "Invalid WKB: unexpected end of data."));
+    }
+    Ok(())
+}
+
+/// Reads the byte order and type of a geometry, returning the base type
+/// code and whether the geometry has z and m ordinates.
+fn read_header(bor: &mut ByteOrderReader) -> Result<(u32, bool, bool), Error> {
+    check_length(bor, 5)?;
+    bor.byte_order = match bor.read_u8() {
+        0 => Endianness::BigEndian,
+        1 => Endianness::LittleEndian,
+        _ => return Err(Error::new(ErrorKind::InvalidData, "Invalid WKB: bad byte order flag.")),
+    };
+    let code = bor.read_u32();
+    // EWKB flags
+    let mut has_z = code & 0x80000000 != 0;
+    let mut has_m = code & 0x40000000 != 0;
+    if code & 0x20000000 != 0 {
+        check_length(bor, 4)?;
+        let _srid = bor.read_u32();
+    }
+    // ISO type codes
+    let code = code & 0x0FFFFFFF;
+    match code / 1000 {
+        1 => has_z = true,
+        2 => has_m = true,
+        3 => {
+            has_z = true;
+            has_m = true;
+        }
+        _ => {}
+    }
+    Ok((code % 1000, has_z, has_m))
+}
+
+fn read_member_header(bor: &mut ByteOrderReader, expected_type: u32) -> Result<(), Error> {
+    let (geometry_type, _, _) = read_header(bor)?;
+    if geometry_type != expected_type {
+        return Err(Error::new(ErrorKind::InvalidData,
+                              "Invalid WKB: unexpected member of a multi-part geometry."));
+    }
+    Ok(())
+}
+
+fn read_count(bor: &mut ByteOrderReader) -> Result<usize, Error> {
+    check_length(bor, 4)?;
+    Ok(bor.read_u32() as usize)
+}
+
+fn read_coords(bor: &mut ByteOrderReader, n: usize, dimension: usize) -> Result<Vec<Vec<f64>>, Error> {
+    check_length(bor, n * dimension * 8)?;
+    let mut coords = Vec::with_capacity(n);
+    for _ in 0..n {
+        let mut c = Vec::with_capacity(dimension);
+        for _ in 0..dimension {
+            c.push(bor.read_f64());
+        }
+        coords.push(c);
+    }
+    Ok(coords)
+}
+
+fn read_sequence(bor: &mut ByteOrderReader, dimension: usize) -> Result<Vec<Vec<f64>>, Error> {
+    let n = read_count(bor)?;
+    read_coords(bor, n, dimension)
+}
+
+fn read_sequences(bor: &mut ByteOrderReader, dimension: usize) -> Result<Vec<Vec<Vec<f64>>>, Error> {
+    let n = read_count(bor)?;
+    let mut sequences = vec![];
+    for _ in 0..n {
+        sequences.push(read_sequence(bor, dimension)?);
+    }
+    Ok(sequences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{geometry_to_wkb, wkb_to_geometry};
+    use vector::{geometry_to_wkt, wkt_to_geometry, ShapeType};
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len() / 2).map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // the geometries are compared by their WKT, which is written at full precision
+        for wkt in &["POINT (1.5 -2.25)",
+                     "MULTIPOINT ((0 0), (1 2), (3 4))",
+                     "LINESTRING (0 0, 10 10, 20 0)",
+                     "MULTILINESTRING ((0 0, 1 1), (2 2, 3 3, 4 2))",
+                     "POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 4, 4 4, 4 2, 2 2))",
+                     "MULTIPOLYGON (((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 4, 4 4, 4 2, 2 2)), ((20 0, 30 0, 30 10, 20 10, 20 0)))",
+                     "POINT Z (1 2 3)",
+                     "MULTIPOINT M ((0 0 1), (1 2 3))",
+                     "LINESTRING Z (0 0 1, 10 10 2)",
+                     "MULTILINESTRING M ((0 0 1, 1 1 2), (2 2 3, 3 3 4))",
+                     "POLYGON Z ((0 0 1, 10 0 2, 10 10 3, 0 10 4, 0 0 1), (2 2 5, 2 4 6, 4 4 7, 4 2 8, 2 2 5))",
+                     "MULTIPOLYGON M (((0 0 1, 10 0 2, 10 10 3, 0 10 4, 0 0 1)), ((20 0 1, 30 0 2, 30 10 3, 20 0 1)))",
+                     "GEOMETRYCOLLECTION EMPTY"] {
+            let geom = wkt_to_geometry(wkt).unwrap();
+            let wkb = geometry_to_wkb(&geom);
+            let decoded = wkb_to_geometry(&wkb).unwrap();
+            assert_eq!(decoded.shape_type, geom.shape_type);
+            assert_eq!(geometry_to_wkt(&decoded), *wkt);
+            assert_eq!(geometry_to_wkb(&decoded), wkb);
+        }
+    }
+
+    #[test]
+    fn test_byte_orders_and_type_codes() {
+        // POINT (1 2) in little- and big-endian byte orders
+        let point = wkb_to_geometry(&from_hex("0101000000000000000000F03F0000000000000040")).unwrap();
+        assert_eq!(geometry_to_wkt(&point), "POINT (1 2)");
+        let point = wkb_to_geometry(&from_hex("00000000013FF00000000000004000000000000000")).unwrap();
+        assert_eq!(geometry_to_wkt(&point), "POINT (1 2)");
+        assert_eq!(geometry_to_wkb(&point), from_hex("0101000000000000000000F03F0000000000000040"));
+
+        // ISO POINT ZM (1 2 3 4) and EWKB POINT Z (1 2 3) with an SRID
+        let point = wkb_to_geometry(&from_hex("01B90B0000000000000000F03F000000000000004000000000000008400000000000001040")).unwrap();
+        assert_eq!(point.shape_type, ShapeType::PointZ);
+        assert_eq!((point.z_array[0], point.m_array[0]), (3f64, 4f64));
+        let point = wkb_to_geometry(&from_hex("01010000A0E6100000000000000000F03F00000000000000400000000000000840")).unwrap();
+        assert_eq!(geometry_to_wkt(&point), "POINT Z (1 2 3)");
+
+        // POINT EMPTY, encoded with NaN ordinates
+        let point = wkb_to_geometry(&from_hex("0101000000000000000000F87F000000000000F87F")).unwrap();
+        assert_eq!(point.shape_type, ShapeType::Null);
+    }
+
+    #[test]
+    fn test_malformed_wkb() {
+        let valid = geometry_to_wkb(&wkt_to_geometry("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0))").unwrap());
+        // every truncation of valid data is an error
+        for len in 0..valid.len() {
+            assert!(wkb_to_geometry(&valid[..len]).is_err());
+        }
+        // bad byte order flag
+        let mut bad = valid.clone();
+        bad[0] = 2;
+        assert!(wkb_to_geometry(&bad).is_err());
+        // an unsupported type, a non-empty geometry collection, and a huge count
+        assert!(wkb_to_geometry(&from_hex("0108000000")).is_err());
+        assert!(wkb_to_geometry(&from_hex("010700000001000000")).is_err());
+        assert!(wkb_to_geometry(&from_hex("0102000000FFFFFFFF")).is_err());
+        // a multi-part geometry with a member of the wrong type
+        assert!(wkb_to_geometry(&from_hex("010400000001000000010200000000000000")).is_err());
+    }
+}
@@ -0,0 +1,446 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: October 18, 2026
+Last Modified: October 18, 2026
+License: MIT
+*/
+
+use std::io::{Error, ErrorKind};
+use vector::{ShapeType, ShapefileGeometry};
+
+/// Encodes a geometry as OGC well-known text (WKT). Single-part lines and
+/// polygons are written as LINESTRING and POLYGON, and multi-part shapes as
+/// MULTILINESTRING and MULTIPOLYGON. Polygon hulls are written in
+/// counter-clockwise order and holes clockwise. Z shapes are written with
+/// the Z tag and M shapes with the M tag; the optional measures of Z shapes
+/// are not written. Null shapes are written as GEOMETRYCOLLECTION EMPTY.
+pub fn geometry_to_wkt(geom: &ShapefileGeometry) -> String {
+    let base = geom.shape_type.base_shape_type();
+    if base == ShapeType::Null || geom.num_points == 0 {
+        return "GEOMETRYCOLLECTION EMPTY".to_string();
+    }
+    let has_z = geom.shape_type.has_z_data();
+    let has_m = !has_z && geom.shape_type.has_m_data();
+    let dims = if has_z {
+        " Z"
+    } else if has_m {
+        " M"
+    } else {
+        ""
+    };
+    let coord = |i: usize| -> String {
+        let p = geom.points[i];
+        if has_z {
+            format!("{} {} {}", p.x, p.y, geom.z_array[i])
+        } else if has_m {
+            format!("{} {} {}", p.x, p.y, geom.m_array[i])
+        } else {
+            format!("{} {}", p.x, p.y)
+        }
+    };
+    let sequence = |indices: Vec<usize>| -> String {
+        let coords: Vec<String> = indices.into_iter().map(|i| coord(i)).collect();
+        format!("({})", coords.join(", "))
+    };
+    match base {
+        ShapeType::Point => format!("POINT{} ({})", dims, coord(0)),
+        ShapeType::MultiPoint => {
+            let points: Vec<String> = (0..geom.points.len()).map(|i| format!("({})", coord(i))).collect();
+            format!("MULTIPOINT{} ({})", dims, points.join(", "))
+        }
+        ShapeType::PolyLine => {
+            let lines: Vec<String> = (0..geom.num_parts as usize).map(|part| {
+                let (start, end) = geom.part_range(part);
+                sequence((start..end).collect())
+            }).collect();
+            if lines.len() == 1 {
+                format!("LINESTRING{} {}", dims, lines[0])
+            } else {
+                format!("MULTILINESTRING{} ({})", dims, lines.join(", "))
+            }
+        }
+        _ => {
+            let polygons: Vec<String> = geom.get_polygon_parts().iter().map(|rings| {
+                let rings: Vec<String> = rings.iter().enumerate().map(|(k, part)| {
+                    // hulls counter-clockwise, holes clockwise
+                    sequence(geom.get_ring_indices(*part, k > 0))
+                }).collect();
+                format!("({})", rings.join(", "))
+            }).collect();
+            if polygons.len() == 1 {
+                format!("POLYGON{} {}", dims, polygons[0])
+            } else {
+                format!("MULTIPOLYGON{} ({})", dims, polygons.join(", "))
+            }
+        }
+    }
+}
+
+/// Decodes a geometry from OGC well-known text (WKT). The POINT, LINESTRING,
+/// POLYGON, MULTIPOINT, MULTILINESTRING and MULTIPOLYGON types are supported,
+/// with or without the Z, M and ZM tags; untagged coordinates with three or
+/// four ordinates are read as XYZ and XYZM. Polygon rings are closed and
+/// reoriented to the shapefile convention. EMPTY geometries are returned as
+/// null shapes.
+pub fn wkt_to_geometry(wkt: &str) -> Result<ShapefileGeometry, Error> {
+    let mut parser = WktParser {
+        tokens: tokenize(wkt)?,
+        pos: 0,
+    };
+    let tag = match parser.next() {
+        Some(Token::Word(w)) => w,
+        _ => return Err(Error::new(ErrorKind::InvalidData, "Invalid WKT: missing geometry type.")),
+    };
+    let (mut has_z, mut has_m, mut tagged) = (false, false, false);
+    if let Some(Token::Word(w)) = parser.peek() {
+        if w == "Z" || w == "M" || w == "ZM" {
+            has_z = w.contains("Z");
+            has_m = w.contains("M");
+            tagged = true;
+            parser.pos += 1;
+        }
+    }
+    if let Some(Token::Word(w)) = parser.peek() {
+        if w == "EMPTY" {
+            parser.pos += 1;
+            if parser.pos < parser.tokens.len() {
+                return Err(Error::new(ErrorKind::InvalidData, "Invalid WKT: unexpected input after EMPTY."));
+            }
+            return Ok(ShapefileGeometry::new(ShapeType::Null));
+        }
+    }
+
+    // every type is read as a list of groups (polygons) of parts of coordinates
+    let (base, groups) = match tag.as_ref() {
+        "POINT" => (ShapeType::Point, vec![vec![vec![parser.read_point()?]]]),
+        "MULTIPOINT" => {
+            parser.expect(Token::Open)?;
+            let mut points = vec![];
+            loop {
+                if parser.peek() == Some(Token::Open) {
+                    points.push(parser.read_point()?);
+                } else {
+                    points.push(parser.read_tuple()?);
+                }
+                if !parser.read_separator()? {
+                    break;
+                }
+            }
+            (ShapeType::MultiPoint, vec![vec![points]])
+        }
+        "LINESTRING" => (ShapeType::PolyLine, vec![vec![parser.read_sequence()?]]),
+        "MULTILINESTRING" => (ShapeType::PolyLine, vec![parser.read_sequences()?]),
+        "POLYGON" => (ShapeType::Polygon, vec![parser.read_sequences()?]),
+        "MULTIPOLYGON" => {
+            parser.expect(Token::Open)?;
+            let mut polygons = vec![];
+            loop {
+                polygons.push(parser.read_sequences()?);
+                if !parser.read_separator()? {
+                    break;
+                }
+            }
+            (ShapeType::Polygon, polygons)
+        }
+        _ => {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  format!("Unsupported WKT geometry type ({}).", tag)))
+        }
+    };
+
+    if parser.pos < parser.tokens.len() {
+        return Err(Error::new(ErrorKind::InvalidData,
+                              format!("Invalid WKT: unexpected {:?} after the geometry.", parser.tokens[parser.pos])));
+    }
+
+    if !tagged {
+        let dimension = groups.iter()
+                              .flat_map(|g| g.iter())
+                              .flat_map(|p| p.iter())
+                              .map(|c| c.len())
+                              .next()
+                              .unwrap_or(2);
+        has_z = dimension > 2;
+        has_m = dimension > 3;
+    }
+    Ok(ShapefileGeometry::from_coordinates(base, has_z, has_m, &groups))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Number(f64),
+    Open,
+    Close,
+    Comma,
+}
+
+fn tokenize(wkt: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens = vec![];
+    let chars: Vec<char> = wkt.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::Open);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::Close);
+            i += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            i += 1;
+        } else if c.is_alphabetic() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            tokens.push(Token::Word(word.to_uppercase()));
+        } else if c.is_digit(10) || c == '-' || c == '+' || c == '.' {
+            let start = i;
+            i += 1;
+            while i < chars.len() &&
+                  (chars[i].is_digit(10) || chars[i] == '.' || chars[i] == 'e' || chars[i] == 'E' ||
+                   ((chars[i] == '-' || chars[i] == '+') && (chars[i - 1] == 'e' || chars[i - 1] == 'E'))) {
+                i += 1;
+            }
+            let s: String = chars[start..i].iter().collect();
+            match s.parse::<f64>() {
+                Ok(v) => tokens.push(Token::Number(v)),
+                Err(_) => {
+                    return Err(Error::new(ErrorKind::InvalidData,
+                                          format!("Invalid WKT: bad number ({}).", s)))
+                }
+            }
+        } else {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  format!("Invalid WKT: unexpected character ({}).", c)));
+        }
+    }
+    Ok(tokens)
+}
+
+struct WktParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl WktParser {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.pos).cloned()
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.peek();
+        self.pos += 1;
+        t
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), Error> {
+        match self.next() {
+            Some(ref t) if *t == token => Ok(()),
+            t => Err(Error::new(ErrorKind::InvalidData,
+                                format!("Invalid WKT: expected {:?} but found {:?}.", token, t))),
+        }
+    }
+
+    /// Reads a comma (returning true) or a closing bracket (returning false).
+    fn read_separator(&mut self) -> Result<bool, Error> {
+        match self.next() {
+            Some(Token::Comma) => Ok(true),
+            Some(Token::Close) => Ok(false),
+            t => Err(Error::new(ErrorKind::InvalidData,
+                                format!("Invalid WKT: expected ',' or ')' but found {:?}.", t))),
+        }
+    }
+
+    /// Reads the ordinates of a single coordinate, e.g. '1.0 2.0 3.0'.
+    fn read_tuple(&mut self) -> Result<Vec<f64>, Error> {
+        let mut tuple = vec![];
+        while let Some(Token::Number(v)) = self.peek() {
+            tuple.push(v);
+            self.pos += 1;
+        }
+        if tuple.len() < 2 {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  "Invalid WKT: coordinates must have at least two ordinates."));
+        }
+        Ok(tuple)
+    }
+
+    /// Reads a bracketed coordinate, e.g. '(1.0 2.0)'.
+    fn read_point(&mut self) -> Result<Vec<f64>, Error> {
+        self.expect(Token::Open)?;
+        let tuple = self.read_tuple()?;
+        self.expect(Token::Close)?;
+        Ok(tuple)
+    }
+
+    /// Reads a bracketed list of coordinates, e.g. '(1 2, 3 4)'.
+    fn read_sequence(&mut self) -> Result<Vec<Vec<f64>>, Error> {
+        if self.peek() == Some(Token::Word("EMPTY".to_string())) {
+            self.pos += 1;
+            return Ok(vec![]);
+        }
+        self.expect(Token::Open)?;
+        let mut coords = vec![];
+        loop {
+            coords.push(self.read_tuple()?);
+            if !self.read_separator()? {
+                break;
+            }
+        }
+        Ok(coords)
+    }
+
+    /// Reads a bracketed list of coordinate lists, e.g. '((1 2, 3 4), (5 6, 7 8))'.
+    fn read_sequences(&mut self) -> Result<Vec<Vec<Vec<f64>>>, Error> {
+        if self.peek() == Some(Token::Word("EMPTY".to_string())) {
+            self.pos += 1;
+            return Ok(vec![]);
+        }
+        self.expect(Token::Open)?;
+        let mut sequences = vec![];
+        loop {
+            sequences.push(self.read_sequence()?);
+            if !self.read_separator()? {
+                break;
+            }
+        }
+        Ok(sequences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{geometry_to_wkt, wkt_to_geometry};
+    use vector::{ShapeType, ShapefileGeometry};
+
+    fn assert_same_geometry(a: &ShapefileGeometry, b: &ShapefileGeometry) {
+        assert_eq!(a.shape_type, b.shape_type);
+        assert_eq!(a.num_parts, b.num_parts);
+        assert_eq!(a.num_points, b.num_points);
+        assert_eq!(a.parts, b.parts);
+        assert_eq!(a.points, b.points);
+        assert_eq!(a.z_array, b.z_array);
+        assert_eq!(a.m_array, b.m_array);
+    }
+
+    // parses, writes and parses again, returning the written text
+    fn round_trip(wkt: &str) -> (ShapefileGeometry, String) {
+        let geom = wkt_to_geometry(wkt).unwrap();
+        let written = geometry_to_wkt(&geom);
+        let reread = wkt_to_geometry(&written).unwrap();
+        assert_same_geometry(&geom, &reread);
+        // writing is stable
+        assert_eq!(geometry_to_wkt(&reread), written);
+        (geom, written)
+    }
+
+    #[test]
+    fn test_round_trip_2d() {
+        let cases = [
+            ("POINT (1.5 -2.25)", ShapeType::Point),
+            ("MULTIPOINT ((0 0), (1 2), (3 4))", ShapeType::MultiPoint),
+            ("LINESTRING (0 0, 10 10, 20 0)", ShapeType::PolyLine),
+            ("MULTILINESTRING ((0 0, 1 1), (2 2, 3 3, 4 2))", ShapeType::PolyLine),
+            ("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0))", ShapeType::Polygon),
+            ("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 4, 4 4, 4 2, 2 2))", ShapeType::Polygon),
+            ("MULTIPOLYGON (((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 4, 4 4, 4 2, 2 2), (6 6, 6 8, 8 8, 8 6, 6 6)), \
+              ((20 0, 30 0, 30 10, 20 10, 20 0)), ((3 3, 3.5 3, 3.5 3.5, 3 3.5, 3 3)))", ShapeType::Polygon),
+        ];
+        for &(wkt, shape_type) in &cases {
+            let (geom, written) = round_trip(wkt);
+            assert_eq!(geom.shape_type, shape_type);
+            // the input is already in canonical form
+            assert_eq!(written, wkt.split_whitespace().collect::<Vec<&str>>().join(" "));
+        }
+    }
+
+    #[test]
+    fn test_multipolygon_with_holes() {
+        let (geom, _) = round_trip("MULTIPOLYGON (((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 4, 4 4, 4 2, 2 2)), \
+                                    ((20 0, 30 0, 30 10, 20 10, 20 0)), ((3 3, 3.5 3, 3.5 3.5, 3 3.5, 3 3)))");
+        assert_eq!(geom.num_parts, 4);
+        // hulls are clockwise and holes counter-clockwise in a shapefile
+        assert!(!geom.is_hole(0));
+        assert!(geom.is_hole(1));
+        assert!(!geom.is_hole(2));
+        // an island within a hole is a hull
+        assert!(!geom.is_hole(3));
+        assert_eq!(geom.get_polygon_parts(), vec![vec![0, 1], vec![2], vec![3]]);
+    }
+
+    #[test]
+    fn test_round_trip_z_and_m() {
+        let cases = [
+            ("POINT Z (1 2 3)", ShapeType::PointZ),
+            ("POINT M (1 2 4)", ShapeType::PointM),
+            ("MULTIPOINT Z ((0 0 1), (1 2 3))", ShapeType::MultiPointZ),
+            ("MULTIPOINT M ((0 0 1), (1 2 3))", ShapeType::MultiPointM),
+            ("LINESTRING Z (0 0 1, 10 10 2)", ShapeType::PolyLineZ),
+            ("MULTILINESTRING M ((0 0 1, 1 1 2), (2 2 3, 3 3 4))", ShapeType::PolyLineM),
+            ("POLYGON Z ((0 0 1, 10 0 2, 10 10 3, 0 10 4, 0 0 1), (2 2 5, 2 4 6, 4 4 7, 4 2 8, 2 2 5))", ShapeType::PolygonZ),
+            ("MULTIPOLYGON M (((0 0 1, 10 0 2, 10 10 3, 0 10 4, 0 0 1)), ((20 0 1, 30 0 2, 30 10 3, 20 0 1)))", ShapeType::PolygonM),
+        ];
+        for &(wkt, shape_type) in &cases {
+            let (geom, written) = round_trip(wkt);
+            assert_eq!(geom.shape_type, shape_type);
+            assert_eq!(written, wkt);
+        }
+        let (geom, _) = round_trip("POINT M (1 2 4)");
+        assert_eq!(geom.m_array, vec![4f64]);
+    }
+
+    #[test]
+    fn test_zm_and_untagged_coordinates() {
+        let geom = wkt_to_geometry("LINESTRING ZM (0 0 1 5, 1 1 2 6)").unwrap();
+        assert_eq!(geom.shape_type, ShapeType::PolyLineZ);
+        assert_eq!(geom.z_array, vec![1f64, 2f64]);
+        assert_eq!(geom.m_array, vec![5f64, 6f64]);
+        // the measures of Z shapes are not written
+        assert_eq!(geometry_to_wkt(&geom), "LINESTRING Z (0 0 1, 1 1 2)");
+
+        let untagged = wkt_to_geometry("LINESTRING (0 0 1 5, 1 1 2 6)").unwrap();
+        assert_eq!(untagged.shape_type, ShapeType::PolyLineZ);
+        assert_eq!(untagged.m_array, geom.m_array);
+        let untagged = wkt_to_geometry("point (1 2 3)").unwrap();
+        assert_eq!(untagged.shape_type, ShapeType::PointZ);
+    }
+
+    #[test]
+    fn test_empty_geometries() {
+        for wkt in &["POINT EMPTY", "POINT Z EMPTY", "MULTIPOINT EMPTY", "LINESTRING EMPTY",
+                     "MULTILINESTRING M EMPTY", "POLYGON EMPTY", "MULTIPOLYGON ZM EMPTY",
+                     "GEOMETRYCOLLECTION EMPTY"] {
+            let (geom, written) = round_trip(wkt);
+            assert_eq!(geom.shape_type, ShapeType::Null);
+            assert_eq!(written, "GEOMETRYCOLLECTION EMPTY");
+        }
+        // empty members are dropped
+        let geom = wkt_to_geometry("MULTILINESTRING ((0 0, 1 1), EMPTY)").unwrap();
+        assert_eq!(geom.num_parts, 1);
+    }
+
+    #[test]
+    fn test_rings_are_closed_and_reoriented() {
+        // a counter-clockwise hull given without the closing point
+        let geom = wkt_to_geometry("POLYGON ((0 0, 10 0, 10 10, 0 10))").unwrap();
+        assert_eq!(geom.num_points, 5);
+        assert!(!geom.is_hole(0));
+        assert_eq!(geometry_to_wkt(&geom), "POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0))");
+    }
+
+    #[test]
+    fn test_malformed_wkt() {
+        for wkt in &["", "POINT", "POINT (1)", "POINT (1 2", "POINT (1 2))x", "LINESTRING (0 0, )",
+                     "POLYGON ((0 0, 1 1, 1 0, 0 0)", "CIRCLE (0 0 1)", "POINT (1 2e)", "POINT (1 # 2)",
+                     "MULTIPOINT (1 2,", "(1 2)"] {
+            assert!(wkt_to_geometry(wkt).is_err(), "{}", wkt);
+        }
+    }
+}